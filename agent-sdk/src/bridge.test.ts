@@ -4,6 +4,8 @@ import {
   AsyncQueue,
   CACHE_SPLIT_POLICY,
   buildRateLimitUpdate,
+  buildRequestUsageUpdate,
+  buildResultUsageUpdate,
   buildQueryOptions,
   canGenerateSessionTitle,
   generatePersistedSessionTitle,
@@ -11,7 +13,9 @@ import {
   buildSessionListOptions,
   buildToolResultFields,
   createToolCall,
+  handleStreamEvent,
   handleTaskSystemMessage,
+  hookResultFromMessage,
   mapAvailableAgents,
   mapAvailableModels,
   mapSessionMessagesToUpdates,
@@ -26,6 +30,7 @@ import {
   permissionOptionsFromSuggestions,
   permissionResultFromOutcome,
   previewKilobyteLabel,
+  promptContentFromChunks,
   staleMcpAuthCandidates,
   resolveInstalledAgentSdkVersion,
   unwrapToolUseResult,
@@ -143,6 +148,15 @@ test("parseCommandEnvelope validates initialize command", () => {
   assert.equal(parsed.command.cwd, "C:/work");
 });
 
+test("parseCommandEnvelope validates ping nonce", () => {
+  const parsed = parseCommandEnvelope(JSON.stringify({ command: "ping", nonce: 7 }));
+  assert.deepEqual(parsed.command, { command: "ping", nonce: 7 });
+  assert.throws(
+    () => parseCommandEnvelope(JSON.stringify({ command: "ping", nonce: -1 })),
+    /ping\.nonce must be a non-negative integer/,
+  );
+});
+
 test("parseCommandEnvelope validates resume_session command without cwd", () => {
   const parsed = parseCommandEnvelope(
     JSON.stringify({
@@ -162,6 +176,9 @@ test("parseCommandEnvelope validates resume_session command without cwd", () =>
           terminalProgressBarEnabled: true,
         },
         agent_progress_summaries: true,
+        additional_directories: ["/repo/packages/api"],
+        env: { RUST_LOG: "debug" },
+        max_thinking_tokens: 10000,
       },
     }),
   );
@@ -183,6 +200,26 @@ test("parseCommandEnvelope validates resume_session command without cwd", () =>
     terminalProgressBarEnabled: true,
   });
   assert.equal(parsed.command.launch_settings.agent_progress_summaries, true);
+  assert.deepEqual(parsed.command.launch_settings.additional_directories, ["/repo/packages/api"]);
+  assert.deepEqual(parsed.command.launch_settings.env, { RUST_LOG: "debug" });
+  assert.equal(parsed.command.launch_settings.max_thinking_tokens, 10000);
+});
+
+test("promptContentFromChunks joins text and appends base64 image blocks", () => {
+  const content = promptContentFromChunks({
+    command: "prompt",
+    session_id: "s1",
+    chunks: [
+      { kind: "text", value: "look at " },
+      { kind: "text", value: "@shot.png" },
+      { kind: "image", value: { mime_type: "image/png", data: "iVBORw0KGgo=" } },
+      { kind: "image", value: { mime_type: "image/png" } },
+    ],
+  });
+  assert.deepEqual(content, [
+    { type: "text", text: "look at @shot.png" },
+    { type: "image", source: { type: "base64", media_type: "image/png", data: "iVBORw0KGgo=" } },
+  ]);
 });
 
 test("parseCommandEnvelope validates rename_session command", () => {
@@ -383,6 +420,9 @@ test("buildQueryOptions maps launch settings into sdk query options", () => {
         terminalProgressBarEnabled: true,
       },
       agent_progress_summaries: true,
+      env: { RUST_LOG: "debug" },
+      max_thinking_tokens: 10000,
+      max_turns: 40,
     },
     provisionalSessionId: "session-1",
     input,
@@ -392,6 +432,10 @@ test("buildQueryOptions maps launch settings into sdk query options", () => {
     sessionIdForLogs: () => "session-1",
   });
 
+  assert.equal(options.env?.RUST_LOG, "debug");
+  assert.equal(options.env?.PATH, process.env.PATH);
+  assert.equal(options.maxThinkingTokens, 10000);
+  assert.equal(options.maxTurns, 40);
   assert.deepEqual(options.settings, {
     alwaysThinkingEnabled: true,
     model: "haiku",
@@ -524,6 +568,48 @@ test("buildQueryOptions omits startup overrides for default logout path", () =>
   assert.equal("agentProgressSummaries" in options, false);
 });
 
+test("hookResultFromMessage reads decisions from JSON output and exit code 2", () => {
+  const denied = hookResultFromMessage({
+    subtype: "hook_response",
+    hook_event: "PreToolUse",
+    hook_name: "PreToolUse:Bash",
+    outcome: "success",
+    exit_code: 0,
+    stdout: JSON.stringify({
+      hookSpecificOutput: {
+        hookEventName: "PreToolUse",
+        permissionDecision: "deny",
+        permissionDecisionReason: "rm -rf is not allowed",
+        updatedInput: { command: "ls" },
+      },
+    }),
+    stderr: "",
+  });
+  assert.deepEqual(denied, {
+    hook_event: "PreToolUse",
+    hook_name: "PreToolUse:Bash",
+    outcome: "success",
+    exit_code: 0,
+    decision: "deny",
+    reason: "rm -rf is not allowed",
+    input_modified: true,
+  });
+
+  const blocked = hookResultFromMessage({
+    hook_event: "PostToolUse",
+    outcome: "error",
+    exit_code: 2,
+    stdout: "lint failed",
+    stderr: "src/main.rs: trailing whitespace\n",
+  });
+  assert.equal(blocked?.decision, "block");
+  assert.equal(blocked?.reason, "src/main.rs: trailing whitespace");
+  assert.equal(blocked?.hook_name, "PostToolUse");
+  assert.equal(blocked?.input_modified, false);
+
+  assert.equal(hookResultFromMessage({ subtype: "hook_response" }), null);
+});
+
 test("handleTaskSystemMessage prefers task_progress summary over fallback text", () => {
   const session = makeSessionState();
 
@@ -599,6 +685,37 @@ test("handleTaskSystemMessage falls back to description and last tool when progr
   });
 });
 
+test("handleStreamEvent turns citations into markdown footnotes", () => {
+  const session = makeSessionState();
+  const citation = (url: string, title: string) => ({
+    type: "content_block_delta",
+    delta: { type: "citations_delta", citation: { type: "web_search_result_location", url, title } },
+  });
+
+  const events = captureBridgeEvents(() => {
+    handleStreamEvent(session, { type: "message_start" });
+    handleStreamEvent(session, citation("https://a.dev", "Docs [v2]"));
+    handleStreamEvent(session, { type: "content_block_delta", delta: { type: "text_delta", text: "Fast" } });
+    handleStreamEvent(session, citation("https://b.dev", ""));
+    handleStreamEvent(session, { type: "content_block_stop" });
+    handleStreamEvent(session, citation("https://a.dev", "Docs [v2]"));
+    handleStreamEvent(session, { type: "content_block_stop" });
+    handleStreamEvent(session, { type: "message_stop" });
+  });
+
+  const texts = events.map((event) => {
+    const update = event.update as { content?: { text?: string } } | undefined;
+    return update?.content?.text;
+  });
+  assert.deepEqual(texts, [
+    "Fast",
+    "[^1],[^2]",
+    "[^1]",
+    "\n\n[^1]: [Docs v2](https://a.dev)\n[^2]: <https://b.dev>\n",
+  ]);
+  assert.equal(session.citations, undefined);
+});
+
 test("handleTaskSystemMessage final summary replaces prior task content and finalizes status", () => {
   const session = makeSessionState();
 
@@ -939,6 +1056,47 @@ test("buildRateLimitUpdate rejects invalid payloads", () => {
   );
 });
 
+test("buildRequestUsageUpdate maps assistant usage to prompt-side tokens", () => {
+  assert.deepEqual(
+    buildRequestUsageUpdate({
+      input_tokens: 12,
+      output_tokens: 40,
+      cache_read_input_tokens: 30_000,
+      cache_creation_input_tokens: 512,
+    }),
+    {
+      type: "usage_update",
+      input_tokens: 12,
+      cache_read_tokens: 30_000,
+      cache_write_tokens: 512,
+    },
+  );
+  assert.equal(buildRequestUsageUpdate(null), null);
+  assert.equal(buildRequestUsageUpdate({ output_tokens: 3 }), null);
+});
+
+test("buildResultUsageUpdate reports output, cost and the largest context window", () => {
+  assert.deepEqual(
+    buildResultUsageUpdate({
+      type: "result",
+      subtype: "success",
+      usage: { input_tokens: 10, output_tokens: 250 },
+      total_cost_usd: 0.0421,
+      modelUsage: {
+        "claude-haiku": { contextWindow: 200_000 },
+        "claude-sonnet": { contextWindow: 1_000_000 },
+      },
+    }),
+    {
+      type: "usage_update",
+      output_tokens: 250,
+      total_cost_usd: 0.0421,
+      context_window: 1_000_000,
+    },
+  );
+  assert.equal(buildResultUsageUpdate({ type: "result", subtype: "success" }), null);
+});
+
 test("mapAvailableAgents normalizes and deduplicates agents", () => {
   const agents = mapAvailableAgents([
     { name: "reviewer", description: "", model: "" },
@@ -19,7 +19,7 @@ import {
   currentSessionListOptions,
   setSessionListingDir,
 } from "./bridge/events.js";
-import { textFromPrompt } from "./bridge/message_handlers.js";
+import { promptContentFromChunks } from "./bridge/message_handlers.js";
 import {
   sessions,
   sessionById,
@@ -65,13 +65,20 @@ export {
   mapSessionMessagesToUpdates,
   mapSdkSessions,
 } from "./bridge/history.js";
-export { handleTaskSystemMessage } from "./bridge/message_handlers.js";
+export {
+  handleStreamEvent,
+  handleTaskSystemMessage,
+  hookResultFromMessage,
+  promptContentFromChunks,
+} from "./bridge/message_handlers.js";
 export { mapAvailableAgents } from "./bridge/agents.js";
 export { buildQueryOptions, mapAvailableModels } from "./bridge/session_lifecycle.js";
 export {
   parseFastModeState,
   parseRateLimitStatus,
   buildRateLimitUpdate,
+  buildRequestUsageUpdate,
+  buildResultUsageUpdate,
 } from "./bridge/state_parsing.js";
 export { MCP_STALE_STATUS_REVALIDATION_COOLDOWN_MS, staleMcpAuthCandidates };
 export type {
@@ -115,6 +122,8 @@ export async function generatePersistedSessionTitle(
 }
 
 const EXPECTED_AGENT_SDK_VERSION = "0.2.74";
+// Keep in sync with BRIDGE_PROTOCOL_VERSION in src/agent/wire.rs.
+const BRIDGE_PROTOCOL_VERSION = 1;
 const require = createRequire(import.meta.url);
 
 export function resolveInstalledAgentSdkVersion(): string | undefined {
@@ -173,11 +182,14 @@ async function handleCommand(command: BridgeCommand, requestId?: string): Promis
               },
             ],
             capabilities: {
-              prompt_image: false,
+              prompt_image: true,
               prompt_embedded_context: true,
               supports_session_listing: true,
               supports_resume_session: true,
+              supports_modes: true,
+              supports_plan_updates: true,
             },
+            protocol_version: BRIDGE_PROTOCOL_VERSION,
           },
         },
         requestId,
@@ -245,8 +257,8 @@ async function handleCommand(command: BridgeCommand, requestId?: string): Promis
         slashError(command.session_id, `unknown session: ${command.session_id}`, requestId);
         return;
       }
-      const text = textFromPrompt(command);
-      if (!text.trim()) {
+      const content = promptContentFromChunks(command);
+      if (content.length === 0) {
         return;
       }
       session.input.enqueue({
@@ -255,7 +267,7 @@ async function handleCommand(command: BridgeCommand, requestId?: string): Promis
         parent_tool_use_id: null,
         message: {
           role: "user",
-          content: [{ type: "text", text }],
+          content,
         },
       } as import("@anthropic-ai/claude-agent-sdk").SDKUserMessage);
       return;
@@ -451,6 +463,10 @@ async function handleCommand(command: BridgeCommand, requestId?: string): Promis
       handleElicitationResponse(command);
       return;
 
+    case "ping":
+      writeEvent({ event: "pong", nonce: command.nonce }, requestId);
+      return;
+
     case "shutdown":
       await closeAllSessions();
       process.exit(0);
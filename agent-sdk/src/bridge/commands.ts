@@ -58,6 +58,36 @@ function optionalString(
   return value;
 }
 
+function optionalStringArray(
+  record: Record<string, unknown>,
+  key: string,
+  context: string,
+): string[] | undefined {
+  const value = record[key];
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+  if (!Array.isArray(value) || value.some((entry) => typeof entry !== "string")) {
+    throw new Error(`${context}.${key} must be an array of strings when provided`);
+  }
+  return value as string[];
+}
+
+function optionalStringRecord(
+  record: Record<string, unknown>,
+  key: string,
+  context: string,
+): { [key: string]: string } | undefined {
+  const value = optionalJsonObject(record, key, context);
+  if (value === undefined) {
+    return undefined;
+  }
+  if (Object.values(value).some((entry) => typeof entry !== "string")) {
+    throw new Error(`${context}.${key} must map names to strings when provided`);
+  }
+  return value as { [key: string]: string };
+}
+
 function optionalMetadata(record: Record<string, unknown>, key: string): Record<string, Json> {
   const value = record[key];
   if (value === undefined || value === null) {
@@ -83,12 +113,30 @@ function optionalLaunchSettings(
     "agent_progress_summaries",
     `${context}.${key}`,
   );
+  const additionalDirectories = optionalStringArray(
+    parsed,
+    "additional_directories",
+    `${context}.${key}`,
+  );
+  const env = optionalStringRecord(parsed, "env", `${context}.${key}`);
+  const maxThinkingTokens = optionalPositiveInteger(
+    parsed,
+    "max_thinking_tokens",
+    `${context}.${key}`,
+  );
+  const maxTurns = optionalPositiveInteger(parsed, "max_turns", `${context}.${key}`);
   return {
     ...(language ? { language } : {}),
     ...(settings ? { settings } : {}),
     ...(agentProgressSummaries !== undefined
       ? { agent_progress_summaries: agentProgressSummaries }
       : {}),
+    ...(additionalDirectories && additionalDirectories.length > 0
+      ? { additional_directories: additionalDirectories }
+      : {}),
+    ...(env && Object.keys(env).length > 0 ? { env } : {}),
+    ...(maxThinkingTokens !== undefined ? { max_thinking_tokens: maxThinkingTokens } : {}),
+    ...(maxTurns !== undefined ? { max_turns: maxTurns } : {}),
   };
 }
 
@@ -107,6 +155,21 @@ function optionalBoolean(
   return value;
 }
 
+function optionalPositiveInteger(
+  record: Record<string, unknown>,
+  key: string,
+  context: string,
+): number | undefined {
+  const value = record[key];
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+  if (typeof value !== "number" || !Number.isInteger(value) || value <= 0) {
+    throw new Error(`${context}.${key} must be a positive integer when provided`);
+  }
+  return value;
+}
+
 function optionalJsonObject(
   record: Record<string, unknown>,
   key: string,
@@ -161,6 +224,18 @@ function expectBoolean(
   return value;
 }
 
+function expectNonNegativeInteger(
+  record: Record<string, unknown>,
+  key: string,
+  context: string,
+): number {
+  const value = record[key];
+  if (typeof value !== "number" || !Number.isSafeInteger(value) || value < 0) {
+    throw new Error(`${context}.${key} must be a non-negative integer`);
+  }
+  return value;
+}
+
 function parseMcpServerConfig(
   value: unknown,
   context: string,
@@ -378,6 +453,8 @@ export function parseCommandEnvelope(line: string): { requestId?: string; comman
           server_name: expectString(raw, "server_name", "mcp_oauth_callback_url"),
           callback_url: expectString(raw, "callback_url", "mcp_oauth_callback_url"),
         };
+      case "ping":
+        return { command: "ping", nonce: expectNonNegativeInteger(raw, "nonce", "ping") };
       case "shutdown":
         return { command: "shutdown" };
       default:
@@ -1,5 +1,5 @@
 import type { SDKMessage } from "@anthropic-ai/claude-agent-sdk";
-import type { AvailableCommand, BridgeCommand, ToolCallUpdateFields } from "../types.js";
+import type { AvailableCommand, BridgeCommand, HookResult, ToolCallUpdateFields } from "../types.js";
 import { asRecordOrNull } from "./shared.js";
 import { toPermissionMode, buildModeState } from "./commands.js";
 import { writeEvent, emitSessionUpdate, emitConnectEvent, refreshSessionsList } from "./events.js";
@@ -17,7 +17,12 @@ import {
 } from "./tool_calls.js";
 import { emitAuthRequired, classifyTurnErrorKind, emitFastModeUpdateIfChanged } from "./error_classification.js";
 import { mapAvailableAgents, mapAvailableAgentsFromNames, emitAvailableAgentsIfChanged, refreshAvailableAgents } from "./agents.js";
-import { buildRateLimitUpdate, numberField } from "./state_parsing.js";
+import {
+  buildRateLimitUpdate,
+  buildRequestUsageUpdate,
+  buildResultUsageUpdate,
+  numberField,
+} from "./state_parsing.js";
 import { looksLikeAuthRequired } from "./auth.js";
 import type { SessionState } from "./session_lifecycle.js";
 import { updateSessionId } from "./session_lifecycle.js";
@@ -35,6 +40,90 @@ export function textFromPrompt(command: Extract<BridgeCommand, { command: "promp
     .join("");
 }
 
+export type PromptContentBlock =
+  | { type: "text"; text: string }
+  | {
+      type: "image";
+      source: { type: "base64"; media_type: string; data: string };
+    };
+
+/** Text chunks are joined into one text block; `image` chunks carry `{ mime_type, data }` (base64). */
+export function promptContentFromChunks(
+  command: Extract<BridgeCommand, { command: "prompt" }>,
+): PromptContentBlock[] {
+  const content: PromptContentBlock[] = [];
+  const text = textFromPrompt(command);
+  if (text.trim()) {
+    content.push({ type: "text", text });
+  }
+  for (const chunk of command.chunks ?? []) {
+    if (chunk.kind !== "image") {
+      continue;
+    }
+    const value = asRecordOrNull(chunk.value);
+    const mimeType = value?.mime_type;
+    const data = value?.data;
+    if (typeof mimeType === "string" && typeof data === "string" && data.length > 0) {
+      content.push({ type: "image", source: { type: "base64", media_type: mimeType, data } });
+    }
+  }
+  return content;
+}
+
+const HOOK_DECISIONS = new Set(["block", "deny", "ask", "allow"]);
+
+/**
+ * Map a `hook_response` system message to a hook result. Decisions come from
+ * the hook's JSON output, or from exit code 2, which blocks with stderr as the
+ * reason.
+ */
+export function hookResultFromMessage(msg: Record<string, unknown>): HookResult | null {
+  const hookEvent = typeof msg.hook_event === "string" ? msg.hook_event : "";
+  if (!hookEvent) {
+    return null;
+  }
+  const stdout =
+    typeof msg.stdout === "string" ? msg.stdout : typeof msg.output === "string" ? msg.output : "";
+  const stderr = typeof msg.stderr === "string" ? msg.stderr.trim() : "";
+  const exitCode = typeof msg.exit_code === "number" ? msg.exit_code : undefined;
+  const outcome =
+    msg.outcome === "error" || msg.outcome === "cancelled" ? msg.outcome : "success";
+
+  let parsed: Record<string, unknown> | null = null;
+  try {
+    parsed = asRecordOrNull(JSON.parse(stdout.trim()));
+  } catch {
+    // Plain-text hook output carries no decision.
+  }
+  const specific = asRecordOrNull(parsed?.hookSpecificOutput);
+  const rawDecision = specific?.permissionDecision ?? parsed?.decision;
+  let decision =
+    typeof rawDecision === "string" && HOOK_DECISIONS.has(rawDecision)
+      ? (rawDecision as HookResult["decision"])
+      : undefined;
+  const rawReason = specific?.permissionDecisionReason ?? parsed?.reason ?? parsed?.stopReason;
+  let reason = typeof rawReason === "string" && rawReason.trim() ? rawReason.trim() : undefined;
+  if (!decision && exitCode === 2) {
+    decision = "block";
+    reason = reason ?? (stderr || undefined);
+  }
+  if (!decision && parsed?.continue === false) {
+    decision = "block";
+  }
+
+  const systemMessage = typeof parsed?.systemMessage === "string" ? parsed.systemMessage.trim() : "";
+  return {
+    hook_event: hookEvent,
+    hook_name: typeof msg.hook_name === "string" && msg.hook_name ? msg.hook_name : hookEvent,
+    outcome,
+    ...(exitCode !== undefined ? { exit_code: exitCode } : {}),
+    ...(decision ? { decision } : {}),
+    ...(reason ? { reason } : outcome === "error" && stderr ? { reason: stderr } : {}),
+    input_modified: asRecordOrNull(specific?.updatedInput) !== null,
+    ...(systemMessage ? { system_message: systemMessage } : {}),
+  };
+}
+
 export function handleTaskSystemMessage(
   session: SessionState,
   subtype: string,
@@ -161,9 +250,82 @@ export function handleContentBlock(session: SessionState, block: Record<string,
   }
 }
 
+function citationDefinition(citation: Record<string, unknown>): { key: string; definition: string } | null {
+  const url = typeof citation.url === "string" ? citation.url : "";
+  const title =
+    typeof citation.title === "string"
+      ? citation.title
+      : typeof citation.document_title === "string"
+        ? citation.document_title
+        : "";
+  const key = url || title;
+  if (!key) {
+    return null;
+  }
+  const label = title.replace(/[[\]]/g, "").replace(/\s+/g, " ").trim();
+  if (!url) {
+    return { key, definition: label };
+  }
+  return { key, definition: label ? `[${label}](${url})` : `<${url}>` };
+}
+
+function noteCitation(session: SessionState, citation: Record<string, unknown>): void {
+  const source = citationDefinition(citation);
+  if (!source) {
+    return;
+  }
+  const state = (session.citations ??= { sources: [], pending: [] });
+  let index = state.sources.findIndex((entry) => entry.key === source.key);
+  if (index < 0) {
+    state.sources.push(source);
+    index = state.sources.length - 1;
+  }
+  if (!state.pending.includes(index + 1)) {
+    state.pending.push(index + 1);
+  }
+}
+
+// Citations become markdown footnotes: markers after the cited block, the
+// definitions once the message ends.
+function flushCitationMarkers(session: SessionState): void {
+  const pending = session.citations?.pending ?? [];
+  if (pending.length === 0) {
+    return;
+  }
+  const text = pending.map((number) => `[^${number}]`).join(",");
+  pending.length = 0;
+  emitSessionUpdate(session.sessionId, { type: "agent_message_chunk", content: { type: "text", text } });
+}
+
+function flushCitationFootnotes(session: SessionState): void {
+  flushCitationMarkers(session);
+  const sources = session.citations?.sources ?? [];
+  session.citations = undefined;
+  if (sources.length === 0) {
+    return;
+  }
+  const text = `\n\n${sources.map((source, index) => `[^${index + 1}]: ${source.definition}`).join("\n")}\n`;
+  emitSessionUpdate(session.sessionId, { type: "agent_message_chunk", content: { type: "text", text } });
+}
+
 export function handleStreamEvent(session: SessionState, event: Record<string, unknown>): void {
   const eventType = typeof event.type === "string" ? event.type : "";
 
+  if (eventType === "message_start") {
+    session.citations = undefined;
+    return;
+  }
+
+  if (eventType === "content_block_stop") {
+    flushCitationMarkers(session);
+    return;
+  }
+
+  if (eventType === "message_stop") {
+    flushCitationFootnotes(session);
+    return;
+  }
+
   if (eventType === "content_block_start") {
     if (event.content_block && typeof event.content_block === "object") {
       handleContentBlock(session, event.content_block as Record<string, unknown>);
@@ -187,6 +349,11 @@ export function handleStreamEvent(session: SessionState, event: Record<string, u
       if (text) {
         emitSessionUpdate(session.sessionId, { type: "agent_thought_chunk", content: { type: "text", text } });
       }
+    } else if (deltaType === "citations_delta") {
+      const citation = asRecordOrNull(delta.citation);
+      if (citation) {
+        noteCitation(session, citation);
+      }
     }
   }
 }
@@ -204,6 +371,13 @@ export function handleAssistantMessage(session: SessionState, message: Record<st
   if (!messageObject) {
     return;
   }
+  // Subagent requests run in their own context window.
+  if (typeof message.parent_tool_use_id !== "string") {
+    const usageUpdate = buildRequestUsageUpdate(messageObject.usage);
+    if (usageUpdate) {
+      emitSessionUpdate(session.sessionId, usageUpdate);
+    }
+  }
   const content = Array.isArray(messageObject.content) ? messageObject.content : [];
   for (const block of content) {
     if (!block || typeof block !== "object") {
@@ -245,6 +419,10 @@ export function handleUserToolResultBlocks(session: SessionState, message: Recor
 
 export function handleResultMessage(session: SessionState, message: Record<string, unknown>): void {
   emitFastModeUpdateIfChanged(session, message.fast_mode_state);
+  const usageUpdate = buildResultUsageUpdate(message);
+  if (usageUpdate) {
+    emitSessionUpdate(session.sessionId, usageUpdate);
+  }
 
   const subtype = typeof message.subtype === "string" ? message.subtype : "";
   if (subtype === "success") {
@@ -423,6 +601,14 @@ export function handleSdkMessage(session: SessionState, message: SDKMessage): vo
       return;
     }
 
+    if (subtype === "hook_response") {
+      const result = hookResultFromMessage(msg);
+      if (result) {
+        emitSessionUpdate(session.sessionId, { type: "hook_result", ...result });
+      }
+      return;
+    }
+
     handleTaskSystemMessage(session, subtype, msg);
     return;
   }
@@ -106,6 +106,14 @@ export type SessionState = {
   lastAssistantError?: string;
   sessionsToCloseAfterConnect?: SessionState[];
   resumeUpdates?: SessionUpdate[];
+  citations?: CitationState;
+};
+
+/** Sources cited by the assistant message being streamed, numbered from 1. */
+export type CitationState = {
+  sources: Array<{ key: string; definition: string }>;
+  /** Footnote numbers cited by the current content block, emitted when it ends. */
+  pending: number[];
 };
 
 export const sessions = new Map<string, SessionState>();
@@ -449,6 +457,19 @@ export function buildQueryOptions(params: QueryOptionsBuilderParams) {
     ...(params.launchSettings.agent_progress_summaries !== undefined
       ? { agentProgressSummaries: params.launchSettings.agent_progress_summaries }
       : {}),
+    ...(params.launchSettings.additional_directories
+      ? { additionalDirectories: params.launchSettings.additional_directories }
+      : {}),
+    // The SDK replaces the process environment with `env`, so extend it.
+    ...(params.launchSettings.env
+      ? { env: { ...process.env, ...params.launchSettings.env } }
+      : {}),
+    ...(params.launchSettings.max_thinking_tokens !== undefined
+      ? { maxThinkingTokens: params.launchSettings.max_thinking_tokens }
+      : {}),
+    ...(params.launchSettings.max_turns !== undefined
+      ? { maxTurns: params.launchSettings.max_turns }
+      : {}),
     ...(params.claudeCodeExecutable
       ? { pathToClaudeCodeExecutable: params.claudeCodeExecutable }
       : {}),
@@ -490,6 +511,9 @@ export function buildQueryOptions(params: QueryOptionsBuilderParams) {
     // Match claude-agent-acp defaults to avoid emitting an empty
     // --setting-sources argument.
     settingSources: DEFAULT_SETTING_SOURCES,
+    // Hooks from those settings run inside the SDK; ask it to report their
+    // results so blocked or rewritten tool calls show up in the transcript.
+    extraArgs: { "include-hook-events": null },
     resume: params.resume,
     canUseTool: params.canUseTool,
     onElicitation: async (request: {
@@ -82,3 +82,60 @@ export function buildRateLimitUpdate(
 
   return update;
 }
+
+/** Prompt-side tokens of one API call, from an assistant message's `usage`. */
+export function buildRequestUsageUpdate(
+  usage: unknown,
+): Extract<SessionUpdate, { type: "usage_update" }> | null {
+  const record = asRecordOrNull(usage);
+  if (!record) {
+    return null;
+  }
+  const inputTokens = numberField(record, "input_tokens");
+  if (inputTokens === undefined) {
+    return null;
+  }
+
+  const update: Extract<SessionUpdate, { type: "usage_update" }> = {
+    type: "usage_update",
+    input_tokens: inputTokens,
+  };
+  const cacheRead = numberField(record, "cache_read_input_tokens");
+  if (cacheRead !== undefined) {
+    update.cache_read_tokens = cacheRead;
+  }
+  const cacheWrite = numberField(record, "cache_creation_input_tokens");
+  if (cacheWrite !== undefined) {
+    update.cache_write_tokens = cacheWrite;
+  }
+  return update;
+}
+
+/** Turn totals from a `result` message: output tokens, session cost and context window. */
+export function buildResultUsageUpdate(
+  result: Record<string, unknown>,
+): Extract<SessionUpdate, { type: "usage_update" }> | null {
+  const update: Extract<SessionUpdate, { type: "usage_update" }> = { type: "usage_update" };
+
+  const usage = asRecordOrNull(result.usage);
+  const outputTokens = usage ? numberField(usage, "output_tokens") : undefined;
+  if (outputTokens !== undefined) {
+    update.output_tokens = outputTokens;
+  }
+
+  const totalCost = numberField(result, "total_cost_usd");
+  if (totalCost !== undefined) {
+    update.total_cost_usd = totalCost;
+  }
+
+  const modelUsage = asRecordOrNull(result.modelUsage);
+  for (const entry of Object.values(modelUsage ?? {})) {
+    const record = asRecordOrNull(entry);
+    const contextWindow = record ? numberField(record, "contextWindow") : undefined;
+    if (contextWindow !== undefined && contextWindow > (update.context_window ?? 0)) {
+      update.context_window = contextWindow;
+    }
+  }
+
+  return Object.keys(update).length > 1 ? update : null;
+}
@@ -57,6 +57,15 @@ export interface RateLimitUpdate {
   surpassed_threshold?: number;
 }
 
+export interface UsageUpdate {
+  input_tokens?: number;
+  cache_read_tokens?: number;
+  cache_write_tokens?: number;
+  output_tokens?: number;
+  context_window?: number;
+  total_cost_usd?: number;
+}
+
 export type ContentBlock =
   | { type: "text"; text: string }
   | { type: "image"; mime_type?: string; uri?: string; data?: string };
@@ -153,8 +162,25 @@ export type SessionUpdate =
   | { type: "config_option_update"; option_id: string; value: Json }
   | { type: "fast_mode_update"; fast_mode_state: FastModeState }
   | ({ type: "rate_limit_update" } & RateLimitUpdate)
+  | ({ type: "usage_update" } & UsageUpdate)
   | { type: "session_status_update"; status: "compacting" | "idle" }
-  | { type: "compaction_boundary"; trigger: "manual" | "auto"; pre_tokens: number };
+  | { type: "compaction_boundary"; trigger: "manual" | "auto"; pre_tokens: number }
+  | ({ type: "hook_result" } & HookResult);
+
+/** Outcome of a Claude Code hook from the user's or project's settings. */
+export interface HookResult {
+  hook_event: string;
+  hook_name: string;
+  outcome: "success" | "error" | "cancelled";
+  exit_code?: number;
+  /** `block`/`deny` stopped the tool call, `ask` sent it to the permission prompt. */
+  decision?: "block" | "deny" | "ask" | "allow";
+  reason?: string;
+  /** The hook rewrote the tool input through `updatedInput`. */
+  input_modified: boolean;
+  /** `systemMessage` the hook asked to show the user. */
+  system_message?: string;
+}
 
 export interface PermissionOption {
   option_id: string;
@@ -330,6 +356,10 @@ export interface SessionLaunchSettings {
   language?: string;
   settings?: { [key: string]: Json };
   agent_progress_summaries?: boolean;
+  additional_directories?: string[];
+  env?: { [key: string]: string };
+  max_thinking_tokens?: number;
+  max_turns?: number;
 }
 
 export interface BridgeCommandEnvelope {
@@ -450,6 +480,10 @@ export type BridgeCommand =
       server_name: string;
       callback_url: string;
     }
+  | {
+      command: "ping";
+      nonce: number;
+    }
   | {
       command: "shutdown";
     };
@@ -469,10 +503,21 @@ export interface InitializeResult {
     prompt_embedded_context: boolean;
     supports_session_listing: boolean;
     supports_resume_session: boolean;
+    supports_modes: boolean;
+    supports_plan_updates: boolean;
   };
+  protocol_version: number;
 }
 
-export type TurnErrorKind = "plan_limit" | "auth_required" | "internal" | "other";
+export type TurnErrorKind =
+  | "plan_limit"
+  | "auth_required"
+  | "network"
+  | "tool_failure"
+  | "protocol_mismatch"
+  | "crash"
+  | "internal"
+  | "other";
 
 export type BridgeEvent =
   | {
@@ -515,6 +560,7 @@ export type BridgeEvent =
   | { event: "initialized"; result: InitializeResult }
   | { event: "sessions_listed"; sessions: SessionListEntry[] }
   | { event: "status_snapshot"; session_id: string; account: AccountInfo }
+  | { event: "pong"; nonce: number }
   | {
       event: "mcp_snapshot";
       session_id: string;
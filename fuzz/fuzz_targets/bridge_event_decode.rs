@@ -0,0 +1,28 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Feeds arbitrary bridge stdout lines to the event decoder. Decoding must
+//! never panic, and every event it accepts must survive a JSON round trip.
+
+#![no_main]
+
+use claude_code_rust::agent::wire::{BridgeEvent, EventEnvelope};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(line) = std::str::from_utf8(data) else {
+        return;
+    };
+    let envelope = EventEnvelope::decode(line);
+    let _ = envelope.event.name();
+    let _ = envelope.event.session_id();
+    if matches!(envelope.event, BridgeEvent::Unrecognized { .. }) {
+        return;
+    }
+    let encoded = serde_json::to_string(&envelope).expect("decoded events re-encode");
+    let decoded = EventEnvelope::decode(&encoded);
+    assert!(
+        !matches!(decoded.event, BridgeEvent::Unrecognized { .. }),
+        "re-encoded event no longer decodes: {encoded}"
+    );
+});
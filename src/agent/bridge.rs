@@ -1,27 +1,48 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+//! Resolution of the agent process the TUI talks to.
+//!
+//! The default profile runs the bundled Claude bridge under Node. Additional
+//! profiles are read from `agents.json` in the config directory and name any
+//! program that speaks the bridge protocol on stdio (for example an ACP
+//! adapter wrapping Gemini CLI or a local agent):
+//!
+//! ```json
+//! { "gemini": { "command": "gemini-bridge", "args": ["--acp"], "env": { "GEMINI_MODEL": "pro" } } }
+//! ```
+
 use crate::error::AppError;
 use anyhow::Context as _;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
+/// Name of the built-in profile that launches the bundled Claude bridge.
+pub const DEFAULT_AGENT_PROFILE: &str = "claude";
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BridgeLauncher {
-    pub runtime_path: PathBuf,
-    pub script_path: PathBuf,
+    pub program: PathBuf,
+    pub args: Vec<OsString>,
+    pub env: BTreeMap<String, String>,
 }
 
 impl BridgeLauncher {
     #[must_use]
     pub fn describe(&self) -> String {
-        format!("{} {}", self.runtime_path.to_string_lossy(), self.script_path.to_string_lossy())
+        let mut parts = vec![self.program.to_string_lossy().into_owned()];
+        parts.extend(self.args.iter().map(|arg| arg.to_string_lossy().into_owned()));
+        parts.join(" ")
     }
 
     #[must_use]
     pub fn command(&self) -> Command {
-        let mut cmd = Command::new(&self.runtime_path);
-        cmd.arg(&self.script_path);
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+        cmd.envs(&self.env);
         cmd.stdin(std::process::Stdio::piped());
         cmd.stdout(std::process::Stdio::piped());
         cmd.stderr(std::process::Stdio::piped());
@@ -29,12 +50,81 @@ impl BridgeLauncher {
     }
 }
 
+/// A user-configured agent command from `agents.json`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AgentProfile {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+/// Path of the agent profile file. `CLAUDE_RS_AGENTS_FILE` overrides the
+/// default location under the user config directory.
+#[must_use]
+pub fn agent_profiles_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("CLAUDE_RS_AGENTS_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir().map(|dir| dir.join("claude-code-rust").join("agents.json"))
+}
+
+pub fn parse_agent_profiles(raw: &str) -> anyhow::Result<BTreeMap<String, AgentProfile>> {
+    serde_json::from_str(raw).context("invalid agent profile file")
+}
+
+fn load_agent_profile(name: &str) -> anyhow::Result<AgentProfile> {
+    let path = agent_profiles_path()
+        .ok_or_else(|| anyhow::anyhow!("no config directory to read agent profiles from"))?;
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read agent profiles from {}", path.display()))?;
+    let mut profiles = parse_agent_profiles(&raw)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    profiles.remove(name).ok_or_else(|| {
+        let known = profiles.keys().map(String::as_str).collect::<Vec<_>>().join(", ");
+        anyhow::anyhow!(
+            "unknown agent profile `{name}` in {} (available: {DEFAULT_AGENT_PROFILE}{}{known})",
+            path.display(),
+            if known.is_empty() { "" } else { ", " }
+        )
+    })
+}
+
+/// Resolve the launcher for `profile`, falling back to the bundled Claude
+/// bridge when no profile (or the default one) is requested.
+pub fn resolve_agent_launcher(
+    profile: Option<&str>,
+    explicit_script: Option<&Path>,
+) -> anyhow::Result<BridgeLauncher> {
+    match profile {
+        None | Some(DEFAULT_AGENT_PROFILE) => resolve_bridge_launcher(explicit_script),
+        Some(name) => launcher_for_profile(name, &load_agent_profile(name)?),
+    }
+}
+
+fn launcher_for_profile(name: &str, profile: &AgentProfile) -> anyhow::Result<BridgeLauncher> {
+    let program = which::which(&profile.command).with_context(|| {
+        format!("agent profile `{name}`: command `{}` not found", profile.command)
+    })?;
+    Ok(BridgeLauncher {
+        program,
+        args: profile.args.iter().map(OsString::from).collect(),
+        env: profile.env.clone(),
+    })
+}
+
 pub fn resolve_bridge_launcher(explicit_script: Option<&Path>) -> anyhow::Result<BridgeLauncher> {
     let runtime = which::which("node")
         .map_err(|_| anyhow::Error::new(AppError::NodeNotFound))
         .context("failed to resolve `node` runtime")?;
     let script = resolve_bridge_script_path(explicit_script)?;
-    Ok(BridgeLauncher { runtime_path: runtime, script_path: script })
+    Ok(BridgeLauncher {
+        program: runtime,
+        args: vec![script.into_os_string()],
+        env: BTreeMap::new(),
+    })
 }
 
 fn resolve_bridge_script_path(explicit_script: Option<&Path>) -> anyhow::Result<PathBuf> {
@@ -80,7 +170,8 @@ fn validate_script_path(path: &Path) -> anyhow::Result<PathBuf> {
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_bridge_launcher;
+    use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn explicit_missing_script_path_fails() {
@@ -88,4 +179,45 @@ mod tests {
             resolve_bridge_launcher(Some(std::path::Path::new("agent-sdk/dist/missing.mjs")));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn agent_profiles_parse_with_optional_args_and_env() {
+        let profiles = parse_agent_profiles(
+            r#"{
+                "gemini": { "command": "gemini-bridge", "args": ["--acp"], "env": { "A": "1" } },
+                "local": { "command": "./agent" }
+            }"#,
+        )
+        .expect("profiles");
+
+        assert_eq!(
+            profiles.get("gemini"),
+            Some(&AgentProfile {
+                command: "gemini-bridge".to_owned(),
+                args: vec!["--acp".to_owned()],
+                env: BTreeMap::from([("A".to_owned(), "1".to_owned())]),
+            })
+        );
+        assert_eq!(profiles.get("local").map(|p| p.args.len()), Some(0));
+        assert!(parse_agent_profiles(r#"{ "x": { "cmd": "y" } }"#).is_err());
+    }
+
+    #[test]
+    fn launcher_describes_program_and_args() {
+        let launcher = BridgeLauncher {
+            program: PathBuf::from("/usr/bin/agent"),
+            args: vec![OsString::from("--acp"), OsString::from("--verbose")],
+            env: BTreeMap::new(),
+        };
+        assert_eq!(launcher.describe(), "/usr/bin/agent --acp --verbose");
+    }
+
+    #[test]
+    fn profile_with_missing_command_fails() {
+        let profile = AgentProfile {
+            command: "definitely-not-an-agent-binary".to_owned(),
+            ..Default::default()
+        };
+        assert!(launcher_for_profile("ghost", &profile).is_err());
+    }
 }
@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::agent::bridge::BridgeLauncher;
+use crate::agent::event_tee::SharedEventTee;
+use crate::agent::mock::MockAgent;
+use crate::agent::stderr_log::{SharedStderrBuffer, StderrLevel};
 use crate::agent::wire::{BridgeCommand, CommandEnvelope, EventEnvelope, SessionLaunchSettings};
 use crate::error::AppError;
 use anyhow::Context as _;
@@ -10,13 +13,30 @@ use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout};
 use tokio::sync::mpsc;
 
 pub struct BridgeClient {
-    child: Child,
-    stdin: BufWriter<ChildStdin>,
-    stdout: tokio::io::Lines<BufReader<ChildStdout>>,
+    transport: Transport,
+    /// `--tee-events` copy of everything sent and received.
+    tee: Option<SharedEventTee>,
+}
+
+// One per session, so the size of the process variant does not matter.
+#[allow(clippy::large_enum_variant)]
+enum Transport {
+    Process {
+        child: Child,
+        stdin: BufWriter<ChildStdin>,
+        stdout: tokio::io::Lines<BufReader<ChildStdout>>,
+    },
+    /// `--mock`: scripted in-process agent, no child process.
+    Mock(MockAgent),
 }
 
 impl BridgeClient {
-    pub fn spawn(launcher: &BridgeLauncher) -> anyhow::Result<Self> {
+    /// Spawn the agent process. Its stderr is logged through tracing and kept
+    /// in `stderr_log` for the diagnostics overlay.
+    pub fn spawn(
+        launcher: &BridgeLauncher,
+        stderr_log: &SharedStderrBuffer,
+    ) -> anyhow::Result<Self> {
         let mut child = launcher
             .command()
             .spawn()
@@ -26,17 +46,45 @@ impl BridgeClient {
         let stdin = child.stdin.take().context("bridge stdin not available")?;
         let stdout = child.stdout.take().context("bridge stdout not available")?;
         let stderr = child.stderr.take().context("bridge stderr not available")?;
-        Self::spawn_stderr_logger(stderr);
+        stderr_log
+            .borrow_mut()
+            .push(StderrLevel::Info, format!("--- started {} ---", launcher.describe()));
+        Self::spawn_stderr_logger(stderr, std::rc::Rc::clone(stderr_log));
+
+        Ok(Self {
+            transport: Transport::Process {
+                child,
+                stdin: BufWriter::new(stdin),
+                stdout: BufReader::new(stdout).lines(),
+            },
+            tee: None,
+        })
+    }
+
+    /// Talk to the built-in mock agent instead of a bridge process.
+    #[must_use]
+    pub fn mock(stderr_log: &SharedStderrBuffer) -> Self {
+        stderr_log.borrow_mut().push(StderrLevel::Info, "--- started mock agent ---".to_owned());
+        Self { transport: Transport::Mock(MockAgent::new()), tee: None }
+    }
 
-        Ok(Self { child, stdin: BufWriter::new(stdin), stdout: BufReader::new(stdout).lines() })
+    /// Mirror traffic to an event tee.
+    #[must_use]
+    pub fn with_tee(mut self, tee: Option<SharedEventTee>) -> Self {
+        self.tee = tee;
+        self
     }
 
-    fn spawn_stderr_logger(stderr: ChildStderr) {
+    fn spawn_stderr_logger(stderr: ChildStderr, stderr_log: SharedStderrBuffer) {
         tokio::task::spawn_local(async move {
             let mut lines = BufReader::new(stderr).lines();
             loop {
                 match lines.next_line().await {
-                    Ok(Some(line)) => Self::log_bridge_stderr_line(&line),
+                    Ok(Some(line)) => {
+                        let level = StderrLevel::classify(&line);
+                        Self::log_bridge_stderr_line(level, &line);
+                        stderr_log.borrow_mut().push(level, line);
+                    }
                     Ok(None) => break,
                     Err(err) => {
                         tracing::error!("failed to read bridge stderr: {err}");
@@ -47,36 +95,51 @@ impl BridgeClient {
         });
     }
 
-    fn log_bridge_stderr_line(line: &str) {
-        // The bridge uses a structured "[sdk <verb>]" prefix format.
-        // Extract an explicit level from it; fall back to debug for ordinary chatter.
-        let lower = line.to_ascii_lowercase();
-        if lower.contains("[sdk error]") || lower.starts_with("error") || lower.contains("panic") {
-            tracing::error!("bridge stderr: {line}");
-        } else if lower.contains("[sdk warn]") || lower.starts_with("warn") {
-            tracing::warn!("bridge stderr: {line}");
-        } else {
-            tracing::debug!("bridge stderr: {line}");
+    fn log_bridge_stderr_line(level: StderrLevel, line: &str) {
+        match level {
+            StderrLevel::Error => tracing::error!("bridge stderr: {line}"),
+            StderrLevel::Warn => tracing::warn!("bridge stderr: {line}"),
+            StderrLevel::Info | StderrLevel::Debug => tracing::debug!("bridge stderr: {line}"),
         }
     }
 
     pub async fn send(&mut self, envelope: CommandEnvelope) -> anyhow::Result<()> {
+        if let Some(tee) = &self.tee {
+            tee.borrow_mut().record_outbound(&envelope);
+        }
+        let stdin = match &mut self.transport {
+            Transport::Process { stdin, .. } => stdin,
+            Transport::Mock(agent) => {
+                agent.handle(envelope.command);
+                return Ok(());
+            }
+        };
         let line =
             serde_json::to_string(&envelope).context("failed to serialize bridge command")?;
-        self.stdin.write_all(line.as_bytes()).await.context("failed to write bridge command")?;
-        self.stdin.write_all(b"\n").await.context("failed to write bridge newline")?;
-        self.stdin.flush().await.context("failed to flush bridge stdin")?;
+        stdin.write_all(line.as_bytes()).await.context("failed to write bridge command")?;
+        stdin.write_all(b"\n").await.context("failed to write bridge newline")?;
+        stdin.flush().await.context("failed to flush bridge stdin")?;
         Ok(())
     }
 
     pub async fn recv(&mut self) -> anyhow::Result<Option<EventEnvelope>> {
-        let Some(line) = self.stdout.next_line().await.context("failed to read bridge stdout")?
-        else {
+        let stdout = match &mut self.transport {
+            Transport::Process { stdout, .. } => stdout,
+            Transport::Mock(agent) => {
+                let event = agent.next_event().await;
+                if let (Some(tee), Some(event)) = (&self.tee, &event) {
+                    tee.borrow_mut().record_inbound(event);
+                }
+                return Ok(event);
+            }
+        };
+        let Some(line) = stdout.next_line().await.context("failed to read bridge stdout")? else {
             return Ok(None);
         };
-        let event: EventEnvelope =
-            serde_json::from_str(&line).context("failed to decode bridge event json")?;
-        Ok(Some(event))
+        if let Some(tee) = &self.tee {
+            tee.borrow_mut().record_inbound_line(&line);
+        }
+        Ok(Some(EventEnvelope::decode(&line)))
     }
 
     pub async fn shutdown(&mut self) -> anyhow::Result<()> {
@@ -84,8 +147,13 @@ impl BridgeClient {
         Ok(())
     }
 
-    pub async fn wait(mut self) -> anyhow::Result<std::process::ExitStatus> {
-        self.child.wait().await.context("failed to wait for bridge process")
+    pub async fn wait(self) -> anyhow::Result<std::process::ExitStatus> {
+        match self.transport {
+            Transport::Process { mut child, .. } => {
+                child.wait().await.context("failed to wait for bridge process")
+            }
+            Transport::Mock(_) => Err(anyhow::anyhow!("the mock agent has no process to wait for")),
+        }
     }
 }
 
@@ -106,15 +174,23 @@ impl AgentConnection {
     }
 
     pub fn prompt_text(&self, session_id: String, text: String) -> anyhow::Result<PromptResponse> {
+        self.prompt(
+            session_id,
+            vec![crate::agent::types::PromptChunk {
+                kind: "text".to_owned(),
+                value: serde_json::Value::String(text),
+            }],
+        )
+    }
+
+    pub fn prompt(
+        &self,
+        session_id: String,
+        chunks: Vec<crate::agent::types::PromptChunk>,
+    ) -> anyhow::Result<PromptResponse> {
         self.send(CommandEnvelope {
             request_id: None,
-            command: BridgeCommand::Prompt {
-                session_id,
-                chunks: vec![crate::agent::types::PromptChunk {
-                    kind: "text".to_owned(),
-                    value: serde_json::Value::String(text),
-                }],
-            },
+            command: BridgeCommand::Prompt { session_id, chunks },
         })?;
         Ok(PromptResponse { stop_reason: "end_turn".to_owned() })
     }
@@ -280,6 +356,10 @@ impl AgentConnection {
         })
     }
 
+    pub fn ping(&self, nonce: u64) -> anyhow::Result<()> {
+        self.send(CommandEnvelope { request_id: None, command: BridgeCommand::Ping { nonce } })
+    }
+
     fn send(&self, envelope: CommandEnvelope) -> anyhow::Result<()> {
         self.command_tx.send(envelope).map_err(|_| anyhow::anyhow!("bridge command channel closed"))
     }
@@ -1,19 +1,86 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TurnErrorClass {
+    /// Rate, usage, or plan limits.
     PlanLimit,
     AuthRequired,
+    /// The API could not be reached (DNS, proxy, TLS, dropped connection).
+    Network,
+    /// A tool the agent called failed in a way that ended the turn.
+    ToolFailure,
+    /// The bridge and the Agent SDK disagree about message shapes or versions.
+    ProtocolMismatch,
+    /// The agent process died.
+    Crash,
     Internal,
     Other,
 }
 
+impl TurnErrorClass {
+    /// Single-cell glyph shown in front of the error title.
+    #[must_use]
+    pub const fn icon(self) -> &'static str {
+        match self {
+            Self::PlanLimit => "\u{25f7}",
+            Self::AuthRequired => "\u{26bf}",
+            Self::Network => "\u{21c5}",
+            Self::ToolFailure => "\u{2692}",
+            Self::ProtocolMismatch => "\u{2260}",
+            Self::Crash => "\u{21af}",
+            Self::Internal => "\u{26a0}",
+            Self::Other => "\u{2717}",
+        }
+    }
+
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::PlanLimit => "Rate or plan limit",
+            Self::AuthRequired => "Authentication required",
+            Self::Network => "Network error",
+            Self::ToolFailure => "Tool failure",
+            Self::ProtocolMismatch => "Protocol mismatch",
+            Self::Crash => "Agent crashed",
+            Self::Internal => "Internal error",
+            Self::Other => "Turn failed",
+        }
+    }
+
+    /// What the user can do about it.
+    #[must_use]
+    pub const fn recovery_hint(self) -> &'static str {
+        match self {
+            Self::PlanLimit => "Wait for the limit to reset, then retry.",
+            Self::AuthRequired => "Run /login, then retry.",
+            Self::Network => {
+                "Check your connection and proxy settings (HTTPS_PROXY, NO_PROXY), then retry."
+            }
+            Self::ToolFailure => {
+                "Check the failed tool call above, then retry or ask for another approach."
+            }
+            Self::ProtocolMismatch => {
+                "Update claude-code-rust so its bridge matches the installed Agent SDK."
+            }
+            Self::Crash => "Restart claude-code-rust; the log file has the crash details.",
+            Self::Internal => "Retry; if it keeps happening, report it with the log file.",
+            Self::Other => "Retry.",
+        }
+    }
+}
+
 pub fn parse_turn_error_class(tag: &str) -> Option<TurnErrorClass> {
     match tag {
         "plan_limit" => Some(TurnErrorClass::PlanLimit),
         "auth_required" => Some(TurnErrorClass::AuthRequired),
         "internal" => Some(TurnErrorClass::Internal),
+        "network" => Some(TurnErrorClass::Network),
+        "tool_failure" => Some(TurnErrorClass::ToolFailure),
+        "protocol_mismatch" => Some(TurnErrorClass::ProtocolMismatch),
+        "crash" => Some(TurnErrorClass::Crash),
         "other" => Some(TurnErrorClass::Other),
         _ => None,
     }
@@ -25,6 +92,14 @@ pub fn classify_turn_error(input: &str) -> TurnErrorClass {
         TurnErrorClass::PlanLimit
     } else if looks_like_auth_required_error_lower(&lower) {
         TurnErrorClass::AuthRequired
+    } else if looks_like_network_error_lower(&lower) {
+        TurnErrorClass::Network
+    } else if looks_like_protocol_mismatch_lower(&lower) {
+        TurnErrorClass::ProtocolMismatch
+    } else if looks_like_crash_lower(&lower) {
+        TurnErrorClass::Crash
+    } else if looks_like_tool_failure_lower(&lower) {
+        TurnErrorClass::ToolFailure
     } else if looks_like_internal_error_lower(&lower) {
         TurnErrorClass::Internal
     } else {
@@ -32,6 +107,72 @@ pub fn classify_turn_error(input: &str) -> TurnErrorClass {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientErrorKind {
+    /// HTTP 429: too many requests in a short window.
+    RateLimited,
+    /// HTTP 529/503: the API is temporarily over capacity.
+    Overloaded,
+}
+
+/// A turn error that is expected to clear up on its own, so the turn can be
+/// retried after a pause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransientError {
+    pub kind: TransientErrorKind,
+    /// Server-suggested wait, when the error carries one.
+    pub retry_after: Option<Duration>,
+}
+
+/// Recognize rate-limit and overload errors worth retrying. Errors that name
+/// a hard limit (quota, budget, max turns) are not transient even when they
+/// come with a 429.
+pub fn parse_transient_error(input: &str) -> Option<TransientError> {
+    let lower = input.to_ascii_lowercase();
+    let hard_limit = [
+        "max turns",
+        "max turn",
+        "max budget",
+        "quota",
+        "plan limit",
+        "plan-limit",
+        "usage limit",
+        "billing",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle));
+    if hard_limit {
+        return None;
+    }
+    let kind = if ["overloaded", "529", "503", "service unavailable"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        TransientErrorKind::Overloaded
+    } else if ["rate limit", "rate-limit", "rate_limit", "429", "too many requests"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        TransientErrorKind::RateLimited
+    } else {
+        return None;
+    };
+    Some(TransientError { kind, retry_after: parse_retry_after(&lower) })
+}
+
+/// Seconds following a `retry-after` / `retry after` / `"retry_after"` marker.
+fn parse_retry_after(lower: &str) -> Option<Duration> {
+    ["retry-after", "retry_after", "retry after"].iter().find_map(|marker| {
+        let rest = &lower[lower.find(marker)? + marker.len()..];
+        let digits: String = rest
+            .trim_start_matches([' ', ':', '=', '"'])
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok().map(Duration::from_secs)
+    })
+}
+
 pub fn looks_like_internal_error(input: &str) -> bool {
     looks_like_internal_error_lower(&input.to_ascii_lowercase())
 }
@@ -83,6 +224,64 @@ fn looks_like_auth_required_error_lower(lower: &str) -> bool {
     .any(|needle| lower.contains(needle))
 }
 
+fn looks_like_network_error_lower(lower: &str) -> bool {
+    [
+        "econnrefused",
+        "econnreset",
+        "etimedout",
+        "enotfound",
+        "eai_again",
+        "getaddrinfo",
+        "socket hang up",
+        "fetch failed",
+        "network error",
+        "network is unreachable",
+        "proxy",
+        "unable to verify the first certificate",
+        "self-signed certificate",
+        "self signed certificate",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+fn looks_like_protocol_mismatch_lower(lower: &str) -> bool {
+    [
+        "protocol mismatch",
+        "protocol version",
+        "unsupported command",
+        "unknown command",
+        "invalid command envelope",
+        "zoderror",
+        "invalid_union",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+fn looks_like_crash_lower(lower: &str) -> bool {
+    [
+        "process exited",
+        "exited with code",
+        "exited unexpectedly",
+        "crashed",
+        "sigsegv",
+        "sigkill",
+        "sigabrt",
+        "out of memory",
+        "connection closed",
+        "event channel closed",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+fn looks_like_tool_failure_lower(lower: &str) -> bool {
+    ["tool execution failed", "tool call failed", "tool_use_error", "tool failed"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+}
+
 fn looks_like_internal_error_lower(lower: &str) -> bool {
     has_internal_error_keywords(lower)
         || looks_like_json_rpc_error_shape(lower)
@@ -203,9 +402,11 @@ fn extract_json_string_field(input: &str, field: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        TurnErrorClass, classify_turn_error, looks_like_internal_error, parse_turn_error_class,
+        TransientError, TransientErrorKind, TurnErrorClass, classify_turn_error,
+        looks_like_internal_error, parse_transient_error, parse_turn_error_class,
         summarize_internal_error,
     };
+    use std::time::Duration;
 
     #[test]
     fn classifies_plan_limit_errors() {
@@ -216,6 +417,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transient_errors_are_retryable_unless_a_hard_limit_is_named() {
+        assert_eq!(
+            parse_transient_error(r#"529 {"type":"error","error":{"type":"overloaded_error"}}"#),
+            Some(TransientError { kind: TransientErrorKind::Overloaded, retry_after: None })
+        );
+        assert_eq!(
+            parse_transient_error("HTTP 429 Too Many Requests (retry-after: 12)"),
+            Some(TransientError {
+                kind: TransientErrorKind::RateLimited,
+                retry_after: Some(Duration::from_secs(12)),
+            })
+        );
+        assert_eq!(parse_transient_error("HTTP 429 Too Many Requests: max turns exceeded"), None);
+        assert_eq!(parse_transient_error("insufficient quota"), None);
+        assert_eq!(parse_transient_error("turn failed: timeout"), None);
+    }
+
     #[test]
     fn classifies_auth_required_errors() {
         assert_eq!(
@@ -237,6 +456,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn classifies_network_protocol_crash_and_tool_errors() {
+        assert_eq!(
+            classify_turn_error("request to https://api.anthropic.com failed: ECONNRESET"),
+            TurnErrorClass::Network
+        );
+        assert_eq!(
+            classify_turn_error("Tool permission request failed: ZodError: invalid_union"),
+            TurnErrorClass::ProtocolMismatch
+        );
+        assert_eq!(
+            classify_turn_error("agent process exited with code 137"),
+            TurnErrorClass::Crash
+        );
+        assert_eq!(
+            classify_turn_error("Tool execution failed: Bash timed out"),
+            TurnErrorClass::ToolFailure
+        );
+        assert_eq!(
+            parse_turn_error_class("protocol_mismatch"),
+            Some(TurnErrorClass::ProtocolMismatch)
+        );
+        assert_eq!(TurnErrorClass::AuthRequired.recovery_hint(), "Run /login, then retry.");
+    }
+
     #[test]
     fn classifies_other_errors() {
         assert_eq!(classify_turn_error("turn failed: timeout"), TurnErrorClass::Other);
@@ -0,0 +1,103 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `--tee-events`: a JSONL copy of the bridge traffic.
+//!
+//! Every command sent to the agent and every event received from it is
+//! appended as one line, `{"ts_ms":…,"direction":"out"|"in","message":…}`,
+//! and written through immediately so dashboards or audit tools can follow
+//! the file live. Inbound lines are copied as the agent sent them, including
+//! ones the client could not decode. The file outlives bridge restarts.
+
+use crate::agent::wire::{CommandEnvelope, EventEnvelope};
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub type SharedEventTee = Rc<RefCell<EventTee>>;
+
+#[derive(Debug)]
+pub struct EventTee {
+    file: Option<File>,
+}
+
+impl EventTee {
+    /// Open `path` for appending.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Some(file) })
+    }
+
+    pub fn record_outbound(&mut self, envelope: &CommandEnvelope) {
+        match serde_json::to_value(envelope) {
+            Ok(message) => self.write("out", &message),
+            Err(err) => tracing::warn!("event tee: cannot serialize command: {err}"),
+        }
+    }
+
+    /// A raw line read from the bridge's stdout.
+    pub fn record_inbound_line(&mut self, line: &str) {
+        let message =
+            serde_json::from_str::<Value>(line).unwrap_or_else(|_| Value::String(line.to_owned()));
+        self.write("in", &message);
+    }
+
+    /// An event produced in-process, e.g. by the mock agent.
+    pub fn record_inbound(&mut self, envelope: &EventEnvelope) {
+        match serde_json::to_value(envelope) {
+            Ok(message) => self.write("in", &message),
+            Err(err) => tracing::warn!("event tee: cannot serialize event: {err}"),
+        }
+    }
+
+    fn write(&mut self, direction: &str, message: &Value) {
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+        let ts_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
+        let mut line =
+            json!({ "ts_ms": ts_ms, "direction": direction, "message": message }).to_string();
+        line.push('\n');
+        if let Err(err) = file.write_all(line.as_bytes()) {
+            tracing::warn!("event tee: write failed, no further events are recorded: {err}");
+            self.file = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::wire::BridgeCommand;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tee_writes_one_json_object_per_message() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("events.jsonl");
+        let mut tee = EventTee::open(&path).expect("open tee");
+
+        tee.record_outbound(&CommandEnvelope {
+            request_id: None,
+            command: BridgeCommand::Shutdown,
+        });
+        tee.record_inbound_line("not json");
+
+        let lines: Vec<Value> = std::fs::read_to_string(&path)
+            .expect("read tee")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json line"))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["direction"], "out");
+        assert!(lines[0]["message"].is_object());
+        assert_eq!(lines[1]["direction"], "in");
+        assert_eq!(lines[1]["message"], "not json");
+    }
+}
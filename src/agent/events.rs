@@ -51,6 +51,8 @@ pub enum ClientEvent {
         mode: Option<crate::app::ModeState>,
         history_updates: Vec<model::SessionUpdate>,
     },
+    /// The bridge process exited after a session was established.
+    BridgeExited(String),
     /// Background connection failed.
     ConnectionFailed(String),
     /// Authentication is required before a session can be created.
@@ -66,18 +68,49 @@ pub enum ClientEvent {
         mode: Option<crate::app::ModeState>,
         history_updates: Vec<model::SessionUpdate>,
     },
+    /// The agent finished the initialize handshake and advertised its capabilities.
+    AgentInitialized(crate::agent::types::InitializeResult),
     /// Recent sessions discovered via SDK session listing.
     SessionsListed { sessions: Vec<crate::agent::types::SessionListEntry> },
     /// Startup update check found a newer published version.
-    UpdateAvailable { latest_version: String, current_version: String },
+    UpdateAvailable {
+        release: crate::app::ReleaseInfo,
+        current_version: String,
+        channel: crate::app::UpdateChannel,
+    },
+    /// `/update` finished checking or installing; the message is shown in the transcript.
+    SelfUpdateFinished { result: Result<String, String>, installed: bool },
+    /// `git commit` from the /commit overlay finished; `Ok` holds git's summary line.
+    GitCommitFinished { result: Result<String, String> },
+    /// The forge CLI from the /pr overlay finished; `Ok` holds the pull request URL.
+    PullRequestFinished { result: Result<String, String> },
+    /// /issue fetched an issue; `Ok` holds the prompt headline and the issue details.
+    IssueFetched { result: Result<(String, String), String> },
+    /// `/share --gist` finished; `Ok` holds the gist URL.
+    ShareFinished { result: Result<String, String> },
+    /// The `checkCommand` run after edits finished; `Ok` holds its combined output.
+    EditCheckFinished { result: Result<String, String> },
+    /// `/test` finished; `Ok` holds the exit code and output of `command`.
+    TestRunFinished {
+        command: String,
+        result: Result<crate::app::test_run::CommandOutcome, String>,
+    },
     /// Startup Claude Code status check detected degraded/outage conditions.
     ServiceStatus { severity: ServiceStatusSeverity, message: String },
     /// /login completed via `claude auth login` -- credentials stored, ready to start a session.
     AuthCompleted { conn: Rc<crate::agent::client::AgentConnection> },
+    /// A line printed by the `claude auth login` process behind the /login dialog.
+    LoginOutput(String),
+    /// The /login dialog's `claude auth login` process failed or timed out.
+    LoginFailed(String),
     /// /logout completed via `claude auth logout`.
     LogoutCompleted,
     /// Status snapshot received from bridge (account info).
     StatusSnapshotReceived { account: crate::agent::types::AccountInfo },
+    /// Bridge answered a heartbeat ping.
+    Pong { nonce: u64 },
+    /// Bridge sent an event this build could not decode.
+    UnhandledEvent { kind: String, reason: String, raw: String },
     /// MCP server snapshot received from bridge.
     McpSnapshotReceived {
         servers: Vec<crate::agent::types::McpServerStatus>,
@@ -0,0 +1,425 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built-in mock agent for `--mock`. It answers bridge commands in-process
+//! with a fixed script: streamed thinking and text, a read, a shell command
+//! and an edit that waits on a permission prompt. Every run produces the same
+//! transcript, so demos, screenshots and end-to-end tests work without Claude
+//! Code or Node.js installed.
+
+use crate::agent::types;
+use crate::agent::wire::{BRIDGE_PROTOCOL_VERSION, BridgeCommand, BridgeEvent, EventEnvelope};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+const SESSION_ID: &str = "mock-session";
+const MODEL_NAME: &str = "mock-model";
+
+/// Pause between streamed text chunks.
+const CHUNK_DELAY: Duration = Duration::from_millis(40);
+
+/// Option ids offered on the edit's permission prompt.
+const ALLOW_OPTION: &str = "allow";
+const REJECT_OPTION: &str = "reject";
+
+const DEMO_FILE: &str = "src/greeting.rs";
+const DEMO_OLD: &str = "pub fn greeting() -> &'static str {\n    \"hello\"\n}\n";
+const DEMO_NEW: &str = "pub fn greeting() -> &'static str {\n    \"hello, world\"\n}\n";
+
+#[derive(Debug, Default)]
+pub struct MockAgent {
+    cwd: String,
+    /// Events still to send, each after its delay.
+    queue: VecDeque<(Duration, BridgeEvent)>,
+    /// Tool call whose permission prompt holds the rest of the turn back.
+    awaiting_permission: Option<String>,
+    turns: u64,
+    closed: bool,
+}
+
+impl MockAgent {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// React to a command by scheduling the events a real bridge would send.
+    pub fn handle(&mut self, command: BridgeCommand) {
+        match command {
+            BridgeCommand::Initialize { cwd, .. } => {
+                self.cwd = cwd;
+                self.push(Duration::ZERO, BridgeEvent::Initialized { result: initialize_result() });
+            }
+            BridgeCommand::CreateSession { .. } | BridgeCommand::ResumeSession { .. } => {
+                self.push(Duration::from_millis(150), self.connected(false));
+            }
+            BridgeCommand::NewSession { .. } => {
+                self.queue.clear();
+                self.awaiting_permission = None;
+                self.push(Duration::from_millis(150), self.connected(true));
+            }
+            BridgeCommand::Prompt { chunks, .. } => {
+                self.turns += 1;
+                let prompt = prompt_text(&chunks);
+                let steps = opening_steps(self.turns, &prompt);
+                self.queue.extend(steps);
+                self.awaiting_permission = Some(tool_id(self.turns, "edit"));
+            }
+            BridgeCommand::PermissionResponse { tool_call_id, outcome, .. } => {
+                if self.awaiting_permission.as_deref() != Some(tool_call_id.as_str()) {
+                    return;
+                }
+                self.awaiting_permission = None;
+                let allowed = matches!(
+                    outcome,
+                    types::PermissionOutcome::Selected { ref option_id } if option_id == ALLOW_OPTION
+                );
+                self.queue.extend(closing_steps(&tool_call_id, allowed));
+            }
+            BridgeCommand::CancelTurn { .. } => {
+                self.queue.clear();
+                self.awaiting_permission = None;
+                self.push(Duration::ZERO, turn_complete());
+            }
+            BridgeCommand::Ping { nonce } => {
+                // Answer ahead of any scripted output, like the real bridge.
+                self.queue.push_front((Duration::ZERO, BridgeEvent::Pong { nonce }));
+            }
+            BridgeCommand::Shutdown => self.closed = true,
+            other => tracing::debug!(?other, "mock agent ignored command"),
+        }
+    }
+
+    /// Next scripted event once its delay has passed; `None` after shutdown.
+    /// Waits forever while nothing is scheduled. Cancel-safe: an interrupted
+    /// wait leaves the event queued.
+    pub async fn next_event(&mut self) -> Option<EventEnvelope> {
+        if self.closed {
+            return None;
+        }
+        let Some(&(delay, _)) = self.queue.front() else {
+            return std::future::pending().await;
+        };
+        tokio::time::sleep(delay).await;
+        let (_, event) = self.queue.pop_front()?;
+        Some(EventEnvelope { request_id: None, event })
+    }
+
+    fn push(&mut self, delay: Duration, event: BridgeEvent) {
+        self.queue.push_back((delay, event));
+    }
+
+    fn connected(&self, replaced: bool) -> BridgeEvent {
+        let session_id = SESSION_ID.to_owned();
+        let cwd = self.cwd.clone();
+        let model_name = MODEL_NAME.to_owned();
+        if replaced {
+            BridgeEvent::SessionReplaced {
+                session_id,
+                cwd,
+                model_name,
+                available_models: Vec::new(),
+                mode: None,
+                history_updates: None,
+            }
+        } else {
+            BridgeEvent::Connected {
+                session_id,
+                cwd,
+                model_name,
+                available_models: Vec::new(),
+                mode: None,
+                history_updates: None,
+            }
+        }
+    }
+}
+
+fn initialize_result() -> types::InitializeResult {
+    types::InitializeResult {
+        agent_name: "mock-agent".to_owned(),
+        agent_version: env!("CARGO_PKG_VERSION").to_owned(),
+        auth_methods: Vec::new(),
+        capabilities: types::AgentCapabilities {
+            prompt_image: false,
+            prompt_embedded_context: false,
+            supports_session_listing: false,
+            supports_resume_session: true,
+            supports_modes: false,
+            supports_plan_updates: false,
+        },
+        protocol_version: Some(BRIDGE_PROTOCOL_VERSION),
+    }
+}
+
+/// First line of the prompt's text, for echoing back.
+fn prompt_text(chunks: &[types::PromptChunk]) -> String {
+    let text = chunks
+        .iter()
+        .filter(|chunk| chunk.kind == "text")
+        .filter_map(|chunk| chunk.value.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    text.lines().next().unwrap_or_default().trim().to_owned()
+}
+
+fn tool_id(turn: u64, step: &str) -> String {
+    format!("mock-{turn}-{step}")
+}
+
+fn opening_steps(turn: u64, prompt: &str) -> Vec<(Duration, BridgeEvent)> {
+    let read_id = tool_id(turn, "read");
+    let run_id = tool_id(turn, "run");
+    let edit_id = tool_id(turn, "edit");
+    let mut steps =
+        vec![(Duration::from_millis(250), thought("Planning a short scripted turn for the demo."))];
+    steps.extend(stream_text(&format!(
+        "Mock turn {turn}. You asked: \"{prompt}\"\n\n\
+         I will read a file, run the tests, and then propose a small edit.\n\n"
+    )));
+    steps.push((
+        Duration::from_millis(200),
+        tool_call(&read_id, &format!("Read {DEMO_FILE}"), "read", "in_progress", None),
+    ));
+    steps.push((
+        Duration::from_millis(400),
+        tool_update(&read_id, "completed", vec![text_content(DEMO_OLD)]),
+    ));
+    steps.push((
+        Duration::from_millis(150),
+        tool_call(
+            &run_id,
+            "cargo test",
+            "execute",
+            "in_progress",
+            Some(serde_json::json!({ "command": "cargo test" })),
+        ),
+    ));
+    steps.push((
+        Duration::from_millis(900),
+        tool_update(
+            &run_id,
+            "completed",
+            vec![text_content(
+                "running 1 test\ntest greeting_is_friendly ... ok\n\n\
+                 test result: ok. 1 passed; 0 failed",
+            )],
+        ),
+    ));
+    steps.push((
+        Duration::from_millis(200),
+        tool_call(&edit_id, &format!("Edit {DEMO_FILE}"), "edit", "pending", None),
+    ));
+    steps.push((
+        Duration::from_millis(100),
+        BridgeEvent::PermissionRequest {
+            session_id: SESSION_ID.to_owned(),
+            request: types::PermissionRequest {
+                tool_call: tool_call_body(
+                    &edit_id,
+                    &format!("Edit {DEMO_FILE}"),
+                    "edit",
+                    "pending",
+                    None,
+                ),
+                options: vec![
+                    permission_option(ALLOW_OPTION, "Allow", "allow_once"),
+                    permission_option(REJECT_OPTION, "Reject", "reject_once"),
+                ],
+            },
+        },
+    ));
+    steps
+}
+
+fn closing_steps(edit_id: &str, allowed: bool) -> Vec<(Duration, BridgeEvent)> {
+    let mut steps = Vec::new();
+    if allowed {
+        steps.push((
+            Duration::from_millis(300),
+            tool_update(
+                edit_id,
+                "completed",
+                vec![types::ToolCallContent::Diff {
+                    old_path: DEMO_FILE.to_owned(),
+                    new_path: DEMO_FILE.to_owned(),
+                    old: DEMO_OLD.to_owned(),
+                    new: DEMO_NEW.to_owned(),
+                    repository: None,
+                }],
+            ),
+        ));
+        steps.extend(stream_text("The greeting now says hello to the whole world."));
+    } else {
+        steps.push((
+            Duration::from_millis(100),
+            tool_update(edit_id, "failed", vec![text_content("Permission denied by the user.")]),
+        ));
+        steps.extend(stream_text("Understood, I left the file unchanged."));
+    }
+    steps.push((Duration::from_millis(200), turn_complete()));
+    steps
+}
+
+/// Split text into word-sized chunks so it streams like a model reply.
+fn stream_text(text: &str) -> Vec<(Duration, BridgeEvent)> {
+    text.split_inclusive(' ')
+        .map(|word| {
+            (
+                CHUNK_DELAY,
+                session_update(types::SessionUpdate::AgentMessageChunk {
+                    content: types::ContentBlock::Text { text: word.to_owned() },
+                }),
+            )
+        })
+        .collect()
+}
+
+fn thought(text: &str) -> BridgeEvent {
+    session_update(types::SessionUpdate::AgentThoughtChunk {
+        content: types::ContentBlock::Text { text: text.to_owned() },
+    })
+}
+
+fn text_content(text: &str) -> types::ToolCallContent {
+    types::ToolCallContent::Content { content: types::ContentBlock::Text { text: text.to_owned() } }
+}
+
+fn tool_call_body(
+    id: &str,
+    title: &str,
+    kind: &str,
+    status: &str,
+    raw_input: Option<serde_json::Value>,
+) -> types::ToolCall {
+    types::ToolCall {
+        tool_call_id: id.to_owned(),
+        title: title.to_owned(),
+        kind: kind.to_owned(),
+        status: status.to_owned(),
+        content: Vec::new(),
+        raw_input,
+        raw_output: None,
+        output_metadata: None,
+        locations: if kind == "execute" {
+            Vec::new()
+        } else {
+            vec![types::ToolLocation { path: DEMO_FILE.to_owned(), line: None }]
+        },
+        meta: None,
+    }
+}
+
+fn tool_call(
+    id: &str,
+    title: &str,
+    kind: &str,
+    status: &str,
+    raw_input: Option<serde_json::Value>,
+) -> BridgeEvent {
+    session_update(types::SessionUpdate::ToolCall {
+        tool_call: tool_call_body(id, title, kind, status, raw_input),
+    })
+}
+
+fn tool_update(id: &str, status: &str, content: Vec<types::ToolCallContent>) -> BridgeEvent {
+    session_update(types::SessionUpdate::ToolCallUpdate {
+        tool_call_update: types::ToolCallUpdate {
+            tool_call_id: id.to_owned(),
+            fields: types::ToolCallUpdateFields {
+                status: Some(status.to_owned()),
+                content: Some(content),
+                ..types::ToolCallUpdateFields::default()
+            },
+        },
+    })
+}
+
+fn permission_option(id: &str, name: &str, kind: &str) -> types::PermissionOption {
+    types::PermissionOption {
+        option_id: id.to_owned(),
+        name: name.to_owned(),
+        description: None,
+        kind: kind.to_owned(),
+    }
+}
+
+fn session_update(update: types::SessionUpdate) -> BridgeEvent {
+    BridgeEvent::SessionUpdate { session_id: SESSION_ID.to_owned(), update }
+}
+
+fn turn_complete() -> BridgeEvent {
+    BridgeEvent::TurnComplete { session_id: SESSION_ID.to_owned() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn drain(agent: &mut MockAgent) -> Vec<BridgeEvent> {
+        agent.queue.drain(..).map(|(_, event)| event).collect()
+    }
+
+    fn prompt(agent: &mut MockAgent, text: &str) {
+        agent.handle(BridgeCommand::Prompt {
+            session_id: SESSION_ID.to_owned(),
+            chunks: vec![types::PromptChunk {
+                kind: "text".to_owned(),
+                value: serde_json::Value::String(text.to_owned()),
+            }],
+        });
+    }
+
+    #[test]
+    fn turn_pauses_on_the_permission_prompt_until_answered() {
+        let mut agent = MockAgent::new();
+        agent.handle(BridgeCommand::Initialize {
+            cwd: "/work".to_owned(),
+            metadata: std::collections::BTreeMap::new(),
+        });
+        assert!(matches!(drain(&mut agent).as_slice(), [BridgeEvent::Initialized { .. }]));
+
+        prompt(&mut agent, "fix the greeting\nsecond line");
+        let opening = drain(&mut agent);
+        assert!(matches!(opening.last(), Some(BridgeEvent::PermissionRequest { .. })));
+        assert!(!opening.iter().any(|event| matches!(event, BridgeEvent::TurnComplete { .. })));
+        let echoed: String = opening
+            .iter()
+            .filter_map(|event| match event {
+                BridgeEvent::SessionUpdate {
+                    update:
+                        types::SessionUpdate::AgentMessageChunk {
+                            content: types::ContentBlock::Text { text },
+                        },
+                    ..
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(echoed.starts_with("Mock turn 1. You asked: \"fix the greeting\""));
+
+        // A response for some other tool call does not resume the turn.
+        agent.handle(BridgeCommand::PermissionResponse {
+            session_id: SESSION_ID.to_owned(),
+            tool_call_id: "other".to_owned(),
+            outcome: types::PermissionOutcome::Cancelled,
+        });
+        assert_eq!(agent.queue.len(), 0);
+
+        agent.handle(BridgeCommand::PermissionResponse {
+            session_id: SESSION_ID.to_owned(),
+            tool_call_id: tool_id(1, "edit"),
+            outcome: types::PermissionOutcome::Selected { option_id: REJECT_OPTION.to_owned() },
+        });
+        let closing = drain(&mut agent);
+        assert!(matches!(closing.last(), Some(BridgeEvent::TurnComplete { .. })));
+        assert!(matches!(
+            &closing[0],
+            BridgeEvent::SessionUpdate {
+                update: types::SessionUpdate::ToolCallUpdate { tool_call_update },
+                ..
+            } if tool_call_update.fields.status.as_deref() == Some("failed")
+        ));
+    }
+}
@@ -4,7 +4,10 @@
 pub mod bridge;
 pub mod client;
 pub mod error_handling;
+pub mod event_tee;
 pub mod events;
+pub mod mock;
 pub mod model;
+pub mod stderr_log;
 pub mod types;
 pub mod wire;
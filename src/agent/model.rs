@@ -787,6 +787,20 @@ pub struct RateLimitUpdate {
     pub surpassed_threshold: Option<f64>,
 }
 
+/// Token accounting reported by the agent. Prompt-side fields describe the
+/// latest main-agent request; the rest are turn or session totals.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct UsageUpdate {
+    pub input_tokens: Option<u64>,
+    pub cache_read_tokens: Option<u64>,
+    pub cache_write_tokens: Option<u64>,
+    /// Output tokens of the finished turn.
+    pub output_tokens: Option<u64>,
+    pub context_window: Option<u64>,
+    /// Cumulative session cost.
+    pub total_cost_usd: Option<f64>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SessionStatus {
@@ -807,6 +821,42 @@ pub struct CompactionBoundary {
     pub pre_tokens: u64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookOutcome {
+    Success,
+    Error,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookDecision {
+    /// The hook stopped the tool call or turn.
+    Block,
+    /// A `PreToolUse` hook denied the tool call.
+    Deny,
+    /// A `PreToolUse` hook sent the tool call to the permission prompt.
+    Ask,
+    Allow,
+}
+
+/// A Claude Code hook from the user's or project's settings ran inside the agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookResult {
+    /// `PreToolUse`, `PostToolUse`, `Stop`, ...
+    pub hook_event: String,
+    /// Event plus matcher, e.g. `PreToolUse:Bash`.
+    pub hook_name: String,
+    pub outcome: HookOutcome,
+    pub exit_code: Option<i64>,
+    pub decision: Option<HookDecision>,
+    pub reason: Option<String>,
+    /// The hook rewrote the tool input.
+    pub input_modified: bool,
+    pub system_message: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SessionUpdate {
     AgentMessageChunk(ContentChunk),
@@ -822,8 +872,10 @@ pub enum SessionUpdate {
     ConfigOptionUpdate(ConfigOptionUpdate),
     FastModeUpdate(FastModeState),
     RateLimitUpdate(RateLimitUpdate),
+    UsageUpdate(UsageUpdate),
     SessionStatusUpdate(SessionStatus),
     CompactionBoundary(CompactionBoundary),
+    HookResult(HookResult),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -0,0 +1,122 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rolling capture of the agent subprocess's stderr.
+//!
+//! Every line is still forwarded to tracing; the buffer keeps the most recent
+//! lines in memory so the `/diagnostics` overlay can show connection and auth
+//! failures without rerunning the app with stderr redirected.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Lines kept in memory before the oldest are dropped.
+pub const MAX_STDERR_LINES: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StderrLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl StderrLevel {
+    /// Classify a stderr line. The bridge uses a structured `[sdk <level>]`
+    /// prefix; other agents usually start lines with the level name.
+    #[must_use]
+    pub fn classify(line: &str) -> Self {
+        let lower = line.trim_start().to_ascii_lowercase();
+        if lower.contains("[sdk error]") || lower.starts_with("error") || lower.contains("panic") {
+            Self::Error
+        } else if lower.contains("[sdk warn]") || lower.starts_with("warn") {
+            Self::Warn
+        } else if lower.contains("[sdk info]") || lower.starts_with("info") {
+            Self::Info
+        } else {
+            Self::Debug
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StderrLine {
+    pub level: StderrLevel,
+    pub text: String,
+}
+
+#[derive(Debug, Default)]
+pub struct StderrBuffer {
+    lines: VecDeque<StderrLine>,
+    dropped: usize,
+}
+
+impl StderrBuffer {
+    pub fn push(&mut self, level: StderrLevel, text: impl Into<String>) {
+        if self.lines.len() == MAX_STDERR_LINES {
+            self.lines.pop_front();
+            self.dropped += 1;
+        }
+        self.lines.push_back(StderrLine { level, text: text.into() });
+    }
+
+    pub fn lines(&self) -> impl ExactSizeIterator<Item = &StderrLine> {
+        self.lines.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Lines evicted since the buffer was created or last cleared.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.dropped = 0;
+    }
+}
+
+/// Shared between the stderr reader task and the UI.
+pub type SharedStderrBuffer = Rc<RefCell<StderrBuffer>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn classify_recognizes_bridge_prefixes_and_plain_levels() {
+        assert_eq!(StderrLevel::classify("[sdk error] auth failed"), StderrLevel::Error);
+        assert_eq!(StderrLevel::classify("thread 'main' panicked"), StderrLevel::Error);
+        assert_eq!(StderrLevel::classify("WARN retrying"), StderrLevel::Warn);
+        assert_eq!(StderrLevel::classify("[sdk info] session created"), StderrLevel::Info);
+        assert_eq!(StderrLevel::classify("loading config"), StderrLevel::Debug);
+    }
+
+    #[test]
+    fn buffer_evicts_oldest_lines_past_capacity() {
+        let mut buffer = StderrBuffer::default();
+        for i in 0..MAX_STDERR_LINES + 3 {
+            buffer.push(StderrLevel::Debug, format!("line {i}"));
+        }
+
+        assert_eq!(buffer.len(), MAX_STDERR_LINES);
+        assert_eq!(buffer.dropped(), 3);
+        assert_eq!(buffer.lines().next().map(|line| line.text.as_str()), Some("line 3"));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.dropped(), 0);
+    }
+}
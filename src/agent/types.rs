@@ -96,6 +96,23 @@ pub enum CompactionTrigger {
     Auto,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookOutcome {
+    Success,
+    Error,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookDecision {
+    Block,
+    Deny,
+    Ask,
+    Allow,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
@@ -245,6 +262,14 @@ pub enum SessionUpdate {
         is_using_overage: Option<bool>,
         surpassed_threshold: Option<f64>,
     },
+    UsageUpdate {
+        input_tokens: Option<u64>,
+        cache_read_tokens: Option<u64>,
+        cache_write_tokens: Option<u64>,
+        output_tokens: Option<u64>,
+        context_window: Option<u64>,
+        total_cost_usd: Option<f64>,
+    },
     SessionStatusUpdate {
         status: SessionStatus,
     },
@@ -252,6 +277,17 @@ pub enum SessionUpdate {
         trigger: CompactionTrigger,
         pre_tokens: u64,
     },
+    HookResult {
+        hook_event: String,
+        hook_name: String,
+        outcome: HookOutcome,
+        exit_code: Option<i64>,
+        decision: Option<HookDecision>,
+        reason: Option<String>,
+        #[serde(default)]
+        input_modified: bool,
+        system_message: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -365,21 +401,33 @@ pub struct AuthMethod {
     pub description: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Missing fields default to `false` so agents that advertise less than the
+/// Claude bridge still initialize.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct AgentCapabilities {
     pub prompt_image: bool,
     pub prompt_embedded_context: bool,
     pub supports_session_listing: bool,
     pub supports_resume_session: bool,
+    /// Permission modes (`/mode`, Shift+Tab cycling).
+    pub supports_modes: bool,
+    /// `plan` session updates feeding the todo panel.
+    pub supports_plan_updates: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InitializeResult {
     pub agent_name: String,
     pub agent_version: String,
+    #[serde(default)]
     pub auth_methods: Vec<AuthMethod>,
+    #[serde(default)]
     pub capabilities: AgentCapabilities,
+    /// Bridge protocol revision; absent on bridges that predate negotiation.
+    #[serde(default)]
+    pub protocol_version: Option<u32>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5,6 +5,36 @@ use crate::agent::types;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+/// Bridge protocol revision this build speaks. Bump it whenever commands or
+/// events change shape in a way older peers cannot ignore.
+pub const BRIDGE_PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest bridge protocol revision this build still understands.
+pub const MIN_BRIDGE_PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolCompatibility {
+    Supported,
+    /// The bridge predates [`MIN_BRIDGE_PROTOCOL_VERSION`].
+    TooOld(u32),
+    /// The bridge is newer than [`BRIDGE_PROTOCOL_VERSION`].
+    TooNew(u32),
+}
+
+/// Compare the revision a bridge reported at initialize with ours. Bridges
+/// that report none predate negotiation and count as revision 0.
+#[must_use]
+pub fn protocol_compatibility(reported: Option<u32>) -> ProtocolCompatibility {
+    let version = reported.unwrap_or(0);
+    if version < MIN_BRIDGE_PROTOCOL_VERSION {
+        ProtocolCompatibility::TooOld(version)
+    } else if version > BRIDGE_PROTOCOL_VERSION {
+        ProtocolCompatibility::TooNew(version)
+    } else {
+        ProtocolCompatibility::Supported
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SessionLaunchSettings {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -13,6 +43,16 @@ pub struct SessionLaunchSettings {
     pub settings: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent_progress_summaries: Option<bool>,
+    /// Extra roots the agent may read and edit besides the session cwd.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_directories: Vec<String>,
+    /// Variables added to the environment of agent-run commands.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_thinking_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
 }
 
 impl SessionLaunchSettings {
@@ -21,6 +61,10 @@ impl SessionLaunchSettings {
         self.language.is_none()
             && self.settings.is_none()
             && self.agent_progress_summaries.is_none()
+            && self.additional_directories.is_empty()
+            && self.env.is_empty()
+            && self.max_thinking_tokens.is_none()
+            && self.max_turns.is_none()
     }
 }
 
@@ -133,6 +177,10 @@ pub enum BridgeCommand {
         server_name: String,
         callback_url: String,
     },
+    /// Liveness probe; answered with [`BridgeEvent::Pong`] carrying the same nonce.
+    Ping {
+        nonce: u64,
+    },
     Shutdown,
 }
 
@@ -154,6 +202,7 @@ pub enum BridgeEvent {
         #[serde(default)]
         available_models: Vec<types::AvailableModel>,
         mode: Option<types::ModeState>,
+        #[serde(default, deserialize_with = "deserialize_known_updates")]
         history_updates: Option<Vec<types::SessionUpdate>>,
     },
     AuthRequired {
@@ -213,6 +262,7 @@ pub enum BridgeEvent {
         #[serde(default)]
         available_models: Vec<types::AvailableModel>,
         mode: Option<types::ModeState>,
+        #[serde(default, deserialize_with = "deserialize_known_updates")]
         history_updates: Option<Vec<types::SessionUpdate>>,
     },
     Initialized {
@@ -231,6 +281,137 @@ pub enum BridgeEvent {
         servers: Vec<types::McpServerStatus>,
         error: Option<String>,
     },
+    Pong {
+        nonce: u64,
+    },
+    /// A line from the bridge this build could not decode, usually an event
+    /// or session update added by a newer bridge. Never sent on the wire.
+    #[serde(skip)]
+    Unrecognized {
+        /// Event tag, plus the update type for session updates.
+        kind: String,
+        reason: String,
+        /// The line as pretty-printed JSON, or verbatim when it is not JSON.
+        raw: String,
+    },
+}
+
+impl BridgeEvent {
+    /// Wire tag of the event, for logs.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Connected { .. } => "connected",
+            Self::AuthRequired { .. } => "auth_required",
+            Self::ConnectionFailed { .. } => "connection_failed",
+            Self::SessionUpdate { .. } => "session_update",
+            Self::PermissionRequest { .. } => "permission_request",
+            Self::QuestionRequest { .. } => "question_request",
+            Self::ElicitationRequest { .. } => "elicitation_request",
+            Self::ElicitationComplete { .. } => "elicitation_complete",
+            Self::McpAuthRedirect { .. } => "mcp_auth_redirect",
+            Self::McpOperationError { .. } => "mcp_operation_error",
+            Self::TurnComplete { .. } => "turn_complete",
+            Self::TurnError { .. } => "turn_error",
+            Self::SlashError { .. } => "slash_error",
+            Self::SessionReplaced { .. } => "session_replaced",
+            Self::Initialized { .. } => "initialized",
+            Self::SessionsListed { .. } => "sessions_listed",
+            Self::StatusSnapshot { .. } => "status_snapshot",
+            Self::McpSnapshot { .. } => "mcp_snapshot",
+            Self::Pong { .. } => "pong",
+            Self::Unrecognized { .. } => "unrecognized",
+        }
+    }
+
+    /// Session the event belongs to, used as the log correlation id.
+    #[must_use]
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Connected { session_id, .. }
+            | Self::SessionUpdate { session_id, .. }
+            | Self::PermissionRequest { session_id, .. }
+            | Self::QuestionRequest { session_id, .. }
+            | Self::ElicitationRequest { session_id, .. }
+            | Self::ElicitationComplete { session_id, .. }
+            | Self::McpAuthRedirect { session_id, .. }
+            | Self::McpOperationError { session_id, .. }
+            | Self::TurnComplete { session_id }
+            | Self::TurnError { session_id, .. }
+            | Self::SlashError { session_id, .. }
+            | Self::SessionReplaced { session_id, .. }
+            | Self::StatusSnapshot { session_id, .. }
+            | Self::McpSnapshot { session_id, .. } => Some(session_id),
+            Self::AuthRequired { .. }
+            | Self::ConnectionFailed { .. }
+            | Self::Initialized { .. }
+            | Self::SessionsListed { .. }
+            | Self::Pong { .. }
+            | Self::Unrecognized { .. } => None,
+        }
+    }
+}
+
+impl EventEnvelope {
+    /// Decode one line of bridge stdout. Lines this build does not understand
+    /// come back as [`BridgeEvent::Unrecognized`]; decoding never fails.
+    #[must_use]
+    pub fn decode(line: &str) -> Self {
+        match serde_json::from_str(line) {
+            Ok(event) => event,
+            Err(err) => {
+                tracing::warn!("unrecognized bridge event: {err}");
+                Self::unrecognized(line, &err)
+            }
+        }
+    }
+
+    /// Wrap a bridge line that failed to decode so it can be surfaced instead
+    /// of tearing down the connection.
+    #[must_use]
+    pub fn unrecognized(line: &str, error: &serde_json::Error) -> Self {
+        let value = serde_json::from_str::<serde_json::Value>(line).ok();
+        let kind = value.as_ref().map_or_else(|| "(not JSON)".to_owned(), unrecognized_kind);
+        let raw = value
+            .as_ref()
+            .and_then(|value| serde_json::to_string_pretty(value).ok())
+            .unwrap_or_else(|| line.to_owned());
+        Self {
+            request_id: None,
+            event: BridgeEvent::Unrecognized { kind, reason: error.to_string(), raw },
+        }
+    }
+}
+
+fn unrecognized_kind(value: &serde_json::Value) -> String {
+    let event = value.get("event").and_then(serde_json::Value::as_str).unwrap_or("(untagged)");
+    match value.pointer("/update/type").and_then(serde_json::Value::as_str) {
+        Some(update) if event == "session_update" => format!("{event}/{update}"),
+        _ => event.to_owned(),
+    }
+}
+
+/// Replayed history with the updates this build cannot decode left out, so
+/// one entry from a newer bridge does not fail the whole session load.
+fn deserialize_known_updates<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<types::SessionUpdate>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = Option::<Vec<serde_json::Value>>::deserialize(deserializer)?;
+    Ok(raw.map(|values| {
+        values
+            .into_iter()
+            .filter_map(|value| match serde_json::from_value(value) {
+                Ok(update) => Some(update),
+                Err(err) => {
+                    tracing::warn!("skipping unrecognized history update: {err}");
+                    None
+                }
+            })
+            .collect()
+    }))
 }
 
 #[cfg(test)]
@@ -240,6 +421,27 @@ mod tests {
     };
     use crate::agent::types;
 
+    #[test]
+    fn unknown_session_update_is_kept_as_raw_json() {
+        let line = r#"{"event":"session_update","session_id":"s1","update":{"type":"hologram","beams":3}}"#;
+        let err = serde_json::from_str::<EventEnvelope>(line).expect_err("unknown update");
+        let BridgeEvent::Unrecognized { kind, raw, .. } =
+            EventEnvelope::unrecognized(line, &err).event
+        else {
+            panic!("expected unrecognized event");
+        };
+        assert_eq!(kind, "session_update/hologram");
+        assert!(raw.contains("\"beams\": 3"));
+
+        let connected = r#"{"event":"connected","session_id":"s1","cwd":"/","model_name":"m","mode":null,
+            "history_updates":[{"type":"hologram"},{"type":"current_mode_update","current_mode_id":"plan"}]}"#;
+        let decoded: EventEnvelope = serde_json::from_str(connected).expect("tolerant history");
+        let BridgeEvent::Connected { history_updates, .. } = decoded.event else {
+            panic!("expected connected event");
+        };
+        assert_eq!(history_updates.map(|updates| updates.len()), Some(1));
+    }
+
     #[test]
     fn command_envelope_roundtrip_json() {
         let env = CommandEnvelope {
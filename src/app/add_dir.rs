@@ -0,0 +1,150 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/add-dir <path>`: add a working directory besides the session cwd, e.g. a
+//! sibling package in a monorepo. Extra roots are indexed for `@` mentions,
+//! listed in the header and passed to the agent as additional directories;
+//! the current session is reloaded so the agent can read and edit them right
+//! away. `/add-dir` alone lists the roots.
+
+use super::connect::reload_session;
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Roots at most this many levels above the cwd's ancestors are mentioned
+/// with a `../` path; farther ones with their absolute path.
+const MAX_PARENT_HOPS: usize = 3;
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    let path = match args {
+        [] => {
+            list_roots(app);
+            return Ok(());
+        }
+        [path] => *path,
+        _ => return Err("Usage: /add-dir [path]".to_owned()),
+    };
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /add-dir.".to_owned());
+    }
+    let cwd = Path::new(&app.cwd_raw);
+    let dir = resolve(cwd, path)?;
+    let cwd = std::fs::canonicalize(cwd).unwrap_or_else(|_| cwd.to_path_buf());
+    if dir.starts_with(&cwd) {
+        return Err(format!("{path} is already inside the session directory."));
+    }
+    let dir = dir.to_string_lossy().into_owned();
+    if app.additional_dirs.contains(&dir) {
+        return Err(format!("{dir} is already a working directory."));
+    }
+
+    app.additional_dirs.push(dir.clone());
+    app.cached_header_line = None;
+    let note = format!("Added working directory {dir}.");
+    if !reload_session(app, format!("Adding {dir}..."), note.clone()) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("{note} The agent can use it from the next session."),
+        );
+    }
+    Ok(())
+}
+
+/// Resolve a user-typed directory (relative to `cwd`, or `~/...`) to its
+/// canonical path.
+pub(super) fn resolve(cwd: &Path, path: &str) -> Result<PathBuf, String> {
+    let joined = match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().ok_or("Cannot find the home directory.")?.join(rest),
+        None => cwd.join(path),
+    };
+    let dir = std::fs::canonicalize(&joined).map_err(|err| format!("Cannot add {path}: {err}"))?;
+    if !dir.is_dir() {
+        return Err(format!("{path} is not a directory."));
+    }
+    Ok(dir)
+}
+
+fn list_roots(app: &mut App) {
+    let mut message = format!("Working directories:\n- {} (session)", app.cwd_raw);
+    for dir in &app.additional_dirs {
+        let _ = write!(message, "\n- {dir}");
+    }
+    if app.additional_dirs.is_empty() {
+        message.push_str("\n\nUse /add-dir <path> to add another one.");
+    }
+    push_system_message_with_severity(app, Some(SystemSeverity::Info), &message);
+}
+
+/// How `@` mentions spell paths under the extra `root`: relative to `cwd`
+/// through `../` when the two are close, absolute otherwise. Ends with `/`.
+pub(crate) fn mention_prefix(cwd: &Path, root: &Path) -> String {
+    let mut base = cwd;
+    for hops in 0..=MAX_PARENT_HOPS {
+        if let Ok(rest) = root.strip_prefix(base) {
+            let mut prefix = "../".repeat(hops);
+            prefix.push_str(&rest.to_string_lossy().replace('\\', "/"));
+            prefix.push('/');
+            return prefix;
+        }
+        let Some(parent) = base.parent() else {
+            break;
+        };
+        base = parent;
+    }
+    format!("{}/", root.to_string_lossy().replace('\\', "/").trim_end_matches('/'))
+}
+
+/// Last path component of each extra root, for the header.
+#[must_use]
+pub fn root_labels(app: &App) -> Vec<String> {
+    app.additional_dirs
+        .iter()
+        .map(|dir| {
+            Path::new(dir)
+                .file_name()
+                .map_or_else(|| dir.clone(), |name| name.to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn mention_prefix_walks_up_to_close_roots() {
+        let cwd = Path::new("/repo/packages/web");
+
+        assert_eq!(mention_prefix(cwd, Path::new("/repo/packages/api")), "../api/");
+        assert_eq!(mention_prefix(cwd, Path::new("/repo/shared/types")), "../../shared/types/");
+        assert_eq!(mention_prefix(Path::new("/a/b/c/d/e"), Path::new("/x/y")), "/x/y/".to_owned());
+    }
+
+    #[test]
+    fn add_dir_rejects_cwd_subdirectories_and_duplicates() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let cwd = tmp.path().join("web");
+        let api = tmp.path().join("api");
+        std::fs::create_dir_all(cwd.join("src")).expect("create web");
+        std::fs::create_dir_all(&api).expect("create api");
+        let mut app = App::test_default();
+        app.cwd_raw = cwd.to_string_lossy().into_owned();
+
+        assert_eq!(
+            start(&mut app, &["src"]),
+            Err("src is already inside the session directory.".to_owned())
+        );
+        assert_eq!(start(&mut app, &["../api"]), Ok(()));
+        let added = std::fs::canonicalize(&api).expect("canonical").to_string_lossy().into_owned();
+        assert_eq!(app.additional_dirs, vec![added.clone()]);
+        assert_eq!(
+            start(&mut app, &["../api"]),
+            Err(format!("{added} is already a working directory."))
+        );
+        assert_eq!(root_labels(&app), vec!["api".to_owned()]);
+    }
+}
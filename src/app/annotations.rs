@@ -0,0 +1,146 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local message annotations. Pressing `m` in keyboard selection mode
+//! (Ctrl+s) opens a one-line editor for a note on the message under the
+//! cursor, e.g. "wrong approach, see the next turn". Notes render dimmed
+//! under their message and are included in exports; they are never sent to
+//! the agent. Saving an empty note removes it. The same editor names
+//! bookmarks (`b`, see [`super::bookmarks`]).
+
+use super::line_input::LineInput;
+use super::toast::{ToastKind, show_toast};
+use super::{App, InvalidationLevel, MessageRole};
+use crossterm::event::{KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationKind {
+    Note,
+    Bookmark,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationEditor {
+    pub kind: AnnotationKind,
+    pub message_index: usize,
+    pub input: LineInput,
+}
+
+/// Open the editor for the message under the selection cursor. Returns false
+/// when there is no annotatable message there.
+pub(crate) fn edit_at_visual_cursor(app: &mut App, kind: AnnotationKind) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    let Some(message) = app.messages.get(message_index) else {
+        return false;
+    };
+    if matches!(message.role, MessageRole::Welcome) {
+        return false;
+    }
+    let draft = match kind {
+        AnnotationKind::Note => message.annotation.clone().unwrap_or_default(),
+        AnnotationKind::Bookmark => super::bookmarks::default_name(app, message_index),
+    };
+    app.annotation_editor =
+        Some(AnnotationEditor { kind, message_index, input: LineInput::new(draft) });
+    app.needs_redraw = true;
+    true
+}
+
+pub(crate) fn handle_key(app: &mut App, key: KeyEvent) {
+    let Some(editor) = app.annotation_editor.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.annotation_editor = None,
+        KeyCode::Enter => {
+            if let Some(editor) = app.annotation_editor.take() {
+                match editor.kind {
+                    AnnotationKind::Note => save(app, editor.message_index, &editor.input.text),
+                    AnnotationKind::Bookmark => {
+                        super::bookmarks::add(app, editor.message_index, &editor.input.text);
+                    }
+                }
+            }
+        }
+        _ => {
+            editor.input.handle_key(key);
+        }
+    }
+}
+
+/// Set (or with a blank `note`, remove) the annotation on a message.
+fn save(app: &mut App, message_index: usize, note: &str) {
+    let note = note.trim();
+    let Some(message) = app.messages.get_mut(message_index) else {
+        return;
+    };
+    let had_note = message.annotation.is_some();
+    message.annotation = (!note.is_empty()).then(|| note.to_owned());
+    let toast = match (had_note, note.is_empty()) {
+        (_, false) => "Note saved",
+        (true, true) => "Note removed",
+        (false, true) => return,
+    };
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    show_toast(app, ToastKind::Info, toast);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, MessageBlock, TextBlock};
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    fn type_text(app: &mut App, text: &str) {
+        for ch in text.chars() {
+            handle_key(app, KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn editor_saves_edits_and_removes_blank_notes() {
+        let mut app = App::test_default();
+        app.messages = vec![ChatMessage {
+            role: MessageRole::User,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete("try a regex"))],
+            usage: None,
+            annotation: None,
+        }];
+        app.annotation_editor = Some(AnnotationEditor {
+            kind: AnnotationKind::Note,
+            message_index: 0,
+            input: LineInput::default(),
+        });
+
+        type_text(&mut app, "wrong aproach");
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Left);
+        }
+        type_text(&mut app, "p");
+        press(&mut app, KeyCode::Enter);
+
+        assert!(app.annotation_editor.is_none());
+        assert_eq!(app.messages[0].annotation.as_deref(), Some("wrong approach"));
+
+        app.annotation_editor = Some(AnnotationEditor {
+            kind: AnnotationKind::Note,
+            message_index: 0,
+            input: LineInput::new("x".to_owned()),
+        });
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.messages[0].annotation, None);
+    }
+}
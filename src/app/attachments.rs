@@ -0,0 +1,230 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Classification of `@`-mentioned files and image attachment for prompts.
+//!
+//! Mentioned images are sent as base64 image chunks alongside the prompt text.
+//! Large and binary files stay plain `@path` references so the agent can decide
+//! how to read them instead of having content inlined.
+
+use super::mention;
+use std::io::Read;
+use std::path::Path;
+
+/// Files above this size are flagged as large in the mention dropdown.
+pub const LARGE_FILE_BYTES: u64 = 256 * 1024;
+/// Images above this size are not attached (API limit is 5 MB per image).
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+/// Bytes sniffed for NUL characters when detecting binary files.
+const BINARY_SNIFF_BYTES: u64 = 8 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MentionFileKind {
+    Text,
+    Image { mime_type: &'static str, bytes: u64 },
+    Binary { bytes: u64 },
+    Large { bytes: u64 },
+}
+
+impl MentionFileKind {
+    /// Footer notice for the mention dropdown, if the file needs one.
+    #[must_use]
+    pub fn notice(self) -> Option<String> {
+        match self {
+            Self::Text => None,
+            Self::Image { bytes, .. } if bytes > MAX_IMAGE_BYTES => Some(format!(
+                "Image too large to attach ({}), sent as a path reference",
+                format_bytes(bytes)
+            )),
+            Self::Image { .. } => Some("Image, attached to the prompt".to_owned()),
+            Self::Binary { bytes } => {
+                Some(format!("Binary file ({}), sent as a path reference", format_bytes(bytes)))
+            }
+            Self::Large { bytes } => {
+                Some(format!("Large file ({}), sent as a path reference", format_bytes(bytes)))
+            }
+        }
+    }
+}
+
+/// An image read from a mentioned file, ready to send as a prompt chunk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageAttachment {
+    pub rel_path: String,
+    pub mime_type: &'static str,
+    /// Base64-encoded file contents.
+    pub data: String,
+}
+
+#[must_use]
+pub fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Classify a mentioned path. Returns `None` for missing paths and directories.
+#[must_use]
+pub fn classify_path(path: &Path) -> Option<MentionFileKind> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    let bytes = metadata.len();
+    if let Some(mime_type) = image_mime_type(path) {
+        return Some(MentionFileKind::Image { mime_type, bytes });
+    }
+    if looks_binary(path) {
+        return Some(MentionFileKind::Binary { bytes });
+    }
+    if bytes > LARGE_FILE_BYTES {
+        return Some(MentionFileKind::Large { bytes });
+    }
+    Some(MentionFileKind::Text)
+}
+
+fn looks_binary(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::new();
+    if file.take(BINARY_SNIFF_BYTES).read_to_end(&mut head).is_err() {
+        return false;
+    }
+    head.contains(&0)
+}
+
+/// Read every image mentioned in `text` (relative to `cwd`), once per path.
+/// Oversized or unreadable images are skipped.
+#[must_use]
+pub fn collect_image_attachments(cwd: &str, text: &str) -> Vec<ImageAttachment> {
+    let mut attachments: Vec<ImageAttachment> = Vec::new();
+    for line in text.lines() {
+        for (_, _, rel_path) in mention::find_mention_spans(line) {
+            if attachments.iter().any(|attachment| attachment.rel_path == rel_path) {
+                continue;
+            }
+            let path = Path::new(cwd).join(&rel_path);
+            let Some(MentionFileKind::Image { mime_type, bytes }) = classify_path(&path) else {
+                continue;
+            };
+            if bytes > MAX_IMAGE_BYTES {
+                tracing::warn!(path = %path.display(), bytes, "mentioned image too large to attach");
+                continue;
+            }
+            match std::fs::read(&path) {
+                Ok(contents) => attachments.push(ImageAttachment {
+                    rel_path,
+                    mime_type,
+                    data: base64_encode(&contents),
+                }),
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), %err, "failed to read mentioned image");
+                }
+            }
+        }
+    }
+    attachments
+}
+
+#[must_use]
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = u32::from(chunk[0]);
+        let b1 = chunk.get(1).copied().map_or(0, u32::from);
+        let b2 = chunk.get(2).copied().map_or(0, u32::from);
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(char::from(ALPHABET[((triple >> shift) & 0x3f) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.0} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        let cases = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(base64_encode(input.as_bytes()), expected);
+        }
+    }
+
+    #[test]
+    fn classify_detects_images_binary_large_and_text() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let write = |name: &str, contents: &[u8]| {
+            let path = tmp.path().join(name);
+            std::fs::write(&path, contents).expect("write");
+            path
+        };
+
+        let image = write("shot.PNG", b"\x89PNG");
+        let binary = write("blob.bin", b"abc\0def");
+        let large = write("big.log", &vec![b'a'; 300 * 1024]);
+        let text = write("notes.md", b"hello");
+
+        assert_eq!(
+            classify_path(&image),
+            Some(MentionFileKind::Image { mime_type: "image/png", bytes: 4 })
+        );
+        assert_eq!(classify_path(&binary), Some(MentionFileKind::Binary { bytes: 7 }));
+        assert_eq!(classify_path(&large), Some(MentionFileKind::Large { bytes: 300 * 1024 }));
+        assert_eq!(classify_path(&text), Some(MentionFileKind::Text));
+        assert_eq!(classify_path(tmp.path()), None);
+    }
+
+    #[test]
+    fn collects_each_mentioned_image_once() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("a.png"), b"foo").expect("write image");
+        std::fs::write(tmp.path().join("notes.md"), b"text").expect("write text");
+        let cwd = tmp.path().to_string_lossy().into_owned();
+
+        let attachments =
+            collect_image_attachments(&cwd, "compare @a.png with @notes.md\nagain @a.png");
+
+        assert_eq!(
+            attachments,
+            vec![ImageAttachment {
+                rel_path: "a.png".to_owned(),
+                mime_type: "image/png",
+                data: "Zm9v".to_owned(),
+            }]
+        );
+    }
+}
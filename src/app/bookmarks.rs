@@ -0,0 +1,248 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Named bookmarks in long transcripts. Pressing `b` in keyboard selection
+//! mode (Ctrl+s) names a bookmark on the message under the cursor; `/bookmarks`
+//! lists them, `/bookmarks <n|name>` scrolls back to one and
+//! `/bookmarks rm <n|name>` drops it.
+//!
+//! Bookmarks are saved per session in `bookmarks.json` under the user config
+//! directory, so a resumed session keeps them. A bookmark remembers the first
+//! line of its message as well as the index, because a resumed transcript
+//! does not replay local notices and indices can shift.
+
+use super::events::push_system_message_with_severity;
+use super::toast::{ToastKind, show_toast};
+use super::{App, MessageBlock, SystemSeverity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub message_index: usize,
+    /// First non-empty line of the message, used to find it again.
+    #[serde(default)]
+    pub excerpt: String,
+}
+
+/// Bookmarks of every session, keyed by session id.
+type BookmarkFile = BTreeMap<String, Vec<Bookmark>>;
+
+fn bookmarks_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("claude-code-rust").join("bookmarks.json"))
+}
+
+/// Load the bookmarks of the active session, replacing any held for the last one.
+pub(crate) fn load_for_session(app: &mut App) {
+    app.bookmarks = match (bookmarks_path(), app.session_id.as_ref()) {
+        (Some(path), Some(id)) => read_file(&path).remove(&id.to_string()).unwrap_or_default(),
+        _ => Vec::new(),
+    };
+}
+
+fn read_file(path: &Path) -> BookmarkFile {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_session(path: &Path, session_id: &str, bookmarks: &[Bookmark]) -> Result<(), String> {
+    let mut file = read_file(path);
+    if bookmarks.is_empty() {
+        file.remove(session_id);
+    } else {
+        file.insert(session_id.to_owned(), bookmarks.to_vec());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let raw = serde_json::to_string_pretty(&file).map_err(|err| err.to_string())?;
+    std::fs::write(path, raw).map_err(|err| err.to_string())
+}
+
+fn persist(app: &App) {
+    let (Some(path), Some(id)) = (bookmarks_path(), app.session_id.as_ref()) else {
+        return;
+    };
+    if let Err(err) = write_session(&path, &id.to_string(), &app.bookmarks) {
+        tracing::warn!("failed to save bookmarks to {}: {err}", path.display());
+    }
+}
+
+/// Default bookmark name for a message: the start of its first line.
+pub(crate) fn default_name(app: &App, message_index: usize) -> String {
+    let excerpt = excerpt(app, message_index);
+    let name: String = excerpt.chars().take(40).collect();
+    if name.is_empty() { format!("Message {message_index}") } else { name }
+}
+
+/// Bookmark `message_index` as `name`, replacing a bookmark of the same name.
+pub(crate) fn add(app: &mut App, message_index: usize, name: &str) {
+    let name = name.trim();
+    let name = if name.is_empty() { default_name(app, message_index) } else { name.to_owned() };
+    let excerpt = excerpt(app, message_index);
+    app.bookmarks.retain(|bookmark| bookmark.name != name);
+    app.bookmarks.push(Bookmark { name: name.clone(), message_index, excerpt });
+    app.bookmarks.sort_by_key(|bookmark| bookmark.message_index);
+    persist(app);
+    show_toast(app, ToastKind::Success, format!("Bookmarked \"{name}\""));
+}
+
+/// Drop bookmarks on messages from `index` onward, e.g. after a rewind.
+pub(crate) fn forget_from(app: &mut App, index: usize) {
+    let before = app.bookmarks.len();
+    app.bookmarks.retain(|bookmark| bookmark.message_index < index);
+    if app.bookmarks.len() != before {
+        persist(app);
+    }
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    match args {
+        [] => {
+            let summary = summary(app);
+            push_system_message_with_severity(app, Some(SystemSeverity::Info), &summary);
+            Ok(())
+        }
+        ["rm" | "remove", query @ ..] if !query.is_empty() => {
+            let position = find(app, &query.join(" "))?;
+            let removed = app.bookmarks.remove(position);
+            persist(app);
+            show_toast(app, ToastKind::Info, format!("Removed bookmark \"{}\"", removed.name));
+            Ok(())
+        }
+        query => {
+            let position = find(app, &query.join(" "))?;
+            let bookmark = app.bookmarks[position].clone();
+            jump_to(app, &bookmark);
+            Ok(())
+        }
+    }
+}
+
+/// Position in `app.bookmarks` of the bookmark numbered or named `query`.
+fn find(app: &App, query: &str) -> Result<usize, String> {
+    if let Ok(number) = query.parse::<usize>()
+        && (1..=app.bookmarks.len()).contains(&number)
+    {
+        return Ok(number - 1);
+    }
+    app.bookmarks
+        .iter()
+        .position(|bookmark| bookmark.name == query)
+        .or_else(|| {
+            let query = query.to_lowercase();
+            app.bookmarks
+                .iter()
+                .position(|bookmark| bookmark.name.to_lowercase().starts_with(&query))
+        })
+        .ok_or_else(|| format!("No bookmark `{query}`. Run /bookmarks to list them."))
+}
+
+fn jump_to(app: &mut App, bookmark: &Bookmark) {
+    let Some(index) = resolve(app, bookmark) else {
+        show_toast(app, ToastKind::Info, "That part of the conversation is no longer loaded");
+        return;
+    };
+    app.viewport.auto_scroll = false;
+    app.viewport.restore_scroll_anchor(index, 0);
+    app.needs_redraw = true;
+}
+
+/// Current index of a bookmarked message: the message whose first line still
+/// matches closest to the saved index, else the saved index itself.
+fn resolve(app: &App, bookmark: &Bookmark) -> Option<usize> {
+    if !bookmark.excerpt.is_empty() {
+        let matched = (0..app.messages.len())
+            .filter(|&index| excerpt(app, index) == bookmark.excerpt)
+            .min_by_key(|&index| index.abs_diff(bookmark.message_index));
+        if matched.is_some() {
+            return matched;
+        }
+    }
+    (bookmark.message_index < app.messages.len()).then_some(bookmark.message_index)
+}
+
+fn excerpt(app: &App, message_index: usize) -> String {
+    app.messages
+        .get(message_index)
+        .and_then(|message| {
+            message.blocks.iter().find_map(|block| match block {
+                MessageBlock::Text(text) => {
+                    text.text.lines().map(str::trim).find(|line| !line.is_empty())
+                }
+                MessageBlock::ToolCall(tc) => Some(tc.title.trim()),
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+        .to_owned()
+}
+
+fn summary(app: &App) -> String {
+    if app.bookmarks.is_empty() {
+        return "No bookmarks yet. Press b in keyboard selection mode (Ctrl+s) to add one."
+            .to_owned();
+    }
+    let mut out = "Bookmarks (/bookmarks <n|name> jumps):".to_owned();
+    for (number, bookmark) in app.bookmarks.iter().enumerate() {
+        let _ = write!(out, "\n{}. **{}**", number + 1, bookmark.name);
+        if !bookmark.excerpt.is_empty() && bookmark.excerpt != bookmark.name {
+            let _ = write!(out, " - {}", bookmark.excerpt);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, MessageRole, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    fn message(text: &str) -> ChatMessage {
+        ChatMessage {
+            role: MessageRole::User,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn bookmarks_resolve_by_excerpt_after_indices_shift() {
+        let mut app = App::test_default();
+        app.messages = vec![message("set up the db"), message("add the parser")];
+        app.bookmarks = vec![Bookmark {
+            name: "parser".to_owned(),
+            message_index: 1,
+            excerpt: excerpt(&app, 1),
+        }];
+
+        app.messages.insert(0, message("a local notice"));
+        assert_eq!(resolve(&app, &app.bookmarks[0]), Some(2));
+        assert_eq!(find(&app, "1"), Ok(0));
+        assert_eq!(find(&app, "Pars"), Ok(0));
+        assert!(find(&app, "2").is_err());
+    }
+
+    #[test]
+    fn bookmark_file_keeps_other_sessions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("bookmarks.json");
+        let bookmark =
+            Bookmark { name: "start".to_owned(), message_index: 1, excerpt: "hi".to_owned() };
+
+        write_session(&path, "a", std::slice::from_ref(&bookmark)).expect("write a");
+        write_session(&path, "b", std::slice::from_ref(&bookmark)).expect("write b");
+        write_session(&path, "a", &[]).expect("clear a");
+
+        let file = read_file(&path);
+        assert_eq!(file.keys().collect::<Vec<_>>(), vec!["b"]);
+        assert_eq!(file["b"], vec![bookmark]);
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/cd <path>`: move to another working directory without restarting.
+//!
+//! A session's directory is fixed when the agent creates it, so the switch
+//! starts a new session in the target directory. The new transcript records
+//! where it came from and how to resume the previous session; mentions are
+//! re-indexed for the new directory once it is active. `/cd` alone shows the
+//! current directory.
+
+use super::add_dir::resolve;
+use super::connect::{SessionStartReason, start_new_session_in};
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+use crate::agent::events::ClientEvent;
+use std::fmt::Write as _;
+use std::path::Path;
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    let path = match args {
+        [] => {
+            let message = format!("Session directory: {}", app.cwd_raw);
+            push_system_message_with_severity(app, Some(SystemSeverity::Info), &message);
+            return Ok(());
+        }
+        [path] => *path,
+        _ => return Err("Usage: /cd <path>".to_owned()),
+    };
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /cd.".to_owned());
+    }
+    let cwd = Path::new(&app.cwd_raw);
+    let dir = resolve(cwd, path)?;
+    if std::fs::canonicalize(cwd).is_ok_and(|cwd| cwd == dir) {
+        return Err(format!("Already in {}.", dir.display()));
+    }
+    let conn = app.conn.clone().ok_or("Cannot change directory: not connected yet.")?;
+    let dir = dir.to_string_lossy().into_owned();
+
+    // The new directory is the session root now, not an extra one.
+    app.additional_dirs.retain(|extra| *extra != dir);
+    app.failed_command_offer = None;
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some(format!("Switching to {dir}..."));
+    app.pending_session_note = Some(switch_note(&app.cwd_raw, &dir, app.session_id.as_ref()));
+    if let Err(err) = start_new_session_in(app, &conn, dir, SessionStartReason::NewSession) {
+        app.pending_session_note = None;
+        let _ =
+            app.event_tx.send(ClientEvent::SlashCommandError(format!("Failed to run /cd: {err}")));
+    }
+    Ok(())
+}
+
+fn switch_note(from: &str, to: &str, previous: Option<&crate::agent::model::SessionId>) -> String {
+    let mut note = format!("Changed directory from {from} to {to}.");
+    if let Some(id) = previous {
+        let _ = write!(note, " The previous session continues with /resume {id}.");
+    }
+    note
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::model::SessionId;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn switch_note_links_previous_session() {
+        assert_eq!(
+            switch_note("/repo/web", "/repo/api", Some(&SessionId::from("abc-123"))),
+            "Changed directory from /repo/web to /repo/api. \
+             The previous session continues with /resume abc-123."
+        );
+        assert_eq!(switch_note("/a", "/b", None), "Changed directory from /a to /b.");
+    }
+
+    #[test]
+    fn cd_rejects_current_directory_and_needs_a_connection() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join("api")).expect("create api");
+        let mut app = App::test_default();
+        app.cwd_raw = tmp.path().to_string_lossy().into_owned();
+
+        assert!(start(&mut app, &["."]).is_err_and(|err| err.starts_with("Already in ")));
+        assert_eq!(
+            start(&mut app, &["api"]),
+            Err("Cannot change directory: not connected yet.".to_owned())
+        );
+        assert!(app.pending_session_note.is_none());
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Session-wide record of the files edit tools touched. The first time an
+//! edit names a file, before the tool runs, its bytes on disk are kept as the
+//! baseline, so `/files` can diff against the session start and revert to it.
+
+use super::{App, MessageBlock, ToolCallInfo, is_execute_tool_name};
+use crate::agent::model;
+use std::path::{Path, PathBuf};
+
+/// What a file held when the session first touched it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Baseline {
+    /// The file did not exist yet.
+    Absent,
+    Content(Vec<u8>),
+    /// The path first showed up once the tool was already running, or it
+    /// could not be read, so there is nothing safe to revert to.
+    Unknown,
+}
+
+/// A file an edit tool touched this session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedFile {
+    pub path: PathBuf,
+    pub baseline: Baseline,
+}
+
+#[derive(Debug, Default)]
+pub struct ChangedFiles {
+    /// In order of first touch.
+    files: Vec<TrackedFile>,
+}
+
+impl ChangedFiles {
+    /// Remember the current content of `path` unless it is already tracked.
+    /// Only a tool that has not run yet can tell what the file held before it.
+    pub fn track(&mut self, path: &Path, before_execution: bool) {
+        if self.files.iter().any(|file| file.path == path) {
+            return;
+        }
+        let baseline = if before_execution {
+            match std::fs::read(path) {
+                Ok(bytes) => Baseline::Content(bytes),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Baseline::Absent,
+                Err(_) => Baseline::Unknown,
+            }
+        } else {
+            Baseline::Unknown
+        };
+        self.files.push(TrackedFile { path: path.to_path_buf(), baseline });
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &TrackedFile> {
+        self.files.iter()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&TrackedFile> {
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+    /// Touched, but without a baseline to compare with.
+    Unknown,
+}
+
+impl ChangeKind {
+    /// One-letter tag in the style of `git status --short`.
+    #[must_use]
+    pub const fn tag(self) -> &'static str {
+        match self {
+            Self::Created => "A",
+            Self::Modified => "M",
+            Self::Deleted => "D",
+            Self::Unknown => "?",
+        }
+    }
+}
+
+/// A tracked file compared with its baseline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFileEntry {
+    pub path: PathBuf,
+    /// Path relative to the session cwd when inside it.
+    pub label: String,
+    pub kind: ChangeKind,
+    pub added: usize,
+    pub removed: usize,
+    pub diff: model::Diff,
+}
+
+/// Start tracking the files an edit tool call is about to change. Runs on
+/// every call and update, so paths that only show up later are caught too;
+/// those get no baseline unless they come with the initial, pending call.
+pub(crate) fn track_tool_call(app: &mut App, tool_call_id: &str, initial: bool) {
+    let Some((mi, bi)) = app.lookup_tool_call(tool_call_id) else {
+        return;
+    };
+    let Some(MessageBlock::ToolCall(tc)) = app.messages.get(mi).and_then(|m| m.blocks.get(bi))
+    else {
+        return;
+    };
+    let before_execution = initial && tc.status == model::ToolCallStatus::Pending;
+    let paths = edited_paths(tc, &app.cwd_raw);
+    for path in paths {
+        app.changed_files.track(&path, before_execution);
+    }
+}
+
+pub(crate) fn edited_paths(tc: &ToolCallInfo, cwd: &str) -> Vec<PathBuf> {
+    if !tc.is_mutating_tool() || is_execute_tool_name(&tc.sdk_tool_name) {
+        return Vec::new();
+    }
+    let input_path = tc.raw_input.as_ref().and_then(|input| {
+        ["file_path", "notebook_path"].iter().find_map(|key| input.get(key)?.as_str())
+    });
+    let diff_paths = tc.content.iter().filter_map(|content| match content {
+        model::ToolCallContent::Diff(diff) => Some(diff.path.as_path()),
+        _ => None,
+    });
+    input_path.map(Path::new).into_iter().chain(diff_paths).map(|path| resolve(cwd, path)).collect()
+}
+
+/// Tracked files whose content differs from the session start, as of now.
+#[must_use]
+pub fn changed_entries(app: &App) -> Vec<ChangedFileEntry> {
+    app.changed_files
+        .files()
+        .filter_map(|file| {
+            let current = std::fs::read(&file.path).ok();
+            let kind = match (&file.baseline, &current) {
+                (Baseline::Absent, None) => return None,
+                (Baseline::Content(before), Some(after)) if before == after => return None,
+                (Baseline::Absent, Some(_)) => ChangeKind::Created,
+                (Baseline::Content(_), None) => ChangeKind::Deleted,
+                (Baseline::Content(_), Some(_)) => ChangeKind::Modified,
+                (Baseline::Unknown, _) => ChangeKind::Unknown,
+            };
+            let new_text = current.as_deref().map(text).unwrap_or_default();
+            let old_text = match &file.baseline {
+                Baseline::Absent => None,
+                Baseline::Content(bytes) => Some(text(bytes)),
+                // Diff the file against itself: there is nothing to compare with.
+                Baseline::Unknown => Some(new_text.clone()),
+            };
+            let diff = model::Diff::new(&file.path, new_text).old_text(old_text);
+            let (added, removed) = super::diff_stats::line_counts(&diff);
+            Some(ChangedFileEntry {
+                label: super::explain::mention_path(app, &file.path),
+                path: file.path.clone(),
+                kind,
+                added,
+                removed,
+                diff,
+            })
+        })
+        .collect()
+}
+
+/// Put `path` back the way it was when the session first touched it.
+pub(crate) fn revert(app: &App, path: &Path) -> Result<String, String> {
+    let file = app
+        .changed_files
+        .get(path)
+        .ok_or_else(|| format!("{} was not changed in this session", path.display()))?;
+    let label = super::explain::mention_path(app, path);
+    match &file.baseline {
+        Baseline::Content(content) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+            }
+            std::fs::write(path, content)
+                .map_err(|err| format!("Failed to restore {label}: {err}"))?;
+            Ok(format!("Restored {label} to its session-start content."))
+        }
+        Baseline::Absent => {
+            std::fs::remove_file(path).map_err(|err| format!("Failed to remove {label}: {err}"))?;
+            Ok(format!("Removed {label}, which this session created."))
+        }
+        Baseline::Unknown => Err(unknown_baseline_message(&label)),
+    }
+}
+
+pub(crate) fn unknown_baseline_message(label: &str) -> String {
+    format!("{label} has no session-start copy to revert to.")
+}
+
+/// Diff text for file bytes; invalid UTF-8 shows as replacement characters.
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn resolve(cwd: &str, path: &Path) -> PathBuf {
+    if path.is_absolute() { path.to_path_buf() } else { Path::new(cwd).join(path) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn entries_compare_with_baseline_and_revert_restores_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let edited = dir.path().join("lib.rs");
+        let created = dir.path().join("new.rs");
+        let untouched = dir.path().join("same.rs");
+        std::fs::write(&edited, "fn a() {}\n").expect("write");
+        std::fs::write(&untouched, "same\n").expect("write");
+
+        let mut app = App::test_default();
+        app.cwd_raw = dir.path().to_string_lossy().into_owned();
+        for path in [&edited, &created, &untouched] {
+            app.changed_files.track(path, true);
+        }
+        std::fs::write(&edited, "fn a() {}\nfn b() {}\n").expect("write");
+        std::fs::write(&created, "new\n").expect("write");
+        app.changed_files.track(&edited, true);
+
+        let summary: Vec<_> = changed_entries(&app)
+            .into_iter()
+            .map(|entry| (entry.label, entry.kind, entry.added, entry.removed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("lib.rs".to_owned(), ChangeKind::Modified, 1, 0),
+                ("new.rs".to_owned(), ChangeKind::Created, 1, 0),
+            ]
+        );
+
+        revert(&app, &edited).expect("revert edit");
+        revert(&app, &created).expect("revert create");
+        assert_eq!(std::fs::read_to_string(&edited).expect("read"), "fn a() {}\n");
+        assert!(!created.exists());
+        assert!(changed_entries(&app).is_empty());
+    }
+
+    #[test]
+    fn binary_baseline_round_trips_and_late_paths_cannot_be_reverted() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let binary = dir.path().join("logo.bin");
+        let late = dir.path().join("late.rs");
+        let bytes = vec![0x89, 0x50, 0xff, 0xfe, 0x00];
+        std::fs::write(&binary, &bytes).expect("write");
+        std::fs::write(&late, "before\n").expect("write");
+
+        let mut app = App::test_default();
+        app.cwd_raw = dir.path().to_string_lossy().into_owned();
+        app.changed_files.track(&binary, true);
+        app.changed_files.track(&late, false);
+        std::fs::write(&binary, "replaced").expect("write");
+        std::fs::write(&late, "after\n").expect("write");
+
+        let kinds: Vec<_> =
+            changed_entries(&app).into_iter().map(|entry| (entry.label, entry.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("logo.bin".to_owned(), ChangeKind::Modified),
+                ("late.rs".to_owned(), ChangeKind::Unknown),
+            ]
+        );
+
+        revert(&app, &binary).expect("revert binary");
+        assert_eq!(std::fs::read(&binary).expect("read"), bytes);
+        assert!(revert(&app, &late).is_err());
+        assert_eq!(std::fs::read_to_string(&late).expect("read"), "after\n");
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Claude Code hooks (`hooks` in user, project and local settings). The agent
+//! runs them itself, exactly as the official client would; this module lists
+//! what is configured for `/status` and turns the results the agent reports
+//! into transcript notes, so a blocked tool call or rewritten input is not a
+//! silent surprise.
+
+use super::{App, SystemSeverity};
+use crate::agent::model::{HookDecision, HookOutcome, HookResult};
+use serde_json::Value;
+use std::path::Path;
+
+/// Hook counts per event over the settings the agent loads, e.g.
+/// `PreToolUse (2), Stop (1)`. `None` when no hooks are configured.
+#[must_use]
+pub fn configured_summary(app: &App) -> Option<String> {
+    let project = std::fs::read_to_string(Path::new(&app.cwd_raw).join(".claude/settings.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .unwrap_or(Value::Null);
+    summarize(&[
+        &app.config.committed_settings_document,
+        &project,
+        &app.config.committed_local_settings_document,
+    ])
+}
+
+fn summarize(documents: &[&Value]) -> Option<String> {
+    if documents.iter().any(|doc| doc.get("disableAllHooks").and_then(Value::as_bool) == Some(true))
+    {
+        return Some("disabled (disableAllHooks)".to_owned());
+    }
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for events in documents.iter().filter_map(|doc| doc.get("hooks")?.as_object()) {
+        for (event, matchers) in events {
+            let count: usize = matchers
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|matcher| matcher.get("hooks")?.as_array().map(Vec::len))
+                .sum();
+            if count == 0 {
+                continue;
+            }
+            match counts.iter_mut().find(|(name, _)| name == event) {
+                Some((_, total)) => *total += count,
+                None => counts.push((event.clone(), count)),
+            }
+        }
+    }
+    if counts.is_empty() {
+        return None;
+    }
+    Some(
+        counts
+            .iter()
+            .map(|(event, count)| format!("{event} ({count})"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// Show what a hook did to the turn. Hooks that ran cleanly without a
+/// decision stay out of the transcript.
+pub(crate) fn handle_hook_result(app: &mut App, result: &HookResult) {
+    tracing::debug!(
+        hook = %result.hook_name,
+        outcome = ?result.outcome,
+        decision = ?result.decision,
+        exit_code = ?result.exit_code,
+        "Hook result"
+    );
+    for (severity, note) in result_notes(result) {
+        super::events::push_system_message_with_severity(app, Some(severity), &note);
+    }
+}
+
+fn result_notes(result: &HookResult) -> Vec<(SystemSeverity, String)> {
+    let name = &result.hook_name;
+    let reason = result.reason.as_deref().map(str::trim).filter(|reason| !reason.is_empty());
+    let with_reason = |text: String| match reason {
+        Some(reason) => format!("{text}: {reason}"),
+        None => text,
+    };
+    let mut notes = Vec::new();
+    match result.decision {
+        Some(HookDecision::Block | HookDecision::Deny) => {
+            let action = match result.hook_event.as_str() {
+                "PreToolUse" => "blocked the tool call",
+                "UserPromptSubmit" => "blocked the prompt",
+                "Stop" | "SubagentStop" => "asked Claude to keep going",
+                _ => "sent feedback to Claude",
+            };
+            notes.push((SystemSeverity::Warning, with_reason(format!("Hook {name} {action}"))));
+        }
+        Some(HookDecision::Ask) => {
+            notes.push((
+                SystemSeverity::Info,
+                with_reason(format!("Hook {name} asked for confirmation")),
+            ));
+        }
+        Some(HookDecision::Allow) | None if result.outcome == HookOutcome::Error => {
+            let exit = result.exit_code.map(|code| format!(" (exit {code})")).unwrap_or_default();
+            notes.push((SystemSeverity::Warning, with_reason(format!("Hook {name} failed{exit}"))));
+        }
+        Some(HookDecision::Allow) | None => {}
+    }
+    if result.input_modified {
+        notes.push((SystemSeverity::Info, format!("Hook {name} modified the tool input")));
+    }
+    if let Some(message) = result.system_message.as_deref().filter(|text| !text.is_empty()) {
+        notes.push((SystemSeverity::Info, format!("Hook {name}: {message}")));
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn result(event: &str, decision: Option<HookDecision>) -> HookResult {
+        HookResult {
+            hook_event: event.to_owned(),
+            hook_name: format!("{event}:Bash"),
+            outcome: HookOutcome::Success,
+            exit_code: Some(0),
+            decision,
+            reason: Some("no rm -rf".to_owned()),
+            input_modified: false,
+            system_message: None,
+        }
+    }
+
+    #[test]
+    fn notes_describe_blocks_rewrites_and_failures() {
+        assert_eq!(
+            result_notes(&result("PreToolUse", Some(HookDecision::Deny))),
+            vec![(
+                SystemSeverity::Warning,
+                "Hook PreToolUse:Bash blocked the tool call: no rm -rf".to_owned()
+            )]
+        );
+        assert_eq!(result_notes(&result("PostToolUse", None)), vec![]);
+
+        let rewritten = HookResult {
+            input_modified: true,
+            system_message: Some("Using ls instead".to_owned()),
+            ..result("PreToolUse", Some(HookDecision::Allow))
+        };
+        assert_eq!(
+            result_notes(&rewritten),
+            vec![
+                (SystemSeverity::Info, "Hook PreToolUse:Bash modified the tool input".to_owned()),
+                (SystemSeverity::Info, "Hook PreToolUse:Bash: Using ls instead".to_owned()),
+            ]
+        );
+
+        let failed = HookResult {
+            outcome: HookOutcome::Error,
+            exit_code: Some(1),
+            reason: None,
+            ..result("PostToolUse", None)
+        };
+        assert_eq!(
+            result_notes(&failed),
+            vec![(SystemSeverity::Warning, "Hook PostToolUse:Bash failed (exit 1)".to_owned())]
+        );
+    }
+
+    #[test]
+    fn summary_counts_hooks_per_event_across_settings() {
+        let user = json!({ "hooks": { "PreToolUse": [
+            { "matcher": "Bash", "hooks": [{ "type": "command", "command": "a" }] },
+            { "matcher": "Edit", "hooks": [{ "type": "command", "command": "b" }] }
+        ] } });
+        let project = json!({ "hooks": {
+            "Stop": [{ "hooks": [{ "type": "command", "command": "c" }] }],
+            "PreToolUse": [{ "matcher": "*", "hooks": [{ "type": "command", "command": "d" }] }]
+        } });
+
+        assert_eq!(summarize(&[&user, &project]).as_deref(), Some("PreToolUse (3), Stop (1)"));
+        assert_eq!(summarize(&[&Value::Null, &json!({})]), None);
+        assert_eq!(
+            summarize(&[&user, &json!({ "disableAllHooks": true })]).as_deref(),
+            Some("disabled (disableAllHooks)")
+        );
+    }
+}
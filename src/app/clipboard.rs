@@ -0,0 +1,168 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Clipboard writes. The system clipboard is reached through `arboard`; when
+//! that is unavailable or the session is remote, the text is sent to the
+//! terminal as an OSC 52 sequence so copies still land on the user's machine
+//! over SSH and inside tmux or screen.
+
+use super::attachments::base64_encode;
+use super::config::ClipboardMode;
+use std::io::Write;
+
+/// xterm's default limit for OSC 52 selections; most terminals cap near it.
+const OSC52_MAX_ENCODED_BYTES: usize = 100_000;
+/// GNU screen truncates DCS strings longer than its 768-byte buffer.
+const SCREEN_DCS_CHUNK_BYTES: usize = 768;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Multiplexer {
+    #[default]
+    None,
+    Tmux,
+    Screen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct ClipboardEnv {
+    /// Connected over SSH, where the system clipboard belongs to the wrong machine.
+    remote: bool,
+    multiplexer: Multiplexer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClipboardTarget {
+    System,
+    Osc52,
+}
+
+/// Copy `text` using the configured backend.
+pub(crate) fn copy_text(mode: ClipboardMode, text: &str) -> Result<ClipboardTarget, String> {
+    let env = detect_clipboard_env();
+    match mode {
+        ClipboardMode::System => copy_to_system(text).map(|()| ClipboardTarget::System),
+        ClipboardMode::Osc52 => copy_via_osc52(env, text).map(|()| ClipboardTarget::Osc52),
+        ClipboardMode::Auto if env.remote => {
+            copy_via_osc52(env, text).map(|()| ClipboardTarget::Osc52)
+        }
+        ClipboardMode::Auto => match copy_to_system(text) {
+            Ok(()) => Ok(ClipboardTarget::System),
+            Err(error) => {
+                tracing::debug!(%error, "System clipboard unavailable; falling back to OSC 52");
+                copy_via_osc52(env, text).map(|()| ClipboardTarget::Osc52)
+            }
+        },
+    }
+}
+
+fn copy_to_system(text: &str) -> Result<(), String> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|error| format!("Failed to access clipboard: {error}"))?;
+    clipboard
+        .set_text(text.to_owned())
+        .map_err(|error| format!("Failed to copy to clipboard: {error}"))
+}
+
+fn copy_via_osc52(env: ClipboardEnv, text: &str) -> Result<(), String> {
+    let sequence = osc52_sequence(env.multiplexer, text)?;
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|error| format!("Failed to write OSC 52 sequence: {error}"))
+}
+
+fn osc52_sequence(multiplexer: Multiplexer, text: &str) -> Result<String, String> {
+    let encoded = base64_encode(text.as_bytes());
+    if encoded.len() > OSC52_MAX_ENCODED_BYTES {
+        return Err(format!(
+            "Selection is too large to copy through the terminal ({} KB encoded, limit {} KB)",
+            encoded.len() / 1000,
+            OSC52_MAX_ENCODED_BYTES / 1000
+        ));
+    }
+    let osc = format!("\u{1b}]52;c;{encoded}\u{7}");
+    Ok(match multiplexer {
+        Multiplexer::None => osc,
+        // tmux passthrough: ESCs inside the payload are doubled.
+        Multiplexer::Tmux => {
+            format!("\u{1b}Ptmux;{}\u{1b}\\", osc.replace('\u{1b}', "\u{1b}\u{1b}"))
+        }
+        // screen forwards each DCS string separately, so split below its buffer size.
+        Multiplexer::Screen => {
+            let mut out =
+                String::with_capacity(osc.len() + osc.len().div_ceil(SCREEN_DCS_CHUNK_BYTES) * 4);
+            let mut rest = osc.as_str();
+            while !rest.is_empty() {
+                let (chunk, tail) = rest.split_at(rest.len().min(SCREEN_DCS_CHUNK_BYTES));
+                out.push_str("\u{1b}P");
+                out.push_str(chunk);
+                out.push_str("\u{1b}\\");
+                rest = tail;
+            }
+            out
+        }
+    })
+}
+
+fn detect_clipboard_env() -> ClipboardEnv {
+    clipboard_env_from_vars(
+        std::env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?))),
+    )
+}
+
+fn clipboard_env_from_vars<I>(vars: I) -> ClipboardEnv
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut env = ClipboardEnv::default();
+    for (key, value) in vars {
+        if value.is_empty() {
+            continue;
+        }
+        match key.as_str() {
+            "SSH_TTY" | "SSH_CONNECTION" | "SSH_CLIENT" => env.remote = true,
+            "TMUX" => env.multiplexer = Multiplexer::Tmux,
+            "STY" if env.multiplexer == Multiplexer::None => env.multiplexer = Multiplexer::Screen,
+            _ => {}
+        }
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn env_of(pairs: &[(&str, &str)]) -> ClipboardEnv {
+        clipboard_env_from_vars(pairs.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())))
+    }
+
+    #[test]
+    fn detects_ssh_and_multiplexers() {
+        assert_eq!(env_of(&[]), ClipboardEnv::default());
+        assert_eq!(
+            env_of(&[("SSH_TTY", "/dev/pts/1"), ("STY", "1.pts"), ("TMUX", "/tmp/tmux")]),
+            ClipboardEnv { remote: true, multiplexer: Multiplexer::Tmux }
+        );
+        assert_eq!(env_of(&[("SSH_CONNECTION", "")]), ClipboardEnv::default());
+    }
+
+    #[test]
+    fn osc52_wraps_for_multiplexers_and_rejects_oversized_payloads() {
+        assert_eq!(osc52_sequence(Multiplexer::None, "hi").as_deref(), Ok("\u{1b}]52;c;aGk=\u{7}"));
+        assert_eq!(
+            osc52_sequence(Multiplexer::Tmux, "hi").as_deref(),
+            Ok("\u{1b}Ptmux;\u{1b}\u{1b}]52;c;aGk=\u{7}\u{1b}\\")
+        );
+
+        let screen = osc52_sequence(Multiplexer::Screen, &"x".repeat(1_000)).expect("fits");
+        let chunks: Vec<&str> = screen.split("\u{1b}P").filter(|part| !part.is_empty()).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= SCREEN_DCS_CHUNK_BYTES + 2));
+
+        assert!(osc52_sequence(Multiplexer::None, &"x".repeat(80_000)).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Code block actions in keyboard selection mode (Ctrl+s). With the cursor on
+//! a code block, `c` copies its code, `w` switches its long lines between soft
+//! wrap and clipping, and `n` toggles line numbers. The layout is kept per
+//! text block in `TextBlock::code_view`.
+
+use super::toast::{ToastKind, show_toast};
+use super::{App, CodeView, InvalidationLevel, MessageBlock};
+use crate::ui::hscroll;
+use crate::ui::markdown::is_fence;
+
+/// Bodies of the fenced code blocks in `markdown`.
+fn code_bodies(markdown: &str) -> Vec<String> {
+    let mut bodies = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in markdown.lines() {
+        if is_fence(line) {
+            match current.take() {
+                Some(body) => bodies.push(body.join("\n")),
+                None => current = Some(Vec::new()),
+            }
+        } else if let Some(body) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    bodies.extend(current.map(|body| body.join("\n")));
+    bodies
+}
+
+/// Code text of a rendered code row: line number gutter and clip mark removed.
+fn row_code(row: &str) -> &str {
+    let row = row.trim_end().trim_end_matches(hscroll::RIGHT_MARK);
+    let trimmed = row.trim_start();
+    let digits = trimmed.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+    let after_gutter = if digits > 0 { trimmed[digits..].strip_prefix(" \u{2502} ") } else { None };
+    after_gutter.unwrap_or(row).trim_start().trim_start_matches(hscroll::LEFT_MARK).trim()
+}
+
+/// Message index, text block index and code of the block under the cursor.
+pub(super) fn code_at_visual_cursor(app: &App) -> Option<(usize, usize, String)> {
+    let visual = app.chat_visual?;
+    if app.viewport.height_prefix_sums.is_empty() {
+        return None;
+    }
+    let message_index =
+        app.viewport.find_first_visible(app.viewport.scroll_offset + visual.cursor.row);
+    let message = app.messages.get(message_index)?;
+    let mut blocks: Vec<(usize, String)> = Vec::new();
+    for (block_index, block) in message.blocks.iter().enumerate() {
+        if let MessageBlock::Text(text) = block {
+            blocks.extend(code_bodies(&text.text).into_iter().map(|body| (block_index, body)));
+        }
+    }
+    if blocks.len() > 1 {
+        // On the header row, look at the first code row below it.
+        let rows = &app.rendered_chat_lines;
+        let row = rows.get(visual.cursor.row).map_or("", String::as_str);
+        let rule = format!("{0}{0} ", crate::ui::theme::SEPARATOR_CHAR);
+        let row = if row.starts_with(&rule) {
+            rows.get(visual.cursor.row + 1).map_or("", String::as_str)
+        } else {
+            row
+        };
+        let code = row_code(row);
+        if code.is_empty() {
+            return None;
+        }
+        blocks.retain(|(_, body)| body.contains(code));
+    }
+    let (block_index, body) = blocks.into_iter().next()?;
+    Some((message_index, block_index, body))
+}
+
+/// Copy the code block under the cursor. Returns false when there is none.
+pub(crate) fn copy_at_visual_cursor(app: &mut App) -> bool {
+    let Some((_, _, code)) = code_at_visual_cursor(app) else {
+        return false;
+    };
+    match super::clipboard::copy_text(app.config.clipboard_mode_effective(), &code) {
+        Ok(_) => show_toast(app, ToastKind::Success, "Copied code block"),
+        Err(error) => {
+            tracing::warn!(%error, "failed to copy code block");
+            show_toast(app, ToastKind::Error, error);
+        }
+    }
+    true
+}
+
+/// Change the layout of the code block under the cursor. Returns false when
+/// there is none.
+pub(crate) fn update_view_at_visual_cursor(app: &mut App, update: fn(&mut CodeView)) -> bool {
+    let Some((message_index, block_index, _)) = code_at_visual_cursor(app) else {
+        return false;
+    };
+    let Some(MessageBlock::Text(block)) =
+        app.messages.get_mut(message_index).and_then(|message| message.blocks.get_mut(block_index))
+    else {
+        return false;
+    };
+    update(&mut block.code_view);
+    block.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn code_bodies_and_rows_line_up() {
+        let markdown = "Try:\n\n```sh\ncargo test\n```\n\nthen\n~~~\na\nb\n~~~";
+        assert_eq!(code_bodies(markdown), vec!["cargo test".to_owned(), "a\nb".to_owned()]);
+        assert_eq!(row_code(" 9 \u{2502} let x = 1;"), "let x = 1;");
+        assert_eq!(row_code("let long_na\u{203a}"), "let long_na");
+        assert_eq!(row_code("2024 was a year"), "2024 was a year");
+    }
+}
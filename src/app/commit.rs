@@ -0,0 +1,298 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/commit`: send the staged diff (or every tracked change when nothing is
+//! staged) to the agent for a commit message, open the draft in an editable
+//! overlay, and run `git commit` once the user confirms it there. Plan mode
+//! and `--read-only` refuse to commit. `--amend` with nothing staged skips
+//! the agent and edits the current message instead.
+
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, MessageBlock, MessageRole, SystemSeverity};
+use crate::agent::events::ClientEvent;
+use crate::app::config::DefaultPermissionMode;
+use std::fmt::Write as _;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt as _;
+
+/// Diff text sent to the agent is cut after this many bytes.
+const MAX_DIFF_BYTES: usize = 60_000;
+/// Recent subjects quoted so the draft follows the repository's style.
+const RECENT_SUBJECTS: &str = "10";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitOptions {
+    pub amend: bool,
+    /// Add a `Signed-off-by` trailer (`--signoff`).
+    pub signoff: bool,
+    /// Nothing was staged, so commit every tracked change (`--all`).
+    pub all: bool,
+}
+
+/// A `/commit` waiting for the agent's draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingCommitDraft {
+    pub options: CommitOptions,
+    /// `git diff --stat` of what will be committed.
+    pub summary: String,
+}
+
+/// A prompt turn whose reply is a draft to open for editing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingDraft {
+    Commit(PendingCommitDraft),
+    PullRequest(super::pull_request::PendingPullRequestDraft),
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    let mut options = CommitOptions::default();
+    for arg in args {
+        match *arg {
+            "--amend" => options.amend = true,
+            "--signoff" | "-s" => options.signoff = true,
+            _ => return Err("Usage: /commit [--amend] [--signoff]".to_owned()),
+        }
+    }
+    if writes_blocked(app) {
+        return Err("/commit is unavailable in plan mode and read-only sessions.".to_owned());
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /commit.".to_owned());
+    }
+
+    let cwd = app.cwd_raw.clone();
+    git(&cwd, &["rev-parse", "--is-inside-work-tree"])
+        .map_err(|_| "/commit needs a git repository.".to_owned())?;
+    let mut summary = git(&cwd, &["diff", "--cached", "--stat"])?;
+    if summary.trim().is_empty() {
+        summary = git(&cwd, &["diff", "HEAD", "--stat"]).unwrap_or_default();
+        options.all = !summary.trim().is_empty();
+    }
+
+    if summary.trim().is_empty() {
+        if !options.amend {
+            return Err("Nothing to commit.".to_owned());
+        }
+        let message = git(&cwd, &["log", "-1", "--format=%B"])?;
+        let summary = git(&cwd, &["show", "--stat", "--format=", "HEAD"])?;
+        let draft = PendingCommitDraft { options, summary: summary.trim_end().to_owned() };
+        return crate::app::config::open_commit(app, draft, message.trim_end().to_owned());
+    }
+
+    if app.conn.is_none() || app.session_id.is_none() {
+        return Err("/commit needs a connected session to draft the message.".to_owned());
+    }
+    let diff_args: &[&str] = if options.all { &["diff", "HEAD"] } else { &["diff", "--cached"] };
+    let diff = git(&cwd, diff_args)?;
+    let subjects = git(&cwd, &["log", "-n", RECENT_SUBJECTS, "--format=%s"]).unwrap_or_default();
+    let prompt = draft_prompt(&summary, &diff, &subjects);
+    app.pending_draft = Some(PendingDraft::Commit(PendingCommitDraft {
+        options,
+        summary: summary.trim_end().to_owned(),
+    }));
+    let display = std::iter::once("/commit").chain(args.iter().copied()).collect::<Vec<_>>();
+    super::input_submit::dispatch_prompt_turn_as(app, &display.join(" "), prompt);
+    Ok(())
+}
+
+/// Plan mode and `--read-only` sessions must not change the repository.
+pub(super) fn writes_blocked(app: &App) -> bool {
+    app.read_only
+        || app
+            .mode
+            .as_ref()
+            .is_some_and(|mode| mode.current_mode_id == DefaultPermissionMode::Plan.as_stored())
+}
+
+fn draft_prompt(summary: &str, diff: &str, subjects: &str) -> String {
+    let mut prompt = String::from(
+        "Write a git commit message for the changes below. Reply with only the message: \
+         a subject line of at most 72 characters, then a blank line and a short body if the \
+         change needs explaining. No code fences, no commentary, and do not run any tools.\n",
+    );
+    if !subjects.trim().is_empty() {
+        let _ = write!(prompt, "\nRecent commit subjects, for style:\n{}\n", subjects.trim_end());
+    }
+    push_diff(&mut prompt, summary, diff);
+    prompt
+}
+
+/// Append the `--stat` summary and the diff, cut at [`MAX_DIFF_BYTES`], as
+/// fenced blocks.
+pub(super) fn push_diff(prompt: &mut String, summary: &str, diff: &str) {
+    let _ = write!(prompt, "\n```\n{}\n```\n\n```diff\n", summary.trim_end());
+    if diff.len() > MAX_DIFF_BYTES {
+        let mut cut = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        prompt.push_str(&diff[..cut]);
+        prompt.push_str("\n[diff truncated]");
+    } else {
+        prompt.push_str(diff.trim_end());
+    }
+    prompt.push_str("\n```");
+}
+
+/// The agent's reply to a `/commit` or `/pr` prompt arrived; open it for
+/// editing.
+pub(crate) fn open_pending_draft(app: &mut App) {
+    let Some(draft) = app.pending_draft.take() else {
+        return;
+    };
+    let reply = message_from_reply(&last_reply_text(app));
+    let opened = match draft {
+        PendingDraft::Commit(draft) => crate::app::config::open_commit(app, draft, reply),
+        PendingDraft::PullRequest(draft) => {
+            crate::app::config::open_pull_request(app, draft, &reply)
+        }
+    };
+    if let Err(err) = opened {
+        push_system_message_with_severity(app, Some(SystemSeverity::Error), &err);
+    }
+}
+
+fn last_reply_text(app: &App) -> String {
+    app.messages
+        .iter()
+        .rfind(|msg| matches!(msg.role, MessageRole::Assistant))
+        .map(|msg| {
+            msg.blocks
+                .iter()
+                .filter_map(|block| match block {
+                    MessageBlock::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+/// The reply without surrounding code fences or blank lines.
+fn message_from_reply(reply: &str) -> String {
+    let trimmed = reply.trim();
+    let unfenced = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.split_once('\n'))
+        .and_then(|(_, body)| body.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed);
+    unfenced.trim().to_owned()
+}
+
+/// Run `git commit` in the background with `message` on stdin.
+pub(crate) fn commit(app: &mut App, message: String, options: CommitOptions) {
+    if writes_blocked(app) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Warning),
+            "Commit skipped: the session switched to plan mode.",
+        );
+        return;
+    }
+    let mut args = vec!["commit", "--file=-", "--cleanup=strip"];
+    if options.amend {
+        args.push("--amend");
+    }
+    if options.signoff {
+        args.push("--signoff");
+    }
+    if options.all {
+        args.push("--all");
+    }
+    let mut command = tokio::process::Command::new("git");
+    command
+        .args(&args)
+        .current_dir(&app.cwd_raw)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some("Committing...".to_owned());
+    let tx = app.event_tx.clone();
+    tokio::task::spawn_local(async move {
+        let result = run_commit(command, message).await;
+        let _ = tx.send(ClientEvent::GitCommitFinished { result });
+    });
+}
+
+async fn run_commit(
+    mut command: tokio::process::Command,
+    message: String,
+) -> Result<String, String> {
+    let mut child = command.spawn().map_err(|err| format!("Failed to run git: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(message.as_bytes())
+            .await
+            .map_err(|err| format!("Failed to pass the message to git: {err}"))?;
+    }
+    let output =
+        child.wait_with_output().await.map_err(|err| format!("git commit failed: {err}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        // First line reads like `[main 1a2b3c4] Subject`.
+        Ok(stdout.lines().next().unwrap_or("Committed.").trim().to_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = [stderr.trim(), stdout.trim()].into_iter().find(|text| !text.is_empty());
+        Err(detail.unwrap_or("git commit failed").to_owned())
+    }
+}
+
+pub(super) fn git(cwd: &str, args: &[&str]) -> Result<String, String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reply_fences_are_stripped() {
+        assert_eq!(
+            message_from_reply("```text\nFix overflow\n\nClamp the index.\n```\n"),
+            "Fix overflow\n\nClamp the index."
+        );
+        assert_eq!(message_from_reply("  Add tests  "), "Add tests");
+    }
+
+    #[test]
+    fn prompt_quotes_summary_subjects_and_truncated_diff() {
+        let diff = "é".repeat(MAX_DIFF_BYTES);
+        let prompt = draft_prompt(" a.rs | 2 +-\n", &diff, "Fix a\nAdd b\n");
+
+        assert!(prompt.contains("Recent commit subjects, for style:\nFix a\nAdd b\n"));
+        assert!(prompt.contains("```\n a.rs | 2 +-\n```"));
+        assert!(prompt.ends_with("\n[diff truncated]\n```"));
+    }
+
+    #[test]
+    fn plan_mode_and_read_only_block_commits() {
+        let mut app = App::test_default();
+        assert!(!writes_blocked(&app));
+        app.read_only = true;
+        assert!(writes_blocked(&app));
+        app.read_only = false;
+        app.mode = Some(crate::app::ModeState {
+            current_mode_id: "plan".to_owned(),
+            current_mode_name: "Plan".to_owned(),
+            available_modes: Vec::new(),
+        });
+        assert!(writes_blocked(&app));
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/files` overlay: every file edit tools changed this session, compared with
+//! its content when the session first touched it. Rows open a diff against
+//! that baseline, open the file in the editor, or revert it.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crate::app::changed_files::{
+    ChangeKind, ChangedFileEntry, changed_entries, unknown_baseline_message,
+};
+use crate::app::confirm::{ConfirmAction, ConfirmDialog, open_confirm};
+use crate::app::toast::{ToastKind, show_toast};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Lines moved by `PageUp` / `PageDown` in the diff view.
+const PAGE_LINES: usize = 10;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangedFilesOverlayState {
+    pub entries: Vec<ChangedFileEntry>,
+    pub selected: usize,
+    /// Showing the selected file's diff instead of the list.
+    pub show_diff: bool,
+    pub diff_scroll: usize,
+}
+
+impl ChangedFilesOverlayState {
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&ChangedFileEntry> {
+        self.entries.get(self.selected)
+    }
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn changed_files_overlay(&self) -> Option<&ChangedFilesOverlayState> {
+        if let Some(ConfigOverlayState::ChangedFiles(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn changed_files_overlay_mut(&mut self) -> Option<&mut ChangedFilesOverlayState> {
+        if let Some(ConfigOverlayState::ChangedFiles(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_changed_files(app: &mut App) -> Result<(), String> {
+    let entries = changed_entries(app);
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::ChangedFiles(ChangedFilesOverlayState {
+        entries,
+        ..ChangedFilesOverlayState::default()
+    }));
+    Ok(())
+}
+
+/// Re-read the files on disk, keeping the selection on the same path.
+pub(crate) fn refresh_changed_files(app: &mut App) {
+    let entries = changed_entries(app);
+    let Some(overlay) = app.config.changed_files_overlay_mut() else {
+        return;
+    };
+    let selected_path = overlay.selected_entry().map(|entry| entry.path.clone());
+    overlay.selected = selected_path
+        .and_then(|path| entries.iter().position(|entry| entry.path == path))
+        .unwrap_or(overlay.selected)
+        .min(entries.len().saturating_sub(1));
+    if entries.is_empty() {
+        overlay.show_diff = false;
+    }
+    overlay.entries = entries;
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overlay) = app.config.changed_files_overlay_mut() else {
+        return false;
+    };
+    let last = overlay.entries.len().saturating_sub(1);
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) if overlay.show_diff => overlay.show_diff = false,
+        (KeyCode::Esc, KeyModifiers::NONE) => app.config.overlay = None,
+        (KeyCode::Enter | KeyCode::Char('d'), KeyModifiers::NONE) => {
+            overlay.show_diff = !overlay.show_diff && !overlay.entries.is_empty();
+            overlay.diff_scroll = 0;
+        }
+        (KeyCode::Up, KeyModifiers::NONE) if overlay.show_diff => {
+            overlay.diff_scroll = overlay.diff_scroll.saturating_sub(1);
+        }
+        (KeyCode::Down, KeyModifiers::NONE) if overlay.show_diff => overlay.diff_scroll += 1,
+        (KeyCode::PageUp, KeyModifiers::NONE) => {
+            overlay.diff_scroll = overlay.diff_scroll.saturating_sub(PAGE_LINES);
+        }
+        (KeyCode::PageDown, KeyModifiers::NONE) => overlay.diff_scroll += PAGE_LINES,
+        (KeyCode::Up, KeyModifiers::NONE) => overlay.selected = overlay.selected.saturating_sub(1),
+        (KeyCode::Down, KeyModifiers::NONE) => overlay.selected = (overlay.selected + 1).min(last),
+        (KeyCode::Char('e'), KeyModifiers::NONE) => {
+            if let Some(path) = overlay.selected_entry().map(|entry| entry.path.clone()) {
+                if let Err(err) = crate::app::memory::open_in_editor(app, &path) {
+                    show_toast(app, ToastKind::Error, err);
+                }
+                refresh_changed_files(app);
+            }
+        }
+        (KeyCode::Char('r'), KeyModifiers::NONE) => {
+            if let Some(entry) = overlay.selected_entry() {
+                let body = match entry.kind {
+                    ChangeKind::Created => format!(
+                        "{} did not exist when the session started and will be deleted.",
+                        entry.label
+                    ),
+                    ChangeKind::Modified | ChangeKind::Deleted => format!(
+                        "{} goes back to its content from when the session first touched it. \
+                         Later edits are lost.",
+                        entry.label
+                    ),
+                    ChangeKind::Unknown => {
+                        let message = unknown_baseline_message(&entry.label);
+                        show_toast(app, ToastKind::Warning, message);
+                        return true;
+                    }
+                };
+                let dialog = ConfirmDialog::yes_no(
+                    format!("Revert {}?", entry.label),
+                    body,
+                    "Revert",
+                    ConfirmAction::RevertFile(entry.path.clone()),
+                );
+                open_confirm(app, dialog);
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn revert_from_overlay_asks_first_and_refreshes_the_list() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("notes.md");
+        let mut app = App::test_default();
+        app.cwd_raw = dir.path().to_string_lossy().into_owned();
+        app.changed_files.track(&file, true);
+        std::fs::write(&file, "draft\n").expect("write");
+
+        app.config.overlay = Some(ConfigOverlayState::ChangedFiles(ChangedFilesOverlayState {
+            entries: changed_entries(&app),
+            ..ChangedFilesOverlayState::default()
+        }));
+        let press = |app: &mut App, ch| {
+            handle_overlay_key(app, KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE))
+        };
+        press(&mut app, 'd');
+        assert!(app.config.changed_files_overlay().is_some_and(|overlay| overlay.show_diff));
+        press(&mut app, 'r');
+        assert!(file.exists());
+        assert_eq!(
+            app.confirm.as_ref().map(|dialog| dialog.title.as_str()),
+            Some("Revert notes.md?")
+        );
+
+        crate::app::confirm::handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE),
+        );
+        assert!(!file.exists());
+        let overlay = app.config.changed_files_overlay().expect("overlay stays open");
+        assert!(overlay.entries.is_empty());
+        assert!(!overlay.show_diff);
+    }
+}
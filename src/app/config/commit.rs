@@ -0,0 +1,144 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/commit` overlay: the drafted message in a multi-line editor under the
+//! diff summary. Alt+a and Alt+s toggle `--amend` and `--signoff`, Ctrl+s
+//! runs the commit and Esc drops the draft.
+
+use super::message_editor::MessageEditor;
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crate::app::commit::{CommitOptions, PendingCommitDraft};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitOverlayState {
+    /// `git diff --stat` of what will be committed.
+    pub summary: String,
+    pub message: MessageEditor,
+    pub options: CommitOptions,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn commit_overlay(&self) -> Option<&CommitOverlayState> {
+        if let Some(ConfigOverlayState::Commit(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn commit_overlay_mut(&mut self) -> Option<&mut CommitOverlayState> {
+        if let Some(ConfigOverlayState::Commit(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_commit(
+    app: &mut App,
+    draft: PendingCommitDraft,
+    message: String,
+) -> Result<(), String> {
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::Commit(CommitOverlayState {
+        summary: draft.summary,
+        message: MessageEditor::new(message),
+        options: draft.options,
+    }));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overlay) = app.config.commit_overlay_mut() else {
+        return false;
+    };
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => {
+            app.config.overlay = None;
+            super::close(app);
+        }
+        (KeyCode::Char('s'), KeyModifiers::CONTROL) => confirm_commit(app),
+        (KeyCode::Char('a'), KeyModifiers::ALT) => overlay.options.amend = !overlay.options.amend,
+        (KeyCode::Char('s'), KeyModifiers::ALT) => {
+            overlay.options.signoff = !overlay.options.signoff;
+        }
+        _ => {
+            overlay.message.handle_key(key);
+        }
+    }
+    true
+}
+
+pub(super) fn handle_overlay_paste(app: &mut App, text: &str) -> bool {
+    let Some(overlay) = app.config.commit_overlay_mut() else {
+        return false;
+    };
+    overlay.message.paste(text);
+    true
+}
+
+fn confirm_commit(app: &mut App) {
+    let Some(overlay) = app.config.commit_overlay() else {
+        return;
+    };
+    if overlay.message.text.trim().is_empty() {
+        app.config.last_error = Some("The commit message is empty.".to_owned());
+        return;
+    }
+    let message = overlay.message.text.clone();
+    let options = overlay.options;
+    app.config.overlay = None;
+    app.config.last_error = None;
+    super::close(app);
+    crate::app::commit::commit(app, message, options);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn option_keys_toggle_and_other_keys_edit() {
+        let mut app = App::test_default();
+        app.config.overlay = Some(ConfigOverlayState::Commit(CommitOverlayState {
+            summary: String::new(),
+            message: MessageEditor::new("Fix bug".to_owned()),
+            options: CommitOptions::default(),
+        }));
+
+        handle_overlay_key(&mut app, key(KeyCode::Char('a'), KeyModifiers::ALT));
+        handle_overlay_key(&mut app, key(KeyCode::Char('s'), KeyModifiers::ALT));
+        handle_overlay_key(&mut app, key(KeyCode::Char('s'), KeyModifiers::ALT));
+        handle_overlay_key(&mut app, key(KeyCode::Char('!'), KeyModifiers::SHIFT));
+
+        let overlay = app.config.commit_overlay().expect("commit overlay");
+        assert_eq!(overlay.message.text, "Fix bug!");
+        assert!(overlay.options.amend);
+        assert!(!overlay.options.signoff);
+    }
+
+    #[test]
+    fn empty_message_is_not_committed() {
+        let mut app = App::test_default();
+        app.config.overlay = Some(ConfigOverlayState::Commit(CommitOverlayState {
+            summary: String::new(),
+            message: MessageEditor::new("  \n".to_owned()),
+            options: CommitOptions::default(),
+        }));
+
+        handle_overlay_key(&mut app, key(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+        assert!(app.config.commit_overlay().is_some());
+        assert_eq!(app.config.last_error.as_deref(), Some("The commit message is empty."));
+    }
+}
@@ -0,0 +1,231 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/context` overlay: what fills the context window. The total comes from the
+//! agent's usage reports; memory files, conversation and tool output are
+//! estimated locally and the remainder is attributed to the system prompt.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::agent::model;
+use crate::app::{App, MessageBlock, MessageRole, ToolCallInfo};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Rough characters-per-token ratio for English text and code.
+const CHARS_PER_TOKEN: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextCategoryKind {
+    SystemPrompt,
+    Memory,
+    Conversation,
+    ToolResults,
+}
+
+impl ContextCategoryKind {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::SystemPrompt => "System prompt & tools",
+            Self::Memory => "Memory files",
+            Self::Conversation => "Conversation",
+            Self::ToolResults => "Tool results",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextCategory {
+    pub kind: ContextCategoryKind,
+    pub tokens: u64,
+    /// Counted locally rather than reported by the agent.
+    pub estimated: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextBreakdown {
+    pub context_window: Option<u64>,
+    /// Tokens the latest request occupied, as reported by the agent.
+    pub reported_tokens: Option<u64>,
+    pub categories: Vec<ContextCategory>,
+    /// The session was compacted, so the transcript holds more than the context.
+    pub compacted: bool,
+}
+
+impl ContextBreakdown {
+    /// Tokens in use: the reported total when known, else the local estimates.
+    #[must_use]
+    pub fn used_tokens(&self) -> u64 {
+        self.reported_tokens
+            .unwrap_or_else(|| self.categories.iter().map(|category| category.tokens).sum())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextOverlayState {
+    pub breakdown: ContextBreakdown,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn context_overlay(&self) -> Option<&ContextOverlayState> {
+        if let Some(ConfigOverlayState::Context(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_context(app: &mut App) -> Result<(), String> {
+    let breakdown = compute_breakdown(app);
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::Context(ContextOverlayState { breakdown }));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    if app.config.context_overlay().is_none() {
+        return false;
+    }
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.config.overlay = None,
+        (KeyCode::Char('r'), KeyModifiers::NONE) => {
+            let breakdown = compute_breakdown(app);
+            app.config.overlay =
+                Some(ConfigOverlayState::Context(ContextOverlayState { breakdown }));
+        }
+        _ => {}
+    }
+    true
+}
+
+fn estimate_tokens(chars: usize) -> u64 {
+    (chars as u64).div_ceil(CHARS_PER_TOKEN)
+}
+
+fn tool_call_chars(tc: &ToolCallInfo) -> usize {
+    let input = tc.raw_input.as_ref().map_or(0, |input| input.to_string().len());
+    let content: usize = tc
+        .content
+        .iter()
+        .map(|content| match content {
+            model::ToolCallContent::Content(content) => match &content.content {
+                model::ContentBlock::Text(text) => text.text.len(),
+                model::ContentBlock::Image(_) => 0,
+            },
+            model::ToolCallContent::Diff(diff) => {
+                diff.old_text.as_ref().map_or(0, String::len) + diff.new_text.len()
+            }
+            model::ToolCallContent::McpResource(resource) => {
+                resource.text.as_ref().map_or(0, String::len)
+            }
+            model::ToolCallContent::Terminal(_) => 0,
+        })
+        .sum();
+    input + content + tc.terminal_output.as_ref().map_or(0, String::len)
+}
+
+pub(crate) fn compute_breakdown(app: &App) -> ContextBreakdown {
+    let memory_chars: u64 =
+        crate::app::memory::loaded_memory_files(std::path::Path::new(&app.cwd_raw))
+            .iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+    let mut conversation_chars = 0;
+    let mut tool_chars = 0;
+    for message in &app.messages {
+        if !matches!(message.role, MessageRole::User | MessageRole::Assistant) {
+            continue;
+        }
+        for block in &message.blocks {
+            match block {
+                MessageBlock::Text(text) => conversation_chars += text.text.len(),
+                // Subagent tool calls run in the subagent's own context.
+                MessageBlock::ToolCall(tc) if !tc.hidden => tool_chars += tool_call_chars(tc),
+                // Earlier thinking is stripped from the context the agent resends.
+                MessageBlock::Thinking(_)
+                | MessageBlock::ToolCall(_)
+                | MessageBlock::Welcome(_)
+                | MessageBlock::DiffSummary(_)
+                | MessageBlock::RecoveryHint(_) => {}
+            }
+        }
+    }
+
+    let estimates = [
+        (ContextCategoryKind::Memory, memory_chars.div_ceil(CHARS_PER_TOKEN)),
+        (ContextCategoryKind::Conversation, estimate_tokens(conversation_chars)),
+        (ContextCategoryKind::ToolResults, estimate_tokens(tool_chars)),
+    ];
+    let reported_tokens = app.session_usage.context_tokens();
+    let mut categories = Vec::with_capacity(estimates.len() + 1);
+    if let Some(reported) = reported_tokens {
+        let estimated_total: u64 = estimates.iter().map(|(_, tokens)| tokens).sum();
+        categories.push(ContextCategory {
+            kind: ContextCategoryKind::SystemPrompt,
+            tokens: reported.saturating_sub(estimated_total),
+            estimated: false,
+        });
+    }
+    categories.extend(estimates.into_iter().map(|(kind, tokens)| ContextCategory {
+        kind,
+        tokens,
+        estimated: true,
+    }));
+
+    ContextBreakdown {
+        context_window: app.session_usage.context_window,
+        reported_tokens,
+        categories,
+        compacted: app.session_usage.last_compaction_trigger.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, MessageUsage, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn breakdown_attributes_unexplained_tokens_to_system_prompt() {
+        let mut app = App::test_default();
+        app.messages.push(ChatMessage {
+            role: MessageRole::User,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(&"a".repeat(400)))],
+            usage: None,
+            annotation: None,
+        });
+        app.session_usage.last_request = Some(MessageUsage {
+            input_tokens: Some(10),
+            cache_read_tokens: Some(1_000),
+            cache_write_tokens: Some(90),
+            ..MessageUsage::default()
+        });
+        app.session_usage.context_window = Some(200_000);
+
+        let breakdown = compute_breakdown(&app);
+
+        assert_eq!(breakdown.reported_tokens, Some(1_100));
+        assert_eq!(breakdown.used_tokens(), 1_100);
+        let tokens = |kind| {
+            breakdown.categories.iter().find(|category| category.kind == kind).map(|c| c.tokens)
+        };
+        assert_eq!(tokens(ContextCategoryKind::Conversation), Some(100));
+        let memory = tokens(ContextCategoryKind::Memory).unwrap_or(0);
+        assert_eq!(
+            tokens(ContextCategoryKind::SystemPrompt),
+            Some(1_000u64.saturating_sub(memory))
+        );
+    }
+
+    #[test]
+    fn breakdown_without_usage_reports_only_estimates() {
+        let app = App::test_default();
+        let breakdown = compute_breakdown(&app);
+        assert_eq!(breakdown.reported_tokens, None);
+        assert!(breakdown.categories.iter().all(|category| category.estimated));
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/diagnostics` overlay: the captured stderr of the agent process, shown on
+//! top of the Status tab. Tab switches to the bridge events this build could
+//! not decode.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Lines moved by `PageUp` / `PageDown`.
+const PAGE_LINES: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiagnosticsView {
+    #[default]
+    Stderr,
+    UnhandledEvents,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiagnosticsOverlayState {
+    pub view: DiagnosticsView,
+    /// Lines scrolled up from the newest output; `0` follows new output.
+    pub scroll_from_bottom: usize,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn diagnostics_overlay(&self) -> Option<&DiagnosticsOverlayState> {
+        if let Some(ConfigOverlayState::Diagnostics(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn diagnostics_overlay_mut(&mut self) -> Option<&mut DiagnosticsOverlayState> {
+        if let Some(ConfigOverlayState::Diagnostics(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_diagnostics(app: &mut App) -> Result<(), String> {
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::Diagnostics(DiagnosticsOverlayState::default()));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(view) = app.config.diagnostics_overlay().map(|overlay| overlay.view) else {
+        return false;
+    };
+    let max_scroll = match view {
+        DiagnosticsView::Stderr => app.bridge_stderr.borrow().len(),
+        DiagnosticsView::UnhandledEvents => app.unhandled_events.line_count(),
+    }
+    .saturating_sub(1);
+    let Some(overlay) = app.config.diagnostics_overlay_mut() else {
+        return false;
+    };
+    let scroll = &mut overlay.scroll_from_bottom;
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.config.overlay = None,
+        (KeyCode::Tab, KeyModifiers::NONE) => {
+            overlay.view = match view {
+                DiagnosticsView::Stderr => DiagnosticsView::UnhandledEvents,
+                DiagnosticsView::UnhandledEvents => DiagnosticsView::Stderr,
+            };
+            overlay.scroll_from_bottom = 0;
+        }
+        (KeyCode::Up, KeyModifiers::NONE) => *scroll = (*scroll + 1).min(max_scroll),
+        (KeyCode::Down, KeyModifiers::NONE) => *scroll = scroll.saturating_sub(1),
+        (KeyCode::PageUp, KeyModifiers::NONE) => *scroll = (*scroll + PAGE_LINES).min(max_scroll),
+        (KeyCode::PageDown, KeyModifiers::NONE) => *scroll = scroll.saturating_sub(PAGE_LINES),
+        (KeyCode::Home, KeyModifiers::NONE) => *scroll = max_scroll,
+        (KeyCode::End, KeyModifiers::NONE) => *scroll = 0,
+        (KeyCode::Char('c'), KeyModifiers::NONE) => {
+            *scroll = 0;
+            match view {
+                DiagnosticsView::Stderr => app.bridge_stderr.borrow_mut().clear(),
+                DiagnosticsView::UnhandledEvents => app.unhandled_events.clear(),
+            }
+        }
+        _ => {}
+    }
+    true
+}
@@ -1,10 +1,11 @@
 use super::resolve::{language_input_validation_message, normalized_language_value};
 use super::{
-    AddMarketplaceOverlayState, ConfigOverlayState, DEFAULT_EFFORT_LEVELS, DEFAULT_MODEL_ID,
-    DEFAULT_MODEL_LABEL, DefaultPermissionMode, LanguageOverlayState, ModelAndEffortOverlayState,
-    OutputStyle, OutputStyleOverlayState, OverlayFocus, PendingSessionTitleChangeKind,
-    PendingSessionTitleChangeState, PreferredNotifChannel, ResolvedChoice, ResolvedSettingValue,
-    SessionRenameOverlayState, SettingFile, SettingId, SettingOptions, SettingSpec,
+    AddMarketplaceOverlayState, ClipboardMode, ConfigOverlayState, DEFAULT_EFFORT_LEVELS,
+    DEFAULT_MODEL_ID, DEFAULT_MODEL_LABEL, DefaultPermissionMode, LanguageOverlayState,
+    ModelAndEffortOverlayState, OutputStyle, OutputStyleOverlayState, OverlayFocus,
+    PendingSessionTitleChangeKind, PendingSessionTitleChangeState, PreferredNotifChannel,
+    ResolvedChoice, ResolvedSettingValue, ScrollAnimation, SessionRenameOverlayState, SettingFile,
+    SettingId, SettingOptions, SettingSpec, SystemMessageRouting, ThinkingDisplay, WelcomeBanner,
     resolved_setting, setting_display_value, setting_spec, store,
 };
 use crate::agent::model::EffortLevel;
@@ -14,81 +15,146 @@ use serde_json::Value;
 
 pub(super) fn activate_setting(app: &mut App, spec: &SettingSpec) {
     match spec.id {
-        SettingId::AlwaysThinking => {
-            let next = !store::always_thinking_enabled(&app.config.committed_settings_document)
-                .unwrap_or(false);
-            persist_setting_change(app, spec, |document| {
-                store::set_always_thinking_enabled(document, next);
-            });
-        }
-        SettingId::ShowTips => {
-            let next = !store::spinner_tips_enabled(&app.config.committed_local_settings_document)
-                .unwrap_or(true);
-            persist_setting_change(app, spec, |document| {
-                store::set_spinner_tips_enabled(document, next);
-            });
-        }
-        SettingId::TerminalProgressBar => {
-            let next =
-                !store::terminal_progress_bar_enabled(&app.config.committed_preferences_document)
-                    .unwrap_or(true);
-            persist_setting_change(app, spec, |document| {
-                store::set_terminal_progress_bar_enabled(document, next);
-            });
-        }
-        SettingId::ReduceMotion => {
-            let next =
-                !store::prefers_reduced_motion(&app.config.committed_local_settings_document)
-                    .unwrap_or(false);
-            persist_setting_change(app, spec, |document| {
-                store::set_prefers_reduced_motion(document, next);
-            });
-        }
-        SettingId::FastMode => {
-            let next = !store::fast_mode(&app.config.committed_settings_document).unwrap_or(false);
-            persist_setting_change(app, spec, |document| {
-                store::set_fast_mode(document, next);
-            });
-        }
-        SettingId::RespectGitignore => {
-            let next = !store::respect_gitignore(&app.config.committed_preferences_document)
-                .unwrap_or(true);
-            persist_setting_change(app, spec, |document| {
-                store::set_respect_gitignore(document, next);
-            });
-        }
-        SettingId::DefaultPermissionMode => {
-            let current = match super::resolve::resolve_setting_document(
-                &app.config.committed_settings_document,
-                SettingId::DefaultPermissionMode,
-                &[],
-            )
-            .value
-            {
-                ResolvedSettingValue::Choice(ResolvedChoice::Stored(value)) => {
-                    DefaultPermissionMode::from_stored(&value).unwrap_or_default()
-                }
-                ResolvedSettingValue::Bool(_)
-                | ResolvedSettingValue::Choice(ResolvedChoice::Automatic)
-                | ResolvedSettingValue::Text(_) => DefaultPermissionMode::Default,
-            };
-            let next = current.next();
-            persist_setting_change(app, spec, |document| {
-                store::set_default_permission_mode(document, next);
-            });
-        }
+        SettingId::AlwaysThinking
+        | SettingId::ShowTips
+        | SettingId::TerminalProgressBar
+        | SettingId::ReduceMotion
+        | SettingId::FastMode
+        | SettingId::RespectGitignore
+        | SettingId::EmojiAutocomplete
+        | SettingId::PastePathsAsMentions
+        | SettingId::OfflineMode
+        | SettingId::ShowMessageUsage
+        | SettingId::FollowUpSuggestions
+        | SettingId::ScreenReader
+        | SettingId::HighContrast
+        | SettingId::ShowMinimap
+        | SettingId::RevisionDiffs
+        | SettingId::AutoRetryTools => toggle_setting(app, spec),
+        SettingId::DefaultPermissionMode => cycle_default_permission_mode(app, spec, 1),
         SettingId::Language => open_language_overlay(app),
         SettingId::Model => open_model_and_effort_overlay(app, OverlayFocus::Model),
         SettingId::OutputStyle => open_output_style_overlay(app),
         SettingId::ThinkingEffort => {
             open_model_and_effort_overlay(app, OverlayFocus::Effort);
         }
-        SettingId::Theme | SettingId::Notifications | SettingId::EditorMode => {
+        SettingId::Theme
+        | SettingId::Notifications
+        | SettingId::EditorMode
+        | SettingId::UpdateChannel
+        | SettingId::ScrollAnimation
+        | SettingId::Clipboard
+        | SettingId::SystemMessages => {
+            cycle_static_enum(app, spec, 1);
+        }
+        SettingId::ThinkingDisplay => {
             cycle_static_enum(app, spec, 1);
+            app.invalidate_thinking_blocks();
         }
+        SettingId::WelcomeBanner => {
+            cycle_static_enum(app, spec, 1);
+            crate::app::welcome::sync_welcome_banner(app);
+        }
+    }
+}
+
+/// Flip a boolean setting and persist it.
+fn toggle_setting(app: &mut App, spec: &SettingSpec) {
+    let (current, set): (bool, fn(&mut Value, bool)) = match spec.id {
+        SettingId::AlwaysThinking => (
+            store::always_thinking_enabled(&app.config.committed_settings_document)
+                .unwrap_or(false),
+            store::set_always_thinking_enabled,
+        ),
+        SettingId::ShowTips => (
+            store::spinner_tips_enabled(&app.config.committed_local_settings_document)
+                .unwrap_or(true),
+            store::set_spinner_tips_enabled,
+        ),
+        SettingId::TerminalProgressBar => (
+            store::terminal_progress_bar_enabled(&app.config.committed_preferences_document)
+                .unwrap_or(true),
+            store::set_terminal_progress_bar_enabled,
+        ),
+        SettingId::ReduceMotion => (
+            store::prefers_reduced_motion(&app.config.committed_local_settings_document)
+                .unwrap_or(false),
+            store::set_prefers_reduced_motion,
+        ),
+        SettingId::FastMode => (
+            store::fast_mode(&app.config.committed_settings_document).unwrap_or(false),
+            store::set_fast_mode,
+        ),
+        SettingId::RespectGitignore => (
+            store::respect_gitignore(&app.config.committed_preferences_document).unwrap_or(true),
+            store::set_respect_gitignore,
+        ),
+        SettingId::EmojiAutocomplete => (
+            store::emoji_autocomplete(&app.config.committed_preferences_document).unwrap_or(true),
+            store::set_emoji_autocomplete,
+        ),
+        SettingId::PastePathsAsMentions => (
+            store::paste_paths_as_mentions(&app.config.committed_preferences_document)
+                .unwrap_or(false),
+            store::set_paste_paths_as_mentions,
+        ),
+        SettingId::OfflineMode => (
+            store::offline_mode(&app.config.committed_preferences_document).unwrap_or(false),
+            store::set_offline_mode,
+        ),
+        SettingId::ShowMessageUsage => {
+            (app.config.show_message_usage_effective(), store::set_show_message_usage)
+        }
+        SettingId::FollowUpSuggestions => {
+            (app.config.follow_up_suggestions_effective(), store::set_follow_up_suggestions)
+        }
+        SettingId::ScreenReader => {
+            (app.config.screen_reader_effective(), store::set_screen_reader_mode)
+        }
+        SettingId::HighContrast => (app.config.high_contrast_effective(), store::set_high_contrast),
+        SettingId::ShowMinimap => (app.config.show_minimap_effective(), store::set_show_minimap),
+        SettingId::RevisionDiffs => {
+            (app.config.revision_diffs_effective(), store::set_revision_diffs)
+        }
+        SettingId::AutoRetryTools => {
+            (app.config.auto_retry_tools_effective(), store::set_auto_retry_tools)
+        }
+        _ => return,
+    };
+    let next = !current;
+    persist_setting_change(app, spec, |document| set(document, next));
+    match spec.id {
+        SettingId::OfflineMode => app.cached_footer_line = None,
+        SettingId::ShowMessageUsage => app.invalidate_layout(crate::app::InvalidationLevel::Global),
+        SettingId::FollowUpSuggestions if !next => crate::app::suggestions::clear_suggestions(app),
+        SettingId::ScreenReader => crate::app::screen_reader::sync(app),
+        SettingId::RevisionDiffs if next => crate::app::revisions::mark_all(app),
+        SettingId::RevisionDiffs => crate::app::revisions::clear_all(app),
+        _ => {}
     }
 }
 
+fn cycle_default_permission_mode(app: &mut App, spec: &SettingSpec, delta: isize) {
+    let current = match super::resolve::resolve_setting_document(
+        &app.config.committed_settings_document,
+        SettingId::DefaultPermissionMode,
+        &[],
+    )
+    .value
+    {
+        ResolvedSettingValue::Choice(ResolvedChoice::Stored(value)) => {
+            DefaultPermissionMode::from_stored(&value).unwrap_or_default()
+        }
+        ResolvedSettingValue::Bool(_)
+        | ResolvedSettingValue::Choice(ResolvedChoice::Automatic)
+        | ResolvedSettingValue::Text(_) => DefaultPermissionMode::Default,
+    };
+    let next = if delta.is_negative() { current.prev() } else { current.next() };
+    persist_setting_change(app, spec, |document| {
+        store::set_default_permission_mode(document, next);
+    });
+}
+
 pub(super) fn step_setting(app: &mut App, spec: &SettingSpec, delta: isize) {
     match spec.id {
         SettingId::AlwaysThinking
@@ -96,29 +162,34 @@ pub(super) fn step_setting(app: &mut App, spec: &SettingSpec, delta: isize) {
         | SettingId::TerminalProgressBar
         | SettingId::ReduceMotion
         | SettingId::FastMode
-        | SettingId::RespectGitignore => activate_setting(app, spec),
-        SettingId::DefaultPermissionMode => {
-            let current = match super::resolve::resolve_setting_document(
-                &app.config.committed_settings_document,
-                SettingId::DefaultPermissionMode,
-                &[],
-            )
-            .value
-            {
-                ResolvedSettingValue::Choice(ResolvedChoice::Stored(value)) => {
-                    DefaultPermissionMode::from_stored(&value).unwrap_or_default()
-                }
-                ResolvedSettingValue::Bool(_)
-                | ResolvedSettingValue::Choice(ResolvedChoice::Automatic)
-                | ResolvedSettingValue::Text(_) => DefaultPermissionMode::Default,
-            };
-            let next = if delta.is_negative() { current.prev() } else { current.next() };
-            persist_setting_change(app, spec, |document| {
-                store::set_default_permission_mode(document, next);
-            });
+        | SettingId::RespectGitignore
+        | SettingId::PastePathsAsMentions
+        | SettingId::EmojiAutocomplete
+        | SettingId::FollowUpSuggestions
+        | SettingId::OfflineMode
+        | SettingId::HighContrast
+        | SettingId::ScreenReader
+        | SettingId::RevisionDiffs
+        | SettingId::ShowMessageUsage
+        | SettingId::ShowMinimap
+        | SettingId::AutoRetryTools => activate_setting(app, spec),
+        SettingId::DefaultPermissionMode => cycle_default_permission_mode(app, spec, delta),
+        SettingId::Theme
+        | SettingId::Notifications
+        | SettingId::EditorMode
+        | SettingId::UpdateChannel
+        | SettingId::ScrollAnimation
+        | SettingId::Clipboard
+        | SettingId::SystemMessages => {
+            cycle_static_enum(app, spec, delta);
         }
-        SettingId::Theme | SettingId::Notifications | SettingId::EditorMode => {
+        SettingId::ThinkingDisplay => {
             cycle_static_enum(app, spec, delta);
+            app.invalidate_thinking_blocks();
+        }
+        SettingId::WelcomeBanner => {
+            cycle_static_enum(app, spec, delta);
+            crate::app::welcome::sync_welcome_banner(app);
         }
         SettingId::Language
         | SettingId::Model
@@ -130,7 +201,17 @@ pub(super) fn step_setting(app: &mut App, spec: &SettingSpec, delta: isize) {
 }
 
 pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) {
-    if super::mcp_edit::handle_overlay_key(app, key) {
+    if super::mcp_edit::handle_overlay_key(app, key)
+        || super::diagnostics::handle_overlay_key(app, key)
+        || super::login::handle_overlay_key(app, key)
+        || super::release_notes::handle_overlay_key(app, key)
+        || super::context::handle_overlay_key(app, key)
+        || super::changed_files::handle_overlay_key(app, key)
+        || super::commit::handle_overlay_key(app, key)
+        || super::pull_request::handle_overlay_key(app, key)
+        || super::file_browser::handle_overlay_key(app, key)
+        || super::model_params::handle_overlay_key(app, key)
+    {
         return;
     }
     match app.config.overlay.clone() {
@@ -166,7 +247,16 @@ pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) {
             ConfigOverlayState::McpDetails(_)
             | ConfigOverlayState::McpCallbackUrl(_)
             | ConfigOverlayState::McpAuthRedirect(_)
-            | ConfigOverlayState::McpElicitation(_),
+            | ConfigOverlayState::McpElicitation(_)
+            | ConfigOverlayState::Diagnostics(_)
+            | ConfigOverlayState::Login(_)
+            | ConfigOverlayState::ReleaseNotes(_)
+            | ConfigOverlayState::Context(_)
+            | ConfigOverlayState::ChangedFiles(_)
+            | ConfigOverlayState::Commit(_)
+            | ConfigOverlayState::PullRequest(_)
+            | ConfigOverlayState::FileBrowser(_)
+            | ConfigOverlayState::ModelParams(_),
         )
         | None => {}
         Some(ConfigOverlayState::Language(_)) => handle_language_overlay_key(app, key),
@@ -175,7 +265,11 @@ pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) {
 }
 
 pub(super) fn handle_overlay_paste(app: &mut App, text: &str) -> bool {
-    if super::mcp_edit::handle_overlay_paste(app, text) {
+    if super::mcp_edit::handle_overlay_paste(app, text)
+        || super::login::handle_overlay_paste(app, text)
+        || super::commit::handle_overlay_paste(app, text)
+        || super::pull_request::handle_overlay_paste(app, text)
+    {
         return true;
     }
     match app.config.overlay {
@@ -200,12 +294,26 @@ pub(super) fn handle_overlay_paste(app: &mut App, text: &str) -> bool {
             | ConfigOverlayState::McpDetails(_)
             | ConfigOverlayState::McpCallbackUrl(_)
             | ConfigOverlayState::McpAuthRedirect(_)
-            | ConfigOverlayState::McpElicitation(_),
+            | ConfigOverlayState::McpElicitation(_)
+            | ConfigOverlayState::Diagnostics(_)
+            | ConfigOverlayState::Login(_)
+            | ConfigOverlayState::ReleaseNotes(_)
+            | ConfigOverlayState::Context(_)
+            | ConfigOverlayState::ChangedFiles(_)
+            | ConfigOverlayState::Commit(_)
+            | ConfigOverlayState::PullRequest(_)
+            | ConfigOverlayState::FileBrowser(_)
+            | ConfigOverlayState::ModelParams(_),
         )
         | None => false,
     }
 }
 
+/// Flip the message usage annotations from outside the settings view (Ctrl+k).
+pub(crate) fn toggle_show_message_usage(app: &mut App) {
+    activate_setting(app, setting_spec(SettingId::ShowMessageUsage));
+}
+
 pub(crate) fn model_supports_effort(app: &App, model_id: &str) -> bool {
     if model_id == DEFAULT_MODEL_ID {
         return true;
@@ -357,6 +465,12 @@ const fn default_static_value(setting_id: SettingId) -> &'static str {
         SettingId::ThinkingEffort => "medium",
         SettingId::Notifications => "iterm2",
         SettingId::EditorMode => "default",
+        SettingId::UpdateChannel => "stable",
+        SettingId::ThinkingDisplay => ThinkingDisplay::Collapsed.as_stored(),
+        SettingId::ScrollAnimation => ScrollAnimation::Smooth.as_stored(),
+        SettingId::Clipboard => ClipboardMode::Auto.as_stored(),
+        SettingId::SystemMessages => SystemMessageRouting::Transcript.as_stored(),
+        SettingId::WelcomeBanner => WelcomeBanner::Full.as_stored(),
         SettingId::AlwaysThinking
         | SettingId::ReduceMotion
         | SettingId::ShowTips
@@ -365,6 +479,16 @@ const fn default_static_value(setting_id: SettingId) -> &'static str {
         | SettingId::DefaultPermissionMode
         | SettingId::Language
         | SettingId::RespectGitignore
+        | SettingId::PastePathsAsMentions
+        | SettingId::EmojiAutocomplete
+        | SettingId::FollowUpSuggestions
+        | SettingId::OfflineMode
+        | SettingId::HighContrast
+        | SettingId::ScreenReader
+        | SettingId::RevisionDiffs
+        | SettingId::ShowMessageUsage
+        | SettingId::ShowMinimap
+        | SettingId::AutoRetryTools
         | SettingId::Model => "",
     }
 }
@@ -717,7 +841,7 @@ fn step_index_wrapped(current: usize, delta: isize, len: usize) -> usize {
     }
 }
 
-fn char_to_byte_index(text: &str, char_index: usize) -> usize {
+pub(super) fn char_to_byte_index(text: &str, char_index: usize) -> usize {
     text.char_indices().nth(char_index).map_or(text.len(), |(idx, _)| idx)
 }
 
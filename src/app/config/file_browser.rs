@@ -0,0 +1,445 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! File browser overlay (Ctrl+b): a tree of the session directory built from
+//! the `@` mention index, so it honors the same gitignore setting. Files the
+//! session touched are badged; `t` walks through them. Rows insert an `@`
+//! mention, open a scrollable preview or open the file in the editor.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crate::app::attachments::{MentionFileKind, classify_path};
+use crate::app::toast::{ToastKind, show_toast};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Lines moved by `PageUp` / `PageDown`.
+const PAGE_LINES: usize = 10;
+/// Lines of a file loaded into the preview.
+const PREVIEW_MAX_LINES: usize = 2_000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileBrowserOverlayState {
+    /// Expanded directories, relative with a trailing `/`.
+    pub expanded: BTreeSet<String>,
+    /// Relative path of the highlighted row; kept by path so index updates
+    /// do not move the selection.
+    pub selected: Option<String>,
+    pub preview: Option<FilePreview>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilePreview {
+    pub rel_path: String,
+    pub lines: Vec<String>,
+    /// Shown instead of the lines for files without a text preview.
+    pub notice: Option<String>,
+    pub scroll: usize,
+}
+
+/// One visible row of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTreeRow {
+    pub rel_path: String,
+    pub name: String,
+    pub depth: usize,
+    pub is_dir: bool,
+    pub expanded: bool,
+    pub touch: Option<FileTouch>,
+}
+
+/// How the session touched a file, or any file under a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileTouch {
+    /// Read or mentioned.
+    Seen,
+    /// Changed by an edit tool.
+    Edited,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn file_browser_overlay(&self) -> Option<&FileBrowserOverlayState> {
+        if let Some(ConfigOverlayState::FileBrowser(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn file_browser_overlay_mut(&mut self) -> Option<&mut FileBrowserOverlayState> {
+        if let Some(ConfigOverlayState::FileBrowser(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+/// Ctrl+b from the chat: open the browser, or close it when it is showing.
+pub(crate) fn toggle_file_browser(app: &mut App) -> Result<(), String> {
+    if app.config.file_browser_overlay().is_some() {
+        close_browser(app);
+        return Ok(());
+    }
+    crate::app::mention::ensure_file_index(app);
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::FileBrowser(FileBrowserOverlayState::default()));
+    Ok(())
+}
+
+fn close_browser(app: &mut App) {
+    app.config.overlay = None;
+    super::close(app);
+}
+
+/// The rows currently visible: children of expanded directories, in tree order.
+#[must_use]
+pub fn visible_rows(app: &App, overlay: &FileBrowserOverlayState) -> Vec<FileTreeRow> {
+    let Some(index) = app.file_index.as_ref() else {
+        return Vec::new();
+    };
+    let touched = touched_files(app);
+    let mut rows = Vec::new();
+    push_rows(index, "", 0, &overlay.expanded, &touched, &mut rows);
+    rows
+}
+
+fn push_rows(
+    index: &crate::app::file_index::FileIndex,
+    dir_rel: &str,
+    depth: usize,
+    expanded: &BTreeSet<String>,
+    touched: &[(String, FileTouch)],
+    rows: &mut Vec<FileTreeRow>,
+) {
+    for candidate in index.children(dir_rel) {
+        let rel_path = candidate.rel_path.clone();
+        let name = rel_path[dir_rel.len()..].to_owned();
+        let is_open = candidate.is_dir && expanded.contains(&rel_path);
+        let touch =
+            touched
+                .iter()
+                .filter(|(path, _)| {
+                    if candidate.is_dir { path.starts_with(&rel_path) } else { *path == rel_path }
+                })
+                .map(|(_, touch)| *touch)
+                .max();
+        rows.push(FileTreeRow {
+            rel_path: rel_path.clone(),
+            name,
+            depth,
+            is_dir: candidate.is_dir,
+            expanded: is_open,
+            touch,
+        });
+        if is_open {
+            push_rows(index, &rel_path, depth + 1, expanded, touched, rows);
+        }
+    }
+}
+
+/// Files the session touched, relative to the session directory, in recency
+/// order followed by files only known from edits.
+fn touched_files(app: &App) -> Vec<(String, FileTouch)> {
+    let cwd = Path::new(&app.cwd_raw);
+    let edited: Vec<String> = app
+        .changed_files
+        .files()
+        .filter_map(|file| file.path.strip_prefix(cwd).ok())
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .collect();
+    let mut touched: Vec<(String, FileTouch)> = app
+        .recent_files
+        .iter()
+        .map(|path| {
+            let touch =
+                if edited.iter().any(|e| e == path) { FileTouch::Edited } else { FileTouch::Seen };
+            (path.to_owned(), touch)
+        })
+        .collect();
+    for path in edited {
+        if !touched.iter().any(|(known, _)| *known == path) {
+            touched.push((path, FileTouch::Edited));
+        }
+    }
+    touched
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overlay) = app.config.file_browser_overlay() else {
+        return false;
+    };
+    if overlay.preview.is_some() {
+        handle_preview_key(app, key);
+        return true;
+    }
+    let rows = visible_rows(app, overlay);
+    let current = overlay
+        .selected
+        .as_ref()
+        .and_then(|selected| rows.iter().position(|row| row.rel_path == *selected))
+        .unwrap_or(0);
+    let row = rows.get(current).cloned();
+    let last = rows.len().saturating_sub(1);
+    let select = |app: &mut App, idx: usize| {
+        let path = rows.get(idx).map(|row| row.rel_path.clone());
+        if let Some(overlay) = app.config.file_browser_overlay_mut() {
+            overlay.selected = path;
+        }
+    };
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+            close_browser(app);
+        }
+        (KeyCode::Up, KeyModifiers::NONE) => select(app, current.saturating_sub(1)),
+        (KeyCode::Down, KeyModifiers::NONE) => select(app, (current + 1).min(last)),
+        (KeyCode::PageUp, KeyModifiers::NONE) => select(app, current.saturating_sub(PAGE_LINES)),
+        (KeyCode::PageDown, KeyModifiers::NONE) => select(app, (current + PAGE_LINES).min(last)),
+        (KeyCode::Home, KeyModifiers::NONE) => select(app, 0),
+        (KeyCode::End, KeyModifiers::NONE) => select(app, last),
+        (KeyCode::Right, KeyModifiers::NONE) => {
+            if let Some(row) = row.filter(|row| row.is_dir) {
+                if row.expanded {
+                    select(app, (current + 1).min(last));
+                } else {
+                    set_expanded(app, &row.rel_path, true);
+                }
+            }
+        }
+        (KeyCode::Left, KeyModifiers::NONE) => {
+            if let Some(row) = row {
+                if row.is_dir && row.expanded {
+                    set_expanded(app, &row.rel_path, false);
+                } else if let Some(parent) = parent_dir(&row.rel_path)
+                    && let Some(overlay) = app.config.file_browser_overlay_mut()
+                {
+                    overlay.selected = Some(parent.to_owned());
+                }
+            }
+        }
+        (KeyCode::Enter, KeyModifiers::NONE) => match row {
+            Some(row) if row.is_dir => set_expanded(app, &row.rel_path, !row.expanded),
+            Some(row) => open_preview(app, &row.rel_path),
+            None => {}
+        },
+        (KeyCode::Char('p'), KeyModifiers::NONE) => {
+            if let Some(row) = row.filter(|row| !row.is_dir) {
+                open_preview(app, &row.rel_path);
+            }
+        }
+        (KeyCode::Char('@' | 'm'), _) => {
+            if let Some(row) = row {
+                insert_mention(app, &row.rel_path);
+            }
+        }
+        (KeyCode::Char('e'), KeyModifiers::NONE) => {
+            if let Some(row) = row.filter(|row| !row.is_dir) {
+                open_in_editor(app, &row.rel_path);
+            }
+        }
+        (KeyCode::Char('t'), KeyModifiers::NONE) => reveal_next_touched(app),
+        _ => {}
+    }
+    true
+}
+
+fn handle_preview_key(app: &mut App, key: KeyEvent) {
+    let Some(preview) = app.config.file_browser_overlay_mut().and_then(|o| o.preview.as_mut())
+    else {
+        return;
+    };
+    let last = preview.lines.len().saturating_sub(1);
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => {
+            if let Some(overlay) = app.config.file_browser_overlay_mut() {
+                overlay.preview = None;
+            }
+        }
+        (KeyCode::Char('b'), KeyModifiers::CONTROL) => close_browser(app),
+        (KeyCode::Up, KeyModifiers::NONE) => preview.scroll = preview.scroll.saturating_sub(1),
+        (KeyCode::Down, KeyModifiers::NONE) => preview.scroll = (preview.scroll + 1).min(last),
+        (KeyCode::PageUp, KeyModifiers::NONE) => {
+            preview.scroll = preview.scroll.saturating_sub(PAGE_LINES);
+        }
+        (KeyCode::PageDown, KeyModifiers::NONE) => {
+            preview.scroll = (preview.scroll + PAGE_LINES).min(last);
+        }
+        (KeyCode::Home, KeyModifiers::NONE) => preview.scroll = 0,
+        (KeyCode::End, KeyModifiers::NONE) => preview.scroll = last,
+        (KeyCode::Char('@' | 'm'), _) => {
+            let rel_path = preview.rel_path.clone();
+            insert_mention(app, &rel_path);
+        }
+        (KeyCode::Char('e'), KeyModifiers::NONE) => {
+            let rel_path = preview.rel_path.clone();
+            open_in_editor(app, &rel_path);
+        }
+        _ => {}
+    }
+}
+
+fn set_expanded(app: &mut App, dir_rel: &str, expanded: bool) {
+    let Some(overlay) = app.config.file_browser_overlay_mut() else {
+        return;
+    };
+    if expanded {
+        overlay.expanded.insert(dir_rel.to_owned());
+    } else {
+        overlay.expanded.remove(dir_rel);
+    }
+}
+
+/// The directory containing `rel_path`, with its trailing `/`.
+fn parent_dir(rel_path: &str) -> Option<&str> {
+    let trimmed = rel_path.trim_end_matches('/');
+    trimmed.rfind('/').map(|idx| &trimmed[..=idx])
+}
+
+fn open_preview(app: &mut App, rel_path: &str) {
+    let path = Path::new(&app.cwd_raw).join(rel_path);
+    let (lines, notice) = match classify_path(&path) {
+        Some(MentionFileKind::Text | MentionFileKind::Large { .. }) => {
+            (crate::app::mention::load_preview(&path, PREVIEW_MAX_LINES), None)
+        }
+        Some(MentionFileKind::Image { .. }) => {
+            (Vec::new(), Some("Image file, no text preview.".to_owned()))
+        }
+        Some(MentionFileKind::Binary { .. }) => {
+            (Vec::new(), Some("Binary file, no text preview.".to_owned()))
+        }
+        None => (Vec::new(), Some("The file cannot be read.".to_owned())),
+    };
+    if let Some(overlay) = app.config.file_browser_overlay_mut() {
+        overlay.preview =
+            Some(FilePreview { rel_path: rel_path.to_owned(), lines, notice, scroll: 0 });
+    }
+}
+
+fn insert_mention(app: &mut App, rel_path: &str) {
+    if rel_path.chars().any(char::is_whitespace) {
+        show_toast(app, ToastKind::Warning, "Paths with spaces cannot be @ mentioned");
+        return;
+    }
+    close_browser(app);
+    let needs_space = app
+        .input
+        .lines()
+        .get(app.input.cursor_row())
+        .and_then(|line| line.chars().nth(app.input.cursor_col().checked_sub(1)?))
+        .is_some_and(|ch| !ch.is_whitespace());
+    let mention = format!("{}@{rel_path} ", if needs_space { " " } else { "" });
+    app.input.insert_str(&mention);
+    app.recent_files.touch(rel_path);
+}
+
+fn open_in_editor(app: &mut App, rel_path: &str) {
+    let path: PathBuf = Path::new(&app.cwd_raw).join(rel_path);
+    if let Err(err) = crate::app::memory::open_in_editor(app, &path) {
+        show_toast(app, ToastKind::Error, err);
+    }
+}
+
+/// Select the next touched file after the current row, expanding its parents.
+fn reveal_next_touched(app: &mut App) {
+    let mut touched: Vec<String> = touched_files(app).into_iter().map(|(path, _)| path).collect();
+    if touched.is_empty() {
+        show_toast(app, ToastKind::Info, "No files touched in this session yet");
+        return;
+    }
+    touched.sort();
+    let Some(overlay) = app.config.file_browser_overlay_mut() else {
+        return;
+    };
+    let next = overlay
+        .selected
+        .as_ref()
+        .and_then(|selected| touched.iter().find(|path| *path > selected))
+        .unwrap_or(&touched[0])
+        .clone();
+    let mut dir = next.as_str();
+    while let Some(parent) = parent_dir(dir) {
+        overlay.expanded.insert(parent.to_owned());
+        dir = parent;
+    }
+    overlay.selected = Some(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::{Duration, Instant};
+
+    fn app_with_tree() -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src/app")).expect("create dirs");
+        std::fs::write(dir.path().join("README.md"), "# readme\n").expect("write readme");
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").expect("write main");
+        std::fs::write(dir.path().join("src/app/state.rs"), "").expect("write state");
+        let mut app = App::test_default();
+        app.cwd_raw = dir.path().to_string_lossy().into_owned();
+        crate::app::mention::ensure_file_index(&mut app);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.file_index.as_ref().is_some_and(crate::app::file_index::FileIndex::is_indexing)
+            && Instant::now() < deadline
+        {
+            if let Some(index) = app.file_index.as_mut() {
+                index.drain();
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        app.config.overlay =
+            Some(ConfigOverlayState::FileBrowser(FileBrowserOverlayState::default()));
+        (dir, app)
+    }
+
+    fn visible(app: &App) -> Vec<String> {
+        let overlay = app.config.file_browser_overlay().expect("browser overlay");
+        visible_rows(app, overlay).into_iter().map(|row| row.rel_path).collect()
+    }
+
+    #[test]
+    fn reveal_expands_parents_of_touched_files_and_badges_them() {
+        let (_dir, mut app) = app_with_tree();
+        assert_eq!(visible(&app), vec!["src/", "README.md"]);
+
+        app.recent_files.touch("src/app/state.rs");
+        handle_overlay_key(&mut app, KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+
+        assert_eq!(
+            visible(&app),
+            vec!["src/", "src/app/", "src/app/state.rs", "src/main.rs", "README.md"]
+        );
+        let overlay = app.config.file_browser_overlay().expect("browser overlay");
+        assert_eq!(overlay.selected.as_deref(), Some("src/app/state.rs"));
+        let rows = visible_rows(&app, overlay);
+        assert_eq!(rows[0].touch, Some(FileTouch::Seen));
+        assert_eq!(rows[3].touch, None);
+    }
+
+    #[test]
+    fn enter_previews_files_and_at_inserts_a_mention() {
+        let (_dir, mut app) = app_with_tree();
+        let press = |app: &mut App, code| {
+            handle_overlay_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+        press(&mut app, KeyCode::End);
+        press(&mut app, KeyCode::Enter);
+        let preview = app
+            .config
+            .file_browser_overlay()
+            .and_then(|overlay| overlay.preview.clone())
+            .expect("preview");
+        assert_eq!(preview.rel_path, "README.md");
+        assert_eq!(preview.lines, vec!["# readme"]);
+
+        app.input.set_text("see");
+        press(&mut app, KeyCode::Char('@'));
+
+        assert!(app.config.overlay.is_none());
+        assert_eq!(app.input.text(), "see @README.md ");
+    }
+}
@@ -0,0 +1,484 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/login` overlay: lists the auth methods the agent advertised, runs
+//! `claude auth login` in the background, opens the browser (or shows the URL
+//! and a code prompt), and polls for stored credentials.
+
+use super::edit::{
+    TextInputOverlay, accepts_text_input, delete_text_at_cursor, delete_text_before_cursor,
+    insert_text_char, insert_text_str, move_text_cursor_left, move_text_cursor_right,
+    move_text_cursor_to_end, set_text_cursor, step_index_clamped,
+};
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::agent::client::AgentConnection;
+use crate::agent::events::ClientEvent;
+use crate::agent::types::AuthMethod;
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::rc::Rc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader, Lines};
+use tokio::sync::mpsc;
+
+/// How often the login task checks whether credentials have been written.
+const CREDENTIAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Give up on a login that has not completed after this long.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// CLI output lines kept for display under the URL.
+const MAX_OUTPUT_LINES: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoginOverlayState {
+    pub methods: Vec<AuthMethod>,
+    pub selected_index: usize,
+    pub phase: LoginPhase,
+    /// Authorization code typed or pasted while the CLI is waiting for one.
+    pub code_draft: String,
+    pub code_cursor: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoginPhase {
+    ChooseMethod,
+    Waiting(LoginProgress),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoginProgress {
+    pub method_name: String,
+    pub url: Option<String>,
+    pub browser_opened: bool,
+    pub browser_open_error: Option<String>,
+    /// The CLI asked for a code to be pasted back.
+    pub code_prompted: bool,
+    pub output: Vec<String>,
+}
+
+/// Handle to the running `claude auth login` task. Dropping it cancels the
+/// login and kills the CLI process.
+#[derive(Debug)]
+pub struct LoginFlowHandle {
+    code_tx: mpsc::UnboundedSender<String>,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn login_overlay(&self) -> Option<&LoginOverlayState> {
+        if let Some(ConfigOverlayState::Login(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn login_overlay_mut(&mut self) -> Option<&mut LoginOverlayState> {
+        if let Some(ConfigOverlayState::Login(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+impl TextInputOverlay for LoginOverlayState {
+    fn draft(&self) -> &str {
+        &self.code_draft
+    }
+
+    fn draft_mut(&mut self) -> &mut String {
+        &mut self.code_draft
+    }
+
+    fn cursor(&self) -> usize {
+        self.code_cursor
+    }
+
+    fn cursor_mut(&mut self) -> &mut usize {
+        &mut self.code_cursor
+    }
+}
+
+pub(crate) fn open_login(app: &mut App) -> Result<(), String> {
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay = Some(ConfigOverlayState::Login(LoginOverlayState {
+        methods: login_methods(app),
+        selected_index: 0,
+        phase: LoginPhase::ChooseMethod,
+        code_draft: String::new(),
+        code_cursor: 0,
+    }));
+    Ok(())
+}
+
+/// Auth methods advertised by the agent, falling back to the Claude CLI login
+/// when the agent did not list any.
+fn login_methods(app: &App) -> Vec<AuthMethod> {
+    let advertised = app.agent_info.as_ref().map(|info| info.auth_methods.clone());
+    match advertised {
+        Some(methods) if !methods.is_empty() => methods,
+        _ => vec![AuthMethod {
+            id: "claude-login".to_owned(),
+            name: "Log in with Claude".to_owned(),
+            description: "Sign in with your Claude account in the browser".to_owned(),
+        }],
+    }
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(phase) = app.config.login_overlay().map(|overlay| overlay.phase.clone()) else {
+        return false;
+    };
+    match phase {
+        LoginPhase::ChooseMethod => match (key.code, key.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) => close_login(app),
+            (KeyCode::Up, KeyModifiers::NONE) => move_selection(app, -1),
+            (KeyCode::Down, KeyModifiers::NONE) => move_selection(app, 1),
+            (KeyCode::Enter, KeyModifiers::NONE) => start_login(app),
+            _ => {}
+        },
+        LoginPhase::Waiting(progress) => {
+            let code_prompted = progress.code_prompted;
+            if (key.code, key.modifiers) == (KeyCode::Esc, KeyModifiers::NONE) {
+                cancel_login(app);
+            } else if code_prompted {
+                handle_code_input_key(app, key);
+            }
+        }
+        LoginPhase::Failed(_) => match (key.code, key.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) => close_login(app),
+            (KeyCode::Enter, KeyModifiers::NONE) => start_login(app),
+            _ => {}
+        },
+    }
+    true
+}
+
+pub(super) fn handle_overlay_paste(app: &mut App, text: &str) -> bool {
+    let accepts_code = app.config.login_overlay().is_some_and(
+        |overlay| matches!(&overlay.phase, LoginPhase::Waiting(progress) if progress.code_prompted),
+    );
+    if accepts_code {
+        insert_text_str(app.config.login_overlay_mut(), text.trim());
+    }
+    app.config.login_overlay().is_some()
+}
+
+fn handle_code_input_key(app: &mut App, key: KeyEvent) {
+    match (key.code, key.modifiers) {
+        (KeyCode::Enter, KeyModifiers::NONE) => submit_code(app),
+        (KeyCode::Left, KeyModifiers::NONE) => {
+            move_text_cursor_left(app.config.login_overlay_mut());
+        }
+        (KeyCode::Right, KeyModifiers::NONE) => {
+            move_text_cursor_right(app.config.login_overlay_mut());
+        }
+        (KeyCode::Home, KeyModifiers::NONE) => set_text_cursor(app.config.login_overlay_mut(), 0),
+        (KeyCode::End, KeyModifiers::NONE) => {
+            move_text_cursor_to_end(app.config.login_overlay_mut());
+        }
+        (KeyCode::Backspace, KeyModifiers::NONE) => {
+            delete_text_before_cursor(app.config.login_overlay_mut());
+        }
+        (KeyCode::Delete, KeyModifiers::NONE) => {
+            delete_text_at_cursor(app.config.login_overlay_mut());
+        }
+        (KeyCode::Char(ch), modifiers) if accepts_text_input(modifiers) => {
+            insert_text_char(app.config.login_overlay_mut(), ch);
+        }
+        _ => {}
+    }
+}
+
+fn move_selection(app: &mut App, delta: isize) {
+    if let Some(overlay) = app.config.login_overlay_mut() {
+        overlay.selected_index =
+            step_index_clamped(overlay.selected_index, delta, overlay.methods.len());
+    }
+}
+
+fn close_login(app: &mut App) {
+    app.login_flow = None;
+    app.config.overlay = None;
+    super::close(app);
+}
+
+fn cancel_login(app: &mut App) {
+    close_login(app);
+    crate::app::events::push_system_message_with_severity(
+        app,
+        Some(crate::app::SystemSeverity::Info),
+        "Login cancelled.",
+    );
+}
+
+fn set_phase(app: &mut App, phase: LoginPhase) {
+    if let Some(overlay) = app.config.login_overlay_mut() {
+        overlay.phase = phase;
+        overlay.code_draft.clear();
+        overlay.code_cursor = 0;
+    }
+}
+
+fn start_login(app: &mut App) {
+    let Some(method_name) = app.config.login_overlay().and_then(|overlay| {
+        overlay.methods.get(overlay.selected_index).map(|method| method.name.clone())
+    }) else {
+        return;
+    };
+    let Ok(claude_path) = which::which("claude") else {
+        set_phase(
+            app,
+            LoginPhase::Failed(
+                "claude CLI not found in PATH. Install it and press Enter to retry.".to_owned(),
+            ),
+        );
+        return;
+    };
+    tracing::debug!(path = %claude_path.display(), method = %method_name, "Starting login");
+
+    if app.conn.is_none()
+        && let Some(slot) = crate::app::connect::take_connection_slot()
+    {
+        app.conn = Some(slot.conn);
+    }
+    let (code_tx, code_rx) = mpsc::unbounded_channel();
+    app.login_flow = Some(LoginFlowHandle { code_tx });
+    tokio::task::spawn_local(run_login_process(
+        claude_path,
+        app.event_tx.clone(),
+        app.conn.clone(),
+        code_rx,
+    ));
+    set_phase(app, LoginPhase::Waiting(LoginProgress { method_name, ..LoginProgress::default() }));
+}
+
+fn submit_code(app: &mut App) {
+    let Some(overlay) = app.config.login_overlay_mut() else {
+        return;
+    };
+    let code = overlay.code_draft.trim().to_owned();
+    if code.is_empty() {
+        return;
+    }
+    overlay.code_draft.clear();
+    overlay.code_cursor = 0;
+    if let Some(flow) = &app.login_flow
+        && flow.code_tx.send(code).is_err()
+    {
+        tracing::warn!("login task exited before the code could be sent");
+    }
+}
+
+/// Record a line printed by `claude auth login`: open the first URL in the
+/// browser and switch to code entry once the CLI asks for one.
+pub(crate) fn handle_login_output(app: &mut App, line: &str) {
+    let offline = app.is_offline();
+    let Some(LoginPhase::Waiting(progress)) =
+        app.config.login_overlay_mut().map(|overlay| &mut overlay.phase)
+    else {
+        tracing::debug!(line, "login output without an active login overlay");
+        return;
+    };
+    let line = strip_ansi(line);
+    if progress.url.is_none()
+        && let Some(url) = extract_url(&line)
+    {
+        match super::mcp::open_url_in_browser(offline, &url) {
+            Ok(()) => progress.browser_opened = true,
+            Err(error) => progress.browser_open_error = Some(error),
+        }
+        progress.url = Some(url);
+    }
+    if prompts_for_code(&line) {
+        progress.code_prompted = true;
+    }
+    if !line.trim().is_empty() {
+        progress.output.push(line.trim().to_owned());
+        let excess = progress.output.len().saturating_sub(MAX_OUTPUT_LINES);
+        progress.output.drain(..excess);
+    }
+}
+
+pub(crate) fn handle_login_failed(app: &mut App, message: &str) {
+    tracing::warn!(message, "login failed");
+    app.login_flow = None;
+    if let Some(hint) = &mut app.login_hint {
+        message.clone_into(&mut hint.method_description);
+    }
+    if app.config.login_overlay().is_some() {
+        set_phase(app, LoginPhase::Failed(message.to_owned()));
+    } else {
+        crate::app::events::push_system_message_with_severity(
+            app,
+            Some(crate::app::SystemSeverity::Error),
+            message,
+        );
+    }
+}
+
+/// Credentials were stored: drop the flow and return to the chat.
+pub(crate) fn finish_login(app: &mut App) {
+    app.login_flow = None;
+    if app.config.login_overlay().is_some() {
+        close_login(app);
+    }
+}
+
+fn extract_url(line: &str) -> Option<String> {
+    let start = line.find("https://")?;
+    let url: String = line[start..]
+        .chars()
+        .take_while(|ch| !ch.is_whitespace() && !matches!(ch, '"' | '\'' | '<' | '>' | ')'))
+        .collect();
+    Some(url.trim_end_matches(['.', ',']).to_owned())
+}
+
+fn prompts_for_code(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    lower.contains("code") && (lower.contains("paste") || lower.contains("enter"))
+}
+
+/// Remove CSI escape sequences the CLI uses for colors and cursor control.
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            if chars.next() == Some('[') {
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        out.push(ch);
+    }
+    out
+}
+
+async fn run_login_process(
+    claude_path: PathBuf,
+    tx: mpsc::UnboundedSender<ClientEvent>,
+    conn: Option<Rc<AgentConnection>>,
+    mut code_rx: mpsc::UnboundedReceiver<String>,
+) {
+    let spawned = tokio::process::Command::new(&claude_path)
+        .args(["auth", "login"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(error) => {
+            let _ = tx.send(ClientEvent::LoginFailed(format!(
+                "Failed to run claude auth login: {error}"
+            )));
+            return;
+        }
+    };
+    let mut stdin = child.stdin.take();
+    let mut stdout = child.stdout.take().map(|out| BufReader::new(out).lines());
+    let mut stderr = child.stderr.take().map(|err| BufReader::new(err).lines());
+    let mut poll = tokio::time::interval(CREDENTIAL_POLL_INTERVAL);
+    let deadline = tokio::time::Instant::now() + LOGIN_TIMEOUT;
+
+    let outcome = loop {
+        tokio::select! {
+            line = next_line(stdout.as_mut()), if stdout.is_some() => match line {
+                Some(line) => { let _ = tx.send(ClientEvent::LoginOutput(line)); }
+                None => stdout = None,
+            },
+            line = next_line(stderr.as_mut()), if stderr.is_some() => match line {
+                Some(line) => { let _ = tx.send(ClientEvent::LoginOutput(line)); }
+                None => stderr = None,
+            },
+            code = code_rx.recv() => {
+                let Some(code) = code else {
+                    tracing::debug!("login cancelled; killing claude auth login");
+                    let _ = child.kill().await;
+                    return;
+                };
+                if let Some(input) = stdin.as_mut()
+                    && let Err(error) = write_code(input, &code).await
+                {
+                    tracing::warn!(%error, "failed to send login code");
+                }
+            },
+            status = child.wait() => {
+                break match status {
+                    Ok(status) if status.success() && crate::app::auth::has_credentials() => Ok(()),
+                    Ok(status) if status.success() => Err(
+                        "Login exited successfully but no credentials were saved. \
+                         Press Enter to try again."
+                            .to_owned(),
+                    ),
+                    Ok(status) => Err(format!(
+                        "Login failed (exit code: {})",
+                        status.code().map_or("unknown".to_owned(), |code| code.to_string())
+                    )),
+                    Err(error) => Err(format!("Failed to wait for claude auth login: {error}")),
+                };
+            },
+            _ = poll.tick() => {
+                if crate::app::auth::has_credentials() {
+                    let _ = child.kill().await;
+                    break Ok(());
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    let _ = child.kill().await;
+                    break Err("Timed out waiting for login to complete.".to_owned());
+                }
+            },
+        }
+    };
+
+    let event = match (outcome, conn) {
+        (Ok(()), Some(conn)) => ClientEvent::AuthCompleted { conn },
+        (Ok(()), None) => ClientEvent::LoginFailed(
+            "Login succeeded but no connection available to start a session.".to_owned(),
+        ),
+        (Err(message), _) => ClientEvent::LoginFailed(message),
+    };
+    let _ = tx.send(event);
+}
+
+async fn next_line<R: AsyncRead + Unpin>(
+    lines: Option<&mut Lines<BufReader<R>>>,
+) -> Option<String> {
+    lines?.next_line().await.ok().flatten()
+}
+
+async fn write_code(input: &mut tokio::process::ChildStdin, code: &str) -> std::io::Result<()> {
+    input.write_all(code.as_bytes()).await?;
+    input.write_all(b"\n").await?;
+    input.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_url, prompts_for_code, strip_ansi};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn output_parsing_finds_url_and_code_prompt() {
+        let line = strip_ansi(
+            "\u{1b}[1mOpen https://claude.ai/oauth/authorize?code=true&state=abc.\u{1b}[0m",
+        );
+        assert_eq!(
+            extract_url(&line).as_deref(),
+            Some("https://claude.ai/oauth/authorize?code=true&state=abc")
+        );
+        assert_eq!(extract_url("Waiting for browser..."), None);
+        assert!(prompts_for_code("Paste code here if prompted >"));
+        assert!(!prompts_for_code("Opening browser to sign in"));
+    }
+}
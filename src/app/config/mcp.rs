@@ -357,7 +357,7 @@ pub(crate) fn present_mcp_elicitation_request(
         if matches!(request.mode, crate::agent::types::ElicitationMode::Url) {
             request.url.as_deref().map_or(
                 (false, Some("SDK did not provide an auth URL".to_owned())),
-                |url| match open_url_in_browser(url) {
+                |url| match open_url_in_browser(app.is_offline(), url) {
                     Ok(()) => (true, None),
                     Err(error) => (false, Some(error)),
                 },
@@ -381,10 +381,11 @@ pub(crate) fn present_mcp_auth_redirect(
     view::set_active_view(app, ActiveView::Config);
     app.config.active_tab = ConfigTab::Mcp;
     refresh_mcp_snapshot(app);
-    let (browser_opened, browser_open_error) = match open_url_in_browser(&redirect.auth_url) {
-        Ok(()) => (true, None),
-        Err(error) => (false, Some(error)),
-    };
+    let (browser_opened, browser_open_error) =
+        match open_url_in_browser(app.is_offline(), &redirect.auth_url) {
+            Ok(()) => (true, None),
+            Err(error) => (false, Some(error)),
+        };
     app.config.overlay = Some(ConfigOverlayState::McpAuthRedirect(McpAuthRedirectOverlayState {
         redirect,
         selected_index: 0,
@@ -442,7 +443,11 @@ fn format_mcp_operation_error(error: &crate::agent::types::McpOperationError) ->
     }
 }
 
-fn open_url_in_browser(url: &str) -> Result<(), String> {
+/// `offline` is [`App::is_offline`]; links are never opened in offline mode.
+pub(super) fn open_url_in_browser(offline: bool, url: &str) -> Result<(), String> {
+    if offline {
+        return Err("Opening links is disabled in offline mode".to_owned());
+    }
     #[cfg(target_os = "windows")]
     let mut command = {
         let mut cmd = std::process::Command::new("rundll32.exe");
@@ -468,10 +473,6 @@ fn open_url_in_browser(url: &str) -> Result<(), String> {
         .map_err(|error| format!("Failed to open browser automatically: {error}"))
 }
 
-pub(crate) fn copy_text_to_clipboard(text: &str) -> Result<(), String> {
-    let mut clipboard = arboard::Clipboard::new()
-        .map_err(|error| format!("Failed to access clipboard: {error}"))?;
-    clipboard
-        .set_text(text.to_owned())
-        .map_err(|error| format!("Failed to copy to clipboard: {error}"))
+pub(crate) fn copy_text_to_clipboard(app: &App, text: &str) -> Result<(), String> {
+    crate::app::clipboard::copy_text(app.config.clipboard_mode_effective(), text).map(|_| ())
 }
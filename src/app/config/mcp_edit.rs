@@ -11,6 +11,7 @@ use super::mcp::{
     send_mcp_elicitation_response, set_mcp_server_enabled, submit_mcp_oauth_callback_url,
 };
 use crate::app::App;
+use crate::app::toast::{ToastKind, show_toast};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
@@ -239,9 +240,9 @@ fn execute_mcp_auth_redirect_overlay_action(app: &mut App) {
             app.config.overlay = None;
         }
         McpAuthRedirectAction::CopyUrl => {
-            match copy_text_to_clipboard(&overlay.redirect.auth_url) {
+            match copy_text_to_clipboard(app, &overlay.redirect.auth_url) {
                 Ok(()) => {
-                    app.config.status_message = Some("Copied auth URL to clipboard.".to_owned());
+                    show_toast(app, ToastKind::Success, "Copied auth URL to clipboard");
                     app.config.last_error = None;
                 }
                 Err(error) => {
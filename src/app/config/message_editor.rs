@@ -0,0 +1,135 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Multi-line text editor shared by the `/commit` and `/pr` overlays.
+
+use super::edit::{accepts_text_input, char_to_byte_index};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageEditor {
+    pub text: String,
+    /// Cursor position in chars.
+    pub cursor: usize,
+}
+
+impl MessageEditor {
+    /// An editor over `text` with the cursor at the end.
+    #[must_use]
+    pub fn new(text: String) -> Self {
+        let cursor = text.chars().count();
+        Self { text, cursor }
+    }
+
+    /// Line and column (in chars) of the cursor.
+    #[must_use]
+    pub fn cursor_line_col(&self) -> (usize, usize) {
+        let before: String = self.text.chars().take(self.cursor).collect();
+        let line = before.matches('\n').count();
+        let col = before.rsplit('\n').next().map_or(0, |tail| tail.chars().count());
+        (line, col)
+    }
+
+    /// Apply an editing key. Returns `false` for keys the editor ignores, so
+    /// the overlay can bind them.
+    pub(super) fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let (line, col) = self.cursor_line_col();
+        match (key.code, key.modifiers) {
+            (KeyCode::Enter, KeyModifiers::NONE | KeyModifiers::SHIFT) => self.insert("\n"),
+            (KeyCode::Left, KeyModifiers::NONE) => self.cursor = self.cursor.saturating_sub(1),
+            (KeyCode::Right, KeyModifiers::NONE) => {
+                self.cursor = (self.cursor + 1).min(self.text.chars().count());
+            }
+            (KeyCode::Up, KeyModifiers::NONE) => {
+                if line > 0 {
+                    self.move_to(line - 1, col);
+                }
+            }
+            (KeyCode::Down, KeyModifiers::NONE) => self.move_to(line + 1, col),
+            (KeyCode::Home, KeyModifiers::NONE) => self.move_to(line, 0),
+            (KeyCode::End, KeyModifiers::NONE) => self.move_to(line, usize::MAX),
+            (KeyCode::Backspace, KeyModifiers::NONE) => {
+                if self.cursor > 0 {
+                    self.delete_range(self.cursor - 1, self.cursor);
+                    self.cursor -= 1;
+                }
+            }
+            (KeyCode::Delete, KeyModifiers::NONE) => {
+                if self.cursor < self.text.chars().count() {
+                    self.delete_range(self.cursor, self.cursor + 1);
+                }
+            }
+            (KeyCode::Char(ch), modifiers) if accepts_text_input(modifiers) => {
+                self.insert(ch.encode_utf8(&mut [0; 4]));
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Insert pasted text, keeping its line breaks.
+    pub(super) fn paste(&mut self, text: &str) {
+        self.insert(&text.replace("\r\n", "\n").replace('\r', "\n"));
+    }
+
+    fn line_starts(&self) -> Vec<usize> {
+        let mut starts = vec![0];
+        for (index, ch) in self.text.chars().enumerate() {
+            if ch == '\n' {
+                starts.push(index + 1);
+            }
+        }
+        starts
+    }
+
+    fn line_len(&self, line: usize) -> usize {
+        self.text.split('\n').nth(line).map_or(0, |text| text.chars().count())
+    }
+
+    fn move_to(&mut self, line: usize, col: usize) {
+        let starts = self.line_starts();
+        let line = line.min(starts.len() - 1);
+        self.cursor = starts[line] + col.min(self.line_len(line));
+    }
+
+    fn insert(&mut self, text: &str) {
+        let byte_index = char_to_byte_index(&self.text, self.cursor);
+        self.text.insert_str(byte_index, text);
+        self.cursor += text.chars().count();
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        let start = char_to_byte_index(&self.text, start);
+        let end = char_to_byte_index(&self.text, end);
+        self.text.replace_range(start..end, "");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn editor_moves_between_lines_and_edits() {
+        let mut editor = MessageEditor::new("Fix bug\n\nLonger body".to_owned());
+
+        editor.handle_key(key(KeyCode::Up));
+        editor.handle_key(key(KeyCode::Up));
+        editor.handle_key(key(KeyCode::End));
+        assert_eq!(editor.cursor_line_col(), (0, 7));
+
+        editor.handle_key(KeyEvent::new(KeyCode::Char('!'), KeyModifiers::SHIFT));
+        editor.handle_key(key(KeyCode::Enter));
+        editor.paste("a\r\nb");
+        editor.handle_key(key(KeyCode::Backspace));
+
+        assert_eq!(editor.text, "Fix bug!\na\n\n\nLonger body");
+        assert_eq!(editor.cursor_line_col(), (2, 0));
+        assert!(!editor.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+    }
+}
@@ -1,9 +1,19 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+mod changed_files;
+mod commit;
+mod context;
+mod diagnostics;
 mod edit;
+mod file_browser;
+mod login;
 mod mcp;
 mod mcp_edit;
+mod message_editor;
+mod model_params;
+mod pull_request;
+mod release_notes;
 mod resolve;
 pub mod store;
 
@@ -13,8 +23,24 @@ use crate::app::App;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::path::PathBuf;
 
+pub(crate) use changed_files::{
+    ChangedFilesOverlayState, open_changed_files, refresh_changed_files,
+};
+pub(crate) use commit::{CommitOverlayState, open_commit};
+pub(crate) use context::{
+    ContextBreakdown, ContextCategoryKind, ContextOverlayState, open_context,
+};
+pub(crate) use diagnostics::{DiagnosticsOverlayState, DiagnosticsView, open_diagnostics};
 pub(crate) use edit::{
     OverlayModelOption, model_overlay_options, supported_effort_levels_for_model,
+    toggle_show_message_usage,
+};
+pub(crate) use file_browser::{
+    FileBrowserOverlayState, FilePreview, FileTouch, FileTreeRow, toggle_file_browser, visible_rows,
+};
+pub(crate) use login::{
+    LoginFlowHandle, LoginOverlayState, LoginPhase, LoginProgress, finish_login,
+    handle_login_failed, handle_login_output, open_login,
 };
 pub(crate) use mcp::{
     McpAuthRedirectOverlayState, McpCallbackUrlOverlayState, McpDetailsOverlayState,
@@ -22,6 +48,10 @@ pub(crate) use mcp::{
     handle_mcp_operation_error, is_mcp_action_available, present_mcp_auth_redirect,
     present_mcp_elicitation_request, refresh_mcp_snapshot,
 };
+pub(crate) use message_editor::MessageEditor;
+pub(crate) use model_params::{ModelParamsOverlayState, open_model_params};
+pub(crate) use pull_request::{PullRequestOverlayState, open_pull_request};
+pub(crate) use release_notes::{ReleaseNotesOverlayState, open_release_notes};
 pub(crate) use resolve::language_input_validation_message;
 use resolve::resolve_setting_document;
 use serde_json::Value;
@@ -74,19 +104,35 @@ impl ConfigTab {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SettingId {
     AlwaysThinking,
+    AutoRetryTools,
+    Clipboard,
     Model,
     DefaultPermissionMode,
     EditorMode,
+    EmojiAutocomplete,
     FastMode,
+    FollowUpSuggestions,
+    HighContrast,
     Language,
     Notifications,
+    OfflineMode,
     OutputStyle,
+    PastePathsAsMentions,
     ReduceMotion,
     RespectGitignore,
+    RevisionDiffs,
+    ScreenReader,
+    ScrollAnimation,
+    ShowMessageUsage,
+    ShowMinimap,
     ShowTips,
+    SystemMessages,
     TerminalProgressBar,
     Theme,
+    ThinkingDisplay,
     ThinkingEffort,
+    UpdateChannel,
+    WelcomeBanner,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -243,6 +289,209 @@ impl DefaultPermissionMode {
     }
 }
 
+/// Which GitHub releases the startup update check and `/update` consider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Also offers `-rc` and other prerelease tags.
+    Prerelease,
+}
+
+impl UpdateChannel {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Prerelease => "prerelease",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "stable" => Some(Self::Stable),
+            "prerelease" => Some(Self::Prerelease),
+            _ => None,
+        }
+    }
+}
+
+/// How the agent's thinking text appears in assistant messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThinkingDisplay {
+    Hide,
+    /// One summary line per block; Ctrl+o expands them with the tool calls.
+    #[default]
+    Collapsed,
+    Full,
+}
+
+impl ThinkingDisplay {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Hide => "hide",
+            Self::Collapsed => "collapsed",
+            Self::Full => "full",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "hide" => Some(Self::Hide),
+            "collapsed" => Some(Self::Collapsed),
+            "full" => Some(Self::Full),
+            _ => None,
+        }
+    }
+}
+
+/// Where copy actions send text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardMode {
+    /// System clipboard locally, OSC 52 over SSH or when the system clipboard fails.
+    #[default]
+    Auto,
+    System,
+    Osc52,
+}
+
+impl ClipboardMode {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::System => "system",
+            Self::Osc52 => "osc52",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "system" => Some(Self::System),
+            "osc52" => Some(Self::Osc52),
+            _ => None,
+        }
+    }
+}
+
+/// How much of the welcome banner is drawn above a new conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WelcomeBanner {
+    /// Ferris (or the art in `welcomeBannerFile`), session info and tips.
+    #[default]
+    Full,
+    /// Session info and the start screen without art or tips.
+    Minimal,
+    Off,
+}
+
+impl WelcomeBanner {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Minimal => "minimal",
+            Self::Off => "off",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "full" => Some(Self::Full),
+            "minimal" => Some(Self::Minimal),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// Where informational status messages are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SystemMessageRouting {
+    /// In the transcript as well as the status log.
+    #[default]
+    Transcript,
+    /// Only in the status log. Errors and untagged messages such as turn
+    /// failures still reach the transcript.
+    LogPane,
+}
+
+impl SystemMessageRouting {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Transcript => "transcript",
+            Self::LogPane => "log",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "transcript" => Some(Self::Transcript),
+            "log" => Some(Self::LogPane),
+            _ => None,
+        }
+    }
+}
+
+/// How the chat eases toward a new scroll position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollAnimation {
+    #[default]
+    Smooth,
+    Fast,
+    /// Jump straight to the target; best over slow links such as SSH.
+    Instant,
+}
+
+impl ScrollAnimation {
+    #[must_use]
+    pub const fn as_stored(self) -> &'static str {
+        match self {
+            Self::Smooth => "smooth",
+            Self::Fast => "fast",
+            Self::Instant => "instant",
+        }
+    }
+
+    #[must_use]
+    pub fn from_stored(value: &str) -> Option<Self> {
+        match value {
+            "smooth" => Some(Self::Smooth),
+            "fast" => Some(Self::Fast),
+            "instant" => Some(Self::Instant),
+            _ => None,
+        }
+    }
+
+    /// Fraction of the remaining distance covered each frame.
+    #[must_use]
+    pub const fn easing_factor(self) -> f32 {
+        match self {
+            Self::Smooth => 0.3,
+            Self::Fast => 0.6,
+            Self::Instant => 1.0,
+        }
+    }
+
+    /// Longest an animation may run before snapping to its target.
+    #[must_use]
+    pub const fn max_duration(self) -> std::time::Duration {
+        match self {
+            Self::Smooth => std::time::Duration::from_millis(300),
+            Self::Fast => std::time::Duration::from_millis(120),
+            Self::Instant => std::time::Duration::ZERO,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PreferredNotifChannel {
     #[default]
@@ -371,6 +620,34 @@ const EDITOR_MODE_OPTIONS: &[SettingOption] = &[
     SettingOption { stored: "default", label: "Default" },
     SettingOption { stored: "vim", label: "Vim" },
 ];
+const UPDATE_CHANNEL_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "stable", label: "Stable" },
+    SettingOption { stored: "prerelease", label: "Prerelease" },
+];
+const THINKING_DISPLAY_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "hide", label: "Hide" },
+    SettingOption { stored: "collapsed", label: "Collapsed" },
+    SettingOption { stored: "full", label: "Full" },
+];
+const CLIPBOARD_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "auto", label: "Auto" },
+    SettingOption { stored: "system", label: "System" },
+    SettingOption { stored: "osc52", label: "Terminal (OSC 52)" },
+];
+const SYSTEM_MESSAGES_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "transcript", label: "Transcript" },
+    SettingOption { stored: "log", label: "Log pane" },
+];
+const WELCOME_BANNER_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "full", label: "Full" },
+    SettingOption { stored: "minimal", label: "Minimal" },
+    SettingOption { stored: "off", label: "Off" },
+];
+const SCROLL_ANIMATION_OPTIONS: &[SettingOption] = &[
+    SettingOption { stored: "smooth", label: "Smooth" },
+    SettingOption { stored: "fast", label: "Fast" },
+    SettingOption { stored: "instant", label: "Instant" },
+];
 const DEFAULT_MODEL_ID: &str = "default";
 const DEFAULT_MODEL_LABEL: &str = "Default";
 const DEFAULT_EFFORT_LEVELS: [EffortLevel; 3] =
@@ -384,7 +661,7 @@ const EFFORT_OPTIONS: &[SettingOption] = &[
     SettingOption { stored: "high", label: "High" },
 ];
 
-const CONFIG_SETTINGS: [SettingSpec; 14] = [
+const CONFIG_SETTINGS: &[SettingSpec] = &[
     SettingSpec {
         id: SettingId::AlwaysThinking,
         entry_id: "A04",
@@ -399,6 +676,34 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::AutoRetryTools,
+        entry_id: "A36",
+        label: "Retry flaky tools",
+        description: "Asks Claude once to run a tool call again when it failed on a network error or a test run failed without an assertion or build error, before the failure is left standing.",
+        file: SettingFile::Preferences,
+        json_path: &["autoRetryTools"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::Clipboard,
+        entry_id: "A28",
+        label: "Clipboard",
+        description: "Controls where copied text goes. Auto uses the system clipboard locally and the terminal (OSC 52) over SSH or when no system clipboard is reachable.",
+        file: SettingFile::Preferences,
+        json_path: &["clipboard"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(CLIPBOARD_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::Model,
         entry_id: "A19",
@@ -441,6 +746,20 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: false,
     },
+    SettingSpec {
+        id: SettingId::EmojiAutocomplete,
+        entry_id: "A22",
+        label: "Emoji autocomplete",
+        description: "Controls whether typing : followed by a name suggests emoji, e.g. :rocket:.",
+        file: SettingFile::Preferences,
+        json_path: &["emojiAutocomplete"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::FastMode,
         entry_id: "A05",
@@ -455,6 +774,34 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::FollowUpSuggestions,
+        entry_id: "A31",
+        label: "Follow-up suggestions",
+        description: "Offers up to three follow-up prompts under a finished turn, e.g. after failed tests or new TODOs. Alt+1..3 copies one into the input.",
+        file: SettingFile::Preferences,
+        json_path: &["followUpSuggestions"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::HighContrast,
+        entry_id: "A33",
+        label: "High contrast",
+        description: "Lifts every text color to a readable contrast on the terminal background and drops dimmed text.",
+        file: SettingFile::Preferences,
+        json_path: &["highContrast"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::Language,
         entry_id: "A16",
@@ -483,6 +830,20 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::OfflineMode,
+        entry_id: "A24",
+        label: "Offline mode",
+        description: "Disables update and status checks, usage requests and opening links in the browser. Same as --offline.",
+        file: SettingFile::Preferences,
+        json_path: &["offlineMode"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::OutputStyle,
         entry_id: "A15",
@@ -497,11 +858,25 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::PastePathsAsMentions,
+        entry_id: "A21",
+        label: "Paste paths as mentions",
+        description: "Controls whether pasted file paths are converted to @ mentions automatically instead of offering the conversion.",
+        file: SettingFile::Preferences,
+        json_path: &["pastePathsAsMentions"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::ReduceMotion,
         entry_id: "A03",
         label: "Reduce motion",
-        description: "Reduce UI motion by slowing spinners and disabling smooth chat scrolling.",
+        description: "Holds spinners and the streaming cursor still and makes chat scrolling jump instead of ease. Also cuts redraws over slow SSH links.",
         file: SettingFile::LocalSettings,
         json_path: &["prefersReducedMotion"],
         kind: SettingKind::Bool,
@@ -525,6 +900,76 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::RevisionDiffs,
+        entry_id: "A35",
+        label: "Diff revised code",
+        description: "Shows a code block that corrects one from an earlier reply as a word diff against it. o in keyboard selection mode (Ctrl+s) shows the full text.",
+        file: SettingFile::Preferences,
+        json_path: &["revisionDiffs"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::ScreenReader,
+        entry_id: "A32",
+        label: "Screen reader mode",
+        description: "Stops spinners and animations and spells out tool and connection state as text instead of color.",
+        file: SettingFile::Preferences,
+        json_path: &["screenReaderMode"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::ScrollAnimation,
+        entry_id: "A27",
+        label: "Scroll animation",
+        description: "Controls how the chat eases toward a new scroll position. Instant suits slow connections such as SSH.",
+        file: SettingFile::Preferences,
+        json_path: &["scrollAnimation"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(SCROLL_ANIMATION_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::ShowMessageUsage,
+        entry_id: "A25",
+        label: "Show message usage",
+        description: "Annotates each assistant reply with its token counts and cost. Ctrl+k toggles it from the chat.",
+        file: SettingFile::Preferences,
+        json_path: &["showMessageUsage"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::ShowMinimap,
+        entry_id: "A34",
+        label: "Show minimap",
+        description: "Marks prompts, errors and failed tool calls in a thin column beside the chat scrollbar. Click a mark to jump to it.",
+        file: SettingFile::Preferences,
+        json_path: &["showMinimap"],
+        kind: SettingKind::Bool,
+        editor: EditorKind::Toggle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::None,
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::ShowTips,
         entry_id: "A02",
@@ -539,6 +984,20 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: false,
     },
+    SettingSpec {
+        id: SettingId::SystemMessages,
+        entry_id: "A29",
+        label: "System messages",
+        description: "Controls where status updates such as reconnects and compaction appear. Log pane keeps them out of the transcript; Ctrl+g shows the log.",
+        file: SettingFile::Preferences,
+        json_path: &["systemMessages"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(SYSTEM_MESSAGES_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::TerminalProgressBar,
         entry_id: "A08",
@@ -567,6 +1026,20 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: false,
     },
+    SettingSpec {
+        id: SettingId::ThinkingDisplay,
+        entry_id: "A26",
+        label: "Thinking display",
+        description: "Controls how Claude's thinking appears in replies: hidden, collapsed to one line, or in full.",
+        file: SettingFile::Preferences,
+        json_path: &["thinkingDisplay"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(THINKING_DISPLAY_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
     SettingSpec {
         id: SettingId::ThinkingEffort,
         entry_id: "A20",
@@ -581,6 +1054,34 @@ const CONFIG_SETTINGS: [SettingSpec; 14] = [
         fallback: FallbackPolicy::AppDefault,
         supported: true,
     },
+    SettingSpec {
+        id: SettingId::UpdateChannel,
+        entry_id: "A23",
+        label: "Update channel",
+        description: "Controls which releases the update check offers. Prerelease also includes -rc builds.",
+        file: SettingFile::Preferences,
+        json_path: &["updateChannel"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(UPDATE_CHANNEL_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
+    SettingSpec {
+        id: SettingId::WelcomeBanner,
+        entry_id: "A30",
+        label: "Welcome banner",
+        description: "Controls the banner above a new conversation. Full draws Ferris, or the art file set as welcomeBannerFile; Minimal keeps only session info.",
+        file: SettingFile::Preferences,
+        json_path: &["welcomeBanner"],
+        kind: SettingKind::Enum,
+        editor: EditorKind::Cycle,
+        source: ValueSource::PersistedOnly,
+        options: SettingOptions::Static(WELCOME_BANNER_OPTIONS),
+        fallback: FallbackPolicy::AppDefault,
+        supported: true,
+    },
 ];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -760,6 +1261,15 @@ pub enum ConfigOverlayState {
     McpCallbackUrl(McpCallbackUrlOverlayState),
     McpElicitation(McpElicitationOverlayState),
     McpAuthRedirect(McpAuthRedirectOverlayState),
+    Diagnostics(DiagnosticsOverlayState),
+    Login(LoginOverlayState),
+    ReleaseNotes(ReleaseNotesOverlayState),
+    Context(ContextOverlayState),
+    ChangedFiles(ChangedFilesOverlayState),
+    Commit(CommitOverlayState),
+    PullRequest(PullRequestOverlayState),
+    FileBrowser(FileBrowserOverlayState),
+    ModelParams(ModelParamsOverlayState),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -880,17 +1390,103 @@ impl ConfigState {
         store::respect_gitignore(&self.committed_preferences_document).unwrap_or(true)
     }
 
+    #[must_use]
+    pub fn emoji_autocomplete_effective(&self) -> bool {
+        store::emoji_autocomplete(&self.committed_preferences_document).unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn follow_up_suggestions_effective(&self) -> bool {
+        store::follow_up_suggestions(&self.committed_preferences_document).unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn paste_paths_as_mentions_effective(&self) -> bool {
+        store::paste_paths_as_mentions(&self.committed_preferences_document).unwrap_or(false)
+    }
+
     #[must_use]
     pub fn preferred_notification_channel_effective(&self) -> PreferredNotifChannel {
         store::preferred_notification_channel(&self.committed_preferences_document)
             .unwrap_or_default()
     }
 
+    #[must_use]
+    pub fn show_message_usage_effective(&self) -> bool {
+        store::show_message_usage(&self.committed_preferences_document).unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn revision_diffs_effective(&self) -> bool {
+        store::revision_diffs(&self.committed_preferences_document).unwrap_or(true)
+    }
+
+    #[must_use]
+    pub fn auto_retry_tools_effective(&self) -> bool {
+        store::auto_retry_tools(&self.committed_preferences_document).unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn show_minimap_effective(&self) -> bool {
+        store::show_minimap(&self.committed_preferences_document).unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn offline_mode_effective(&self) -> bool {
+        store::offline_mode(&self.committed_preferences_document).unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn thinking_display_effective(&self) -> ThinkingDisplay {
+        store::thinking_display(&self.committed_preferences_document).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn clipboard_mode_effective(&self) -> ClipboardMode {
+        store::clipboard_mode(&self.committed_preferences_document).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn system_message_routing_effective(&self) -> SystemMessageRouting {
+        store::system_message_routing(&self.committed_preferences_document).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn welcome_banner_effective(&self) -> WelcomeBanner {
+        store::welcome_banner(&self.committed_preferences_document).unwrap_or_default()
+    }
+
+    /// Custom banner art from `welcomeBannerFile`, if one is configured.
+    #[must_use]
+    pub fn welcome_banner_file(&self) -> Option<std::path::PathBuf> {
+        store::welcome_banner_file(&self.committed_preferences_document)
+    }
+
+    #[must_use]
+    pub fn scroll_animation_effective(&self) -> ScrollAnimation {
+        store::scroll_animation(&self.committed_preferences_document).unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn update_channel_effective(&self) -> UpdateChannel {
+        store::update_channel(&self.committed_preferences_document).unwrap_or_default()
+    }
+
     #[must_use]
     pub fn prefers_reduced_motion_effective(&self) -> bool {
         store::prefers_reduced_motion(&self.committed_local_settings_document).unwrap_or(false)
     }
 
+    #[must_use]
+    pub fn high_contrast_effective(&self) -> bool {
+        store::high_contrast(&self.committed_preferences_document).unwrap_or(false)
+    }
+
+    #[must_use]
+    pub fn screen_reader_effective(&self) -> bool {
+        store::screen_reader_mode(&self.committed_preferences_document).unwrap_or(false)
+    }
+
     #[must_use]
     pub fn output_style_effective(&self) -> OutputStyle {
         store::output_style(&self.committed_local_settings_document).unwrap_or_default()
@@ -916,7 +1512,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -936,7 +1541,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -957,7 +1571,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -977,7 +1600,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -998,7 +1630,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1018,7 +1659,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1039,7 +1689,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1059,7 +1718,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1080,7 +1748,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1102,7 +1779,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1123,7 +1809,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1143,7 +1838,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1164,7 +1868,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1186,7 +1899,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1207,7 +1929,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1227,7 +1958,16 @@ impl ConfigState {
                 | ConfigOverlayState::McpDetails(_)
                 | ConfigOverlayState::McpCallbackUrl(_)
                 | ConfigOverlayState::McpElicitation(_)
-                | ConfigOverlayState::McpAuthRedirect(_),
+                | ConfigOverlayState::McpAuthRedirect(_)
+                | ConfigOverlayState::Diagnostics(_)
+                | ConfigOverlayState::Login(_)
+                | ConfigOverlayState::ReleaseNotes(_)
+                | ConfigOverlayState::Context(_)
+                | ConfigOverlayState::ChangedFiles(_)
+                | ConfigOverlayState::Commit(_)
+                | ConfigOverlayState::PullRequest(_)
+                | ConfigOverlayState::FileBrowser(_)
+                | ConfigOverlayState::ModelParams(_),
             )
             | None => None,
         }
@@ -1287,7 +2027,7 @@ impl ConfigState {
 
 #[must_use]
 pub const fn setting_specs() -> &'static [SettingSpec] {
-    &CONFIG_SETTINGS
+    CONFIG_SETTINGS
 }
 
 #[must_use]
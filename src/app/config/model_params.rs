@@ -0,0 +1,117 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/set` overlay: the session's generation parameters. Left/Right step the
+//! selected value, Backspace resets it to the agent default, Enter applies
+//! the draft (reloading the session) and Esc drops it.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crate::app::model_params::{ModelParam, ModelParams};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelParamsOverlayState {
+    pub selected: usize,
+    pub draft: ModelParams,
+}
+
+impl ModelParamsOverlayState {
+    #[must_use]
+    pub fn selected_param(&self) -> ModelParam {
+        ModelParam::ALL[self.selected.min(ModelParam::ALL.len() - 1)]
+    }
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn model_params_overlay(&self) -> Option<&ModelParamsOverlayState> {
+        if let Some(ConfigOverlayState::ModelParams(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn model_params_overlay_mut(&mut self) -> Option<&mut ModelParamsOverlayState> {
+        if let Some(ConfigOverlayState::ModelParams(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_model_params(app: &mut App) -> Result<(), String> {
+    let draft = app.model_params;
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay =
+        Some(ConfigOverlayState::ModelParams(ModelParamsOverlayState { selected: 0, draft }));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overlay) = app.config.model_params_overlay_mut() else {
+        return false;
+    };
+    let param = overlay.selected_param();
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.config.overlay = None,
+        (KeyCode::Up, KeyModifiers::NONE) => overlay.selected = overlay.selected.saturating_sub(1),
+        (KeyCode::Down, KeyModifiers::NONE) => {
+            overlay.selected = (overlay.selected + 1).min(ModelParam::ALL.len() - 1);
+        }
+        (KeyCode::Left | KeyCode::Right, KeyModifiers::NONE) => {
+            let value = param.stepped(param.get(&overlay.draft), key.code == KeyCode::Right);
+            param.set(&mut overlay.draft, value);
+        }
+        (KeyCode::Backspace | KeyCode::Delete, KeyModifiers::NONE) => {
+            param.set(&mut overlay.draft, None);
+        }
+        (KeyCode::Enter, KeyModifiers::NONE) => {
+            let draft = overlay.draft;
+            match crate::app::model_params::apply(app, draft) {
+                Ok(()) => {
+                    app.config.overlay = None;
+                    app.config.last_error = None;
+                    super::close(app);
+                }
+                Err(err) => app.config.last_error = Some(err),
+            }
+        }
+        _ => {}
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn keys_edit_the_draft_and_enter_applies_it() {
+        let mut app = App::test_default();
+        app.config.overlay = Some(ConfigOverlayState::ModelParams(ModelParamsOverlayState {
+            selected: 0,
+            draft: ModelParams::default(),
+        }));
+        let press = |app: &mut App, code| {
+            handle_overlay_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+        };
+
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Right);
+        press(&mut app, KeyCode::Backspace);
+
+        let overlay = app.config.model_params_overlay().expect("model params overlay");
+        assert_eq!(overlay.draft, ModelParams { thinking_budget: Some(2_048), max_turns: None });
+
+        press(&mut app, KeyCode::Enter);
+        assert!(app.config.overlay.is_none());
+        assert_eq!(app.model_params.thinking_budget, Some(2_048));
+    }
+}
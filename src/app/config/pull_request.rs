@@ -0,0 +1,122 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/pr` overlay: the drafted title (first line) and description in a
+//! multi-line editor under the branch summary. Alt+d toggles a draft pull
+//! request, Ctrl+s pushes and creates it and Esc drops the draft.
+
+use super::message_editor::MessageEditor;
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crate::app::pull_request::{PendingPullRequestDraft, PullRequestOptions, split_title_body};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestOverlayState {
+    /// Commit subjects and `git diff --stat` of the branch.
+    pub summary: String,
+    pub message: MessageEditor,
+    pub options: PullRequestOptions,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn pull_request_overlay(&self) -> Option<&PullRequestOverlayState> {
+        if let Some(ConfigOverlayState::PullRequest(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn pull_request_overlay_mut(&mut self) -> Option<&mut PullRequestOverlayState> {
+        if let Some(ConfigOverlayState::PullRequest(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_pull_request(
+    app: &mut App,
+    draft: PendingPullRequestDraft,
+    reply: &str,
+) -> Result<(), String> {
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    // Normalize the reply to "title, blank line, body" for editing.
+    let (title, body) = split_title_body(reply);
+    let text = if body.is_empty() { title } else { format!("{title}\n\n{body}") };
+    app.config.overlay = Some(ConfigOverlayState::PullRequest(PullRequestOverlayState {
+        summary: draft.summary,
+        message: MessageEditor::new(text),
+        options: draft.options,
+    }));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let Some(overlay) = app.config.pull_request_overlay_mut() else {
+        return false;
+    };
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => {
+            app.config.overlay = None;
+            super::close(app);
+        }
+        (KeyCode::Char('s'), KeyModifiers::CONTROL) => confirm_pull_request(app),
+        (KeyCode::Char('d'), KeyModifiers::ALT) => overlay.options.draft = !overlay.options.draft,
+        _ => {
+            overlay.message.handle_key(key);
+        }
+    }
+    true
+}
+
+pub(super) fn handle_overlay_paste(app: &mut App, text: &str) -> bool {
+    let Some(overlay) = app.config.pull_request_overlay_mut() else {
+        return false;
+    };
+    overlay.message.paste(text);
+    true
+}
+
+fn confirm_pull_request(app: &mut App) {
+    let Some(overlay) = app.config.pull_request_overlay() else {
+        return;
+    };
+    let (title, body) = split_title_body(&overlay.message.text);
+    if title.is_empty() {
+        app.config.last_error = Some("The pull request title is empty.".to_owned());
+        return;
+    }
+    let options = overlay.options.clone();
+    app.config.overlay = None;
+    app.config.last_error = None;
+    super::close(app);
+    crate::app::pull_request::create(app, &title, &body, &options);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn draft_toggles_and_empty_title_is_rejected() {
+        let mut app = App::test_default();
+        app.config.overlay = Some(ConfigOverlayState::PullRequest(PullRequestOverlayState {
+            summary: String::new(),
+            message: MessageEditor::new("  \n".to_owned()),
+            options: PullRequestOptions::default(),
+        }));
+
+        handle_overlay_key(&mut app, KeyEvent::new(KeyCode::Char('d'), KeyModifiers::ALT));
+        handle_overlay_key(&mut app, KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+        let overlay = app.config.pull_request_overlay().expect("pull request overlay");
+        assert!(overlay.options.draft);
+        assert_eq!(app.config.last_error.as_deref(), Some("The pull request title is empty."));
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Release-notes overlay for the update hint: the body of the newest GitHub
+//! release, shown on top of the Status tab.
+
+use super::{ConfigOverlayState, ConfigState, ConfigTab};
+use crate::app::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Lines moved by `PageUp` / `PageDown`.
+const PAGE_LINES: usize = 10;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseNotesOverlayState {
+    /// First visible line of the notes.
+    pub scroll: usize,
+}
+
+impl ConfigState {
+    #[must_use]
+    pub fn release_notes_overlay(&self) -> Option<&ReleaseNotesOverlayState> {
+        if let Some(ConfigOverlayState::ReleaseNotes(overlay)) = &self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+
+    pub fn release_notes_overlay_mut(&mut self) -> Option<&mut ReleaseNotesOverlayState> {
+        if let Some(ConfigOverlayState::ReleaseNotes(overlay)) = &mut self.overlay {
+            Some(overlay)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn open_release_notes(app: &mut App) -> Result<(), String> {
+    if app.update_release.is_none() {
+        return Err("No release information available".to_owned());
+    }
+    super::open(app)?;
+    super::activate_tab(app, ConfigTab::Status);
+    app.config.overlay =
+        Some(ConfigOverlayState::ReleaseNotes(ReleaseNotesOverlayState::default()));
+    Ok(())
+}
+
+pub(super) fn handle_overlay_key(app: &mut App, key: KeyEvent) -> bool {
+    let max_scroll = app
+        .update_release
+        .as_ref()
+        .map_or(0, |release| release.notes.lines().count().saturating_sub(1));
+    let Some(overlay) = app.config.release_notes_overlay_mut() else {
+        return false;
+    };
+    let scroll = &mut overlay.scroll;
+    match (key.code, key.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.config.overlay = None,
+        (KeyCode::Up, KeyModifiers::NONE) => *scroll = scroll.saturating_sub(1),
+        (KeyCode::Down, KeyModifiers::NONE) => *scroll = (*scroll + 1).min(max_scroll),
+        (KeyCode::PageUp, KeyModifiers::NONE) => *scroll = scroll.saturating_sub(PAGE_LINES),
+        (KeyCode::PageDown, KeyModifiers::NONE) => *scroll = (*scroll + PAGE_LINES).min(max_scroll),
+        (KeyCode::Home, KeyModifiers::NONE) => *scroll = 0,
+        (KeyCode::End, KeyModifiers::NONE) => *scroll = max_scroll,
+        (KeyCode::Char('o'), KeyModifiers::NONE) => open_release_page(app),
+        _ => {}
+    }
+    true
+}
+
+fn open_release_page(app: &mut App) {
+    let Some(url) = app.update_release.as_ref().map(|release| release.html_url.clone()) else {
+        return;
+    };
+    if url.is_empty() {
+        return;
+    }
+    match super::mcp::open_url_in_browser(app.is_offline(), &url) {
+        Ok(()) => app.config.status_message = Some("Opened the release page".to_owned()),
+        Err(err) => app.config.last_error = Some(err),
+    }
+}
@@ -1,8 +1,9 @@
 use super::{
-    DEFAULT_MODEL_ID, DEFAULT_PERMISSION_OPTIONS, DefaultPermissionMode, LANGUAGE_MAX_CHARS,
-    LANGUAGE_MIN_CHARS, OutputStyle, PreferredNotifChannel, ResolvedChoice, ResolvedSetting,
-    ResolvedSettingValue, RuntimeCatalogKind, SettingId, SettingOptions, SettingSpec,
-    SettingValidation, store,
+    ClipboardMode, DEFAULT_MODEL_ID, DEFAULT_PERMISSION_OPTIONS, DefaultPermissionMode,
+    LANGUAGE_MAX_CHARS, LANGUAGE_MIN_CHARS, OutputStyle, PreferredNotifChannel, ResolvedChoice,
+    ResolvedSetting, ResolvedSettingValue, RuntimeCatalogKind, ScrollAnimation, SettingId,
+    SettingOptions, SettingSpec, SettingValidation, SystemMessageRouting, ThinkingDisplay,
+    UpdateChannel, WelcomeBanner, store,
 };
 use crate::agent::model::AvailableModel;
 use serde_json::Value;
@@ -14,16 +15,26 @@ pub(super) fn resolve_setting_document(
 ) -> ResolvedSetting {
     let spec = super::setting_spec(setting_id);
     match setting_id {
-        SettingId::AlwaysThinking | SettingId::FastMode | SettingId::ReduceMotion => {
-            resolve_bool_setting(document, spec, false)
-        }
+        SettingId::AlwaysThinking
+        | SettingId::AutoRetryTools
+        | SettingId::FastMode
+        | SettingId::HighContrast
+        | SettingId::OfflineMode
+        | SettingId::PastePathsAsMentions
+        | SettingId::ReduceMotion
+        | SettingId::ScreenReader
+        | SettingId::ShowMessageUsage
+        | SettingId::ShowMinimap => resolve_bool_setting(document, spec, false),
         SettingId::DefaultPermissionMode => {
             resolve_string_setting(document, spec, DefaultPermissionMode::Default.as_stored())
         }
         SettingId::Language => resolve_language_setting(document, spec),
-        SettingId::ShowTips | SettingId::RespectGitignore | SettingId::TerminalProgressBar => {
-            resolve_bool_setting(document, spec, true)
-        }
+        SettingId::EmojiAutocomplete
+        | SettingId::FollowUpSuggestions
+        | SettingId::ShowTips
+        | SettingId::RespectGitignore
+        | SettingId::RevisionDiffs
+        | SettingId::TerminalProgressBar => resolve_bool_setting(document, spec, true),
         SettingId::Model => resolve_model_setting(document, spec, available_models),
         SettingId::OutputStyle => {
             resolve_string_setting(document, spec, OutputStyle::Default.as_stored())
@@ -34,6 +45,24 @@ pub(super) fn resolve_setting_document(
             resolve_string_setting(document, spec, PreferredNotifChannel::default().as_stored())
         }
         SettingId::EditorMode => resolve_string_setting(document, spec, "default"),
+        SettingId::UpdateChannel => {
+            resolve_string_setting(document, spec, UpdateChannel::default().as_stored())
+        }
+        SettingId::ThinkingDisplay => {
+            resolve_string_setting(document, spec, ThinkingDisplay::default().as_stored())
+        }
+        SettingId::Clipboard => {
+            resolve_string_setting(document, spec, ClipboardMode::default().as_stored())
+        }
+        SettingId::SystemMessages => {
+            resolve_string_setting(document, spec, SystemMessageRouting::default().as_stored())
+        }
+        SettingId::ScrollAnimation => {
+            resolve_string_setting(document, spec, ScrollAnimation::default().as_stored())
+        }
+        SettingId::WelcomeBanner => {
+            resolve_string_setting(document, spec, WelcomeBanner::default().as_stored())
+        }
     }
 }
 
@@ -8,8 +8,9 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::{
-    DefaultPermissionMode, OutputStyle, PreferredNotifChannel, SettingId, SettingKind, SettingSpec,
-    setting_spec,
+    ClipboardMode, DefaultPermissionMode, OutputStyle, PreferredNotifChannel, ScrollAnimation,
+    SettingId, SettingKind, SettingSpec, SystemMessageRouting, ThinkingDisplay, UpdateChannel,
+    WelcomeBanner, setting_spec,
 };
 use crate::agent::model::EffortLevel;
 
@@ -303,6 +304,54 @@ pub fn set_respect_gitignore(document: &mut Value, enabled: bool) {
     );
 }
 
+pub fn emoji_autocomplete(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::EmojiAutocomplete))? {
+        PersistedSettingValue::Missing => Ok(true),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_emoji_autocomplete(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::EmojiAutocomplete),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn follow_up_suggestions(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::FollowUpSuggestions))? {
+        PersistedSettingValue::Missing => Ok(true),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_follow_up_suggestions(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::FollowUpSuggestions),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn paste_paths_as_mentions(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::PastePathsAsMentions))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_paste_paths_as_mentions(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::PastePathsAsMentions),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
 pub fn preferred_notification_channel(document: &Value) -> Result<PreferredNotifChannel, ()> {
     match read_persisted_setting(document, setting_spec(SettingId::Notifications))? {
         PersistedSettingValue::Missing => Ok(PreferredNotifChannel::default()),
@@ -321,7 +370,208 @@ pub fn set_preferred_notification_channel(document: &mut Value, channel: Preferr
     );
 }
 
-fn resolve_paths(
+pub fn show_message_usage(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::ShowMessageUsage))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_show_message_usage(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::ShowMessageUsage),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn revision_diffs(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::RevisionDiffs))? {
+        PersistedSettingValue::Missing => Ok(true),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_revision_diffs(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::RevisionDiffs),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn auto_retry_tools(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::AutoRetryTools))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_auto_retry_tools(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::AutoRetryTools),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn show_minimap(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::ShowMinimap))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_show_minimap(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::ShowMinimap),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn high_contrast(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::HighContrast))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_high_contrast(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::HighContrast),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+/// Stored `theme` name, e.g. `dark` or `light-daltonized`.
+pub fn theme_name(document: &Value) -> Option<String> {
+    match read_persisted_setting(document, setting_spec(SettingId::Theme)) {
+        Ok(PersistedSettingValue::String(value)) => Some(value),
+        _ => None,
+    }
+}
+
+pub fn screen_reader_mode(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::ScreenReader))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_screen_reader_mode(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::ScreenReader),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn offline_mode(document: &Value) -> Result<bool, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::OfflineMode))? {
+        PersistedSettingValue::Missing => Ok(false),
+        PersistedSettingValue::Bool(value) => Ok(value),
+        PersistedSettingValue::String(_) => Err(()),
+    }
+}
+
+pub fn set_offline_mode(document: &mut Value, enabled: bool) {
+    write_persisted_setting(
+        document,
+        setting_spec(SettingId::OfflineMode),
+        PersistedSettingValue::Bool(enabled),
+    );
+}
+
+pub fn update_channel(document: &Value) -> Result<UpdateChannel, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::UpdateChannel))? {
+        PersistedSettingValue::Missing => Ok(UpdateChannel::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => UpdateChannel::from_stored(&value).ok_or(()),
+    }
+}
+
+pub fn clipboard_mode(document: &Value) -> Result<ClipboardMode, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::Clipboard))? {
+        PersistedSettingValue::Missing => Ok(ClipboardMode::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => ClipboardMode::from_stored(&value).ok_or(()),
+    }
+}
+
+pub fn system_message_routing(document: &Value) -> Result<SystemMessageRouting, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::SystemMessages))? {
+        PersistedSettingValue::Missing => Ok(SystemMessageRouting::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => SystemMessageRouting::from_stored(&value).ok_or(()),
+    }
+}
+
+pub fn welcome_banner(document: &Value) -> Result<WelcomeBanner, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::WelcomeBanner))? {
+        PersistedSettingValue::Missing => Ok(WelcomeBanner::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => WelcomeBanner::from_stored(&value).ok_or(()),
+    }
+}
+
+/// `welcomeBannerFile`: a text file whose lines replace the Ferris art. A
+/// leading `~/` expands to the home directory.
+pub fn welcome_banner_file(document: &Value) -> Option<std::path::PathBuf> {
+    let path = document.get("welcomeBannerFile")?.as_str()?.trim();
+    if path.is_empty() {
+        return None;
+    }
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+        None => Some(std::path::PathBuf::from(path)),
+    }
+}
+
+/// `forgeCli`: the CLI `/pr` creates pull requests with, e.g. `glab` or a
+/// path to `gh`.
+pub fn forge_cli(document: &Value) -> Option<String> {
+    let cli = document.get("forgeCli")?.as_str()?.trim();
+    (!cli.is_empty()).then(|| cli.to_owned())
+}
+
+/// `checkCommand`: the command run after edit tool calls, e.g.
+/// `cargo check --message-format=json`.
+pub fn check_command(document: &Value) -> Option<String> {
+    let command = document.get("checkCommand")?.as_str()?.trim();
+    (!command.is_empty()).then(|| command.to_owned())
+}
+
+/// `testCommand`: the command `/test` runs, e.g. `cargo nextest run`.
+pub fn test_command(document: &Value) -> Option<String> {
+    let command = document.get("testCommand")?.as_str()?.trim();
+    (!command.is_empty()).then(|| command.to_owned())
+}
+
+pub fn scroll_animation(document: &Value) -> Result<ScrollAnimation, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::ScrollAnimation))? {
+        PersistedSettingValue::Missing => Ok(ScrollAnimation::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => ScrollAnimation::from_stored(&value).ok_or(()),
+    }
+}
+
+pub fn thinking_display(document: &Value) -> Result<ThinkingDisplay, ()> {
+    match read_persisted_setting(document, setting_spec(SettingId::ThinkingDisplay))? {
+        PersistedSettingValue::Missing => Ok(ThinkingDisplay::default()),
+        PersistedSettingValue::Bool(_) => Err(()),
+        PersistedSettingValue::String(value) => ThinkingDisplay::from_stored(&value).ok_or(()),
+    }
+}
+
+pub fn resolve_paths(
     home_override: Option<&Path>,
     project_root_override: Option<&Path>,
 ) -> Result<SettingsPaths, String> {
@@ -541,6 +791,20 @@ mod tests {
         assert_eq!(respect_gitignore(&document), Ok(true));
     }
 
+    #[test]
+    fn emoji_autocomplete_defaults_to_true() {
+        let document = Value::Object(Map::new());
+
+        assert_eq!(emoji_autocomplete(&document), Ok(true));
+    }
+
+    #[test]
+    fn paste_paths_as_mentions_defaults_to_false() {
+        let document = Value::Object(Map::new());
+
+        assert_eq!(paste_paths_as_mentions(&document), Ok(false));
+    }
+
     #[test]
     fn terminal_progress_bar_defaults_to_true() {
         let document = Value::Object(Map::new());
@@ -742,7 +742,7 @@ fn immediate_save_respect_gitignore_invalidates_active_mention_session_cache() {
 
     let mention = app.mention.as_ref().expect("mention should stay active");
     assert!(mention.candidates.is_empty());
-    assert_eq!(mention.placeholder_message().as_deref(), Some("Searching files..."));
+    assert_eq!(mention.placeholder_message().as_deref(), Some("Indexing files..."));
     assert!(!app.config.respect_gitignore_effective());
 }
 
@@ -836,6 +836,55 @@ fn editor_mode_cycles_in_preferences_document() {
     assert_eq!(stored, Ok(store::PersistedSettingValue::String("vim".to_owned())));
 }
 
+#[test]
+fn offline_mode_toggle_marks_app_offline() {
+    let (_dir, mut app) = open_settings_test_app();
+    assert!(!app.is_offline());
+    select_setting(&mut app, SettingId::OfflineMode);
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+    assert!(app.is_offline());
+    assert_eq!(store::offline_mode(&app.config.committed_preferences_document), Ok(true));
+}
+
+#[test]
+fn show_message_usage_toggles_from_chat_shortcut() {
+    let (_dir, mut app) = open_settings_test_app();
+    assert!(!app.config.show_message_usage_effective());
+
+    toggle_show_message_usage(&mut app);
+
+    assert!(app.config.show_message_usage_effective());
+    assert_eq!(store::show_message_usage(&app.config.committed_preferences_document), Ok(true));
+}
+
+#[test]
+fn thinking_display_cycles_from_collapsed_to_full() {
+    let (_dir, mut app) = open_settings_test_app();
+    assert_eq!(app.config.thinking_display_effective(), ThinkingDisplay::Collapsed);
+    select_setting(&mut app, SettingId::ThinkingDisplay);
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+    assert_eq!(app.config.thinking_display_effective(), ThinkingDisplay::Full);
+}
+
+#[test]
+fn update_channel_cycles_to_prerelease_in_preferences_document() {
+    let (_dir, mut app) = open_settings_test_app();
+    assert_eq!(app.config.update_channel_effective(), UpdateChannel::Stable);
+    select_setting(&mut app, SettingId::UpdateChannel);
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+
+    assert_eq!(app.config.update_channel_effective(), UpdateChannel::Prerelease);
+    assert_eq!(
+        store::update_channel(&app.config.committed_preferences_document),
+        Ok(UpdateChannel::Prerelease)
+    );
+}
+
 #[test]
 fn output_style_resolves_existing_project_value() {
     let mut app = App::test_default();
@@ -1414,3 +1463,58 @@ fn save_failure_keeps_previous_value_and_surfaces_error() {
     assert!(app.config.last_error.is_some());
     assert!(app.config.status_message.is_none());
 }
+
+#[test]
+fn diagnostics_overlay_scrolls_clears_and_closes() {
+    let (_dir, mut app) = open_settings_test_app();
+    for i in 0..5 {
+        app.bridge_stderr
+            .borrow_mut()
+            .push(crate::agent::stderr_log::StderrLevel::Debug, format!("line {i}"));
+    }
+    app.config.overlay = Some(ConfigOverlayState::Diagnostics(DiagnosticsOverlayState::default()));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+    assert_eq!(app.config.diagnostics_overlay().map(|o| o.scroll_from_bottom), Some(4));
+    handle_key(&mut app, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(app.config.diagnostics_overlay().map(|o| o.scroll_from_bottom), Some(3));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+    assert!(app.bridge_stderr.borrow().is_empty());
+    assert_eq!(app.config.diagnostics_overlay().map(|o| o.scroll_from_bottom), Some(0));
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.config.overlay.is_none());
+    assert_eq!(app.active_view, ActiveView::Config);
+}
+
+#[test]
+fn login_overlay_lists_default_method_and_shows_failures() {
+    let (_dir, mut app) = open_settings_test_app();
+    app.login_hint = Some(crate::app::LoginHint {
+        method_name: "Log in with Claude".to_owned(),
+        method_description: "Run /login".to_owned(),
+    });
+    open_login(&mut app).expect("open login");
+
+    let overlay = app.config.login_overlay().expect("login overlay");
+    assert_eq!(overlay.methods.len(), 1);
+    assert_eq!(overlay.methods[0].id, "claude-login");
+    assert_eq!(overlay.phase, LoginPhase::ChooseMethod);
+    handle_key(&mut app, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(app.config.login_overlay().map(|o| o.selected_index), Some(0));
+
+    handle_login_failed(&mut app, "Timed out waiting for login to complete.");
+    assert_eq!(
+        app.config.login_overlay().map(|o| o.phase.clone()),
+        Some(LoginPhase::Failed("Timed out waiting for login to complete.".to_owned()))
+    );
+    assert_eq!(
+        app.login_hint.as_ref().map(|hint| hint.method_description.as_str()),
+        Some("Timed out waiting for login to complete.")
+    );
+
+    handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.config.overlay.is_none());
+    assert_eq!(app.active_view, ActiveView::Chat);
+}
@@ -0,0 +1,186 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Modal confirmation prompt shared by destructive actions: quitting while a
+//! turn is running, starting a new session over an existing conversation,
+//! trusting a folder tree and rewinding the conversation. While open it takes
+//! every key press; the chosen option's [`ConfirmAction`] runs once the dialog
+//! has closed.
+
+use super::events::push_system_message_with_severity;
+use super::toast::{ToastKind, show_toast};
+use super::{App, AppStatus};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Close the dialog without doing anything.
+    Dismiss,
+    Quit,
+    NewSession,
+    /// Trust this folder (the current directory when `None`) and everything below it.
+    TrustRoot(Option<String>),
+    /// Restore a file to its content from when the session first touched it.
+    RevertFile(PathBuf),
+    /// Drop the message at this index and everything after it.
+    Rewind(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmOption {
+    pub label: &'static str,
+    /// Single-key shortcut, matched case-insensitively.
+    pub key: char,
+    pub action: ConfirmAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmDialog {
+    pub title: String,
+    pub body: String,
+    pub options: Vec<ConfirmOption>,
+    pub selected: usize,
+}
+
+impl ConfirmDialog {
+    /// A yes/no prompt where Esc and `n` dismiss. Focus starts on the safe choice.
+    #[must_use]
+    pub fn yes_no(
+        title: impl Into<String>,
+        body: impl Into<String>,
+        confirm_label: &'static str,
+        action: ConfirmAction,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            options: vec![
+                ConfirmOption { label: confirm_label, key: 'y', action },
+                ConfirmOption { label: "Cancel", key: 'n', action: ConfirmAction::Dismiss },
+            ],
+            selected: 1,
+        }
+    }
+}
+
+pub(crate) fn open_confirm(app: &mut App, dialog: ConfirmDialog) {
+    app.confirm = Some(dialog);
+    app.needs_redraw = true;
+}
+
+/// Quit now, or ask first when a turn is still running.
+pub(crate) fn request_quit(app: &mut App) {
+    if !matches!(app.status, AppStatus::Thinking | AppStatus::Running) {
+        app.should_quit = true;
+        return;
+    }
+    open_confirm(
+        app,
+        ConfirmDialog::yes_no(
+            "Quit while a turn is running?",
+            "The agent is still working. Quitting cancels the current turn.",
+            "Quit",
+            ConfirmAction::Quit,
+        ),
+    );
+}
+
+pub(crate) fn handle_key(app: &mut App, key: KeyEvent) {
+    let Some(dialog) = app.confirm.as_mut() else {
+        return;
+    };
+    if key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('q' | 'c')) {
+        app.confirm = None;
+        app.should_quit = true;
+        return;
+    }
+    let count = dialog.options.len();
+    if count == 0 {
+        app.confirm = None;
+        return;
+    }
+    let chosen = match key.code {
+        KeyCode::Left | KeyCode::Up | KeyCode::BackTab => {
+            dialog.selected = (dialog.selected + count - 1) % count;
+            None
+        }
+        KeyCode::Right | KeyCode::Down | KeyCode::Tab => {
+            dialog.selected = (dialog.selected + 1) % count;
+            None
+        }
+        KeyCode::Enter => dialog.options.get(dialog.selected).map(|option| option.action.clone()),
+        KeyCode::Esc => Some(ConfirmAction::Dismiss),
+        KeyCode::Char(ch) => dialog
+            .options
+            .iter()
+            .find(|option| option.key.eq_ignore_ascii_case(&ch))
+            .map(|option| option.action.clone()),
+        _ => None,
+    };
+    if let Some(action) = chosen {
+        app.confirm = None;
+        run_action(app, action);
+    }
+}
+
+fn run_action(app: &mut App, action: ConfirmAction) {
+    match action {
+        ConfirmAction::Dismiss => {}
+        ConfirmAction::Quit => app.should_quit = true,
+        ConfirmAction::NewSession => super::slash::run_new_session(app),
+        ConfirmAction::TrustRoot(path) => {
+            let message = match super::trust::add_root(app, path.as_deref()) {
+                Ok(message) => message,
+                Err(err) => format!("Failed to update trust: {err}"),
+            };
+            push_system_message_with_severity(app, None, &message);
+        }
+        ConfirmAction::RevertFile(path) => {
+            match super::changed_files::revert(app, &path) {
+                Ok(message) => show_toast(app, ToastKind::Success, message),
+                Err(err) => show_toast(app, ToastKind::Error, err),
+            }
+            super::config::refresh_changed_files(app);
+        }
+        ConfirmAction::Rewind(index) => super::rewind::rewind_to(app, index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn quit_confirmation_defaults_to_cancel_and_accepts_shortcut() {
+        let mut app = App::test_default();
+        request_quit(&mut app);
+        assert!(app.should_quit);
+
+        let mut app = App::test_default();
+        app.status = AppStatus::Running;
+        request_quit(&mut app);
+        assert!(!app.should_quit);
+        assert_eq!(app.confirm.as_ref().map(|dialog| dialog.selected), Some(1));
+
+        press(&mut app, KeyCode::Enter);
+        assert!(app.confirm.is_none());
+        assert!(!app.should_quit);
+
+        request_quit(&mut app);
+        press(&mut app, KeyCode::Left);
+        assert_eq!(app.confirm.as_ref().map(|dialog| dialog.selected), Some(0));
+        press(&mut app, KeyCode::Esc);
+        assert!(!app.should_quit);
+
+        request_quit(&mut app);
+        press(&mut app, KeyCode::Char('Y'));
+        assert!(app.confirm.is_none());
+        assert!(app.should_quit);
+    }
+}
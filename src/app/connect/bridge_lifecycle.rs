@@ -7,7 +7,8 @@
 use crate::agent::bridge::BridgeLauncher;
 use crate::agent::client::{AgentConnection, BridgeClient};
 use crate::agent::events::ClientEvent;
-use crate::agent::wire::{BridgeCommand, BridgeEvent, CommandEnvelope};
+use crate::agent::stderr_log::SharedStderrBuffer;
+use crate::agent::wire::{BridgeCommand, BridgeEvent, CommandEnvelope, EventEnvelope};
 use crate::error::AppError;
 use std::rc::Rc;
 use std::time::Duration;
@@ -16,18 +17,30 @@ use tokio::sync::mpsc;
 use super::event_dispatch::handle_bridge_event;
 use super::{ConnectionSlot, StartConnectionParams, extract_app_error};
 
+/// Asks the Claude agent to skip telemetry, error reporting and auto-updates
+/// while offline mode is on.
+const DISABLE_NONESSENTIAL_TRAFFIC_ENV: &str = "CLAUDE_CODE_DISABLE_NONESSENTIAL_TRAFFIC";
+
 pub(super) async fn run_connection_task(
     params: StartConnectionParams,
     conn_slot_writer: Rc<std::cell::RefCell<Option<ConnectionSlot>>>,
 ) {
     tracing::debug!("starting agent bridge connection task");
 
-    let Some(launcher) = resolve_launcher(&params) else {
-        return;
-    };
-    let Some(mut bridge) = spawn_bridge_client(&params.event_tx, &launcher) else {
-        return;
+    let bridge = if params.mock_agent {
+        tracing::info!("using the built-in mock agent");
+        BridgeClient::mock(&params.stderr_log)
+    } else {
+        let Some(launcher) = resolve_launcher(&params) else {
+            return;
+        };
+        let Some(bridge) = spawn_bridge_client(&params.event_tx, &launcher, &params.stderr_log)
+        else {
+            return;
+        };
+        bridge
     };
+    let mut bridge = bridge.with_tee(params.event_tee.clone());
 
     let mut connected_once = false;
     let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel::<CommandEnvelope>();
@@ -60,9 +73,18 @@ pub(super) async fn run_connection_task(
 }
 
 fn resolve_launcher(params: &StartConnectionParams) -> Option<BridgeLauncher> {
-    match crate::agent::bridge::resolve_bridge_launcher(params.bridge_script.as_deref()) {
-        Ok(launcher) => {
+    match crate::agent::bridge::resolve_agent_launcher(
+        params.agent_profile.as_deref(),
+        params.bridge_script.as_deref(),
+    ) {
+        Ok(mut launcher) => {
             tracing::info!("resolved bridge launcher: {}", launcher.describe());
+            if params.offline {
+                launcher
+                    .env
+                    .entry(DISABLE_NONESSENTIAL_TRAFFIC_ENV.to_owned())
+                    .or_insert_with(|| "1".to_owned());
+            }
             Some(launcher)
         }
         Err(err) => {
@@ -81,8 +103,9 @@ fn resolve_launcher(params: &StartConnectionParams) -> Option<BridgeLauncher> {
 fn spawn_bridge_client(
     event_tx: &mpsc::UnboundedSender<ClientEvent>,
     launcher: &BridgeLauncher,
+    stderr_log: &SharedStderrBuffer,
 ) -> Option<BridgeClient> {
-    match BridgeClient::spawn(launcher) {
+    match BridgeClient::spawn(launcher, stderr_log) {
         Ok(client) => {
             tracing::debug!("bridge process spawned");
             Some(client)
@@ -173,6 +196,7 @@ async fn bridge_event_loop(
     cmd_rx: &mut mpsc::UnboundedReceiver<CommandEnvelope>,
     connected_once: &mut bool,
 ) {
+    let mut event_seq: u64 = 0;
     loop {
         tokio::select! {
             Some(cmd) = cmd_rx.recv() => {
@@ -189,6 +213,8 @@ async fn bridge_event_loop(
             event = bridge.recv() => {
                 match event {
                     Ok(Some(envelope)) => {
+                        event_seq += 1;
+                        let _span = bridge_event_span(event_seq, &envelope).entered();
                         handle_bridge_event(
                             &params.event_tx,
                             cmd_tx,
@@ -199,6 +225,12 @@ async fn bridge_event_loop(
                     }
                     Ok(None) => {
                         tracing::error!("bridge stdout closed unexpectedly");
+                        if *connected_once {
+                            let _ = params.event_tx.send(ClientEvent::BridgeExited(
+                                "Agent process exited unexpectedly".to_owned(),
+                            ));
+                            break;
+                        }
                         emit_connection_failed(
                             &params.event_tx,
                             "Bridge process exited unexpectedly".to_owned(),
@@ -208,6 +240,12 @@ async fn bridge_event_loop(
                     }
                     Err(err) => {
                         tracing::error!("bridge communication failure: {err}");
+                        if *connected_once {
+                            let _ = params.event_tx.send(ClientEvent::BridgeExited(format!(
+                                "Agent communication failure: {err}"
+                            )));
+                            break;
+                        }
                         emit_connection_failed(
                             &params.event_tx,
                             format!("Bridge communication failure: {err}"),
@@ -221,6 +259,18 @@ async fn bridge_event_loop(
     }
 }
 
+/// Span around one bridge event so every log line it causes carries the
+/// event's sequence number and session id.
+fn bridge_event_span(seq: u64, envelope: &EventEnvelope) -> tracing::Span {
+    tracing::info_span!(
+        "bridge_event",
+        seq,
+        event = envelope.event.name(),
+        session_id = envelope.event.session_id().unwrap_or_default(),
+        request_id = envelope.request_id.as_deref().unwrap_or_default(),
+    )
+}
+
 pub(super) fn emit_connection_failed(
     event_tx: &mpsc::UnboundedSender<ClientEvent>,
     message: String,
@@ -249,7 +299,15 @@ pub(super) async fn wait_for_bridge_initialized(
         let event = tokio::time::timeout(remaining, bridge.recv()).await;
         match event {
             Ok(Ok(Some(envelope))) => {
-                if matches!(envelope.event, BridgeEvent::Initialized { .. }) {
+                if let BridgeEvent::Initialized { result } = envelope.event {
+                    tracing::info!(
+                        "agent initialized: {} {} protocol={:?} ({:?})",
+                        result.agent_name,
+                        result.agent_version,
+                        result.protocol_version,
+                        result.capabilities
+                    );
+                    let _ = event_tx.send(ClientEvent::AgentInitialized(result));
                     return Ok(());
                 }
                 if matches!(envelope.event, BridgeEvent::ConnectionFailed { .. }) {
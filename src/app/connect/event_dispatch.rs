@@ -157,6 +157,12 @@ pub(super) fn handle_bridge_event(
         crate::agent::wire::BridgeEvent::McpSnapshot { servers, error, .. } => {
             let _ = event_tx.send(ClientEvent::McpSnapshotReceived { servers, error });
         }
+        crate::agent::wire::BridgeEvent::Pong { nonce } => {
+            let _ = event_tx.send(ClientEvent::Pong { nonce });
+        }
+        crate::agent::wire::BridgeEvent::Unrecognized { kind, reason, raw } => {
+            let _ = event_tx.send(ClientEvent::UnhandledEvent { kind, reason, raw });
+        }
     }
 }
 
@@ -17,7 +17,7 @@ use super::config::ConfigState;
 use super::dialog::DialogState;
 use super::plugins::PluginsState;
 use super::state::{
-    CacheMetrics, HistoryRetentionPolicy, HistoryRetentionStats, RenderCacheBudget,
+    CacheMetrics, HistoryRetentionPolicy, HistoryRetentionStats, MemoryBudget, RenderCacheBudget,
 };
 use super::trust;
 use super::view::ActiveView;
@@ -28,9 +28,10 @@ use crate::agent::events::ClientEvent;
 use crate::agent::model;
 use crate::agent::wire::SessionLaunchSettings;
 use crate::error::AppError;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
 /// Shorten cwd for display: use `~` for the home directory prefix.
@@ -59,12 +60,19 @@ struct StartConnectionParams {
     event_tx: mpsc::UnboundedSender<ClientEvent>,
     cwd_raw: String,
     bridge_script: Option<std::path::PathBuf>,
+    agent_profile: Option<String>,
+    mock_agent: bool,
+    stderr_log: crate::agent::stderr_log::SharedStderrBuffer,
+    event_tee: Option<crate::agent::event_tee::SharedEventTee>,
     resume_id: Option<String>,
     resume_requested: bool,
     session_launch_settings: SessionLaunchSettings,
+    offline: bool,
 }
 
-pub(crate) use session_start::{SessionStartReason, resume_session, start_new_session};
+pub(crate) use session_start::{
+    SessionStartReason, reload_session, resume_session, start_new_session, start_new_session_in,
+};
 
 /// Create the `App` struct in `Connecting` state and load shared settings state.
 #[allow(clippy::too_many_lines)]
@@ -128,36 +136,73 @@ pub fn create_app(cli: &Cli) -> App {
         tool_call_index: HashMap::new(),
         todos: Vec::<TodoItem>::new(),
         show_header: true,
+        status_log: super::status_log::StatusLog::new(),
+        toasts: super::toast::ToastQueue::default(),
+        confirm: None,
+        annotation_editor: None,
+        transcript_filter: None,
+        filter_editor: None,
+        command_palette: None,
+        help_overlay: None,
+        folded_turns: std::collections::BTreeSet::new(),
+        bookmarks: Vec::new(),
+        pinned_tool_call: None,
+        ctrl_c_armed_at: None,
+        launched_at: Instant::now(),
+        suggestions: Vec::new(),
+        last_prompt: None,
+        turn_retry: None,
+        tool_retry_sent: false,
+        tool_timings: crate::app::tool_timings::ToolTimings::default(),
+        watch: crate::app::watch_patterns::WatchState::default(),
+        health: crate::app::health::ConnectionHealth::default(),
+        unhandled_events: crate::app::unhandled_events::UnhandledEvents::default(),
+        changed_files: crate::app::changed_files::ChangedFiles::default(),
         show_todo_panel: false,
         todo_scroll: 0,
         todo_selected: 0,
         focus: FocusManager::default(),
         available_commands: Vec::new(),
+        custom_commands: Vec::new(),
+        file_index: None,
+        extra_file_indexes: Vec::new(),
+        additional_dirs: Vec::new(),
+        pending_session_note: None,
+        pending_rewind_note: None,
+        session_env: BTreeMap::new(),
+        model_params: crate::app::model_params::ModelParams::default(),
+        recent_files: super::mention::RecentFiles::default(),
         plugins: PluginsState::default(),
         available_agents: Vec::new(),
         available_models: Vec::new(),
         recent_sessions: Vec::new(),
         cached_frame_area: ratatui::layout::Rect::new(0, 0, 0, 0),
         selection: Option::<SelectionState>::None,
+        chat_visual: None,
         scrollbar_drag: None,
         rendered_chat_lines: Vec::new(),
         rendered_chat_area: ratatui::layout::Rect::new(0, 0, 0, 0),
+        minimap_marks: Vec::new(),
         rendered_input_lines: Vec::new(),
         rendered_input_area: ratatui::layout::Rect::new(0, 0, 0, 0),
         mention: None,
         slash: None,
         subagent: None,
+        emoji: None,
         pending_submit: None,
         paste_burst: super::paste_burst::PasteBurstDetector::new(),
         pending_paste_text: String::new(),
         pending_paste_session: None,
         active_paste_session: None,
         next_paste_session_id: 1,
+        pasted_paths_offer: None,
+        failed_command_offer: None,
         cached_todo_compact: None,
         git_branch: None,
         cached_header_line: None,
         cached_footer_line: None,
         update_check_hint: None,
+        update_release: None,
         session_usage: super::SessionUsageState::default(),
         usage: super::UsageState::default(),
         mcp: super::McpState::default(),
@@ -165,6 +210,7 @@ pub fn create_app(cli: &Cli) -> App {
         last_rate_limit_update: None,
         is_compacting: false,
         account_info: None,
+        agent_info: None,
         terminal_tool_calls: Vec::new(),
         needs_redraw: true,
         notifications: super::notify::NotificationManager::new(),
@@ -175,26 +221,104 @@ pub fn create_app(cli: &Cli) -> App {
         render_cache_budget: RenderCacheBudget::default(),
         history_retention: HistoryRetentionPolicy::default(),
         history_retention_stats: HistoryRetentionStats::default(),
+        memory_budget: cli.memory_budget.map_or_else(MemoryBudget::default, |mib| {
+            MemoryBudget::with_max_bytes(
+                usize::try_from(mib.saturating_mul(1024 * 1024)).unwrap_or(usize::MAX),
+            )
+        }),
+        terminal_background: crate::ui::theme::Background::default(),
+        screen_reader: super::screen_reader::ScreenReader::new(
+            cli.screen_reader,
+            cli.screen_reader_mirror.as_deref(),
+        ),
         cache_metrics: CacheMetrics::default(),
         fps_ema: None,
         last_frame_at: None,
         startup_connection_requested: false,
         connection_started: false,
         startup_bridge_script: cli.bridge_script.clone(),
+        startup_agent_profile: cli.agent.clone(),
+        startup_mock_agent: cli.mock,
         startup_resume_id: cli.resume.clone(),
         startup_resume_requested: cli.resume.is_some(),
+        bridge_reconnect: None,
+        bridge_stderr: Rc::default(),
+        event_tee: None,
+        login_flow: None,
+        http: super::http::HttpSettings::default(),
+        offline_flag: cli.offline,
+        read_only: cli.read_only,
+        print_transcript_on_exit: cli.print_transcript,
+        turn_started_at: None,
+        hook_summary: None,
+        pending_draft: None,
+        edit_check: crate::app::edit_check::EditCheck::default(),
     };
 
     if let Err(err) = super::config::initialize_shared_state(&mut app) {
         tracing::warn!("failed to initialize shared settings state: {err}");
         app.config.last_error = Some(err);
     }
+    super::screen_reader::sync(&mut app);
+    check_theme_contrast(&mut app);
+    super::memory::sync_welcome_memory_files(&mut app);
+    super::welcome::sync_welcome_banner(&mut app);
+    app.hook_summary = super::claude_hooks::configured_summary(&app);
+
+    match super::http::HttpSettings::load(cli.ca_bundle.as_deref()) {
+        Ok(http) => app.http = http,
+        Err(err) => {
+            tracing::warn!("{err}");
+            super::events::push_system_message_with_severity(
+                &mut app,
+                Some(super::SystemSeverity::Warning),
+                &format!("{err}. Using the default trust store."),
+            );
+        }
+    }
+
+    if let Some(path) = cli.tee_events.as_deref() {
+        match crate::agent::event_tee::EventTee::open(path) {
+            Ok(tee) => app.event_tee = Some(Rc::new(std::cell::RefCell::new(tee))),
+            Err(err) => super::events::push_system_message_with_severity(
+                &mut app,
+                Some(super::SystemSeverity::Warning),
+                &format!("Cannot write bridge events to {}: {err}", path.display()),
+            ),
+        }
+    }
 
     trust::initialize(&mut app);
+    super::slash::reload_custom_commands(&mut app);
     app.refresh_git_branch();
     app
 }
 
+/// Detect the terminal background and warn when theme colors are hard to read
+/// on it, unless the high-contrast theme already compensates.
+fn check_theme_contrast(app: &mut App) {
+    let theme = super::config::store::theme_name(&app.config.committed_preferences_document);
+    app.terminal_background = crate::ui::theme::Background::detect(theme.as_deref());
+    if app.config.high_contrast_effective() {
+        return;
+    }
+    let low = crate::ui::theme::low_contrast_colors(app.terminal_background);
+    if low.is_empty() {
+        return;
+    }
+    let colors =
+        low.iter().map(|(name, ratio)| format!("{name} ({ratio:.1}:1)")).collect::<Vec<_>>();
+    tracing::info!(background = ?app.terminal_background, "low theme contrast: {}", colors.join(", "));
+    super::events::push_system_message_with_severity(
+        app,
+        Some(super::SystemSeverity::Warning),
+        &format!(
+            "Some colors are hard to read on this terminal background: {}. Turn on High contrast in /config.",
+            colors.join(", ")
+        ),
+    );
+}
+
 /// Spawn the background bridge task.
 pub fn start_connection(app: &mut App) {
     if !app.startup_connection_requested || app.connection_started {
@@ -206,13 +330,73 @@ pub fn start_connection(app: &mut App) {
         event_tx: app.event_tx.clone(),
         cwd_raw: app.cwd_raw.clone(),
         bridge_script: app.startup_bridge_script.clone(),
+        agent_profile: app.startup_agent_profile.clone(),
+        mock_agent: app.startup_mock_agent,
+        stderr_log: Rc::clone(&app.bridge_stderr),
+        event_tee: app.event_tee.clone(),
         resume_id: app.startup_resume_id.clone(),
         resume_requested: app.startup_resume_requested,
         session_launch_settings: session_start::session_launch_settings_for_reason(
             app,
             session_start::SessionStartReason::Startup,
         ),
+        offline: app.is_offline(),
     };
+    CONN_SLOT.with(|slot| {
+        debug_assert!(
+            slot.borrow().is_none(),
+            "CONN_SLOT already populated -- start_connection() called twice?"
+        );
+    });
+    spawn_connection_task(params);
+}
+
+/// Respawn attempts made after the bridge exits mid-session before giving up.
+pub(crate) const MAX_RECONNECT_ATTEMPTS: u32 = 3;
+
+/// Delay before respawn attempt `attempt` (1-based): 1s, 2s, 4s, ...
+#[must_use]
+pub(crate) fn reconnect_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt.saturating_sub(1).min(5))
+}
+
+/// Respawn the bridge once a scheduled reconnect is due. The new bridge
+/// resumes the previous session so the in-memory transcript stays valid.
+pub fn reconnect_bridge_if_due(app: &mut App, now: Instant) {
+    let Some(reconnect) = app.bridge_reconnect.as_mut() else {
+        return;
+    };
+    if reconnect.next_attempt_at.is_none_or(|due| now < due) {
+        return;
+    }
+    reconnect.next_attempt_at = None;
+    reconnect.attempt += 1;
+    let resume_id = reconnect.resume_session_id.clone();
+    tracing::info!(
+        attempt = reconnect.attempt,
+        resume = resume_id.as_deref().unwrap_or("<new session>"),
+        "respawning agent bridge"
+    );
+    let params = StartConnectionParams {
+        event_tx: app.event_tx.clone(),
+        cwd_raw: app.cwd_raw.clone(),
+        bridge_script: app.startup_bridge_script.clone(),
+        agent_profile: app.startup_agent_profile.clone(),
+        mock_agent: app.startup_mock_agent,
+        stderr_log: Rc::clone(&app.bridge_stderr),
+        event_tee: app.event_tee.clone(),
+        resume_id,
+        resume_requested: false,
+        session_launch_settings: session_start::session_launch_settings_for_reason(
+            app,
+            session_start::SessionStartReason::Resume,
+        ),
+        offline: app.is_offline(),
+    };
+    spawn_connection_task(params);
+}
+
+fn spawn_connection_task(params: StartConnectionParams) {
     let conn_slot: Rc<std::cell::RefCell<Option<ConnectionSlot>>> =
         Rc::new(std::cell::RefCell::new(None));
     let conn_slot_writer = Rc::clone(&conn_slot);
@@ -222,10 +406,6 @@ pub fn start_connection(app: &mut App) {
     });
 
     CONN_SLOT.with(|slot| {
-        debug_assert!(
-            slot.borrow().is_none(),
-            "CONN_SLOT already populated -- start_connection() called twice?"
-        );
         *slot.borrow_mut() = Some(conn_slot);
     });
 }
@@ -247,6 +427,7 @@ pub(super) fn take_connection_slot() -> Option<ConnectionSlot> {
 
 #[cfg(test)]
 mod tests {
+    use super::reconnect_delay;
     use super::type_converters::map_session_update;
     use crate::agent::model;
     use crate::agent::types;
@@ -264,4 +445,11 @@ mod tests {
         assert_eq!(cfg.option_id, "model");
         assert_eq!(cfg.value, serde_json::Value::String("sonnet".to_owned()));
     }
+
+    #[test]
+    fn reconnect_delay_doubles_per_attempt() {
+        let secs: Vec<u64> = (1..=4).map(|attempt| reconnect_delay(attempt).as_secs()).collect();
+        assert_eq!(secs, vec![1, 2, 4, 8]);
+        assert_eq!(reconnect_delay(40).as_secs(), 32);
+    }
 }
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::agent::client::AgentConnection;
+use crate::agent::events::ClientEvent;
 use crate::agent::wire::SessionLaunchSettings;
-use crate::app::App;
-use crate::app::config::{language_input_validation_message, store};
+use crate::app::config::{DefaultPermissionMode, language_input_validation_message, store};
+use crate::app::{App, AppStatus};
 use serde_json::{Map, Value, json};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +37,10 @@ pub(crate) fn session_launch_settings_for_reason(
                 language,
                 settings: Some(build_session_settings_object(app)),
                 agent_progress_summaries: Some(true),
+                additional_directories: app.additional_dirs.clone(),
+                env: app.session_env.clone(),
+                max_thinking_tokens: app.model_params.thinking_budget,
+                max_turns: app.model_params.max_turns,
             }
         }
     }
@@ -53,10 +58,15 @@ fn build_session_settings_object(app: &App) -> Value {
         settings.insert("model".to_owned(), Value::String(model));
     }
 
+    let permission_mode = if app.read_only {
+        DefaultPermissionMode::Plan
+    } else {
+        app.config.default_permission_mode_effective()
+    };
     settings.insert(
         "permissions".to_owned(),
         json!({
-            "defaultMode": app.config.default_permission_mode_effective().as_stored()
+            "defaultMode": permission_mode.as_stored()
         }),
     );
     settings.insert("fastMode".to_owned(), Value::Bool(app.config.fast_mode_effective()));
@@ -91,7 +101,17 @@ pub(crate) fn start_new_session(
     conn: &AgentConnection,
     reason: SessionStartReason,
 ) -> anyhow::Result<()> {
-    conn.new_session(app.cwd_raw.clone(), session_launch_settings_for_reason(app, reason))
+    start_new_session_in(app, conn, app.cwd_raw.clone(), reason)
+}
+
+/// Start a new session rooted at `cwd` instead of the current directory.
+pub(crate) fn start_new_session_in(
+    app: &App,
+    conn: &AgentConnection,
+    cwd: String,
+    reason: SessionStartReason,
+) -> anyhow::Result<()> {
+    conn.new_session(cwd, session_launch_settings_for_reason(app, reason))
 }
 
 pub(crate) fn resume_session(
@@ -105,6 +125,31 @@ pub(crate) fn resume_session(
     )
 }
 
+/// Resume the current session so the agent picks up changed launch settings,
+/// posting `note` once it is back. Returns `false` when there is no session
+/// to reload; the settings then apply from the next one.
+pub(crate) fn reload_session(app: &mut App, label: String, note: String) -> bool {
+    let reload = app
+        .session_id
+        .clone()
+        .zip(app.conn.clone())
+        .filter(|_| app.agent_supports(|caps| caps.supports_resume_session));
+    let Some((session_id, conn)) = reload else {
+        return false;
+    };
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some(label);
+    app.resuming_session_id = Some(session_id.to_string());
+    app.pending_session_note = Some(note);
+    if let Err(err) = resume_session(app, &conn, session_id.to_string()) {
+        app.pending_session_note = None;
+        let _ = app
+            .event_tx
+            .send(ClientEvent::SlashCommandError(format!("Failed to reload the session: {err}")));
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::{SessionStartReason, session_launch_settings_for_reason};
@@ -112,6 +157,21 @@ mod tests {
     use crate::app::App;
     use crate::app::config::{DefaultPermissionMode, store};
 
+    #[test]
+    fn read_only_launch_forces_plan_mode() {
+        let mut app = App::test_default();
+        app.read_only = true;
+        store::set_default_permission_mode(
+            &mut app.config.committed_settings_document,
+            DefaultPermissionMode::BypassPermissions,
+        );
+
+        let launch_settings = session_launch_settings_for_reason(&app, SessionStartReason::Resume);
+
+        let settings = launch_settings.settings.expect("settings");
+        assert_eq!(settings["permissions"]["defaultMode"], "plan");
+    }
+
     #[test]
     fn persisted_launch_settings_include_model_and_permission_mode() {
         let mut app = App::test_default();
@@ -146,6 +206,18 @@ mod tests {
         assert_eq!(launch_settings.agent_progress_summaries, Some(true));
     }
 
+    #[test]
+    fn launch_settings_carry_additional_directories_except_on_logout() {
+        let mut app = App::test_default();
+        app.additional_dirs = vec!["/repo/packages/api".to_owned()];
+
+        let resume = session_launch_settings_for_reason(&app, SessionStartReason::Resume);
+        let logout = session_launch_settings_for_reason(&app, SessionStartReason::Logout);
+
+        assert_eq!(resume.additional_directories, vec!["/repo/packages/api".to_owned()]);
+        assert!(logout.is_empty());
+    }
+
     #[test]
     fn persisted_launch_settings_trim_language_value() {
         let mut app = App::test_default();
@@ -175,6 +175,21 @@ pub(super) fn map_session_update(update: types::SessionUpdate) -> Option<model::
                 surpassed_threshold,
             },
         ))),
+        types::SessionUpdate::UsageUpdate {
+            input_tokens,
+            cache_read_tokens,
+            cache_write_tokens,
+            output_tokens,
+            context_window,
+            total_cost_usd,
+        } => Some(model::SessionUpdate::UsageUpdate(model::UsageUpdate {
+            input_tokens,
+            cache_read_tokens,
+            cache_write_tokens,
+            output_tokens,
+            context_window,
+            total_cost_usd,
+        })),
         types::SessionUpdate::SessionStatusUpdate { status } => {
             Some(model::SessionUpdate::SessionStatusUpdate(match status {
                 types::SessionStatus::Compacting => model::SessionStatus::Compacting,
@@ -190,6 +205,34 @@ pub(super) fn map_session_update(update: types::SessionUpdate) -> Option<model::
                 pre_tokens,
             }))
         }
+        types::SessionUpdate::HookResult {
+            hook_event,
+            hook_name,
+            outcome,
+            exit_code,
+            decision,
+            reason,
+            input_modified,
+            system_message,
+        } => Some(model::SessionUpdate::HookResult(model::HookResult {
+            hook_event,
+            hook_name,
+            outcome: match outcome {
+                types::HookOutcome::Success => model::HookOutcome::Success,
+                types::HookOutcome::Error => model::HookOutcome::Error,
+                types::HookOutcome::Cancelled => model::HookOutcome::Cancelled,
+            },
+            exit_code,
+            decision: decision.map(|decision| match decision {
+                types::HookDecision::Block => model::HookDecision::Block,
+                types::HookDecision::Deny => model::HookDecision::Deny,
+                types::HookDecision::Ask => model::HookDecision::Ask,
+                types::HookDecision::Allow => model::HookDecision::Allow,
+            }),
+            reason,
+            input_modified,
+            system_message,
+        })),
     }
 }
 
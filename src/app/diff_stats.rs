@@ -0,0 +1,145 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-turn edit summary. When a turn that edited files ends, a
+//! [`DiffSummaryBlock`] with line counts per file is appended to its reply.
+//! Each file row expands to the turn's diffs for that file: Ctrl+o toggles all
+//! of them, `o` in keyboard selection mode (Ctrl+s) the row under the cursor.
+
+use super::{App, DiffSummaryBlock, FileDiffStat, InvalidationLevel, MessageBlock, MessageRole};
+use crate::agent::model;
+use similar::{ChangeTag, TextDiff};
+
+/// Marker in front of a file row, by expansion state.
+#[must_use]
+pub fn file_row_marker(expanded: bool) -> &'static str {
+    if expanded { "\u{25be}" } else { "\u{25b8}" }
+}
+
+/// Append a summary of the files edited since the last user prompt to the
+/// turn's reply. Does nothing when the turn edited no files.
+pub(crate) fn append_turn_diff_summary(app: &mut App) {
+    let turn_start = app
+        .messages
+        .iter()
+        .rposition(|message| matches!(message.role, MessageRole::User))
+        .map_or(0, |idx| idx + 1);
+    let Some(tail) = app
+        .messages
+        .iter()
+        .rposition(|message| matches!(message.role, MessageRole::Assistant))
+        .filter(|&idx| idx >= turn_start)
+    else {
+        return;
+    };
+
+    let mut files: Vec<FileDiffStat> = Vec::new();
+    for message in app.messages.iter().skip(turn_start) {
+        for block in &message.blocks {
+            let MessageBlock::ToolCall(tc) = block else {
+                continue;
+            };
+            if tc.hidden || tc.status != model::ToolCallStatus::Completed {
+                continue;
+            }
+            for content in &tc.content {
+                let model::ToolCallContent::Diff(diff) = content else {
+                    continue;
+                };
+                let path = super::explain::mention_path(app, &diff.path);
+                let (added, removed) = line_counts(diff);
+                if let Some(file) = files.iter_mut().find(|file| file.path == path) {
+                    file.added += added;
+                    file.removed += removed;
+                    file.diffs.push(diff.clone());
+                } else {
+                    files.push(FileDiffStat {
+                        path,
+                        added,
+                        removed,
+                        diffs: vec![diff.clone()],
+                        expanded: !app.tools_collapsed,
+                    });
+                }
+            }
+        }
+    }
+    if files.is_empty() {
+        return;
+    }
+
+    if let Some(message) = app.messages.get_mut(tail) {
+        message.blocks.push(MessageBlock::DiffSummary(DiffSummaryBlock {
+            files,
+            ..DiffSummaryBlock::default()
+        }));
+        app.invalidate_layout(InvalidationLevel::Single(tail));
+    }
+}
+
+/// Expand or collapse the summary row under the keyboard selection cursor.
+/// Returns false when the cursor is not on a file row.
+pub(crate) fn toggle_file_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let Some(row) = app.rendered_chat_lines.get(visual.cursor.row) else {
+        return false;
+    };
+    let row = row.trim_start().to_owned();
+    let message_index =
+        app.viewport.find_first_visible(app.viewport.scroll_offset + visual.cursor.row);
+    let Some(message) = app.messages.get_mut(message_index) else {
+        return false;
+    };
+    let mut toggled = false;
+    for block in &mut message.blocks {
+        let MessageBlock::DiffSummary(summary) = block else {
+            continue;
+        };
+        let Some(file) = summary.files.iter_mut().find(|file| {
+            row.strip_prefix(file_row_marker(file.expanded))
+                .is_some_and(|rest| rest.trim_start().starts_with(&file.path))
+        }) else {
+            continue;
+        };
+        file.expanded = !file.expanded;
+        summary.cache.invalidate();
+        toggled = true;
+        break;
+    }
+    if toggled {
+        app.invalidate_layout(InvalidationLevel::Single(message_index));
+    }
+    toggled
+}
+
+/// Lines added and removed by one edit.
+pub(super) fn line_counts(diff: &model::Diff) -> (usize, usize) {
+    let old = diff.old_text.as_deref().unwrap_or("");
+    TextDiff::from_lines(old, &diff.new_text).iter_all_changes().fold(
+        (0, 0),
+        |(added, removed), change| match change.tag() {
+            ChangeTag::Insert => (added + 1, removed),
+            ChangeTag::Delete => (added, removed + 1),
+            ChangeTag::Equal => (added, removed),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn line_counts_cover_rewrites_and_new_files() {
+        let mut diff = model::Diff::new("src/lib.rs", "a\nb\nc\n");
+        assert_eq!(line_counts(&diff), (3, 0));
+        diff.old_text = Some("a\nx\n".to_owned());
+        assert_eq!(line_counts(&diff), (2, 1));
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Diagnostics after edits. With `checkCommand` set in `~/.claude.json`:
+//!
+//! ```json
+//! "checkCommand": "cargo check --message-format=json"
+//! ```
+//!
+//! every completed edit tool call runs the command in the background from the
+//! session directory. Edits arriving while it runs queue one more run. The
+//! output is read as cargo's JSON messages, or as `file:line[:col]: error|warning`
+//! lines from most other compilers and linters. Errors and warnings in the
+//! edited files that the previous run did not report are posted to the
+//! transcript, each with its source line.
+
+use super::config::store;
+use super::events::push_system_message_with_severity;
+use super::lifecycle_hooks::shell;
+use super::{App, MessageBlock, SystemSeverity, changed_files};
+use crate::agent::events::ClientEvent;
+use crate::agent::model;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(300);
+const MAX_REPORTED: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct EditCheck {
+    running: bool,
+    /// Files edited since the last run started.
+    queued: BTreeSet<PathBuf>,
+    /// Files the running check reports on.
+    checking: BTreeSet<PathBuf>,
+    /// Diagnostics of the last finished run, without line numbers so that
+    /// edits shifting lines do not make old problems look new.
+    known: HashSet<(Level, String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Level {
+    Error,
+    Warning,
+}
+
+impl Level {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Diagnostic {
+    level: Level,
+    file: String,
+    line: usize,
+    column: Option<usize>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn key(&self) -> (Level, String, String) {
+        (self.level, self.file.clone(), self.message.clone())
+    }
+
+    fn location(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}:{}:{column}", self.file, self.line),
+            None => format!("{}:{}", self.file, self.line),
+        }
+    }
+}
+
+/// An edit tool call completed; queues a check of the files it names.
+pub(crate) fn tool_call_completed(app: &mut App, tool_call_id: &str) {
+    if store::check_command(&app.config.committed_preferences_document).is_none() {
+        return;
+    }
+    let Some((mi, bi)) = app.lookup_tool_call(tool_call_id) else {
+        return;
+    };
+    let Some(MessageBlock::ToolCall(tc)) = app.messages.get(mi).and_then(|m| m.blocks.get(bi))
+    else {
+        return;
+    };
+    if tc.status != model::ToolCallStatus::Completed {
+        return;
+    }
+    let paths = changed_files::edited_paths(tc, &app.cwd_raw);
+    if paths.is_empty() {
+        return;
+    }
+    app.edit_check.queued.extend(paths);
+    if !app.edit_check.running {
+        start(app);
+    }
+}
+
+fn start(app: &mut App) {
+    let Some(command) = store::check_command(&app.config.committed_preferences_document) else {
+        app.edit_check.queued.clear();
+        return;
+    };
+    app.edit_check.checking = std::mem::take(&mut app.edit_check.queued);
+    app.edit_check.running = true;
+    let mut process = shell(&command);
+    process
+        .current_dir(&app.cwd_raw)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    let tx = app.event_tx.clone();
+    tokio::task::spawn_local(async move {
+        let result = match tokio::time::timeout(CHECK_TIMEOUT, process.output()).await {
+            Ok(Ok(output)) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push('\n');
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                Ok(text)
+            }
+            Ok(Err(err)) => Err(format!("cannot run `{command}`: {err}")),
+            Err(_) => Err(format!("`{command}` timed out")),
+        };
+        let _ = tx.send(ClientEvent::EditCheckFinished { result });
+    });
+}
+
+pub(super) fn finished(app: &mut App, result: Result<String, String>) {
+    app.edit_check.running = false;
+    let checking = std::mem::take(&mut app.edit_check.checking);
+    match result {
+        Ok(output) => {
+            let diagnostics = parse_output(&output);
+            let fresh: Vec<_> = diagnostics
+                .iter()
+                .filter(|diagnostic| !app.edit_check.known.contains(&diagnostic.key()))
+                .filter_map(|diagnostic| {
+                    let path = checking.iter().find(|path| same_file(path, &diagnostic.file))?;
+                    Some((diagnostic, path))
+                })
+                .collect();
+            if !fresh.is_empty() {
+                let severity =
+                    if fresh.iter().any(|(diagnostic, _)| diagnostic.level == Level::Error) {
+                        SystemSeverity::Error
+                    } else {
+                        SystemSeverity::Warning
+                    };
+                let report = format_report(&fresh);
+                push_system_message_with_severity(app, Some(severity), &report);
+            }
+            app.edit_check.known = diagnostics.iter().map(Diagnostic::key).collect();
+        }
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Warning),
+            &format!("Check after edit failed: {message}"),
+        ),
+    }
+    if !app.edit_check.queued.is_empty() {
+        start(app);
+    }
+}
+
+fn format_report(fresh: &[(&Diagnostic, &PathBuf)]) -> String {
+    let count = fresh.len();
+    let mut report = format!(
+        "Check found {count} new problem{} in edited files:",
+        if count == 1 { "" } else { "s" }
+    );
+    for (diagnostic, path) in fresh.iter().take(MAX_REPORTED) {
+        let _ = write!(
+            report,
+            "\n\n**{}** `{}` {}",
+            diagnostic.level.name(),
+            diagnostic.location(),
+            diagnostic.message
+        );
+        if let Some(source) = source_line(path, diagnostic.line) {
+            let _ = write!(report, "\n```\n{:>5} | {source}\n```", diagnostic.line);
+        }
+    }
+    if count > MAX_REPORTED {
+        let _ = write!(report, "\n\n...and {} more", count - MAX_REPORTED);
+    }
+    report
+}
+
+fn source_line(path: &Path, line: usize) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    Some(text.trim_end().to_owned())
+}
+
+/// Compilers report paths relative to their own root, which need not be the
+/// session directory, so a relative path matches on trailing components.
+fn same_file(edited: &Path, reported: &str) -> bool {
+    let reported = Path::new(reported);
+    if reported.is_absolute() { edited == reported } else { edited.ends_with(reported) }
+}
+
+fn parse_output(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        let line = line.trim();
+        let diagnostic =
+            if line.starts_with('{') { parse_cargo_message(line) } else { parse_plain_line(line) };
+        if let Some(diagnostic) = diagnostic
+            && !diagnostics.contains(&diagnostic)
+        {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+/// One line of `cargo --message-format=json`.
+fn parse_cargo_message(line: &str) -> Option<Diagnostic> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value.get("reason")?.as_str()? != "compiler-message" {
+        return None;
+    }
+    let message = value.get("message")?;
+    let level = match message.get("level")?.as_str()? {
+        "error" => Level::Error,
+        "warning" => Level::Warning,
+        _ => return None,
+    };
+    let spans = message.get("spans")?.as_array()?;
+    let span = spans
+        .iter()
+        .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))
+        .or_else(|| spans.first())?;
+    let number = |key: &str| span.get(key)?.as_u64().and_then(|n| usize::try_from(n).ok());
+    Some(Diagnostic {
+        level,
+        file: span.get("file_name")?.as_str()?.to_owned(),
+        line: number("line_start")?,
+        column: number("column_start"),
+        message: message.get("message")?.as_str()?.to_owned(),
+    })
+}
+
+/// `file:line[:col]: error|warning...: message`, as gcc, clang, rustc
+/// `--message-format=short`, eslint `-f unix` and many others print.
+fn parse_plain_line(line: &str) -> Option<Diagnostic> {
+    let (location, rest) = line.split_once(": ")?;
+    let (file, line_number, column) = split_location(location)?;
+    let lower = rest.to_ascii_lowercase();
+    let level = if lower.starts_with("error") || lower.starts_with("fatal error") {
+        Level::Error
+    } else if lower.starts_with("warning") {
+        Level::Warning
+    } else {
+        return None;
+    };
+    let message = rest.split_once(": ").map_or(rest, |(_, message)| message).trim();
+    (!file.is_empty() && !message.is_empty()).then(|| Diagnostic {
+        level,
+        file: file.to_owned(),
+        line: line_number,
+        column,
+        message: message.to_owned(),
+    })
+}
+
+fn split_location(location: &str) -> Option<(&str, usize, Option<usize>)> {
+    let (head, last) = location.rsplit_once(':')?;
+    let last = last.parse().ok()?;
+    let with_column =
+        head.rsplit_once(':').and_then(|(file, line)| Some((file, line.parse().ok()?)));
+    Some(match with_column {
+        Some((file, line)) => (file, line, Some(last)),
+        None => (head, last, None),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_cargo_json_and_plain_lines() {
+        let output = concat!(
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"mismatched types","spans":[{"file_name":"src/lib.rs","line_start":4,"column_start":18,"is_primary":true}]}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","spans":[]}}"#,
+            "\n",
+            "src/main.c:12:3: warning: unused variable 'x' [-Wunused-variable]\n",
+            "lib/util.py:7: error: Name \"foo\" is not defined  [name-defined]\n",
+            "   Compiling demo v0.1.0\n",
+        );
+
+        let diagnostics = parse_output(output);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    level: Level::Error,
+                    file: "src/lib.rs".to_owned(),
+                    line: 4,
+                    column: Some(18),
+                    message: "mismatched types".to_owned(),
+                },
+                Diagnostic {
+                    level: Level::Warning,
+                    file: "src/main.c".to_owned(),
+                    line: 12,
+                    column: Some(3),
+                    message: "unused variable 'x' [-Wunused-variable]".to_owned(),
+                },
+                Diagnostic {
+                    level: Level::Error,
+                    file: "lib/util.py".to_owned(),
+                    line: 7,
+                    column: None,
+                    message: "Name \"foo\" is not defined  [name-defined]".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_only_new_problems_in_checked_files() {
+        let mut app = App::test_default();
+        app.edit_check.checking.insert(PathBuf::from("/repo/crate/src/lib.rs"));
+        app.edit_check.known.insert((
+            Level::Warning,
+            "src/lib.rs".to_owned(),
+            "unused import".to_owned(),
+        ));
+        let output = "src/lib.rs:3:5: warning: unused import\n\
+                      src/lib.rs:9:1: error: expected item\n\
+                      src/other.rs:1:1: error: unrelated\n";
+
+        finished(&mut app, Ok(output.to_owned()));
+
+        let report = app.messages.last().and_then(|message| match message.blocks.first() {
+            Some(MessageBlock::Text(text)) => Some(text.text.clone()),
+            _ => None,
+        });
+        assert_eq!(
+            report.as_deref(),
+            Some(
+                "Check found 1 new problem in edited files:\n\n**error** `src/lib.rs:9:1` expected item"
+            )
+        );
+        assert!(!app.edit_check.running);
+        assert_eq!(app.edit_check.known.len(), 3);
+    }
+}
@@ -0,0 +1,617 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `:` emoji autocomplete (`:rocket` -> 🚀), gated by the emoji autocomplete setting.
+
+use super::fuzzy::fuzzy_match;
+use super::{App, FocusTarget, dialog::DialogState};
+
+/// Maximum candidates shown in the dropdown.
+pub const MAX_VISIBLE: usize = 8;
+const MAX_CANDIDATES: usize = 50;
+/// Characters typed after `:` before the dropdown opens, so `a:b` and `10:30`
+/// style text never triggers it.
+const MIN_QUERY_CHARS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmojiCandidate {
+    pub name: &'static str,
+    pub glyph: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmojiState {
+    /// Character position where the `:` token starts.
+    pub trigger_row: usize,
+    pub trigger_col: usize,
+    /// Current query text after `:`.
+    pub query: String,
+    /// Filtered emoji candidates, best match first.
+    pub candidates: Vec<EmojiCandidate>,
+    /// Shared autocomplete dialog navigation state.
+    pub dialog: DialogState,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EmojiDetection {
+    trigger_row: usize,
+    trigger_col: usize,
+    query: String,
+}
+
+fn is_emoji_name_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' || ch == '+'
+}
+
+fn detect_emoji_at_cursor(
+    lines: &[String],
+    cursor_row: usize,
+    cursor_col: usize,
+) -> Option<EmojiDetection> {
+    let line = lines.get(cursor_row)?;
+    let chars: Vec<char> = line.chars().collect();
+    if cursor_col > chars.len() {
+        return None;
+    }
+
+    let mut token_start = cursor_col;
+    while token_start > 0 && !chars[token_start - 1].is_whitespace() {
+        token_start -= 1;
+    }
+    if token_start >= chars.len() || chars[token_start] != ':' {
+        return None;
+    }
+
+    let token_end =
+        (token_start + 1..chars.len()).find(|&i| chars[i].is_whitespace()).unwrap_or(chars.len());
+    if cursor_col < token_start + 1 + MIN_QUERY_CHARS || cursor_col > token_end {
+        return None;
+    }
+    if chars[token_start + 1..token_end].iter().any(|ch| !is_emoji_name_char(*ch)) {
+        return None;
+    }
+    let query: String = chars[token_start + 1..cursor_col].iter().collect();
+
+    Some(EmojiDetection { trigger_row: cursor_row, trigger_col: token_start, query })
+}
+
+fn filter_candidates(query: &str) -> Vec<EmojiCandidate> {
+    let mut scored: Vec<(i32, usize, EmojiCandidate)> = EMOJI
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &(name, glyph))| {
+            let found = fuzzy_match(name, query)?;
+            // Exact and prefix matches first so `:ok` offers `ok` before `smoking`.
+            let rank = if name == query {
+                i32::MAX
+            } else if name.starts_with(query) {
+                found.score.saturating_add(1000)
+            } else {
+                found.score
+            };
+            Some((rank, idx, EmojiCandidate { name, glyph }))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().take(MAX_CANDIDATES).map(|(_, _, candidate)| candidate).collect()
+}
+
+fn build_emoji_state(app: &App) -> Option<EmojiState> {
+    if !app.config.emoji_autocomplete_effective() {
+        return None;
+    }
+    let detection =
+        detect_emoji_at_cursor(app.input.lines(), app.input.cursor_row(), app.input.cursor_col())?;
+    let candidates = filter_candidates(&detection.query);
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(EmojiState {
+        trigger_row: detection.trigger_row,
+        trigger_col: detection.trigger_col,
+        query: detection.query,
+        candidates,
+        dialog: DialogState::default(),
+    })
+}
+
+pub fn activate(app: &mut App) {
+    let Some(state) = build_emoji_state(app) else {
+        return;
+    };
+    app.emoji = Some(state);
+    app.mention = None;
+    app.slash = None;
+    app.subagent = None;
+    app.claim_focus_target(FocusTarget::Mention);
+}
+
+pub fn update_query(app: &mut App) {
+    let Some(next_state) = build_emoji_state(app) else {
+        deactivate(app);
+        return;
+    };
+
+    if let Some(ref mut emoji) = app.emoji {
+        emoji.trigger_row = next_state.trigger_row;
+        emoji.trigger_col = next_state.trigger_col;
+        emoji.query = next_state.query;
+        emoji.candidates = next_state.candidates;
+        emoji.dialog = DialogState::default();
+    } else {
+        app.emoji = Some(next_state);
+        app.claim_focus_target(FocusTarget::Mention);
+    }
+}
+
+pub fn sync_with_cursor(app: &mut App) {
+    match (build_emoji_state(app), app.emoji.is_some()) {
+        (Some(_), true) => update_query(app),
+        (Some(_), false) => activate(app),
+        (None, true) => deactivate(app),
+        (None, false) => {}
+    }
+}
+
+pub fn deactivate(app: &mut App) {
+    app.emoji = None;
+    if app.mention.is_none() && app.slash.is_none() && app.subagent.is_none() {
+        app.release_focus_target(FocusTarget::Mention);
+    }
+}
+
+pub fn move_up(app: &mut App) {
+    if let Some(ref mut emoji) = app.emoji {
+        emoji.dialog.move_up(emoji.candidates.len(), MAX_VISIBLE);
+    }
+}
+
+pub fn move_down(app: &mut App) {
+    if let Some(ref mut emoji) = app.emoji {
+        emoji.dialog.move_down(emoji.candidates.len(), MAX_VISIBLE);
+    }
+}
+
+/// Replace the `:query` token with the selected emoji.
+pub fn confirm_selection(app: &mut App) {
+    let Some(emoji) = app.emoji.take() else {
+        return;
+    };
+    if let Some(candidate) = emoji.candidates.get(emoji.dialog.selected) {
+        replace_token(app, &emoji, candidate.glyph);
+    }
+    deactivate(app);
+}
+
+/// Handle a typed closing `:`. When the query names an emoji exactly
+/// (`:rocket:`), the token is replaced with the emoji; otherwise the colon is
+/// inserted as text and the dropdown closes.
+pub fn close_with_colon(app: &mut App) -> bool {
+    let Some(emoji) = app.emoji.take() else {
+        return false;
+    };
+    let exact = emoji.candidates.iter().find(|candidate| candidate.name == emoji.query);
+    let changed = if let Some(candidate) = exact {
+        replace_token(app, &emoji, candidate.glyph);
+        true
+    } else {
+        app.input.textarea_insert_char(':')
+    };
+    deactivate(app);
+    changed
+}
+
+fn replace_token(app: &mut App, emoji: &EmojiState, glyph: &str) {
+    let mut lines = app.input.lines().to_vec();
+    let Some(line) = lines.get(emoji.trigger_row) else {
+        return;
+    };
+    let chars: Vec<char> = line.chars().collect();
+    if emoji.trigger_col >= chars.len() || chars[emoji.trigger_col] != ':' {
+        return;
+    }
+
+    let token_end = (emoji.trigger_col + 1..chars.len())
+        .find(|&i| chars[i].is_whitespace())
+        .unwrap_or(chars.len());
+    let before: String = chars[..emoji.trigger_col].iter().collect();
+    let after: String = chars[token_end..].iter().collect();
+    let new_cursor_col = emoji.trigger_col + glyph.chars().count();
+    lines[emoji.trigger_row] = format!("{before}{glyph}{after}");
+    app.input.replace_lines_and_cursor(lines, emoji.trigger_row, new_cursor_col);
+}
+
+/// Shortcode table, roughly ordered by how often each emoji is used so ties
+/// in match quality favor the common ones.
+const EMOJI: &[(&str, &str)] = &[
+    ("thumbsup", "👍"),
+    ("+1", "👍"),
+    ("thumbsdown", "👎"),
+    ("-1", "👎"),
+    ("smile", "😄"),
+    ("joy", "😂"),
+    ("heart", "❤️"),
+    ("tada", "🎉"),
+    ("rocket", "🚀"),
+    ("fire", "🔥"),
+    ("eyes", "👀"),
+    ("white_check_mark", "✅"),
+    ("cross_mark", "❌"),
+    ("warning", "⚠️"),
+    ("bug", "🐛"),
+    ("sparkles", "✨"),
+    ("thinking", "🤔"),
+    ("pray", "🙏"),
+    ("clap", "👏"),
+    ("wave", "👋"),
+    ("ok_hand", "👌"),
+    ("muscle", "💪"),
+    ("100", "💯"),
+    ("star", "⭐"),
+    ("zap", "⚡"),
+    ("bulb", "💡"),
+    ("memo", "📝"),
+    ("lock", "🔒"),
+    ("unlock", "🔓"),
+    ("key", "🔑"),
+    ("wrench", "🔧"),
+    ("hammer", "🔨"),
+    ("gear", "⚙️"),
+    ("package", "📦"),
+    ("construction", "🚧"),
+    ("recycle", "♻️"),
+    ("boom", "💥"),
+    ("art", "🎨"),
+    ("lipstick", "💄"),
+    ("rotating_light", "🚨"),
+    ("green_heart", "💚"),
+    ("arrow_up", "⬆️"),
+    ("arrow_down", "⬇️"),
+    ("arrow_right", "➡️"),
+    ("arrow_left", "⬅️"),
+    ("heavy_plus_sign", "➕"),
+    ("heavy_minus_sign", "➖"),
+    ("pencil2", "✏️"),
+    ("truck", "🚚"),
+    ("page_facing_up", "📄"),
+    ("books", "📚"),
+    ("bookmark", "🔖"),
+    ("label", "🏷️"),
+    ("mag", "🔍"),
+    ("link", "🔗"),
+    ("pushpin", "📌"),
+    ("paperclip", "📎"),
+    ("chart_with_upwards_trend", "📈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("bar_chart", "📊"),
+    ("calendar", "📅"),
+    ("clock", "🕒"),
+    ("hourglass", "⌛"),
+    ("stopwatch", "⏱️"),
+    ("alarm_clock", "⏰"),
+    ("bell", "🔔"),
+    ("mega", "📣"),
+    ("speech_balloon", "💬"),
+    ("email", "📧"),
+    ("inbox_tray", "📥"),
+    ("outbox_tray", "📤"),
+    ("floppy_disk", "💾"),
+    ("computer", "💻"),
+    ("keyboard", "⌨️"),
+    ("desktop_computer", "🖥️"),
+    ("iphone", "📱"),
+    ("globe_with_meridians", "🌐"),
+    ("cloud", "☁️"),
+    ("sunny", "☀️"),
+    ("rainbow", "🌈"),
+    ("snowflake", "❄️"),
+    ("droplet", "💧"),
+    ("ocean", "🌊"),
+    ("seedling", "🌱"),
+    ("evergreen_tree", "🌲"),
+    ("cactus", "🌵"),
+    ("rose", "🌹"),
+    ("sunflower", "🌻"),
+    ("four_leaf_clover", "🍀"),
+    ("crab", "🦀"),
+    ("snake", "🐍"),
+    ("whale", "🐳"),
+    ("penguin", "🐧"),
+    ("elephant", "🐘"),
+    ("unicorn", "🦄"),
+    ("dog", "🐶"),
+    ("cat", "🐱"),
+    ("fox_face", "🦊"),
+    ("bee", "🐝"),
+    ("turtle", "🐢"),
+    ("rabbit", "🐰"),
+    ("owl", "🦉"),
+    ("coffee", "☕"),
+    ("tea", "🍵"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("wine_glass", "🍷"),
+    ("pizza", "🍕"),
+    ("hamburger", "🍔"),
+    ("taco", "🌮"),
+    ("cake", "🍰"),
+    ("cookie", "🍪"),
+    ("apple", "🍎"),
+    ("banana", "🍌"),
+    ("avocado", "🥑"),
+    ("popcorn", "🍿"),
+    ("trophy", "🏆"),
+    ("medal", "🏅"),
+    ("dart", "🎯"),
+    ("game_die", "🎲"),
+    ("video_game", "🎮"),
+    ("guitar", "🎸"),
+    ("musical_note", "🎵"),
+    ("headphones", "🎧"),
+    ("camera", "📷"),
+    ("movie_camera", "🎥"),
+    ("gift", "🎁"),
+    ("balloon", "🎈"),
+    ("confetti_ball", "🎊"),
+    ("crown", "👑"),
+    ("gem", "💎"),
+    ("moneybag", "💰"),
+    ("dollar", "💵"),
+    ("credit_card", "💳"),
+    ("shopping_cart", "🛒"),
+    ("house", "🏠"),
+    ("office", "🏢"),
+    ("hospital", "🏥"),
+    ("school", "🏫"),
+    ("car", "🚗"),
+    ("bike", "🚲"),
+    ("airplane", "✈️"),
+    ("ship", "🚢"),
+    ("train", "🚆"),
+    ("checkered_flag", "🏁"),
+    ("triangular_flag_on_post", "🚩"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("stop_sign", "🛑"),
+    ("question", "❓"),
+    ("exclamation", "❗"),
+    ("information_source", "ℹ️"),
+    ("heavy_check_mark", "✔️"),
+    ("ballot_box_with_check", "☑️"),
+    ("red_circle", "🔴"),
+    ("green_circle", "🟢"),
+    ("yellow_circle", "🟡"),
+    ("blue_circle", "🔵"),
+    ("white_circle", "⚪"),
+    ("black_circle", "⚫"),
+    ("new", "🆕"),
+    ("free", "🆓"),
+    ("ok", "🆗"),
+    ("cool", "🆒"),
+    ("up", "🆙"),
+    ("soon", "🔜"),
+    ("top", "🔝"),
+    ("back", "🔙"),
+    ("end", "🔚"),
+    ("grinning", "😀"),
+    ("smiley", "😃"),
+    ("grin", "😁"),
+    ("laughing", "😆"),
+    ("sweat_smile", "😅"),
+    ("rofl", "🤣"),
+    ("slightly_smiling_face", "🙂"),
+    ("upside_down_face", "🙃"),
+    ("wink", "😉"),
+    ("blush", "😊"),
+    ("innocent", "😇"),
+    ("heart_eyes", "😍"),
+    ("star_struck", "🤩"),
+    ("kissing_heart", "😘"),
+    ("yum", "😋"),
+    ("stuck_out_tongue", "😛"),
+    ("zany_face", "🤪"),
+    ("nerd_face", "🤓"),
+    ("sunglasses", "😎"),
+    ("partying_face", "🥳"),
+    ("smirk", "😏"),
+    ("unamused", "😒"),
+    ("disappointed", "😞"),
+    ("pensive", "😔"),
+    ("worried", "😟"),
+    ("confused", "😕"),
+    ("slightly_frowning_face", "🙁"),
+    ("persevere", "😣"),
+    ("tired_face", "😫"),
+    ("weary", "😩"),
+    ("pleading_face", "🥺"),
+    ("cry", "😢"),
+    ("sob", "😭"),
+    ("triumph", "😤"),
+    ("angry", "😠"),
+    ("rage", "😡"),
+    ("exploding_head", "🤯"),
+    ("flushed", "😳"),
+    ("scream", "😱"),
+    ("fearful", "😨"),
+    ("cold_sweat", "😰"),
+    ("hugs", "🤗"),
+    ("shushing_face", "🤫"),
+    ("zipper_mouth_face", "🤐"),
+    ("neutral_face", "😐"),
+    ("expressionless", "😑"),
+    ("no_mouth", "😶"),
+    ("roll_eyes", "🙄"),
+    ("grimacing", "😬"),
+    ("relieved", "😌"),
+    ("sleepy", "😪"),
+    ("sleeping", "😴"),
+    ("drooling_face", "🤤"),
+    ("mask", "😷"),
+    ("face_with_thermometer", "🤒"),
+    ("nauseated_face", "🤢"),
+    ("vomiting_face", "🤮"),
+    ("sneezing_face", "🤧"),
+    ("hot_face", "🥵"),
+    ("cold_face", "🥶"),
+    ("dizzy_face", "😵"),
+    ("cowboy_hat_face", "🤠"),
+    ("skull", "💀"),
+    ("ghost", "👻"),
+    ("alien", "👽"),
+    ("robot", "🤖"),
+    ("poop", "💩"),
+    ("clown_face", "🤡"),
+    ("see_no_evil", "🙈"),
+    ("hear_no_evil", "🙉"),
+    ("speak_no_evil", "🙊"),
+    ("raised_hands", "🙌"),
+    ("handshake", "🤝"),
+    ("point_up", "☝️"),
+    ("point_right", "👉"),
+    ("point_left", "👈"),
+    ("point_down", "👇"),
+    ("crossed_fingers", "🤞"),
+    ("victory_hand", "✌️"),
+    ("metal", "🤘"),
+    ("call_me_hand", "🤙"),
+    ("raised_hand", "✋"),
+    ("fist", "✊"),
+    ("facepalm", "🤦"),
+    ("shrug", "🤷"),
+    ("brain", "🧠"),
+    ("man_technologist", "👨‍💻"),
+    ("woman_technologist", "👩‍💻"),
+    ("ninja", "🥷"),
+    ("broken_heart", "💔"),
+    ("blue_heart", "💙"),
+    ("yellow_heart", "💛"),
+    ("purple_heart", "💜"),
+    ("orange_heart", "🧡"),
+    ("black_heart", "🖤"),
+    ("sparkling_heart", "💖"),
+    ("dizzy", "💫"),
+    ("collision", "💥"),
+    ("sweat_drops", "💦"),
+    ("dash", "💨"),
+    ("zzz", "💤"),
+    ("test_tube", "🧪"),
+    ("microscope", "🔬"),
+    ("telescope", "🔭"),
+    ("satellite", "📡"),
+    ("battery", "🔋"),
+    ("electric_plug", "🔌"),
+    ("magnet", "🧲"),
+    ("toolbox", "🧰"),
+    ("nut_and_bolt", "🔩"),
+    ("chains", "⛓️"),
+    ("shield", "🛡️"),
+    ("crystal_ball", "🔮"),
+    ("dna", "🧬"),
+    ("pill", "💊"),
+    ("syringe", "💉"),
+    ("broom", "🧹"),
+    ("wastebasket", "🗑️"),
+    ("file_folder", "📁"),
+    ("open_file_folder", "📂"),
+    ("clipboard", "📋"),
+    ("scroll", "📜"),
+    ("newspaper", "📰"),
+    ("notebook", "📓"),
+    ("ledger", "📒"),
+    ("triangular_ruler", "📐"),
+    ("straight_ruler", "📏"),
+    ("scissors", "✂️"),
+    ("pen", "🖊️"),
+    ("crayon", "🖍️"),
+    ("mailbox", "📫"),
+    ("envelope", "✉️"),
+    ("telephone_receiver", "📞"),
+    ("loudspeaker", "📢"),
+    ("mute", "🔇"),
+    ("sound", "🔉"),
+    ("loud_sound", "🔊"),
+    ("flashlight", "🔦"),
+    ("candle", "🕯️"),
+    ("moon", "🌙"),
+    ("earth_americas", "🌎"),
+    ("volcano", "🌋"),
+    ("mountain", "⛰️"),
+    ("tent", "⛺"),
+    ("sunrise", "🌅"),
+    ("stars", "🌠"),
+    ("milky_way", "🌌"),
+    ("tornado", "🌪️"),
+    ("umbrella", "☔"),
+    ("comet", "☄️"),
+    ("infinity", "♾️"),
+    ("copyright", "©️"),
+    ("registered", "®️"),
+    ("tm", "™️"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn detect_emoji_needs_two_query_chars_at_token_start() {
+        let lines = vec!["see :ro".to_owned(), "at 10:30".to_owned(), "x :r".to_owned()];
+        assert_eq!(
+            detect_emoji_at_cursor(&lines, 0, 7),
+            Some(EmojiDetection { trigger_row: 0, trigger_col: 4, query: "ro".to_owned() })
+        );
+        assert_eq!(detect_emoji_at_cursor(&lines, 1, 8), None);
+        assert_eq!(detect_emoji_at_cursor(&lines, 2, 4), None);
+    }
+
+    #[test]
+    fn exact_and_prefix_matches_rank_first() {
+        let names: Vec<&str> =
+            filter_candidates("ok").iter().take(2).map(|candidate| candidate.name).collect();
+        assert_eq!(names, vec!["ok", "ok_hand"]);
+    }
+
+    #[test]
+    fn confirm_replaces_token_with_glyph() {
+        let mut app = App::test_default();
+        app.input.set_text("ship it :rock now");
+        let _ = app.input.set_cursor_col(13);
+
+        sync_with_cursor(&mut app);
+        assert_eq!(app.emoji.as_ref().map(|emoji| emoji.query.as_str()), Some("rock"));
+        confirm_selection(&mut app);
+
+        assert_eq!(app.input.text(), "ship it 🚀 now");
+        assert!(app.emoji.is_none());
+    }
+
+    #[test]
+    fn closing_colon_completes_exact_name_only() {
+        let mut app = App::test_default();
+        app.input.set_text(":tada");
+        sync_with_cursor(&mut app);
+        assert!(close_with_colon(&mut app));
+        assert_eq!(app.input.text(), "🎉");
+
+        app.input.set_text(":tad");
+        sync_with_cursor(&mut app);
+        assert!(close_with_colon(&mut app));
+        assert_eq!(app.input.text(), ":tad:");
+    }
+
+    #[test]
+    fn disabled_setting_suppresses_dropdown() {
+        let mut app = App::test_default();
+        crate::app::config::store::set_emoji_autocomplete(
+            &mut app.config.committed_preferences_document,
+            false,
+        );
+        app.input.set_text(":rocket");
+
+        sync_with_cursor(&mut app);
+
+        assert!(app.emoji.is_none());
+    }
+}
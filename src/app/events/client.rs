@@ -3,6 +3,11 @@ use crate::agent::events::ClientEvent;
 
 #[allow(clippy::too_many_lines)]
 pub fn handle_client_event(app: &mut App, event: ClientEvent) {
+    let span = tracing::info_span!("client_event", session_id = tracing::field::Empty);
+    if let Some(session_id) = &app.session_id {
+        span.record("session_id", tracing::field::display(session_id));
+    }
+    let _span = span.entered();
     app.needs_redraw = true;
     match event {
         ClientEvent::SessionUpdate(update) => super::handle_session_update_event(app, update),
@@ -55,6 +60,7 @@ pub fn handle_client_event(app: &mut App, event: ClientEvent) {
         ClientEvent::AuthRequired { method_name, method_description } => {
             session::handle_auth_required_event(app, method_name, method_description);
         }
+        ClientEvent::BridgeExited(msg) => session::handle_bridge_exited_event(app, &msg),
         ClientEvent::ConnectionFailed(msg) => {
             session::handle_connection_failed_event(app, &msg);
         }
@@ -80,22 +86,59 @@ pub fn handle_client_event(app: &mut App, event: ClientEvent) {
             );
             crate::app::config::refresh_mcp_snapshot(app);
         }
-        ClientEvent::UpdateAvailable { latest_version, current_version } => {
-            session::handle_update_available_event(app, &latest_version, &current_version);
+        ClientEvent::UpdateAvailable { release, current_version, channel } => {
+            session::handle_update_available_event(app, release, &current_version, channel);
+        }
+        ClientEvent::SelfUpdateFinished { result, installed } => {
+            session::handle_self_update_finished_event(app, result, installed);
+        }
+        ClientEvent::GitCommitFinished { result } => {
+            session::handle_git_commit_finished_event(app, result);
+        }
+        ClientEvent::PullRequestFinished { result } => {
+            session::handle_pull_request_finished_event(app, result);
+        }
+        ClientEvent::IssueFetched { result } => {
+            session::handle_issue_fetched_event(app, result);
+        }
+        ClientEvent::ShareFinished { result } => {
+            session::handle_share_finished_event(app, result);
+        }
+        ClientEvent::EditCheckFinished { result } => {
+            crate::app::edit_check::finished(app, result);
+        }
+        ClientEvent::TestRunFinished { command, result } => {
+            session::handle_test_run_finished_event(app, command, result);
         }
         ClientEvent::ServiceStatus { severity, message } => {
             session::handle_service_status_event(app, severity, &message);
         }
         ClientEvent::AuthCompleted { conn } => {
+            crate::app::config::finish_login(app);
             session::handle_auth_completed_event(app, &conn);
         }
+        ClientEvent::LoginOutput(line) => {
+            crate::app::config::handle_login_output(app, &line);
+        }
+        ClientEvent::LoginFailed(message) => {
+            crate::app::config::handle_login_failed(app, &message);
+        }
         ClientEvent::LogoutCompleted => {
             session::handle_logout_completed_event(app);
         }
+        ClientEvent::AgentInitialized(result) => {
+            session::handle_agent_initialized_event(app, result);
+        }
         ClientEvent::StatusSnapshotReceived { account } => {
             app.account_info = Some(account);
             app.needs_redraw = true;
         }
+        ClientEvent::Pong { nonce } => {
+            crate::app::health::handle_pong(app, nonce, std::time::Instant::now());
+        }
+        ClientEvent::UnhandledEvent { kind, reason, raw } => {
+            crate::app::unhandled_events::record(app, kind, reason, raw);
+        }
         ClientEvent::McpSnapshotReceived { servers, error } => {
             tracing::debug!(
                 "received MCP snapshot: servers={} error_present={}",
@@ -104,6 +147,7 @@ pub fn handle_client_event(app: &mut App, event: ClientEvent) {
             );
             app.mcp.servers = servers;
             app.mcp.in_flight = false;
+            crate::app::memory::sync_welcome_mcp_servers(app);
             app.mcp.last_error = error;
             app.config.mcp_selected_server_index =
                 app.config.mcp_selected_server_index.min(app.mcp.servers.len().saturating_sub(1));
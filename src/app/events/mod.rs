@@ -13,7 +13,7 @@ mod turn;
 
 use super::{
     ActiveView, App, AppStatus, ChatMessage, MessageBlock, MessageRole, PendingCommandAck,
-    SystemSeverity, TextBlock,
+    SystemMessageRouting, SystemSeverity, TextBlock,
 };
 use crate::agent::model;
 use crate::app::todos::apply_plan_todos;
@@ -56,6 +56,26 @@ pub fn handle_terminal_event(app: &mut App, event: Event) {
 }
 
 fn dispatch_key_by_view(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    if app.confirm.is_some() {
+        super::confirm::handle_key(app, key);
+        return true;
+    }
+    if app.annotation_editor.is_some() {
+        super::annotations::handle_key(app, key);
+        return true;
+    }
+    if app.filter_editor.is_some() {
+        super::transcript_filter::handle_key(app, key);
+        return true;
+    }
+    if app.command_palette.is_some() {
+        super::palette::handle_key(app, key);
+        return true;
+    }
+    if app.help_overlay.is_some() {
+        super::help_overlay::handle_key(app, key);
+        return true;
+    }
     match app.active_view {
         ActiveView::Chat => {
             app.active_paste_session = None;
@@ -106,6 +126,7 @@ fn handle_session_update_event(app: &mut App, update: model::SessionUpdate) {
     let needs_history_retention = matches!(
         &update,
         model::SessionUpdate::AgentMessageChunk(_)
+            | model::SessionUpdate::AgentThoughtChunk(_)
             | model::SessionUpdate::ToolCall(_)
             | model::SessionUpdate::ToolCallUpdate(_)
             | model::SessionUpdate::CompactionBoundary(_)
@@ -129,16 +150,18 @@ fn handle_session_update(app: &mut App, update: model::SessionUpdate) {
         }
         model::SessionUpdate::UserMessageChunk(_) => {}
         model::SessionUpdate::AgentThoughtChunk(chunk) => {
-            tracing::debug!("Agent thought: {:?}", chunk);
-            app.status = AppStatus::Thinking;
+            streaming::handle_agent_thought_chunk(app, chunk);
         }
         model::SessionUpdate::Plan(plan) => {
             tracing::debug!("Plan update: {:?}", plan);
-            apply_plan_todos(app, &plan);
+            if app.agent_supports(|caps| caps.supports_plan_updates) {
+                apply_plan_todos(app, &plan);
+            }
         }
         model::SessionUpdate::AvailableCommandsUpdate(cmds) => {
             tracing::debug!("Available commands: {} commands", cmds.available_commands.len());
             app.available_commands = cmds.available_commands;
+            super::slash::merge_custom_commands(app);
             crate::app::plugins::clamp_selection(app);
             if app.slash.is_some() {
                 super::slash::update_query(app);
@@ -158,45 +181,9 @@ fn handle_session_update(app: &mut App, update: model::SessionUpdate) {
                 session::clear_pending_command(app);
             }
         }
-        model::SessionUpdate::CurrentModeUpdate(update) => {
-            let mode_id = update.current_mode_id.to_string();
-            if let Some(ref mut mode) = app.mode {
-                if let Some(info) = mode.available_modes.iter().find(|m| m.id == mode_id) {
-                    mode.current_mode_name.clone_from(&info.name);
-                    mode.current_mode_id = mode_id;
-                } else {
-                    mode.current_mode_name.clone_from(&mode_id);
-                    mode.current_mode_id = mode_id;
-                }
-                app.cached_footer_line = None;
-            }
-            if matches!(app.pending_command_ack, Some(PendingCommandAck::CurrentModeUpdate)) {
-                session::clear_pending_command(app);
-            }
-        }
+        model::SessionUpdate::CurrentModeUpdate(update) => handle_current_mode_update(app, &update),
         model::SessionUpdate::ConfigOptionUpdate(config) => {
-            tracing::debug!("Config update: {:?}", config);
-            let option_id = config.option_id;
-            let value = config.value;
-            let model_name =
-                if option_id == "model" { value.as_str().map(ToOwned::to_owned) } else { None };
-            app.config_options.insert(option_id.clone(), value);
-
-            if let Some(model_name) = model_name {
-                app.model_name = model_name;
-                app.cached_header_line = None;
-                app.update_welcome_model_once();
-            } else if option_id == "model" {
-                tracing::warn!("ConfigOptionUpdate for model carried non-string value");
-            }
-
-            if matches!(
-                app.pending_command_ack.as_ref(),
-                Some(PendingCommandAck::ConfigOptionUpdate { option_id: expected })
-                    if expected == &option_id
-            ) {
-                session::clear_pending_command(app);
-            }
+            handle_config_option_update(app, config);
         }
         model::SessionUpdate::FastModeUpdate(state) => {
             app.fast_mode_state = state;
@@ -205,6 +192,7 @@ fn handle_session_update(app: &mut App, update: model::SessionUpdate) {
         model::SessionUpdate::RateLimitUpdate(update) => {
             rate_limit::handle_rate_limit_update(app, &update);
         }
+        model::SessionUpdate::UsageUpdate(update) => rate_limit::handle_usage_update(app, update),
         model::SessionUpdate::SessionStatusUpdate(status) => {
             // TODO(runtime-verification): confirm in real SDK sessions that compaction
             // status updates are emitted consistently; if not, add a fallback indicator.
@@ -219,6 +207,51 @@ fn handle_session_update(app: &mut App, update: model::SessionUpdate) {
         model::SessionUpdate::CompactionBoundary(boundary) => {
             rate_limit::handle_compaction_boundary_update(app, boundary);
         }
+        model::SessionUpdate::HookResult(result) => {
+            super::claude_hooks::handle_hook_result(app, &result);
+        }
+    }
+}
+
+fn handle_current_mode_update(app: &mut App, update: &model::CurrentModeUpdate) {
+    let mode_id = update.current_mode_id.to_string();
+    if let Some(ref mut mode) = app.mode {
+        if let Some(info) = mode.available_modes.iter().find(|m| m.id == mode_id) {
+            mode.current_mode_name.clone_from(&info.name);
+            mode.current_mode_id = mode_id;
+        } else {
+            mode.current_mode_name.clone_from(&mode_id);
+            mode.current_mode_id = mode_id;
+        }
+        app.cached_footer_line = None;
+    }
+    if matches!(app.pending_command_ack, Some(PendingCommandAck::CurrentModeUpdate)) {
+        session::clear_pending_command(app);
+    }
+}
+
+fn handle_config_option_update(app: &mut App, config: model::ConfigOptionUpdate) {
+    tracing::debug!("Config update: {:?}", config);
+    let option_id = config.option_id;
+    let value = config.value;
+    let model_name =
+        if option_id == "model" { value.as_str().map(ToOwned::to_owned) } else { None };
+    app.config_options.insert(option_id.clone(), value);
+
+    if let Some(model_name) = model_name {
+        app.model_name = model_name;
+        app.cached_header_line = None;
+        app.update_welcome_model_once();
+    } else if option_id == "model" {
+        tracing::warn!("ConfigOptionUpdate for model carried non-string value");
+    }
+
+    if matches!(
+        app.pending_command_ack.as_ref(),
+        Some(PendingCommandAck::ConfigOptionUpdate { option_id: expected })
+            if expected == &option_id
+    ) {
+        session::clear_pending_command(app);
     }
 }
 
@@ -227,10 +260,30 @@ pub(crate) fn push_system_message_with_severity(
     severity: Option<SystemSeverity>,
     message: &str,
 ) {
+    push_system_message_with_blocks(app, severity, message, Vec::new());
+}
+
+/// Like [`push_system_message_with_severity`], with extra blocks after the text.
+pub(crate) fn push_system_message_with_blocks(
+    app: &mut App,
+    severity: Option<SystemSeverity>,
+    message: &str,
+    extra_blocks: Vec<MessageBlock>,
+) {
+    app.status_log.record(severity, message);
+    if app.config.system_message_routing_effective() == SystemMessageRouting::LogPane
+        && matches!(severity, Some(SystemSeverity::Info | SystemSeverity::Warning))
+    {
+        app.needs_redraw = true;
+        return;
+    }
+    let mut blocks = vec![MessageBlock::Text(TextBlock::from_complete(message))];
+    blocks.extend(extra_blocks);
     app.messages.push(ChatMessage {
         role: MessageRole::System(severity),
-        blocks: vec![MessageBlock::Text(TextBlock::from_complete(message))],
+        blocks,
         usage: None,
+        annotation: None,
     });
     app.enforce_history_retention_tracked();
     app.viewport.engage_auto_scroll();
@@ -269,8 +322,10 @@ fn session_update_name(update: &model::SessionUpdate) -> &'static str {
         model::SessionUpdate::ConfigOptionUpdate(_) => "ConfigOptionUpdate",
         model::SessionUpdate::FastModeUpdate(_) => "FastModeUpdate",
         model::SessionUpdate::RateLimitUpdate(_) => "RateLimitUpdate",
+        model::SessionUpdate::UsageUpdate(_) => "UsageUpdate",
         model::SessionUpdate::SessionStatusUpdate(_) => "SessionStatusUpdate",
         model::SessionUpdate::CompactionBoundary(_) => "CompactionBoundary",
+        model::SessionUpdate::HookResult(_) => "HookResult",
     }
 }
 
@@ -301,8 +356,8 @@ mod tests {
     use crate::agent::events::ServiceStatusSeverity;
     use crate::app::{
         ActiveView, BlockCache, CancelOrigin, FocusOwner, FocusTarget, HelpView, InlinePermission,
-        SelectionKind, SelectionPoint, SelectionState, TextBlockSpacing, TodoItem, TodoStatus,
-        ToolCallInfo, ToolCallScope, mention,
+        MessageUsage, SelectionKind, SelectionPoint, SelectionState, TextBlockSpacing, TodoItem,
+        TodoStatus, ToolCallInfo, ToolCallScope, mention,
     };
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
     use pretty_assertions::assert_eq;
@@ -320,12 +375,14 @@ mod tests {
             sdk_tool_name: "Read".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status,
             content: vec![],
             collapsed: false,
             hidden: false,
             terminal_id: None,
             terminal_command: None,
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -339,11 +396,14 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         }
     }
 
     fn assistant_msg(blocks: Vec<MessageBlock>) -> ChatMessage {
-        ChatMessage { role: MessageRole::Assistant, blocks, usage: None }
+        ChatMessage { role: MessageRole::Assistant, blocks, usage: None, annotation: None }
     }
 
     fn user_msg(text: &str) -> ChatMessage {
@@ -351,6 +411,7 @@ mod tests {
             role: MessageRole::User,
             blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -734,6 +795,52 @@ mod tests {
         assert_eq!(tc.terminal_output.as_deref(), Some("line 1\nline 2"));
     }
 
+    #[test]
+    fn read_only_mode_denies_execute_permission_with_note() {
+        let mut app = make_test_app();
+        app.read_only = true;
+        let tc = model::ToolCall::new("tc-exec", "rm -rf build")
+            .kind(model::ToolKind::Execute)
+            .status(model::ToolCallStatus::Pending);
+        handle_client_event(
+            &mut app,
+            ClientEvent::SessionUpdate(model::SessionUpdate::ToolCall(tc)),
+        );
+
+        let (response_tx, mut response_rx) = tokio::sync::oneshot::channel();
+        let request = model::RequestPermissionRequest::new(
+            "session",
+            model::ToolCallUpdate::new("tc-exec", model::ToolCallUpdateFields::new()),
+            vec![
+                model::PermissionOption::new(
+                    "allow",
+                    "Allow",
+                    model::PermissionOptionKind::AllowOnce,
+                ),
+                model::PermissionOption::new(
+                    "deny",
+                    "Deny",
+                    model::PermissionOptionKind::RejectOnce,
+                ),
+                model::PermissionOption::new(
+                    "deny-always",
+                    "Always deny",
+                    model::PermissionOptionKind::RejectAlways,
+                ),
+            ],
+        );
+        handle_client_event(&mut app, ClientEvent::PermissionRequest { request, response_tx });
+
+        let response = response_rx.try_recv().expect("permission answered");
+        let model::RequestPermissionOutcome::Selected(selected) = response.outcome else {
+            panic!("expected a selected outcome");
+        };
+        assert_eq!(selected.option_id, "deny");
+        assert!(app.pending_permission_ids.is_empty());
+        let last = app.messages.last().expect("note pushed");
+        assert!(matches!(last.role, MessageRole::System(Some(SystemSeverity::Warning))));
+    }
+
     #[test]
     fn tool_call_update_noop_does_not_bump_epochs() {
         let mut app = make_test_app();
@@ -1267,17 +1374,55 @@ mod tests {
         handle_client_event(
             &mut app,
             ClientEvent::UpdateAvailable {
-                latest_version: "0.3.0".into(),
+                release: crate::app::ReleaseInfo {
+                    version: "0.3.0".into(),
+                    ..crate::app::ReleaseInfo::default()
+                },
                 current_version: "0.2.0".into(),
+                channel: crate::app::UpdateChannel::Stable,
             },
         );
 
         assert_eq!(
             app.update_check_hint.as_deref(),
-            Some("Update available: v0.3.0 (current v0.2.0)  Ctrl+U to hide")
+            Some("Update available (stable): v0.3.0 (current v0.2.0)  Ctrl+U to hide")
         );
     }
 
+    #[test]
+    fn ctrl_r_opens_release_notes_when_update_hint_has_notes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut app = make_test_app();
+        app.settings_home_override = Some(dir.path().to_path_buf());
+        app.cwd_raw = dir.path().to_string_lossy().to_string();
+        handle_client_event(
+            &mut app,
+            ClientEvent::UpdateAvailable {
+                release: crate::app::ReleaseInfo {
+                    version: "0.3.0".into(),
+                    notes: "### Fixes\n\n- Faster startup".into(),
+                    ..crate::app::ReleaseInfo::default()
+                },
+                current_version: "0.2.0".into(),
+                channel: crate::app::UpdateChannel::Stable,
+            },
+        );
+        assert_eq!(
+            app.update_check_hint.as_deref(),
+            Some(
+                "Update available (stable): v0.3.0 (current v0.2.0)  Ctrl+R notes  Ctrl+U to hide"
+            )
+        );
+
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+        );
+
+        assert_eq!(app.active_view, crate::app::ActiveView::Config);
+        assert!(app.config.release_notes_overlay().is_some());
+    }
+
     #[test]
     fn service_status_warning_pushes_system_warning_without_locking_input() {
         let mut app = make_test_app();
@@ -1657,6 +1802,44 @@ mod tests {
         assert!(matches!(app.status, AppStatus::Ready));
     }
 
+    #[test]
+    fn thought_chunks_build_thinking_block_before_reply_text() {
+        let mut app = make_test_app();
+        app.tools_collapsed = true;
+        for chunk in ["Let me ", "check the tests."] {
+            handle_client_event(
+                &mut app,
+                ClientEvent::SessionUpdate(model::SessionUpdate::AgentThoughtChunk(
+                    model::ContentChunk::new(model::ContentBlock::Text(model::TextContent::new(
+                        chunk,
+                    ))),
+                )),
+            );
+        }
+        assert!(matches!(app.status, AppStatus::Thinking));
+        handle_client_event(
+            &mut app,
+            ClientEvent::SessionUpdate(model::SessionUpdate::AgentMessageChunk(
+                model::ContentChunk::new(model::ContentBlock::Text(model::TextContent::new(
+                    "All green.",
+                ))),
+            )),
+        );
+
+        let Some(message) = app.messages.last() else {
+            panic!("expected assistant message");
+        };
+        assert!(matches!(message.role, MessageRole::Assistant));
+        let [MessageBlock::Thinking(thinking), MessageBlock::Text(reply)] =
+            message.blocks.as_slice()
+        else {
+            panic!("expected thinking block followed by text");
+        };
+        assert_eq!(thinking.text, "Let me check the tests.");
+        assert!(!thinking.expanded);
+        assert_eq!(reply.text, "All green.");
+    }
+
     #[test]
     fn resume_history_renders_user_message_chunks() {
         let mut app = make_test_app();
@@ -1763,6 +1946,25 @@ mod tests {
         assert_eq!(app.session_id.as_ref().map(ToString::to_string).as_deref(), Some("session-x"));
     }
 
+    #[test]
+    fn log_pane_routing_keeps_info_out_of_transcript_but_not_errors() {
+        let mut app = make_test_app();
+        app.config.committed_preferences_document = serde_json::json!({ "systemMessages": "log" });
+
+        push_system_message_with_severity(
+            &mut app,
+            Some(SystemSeverity::Info),
+            "Session successfully compacted.",
+        );
+        assert!(app.messages.is_empty());
+        push_system_message_with_severity(&mut app, Some(SystemSeverity::Error), "Bridge died");
+        assert_eq!(app.messages.len(), 1);
+
+        let logged: Vec<_> = app.status_log.entries().map(|entry| entry.text.as_str()).collect();
+        assert_eq!(logged, vec!["Session successfully compacted.", "Bridge died"]);
+        assert_eq!(app.status_log.unseen, 2);
+    }
+
     #[test]
     fn first_agent_chunk_clears_unconfirmed_compacting_without_success_message() {
         let mut app = make_test_app();
@@ -1827,6 +2029,91 @@ mod tests {
         assert!(block.text.contains("Press Ctrl+Q to quit and try again"));
     }
 
+    #[test]
+    fn overloaded_turn_error_schedules_retry_instead_of_failing() {
+        let mut app = make_test_app();
+        app.status = AppStatus::Thinking;
+        app.last_prompt = Some("fix the build".into());
+
+        handle_client_event(
+            &mut app,
+            ClientEvent::TurnError(r#"API Error: 529 {"type":"overloaded_error"}"#.into()),
+        );
+
+        assert!(matches!(app.status, AppStatus::Ready));
+        let retry = app.turn_retry.as_ref().expect("retry scheduled");
+        assert_eq!((retry.attempt, retry.prompt.as_str()), (1, "fix the build"));
+        assert!(
+            crate::app::turn_retry::footer_hint(&app, Instant::now())
+                .is_some_and(|hint| hint.contains("retrying in 2s (1/4)"))
+        );
+        let Some(MessageBlock::Text(block)) = app.messages.last().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected retry notice");
+        };
+        assert!(!block.text.contains("Turn failed"));
+
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        );
+        assert!(app.turn_retry.is_none());
+    }
+
+    #[test]
+    fn unclassified_bridge_turn_error_is_refined_and_carries_recovery_hint() {
+        let mut app = make_test_app();
+
+        handle_client_event(
+            &mut app,
+            ClientEvent::TurnErrorClassified {
+                message: "request failed: getaddrinfo ENOTFOUND api.anthropic.com".into(),
+                class: TurnErrorClass::Other,
+            },
+        );
+
+        assert!(matches!(app.status, AppStatus::Error));
+        let Some(last) = app.messages.last() else {
+            panic!("expected system error message");
+        };
+        let Some(MessageBlock::RecoveryHint(hint)) = last.blocks.last() else {
+            panic!("expected recovery hint block");
+        };
+        assert_eq!(hint.class, TurnErrorClass::Network);
+        assert!(hint.class.recovery_hint().contains("proxy"));
+    }
+
+    #[test]
+    fn agent_initialized_from_old_bridge_warns_and_gates_missing_features() {
+        let mut app = make_test_app();
+
+        handle_client_event(
+            &mut app,
+            ClientEvent::AgentInitialized(crate::agent::types::InitializeResult {
+                agent_name: "claude-rs-agent-bridge".into(),
+                agent_version: "0.0.9".into(),
+                auth_methods: Vec::new(),
+                capabilities: crate::agent::types::AgentCapabilities {
+                    prompt_image: true,
+                    supports_resume_session: true,
+                    ..Default::default()
+                },
+                protocol_version: None,
+            }),
+        );
+
+        let Some(MessageBlock::Text(block)) = app.messages.last().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected protocol warning");
+        };
+        assert!(block.text.contains("protocol v0, older than the v1"));
+        assert!(
+            block.text.contains("Unavailable with this agent: permission modes, plan updates.")
+        );
+        assert!(!app.agent_supports(|caps| caps.supports_modes));
+        assert!(app.agent_supports(|caps| caps.prompt_image));
+    }
+
     #[test]
     fn turn_error_plan_limit_shows_next_steps_guidance() {
         let mut app = make_test_app();
@@ -1904,6 +2191,75 @@ mod tests {
         assert_eq!(app.exit_error, Some(crate::error::AppError::ConnectionFailed));
     }
 
+    #[test]
+    fn bridge_exit_schedules_reconnect_and_keeps_transcript() {
+        let mut app = make_test_app();
+        app.session_id = Some(model::SessionId::new("live-session"));
+        app.status = AppStatus::Thinking;
+        let messages_before = app.messages.len();
+
+        handle_client_event(&mut app, ClientEvent::BridgeExited("Agent process exited".into()));
+
+        assert!(!app.should_quit);
+        assert!(matches!(app.status, AppStatus::Connecting));
+        assert!(app.session_id.is_none());
+        assert_eq!(app.messages.len(), messages_before + 1);
+        let reconnect = app.bridge_reconnect.as_ref().expect("reconnect scheduled");
+        assert_eq!(reconnect.attempt, 0);
+        assert_eq!(reconnect.resume_session_id.as_deref(), Some("live-session"));
+        assert!(reconnect.next_attempt_at.is_some());
+    }
+
+    #[test]
+    fn failed_reconnect_backs_off_then_gives_up_without_quitting() {
+        let mut app = make_test_app();
+        app.bridge_reconnect = Some(crate::app::BridgeReconnect {
+            attempt: 1,
+            resume_session_id: Some("live-session".into()),
+            next_attempt_at: None,
+        });
+
+        handle_client_event(&mut app, ClientEvent::ConnectionFailed("spawn failed".into()));
+        handle_client_event(
+            &mut app,
+            ClientEvent::FatalError(crate::error::AppError::AdapterCrashed),
+        );
+        assert!(!app.should_quit);
+        assert!(app.bridge_reconnect.as_ref().is_some_and(|r| r.next_attempt_at.is_some()));
+
+        app.bridge_reconnect = Some(crate::app::BridgeReconnect {
+            attempt: crate::app::connect::MAX_RECONNECT_ATTEMPTS,
+            resume_session_id: Some("live-session".into()),
+            next_attempt_at: None,
+        });
+        handle_client_event(&mut app, ClientEvent::ConnectionFailed("spawn failed".into()));
+        handle_client_event(
+            &mut app,
+            ClientEvent::FatalError(crate::error::AppError::AdapterCrashed),
+        );
+        assert!(!app.should_quit);
+        assert!(matches!(app.status, AppStatus::Error));
+        assert!(app.bridge_reconnect.as_ref().is_some_and(|r| r.next_attempt_at.is_none()));
+    }
+
+    #[test]
+    fn reconnected_session_keeps_transcript_and_clears_reconnect_state() {
+        let mut app = make_test_app();
+        app.bridge_reconnect = Some(crate::app::BridgeReconnect {
+            attempt: 1,
+            resume_session_id: Some("test-session".into()),
+            next_attempt_at: None,
+        });
+        let messages_before = app.messages.len();
+
+        handle_client_event(&mut app, connected_event("claude-sonnet"));
+
+        assert!(app.bridge_reconnect.is_none());
+        assert_eq!(app.session_id, Some(model::SessionId::new("test-session")));
+        assert_eq!(app.messages.len(), messages_before + 1);
+        assert!(matches!(app.status, AppStatus::Ready));
+    }
+
     #[test]
     fn compaction_boundary_enables_compacting_and_records_boundary() {
         let mut app = make_test_app();
@@ -1949,6 +2305,68 @@ mod tests {
         assert_eq!(app.session_usage.last_compaction_pre_tokens, Some(234_567));
     }
 
+    #[test]
+    fn usage_updates_track_context_and_accumulate_output() {
+        let mut app = make_test_app();
+        let request = model::UsageUpdate {
+            input_tokens: Some(20),
+            cache_read_tokens: Some(4_000),
+            cache_write_tokens: Some(80),
+            ..model::UsageUpdate::default()
+        };
+        let result = model::UsageUpdate {
+            output_tokens: Some(300),
+            context_window: Some(200_000),
+            total_cost_usd: Some(0.25),
+            ..model::UsageUpdate::default()
+        };
+        for update in [request, result, result] {
+            handle_client_event(
+                &mut app,
+                ClientEvent::SessionUpdate(model::SessionUpdate::UsageUpdate(update)),
+            );
+        }
+
+        assert_eq!(app.session_usage.context_tokens(), Some(4_100));
+        assert_eq!(app.session_usage.total_output_tokens, 600);
+        assert_eq!(app.session_usage.context_window, Some(200_000));
+        assert_eq!(app.session_usage.total_cost_usd, Some(0.25));
+    }
+
+    #[test]
+    fn usage_updates_annotate_turn_message_with_incremental_cost() {
+        let mut app = make_test_app();
+        app.session_usage.total_cost_usd = Some(0.5);
+        app.messages.push(user_msg("hi"));
+        app.messages
+            .push(assistant_msg(vec![MessageBlock::Text(TextBlock::from_complete("hello"))]));
+        let updates = [
+            model::UsageUpdate { input_tokens: Some(1_000), ..model::UsageUpdate::default() },
+            model::UsageUpdate {
+                output_tokens: Some(40),
+                total_cost_usd: Some(0.75),
+                ..model::UsageUpdate::default()
+            },
+        ];
+        for update in updates {
+            handle_client_event(
+                &mut app,
+                ClientEvent::SessionUpdate(model::SessionUpdate::UsageUpdate(update)),
+            );
+        }
+
+        let usage = app.messages.last().and_then(|message| message.usage.clone());
+        assert_eq!(
+            usage,
+            Some(MessageUsage {
+                input_tokens: Some(1_000),
+                output_tokens: Some(40),
+                cost_usd: Some(0.25),
+                ..MessageUsage::default()
+            })
+        );
+    }
+
     #[test]
     fn fast_mode_update_sets_state_and_invalidates_footer_cache() {
         let mut app = make_test_app();
@@ -2132,6 +2550,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn turn_complete_offers_follow_ups_selectable_with_alt_digit() {
+        let mut app = make_test_app();
+        let mut test_run = tool_call("bash-1", model::ToolCallStatus::Failed);
+        test_run.sdk_tool_name = "Bash".into();
+        test_run.terminal_command = Some("cargo test".into());
+        let mut edit = tool_call("edit-1", model::ToolCallStatus::Completed);
+        edit.content = vec![model::ToolCallContent::Diff(model::Diff::new(
+            "/test/src/lib.rs",
+            "// TODO: cover the error path",
+        ))];
+        app.messages.push(user_msg("fix the parser"));
+        app.messages.push(assistant_msg(vec![
+            MessageBlock::ToolCall(Box::new(edit)),
+            MessageBlock::ToolCall(Box::new(test_run)),
+        ]));
+        app.status = AppStatus::Running;
+
+        handle_client_event(&mut app, ClientEvent::TurnComplete);
+        assert_eq!(
+            app.suggestions,
+            vec![
+                "Fix the failing tests from `cargo test`".to_owned(),
+                "Resolve the TODOs added to @src/lib.rs".to_owned(),
+                "Review this turn's edits for bugs and missed edge cases".to_owned(),
+            ]
+        );
+
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::ALT)),
+        );
+        assert_eq!(app.input.text(), "Resolve the TODOs added to @src/lib.rs");
+    }
+
+    #[test]
+    fn turn_complete_appends_diff_summary_grouped_per_file() {
+        fn edit(id: &str, diff: model::Diff) -> MessageBlock {
+            let mut tc = tool_call(id, model::ToolCallStatus::Completed);
+            tc.content = vec![model::ToolCallContent::Diff(diff)];
+            MessageBlock::ToolCall(Box::new(tc))
+        }
+
+        let mut app = make_test_app();
+        let mut rewrite = model::Diff::new("/test/src/lib.rs", "a\nb\nc\n");
+        rewrite.old_text = Some("a\nx\n".to_owned());
+        app.messages.push(user_msg("tidy up"));
+        app.messages.push(assistant_msg(vec![
+            edit("e1", rewrite),
+            edit("e2", model::Diff::new("/test/src/lib.rs", "d\n")),
+            edit("e3", model::Diff::new("/elsewhere/notes.md", "one\ntwo\n")),
+        ]));
+        app.status = AppStatus::Running;
+
+        handle_client_event(&mut app, ClientEvent::TurnComplete);
+
+        let Some(MessageBlock::DiffSummary(summary)) =
+            app.messages.last().and_then(|message| message.blocks.last())
+        else {
+            panic!("expected a diff summary block");
+        };
+        let stats: Vec<_> = summary
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.added, file.removed, file.diffs.len()))
+            .collect();
+        assert_eq!(stats, vec![("src/lib.rs", 3, 1, 2), ("/elsewhere/notes.md", 2, 0, 1)]);
+        assert_eq!(summary.totals(), (5, 1));
+    }
+
     #[test]
     fn turn_complete_marks_lingering_tools_completed() {
         let mut app = make_test_app();
@@ -2600,6 +3088,74 @@ mod tests {
         assert!(app.selection.is_none());
     }
 
+    #[test]
+    fn ctrl_s_keyboard_selection_extends_over_rendered_chat_rows() {
+        let mut app = make_test_app();
+        app.rendered_chat_area = ratatui::layout::Rect::new(0, 0, 20, 3);
+        let press = |app: &mut App, code, modifiers| {
+            handle_terminal_event(app, Event::Key(KeyEvent::new(code, modifiers)));
+        };
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        // The next frame captures the rows the keyboard selection walks over.
+        app.rendered_chat_lines =
+            vec!["first line".to_owned(), "second line".to_owned(), "third".to_owned()];
+        press(&mut app, KeyCode::Char('k'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('$'), KeyModifiers::SHIFT);
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+
+        let selection = app.selection.expect("keyboard selection");
+        assert_eq!(selection.kind, crate::app::SelectionKind::Chat);
+        assert_eq!(selection.start, crate::app::SelectionPoint { row: 1, col: 0 });
+        assert_eq!(selection.end, crate::app::SelectionPoint { row: 2, col: 5 });
+        assert!(app.input.is_empty());
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.selection.is_none());
+        assert!(app.chat_visual.is_none());
+    }
+
+    #[test]
+    fn ctrl_s_then_e_prefills_explain_prompt_for_failed_command() {
+        let mut app = make_test_app();
+        let mut tc = tool_call("bash-1", model::ToolCallStatus::Failed);
+        tc.sdk_tool_name = "Bash".into();
+        tc.terminal_command = Some("cargo test".into());
+        app.messages = vec![assistant_msg(vec![MessageBlock::ToolCall(Box::new(tc))])];
+        app.viewport.sync_message_count(1);
+        app.viewport.set_message_height(0, 3);
+        app.viewport.rebuild_prefix_sums();
+        app.rendered_chat_area = ratatui::layout::Rect::new(0, 0, 20, 3);
+        app.rendered_chat_lines = vec!["Bash".to_owned(), "cargo test".to_owned(), String::new()];
+
+        for (code, modifiers) in
+            [(KeyCode::Char('s'), KeyModifiers::CONTROL), (KeyCode::Char('e'), KeyModifiers::NONE)]
+        {
+            handle_terminal_event(&mut app, Event::Key(KeyEvent::new(code, modifiers)));
+        }
+
+        assert_eq!(app.input.text(), "Why did this command fail?\n\n```sh\ncargo test\n```");
+        assert!(app.chat_visual.is_none());
+    }
+
+    #[test]
+    fn esc_while_idle_dismisses_newest_toast() {
+        let mut app = make_test_app();
+        crate::app::toast::show_toast(&mut app, crate::app::toast::ToastKind::Info, "first");
+        crate::app::toast::show_toast(&mut app, crate::app::toast::ToastKind::Success, "second");
+
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        );
+
+        let remaining: Vec<_> =
+            app.toasts.iter_newest_first().map(|toast| toast.text.as_str()).collect();
+        assert_eq!(remaining, vec!["first"]);
+    }
+
     #[test]
     fn connecting_state_allows_navigation_and_help_shortcuts() {
         let mut app = make_test_app();
@@ -2684,7 +3240,7 @@ mod tests {
     }
 
     #[test]
-    fn ctrl_c_without_selection_quits() {
+    fn ctrl_c_without_selection_quits_on_second_press() {
         let mut app = make_test_app();
         app.selection = None;
 
@@ -2692,7 +3248,14 @@ mod tests {
             &mut app,
             Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
         );
+        assert!(!app.should_quit);
+        assert!(app.ctrl_c_armed_at.is_some());
+        assert!(!app.toasts.is_empty());
 
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        );
         assert!(app.should_quit);
     }
 
@@ -2714,6 +3277,10 @@ mod tests {
         assert!(!app.should_quit);
         assert!(app.selection.is_none());
 
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        );
         handle_terminal_event(
             &mut app,
             Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
@@ -2732,6 +3299,10 @@ mod tests {
             dragging: false,
         });
 
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        );
         handle_terminal_event(
             &mut app,
             Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
@@ -2831,6 +3402,10 @@ mod tests {
         let mut app = make_test_app();
         app.status = AppStatus::Error;
 
+        handle_terminal_event(
+            &mut app,
+            Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+        );
         handle_terminal_event(
             &mut app,
             Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
@@ -2905,6 +3480,41 @@ mod tests {
         assert!(app.viewport.scroll_target > 0);
     }
 
+    #[test]
+    fn clicking_a_minimap_mark_jumps_to_its_message() {
+        let mut app = make_test_app();
+        app.rendered_chat_area = Rect::new(0, 0, 20, 10);
+        for (index, height) in [2, 8, 2, 8].into_iter().enumerate() {
+            app.viewport.set_message_height(index, height);
+        }
+        app.viewport.rebuild_prefix_sums();
+        app.minimap_marks = vec![
+            (ratatui::layout::Position::new(18, 0), 0),
+            (ratatui::layout::Position::new(18, 5), 2),
+        ];
+        let click = |column, row| {
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })
+        };
+
+        handle_terminal_event(&mut app, click(18, 5));
+        assert!(!app.viewport.auto_scroll);
+        assert_eq!(app.viewport.scroll_target, 10);
+        assert_eq!(app.viewport.scroll_offset, 10);
+        assert!(app.selection.is_none());
+
+        // A click between marks does not jump.
+        handle_terminal_event(&mut app, click(18, 6));
+        assert_eq!(app.viewport.scroll_target, 10);
+
+        handle_terminal_event(&mut app, click(18, 0));
+        assert_eq!(app.viewport.scroll_target, 0);
+    }
+
     #[test]
     fn dragging_scrollbar_thumb_can_reach_bottom_and_top() {
         let mut app = make_test_app();
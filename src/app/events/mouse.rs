@@ -5,6 +5,7 @@ use super::super::selection::clear_selection;
 use super::super::state::ScrollbarDragState;
 use super::super::{App, SelectionKind, SelectionPoint};
 use crossterm::event::{MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 
 pub(super) const MOUSE_SCROLL_LINES: usize = 3;
 const SCROLLBAR_MIN_THUMB_HEIGHT: usize = 1;
@@ -16,11 +17,15 @@ struct MouseSelectionPoint {
 
 pub(super) fn handle_mouse_event(app: &mut App, mouse: MouseEvent) {
     match mouse.kind {
+        MouseEventKind::Moved => {
+            super::super::health::update_hover(app, mouse.column, mouse.row);
+        }
         MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
-            if start_scrollbar_drag(app, mouse) {
+            if start_scrollbar_drag(app, mouse) || jump_to_minimap_mark(app, mouse) {
                 return;
             }
             app.scrollbar_drag = None;
+            app.chat_visual = None;
             if let Some(pt) = mouse_point_to_selection(app, mouse) {
                 app.selection = Some(super::super::SelectionState {
                     kind: pt.kind,
@@ -169,6 +174,19 @@ fn set_scroll_from_thumb_top(app: &mut App, thumb_top: usize, metrics: Scrollbar
     app.viewport.scroll_offset = target;
 }
 
+/// Scroll to the message behind a clicked minimap mark.
+fn jump_to_minimap_mark(app: &mut App, mouse: MouseEvent) -> bool {
+    let position = Position::new(mouse.column, mouse.row);
+    let Some(&(_, index)) = app.minimap_marks.iter().find(|(mark, _)| *mark == position) else {
+        return false;
+    };
+    clear_selection(app);
+    app.viewport.auto_scroll = false;
+    app.viewport.restore_scroll_anchor(index, 0);
+    app.needs_redraw = true;
+    true
+}
+
 fn mouse_on_scrollbar_rail(app: &App, mouse: MouseEvent) -> bool {
     let area = app.rendered_chat_area;
     if area.width == 0 || area.height == 0 {
@@ -1,7 +1,7 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{App, SystemSeverity};
+use super::super::{App, InvalidationLevel, MessageRole, MessageUsage, SystemSeverity};
 use crate::agent::model;
 
 fn format_rate_limit_type(raw: &str) -> &str {
@@ -94,6 +94,7 @@ pub(super) fn format_rate_limit_summary(update: &model::RateLimitUpdate) -> Stri
 pub(super) fn handle_rate_limit_update(app: &mut App, update: &model::RateLimitUpdate) {
     let previous_status = app.last_rate_limit_update.as_ref().map(|existing| existing.status);
     app.last_rate_limit_update = Some(update.clone());
+    app.cached_footer_line = None;
 
     match update.status {
         model::RateLimitStatus::Allowed => {}
@@ -111,6 +112,60 @@ pub(super) fn handle_rate_limit_update(app: &mut App, update: &model::RateLimitU
     }
 }
 
+/// Index of the assistant message answering the latest prompt, if any.
+fn current_turn_assistant_index(app: &App) -> Option<usize> {
+    app.messages
+        .iter()
+        .rposition(|message| matches!(message.role, MessageRole::Assistant | MessageRole::User))
+        .filter(|&idx| matches!(app.messages[idx].role, MessageRole::Assistant))
+}
+
+pub(super) fn handle_usage_update(app: &mut App, update: model::UsageUpdate) {
+    let turn_idx = current_turn_assistant_index(app);
+    let mut message_usage = turn_idx.map(|idx| app.messages[idx].usage.clone().unwrap_or_default());
+    let mut turn_cost = None;
+    let usage = &mut app.session_usage;
+    if update.input_tokens.is_some() {
+        let request = MessageUsage {
+            input_tokens: update.input_tokens,
+            cache_read_tokens: update.cache_read_tokens,
+            cache_write_tokens: update.cache_write_tokens,
+            ..MessageUsage::default()
+        };
+        if let Some(message_usage) = message_usage.as_mut() {
+            message_usage.input_tokens = request.input_tokens;
+            message_usage.cache_read_tokens = request.cache_read_tokens;
+            message_usage.cache_write_tokens = request.cache_write_tokens;
+        }
+        usage.last_request = Some(request);
+    }
+    if let Some(output_tokens) = update.output_tokens {
+        usage.total_output_tokens += output_tokens;
+        if let Some(message_usage) = message_usage.as_mut() {
+            message_usage.output_tokens = Some(output_tokens);
+        }
+    }
+    if update.context_window.is_some() {
+        usage.context_window = update.context_window;
+    }
+    if let Some(total_cost) = update.total_cost_usd {
+        // The agent reports the session total; a drop means it restarted its count.
+        let previous = usage.total_cost_usd.unwrap_or(0.0);
+        let cost = if total_cost >= previous { total_cost - previous } else { total_cost };
+        if let Some(message_usage) = message_usage.as_mut() {
+            message_usage.cost_usd = Some(cost);
+        }
+        usage.total_cost_usd = Some(total_cost);
+        turn_cost = Some(cost);
+    }
+    crate::app::usage::history::record(app, &update, turn_cost);
+    if let (Some(idx), Some(message_usage)) = (turn_idx, message_usage) {
+        app.messages[idx].usage = Some(message_usage);
+        app.invalidate_layout(InvalidationLevel::Single(idx));
+    }
+    app.cached_footer_line = None;
+}
+
 pub(super) fn handle_compaction_boundary_update(
     app: &mut App,
     boundary: model::CompactionBoundary,
@@ -2,19 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::connect::take_connection_slot;
-use super::super::connect::{SessionStartReason, start_new_session};
+use super::super::connect::{
+    MAX_RECONNECT_ATTEMPTS, SessionStartReason, reconnect_delay, start_new_session,
+};
 use super::super::state::RecentSessionInfo;
 use super::super::{
-    App, AppStatus, ChatMessage, InvalidationLevel, LoginHint, MessageBlock, MessageRole,
-    SystemSeverity, TextBlock,
+    App, AppStatus, BridgeReconnect, ChatMessage, InvalidationLevel, LoginHint, MessageBlock,
+    MessageRole, SystemSeverity, TextBlock,
 };
 use super::push_system_message_with_severity;
 use super::session_reset::{load_resume_history, reset_for_new_session};
 use crate::agent::client::AgentConnection;
 use crate::agent::events::ServiceStatusSeverity;
 use crate::agent::model;
+use crate::agent::wire::{
+    BRIDGE_PROTOCOL_VERSION, MIN_BRIDGE_PROTOCOL_VERSION, ProtocolCompatibility,
+    protocol_compatibility,
+};
+use crate::app::toast::{ToastKind, show_toast};
 use crate::error::AppError;
 use std::rc::Rc;
+use std::time::Instant;
 
 const TURN_ERROR_INPUT_LOCK_HINT: &str =
     "Input disabled after an error. Press Ctrl+Q to quit and try again.";
@@ -31,8 +39,10 @@ pub(super) fn handle_connected_client_event(
     if let Some(slot) = take_connection_slot() {
         app.conn = Some(slot.conn);
     }
+    let reconnected = app.bridge_reconnect.take();
     apply_session_cwd(app, cwd);
     app.session_id = Some(session_id);
+    crate::app::bookmarks::load_for_session(app);
     app.model_name = model_name;
     app.available_models = available_models;
     app.mode = mode;
@@ -52,11 +62,142 @@ pub(super) fn handle_connected_client_event(
     app.cached_footer_line = None;
     app.update_welcome_model_once();
     app.sync_welcome_recent_sessions();
-    if !history_updates.is_empty() {
+    // After a respawn the transcript in memory is already complete.
+    if reconnected.is_none() && !history_updates.is_empty() {
         load_resume_history(app, history_updates);
     }
     clear_pending_command(app);
     app.resuming_session_id = None;
+    if let Some(reconnect) = reconnected {
+        let message = if reconnect.resume_session_id.is_some() {
+            "Reconnected to the agent. Session restored."
+        } else {
+            "Reconnected to the agent in a new session. Earlier messages are not in its context."
+        };
+        push_system_message_with_severity(app, Some(SystemSeverity::Info), message);
+        show_toast(app, ToastKind::Success, "Reconnected");
+    }
+}
+
+/// The bridge process died mid-session: keep the transcript, fail any
+/// in-flight tool calls, and schedule a respawn that resumes the session.
+pub(super) fn handle_bridge_exited_event(app: &mut App, msg: &str) {
+    tracing::warn!("agent bridge exited mid-session: {msg}");
+    let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
+    super::clear_compaction_state(app, false);
+    app.conn = None;
+    app.health.reset();
+    app.cancelled_turn_pending_hint = false;
+    app.pending_cancel_origin = None;
+    app.pending_auto_submit_after_cancel = false;
+    app.pending_command_label = None;
+    app.pending_command_ack = None;
+    app.resuming_session_id = None;
+    app.status = AppStatus::Connecting;
+    let resume_session_id = app.session_id.take().map(|id| id.to_string());
+    app.bridge_reconnect = Some(BridgeReconnect {
+        attempt: 0,
+        resume_session_id,
+        next_attempt_at: Some(Instant::now()),
+    });
+    push_system_message_with_severity(
+        app,
+        Some(SystemSeverity::Warning),
+        &format!("{msg}. Reconnecting..."),
+    );
+}
+
+/// Schedule the next respawn after a failed reconnect attempt. Returns `false`
+/// once the attempts are exhausted.
+fn retry_bridge_reconnect(app: &mut App, msg: &str) -> bool {
+    let Some(reconnect) = app.bridge_reconnect.as_mut() else {
+        return false;
+    };
+    if reconnect.attempt >= MAX_RECONNECT_ATTEMPTS {
+        reconnect.next_attempt_at = None;
+        return false;
+    }
+    let delay = reconnect_delay(reconnect.attempt + 1);
+    reconnect.next_attempt_at = Some(Instant::now() + delay);
+    let message = format!(
+        "Reconnect attempt {} of {MAX_RECONNECT_ATTEMPTS} failed: {msg}. Retrying in {}s...",
+        reconnect.attempt,
+        delay.as_secs()
+    );
+    push_system_message_with_severity(app, Some(SystemSeverity::Warning), &message);
+    true
+}
+
+/// The respawned bridge could not resume the old session; start a fresh one
+/// on it so the user can keep working. Returns `false` when not reconnecting.
+fn fall_back_to_new_session_after_reconnect(app: &mut App, msg: &str) -> bool {
+    let resuming = app.bridge_reconnect.as_ref().is_some_and(|reconnect| {
+        reconnect.resume_session_id.is_some() && reconnect.next_attempt_at.is_none()
+    });
+    if !resuming || app.session_id.is_some() {
+        return false;
+    }
+    let Some(slot) = take_connection_slot() else {
+        return false;
+    };
+    tracing::warn!("failed to resume session after bridge respawn: {msg}");
+    if let Some(reconnect) = app.bridge_reconnect.as_mut() {
+        reconnect.resume_session_id = None;
+    }
+    app.conn = Some(Rc::clone(&slot.conn));
+    push_system_message_with_severity(
+        app,
+        Some(SystemSeverity::Warning),
+        &format!("Could not restore the previous session ({msg}). Starting a new one..."),
+    );
+    if let Err(err) = start_new_session(app, &slot.conn, SessionStartReason::NewSession) {
+        handle_connection_failed_event(app, &format!("Failed to start a new session: {err}"));
+    }
+    true
+}
+
+/// Record what the agent advertised and warn when its bridge protocol is one
+/// this build cannot fully speak. Features it lacks are gated through
+/// [`App::agent_supports`] rather than failing mid-session.
+pub(super) fn handle_agent_initialized_event(
+    app: &mut App,
+    result: crate::agent::types::InitializeResult,
+) {
+    let caps = &result.capabilities;
+    let missing: Vec<&str> = [
+        (caps.supports_modes, "permission modes"),
+        (caps.supports_plan_updates, "plan updates"),
+        (caps.supports_resume_session, "session resume"),
+        (caps.prompt_image, "image prompts"),
+    ]
+    .into_iter()
+    .filter_map(|(supported, feature)| (!supported).then_some(feature))
+    .collect();
+    let notice = match protocol_compatibility(result.protocol_version) {
+        ProtocolCompatibility::Supported => None,
+        ProtocolCompatibility::TooOld(version) => Some(format!(
+            "The agent bridge speaks protocol v{version}, older than the \
+             v{MIN_BRIDGE_PROTOCOL_VERSION} this build needs. Reinstall claude-code-rust \
+             so both match."
+        )),
+        ProtocolCompatibility::TooNew(version) => Some(format!(
+            "The agent bridge speaks protocol v{version}, newer than the \
+             v{BRIDGE_PROTOCOL_VERSION} this build knows. Update claude-code-rust; \
+             events it does not recognize are ignored."
+        )),
+    };
+    if let Some(notice) = notice {
+        let notice = if missing.is_empty() {
+            notice
+        } else {
+            format!("{notice} Unavailable with this agent: {}.", missing.join(", "))
+        };
+        tracing::warn!(protocol_version = ?result.protocol_version, "agent protocol mismatch");
+        push_system_message_with_severity(app, Some(SystemSeverity::Warning), &notice);
+    } else if !missing.is_empty() {
+        tracing::info!("agent lacks capabilities: {}", missing.join(", "));
+    }
+    app.agent_info = Some(result);
 }
 
 pub(super) fn handle_sessions_listed_event(
@@ -110,17 +251,22 @@ pub(super) fn handle_auth_required_event(
     app.login_hint = Some(LoginHint { method_name, method_description });
     super::clear_compaction_state(app, false);
     app.last_rate_limit_update = None;
+    app.cached_footer_line = None;
     app.cancelled_turn_pending_hint = false;
     app.pending_cancel_origin = None;
     app.pending_auto_submit_after_cancel = false;
 }
 
 pub(super) fn handle_connection_failed_event(app: &mut App, msg: &str) {
+    if retry_bridge_reconnect(app, msg) {
+        return;
+    }
     super::clear_compaction_state(app, false);
     app.cancelled_turn_pending_hint = false;
     app.pending_cancel_origin = None;
     app.pending_auto_submit_after_cancel = false;
     app.last_rate_limit_update = None;
+    app.cached_footer_line = None;
     app.resuming_session_id = None;
     app.pending_command_label = None;
     app.pending_command_ack = None;
@@ -131,6 +277,9 @@ pub(super) fn handle_connection_failed_event(app: &mut App, msg: &str) {
 }
 
 pub(super) fn handle_slash_command_error_event(app: &mut App, msg: &str) {
+    if fall_back_to_new_session_after_reconnect(app, msg) {
+        return;
+    }
     if app.config.pending_session_title_change.take().is_some() {
         app.config.last_error = Some(msg.to_owned());
         app.config.status_message = None;
@@ -141,6 +290,7 @@ pub(super) fn handle_slash_command_error_event(app: &mut App, msg: &str) {
         role: MessageRole::System(None),
         blocks: vec![MessageBlock::Text(TextBlock::from_complete(msg))],
         usage: None,
+        annotation: None,
     });
     app.enforce_history_retention_tracked();
     app.viewport.engage_auto_scroll();
@@ -219,16 +369,128 @@ pub(super) fn handle_session_replaced_event(
     }
     clear_pending_command(app);
     app.resuming_session_id = None;
+    if let Some(note) = app.pending_session_note.take() {
+        push_system_message_with_severity(app, Some(SystemSeverity::Info), &note);
+    }
 }
 
 pub(super) fn handle_update_available_event(
     app: &mut App,
-    latest_version: &str,
+    release: crate::app::ReleaseInfo,
     current_version: &str,
+    channel: crate::app::UpdateChannel,
 ) {
+    let notes_hint = if release.notes.is_empty() { "" } else { "  Ctrl+R notes" };
+    app.status_log.record(
+        Some(SystemSeverity::Info),
+        &format!("Update available ({}): v{}", channel.as_stored(), release.version),
+    );
     app.update_check_hint = Some(format!(
-        "Update available: v{latest_version} (current v{current_version})  Ctrl+U to hide"
+        "Update available ({}): v{} (current v{current_version}){notes_hint}  Ctrl+U to hide",
+        channel.as_stored(),
+        release.version
     ));
+    app.update_release = Some(release);
+}
+
+pub(super) fn handle_self_update_finished_event(
+    app: &mut App,
+    result: Result<String, String>,
+    installed: bool,
+) {
+    clear_pending_command(app);
+    match result {
+        Ok(message) => {
+            if installed {
+                app.update_check_hint = None;
+            }
+            push_system_message_with_severity(app, Some(SystemSeverity::Info), &message);
+        }
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Update failed: {message}"),
+        ),
+    }
+}
+
+pub(super) fn handle_git_commit_finished_event(app: &mut App, result: Result<String, String>) {
+    clear_pending_command(app);
+    app.refresh_git_branch();
+    match result {
+        Ok(summary) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("Committed: {summary}"),
+        ),
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Commit failed: {message}"),
+        ),
+    }
+}
+
+pub(super) fn handle_pull_request_finished_event(app: &mut App, result: Result<String, String>) {
+    clear_pending_command(app);
+    app.refresh_git_branch();
+    match result {
+        // The URL stays on a line of its own so terminals detect it as a link.
+        Ok(url) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("Created pull request:\n{url}"),
+        ),
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Pull request failed: {message}"),
+        ),
+    }
+}
+
+pub(super) fn handle_issue_fetched_event(app: &mut App, result: Result<(String, String), String>) {
+    clear_pending_command(app);
+    match result {
+        Ok((headline, details)) => super::super::issue::insert_into_input(app, &headline, &details),
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Could not fetch the issue: {message}"),
+        ),
+    }
+}
+
+pub(super) fn handle_share_finished_event(app: &mut App, result: Result<String, String>) {
+    clear_pending_command(app);
+    match result {
+        Ok(url) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("Shared session as a secret gist:\n{url}"),
+        ),
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Gist upload failed: {message}"),
+        ),
+    }
+}
+
+pub(super) fn handle_test_run_finished_event(
+    app: &mut App,
+    command: String,
+    result: Result<crate::app::test_run::CommandOutcome, String>,
+) {
+    clear_pending_command(app);
+    match result {
+        Ok(outcome) => super::super::test_run::finished(app, command, &outcome),
+        Err(message) => push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Could not run the tests: {message}"),
+        ),
+    }
 }
 
 pub(super) fn handle_service_status_event(
@@ -244,6 +506,12 @@ pub(super) fn handle_service_status_event(
 }
 
 pub(super) fn handle_fatal_error_event(app: &mut App, error: AppError) {
+    // Bridge failures while respawning are reported through the reconnect
+    // messages; the transcript stays open instead of exiting.
+    if app.bridge_reconnect.is_some() {
+        tracing::warn!("bridge error during reconnect: {error:?}");
+        return;
+    }
     app.exit_error = Some(error);
     app.should_quit = true;
     app.status = AppStatus::Error;
@@ -260,7 +528,10 @@ pub(super) fn clear_pending_command(app: &mut App) {
 }
 
 fn push_connection_error_message(app: &mut App, error: &str) {
-    let message = format!("Connection failed: {error}\n\n{TURN_ERROR_INPUT_LOCK_HINT}");
+    let message = format!(
+        "Connection failed: {error}\n\nPress Ctrl+d to see the agent's stderr. \
+         {TURN_ERROR_INPUT_LOCK_HINT}"
+    );
     push_system_message_with_severity(app, None, &message);
 }
 
@@ -287,13 +558,20 @@ fn sync_welcome_cwd(app: &mut App) {
     welcome.cwd.clone_from(&app.cwd);
     welcome.cache.invalidate();
     app.invalidate_layout(InvalidationLevel::From(0));
+    crate::app::memory::sync_welcome_memory_files(app);
 }
 
 pub(super) fn apply_session_cwd(app: &mut App, cwd_raw: String) {
+    let changed = app.cwd_raw != cwd_raw;
     app.cwd_raw = cwd_raw;
     app.cwd = shorten_cwd_display(&app.cwd_raw);
     app.cached_header_line = None;
     app.cached_footer_line = None;
     app.refresh_git_branch();
     sync_welcome_cwd(app);
+    if changed && app.file_index.is_some() {
+        // Re-index mentions for the new directory right away.
+        crate::app::mention::invalidate_session_cache(app);
+        crate::app::mention::ensure_file_index(app);
+    }
 }
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::{
-    App, BlockCache, ChatMessage, IncrementalMarkdown, MessageBlock, MessageRole, TextBlock,
-    TextBlockSpacing,
+    App, BlockCache, ChatMessage, CodeView, IncrementalMarkdown, MessageBlock, MessageRole,
+    TextBlock, TextBlockSpacing,
 };
 use crate::agent::model;
 
@@ -31,6 +31,7 @@ fn reset_session_identity_state(
     mode: Option<super::super::ModeState>,
 ) {
     app.session_id = Some(session_id);
+    crate::app::bookmarks::load_for_session(app);
     app.model_name = model_name;
     app.mode = mode;
     app.config_options.clear();
@@ -58,6 +59,7 @@ fn reset_messages_for_new_session(app: &mut App) {
         &app.recent_sessions,
     ));
     app.update_welcome_model_once();
+    crate::app::welcome::sync_welcome_banner(app);
     app.viewport = super::super::ChatViewport::new();
 }
 
@@ -65,6 +67,7 @@ fn reset_input_state_for_new_session(app: &mut App) {
     app.input.clear();
     app.pending_submit = None;
     app.pending_paste_text.clear();
+    app.pending_rewind_note = None;
     app.pending_paste_session = None;
     app.active_paste_session = None;
 }
@@ -73,12 +76,23 @@ fn reset_interaction_state_for_new_session(app: &mut App) {
     app.pending_permission_ids.clear();
     app.clear_tool_scope_tracking();
     app.tool_call_index.clear();
+    app.pinned_tool_call = None;
+    app.transcript_filter = None;
+    app.filter_editor = None;
+    app.folded_turns.clear();
     app.todos.clear();
     app.show_todo_panel = false;
     app.todo_scroll = 0;
     app.todo_selected = 0;
+    app.suggestions.clear();
+    app.changed_files.clear();
+    app.last_prompt = None;
+    app.turn_retry = None;
+    app.tool_timings.clear();
+    app.watch.clear();
     app.focus = super::super::FocusManager::default();
     app.available_commands.clear();
+    super::super::slash::merge_custom_commands(app);
     app.available_agents.clear();
     app.config.overlay = None;
 }
@@ -93,6 +107,7 @@ fn reset_render_state_for_new_session(app: &mut App) {
     app.mention = None;
     app.slash = None;
     app.subagent = None;
+    app.emoji = None;
 }
 
 fn reset_cache_and_footer_state_for_new_session(app: &mut App) {
@@ -128,6 +143,8 @@ fn append_resume_user_message_chunk(app: &mut App, chunk: &model::ContentChunk)
                 cache: BlockCache::default(),
                 markdown: incr,
                 trailing_spacing: TextBlockSpacing::default(),
+                revision: None,
+                code_view: CodeView::default(),
             }));
         }
         return;
@@ -142,8 +159,11 @@ fn append_resume_user_message_chunk(app: &mut App, chunk: &model::ContentChunk)
             cache: BlockCache::default(),
             markdown: incr,
             trailing_spacing: TextBlockSpacing::default(),
+            revision: None,
+            code_view: CodeView::default(),
         })],
         usage: None,
+        annotation: None,
     });
 }
 
@@ -166,6 +186,7 @@ pub(super) fn load_resume_history(app: &mut App, history_updates: &[model::Sessi
         }
     }
     let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
+    super::super::revisions::mark_all(app);
     app.enforce_history_retention_tracked();
     app.viewport = super::super::ChatViewport::new();
     app.viewport.engage_auto_scroll();
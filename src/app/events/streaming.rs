@@ -3,7 +3,7 @@
 
 use super::super::{
     App, AppStatus, ChatMessage, MessageBlock, MessageRole, TextBlock, TextBlockSpacing,
-    TextSplitDecision, TextSplitKind, default_cache_split_policy, find_text_split,
+    TextSplitDecision, TextSplitKind, ThinkingBlock, default_cache_split_policy, find_text_split,
 };
 use crate::agent::model;
 
@@ -25,7 +25,43 @@ pub(super) fn handle_agent_message_chunk(app: &mut App, chunk: model::ContentChu
 
     let mut blocks = Vec::new();
     append_agent_stream_text(&mut blocks, &text.text);
-    app.messages.push(ChatMessage { role: MessageRole::Assistant, blocks, usage: None });
+    app.messages.push(ChatMessage {
+        role: MessageRole::Assistant,
+        blocks,
+        usage: None,
+        annotation: None,
+    });
+}
+
+pub(super) fn handle_agent_thought_chunk(app: &mut App, chunk: model::ContentChunk) {
+    app.status = AppStatus::Thinking;
+    let model::ContentBlock::Text(text) = chunk.content else {
+        return;
+    };
+    if text.text.is_empty() {
+        return;
+    }
+    if !app.messages.last().is_some_and(|last| matches!(last.role, MessageRole::Assistant)) {
+        app.messages.push(ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: Vec::new(),
+            usage: None,
+            annotation: None,
+        });
+    }
+    let expanded = !app.tools_collapsed;
+    let Some(last) = app.messages.last_mut() else {
+        return;
+    };
+    if let Some(MessageBlock::Thinking(block)) = last.blocks.last_mut() {
+        block.text.push_str(&text.text);
+        block.cache.invalidate();
+    } else {
+        last.blocks.push(MessageBlock::Thinking(ThinkingBlock {
+            expanded,
+            ..ThinkingBlock::new(text.text)
+        }));
+    }
 }
 
 pub(super) fn append_agent_stream_text(blocks: &mut Vec<MessageBlock>, chunk: &str) {
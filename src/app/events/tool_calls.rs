@@ -7,7 +7,10 @@ use super::super::{
 };
 use super::tool_updates::raw_output_to_terminal_text;
 use crate::agent::model;
+use crate::app::mention;
 use crate::app::todos::{parse_todos_if_present, set_todos};
+use crate::app::web_tools;
+use std::path::Path;
 use std::time::Instant;
 
 pub(super) fn handle_tool_call(app: &mut App, tc: model::ToolCall) {
@@ -15,19 +18,44 @@ pub(super) fn handle_tool_call(app: &mut App, tc: model::ToolCall) {
     let id_str = tc.tool_call_id.clone();
     let sdk_tool_name = resolve_sdk_tool_name(tc.kind, tc.meta.as_ref());
     let scope = register_tool_call_scope(app, &id_str, &sdk_tool_name);
+    let terminal_status =
+        matches!(tc.status, model::ToolCallStatus::Completed | model::ToolCallStatus::Failed);
+    crate::app::tool_timings::started(app, &id_str, Instant::now());
     maybe_apply_todo_write_from_tool_call(app, &id_str, &sdk_tool_name, tc.raw_input.as_ref());
     update_subagent_scope_state(app, scope, tc.status, &id_str);
+    record_touched_files(app, &tc.locations, tc.raw_input.as_ref());
 
     let tool_info = build_tool_info_from_tool_call(app, tc, sdk_tool_name);
     if should_jump_on_large_write(&tool_info) {
         app.viewport.engage_auto_scroll();
     }
     upsert_tool_call_into_assistant_message(app, tool_info);
+    crate::app::changed_files::track_tool_call(app, &id_str, true);
+    if terminal_status {
+        crate::app::tool_timings::finished(app, &id_str, Instant::now());
+    }
 
     app.status = AppStatus::Running;
     app.files_accessed += 1;
 }
 
+/// Feed file paths a tool call reads or edits into mention recency ranking.
+pub(super) fn record_touched_files(
+    app: &mut App,
+    locations: &[model::ToolCallLocation],
+    raw_input: Option<&serde_json::Value>,
+) {
+    for location in locations {
+        mention::record_touched_path(app, &location.path);
+    }
+    let input_path = raw_input.and_then(|input| {
+        ["file_path", "notebook_path"].iter().find_map(|key| input.get(key)?.as_str())
+    });
+    if let Some(path) = input_path {
+        mention::record_touched_path(app, Path::new(path));
+    }
+}
+
 fn log_tool_call_received(tc: &model::ToolCall) {
     let id_str = tc.tool_call_id.clone();
     let title = tc.title.clone();
@@ -111,11 +139,20 @@ fn build_tool_info_from_tool_call(
     tc: model::ToolCall,
     sdk_tool_name: String,
 ) -> ToolCallInfo {
-    let initial_execute_output = if super::super::is_execute_tool_name(&sdk_tool_name) {
+    let is_execute = super::super::is_execute_tool_name(&sdk_tool_name);
+    let initial_execute_output = if is_execute {
         tc.raw_output.as_ref().and_then(raw_output_to_terminal_text)
     } else {
         None
     };
+    let terminal_env = if is_execute {
+        app.session_env.iter().map(|(name, value)| (name.clone(), value.clone())).collect()
+    } else {
+        Vec::new()
+    };
+    let web_output = tc.raw_output.as_ref().and_then(|raw_output| {
+        web_tools::parse_web_tool_output(&sdk_tool_name, tc.raw_input.as_ref(), raw_output)
+    });
 
     let mut tool_info = ToolCallInfo {
         id: tc.tool_call_id,
@@ -123,12 +160,14 @@ fn build_tool_info_from_tool_call(
         sdk_tool_name,
         raw_input: tc.raw_input,
         output_metadata: tc.output_metadata,
+        web_output,
         status: tc.status,
         content: tc.content,
         collapsed: app.tools_collapsed,
         hidden: false,
         terminal_id: None,
         terminal_command: None,
+        terminal_env,
         terminal_output: None,
         terminal_output_len: 0,
         terminal_bytes_seen: 0,
@@ -142,6 +181,9 @@ fn build_tool_info_from_tool_call(
         cache: BlockCache::default(),
         pending_permission: None,
         pending_question: None,
+        h_scroll: None,
+        auto_retry: None,
+        watch_hits: Vec::new(),
     };
     if let Some(output) = initial_execute_output {
         tool_info.terminal_output_len = output.len();
@@ -174,6 +216,7 @@ pub(super) fn upsert_tool_call_into_assistant_message(app: &mut App, tool_info:
             role: MessageRole::Assistant,
             blocks: vec![MessageBlock::ToolCall(Box::new(tool_info))],
             usage: None,
+            annotation: None,
         });
         app.index_tool_call(tc_id, new_idx, 0);
     }
@@ -192,6 +235,9 @@ fn update_existing_tool_call(app: &mut App, mi: usize, bi: usize, tool_info: &To
         changed |= sync_if_changed(&mut existing.sdk_tool_name, &tool_info.sdk_tool_name);
         changed |= sync_if_changed(&mut existing.raw_input, &tool_info.raw_input);
         changed |= sync_if_changed(&mut existing.output_metadata, &tool_info.output_metadata);
+        if tool_info.web_output.is_some() {
+            changed |= sync_if_changed(&mut existing.web_output, &tool_info.web_output);
+        }
         if changed {
             existing.mark_tool_call_layout_dirty();
             layout_dirty = true;
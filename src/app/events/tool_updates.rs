@@ -3,11 +3,13 @@
 
 use super::super::{App, AppStatus, InvalidationLevel, MessageBlock, ToolCallInfo, ToolCallScope};
 use super::tool_calls::{
-    has_in_progress_tool_calls, sdk_tool_name_from_meta, should_jump_on_large_write,
+    has_in_progress_tool_calls, record_touched_files, sdk_tool_name_from_meta,
+    should_jump_on_large_write,
 };
 use crate::agent::error_handling::{looks_like_internal_error, summarize_internal_error};
 use crate::agent::model;
 use crate::app::todos::{parse_todos_if_present, set_todos};
+use crate::app::web_tools;
 use std::time::Instant;
 
 pub(super) fn handle_tool_call_update_session(app: &mut App, tcu: &model::ToolCallUpdate) {
@@ -16,8 +18,25 @@ pub(super) fn handle_tool_call_update_session(app: &mut App, tcu: &model::ToolCa
     log_tool_call_update_received(&id_str, tcu);
     maybe_log_internal_failed_tool_update(&id_str, tcu);
     apply_tool_scope_status_update(app, &id_str, tool_scope, tcu.fields.status);
+    record_touched_files(
+        app,
+        tcu.fields.locations.as_deref().unwrap_or_default(),
+        tcu.fields.raw_input.as_ref(),
+    );
 
     let update_outcome = apply_tool_call_update_to_indexed_block(app, &id_str, tcu);
+    crate::app::watch_patterns::scan_tool_call(app, &id_str);
+    crate::app::changed_files::track_tool_call(app, &id_str, false);
+    if matches!(
+        tcu.fields.status,
+        Some(model::ToolCallStatus::Completed | model::ToolCallStatus::Failed)
+    ) {
+        crate::app::tool_timings::finished(app, &id_str, Instant::now());
+    }
+    if tcu.fields.status == Some(model::ToolCallStatus::Completed) {
+        crate::app::lifecycle_hooks::tool_call_completed(app, &id_str);
+        crate::app::edit_check::tool_call_completed(app, &id_str);
+    }
     if let Some(mi) = update_outcome.layout_dirty_idx {
         app.invalidate_layout(InvalidationLevel::Single(mi));
     }
@@ -127,6 +146,7 @@ fn apply_tool_call_update_to_indexed_block(
         changed |= apply_tool_call_raw_input_update(tc, tcu.fields.raw_input.as_ref());
         changed |= apply_tool_call_output_metadata_update(tc, tcu.fields.output_metadata.as_ref());
         changed |= apply_tool_call_raw_output_update(tc, tcu.fields.raw_output.as_ref());
+        changed |= apply_tool_call_web_output_update(tc, tcu.fields.raw_output.as_ref());
         changed |= apply_tool_call_name_update(tc, tcu.meta.as_ref());
         out.pending_todos =
             extract_todo_updates_from_tool_call_update(id_str, tc, tcu.fields.raw_input.as_ref());
@@ -257,6 +277,25 @@ fn apply_tool_call_raw_output_update(
     true
 }
 
+fn apply_tool_call_web_output_update(
+    tc: &mut ToolCallInfo,
+    raw_output: Option<&serde_json::Value>,
+) -> bool {
+    if !web_tools::is_web_tool_name(&tc.sdk_tool_name) {
+        return false;
+    }
+    let Some(parsed) = raw_output.and_then(|raw_output| {
+        web_tools::parse_web_tool_output(&tc.sdk_tool_name, tc.raw_input.as_ref(), raw_output)
+    }) else {
+        return false;
+    };
+    if tc.web_output.as_ref() == Some(&parsed) {
+        return false;
+    }
+    tc.web_output = Some(parsed);
+    true
+}
+
 fn apply_tool_call_name_update(tc: &mut ToolCallInfo, meta: Option<&serde_json::Value>) -> bool {
     let Some(name) = sdk_tool_name_from_meta(meta) else {
         return false;
@@ -376,12 +415,14 @@ mod tests {
             sdk_tool_name: "Bash".to_owned(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status,
             content: Vec::new(),
             collapsed: false,
             hidden: false,
             terminal_id: terminal_id.map(str::to_owned),
             terminal_command: Some("echo test".to_owned()),
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -395,6 +436,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         }
     }
 
@@ -410,6 +454,7 @@ mod tests {
                 Some("term-1"),
             )))],
             usage: None,
+            annotation: None,
         });
         app.index_tool_call(tool_id.to_owned(), 0, 0);
         app.terminal_tool_calls.push(("term-1".to_owned(), 0, 0));
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::{
-    App, AppStatus, CancelOrigin, ChatMessage, FocusTarget, InlinePermission, InlineQuestion,
-    InvalidationLevel, MessageBlock, MessageRole, SystemSeverity, TextBlock,
+    App, AppStatus, CancelOrigin, FocusTarget, InlinePermission, InlineQuestion, InvalidationLevel,
+    MessageBlock, MessageRole, RecoveryHintBlock, SystemSeverity,
 };
 use super::clear_compaction_state;
 use super::rate_limit::format_rate_limit_summary;
-use crate::agent::error_handling::{TurnErrorClass, classify_turn_error, summarize_internal_error};
+use crate::agent::error_handling::{
+    TurnErrorClass, classify_turn_error, parse_transient_error, summarize_internal_error,
+};
 use crate::agent::model;
 use std::collections::BTreeSet;
 
@@ -19,7 +21,7 @@ const PLAN_LIMIT_NEXT_STEPS_HINT: &str = "Next steps:\n\
 1. Wait a few minutes and retry.\n\
 2. Reduce request size or request frequency.\n\
 3. Check quota/billing for your account or switch plans.";
-const AUTH_REQUIRED_NEXT_STEPS_HINT: &str = "Authentication required. Type /login to authenticate, or run `claude auth login` in a terminal.";
+const AUTH_REQUIRED_NEXT_STEPS_HINT: &str = "Authentication required. Type /login to authenticate.";
 
 pub(super) fn handle_permission_request_event(
     app: &mut App,
@@ -43,6 +45,21 @@ pub(super) fn handle_permission_request_event(
         return;
     }
 
+    if app.read_only
+        && let Some(MessageBlock::ToolCall(tc)) =
+            app.messages.get(mi).and_then(|m| m.blocks.get(bi))
+        && tc.is_mutating_tool()
+    {
+        let note = format!(
+            "Read-only mode: denied {}. Restart without --read-only to allow edits and commands.",
+            tc.title
+        );
+        tracing::info!("Read-only mode: auto-denying permission for tool call {tool_id}");
+        deny_permission_request(response_tx, &options);
+        super::push_system_message_with_severity(app, Some(SystemSeverity::Warning), &note);
+        return;
+    }
+
     let mut layout_dirty = false;
     if let Some(MessageBlock::ToolCall(tc)) =
         app.messages.get_mut(mi).and_then(|m| m.blocks.get_mut(bi))
@@ -148,6 +165,24 @@ fn reject_permission_request(
     }
 }
 
+/// Answer with the option that rejects this one call, falling back to the
+/// last option like [`reject_permission_request`].
+fn deny_permission_request(
+    response_tx: tokio::sync::oneshot::Sender<model::RequestPermissionResponse>,
+    options: &[model::PermissionOption],
+) {
+    let reject_once = options.iter().position(|option| {
+        matches!(
+            option.kind,
+            model::PermissionOptionKind::RejectOnce | model::PermissionOptionKind::PlanReject
+        )
+    });
+    match reject_once {
+        Some(index) => reject_permission_request(response_tx, &options[..=index]),
+        None => reject_permission_request(response_tx, options),
+    }
+}
+
 pub(super) fn handle_turn_cancelled_event(app: &mut App) {
     clear_compaction_state(app, false);
     if app.pending_cancel_origin.is_none() {
@@ -167,6 +202,9 @@ pub(super) fn handle_turn_complete_event(app: &mut App) {
     let show_interrupted_hint = matches!(app.pending_cancel_origin, Some(CancelOrigin::Manual));
     app.pending_cancel_origin = None;
     app.cancelled_turn_pending_hint = false;
+    if turn_was_active {
+        app.turn_retry = None;
+    }
 
     if cancelled_requested {
         let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
@@ -185,6 +223,25 @@ pub(super) fn handle_turn_complete_event(app: &mut App) {
         mark_turn_exit_assistant_layout_dirty(app, tail_assistant_idx);
     }
     if turn_was_active {
+        super::super::diff_stats::append_turn_diff_summary(app);
+    }
+    // A flaky tool retry carries the turn on; it ends with the retried turn.
+    let retrying_tool = turn_was_active
+        && !cancelled_requested
+        && super::super::tool_retry::retry_transient_failure(app);
+    if retrying_tool {
+        return;
+    }
+    if turn_was_active && !cancelled_requested {
+        super::super::revisions::mark_last_turn(app);
+        super::super::suggestions::refresh_suggestions(app);
+        super::super::screen_reader::mirror_last_reply(app);
+        super::super::commit::open_pending_draft(app);
+    } else if cancelled_requested {
+        app.pending_draft = None;
+    }
+    if turn_was_active {
+        super::super::lifecycle_hooks::turn_finished(app, cancelled_requested);
         app.notifications.notify(
             app.config.preferred_notification_channel_effective(),
             super::super::notify::NotifyEvent::TurnComplete,
@@ -218,6 +275,7 @@ pub(super) fn handle_turn_error_event(
         mark_turn_exit_assistant_layout_dirty(app, tail_assistant_idx);
         let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
         app.pending_submit = None;
+        app.pending_draft = None;
         app.status = AppStatus::Ready;
         app.files_accessed = 0;
         app.clear_tool_scope_tracking();
@@ -231,7 +289,24 @@ pub(super) fn handle_turn_error_event(
         return;
     }
 
-    let error_class = classified.unwrap_or_else(|| classify_turn_error(msg));
+    if let Some(transient) = parse_transient_error(msg)
+        && super::super::turn_retry::schedule_retry(app, transient)
+    {
+        tracing::warn!(error_preview = %summarize_internal_error(msg), "Transient turn error");
+        let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
+        app.pending_submit = None;
+        app.status = AppStatus::Ready;
+        if turn_was_active {
+            mark_turn_exit_assistant_layout_dirty(app, tail_assistant_idx);
+        }
+        return;
+    }
+    app.turn_retry = None;
+
+    // The bridge only tells limits and auth apart; refine its catch-all here.
+    let error_class = classified
+        .filter(|class| *class != TurnErrorClass::Other)
+        .unwrap_or_else(|| classify_turn_error(msg));
     tracing::error!("Turn error: {msg}");
     let summary = summarize_internal_error(msg);
     match error_class {
@@ -249,13 +324,14 @@ pub(super) fn handle_turn_error_event(
             app.exit_error = Some(crate::error::AppError::AuthRequired);
             app.should_quit = true;
         }
-        TurnErrorClass::Internal => {
+        TurnErrorClass::Internal | TurnErrorClass::ProtocolMismatch | TurnErrorClass::Crash => {
             tracing::debug!(
                 error_preview = %summary,
+                class = ?error_class,
                 "Internal Agent SDK turn error payload"
             );
         }
-        TurnErrorClass::Other => {}
+        TurnErrorClass::Network | TurnErrorClass::ToolFailure | TurnErrorClass::Other => {}
     }
     let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
     app.pending_auto_submit_after_cancel = false;
@@ -270,19 +346,19 @@ pub(super) fn handle_turn_error_event(
         None
     };
     push_turn_error_message(app, msg, error_class, rate_limit_context.as_ref());
+    super::super::lifecycle_hooks::turn_failed(app, &summary);
+    app.pending_draft = None;
     if turn_was_active {
         mark_turn_exit_assistant_layout_dirty(app, tail_assistant_idx);
     }
 }
 
 fn push_interrupted_hint(app: &mut App) {
-    app.messages.push(ChatMessage {
-        role: MessageRole::System(Some(SystemSeverity::Info)),
-        blocks: vec![MessageBlock::Text(TextBlock::from_complete(CONVERSATION_INTERRUPTED_HINT))],
-        usage: None,
-    });
-    app.enforce_history_retention_tracked();
-    app.viewport.engage_auto_scroll();
+    super::push_system_message_with_severity(
+        app,
+        Some(SystemSeverity::Info),
+        CONVERSATION_INTERRUPTED_HINT,
+    );
 }
 
 fn mark_turn_exit_assistant_layout_dirty(app: &mut App, idx: Option<usize>) {
@@ -310,9 +386,7 @@ fn push_turn_error_message(
         TurnErrorClass::AuthRequired => {
             format!("{AUTH_REQUIRED_NEXT_STEPS_HINT}\n\n{TURN_ERROR_INPUT_LOCK_HINT}")
         }
-        TurnErrorClass::Internal | TurnErrorClass::Other => {
-            format!("Turn failed: {error}\n\n{TURN_ERROR_INPUT_LOCK_HINT}")
-        }
+        _ => format!("Turn failed: {error}\n\n{TURN_ERROR_INPUT_LOCK_HINT}"),
     };
     let (severity, message) = if matches!(class, TurnErrorClass::PlanLimit)
         && let Some(update) = rate_limit_context
@@ -326,5 +400,10 @@ fn push_turn_error_message(
     } else {
         (None, base_message)
     };
-    super::push_system_message_with_severity(app, severity, &message);
+    super::push_system_message_with_blocks(
+        app,
+        severity,
+        &message,
+        vec![MessageBlock::RecoveryHint(RecoveryHintBlock::new(class))],
+    );
 }
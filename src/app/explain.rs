@@ -0,0 +1,123 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! "Explain this" follow-ups. Pressing `e` in keyboard selection mode (Ctrl+s)
+//! pre-fills the input with a question about the message under the cursor:
+//! the failed command, the edited file or the first code block it holds.
+
+use super::{App, MessageBlock, is_execute_tool_name};
+use crate::agent::model;
+use std::path::Path;
+
+/// Longest code excerpt quoted into the prompt, in lines.
+const MAX_QUOTED_LINES: usize = 40;
+
+/// Fill the input with an explain prompt for the message under the selection
+/// cursor. Returns false when that message has nothing to ask about.
+pub(crate) fn explain_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    let Some(prompt) = explain_prompt(app, message_index) else {
+        return false;
+    };
+    app.input.set_text(&prompt);
+    true
+}
+
+fn explain_prompt(app: &App, message_index: usize) -> Option<String> {
+    let message = app.messages.get(message_index)?;
+    let tool_calls: Vec<_> = message
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            MessageBlock::ToolCall(tc) if !tc.hidden => Some(tc.as_ref()),
+            _ => None,
+        })
+        .collect();
+
+    // A failure is the likeliest thing to ask about, then the latest step.
+    let failed = tool_calls.iter().rev().find(|tc| tc.status == model::ToolCallStatus::Failed);
+    if let Some(tc) = failed.or_else(|| tool_calls.last()) {
+        if is_execute_tool_name(&tc.sdk_tool_name)
+            && let Some(command) = tc.terminal_command.as_deref()
+        {
+            let question = if tc.status == model::ToolCallStatus::Failed {
+                "Why did this command fail?"
+            } else {
+                "Explain what this command did:"
+            };
+            return Some(format!("{question}\n\n```sh\n{command}\n```"));
+        }
+        let diff_path = tc.content.iter().find_map(|content| match content {
+            model::ToolCallContent::Diff(diff) => Some(diff.path.as_path()),
+            _ => None,
+        });
+        if let Some(path) = diff_path {
+            return Some(format!("Explain the change in @{}", mention_path(app, path)));
+        }
+        return Some(format!("Explain what the \"{}\" step did.", tc.title));
+    }
+
+    message.blocks.iter().find_map(|block| match block {
+        MessageBlock::Text(text) => {
+            first_code_block(&text.text).map(|code| format!("Explain this code:\n\n{code}"))
+        }
+        _ => None,
+    })
+}
+
+/// The first fenced code block in `markdown`, fences included and trimmed to
+/// [`MAX_QUOTED_LINES`] lines of body.
+fn first_code_block(markdown: &str) -> Option<String> {
+    let mut lines = markdown.lines();
+    let open = lines.find(|line| line.trim_start().starts_with("```"))?;
+    let fence = open.trim();
+    let mut body: Vec<&str> = Vec::new();
+    let mut truncated = false;
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            break;
+        }
+        if body.len() == MAX_QUOTED_LINES {
+            truncated = true;
+            continue;
+        }
+        body.push(line);
+    }
+    if truncated {
+        body.push("...");
+    }
+    Some(format!("{fence}\n{}\n```", body.join("\n")))
+}
+
+/// `@`-mention form of `path`: relative to the session cwd when inside it.
+pub(super) fn mention_path(app: &App, path: &Path) -> String {
+    let rel = path.strip_prefix(&app.cwd_raw).unwrap_or(path);
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn first_code_block_keeps_language_and_caps_length() {
+        assert_eq!(first_code_block("no code here"), None);
+        assert_eq!(
+            first_code_block("Try:\n```rust\nfn main() {}\n```\nthen\n```\nother\n```").as_deref(),
+            Some("```rust\nfn main() {}\n```")
+        );
+
+        let long = format!("```\n{}```", "line\n".repeat(MAX_QUOTED_LINES + 5));
+        let quoted = first_code_block(&long).expect("code block");
+        assert_eq!(quoted.lines().count(), MAX_QUOTED_LINES + 3);
+        assert!(quoted.ends_with("...\n```"));
+    }
+}
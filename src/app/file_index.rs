@@ -0,0 +1,541 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Background, gitignore-aware file index backing `@` mention autocomplete.
+//!
+//! A worker thread walks the project once, then watches it with `notify` and
+//! streams incremental upserts/removals back to the UI thread. The index lives
+//! on `App` so it survives across mention sessions: queries filter the cached
+//! list instead of walking the filesystem again.
+
+use super::mention::FileCandidate;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::Watcher as _;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::mpsc as std_mpsc;
+use std::time::{Duration, SystemTime};
+
+/// Maximum index updates applied per UI tick.
+const DRAIN_BUDGET: usize = 2_000;
+/// How often the watcher loop checks for cancellation while idle.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+enum IndexUpdate {
+    Upsert(FileCandidate),
+    /// Relative path (no trailing `/`) whose entry and descendants are gone.
+    Remove(String),
+    ScanComplete,
+}
+
+pub struct FileIndex {
+    root: PathBuf,
+    respect_gitignore: bool,
+    /// Prepended to every `rel_path`, so entries of an extra root read as
+    /// paths from the session directory (e.g. `../api/`). Empty for the cwd.
+    prefix: String,
+    update_rx: std_mpsc::Receiver<IndexUpdate>,
+    cancel: Arc<AtomicBool>,
+    /// Indexed entries keyed by `rel_path` (directories keep their trailing `/`).
+    entries: BTreeMap<String, FileCandidate>,
+    scan_complete: bool,
+}
+
+impl FileIndex {
+    /// Start indexing `root` in the background.
+    #[must_use]
+    pub fn spawn(root: PathBuf, respect_gitignore: bool) -> Self {
+        Self::spawn_prefixed(root, respect_gitignore, String::new())
+    }
+
+    /// Start indexing `root` in the background, keying entries as
+    /// `{prefix}{path relative to root}`.
+    #[must_use]
+    pub fn spawn_prefixed(root: PathBuf, respect_gitignore: bool, prefix: String) -> Self {
+        let (update_tx, update_rx) = std_mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let worker_root = root.clone();
+        let worker_cancel = Arc::clone(&cancel);
+
+        std::thread::spawn(move || {
+            run_worker(&worker_root, respect_gitignore, &worker_cancel, &update_tx);
+        });
+
+        Self {
+            root,
+            respect_gitignore,
+            prefix,
+            update_rx,
+            cancel,
+            entries: BTreeMap::new(),
+            scan_complete: false,
+        }
+    }
+
+    /// Whether this index was built for the given root and gitignore setting.
+    #[must_use]
+    pub fn matches(&self, root: &Path, respect_gitignore: bool) -> bool {
+        self.root == root && self.respect_gitignore == respect_gitignore
+    }
+
+    /// `true` until the initial walk has finished.
+    #[must_use]
+    pub fn is_indexing(&self) -> bool {
+        !self.scan_complete
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &FileCandidate> {
+        self.entries.values()
+    }
+
+    /// Number of files (recursively) under the directory `dir_rel` (trailing `/`).
+    #[must_use]
+    pub fn file_count_under(&self, dir_rel: &str) -> usize {
+        self.entries_under(dir_rel).filter(|candidate| !candidate.is_dir).count()
+    }
+
+    /// Files directly inside the directory `dir_rel` (trailing `/`), sorted by path.
+    #[must_use]
+    pub fn top_level_files(&self, dir_rel: &str) -> Vec<String> {
+        self.entries_under(dir_rel)
+            .filter(|candidate| {
+                !candidate.is_dir && !candidate.rel_path[dir_rel.len()..].contains('/')
+            })
+            .map(|candidate| candidate.rel_path.clone())
+            .collect()
+    }
+
+    /// Entries directly inside the directory `dir_rel` (trailing `/`, or empty
+    /// for the root): directories first, each group sorted by path.
+    #[must_use]
+    pub fn children(&self, dir_rel: &str) -> Vec<&FileCandidate> {
+        let mut children: Vec<_> = self
+            .entries_under(dir_rel)
+            .filter(|candidate| {
+                !candidate.rel_path[dir_rel.len()..].trim_end_matches('/').contains('/')
+            })
+            .collect();
+        children.sort_by_key(|candidate| !candidate.is_dir);
+        children
+    }
+
+    fn entries_under<'a>(&'a self, dir_rel: &str) -> impl Iterator<Item = &'a FileCandidate> {
+        self.entries
+            .range::<str, _>((std::ops::Bound::Excluded(dir_rel), std::ops::Bound::Unbounded))
+            .take_while(move |(key, _)| key.starts_with(dir_rel))
+            .map(|(_, candidate)| candidate)
+    }
+
+    /// Apply pending updates from the worker (non-blocking).
+    /// Returns `true` if the indexed entries or indexing state changed.
+    pub fn drain(&mut self) -> bool {
+        let mut changed = false;
+        for _ in 0..DRAIN_BUDGET {
+            match self.update_rx.try_recv() {
+                Ok(IndexUpdate::Upsert(mut candidate)) => {
+                    if !self.prefix.is_empty() {
+                        candidate.rel_path.insert_str(0, &self.prefix);
+                    }
+                    self.entries.insert(candidate.rel_path.clone(), candidate);
+                    changed = true;
+                }
+                Ok(IndexUpdate::Remove(mut rel_path)) => {
+                    rel_path.insert_str(0, &self.prefix);
+                    changed |= self.remove_subtree(&rel_path);
+                }
+                Ok(IndexUpdate::ScanComplete) => {
+                    self.scan_complete = true;
+                    changed = true;
+                }
+                Err(std_mpsc::TryRecvError::Empty) => break,
+                Err(std_mpsc::TryRecvError::Disconnected) => {
+                    changed |= !self.scan_complete;
+                    self.scan_complete = true;
+                    break;
+                }
+            }
+        }
+        changed
+    }
+
+    fn remove_subtree(&mut self, rel_path: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.remove(rel_path);
+        let dir_prefix = format!("{rel_path}/");
+        let doomed: Vec<String> = self
+            .entries
+            .range(dir_prefix.clone()..)
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(&dir_prefix))
+            .cloned()
+            .collect();
+        for key in doomed {
+            self.entries.remove(&key);
+        }
+        self.entries.len() != before
+    }
+}
+
+impl Drop for FileIndex {
+    fn drop(&mut self) {
+        self.cancel.store(true, AtomicOrdering::Relaxed);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Worker thread
+// ---------------------------------------------------------------------------
+
+fn run_worker(
+    root: &Path,
+    respect_gitignore: bool,
+    cancel: &AtomicBool,
+    update_tx: &std_mpsc::Sender<IndexUpdate>,
+) {
+    // Start watching before the walk so changes made during the scan are not lost.
+    let (fs_tx, fs_rx) = std_mpsc::channel::<PathBuf>();
+    let watcher = start_watcher(root, respect_gitignore, fs_tx);
+
+    if !scan(root, root, None, respect_gitignore, cancel, update_tx) {
+        return;
+    }
+    if update_tx.send(IndexUpdate::ScanComplete).is_err() {
+        return;
+    }
+    let Some(_watcher) = watcher else {
+        return;
+    };
+
+    while !cancel.load(AtomicOrdering::Relaxed) {
+        let first = match fs_rx.recv_timeout(WATCH_POLL_INTERVAL) {
+            Ok(path) => path,
+            Err(std_mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(std_mpsc::RecvTimeoutError::Disconnected) => return,
+        };
+        // Coalesce bursts (checkouts, builds) into one pass over unique paths.
+        let mut batch = BTreeSet::from([first]);
+        batch.extend(fs_rx.try_iter());
+        for path in batch {
+            if !apply_fs_change(root, &path, respect_gitignore, cancel, update_tx) {
+                return;
+            }
+        }
+    }
+}
+
+fn start_watcher(
+    root: &Path,
+    respect_gitignore: bool,
+    fs_tx: std_mpsc::Sender<PathBuf>,
+) -> Option<notify::RecommendedWatcher> {
+    // Churn in ignored trees (build output, dependencies) is dropped here, so
+    // it never makes the worker re-walk a directory.
+    let ignored = ignore_matcher(root, respect_gitignore);
+    let watch_root = root.to_path_buf();
+    let handler = move |result: notify::Result<notify::Event>| {
+        let Ok(event) = result else {
+            return;
+        };
+        if matches!(event.kind, notify::EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            if !is_watch_ignored(&watch_root, &path, &ignored) {
+                let _ = fs_tx.send(path);
+            }
+        }
+    };
+    let mut watcher = match notify::recommended_watcher(handler) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            tracing::warn!(%err, "file index: failed to create watcher");
+            return None;
+        }
+    };
+    if let Err(err) = watcher.watch(root, notify::RecursiveMode::Recursive) {
+        tracing::warn!(%err, root = %root.display(), "file index: failed to watch project");
+        return None;
+    }
+    Some(watcher)
+}
+
+/// Root-level ignore rules, built once for the watcher. Nested ignore files
+/// are still honored by the walk that follows an event.
+fn ignore_matcher(root: &Path, respect_gitignore: bool) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    let mut files = vec![root.join(".ignore")];
+    if respect_gitignore {
+        files.push(root.join(".gitignore"));
+        files.push(root.join(".git").join("info").join("exclude"));
+    }
+    for file in files.into_iter().filter(|file| file.is_file()) {
+        if let Some(err) = builder.add(&file) {
+            tracing::warn!(%err, file = %file.display(), "file index: bad ignore file");
+        }
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn is_watch_ignored(root: &Path, path: &Path, ignored: &Gitignore) -> bool {
+    let Some(rel) = rel_path_string(root, path) else {
+        return true;
+    };
+    rel.is_empty()
+        || is_git_dir_path(&rel)
+        || ignored.matched_path_or_any_parents(path, path.is_dir()).is_ignore()
+}
+
+/// Re-index one changed path. Returns `false` once the receiver is gone.
+fn apply_fs_change(
+    root: &Path,
+    path: &Path,
+    respect_gitignore: bool,
+    cancel: &AtomicBool,
+    update_tx: &std_mpsc::Sender<IndexUpdate>,
+) -> bool {
+    let Some(rel) = rel_path_string(root, path) else {
+        return true;
+    };
+    if rel.is_empty() || is_git_dir_path(&rel) {
+        return true;
+    }
+    if !path.exists() {
+        return update_tx.send(IndexUpdate::Remove(rel)).is_ok();
+    }
+    let Some(parent) = path.parent() else {
+        return true;
+    };
+
+    // Walk the parent one level deep, restricted to `path`, so ignore rules
+    // from `parent` and its ancestors decide whether `path` is visible.
+    let mut seen = false;
+    let target = path.to_path_buf();
+    let mut builder = walk_builder(parent, respect_gitignore);
+    builder
+        .max_depth(Some(1))
+        .filter_entry(move |entry| entry.depth() == 0 || entry.path() == target);
+    for entry in builder.build().filter_map(Result::ok) {
+        if entry.depth() == 0 {
+            continue;
+        }
+        if let Some(candidate) = candidate_from_entry(root, &entry) {
+            seen = true;
+            let is_dir = candidate.is_dir;
+            if update_tx.send(IndexUpdate::Upsert(candidate)).is_err() {
+                return false;
+            }
+            // A directory moved into the tree produces no events for its children.
+            if is_dir && !scan(root, path, Some(1), respect_gitignore, cancel, update_tx) {
+                return false;
+            }
+        }
+    }
+    if !seen {
+        return update_tx.send(IndexUpdate::Remove(rel)).is_ok();
+    }
+    true
+}
+
+/// Walk `start` (excluding `start` itself when it is the project root) and
+/// stream candidates. `min_depth` skips the walk root for sub-scans.
+/// Returns `false` if cancelled or the receiver is gone.
+fn scan(
+    root: &Path,
+    start: &Path,
+    min_depth: Option<usize>,
+    respect_gitignore: bool,
+    cancel: &AtomicBool,
+    update_tx: &std_mpsc::Sender<IndexUpdate>,
+) -> bool {
+    let mut builder = walk_builder(start, respect_gitignore);
+    builder.sort_by_file_path(std::cmp::Ord::cmp);
+    for result in builder.build() {
+        if cancel.load(AtomicOrdering::Relaxed) {
+            return false;
+        }
+        let Ok(entry) = result else { continue };
+        if min_depth.is_some_and(|min| entry.depth() < min) {
+            continue;
+        }
+        let Some(candidate) = candidate_from_entry(root, &entry) else {
+            continue;
+        };
+        if update_tx.send(IndexUpdate::Upsert(candidate)).is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+fn walk_builder(start: &Path, respect_gitignore: bool) -> ignore::WalkBuilder {
+    let mut builder = ignore::WalkBuilder::new(start);
+    builder
+        .hidden(false)
+        .parents(true)
+        .git_ignore(respect_gitignore)
+        .git_global(respect_gitignore)
+        .git_exclude(respect_gitignore)
+        .filter_entry(|entry| entry.file_name() != ".git");
+    builder
+}
+
+fn candidate_from_entry(root: &Path, entry: &ignore::DirEntry) -> Option<FileCandidate> {
+    let ft = entry.file_type()?;
+    let is_dir = ft.is_dir();
+    if !is_dir && !ft.is_file() {
+        return None;
+    }
+    let rel_str = rel_path_string(root, entry.path())?;
+    if rel_str.is_empty() {
+        return None;
+    }
+    let depth = rel_str.matches('/').count();
+    let rel_path = if is_dir { format!("{rel_str}/") } else { rel_str };
+    let modified =
+        entry.metadata().ok().and_then(|m| m.modified().ok()).unwrap_or(SystemTime::UNIX_EPOCH);
+    Some(FileCandidate { rel_path, depth, modified, is_dir })
+}
+
+fn rel_path_string(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+fn is_git_dir_path(rel: &str) -> bool {
+    rel == ".git" || rel.starts_with(".git/") || rel.contains("/.git/") || rel.ends_with("/.git")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+
+    fn wait_until(index: &mut FileIndex, mut done: impl FnMut(&FileIndex) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            index.drain();
+            if done(index) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn paths(index: &FileIndex) -> Vec<String> {
+        index.entries().map(|c| c.rel_path.clone()).collect()
+    }
+
+    #[test]
+    fn initial_scan_respects_gitignore_and_skips_git_dir() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join(".git")).expect("create .git");
+        std::fs::write(tmp.path().join(".git").join("HEAD"), "ref").expect("write HEAD");
+        std::fs::write(tmp.path().join(".gitignore"), "target/\n").expect("write .gitignore");
+        std::fs::create_dir_all(tmp.path().join("target")).expect("create target");
+        std::fs::write(tmp.path().join("target").join("out.bin"), "").expect("write out");
+        std::fs::create_dir_all(tmp.path().join("src")).expect("create src");
+        std::fs::write(tmp.path().join("src").join("main.rs"), "").expect("write main");
+
+        let mut index = FileIndex::spawn(tmp.path().to_path_buf(), true);
+        wait_until(&mut index, |index| !index.is_indexing());
+
+        assert!(!index.is_indexing());
+        assert_eq!(paths(&index), vec![".gitignore", "src/", "src/main.rs"]);
+    }
+
+    #[test]
+    fn watcher_applies_incremental_creates_and_removes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("a.rs"), "").expect("write a");
+
+        let mut index = FileIndex::spawn(tmp.path().to_path_buf(), true);
+        wait_until(&mut index, |index| !index.is_indexing());
+        assert_eq!(paths(&index), vec!["a.rs"]);
+
+        std::fs::write(tmp.path().join("b.rs"), "").expect("write b");
+        wait_until(&mut index, |index| index.len() == 2);
+        assert_eq!(paths(&index), vec!["a.rs", "b.rs"]);
+
+        std::fs::remove_file(tmp.path().join("a.rs")).expect("remove a");
+        wait_until(&mut index, |index| index.len() == 1);
+        assert_eq!(paths(&index), vec!["b.rs"]);
+    }
+
+    #[test]
+    fn prefixed_index_keys_entries_from_the_session_directory() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join("src")).expect("create src");
+        std::fs::write(tmp.path().join("src").join("lib.rs"), "").expect("write lib");
+
+        let mut index =
+            FileIndex::spawn_prefixed(tmp.path().to_path_buf(), true, "../api/".to_owned());
+        wait_until(&mut index, |index| !index.is_indexing());
+
+        assert_eq!(paths(&index), vec!["../api/src/", "../api/src/lib.rs"]);
+        assert_eq!(index.file_count_under("../api/src/"), 1);
+    }
+
+    fn insert(index: &mut FileIndex, rel: &str) {
+        index.entries.insert(
+            rel.to_owned(),
+            FileCandidate {
+                rel_path: rel.to_owned(),
+                depth: rel.trim_end_matches('/').matches('/').count(),
+                modified: SystemTime::UNIX_EPOCH,
+                is_dir: rel.ends_with('/'),
+            },
+        );
+    }
+
+    #[test]
+    fn directory_file_counts_and_top_level_files() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut index = FileIndex::spawn(tmp.path().to_path_buf(), true);
+        for rel in ["src/", "src/a.rs", "src/b.rs", "src/nested/", "src/nested/c.rs", "srcx.rs"] {
+            insert(&mut index, rel);
+        }
+
+        assert_eq!(index.file_count_under("src/"), 3);
+        assert_eq!(index.top_level_files("src/"), vec!["src/a.rs", "src/b.rs"]);
+        assert_eq!(index.file_count_under("src/nested/"), 1);
+    }
+
+    #[test]
+    fn watcher_filter_drops_ignored_and_git_paths() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path();
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").expect("write .gitignore");
+        std::fs::create_dir_all(root.join("target")).expect("create target");
+
+        let ignored = ignore_matcher(root, true);
+        assert!(is_watch_ignored(root, &root.join("target").join("out.bin"), &ignored));
+        assert!(is_watch_ignored(root, &root.join("debug.log"), &ignored));
+        assert!(is_watch_ignored(root, &root.join(".git").join("index"), &ignored));
+        assert!(is_watch_ignored(root, Path::new("/elsewhere/a.rs"), &ignored));
+        assert!(!is_watch_ignored(root, &root.join("src").join("main.rs"), &ignored));
+
+        let unfiltered = ignore_matcher(root, false);
+        assert!(!is_watch_ignored(root, &root.join("debug.log"), &unfiltered));
+    }
+
+    #[test]
+    fn remove_subtree_drops_directory_descendants_only() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut index = FileIndex::spawn(tmp.path().to_path_buf(), true);
+        for rel in ["src/", "src/a.rs", "src/nested/", "src/nested/b.rs", "srcx.rs"] {
+            insert(&mut index, rel);
+        }
+
+        assert!(index.remove_subtree("src"));
+
+        assert_eq!(paths(&index), vec!["srcx.rs"]);
+    }
+}
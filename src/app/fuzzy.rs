@@ -0,0 +1,199 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! fzf-style fuzzy matching shared by slash-command, `@` mention, and `:` emoji autocomplete.
+//!
+//! The query must appear in the candidate as a case-insensitive subsequence.
+//! Among all alignments the highest-scoring one is chosen: matches at word
+//! boundaries (after `/`, `_`, `-`, `.`, spaces, or at a camelCase hump) and
+//! consecutive runs score higher, gaps between matched characters cost points.
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_PATH_SEPARATOR: i32 = 9;
+const BONUS_CAMEL: i32 = 7;
+const BONUS_FIRST_CHAR: i32 = 10;
+const BONUS_CONSECUTIVE: i32 = 6;
+const PENALTY_GAP_START: i32 = 3;
+const PENALTY_GAP_EXTENSION: i32 = 1;
+
+/// Result of a successful fuzzy match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better.
+    pub score: i32,
+    /// Byte offsets (into the candidate) of each matched character, ascending.
+    pub positions: Vec<usize>,
+}
+
+/// Match `query` against `candidate`. An empty query matches everything with score 0.
+#[must_use]
+pub fn fuzzy_match(candidate: &str, query: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).map(fold).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    let chars: Vec<(usize, char)> = candidate.char_indices().collect();
+    if !is_subsequence(&chars, &query) {
+        return None;
+    }
+
+    let n = chars.len();
+    let m = query.len();
+    let bonuses: Vec<i32> = (0..n).map(|j| position_bonus(&chars, j)).collect();
+
+    // score[i][j]: best score with query[i] matched at candidate char j.
+    // from[i][j]: candidate index matched by query[i - 1] on that best path.
+    let mut score = vec![vec![None::<i32>; n]; m];
+    let mut from = vec![vec![usize::MAX; n]; m];
+
+    for (j, &(_, ch)) in chars.iter().enumerate() {
+        if fold(ch) == query[0] {
+            score[0][j] = Some(SCORE_MATCH + bonuses[j] * 2);
+        }
+    }
+
+    for i in 1..m {
+        // Best (score, index) among cells at least one char behind, gap-penalized.
+        let mut gapped: Option<(i32, usize)> = None;
+        for j in 1..n {
+            if j >= 2 {
+                gapped = gapped.map(|(s, k)| (s - PENALTY_GAP_EXTENSION, k));
+                if let Some(prev) = score[i - 1][j - 2] {
+                    let opened = prev - PENALTY_GAP_START;
+                    if gapped.is_none_or(|(s, _)| opened > s) {
+                        gapped = Some((opened, j - 2));
+                    }
+                }
+            }
+            if fold(chars[j].1) != query[i] {
+                continue;
+            }
+            let base = SCORE_MATCH + bonuses[j];
+            let consecutive = score[i - 1][j - 1].map(|prev| (prev + BONUS_CONSECUTIVE, j - 1));
+            let best = match (consecutive, gapped) {
+                (Some(c), Some(g)) => Some(if g.0 > c.0 { g } else { c }),
+                (c, g) => c.or(g),
+            };
+            if let Some((prev, k)) = best {
+                score[i][j] = Some(prev + base);
+                from[i][j] = k;
+            }
+        }
+    }
+
+    let (best_score, mut j) = score[m - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, s)| s.map(|s| (s, j)))
+        .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)))?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = chars[j].0;
+        if i > 0 {
+            j = from[i][j];
+        }
+    }
+    Some(FuzzyMatch { score: best_score, positions })
+}
+
+fn fold(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+fn is_subsequence(chars: &[(usize, char)], query: &[char]) -> bool {
+    let mut remaining = query.iter().peekable();
+    for &(_, ch) in chars {
+        if remaining.peek().is_some_and(|&&q| q == fold(ch)) {
+            remaining.next();
+        }
+    }
+    remaining.peek().is_none()
+}
+
+fn position_bonus(chars: &[(usize, char)], j: usize) -> i32 {
+    if j == 0 {
+        return BONUS_FIRST_CHAR;
+    }
+    let prev = chars[j - 1].1;
+    let cur = chars[j].1;
+    match prev {
+        '/' | '\\' => BONUS_PATH_SEPARATOR,
+        '_' | '-' | '.' | ' ' | ':' => BONUS_BOUNDARY,
+        _ if prev.is_lowercase() && cur.is_uppercase() => BONUS_CAMEL,
+        _ if !prev.is_alphanumeric() && cur.is_alphanumeric() => BONUS_BOUNDARY,
+        _ => 0,
+    }
+}
+
+/// Merge matched byte offsets into contiguous `(start, end)` byte ranges for highlighting.
+#[must_use]
+pub fn highlight_ranges(text: &str, positions: &[usize]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &pos in positions {
+        let Some(ch) = text.get(pos..).and_then(|rest| rest.chars().next()) else {
+            continue;
+        };
+        let end = pos + ch.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.1 == pos => last.1 = end,
+            _ => ranges.push((pos, end)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn score(candidate: &str, query: &str) -> i32 {
+        fuzzy_match(candidate, query).map_or(i32::MIN, |m| m.score)
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert!(fuzzy_match("config", "cfx").is_none());
+        assert!(fuzzy_match("", "a").is_none());
+    }
+
+    #[test]
+    fn empty_query_matches_with_zero_score() {
+        assert_eq!(fuzzy_match("anything", ""), Some(FuzzyMatch { score: 0, positions: vec![] }));
+    }
+
+    #[test]
+    fn matches_non_contiguous_characters_case_insensitively() {
+        let found = fuzzy_match("new-session", "NS").expect("match");
+        assert_eq!(found.positions, vec![0, 4]);
+    }
+
+    #[test]
+    fn prefers_word_boundaries_over_earlier_inner_matches() {
+        // "s" appears inside "session" before the word-start of "src".
+        let found = fuzzy_match("session/src/main.rs", "sm").expect("match");
+        assert_eq!(found.positions, vec![8, 12]);
+    }
+
+    #[test]
+    fn contiguous_and_boundary_matches_rank_higher() {
+        assert!(score("src/main.rs", "main") > score("src/mapping_in.rs", "main"));
+        assert!(score("compact", "cmp") > score("cancel-map", "cmp"));
+        assert!(score("MentionState", "ms") > score("mismatch", "ms"));
+    }
+
+    #[test]
+    fn positions_are_byte_offsets_for_multibyte_text() {
+        let found = fuzzy_match("İstanbul", "ib").expect("match");
+        assert_eq!(found.positions, vec![0, 6]);
+        assert_eq!(highlight_ranges("İstanbul", &found.positions), vec![(0, 2), (6, 7)]);
+    }
+
+    #[test]
+    fn highlight_ranges_merge_consecutive_positions() {
+        assert_eq!(highlight_ranges("abcdef", &[0, 1, 2, 4]), vec![(0, 3), (4, 5)]);
+    }
+}
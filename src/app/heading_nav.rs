@@ -0,0 +1,114 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Heading navigation in keyboard selection mode (Ctrl+s). `]` and `[` move the
+//! cursor to the next or previous H1-H3 heading row on screen; past the edge
+//! of the view they scroll to the nearest message that has headings.
+
+use super::selection::sync_chat_visual_selection;
+use super::toast::{ToastKind, show_toast};
+use super::{App, ChatMessage, MessageBlock};
+use crate::ui::markdown::is_fence;
+use crate::ui::theme;
+
+/// Whether a rendered chat row is an ornamented heading.
+fn is_heading_row(row: &str) -> bool {
+    let row = row.trim_start();
+    [theme::HEADING_MARK, theme::SUBHEADING_MARK]
+        .iter()
+        .any(|mark| row.strip_prefix(mark).is_some_and(|rest| rest.starts_with(' ')))
+}
+
+/// Whether `line` is an ATX heading of level 1 to 3.
+fn is_heading_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    let content = trimmed.trim_start_matches('#');
+    (1..=3).contains(&(trimmed.len() - content.len())) && !content.trim().is_empty()
+}
+
+fn has_headings(message: &ChatMessage) -> bool {
+    message.blocks.iter().any(|block| {
+        let MessageBlock::Text(block) = block else {
+            return false;
+        };
+        let mut in_fence = false;
+        block.text.lines().any(|line| {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                return false;
+            }
+            !in_fence && is_heading_line(line)
+        })
+    })
+}
+
+/// Move to the next (`forward`) or previous heading.
+pub(crate) fn jump(app: &mut App, forward: bool) {
+    let Some(visual) = app.chat_visual else {
+        return;
+    };
+    let cursor_row = visual.cursor.row;
+    let visible_row = if forward {
+        (cursor_row + 1..app.rendered_chat_lines.len())
+            .find(|&row| is_heading_row(&app.rendered_chat_lines[row]))
+    } else {
+        (0..cursor_row.min(app.rendered_chat_lines.len()))
+            .rev()
+            .find(|&row| is_heading_row(&app.rendered_chat_lines[row]))
+    };
+    if let Some(row) = visible_row {
+        move_cursor_to(app, row);
+        return;
+    }
+
+    // Nothing on screen: bring the nearest message with headings into view.
+    let scroll = app.viewport.scroll_offset;
+    let candidates = (0..app.messages.len()).filter(|&index| {
+        let top = app.viewport.cumulative_height_before(index);
+        let shown = app.viewport.message_heights.get(index).is_some_and(|&height| height > 0);
+        let ahead = if forward { top > scroll + cursor_row } else { top < scroll };
+        shown && ahead && has_headings(&app.messages[index])
+    });
+    let target = if forward { candidates.min() } else { candidates.max() };
+    let Some(index) = target else {
+        let edge = if forward { "below" } else { "above" };
+        show_toast(app, ToastKind::Info, format!("No headings {edge}"));
+        return;
+    };
+    app.viewport.auto_scroll = false;
+    app.viewport.restore_scroll_anchor(index, 0);
+    // Walking back starts from the bottom so the message's headings come next.
+    let row =
+        if forward { 0 } else { usize::from(app.rendered_chat_area.height).saturating_sub(1) };
+    move_cursor_to(app, row);
+}
+
+fn move_cursor_to(app: &mut App, row: usize) {
+    if let Some(visual) = &mut app.chat_visual {
+        visual.cursor = super::SelectionPoint { row, col: 0 };
+    }
+    sync_chat_visual_selection(app);
+    app.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{MessageRole, TextBlock};
+
+    #[test]
+    fn heading_rows_and_sources_are_recognized() {
+        assert!(is_heading_row("\u{a7} Install"));
+        assert!(is_heading_row("  \u{203a} Step one"));
+        assert!(!is_heading_row("\u{a7}1 of the spec"));
+
+        let message = |text: &str| ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        };
+        assert!(has_headings(&message("intro\n\n## Setup\n")));
+        assert!(!has_headings(&message("#### Deep\n```sh\n# comment\n```")));
+    }
+}
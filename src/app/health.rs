@@ -0,0 +1,175 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Agent connection heartbeat. The bridge is pinged every few seconds and the
+//! round trip drives a health glyph in the footer, so a hung bridge no longer
+//! looks like a model that is still thinking. Hovering the glyph shows the
+//! latency.
+
+use super::App;
+use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
+
+/// Time between pings once the previous one was answered.
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Round trips slower than this mark the connection as degraded.
+const DEGRADED_LATENCY: Duration = Duration::from_secs(2);
+
+/// An unanswered ping older than this marks the connection as lost.
+const LOST_AFTER: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    Ok,
+    Degraded,
+    Lost,
+}
+
+impl HealthState {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "connected",
+            Self::Degraded => "slow to respond",
+            Self::Lost => "not responding",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    last_nonce: u64,
+    /// Nonce and send time of the ping awaiting its pong.
+    in_flight: Option<(u64, Instant)>,
+    next_ping_at: Option<Instant>,
+    pub last_latency: Option<Duration>,
+    pub last_pong_at: Option<Instant>,
+    /// State drawn last, to redraw only on changes.
+    shown: Option<HealthState>,
+    /// Footer cell holding the glyph, for hover hit-testing.
+    pub glyph_area: Rect,
+    pub tooltip_visible: bool,
+}
+
+impl ConnectionHealth {
+    /// Health as of `now`; `None` before the first ping went out.
+    #[must_use]
+    pub fn state(&self, now: Instant) -> Option<HealthState> {
+        let waiting = self.in_flight.map(|(_, sent)| now.saturating_duration_since(sent));
+        if waiting.is_some_and(|waited| waited >= LOST_AFTER) {
+            return Some(HealthState::Lost);
+        }
+        if waiting.is_some_and(|waited| waited >= DEGRADED_LATENCY)
+            || self.last_latency.is_some_and(|latency| latency >= DEGRADED_LATENCY)
+        {
+            return Some(HealthState::Degraded);
+        }
+        if self.last_latency.is_none() && waiting.is_none() {
+            return None;
+        }
+        Some(HealthState::Ok)
+    }
+
+    /// Text of the hover tooltip.
+    #[must_use]
+    pub fn tooltip(&self, now: Instant) -> Vec<String> {
+        let state = self.state(now).unwrap_or(HealthState::Ok);
+        let mut lines = vec![format!("Agent {}", state.label())];
+        if let Some(latency) = self.last_latency {
+            lines.push(format!("Latency {} ms", latency.as_millis()));
+        }
+        if let Some(at) = self.last_pong_at {
+            lines.push(format!("Last reply {}s ago", now.saturating_duration_since(at).as_secs()));
+        }
+        if let Some((_, sent)) = self.in_flight
+            && state != HealthState::Ok
+        {
+            lines.push(format!(
+                "Waiting {}s for a reply",
+                now.saturating_duration_since(sent).as_secs()
+            ));
+        }
+        lines
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Send a ping when one is due and redraw when the health state changes.
+pub fn tick(app: &mut App, now: Instant) {
+    if let Some(conn) = app.conn.as_ref()
+        && app.health.in_flight.is_none()
+        && app.health.next_ping_at.is_none_or(|due| now >= due)
+    {
+        let nonce = app.health.last_nonce.wrapping_add(1);
+        match conn.ping(nonce) {
+            Ok(()) => {
+                app.health.last_nonce = nonce;
+                app.health.in_flight = Some((nonce, now));
+            }
+            Err(err) => tracing::debug!(%err, "heartbeat ping not sent"),
+        }
+        app.health.next_ping_at = Some(now + PING_INTERVAL);
+    }
+    let state = footer_state(app, now);
+    if state != app.health.shown {
+        app.health.shown = state;
+        app.needs_redraw = true;
+    }
+}
+
+pub(crate) fn handle_pong(app: &mut App, nonce: u64, now: Instant) {
+    let Some((expected, sent)) = app.health.in_flight else {
+        return;
+    };
+    if nonce != expected {
+        return;
+    }
+    app.health.in_flight = None;
+    app.health.last_latency = Some(now.saturating_duration_since(sent));
+    app.health.last_pong_at = Some(now);
+    app.health.next_ping_at = Some(now + PING_INTERVAL);
+}
+
+/// The glyph's state for the footer; hidden while no bridge is attached.
+#[must_use]
+pub fn footer_state(app: &App, now: Instant) -> Option<HealthState> {
+    if app.conn.is_none() {
+        return app.bridge_reconnect.as_ref().map(|_| HealthState::Lost);
+    }
+    app.health.state(now)
+}
+
+/// Show the tooltip while the mouse rests on the glyph.
+pub(crate) fn update_hover(app: &mut App, column: u16, row: u16) {
+    let area = app.health.glyph_area;
+    app.health.tooltip_visible = area.width > 0
+        && (area.x..area.x + area.width).contains(&column)
+        && (area.y..area.y + area.height).contains(&row);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn state_follows_round_trips_and_unanswered_pings() {
+        let start = Instant::now();
+        let mut health = ConnectionHealth::default();
+        assert_eq!(health.state(start), None);
+
+        health.in_flight = Some((1, start));
+        assert_eq!(health.state(start + Duration::from_millis(50)), Some(HealthState::Ok));
+        assert_eq!(health.state(start + DEGRADED_LATENCY), Some(HealthState::Degraded));
+        assert_eq!(health.state(start + LOST_AFTER), Some(HealthState::Lost));
+
+        health.in_flight = None;
+        health.last_latency = Some(Duration::from_millis(40));
+        assert_eq!(health.state(start), Some(HealthState::Ok));
+        assert_eq!(health.tooltip(start), vec!["Agent connected", "Latency 40 ms"]);
+    }
+}
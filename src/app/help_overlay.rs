@@ -0,0 +1,80 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Full-screen help (F1). Lists every key binding, the keyboard selection mode
+//! keys, mouse gestures and the slash commands in one scrollable view; typing
+//! narrows it to the entries containing every word of the query. The `?`
+//! panel above the input stays the quick, context-aware summary.
+
+use super::App;
+use super::line_input::LineInput;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Rows moved by PageUp/PageDown.
+const PAGE_ROWS: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct HelpOverlay {
+    pub input: LineInput,
+    /// First content row shown; clamped by the renderer.
+    pub scroll: usize,
+}
+
+/// Whether a help entry matches every whitespace-separated word of `query`,
+/// ignoring case.
+#[must_use]
+pub fn entry_matches(key: &str, description: &str, query: &str) -> bool {
+    let haystack = format!("{key} {description}").to_lowercase();
+    query.split_whitespace().all(|word| haystack.contains(&word.to_lowercase()))
+}
+
+pub(crate) fn open(app: &mut App) {
+    app.help_overlay = Some(HelpOverlay::default());
+    app.needs_redraw = true;
+}
+
+pub(crate) fn handle_key(app: &mut App, key: KeyEvent) {
+    let Some(overlay) = app.help_overlay.as_mut() else {
+        return;
+    };
+    app.needs_redraw = true;
+    match key.code {
+        KeyCode::Esc | KeyCode::F(1) => app.help_overlay = None,
+        KeyCode::Up => overlay.scroll = overlay.scroll.saturating_sub(1),
+        KeyCode::Down => overlay.scroll += 1,
+        KeyCode::PageUp => overlay.scroll = overlay.scroll.saturating_sub(PAGE_ROWS),
+        KeyCode::PageDown => overlay.scroll += PAGE_ROWS,
+        _ => {
+            if overlay.input.handle_key(key) {
+                overlay.scroll = 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn queries_match_all_words_in_key_or_description() {
+        assert!(entry_matches("Ctrl+t", "Toggle todos", "todo"));
+        assert!(entry_matches("Ctrl+t", "Toggle todos", "CTRL toggle"));
+        assert!(!entry_matches("Ctrl+t", "Toggle todos", "todo header"));
+        assert!(entry_matches("Ctrl+t", "Toggle todos", "  "));
+    }
+
+    #[test]
+    fn typing_resets_the_scroll_and_esc_closes() {
+        let mut app = App::test_default();
+        open(&mut app);
+        handle_key(&mut app, KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(app.help_overlay.as_ref().map(|overlay| overlay.scroll), Some(PAGE_ROWS));
+        handle_key(&mut app, KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE));
+        assert_eq!(app.help_overlay.as_ref().map(|overlay| overlay.scroll), Some(0));
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(app.help_overlay.is_none());
+    }
+}
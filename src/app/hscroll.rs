@@ -0,0 +1,154 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sideways scrolling of wide preformatted content in keyboard selection mode
+//! (Ctrl+s). Shift+Right on a code block, command output or diff stops
+//! wrapping its long lines and scrolls them right; Shift+Left scrolls back and,
+//! from the first column, returns to wrapping.
+
+use super::{App, InvalidationLevel, MessageBlock, ToolCallInfo, code_blocks};
+use crate::agent::model;
+use crate::ui::hscroll;
+
+/// Columns moved per key press.
+const STEP: usize = 8;
+
+/// Next offset for a block whose widest line is `widest` columns, `None`
+/// meaning wrapped.
+fn next_offset(current: Option<usize>, right: bool, widest: usize) -> Option<usize> {
+    match (current, right) {
+        (None, true) => Some(0),
+        (Some(offset), true) if offset + STEP < widest => Some(offset + STEP),
+        (Some(0) | None, false) => None,
+        (Some(offset), false) => Some(offset.saturating_sub(STEP)),
+        (Some(offset), true) => Some(offset),
+    }
+}
+
+fn widest_line(text: &str) -> usize {
+    text.lines().map(crate::ui::width::display_width).max().unwrap_or(0)
+}
+
+/// Everything a tool call shows as preformatted text.
+fn tool_text(tc: &ToolCallInfo) -> String {
+    let mut text = String::new();
+    for part in [&tc.terminal_command, &tc.terminal_output].into_iter().flatten() {
+        text.push_str(part);
+        text.push('\n');
+    }
+    for content in &tc.content {
+        match content {
+            model::ToolCallContent::Diff(diff) => {
+                text.push_str(diff.old_text.as_deref().unwrap_or_default());
+                text.push('\n');
+                text.push_str(&diff.new_text);
+                text.push('\n');
+            }
+            model::ToolCallContent::Content(model::Content {
+                content: model::ContentBlock::Text(block),
+            }) => {
+                text.push_str(&block.text);
+                text.push('\n');
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Text of a tool call box row without its border, when `row` is one.
+fn tool_row(row: &str) -> Option<&str> {
+    let rest = row.strip_prefix("  ")?;
+    let rest = ["\u{2502}", "\u{2514}\u{2500}"].iter().find_map(|edge| rest.strip_prefix(edge))?;
+    Some(rest.trim().trim_start_matches(hscroll::LEFT_MARK).trim_end_matches(hscroll::RIGHT_MARK))
+}
+
+/// Scroll the block under the selection cursor. Returns false when there is
+/// nothing to scroll there.
+pub(crate) fn scroll_at_visual_cursor(app: &mut App, right: bool) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let message_index =
+        app.viewport.find_first_visible(app.viewport.scroll_offset + visual.cursor.row);
+    let row = app.rendered_chat_lines.get(visual.cursor.row).map_or("", String::as_str);
+
+    if let Some(row) = tool_row(row).map(|row| row.trim().to_owned()) {
+        return scroll_tool_call(app, message_index, &row, right);
+    }
+    let Some((message_index, block_index, code)) = code_blocks::code_at_visual_cursor(app) else {
+        return false;
+    };
+    let Some(MessageBlock::Text(block)) =
+        app.messages.get_mut(message_index).and_then(|message| message.blocks.get_mut(block_index))
+    else {
+        return false;
+    };
+    let view = &mut block.code_view;
+    match next_offset(view.scroll.then_some(view.offset), right, widest_line(&code)) {
+        Some(offset) => {
+            view.scroll = true;
+            view.offset = offset;
+        }
+        None => *view = super::CodeView { scroll: false, offset: 0, ..*view },
+    }
+    block.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.needs_redraw = true;
+    true
+}
+
+fn scroll_tool_call(app: &mut App, message_index: usize, row: &str, right: bool) -> bool {
+    let Some(message) = app.messages.get_mut(message_index) else {
+        return false;
+    };
+    let mut candidates: Vec<&mut ToolCallInfo> = message
+        .blocks
+        .iter_mut()
+        .filter_map(|block| match block {
+            MessageBlock::ToolCall(tc) if !tc.hidden => Some(tc.as_mut()),
+            _ => None,
+        })
+        .collect();
+    // Several calls in one message: the one showing this row, else the last.
+    if candidates.len() > 1
+        && !row.is_empty()
+        && let Some(index) = candidates.iter().rposition(|tc| tool_text(tc).contains(row))
+    {
+        candidates.truncate(index + 1);
+    }
+    let Some(tc) = candidates.pop() else {
+        return false;
+    };
+    tc.h_scroll = next_offset(tc.h_scroll, right, widest_line(&tool_text(tc)));
+    tc.mark_tool_call_layout_dirty();
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn offsets_step_and_return_to_wrapping() {
+        assert_eq!(next_offset(None, true, 100), Some(0));
+        assert_eq!(next_offset(Some(0), true, 100), Some(8));
+        assert_eq!(next_offset(Some(96), true, 100), Some(96));
+        assert_eq!(next_offset(Some(8), false, 100), Some(0));
+        assert_eq!(next_offset(Some(0), false, 100), None);
+        assert_eq!(next_offset(None, false, 100), None);
+    }
+
+    #[test]
+    fn tool_rows_lose_their_border() {
+        assert_eq!(tool_row("  \u{2502} \u{2039}cargo build"), Some("cargo build"));
+        assert_eq!(tool_row("  \u{2514}\u{2500} done"), Some("done"));
+        assert_eq!(tool_row("plain text"), None);
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shared setup for outbound HTTP (update check, service status, OAuth usage).
+//!
+//! Proxies come from `HTTPS_PROXY` / `HTTP_PROXY` / `ALL_PROXY`, with hosts in
+//! `NO_PROXY` going direct; reqwest reads these itself. A custom CA bundle is
+//! merged into the trusted roots so TLS-intercepting corporate proxies verify.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CA_BUNDLE_ENV: &str = "CLAUDE_RS_CA_BUNDLE";
+/// Also honored by the Claude CLI and the Node bridge, so corporate setups
+/// usually have it set already.
+const NODE_EXTRA_CA_ENV: &str = "NODE_EXTRA_CA_CERTS";
+const PROXY_ENVS: &[&str] =
+    &["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"];
+
+#[derive(Clone, Default)]
+pub struct HttpSettings {
+    ca_bundle: Option<PathBuf>,
+    extra_roots: Vec<reqwest::Certificate>,
+}
+
+impl HttpSettings {
+    /// Load the CA bundle from `--ca-bundle`, `CLAUDE_RS_CA_BUNDLE` or
+    /// `NODE_EXTRA_CA_CERTS`, in that order.
+    pub fn load(explicit_ca_bundle: Option<&Path>) -> Result<Self, String> {
+        let Some(path) = ca_bundle_path(explicit_ca_bundle, |name| std::env::var_os(name)) else {
+            return Ok(Self::default());
+        };
+        let pem = std::fs::read(&path)
+            .map_err(|err| format!("Failed to read CA bundle {}: {err}", path.display()))?;
+        let extra_roots = reqwest::Certificate::from_pem_bundle(&pem)
+            .map_err(|err| format!("Invalid CA bundle {}: {err}", path.display()))?;
+        if extra_roots.is_empty() {
+            return Err(format!("CA bundle {} contains no certificates", path.display()));
+        }
+        tracing::info!(
+            path = %path.display(),
+            certificates = extra_roots.len(),
+            "Trusting extra CA certificates for outbound HTTP"
+        );
+        Ok(Self { ca_bundle: Some(path), extra_roots })
+    }
+
+    #[must_use]
+    pub fn ca_bundle(&self) -> Option<&Path> {
+        self.ca_bundle.as_deref()
+    }
+
+    /// A client builder with the timeout and extra trust roots applied.
+    pub fn client_builder(&self, timeout: Duration) -> reqwest::ClientBuilder {
+        reqwest::Client::builder().timeout(timeout).tls_certs_merge(self.extra_roots.clone())
+    }
+}
+
+fn ca_bundle_path(
+    explicit: Option<&Path>,
+    env: impl Fn(&str) -> Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    explicit.map(Path::to_path_buf).or_else(|| {
+        [CA_BUNDLE_ENV, NODE_EXTRA_CA_ENV]
+            .into_iter()
+            .filter_map(&env)
+            .find(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+fn proxy_configured() -> bool {
+    PROXY_ENVS.iter().any(|name| std::env::var_os(name).is_some_and(|value| !value.is_empty()))
+}
+
+/// Full error chain of a failed request, with a hint when the failure looks
+/// like a proxy or certificate problem.
+#[must_use]
+pub fn describe_error(error: &reqwest::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    let lower = message.to_ascii_lowercase();
+    if lower.contains("certificate") || lower.contains("unknownissuer") {
+        message.push_str(
+            " (if a proxy intercepts TLS, pass its CA with --ca-bundle or CLAUDE_RS_CA_BUNDLE)",
+        );
+    } else if error.is_connect() && proxy_configured() {
+        message.push_str(" (check HTTPS_PROXY and NO_PROXY)");
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ca_bundle_path_prefers_flag_then_env_order() {
+        let env = |name: &str| match name {
+            CA_BUNDLE_ENV => Some("".into()),
+            NODE_EXTRA_CA_ENV => Some("/etc/corp-ca.pem".into()),
+            _ => None,
+        };
+        assert_eq!(
+            ca_bundle_path(Some(Path::new("/tmp/flag.pem")), env),
+            Some(PathBuf::from("/tmp/flag.pem"))
+        );
+        assert_eq!(ca_bundle_path(None, env), Some(PathBuf::from("/etc/corp-ca.pem")));
+        assert_eq!(ca_bundle_path(None, |_| None), None);
+    }
+
+    #[test]
+    fn load_rejects_missing_and_empty_bundles() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let missing = dir.path().join("missing.pem");
+        let err = HttpSettings::load(Some(&missing)).err().expect("missing bundle fails");
+        assert!(err.starts_with("Failed to read CA bundle"), "{err}");
+
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "").expect("write bundle");
+        let err = HttpSettings::load(Some(&empty)).err().expect("empty bundle fails");
+        assert!(err.ends_with("contains no certificates"), "{err}");
+    }
+}
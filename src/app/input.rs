@@ -610,7 +610,7 @@ pub fn parse_paste_placeholder_ranges(line: &str) -> Vec<(usize, usize)> {
 #[cfg(test)]
 mod tests {
     // =====
-    // TESTS: 83
+    // TESTS: 84
     // =====
 
     use super::*;
@@ -1613,4 +1613,126 @@ mod tests {
         assert_eq!(count_text_chars("\u{1F600}\u{4F60}"), 2);
         assert_eq!(count_text_chars("a\nb"), 3);
     }
+
+    // property: random edit sequences keep the cursor in bounds
+
+    #[derive(Debug, Clone)]
+    enum EditOp {
+        Char(char),
+        Str(String),
+        Paste(String),
+        Newline,
+        Backspace,
+        Delete,
+        DeleteWordBefore,
+        DeleteWordAfter,
+        Left,
+        Right,
+        Up,
+        Down,
+        Home,
+        End,
+        WordLeft,
+        WordRight,
+        Undo,
+        Redo,
+        Jump(usize, usize),
+    }
+
+    const EDIT_TEXT: &str = "[a-zA-Z0-9 \\t\\r\\n\u{e9}\u{301}\u{65e5}\u{1f600}-]{0,12}";
+
+    fn edit_op() -> impl proptest::strategy::Strategy<Value = EditOp> {
+        use proptest::prelude::*;
+        prop_oneof![
+            4 => prop::sample::select(vec!['a', 'Z', ' ', '\u{e9}', '\u{301}', '\u{65e5}', '\u{1f600}'])
+                .prop_map(EditOp::Char),
+            2 => EDIT_TEXT.prop_map(EditOp::Str),
+            1 => EDIT_TEXT.prop_map(EditOp::Paste),
+            1 => Just(EditOp::Newline),
+            2 => Just(EditOp::Backspace),
+            1 => Just(EditOp::Delete),
+            1 => Just(EditOp::DeleteWordBefore),
+            1 => Just(EditOp::DeleteWordAfter),
+            1 => Just(EditOp::Left),
+            1 => Just(EditOp::Right),
+            1 => Just(EditOp::Up),
+            1 => Just(EditOp::Down),
+            1 => Just(EditOp::Home),
+            1 => Just(EditOp::End),
+            1 => Just(EditOp::WordLeft),
+            1 => Just(EditOp::WordRight),
+            1 => Just(EditOp::Undo),
+            1 => Just(EditOp::Redo),
+            1 => (0usize..8, 0usize..24).prop_map(|(row, col)| EditOp::Jump(row, col)),
+        ]
+    }
+
+    fn apply_edit(input: &mut InputState, op: &EditOp) {
+        match op {
+            EditOp::Char(c) => input.insert_char(*c),
+            EditOp::Str(s) => input.insert_str(s),
+            EditOp::Paste(s) => {
+                let _ = input.insert_paste_block(s);
+            }
+            EditOp::Newline => input.insert_newline(),
+            EditOp::Backspace => input.delete_char_before(),
+            EditOp::Delete => input.delete_char_after(),
+            EditOp::DeleteWordBefore => {
+                let _ = input.textarea_delete_word_before();
+            }
+            EditOp::DeleteWordAfter => {
+                let _ = input.textarea_delete_word_after();
+            }
+            EditOp::Left => input.move_left(),
+            EditOp::Right => input.move_right(),
+            EditOp::Up => input.move_up(),
+            EditOp::Down => input.move_down(),
+            EditOp::Home => input.move_home(),
+            EditOp::End => input.move_end(),
+            EditOp::WordLeft => {
+                let _ = input.textarea_move_word_left();
+            }
+            EditOp::WordRight => {
+                let _ = input.textarea_move_word_right();
+            }
+            EditOp::Undo => {
+                let _ = input.textarea_undo();
+            }
+            EditOp::Redo => {
+                let _ = input.textarea_redo();
+            }
+            EditOp::Jump(row, col) => {
+                let _ = input.set_cursor(*row, *col);
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn random_edits_keep_cursor_and_lines_consistent(
+            ops in proptest::collection::vec(edit_op(), 0..48),
+            width in 1u16..40,
+        ) {
+            let mut input = InputState::new();
+            for op in &ops {
+                apply_edit(&mut input, op);
+                let lines = input.lines();
+                let (row, col) = input.cursor();
+                proptest::prop_assert!(!lines.is_empty());
+                proptest::prop_assert!(row < lines.len(), "row {row} of {} lines", lines.len());
+                proptest::prop_assert!(
+                    col <= lines[row].chars().count(),
+                    "col {col} past end of {:?}",
+                    lines[row]
+                );
+                proptest::prop_assert!(lines.iter().all(|line| !line.contains(['\n', '\r'])));
+                // Byte-boundary safety: slicing at the cursor must not panic.
+                let byte_idx = char_to_byte_index(&lines[row], col);
+                proptest::prop_assert!(lines[row].is_char_boundary(byte_idx));
+                proptest::prop_assert_eq!(input.is_empty(), lines.len() == 1 && lines[0].is_empty());
+                let _ = input.text();
+            }
+            proptest::prop_assert!(input.measure_visual_lines(width, 12) >= 1);
+        }
+    }
 }
@@ -4,7 +4,8 @@
 use super::{App, AppStatus, CancelOrigin, ChatMessage, MessageBlock, MessageRole, TextBlock};
 use crate::agent::events::ClientEvent;
 use crate::agent::model;
-use crate::app::slash;
+use crate::agent::types::PromptChunk;
+use crate::app::{attachments, slash};
 
 pub(super) fn submit_input(app: &mut App) {
     if matches!(app.status, AppStatus::Connecting | AppStatus::CommandPending | AppStatus::Error) {
@@ -15,6 +16,7 @@ pub(super) fn submit_input(app: &mut App) {
     app.mention = None;
     app.slash = None;
     app.subagent = None;
+    app.emoji = None;
 
     // No connection yet - can't submit
     let text = app.input.text();
@@ -101,6 +103,10 @@ pub(super) fn maybe_auto_submit_after_cancel(app: &mut App) {
 }
 
 fn dispatch_submission(app: &mut App, text: String) {
+    if let Some(expanded) = slash::expand_custom_command(app, &text) {
+        dispatch_prompt_turn_as(app, &text, expanded);
+        return;
+    }
     if slash::try_handle_submit(app, &text) {
         return;
     }
@@ -108,32 +114,75 @@ fn dispatch_submission(app: &mut App, text: String) {
 }
 
 fn dispatch_prompt_turn(app: &mut App, text: String) {
+    dispatch_prompt_turn_as(app, &text.clone(), text);
+}
+
+/// Send `prompt` to the agent while showing `display` as the user message.
+/// Custom slash commands display the typed command but send the expanded template.
+pub(super) fn dispatch_prompt_turn_as(app: &mut App, display: &str, prompt: String) {
     // New turn started by user input: force-stop stale tool calls from older turns
     // so their spinners don't continue during this turn.
     let _ = app.finalize_in_progress_tool_calls(model::ToolCallStatus::Failed);
 
-    let Some(conn) = app.conn.clone() else { return };
-    let Some(sid) = app.session_id.clone() else {
+    if app.conn.is_none() || app.session_id.is_none() {
         return;
-    };
+    }
 
+    super::suggestions::clear_suggestions(app);
+    app.turn_retry = None;
+    app.tool_retry_sent = false;
     app.messages.push(ChatMessage {
         role: MessageRole::User,
-        blocks: vec![MessageBlock::Text(TextBlock::from_complete(&text))],
+        blocks: vec![MessageBlock::Text(TextBlock::from_complete(display))],
         usage: None,
+        annotation: None,
     });
     // Create empty assistant message immediately -- message.rs shows thinking indicator
     app.messages.push(ChatMessage {
         role: MessageRole::Assistant,
         blocks: Vec::new(),
         usage: None,
+        annotation: None,
     });
     app.enforce_history_retention_tracked();
     app.status = AppStatus::Thinking;
     app.viewport.engage_auto_scroll();
+    super::lifecycle_hooks::turn_started(app, &prompt);
+    let prompt = super::rewind::with_rewind_note(app, prompt);
+    send_prompt(app, prompt);
+}
+
+/// Send `prompt` without a new user message: the prompt again after a
+/// rate-limit or overload error, or a request to retry a flaky tool call.
+/// Only a fresh assistant message is added.
+pub(super) fn retry_prompt(app: &mut App, prompt: String) {
+    if app.conn.is_none() || app.session_id.is_none() {
+        app.turn_retry = None;
+        return;
+    }
+    app.messages.push(ChatMessage {
+        role: MessageRole::Assistant,
+        blocks: Vec::new(),
+        usage: None,
+        annotation: None,
+    });
+    app.enforce_history_retention_tracked();
+    app.status = AppStatus::Thinking;
+    app.viewport.engage_auto_scroll();
+    send_prompt(app, prompt);
+}
+
+fn send_prompt(app: &mut App, prompt: String) {
+    let Some(conn) = app.conn.clone() else { return };
+    let Some(sid) = app.session_id.clone() else {
+        return;
+    };
+    app.last_prompt = Some(prompt.clone());
 
     let tx = app.event_tx.clone();
-    match conn.prompt_text(sid.to_string(), text) {
+    let attach_images = app.agent_supports(|caps| caps.prompt_image);
+    let chunks = prompt_chunks(&app.cwd_raw, prompt, attach_images);
+    match conn.prompt(sid.to_string(), chunks) {
         Ok(resp) => {
             tracing::debug!("Prompt dispatched: stop_reason={:?}", resp.stop_reason);
         }
@@ -143,6 +192,22 @@ fn dispatch_prompt_turn(app: &mut App, text: String) {
     }
 }
 
+/// The prompt text plus one image chunk per `@`-mentioned image file. Agents
+/// without image support get the `@path` references only.
+fn prompt_chunks(cwd: &str, prompt: String, attach_images: bool) -> Vec<PromptChunk> {
+    let images = if attach_images {
+        attachments::collect_image_attachments(cwd, &prompt)
+    } else {
+        Vec::new()
+    };
+    let mut chunks = vec![PromptChunk { kind: "text".to_owned(), value: prompt.into() }];
+    chunks.extend(images.into_iter().map(|image| PromptChunk {
+        kind: "image".to_owned(),
+        value: serde_json::json!({ "mime_type": image.mime_type, "data": image.data }),
+    }));
+    chunks
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +368,79 @@ mod tests {
         assert!(app.messages.is_empty());
         assert!(matches!(app.status, AppStatus::Ready));
     }
+
+    #[test]
+    fn custom_command_sends_expanded_template_and_shows_typed_command() {
+        let (mut app, mut rx) = app_with_connection();
+        app.custom_commands = vec![slash::CustomCommand {
+            name: "/fix-issue".to_owned(),
+            description: "Fix an issue".to_owned(),
+            argument_hint: Some("<number>".to_owned()),
+            body: "Find and fix issue #$ARGUMENTS.".to_owned(),
+            scope: slash::CustomCommandScope::Project,
+            path: std::path::PathBuf::from("fix-issue.md"),
+        }];
+        app.input.set_text("/fix-issue 123");
+
+        submit_input(&mut app);
+
+        let Some(MessageBlock::Text(block)) = app.messages.first().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected user text block");
+        };
+        assert_eq!(block.text, "/fix-issue 123");
+        let prompt = rx.try_recv().expect("prompt command should be sent");
+        let BridgeCommand::Prompt { chunks, .. } = prompt.command else {
+            panic!("expected prompt command");
+        };
+        assert_eq!(chunks[0].value, serde_json::json!("Find and fix issue #123."));
+    }
+
+    #[test]
+    fn mentioned_images_are_sent_as_image_chunks() {
+        let (mut app, mut rx) = app_with_connection();
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("shot.png"), b"foo").expect("write image");
+        app.cwd_raw = tmp.path().to_string_lossy().into_owned();
+        app.input.set_text("what is wrong in @shot.png");
+
+        submit_input(&mut app);
+
+        let prompt = rx.try_recv().expect("prompt command should be sent");
+        let BridgeCommand::Prompt { chunks, .. } = prompt.command else {
+            panic!("expected prompt command");
+        };
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].kind, "text");
+        assert_eq!(chunks[1].kind, "image");
+        assert_eq!(
+            chunks[1].value,
+            serde_json::json!({ "mime_type": "image/png", "data": "Zm9v" })
+        );
+    }
+
+    #[test]
+    fn images_are_not_attached_when_agent_lacks_image_support() {
+        let (mut app, mut rx) = app_with_connection();
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("shot.png"), b"foo").expect("write image");
+        app.cwd_raw = tmp.path().to_string_lossy().into_owned();
+        app.agent_info = Some(crate::agent::types::InitializeResult {
+            agent_name: "local-agent".to_owned(),
+            agent_version: "0.1.0".to_owned(),
+            auth_methods: Vec::new(),
+            capabilities: crate::agent::types::AgentCapabilities::default(),
+            protocol_version: Some(crate::agent::wire::BRIDGE_PROTOCOL_VERSION),
+        });
+        app.input.set_text("what is wrong in @shot.png");
+
+        submit_input(&mut app);
+
+        let prompt = rx.try_recv().expect("prompt command should be sent");
+        let BridgeCommand::Prompt { chunks, .. } = prompt.command else {
+            panic!("expected prompt command");
+        };
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].kind, "text");
+    }
 }
@@ -0,0 +1,450 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/issue <number|url>`: fetch a GitHub or GitLab issue with its labels and
+//! comments and put a prompt about it into the input, ready for the user to
+//! add instructions and send.
+//!
+//! A bare number refers to the repository behind the `origin` remote. The
+//! REST API is reached through `gh api` / `glab api` when the CLI is
+//! installed, so its login is reused; otherwise over HTTPS with
+//! `GH_TOKEN`/`GITHUB_TOKEN` or `GITLAB_TOKEN` when set, anonymously when not.
+
+use super::commit::git;
+use super::{App, AppStatus};
+use crate::agent::events::ClientEvent;
+use reqwest::header::{ACCEPT, AUTHORIZATION, HeaderMap, HeaderValue, USER_AGENT};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const ISSUE_USER_AGENT: &str = "claude-code-rust-issue-import";
+/// Issue bodies and comments are cut after this many chars each.
+const MAX_BODY_CHARS: usize = 20_000;
+const MAX_COMMENT_CHARS: usize = 4_000;
+/// Only the latest comments are quoted.
+const MAX_COMMENTS: usize = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Forge {
+    GitHub,
+    GitLab,
+}
+
+impl Forge {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub",
+            Self::GitLab => "GitLab",
+        }
+    }
+
+    const fn cli(self) -> &'static str {
+        match self {
+            Self::GitHub => "gh",
+            Self::GitLab => "glab",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IssueRef {
+    forge: Forge,
+    host: String,
+    /// `owner/repo`, or the full project path on GitLab.
+    repo: String,
+    number: u64,
+}
+
+impl IssueRef {
+    fn endpoints(&self) -> [String; 2] {
+        match self.forge {
+            Forge::GitHub => {
+                let issue = format!("repos/{}/issues/{}", self.repo, self.number);
+                [issue.clone(), format!("{issue}/comments?per_page=100")]
+            }
+            Forge::GitLab => {
+                let issue =
+                    format!("projects/{}/issues/{}", self.repo.replace('/', "%2F"), self.number);
+                [issue.clone(), format!("{issue}/notes?sort=asc&per_page=100")]
+            }
+        }
+    }
+
+    fn api_base(&self) -> String {
+        match self.forge {
+            Forge::GitHub if self.host == "github.com" => "https://api.github.com/".to_owned(),
+            Forge::GitHub => format!("https://{}/api/v3/", self.host),
+            Forge::GitLab => format!("https://{}/api/v4/", self.host),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Issue {
+    number: u64,
+    title: String,
+    url: String,
+    state: String,
+    author: String,
+    labels: Vec<String>,
+    body: String,
+    /// `(author, body)` in posting order.
+    comments: Vec<(String, String)>,
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    let [reference] = args else {
+        return Err("Usage: /issue <number|url>".to_owned());
+    };
+    if app.is_offline() {
+        return Err("/issue is unavailable in offline mode.".to_owned());
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /issue.".to_owned());
+    }
+    let cwd = app.cwd_raw.clone();
+    let issue_ref = parse_reference(reference, || {
+        git(&cwd, &["remote", "get-url", "origin"]).map(|url| url.trim().to_owned())
+    })?;
+
+    let fetcher = if which::which(issue_ref.forge.cli()).is_ok() {
+        Fetcher::Cli
+    } else {
+        let client = app
+            .http
+            .client_builder(FETCH_TIMEOUT)
+            .default_headers(api_headers(issue_ref.forge))
+            .build()
+            .map_err(|err| super::http::describe_error(&err))?;
+        Fetcher::Http(client)
+    };
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some(format!("Fetching issue #{}...", issue_ref.number));
+    let tx = app.event_tx.clone();
+    tokio::task::spawn_local(async move {
+        let result = fetch_issue(&fetcher, &issue_ref).await.map(|issue| {
+            let headline = format!(
+                "Work on {} issue #{}: {}",
+                issue_ref.forge.name(),
+                issue.number,
+                issue.title
+            );
+            (headline, issue_details(&issue))
+        });
+        let _ = tx.send(ClientEvent::IssueFetched { result });
+    });
+    Ok(())
+}
+
+/// Put the fetched issue into the input after whatever is already there.
+/// Long details go in as a paste block so the input stays readable.
+pub(crate) fn insert_into_input(app: &mut App, headline: &str, details: &str) {
+    if !app.input.is_empty() {
+        // Jump clamps to the end of the last line.
+        app.input.set_cursor(usize::MAX, usize::MAX);
+        app.input.insert_str("\n\n");
+    }
+    app.input.insert_str(headline);
+    app.input.insert_str("\n\n");
+    if super::input::count_text_chars(details) > super::input::PASTE_PLACEHOLDER_CHAR_THRESHOLD {
+        app.input.insert_paste_block(details);
+    } else {
+        app.input.insert_str(details);
+    }
+}
+
+fn parse_reference(
+    reference: &str,
+    origin_url: impl FnOnce() -> Result<String, String>,
+) -> Result<IssueRef, String> {
+    let reference = reference.trim();
+    if let Some(rest) =
+        reference.strip_prefix("https://").or_else(|| reference.strip_prefix("http://"))
+    {
+        let (host, path) = rest.split_once('/').ok_or("Not an issue URL.")?;
+        let path = path.split(['?', '#']).next().unwrap_or_default().trim_end_matches('/');
+        let (forge, repo, number) = if let Some((repo, number)) = path.split_once("/-/issues/") {
+            (Forge::GitLab, repo, number)
+        } else if let Some((repo, number)) = path.split_once("/issues/") {
+            (Forge::GitHub, repo, number)
+        } else {
+            return Err(format!("Not an issue URL: {reference}"));
+        };
+        let number = number.parse().map_err(|_| format!("Not an issue URL: {reference}"))?;
+        return Ok(IssueRef { forge, host: host.to_owned(), repo: repo.to_owned(), number });
+    }
+
+    let number = reference
+        .trim_start_matches('#')
+        .parse()
+        .map_err(|_| "Usage: /issue <number|url>".to_owned())?;
+    let origin = origin_url().map_err(|_| {
+        "No origin remote to look the issue up in; pass the issue URL instead.".to_owned()
+    })?;
+    let (host, repo) = remote_host_and_path(&origin)
+        .ok_or_else(|| format!("Cannot read the origin remote URL: {origin}"))?;
+    let forge = if host.contains("gitlab") {
+        Forge::GitLab
+    } else if host.contains("github") {
+        Forge::GitHub
+    } else {
+        return Err(format!("Cannot tell which forge hosts {host}; pass the issue URL instead."));
+    };
+    Ok(IssueRef { forge, host, repo, number })
+}
+
+/// Host and repository path of `https://`, `ssh://` and scp-style remotes.
+fn remote_host_and_path(url: &str) -> Option<(String, String)> {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+    let host = rest.0.rsplit('@').next()?.split(':').next()?;
+    let path = rest.1.trim_end_matches('/').trim_end_matches(".git");
+    (!host.is_empty() && path.contains('/')).then(|| (host.to_owned(), path.to_owned()))
+}
+
+fn api_headers(forge: Forge) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static(ISSUE_USER_AGENT));
+    let token = |names: &[&str]| names.iter().find_map(|name| std::env::var(name).ok());
+    match forge {
+        Forge::GitHub => {
+            headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
+            if let Some(value) = token(&["GH_TOKEN", "GITHUB_TOKEN"])
+                .and_then(|token| HeaderValue::from_str(&format!("Bearer {token}")).ok())
+            {
+                headers.insert(AUTHORIZATION, value);
+            }
+        }
+        Forge::GitLab => {
+            if let Some(value) =
+                token(&["GITLAB_TOKEN"]).and_then(|token| HeaderValue::from_str(&token).ok())
+            {
+                headers.insert("PRIVATE-TOKEN", value);
+            }
+        }
+    }
+    headers
+}
+
+enum Fetcher {
+    Cli,
+    Http(reqwest::Client),
+}
+
+async fn fetch_issue(fetcher: &Fetcher, issue_ref: &IssueRef) -> Result<Issue, String> {
+    let [issue_endpoint, comments_endpoint] = issue_ref.endpoints();
+    let issue = fetch_json(fetcher, issue_ref, &issue_endpoint).await?;
+    let comments = fetch_json(fetcher, issue_ref, &comments_endpoint).await?;
+    Ok(match issue_ref.forge {
+        Forge::GitHub => github_issue(&issue, &comments),
+        Forge::GitLab => gitlab_issue(&issue, &comments),
+    })
+}
+
+async fn fetch_json(
+    fetcher: &Fetcher,
+    issue_ref: &IssueRef,
+    endpoint: &str,
+) -> Result<Value, String> {
+    let text = match fetcher {
+        Fetcher::Cli => {
+            let output = tokio::process::Command::new(issue_ref.forge.cli())
+                .args(["api", "--hostname", &issue_ref.host, endpoint])
+                .stdin(std::process::Stdio::null())
+                .kill_on_drop(true)
+                .output()
+                .await
+                .map_err(|err| format!("Failed to run {}: {err}", issue_ref.forge.cli()))?;
+            if !output.status.success() {
+                return Err(format!(
+                    "{} api failed: {}",
+                    issue_ref.forge.cli(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        Fetcher::Http(client) => {
+            let url = format!("{}{endpoint}", issue_ref.api_base());
+            client
+                .get(&url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|err| super::http::describe_error(&err))?
+                .text()
+                .await
+                .map_err(|err| super::http::describe_error(&err))?
+        }
+    };
+    serde_json::from_str(&text).map_err(|err| format!("Unexpected API response: {err}"))
+}
+
+fn str_at(value: &Value, pointer: &str) -> String {
+    value.pointer(pointer).and_then(Value::as_str).unwrap_or_default().to_owned()
+}
+
+fn github_issue(issue: &Value, comments: &Value) -> Issue {
+    Issue {
+        number: issue.get("number").and_then(Value::as_u64).unwrap_or_default(),
+        title: str_at(issue, "/title"),
+        url: str_at(issue, "/html_url"),
+        state: str_at(issue, "/state"),
+        author: str_at(issue, "/user/login"),
+        labels: issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|label| str_at(label, "/name"))
+            .collect(),
+        body: str_at(issue, "/body"),
+        comments: comments
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|comment| (str_at(comment, "/user/login"), str_at(comment, "/body")))
+            .collect(),
+    }
+}
+
+fn gitlab_issue(issue: &Value, notes: &Value) -> Issue {
+    Issue {
+        number: issue.get("iid").and_then(Value::as_u64).unwrap_or_default(),
+        title: str_at(issue, "/title"),
+        url: str_at(issue, "/web_url"),
+        state: str_at(issue, "/state"),
+        author: str_at(issue, "/author/username"),
+        labels: issue
+            .get("labels")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|label| label.as_str().map(str::to_owned))
+            .collect(),
+        body: str_at(issue, "/description"),
+        // System notes record label and assignee changes, not discussion.
+        comments: notes
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|note| note.get("system").and_then(Value::as_bool) != Some(true))
+            .map(|note| (str_at(note, "/author/username"), str_at(note, "/body")))
+            .collect(),
+    }
+}
+
+fn issue_details(issue: &Issue) -> String {
+    let mut out = format!("# {}\n\n{}\n", issue.title, issue.url);
+    let mut meta = vec![format!("State: {}", issue.state)];
+    if !issue.author.is_empty() {
+        meta.push(format!("Author: @{}", issue.author));
+    }
+    if !issue.labels.is_empty() {
+        meta.push(format!("Labels: {}", issue.labels.join(", ")));
+    }
+    let _ = writeln!(out, "{}", meta.join(" | "));
+    let body = issue.body.trim();
+    let _ = write!(
+        out,
+        "\n{}\n",
+        if body.is_empty() { "(no description)".to_owned() } else { clip(body, MAX_BODY_CHARS) }
+    );
+    if !issue.comments.is_empty() {
+        let skipped = issue.comments.len().saturating_sub(MAX_COMMENTS);
+        let _ = write!(out, "\n## Comments ({})\n", issue.comments.len());
+        if skipped > 0 {
+            let _ = write!(out, "\n({skipped} older comments omitted)\n");
+        }
+        for (author, body) in issue.comments.iter().skip(skipped) {
+            let _ = write!(out, "\n@{author}:\n{}\n", clip(body.trim(), MAX_COMMENT_CHARS));
+        }
+    }
+    out.trim_end().to_owned()
+}
+
+fn clip(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}\n[truncated]", &text[..cut]),
+        None => text.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn no_remote() -> Result<String, String> {
+        Err("no remote".to_owned())
+    }
+
+    #[test]
+    fn references_parse_from_urls_and_the_origin_remote() {
+        assert_eq!(
+            parse_reference("https://github.com/o/r/issues/12#issuecomment-1", no_remote),
+            Ok(IssueRef {
+                forge: Forge::GitHub,
+                host: "github.com".to_owned(),
+                repo: "o/r".to_owned(),
+                number: 12,
+            })
+        );
+        assert_eq!(
+            parse_reference("https://gitlab.example.com/g/sub/p/-/issues/7", no_remote)
+                .map(|r| (r.forge, r.repo, r.number)),
+            Ok((Forge::GitLab, "g/sub/p".to_owned(), 7))
+        );
+        assert_eq!(
+            parse_reference("#5", || Ok("git@github.com:o/r.git".to_owned()))
+                .map(|r| (r.host, r.repo, r.number)),
+            Ok(("github.com".to_owned(), "o/r".to_owned(), 5))
+        );
+        assert_eq!(
+            parse_reference("9", || Ok("ssh://git@gitlab.com:2222/g/p.git".to_owned()))
+                .map(|r| (r.forge, r.host, r.repo)),
+            Ok((Forge::GitLab, "gitlab.com".to_owned(), "g/p".to_owned()))
+        );
+        assert!(parse_reference("5", no_remote).is_err());
+        assert!(parse_reference("https://github.com/o/r/pulls", no_remote).is_err());
+    }
+
+    #[test]
+    fn github_issue_becomes_a_structured_prompt() {
+        let issue = json!({
+            "number": 12,
+            "title": "Scroll jumps",
+            "html_url": "https://github.com/o/r/issues/12",
+            "state": "open",
+            "user": { "login": "alice" },
+            "labels": [{ "name": "bug" }, { "name": "ui" }],
+            "body": "It jumps.\n"
+        });
+        let comments = json!([{ "user": { "login": "bob" }, "body": "Same here." }]);
+
+        assert_eq!(
+            issue_details(&github_issue(&issue, &comments)),
+            "# Scroll jumps\n\nhttps://github.com/o/r/issues/12\n\
+             State: open | Author: @alice | Labels: bug, ui\n\nIt jumps.\n\n\
+             ## Comments (1)\n\n@bob:\nSame here."
+        );
+    }
+
+    #[test]
+    fn gitlab_system_notes_are_skipped() {
+        let notes = json!([
+            { "author": { "username": "bot" }, "body": "added ~bug label", "system": true },
+            { "author": { "username": "carol" }, "body": "Repro attached", "system": false }
+        ]);
+        let issue = gitlab_issue(&json!({ "iid": 3, "labels": ["bug"] }), &notes);
+
+        assert_eq!(issue.number, 3);
+        assert_eq!(issue.labels, vec!["bug".to_owned()]);
+        assert_eq!(issue.comments, vec![("carol".to_owned(), "Repro attached".to_owned())]);
+    }
+}
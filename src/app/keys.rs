@@ -7,13 +7,22 @@ use super::{
     App, AppStatus, CancelOrigin, FocusOwner, FocusTarget, HelpView, InvalidationLevel,
     MessageBlock, ModeInfo, ModeState,
 };
+use crate::app::annotations::AnnotationKind;
+use crate::app::confirm::request_quit;
 use crate::app::inline_interactions::handle_inline_interaction_key;
-use crate::app::selection::clear_selection;
-use crate::app::{mention, slash, subagent};
+use crate::app::selection::{
+    VisualMotion, clear_selection, enter_chat_visual_mode, move_chat_visual_cursor,
+    select_chat_visual_row, toggle_chat_visual_anchor,
+};
+use crate::app::toast::{ToastKind, show_toast};
+use crate::app::{emoji, mention, slash, subagent};
+use crate::ui::width;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+/// How long after a first Ctrl+C a second one quits.
+const CTRL_C_QUIT_WINDOW: Duration = Duration::from_secs(2);
 const HELP_TAB_PREV_KEY: KeyCode = KeyCode::Left;
 const HELP_TAB_NEXT_KEY: KeyCode = KeyCode::Right;
 
@@ -34,7 +43,7 @@ fn is_permission_ctrl_shortcut(key: KeyEvent) -> bool {
 
 fn handle_always_allowed_shortcuts(app: &mut App, key: KeyEvent) -> bool {
     if is_ctrl_char_shortcut(key, 'q') {
-        app.should_quit = true;
+        request_quit(app);
         return true;
     }
     if is_ctrl_char_shortcut(key, 'c') {
@@ -42,13 +51,24 @@ fn handle_always_allowed_shortcuts(app: &mut App, key: KeyEvent) -> bool {
             clear_selection(app);
             return true;
         }
-        app.should_quit = true;
+        handle_ctrl_c_quit(app, Instant::now());
         return true;
     }
     false
 }
 
-fn copy_selection_to_clipboard(app: &App) -> bool {
+/// A stray Ctrl+C only arms quitting; a second press within the window quits.
+fn handle_ctrl_c_quit(app: &mut App, now: Instant) {
+    if app.ctrl_c_armed_at.is_some_and(|armed| now.duration_since(armed) <= CTRL_C_QUIT_WINDOW) {
+        app.ctrl_c_armed_at = None;
+        app.should_quit = true;
+        return;
+    }
+    app.ctrl_c_armed_at = Some(now);
+    show_toast(app, ToastKind::Info, "Press Ctrl+C again to quit");
+}
+
+fn copy_selection_to_clipboard(app: &mut App) -> bool {
     let Some(selection) = app.selection else {
         return false;
     };
@@ -56,8 +76,12 @@ fn copy_selection_to_clipboard(app: &App) -> bool {
     if selected_text.is_empty() {
         return false;
     }
-    if let Ok(mut clipboard) = arboard::Clipboard::new() {
-        let _ = clipboard.set_text(selected_text);
+    match super::clipboard::copy_text(app.config.clipboard_mode_effective(), &selected_text) {
+        Ok(_) => show_toast(app, ToastKind::Success, "Copied to clipboard"),
+        Err(error) => {
+            tracing::warn!(%error, "failed to copy selection");
+            show_toast(app, ToastKind::Error, error);
+        }
     }
     true
 }
@@ -81,8 +105,8 @@ fn selection_text_from_rendered_lines(app: &App, selection: super::SelectionStat
     for row in start.row..=last_row {
         let line = lines.get(row).map_or("", String::as_str);
         let start_col = if row == start.row { start.col } else { 0 };
-        let end_col = if row == end.row { end.col } else { line.chars().count() };
-        out.push_str(&slice_by_cols(line, start_col, end_col));
+        let end_col = if row == end.row { end.col } else { width::display_width(line) };
+        out.push_str(&width::slice_columns(line, start_col, end_col));
         if row < last_row {
             out.push('\n');
         }
@@ -90,26 +114,170 @@ fn selection_text_from_rendered_lines(app: &App, selection: super::SelectionStat
     out
 }
 
-fn slice_by_cols(text: &str, start_col: usize, end_col: usize) -> String {
-    if start_col >= end_col {
-        return String::new();
+/// A selection-mode key that acts on whatever sits under the cursor.
+struct CursorAction {
+    key: char,
+    /// Returns false when there is nothing to act on.
+    run: fn(&mut App) -> bool,
+    /// Leave selection mode once the action ran.
+    leaves: bool,
+    /// Toast shown when there is nothing to act on.
+    missing: &'static str,
+}
+
+const CURSOR_ACTIONS: &[CursorAction] = &[
+    CursorAction {
+        key: 'e',
+        run: super::explain::explain_at_visual_cursor,
+        leaves: true,
+        missing: "Nothing to explain here",
+    },
+    CursorAction {
+        key: 'b',
+        run: |app| super::annotations::edit_at_visual_cursor(app, AnnotationKind::Bookmark),
+        leaves: true,
+        missing: "Nothing to bookmark here",
+    },
+    CursorAction {
+        key: 'm',
+        run: |app| super::annotations::edit_at_visual_cursor(app, AnnotationKind::Note),
+        leaves: true,
+        missing: "Nothing to annotate here",
+    },
+    CursorAction {
+        key: 'p',
+        run: super::pinned_output::toggle_at_visual_cursor,
+        leaves: true,
+        missing: "No command output to pin here",
+    },
+    CursorAction {
+        key: 'c',
+        run: super::code_blocks::copy_at_visual_cursor,
+        leaves: true,
+        missing: "No code block here",
+    },
+    CursorAction {
+        key: 'w',
+        run: |app| {
+            super::code_blocks::update_view_at_visual_cursor(app, |view| view.scroll = !view.scroll)
+        },
+        leaves: false,
+        missing: "No code block here",
+    },
+    CursorAction {
+        key: 'n',
+        run: |app| {
+            super::code_blocks::update_view_at_visual_cursor(app, |view| {
+                view.line_numbers = !view.line_numbers;
+            })
+        },
+        leaves: false,
+        missing: "No code block here",
+    },
+    CursorAction {
+        key: 't',
+        run: super::task_lists::toggle_at_visual_cursor,
+        leaves: true,
+        missing: "No task list item here",
+    },
+    CursorAction {
+        key: 'x',
+        run: super::rewind::rewind_at_visual_cursor,
+        leaves: true,
+        missing: "No turn to rewind to here",
+    },
+    CursorAction {
+        key: 'z',
+        run: super::turn_folds::toggle_at_visual_cursor,
+        leaves: true,
+        missing: "No turn to fold here",
+    },
+    CursorAction {
+        key: 'o',
+        run: |app| {
+            super::diff_stats::toggle_file_at_visual_cursor(app)
+                || super::revisions::toggle_at_visual_cursor(app)
+        },
+        leaves: false,
+        missing: "Nothing to expand here",
+    },
+];
+
+/// Keyboard chat selection: vim-style motions move the cursor, `v` anchors the
+/// selection, `y`/Enter copies and Esc leaves. Ctrl/Alt chords fall through.
+fn handle_chat_visual_key(app: &mut App, key: KeyEvent) -> bool {
+    if app.chat_visual.is_none()
+        || key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return false;
     }
-    let mut out = String::new();
-    for (i, ch) in text.chars().enumerate() {
-        if i >= end_col {
-            break;
+    if key.modifiers.contains(KeyModifiers::SHIFT)
+        && matches!(key.code, KeyCode::Left | KeyCode::Right)
+    {
+        if !super::hscroll::scroll_at_visual_cursor(app, key.code == KeyCode::Right) {
+            show_toast(app, ToastKind::Info, "Nothing to scroll sideways here");
+        }
+        return true;
+    }
+    let motion = match key.code {
+        KeyCode::Char('h') | KeyCode::Left => Some(VisualMotion::Left),
+        KeyCode::Char('l') | KeyCode::Right => Some(VisualMotion::Right),
+        KeyCode::Char('k') | KeyCode::Up => Some(VisualMotion::Up),
+        KeyCode::Char('j') | KeyCode::Down => Some(VisualMotion::Down),
+        KeyCode::Char('0') | KeyCode::Home => Some(VisualMotion::LineStart),
+        KeyCode::Char('$') | KeyCode::End => Some(VisualMotion::LineEnd),
+        KeyCode::Char('g') => Some(VisualMotion::Top),
+        KeyCode::Char('G') => Some(VisualMotion::Bottom),
+        _ => None,
+    };
+    if let Some(motion) = motion {
+        move_chat_visual_cursor(app, motion);
+        return true;
+    }
+    if let KeyCode::Char(ch) = key.code
+        && let Some(action) = CURSOR_ACTIONS.iter().find(|action| action.key == ch)
+    {
+        if (action.run)(app) {
+            if action.leaves {
+                clear_selection(app);
+            }
+        } else {
+            show_toast(app, ToastKind::Info, action.missing);
+        }
+        return true;
+    }
+    match key.code {
+        KeyCode::Esc => clear_selection(app),
+        KeyCode::Char('v') => toggle_chat_visual_anchor(app),
+        KeyCode::Char(']') => super::heading_nav::jump(app, true),
+        KeyCode::Char('[') => super::heading_nav::jump(app, false),
+        KeyCode::Char('Z') => {
+            clear_selection(app);
+            super::turn_folds::toggle_previous(app);
+        }
+        KeyCode::Char('\\') => {
+            clear_selection(app);
+            super::transcript_filter::open(app);
         }
-        if i >= start_col {
-            out.push(ch);
+        KeyCode::Char('y') | KeyCode::Enter => {
+            if app.chat_visual.is_some_and(|visual| visual.anchor.is_none()) {
+                select_chat_visual_row(app);
+            }
+            copy_selection_to_clipboard(app);
+            clear_selection(app);
         }
+        _ => {}
     }
-    out
+    true
 }
 
 pub(super) fn dispatch_key_by_focus(app: &mut App, key: KeyEvent) -> bool {
     if handle_always_allowed_shortcuts(app, key) {
         return true;
     }
+    if handle_chat_visual_key(app, key) {
+        return true;
+    }
 
     if matches!(app.status, AppStatus::Connecting | AppStatus::CommandPending | AppStatus::Error)
         || app.is_compacting
@@ -146,6 +314,11 @@ fn handle_blocked_input_shortcuts(app: &mut App, key: KeyEvent) -> bool {
         return true;
     }
 
+    if is_ctrl_char_shortcut(key, 'r') && has_release_notes(app) {
+        open_release_notes(app);
+        return true;
+    }
+
     if is_ctrl_char_shortcut(key, 'h') {
         toggle_header(app);
         sync_help_focus(app);
@@ -158,6 +331,18 @@ fn handle_blocked_input_shortcuts(app: &mut App, key: KeyEvent) -> bool {
         return true;
     }
 
+    if is_ctrl_char_shortcut(key, 'd') {
+        if let Err(err) = super::config::open_diagnostics(app) {
+            tracing::warn!("failed to open diagnostics: {err}");
+        }
+        return true;
+    }
+
+    if key.code == KeyCode::F(1) {
+        super::help_overlay::open(app);
+        return true;
+    }
+
     let changed = match (key.code, key.modifiers) {
         (KeyCode::Char('?'), m) if !m.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
             if app.is_help_active() {
@@ -190,6 +375,27 @@ fn handle_blocked_input_shortcuts(app: &mut App, key: KeyEvent) -> bool {
     changed
 }
 
+/// The update hint is visible and its release has notes to show.
+pub(crate) fn has_release_notes(app: &App) -> bool {
+    app.update_check_hint.is_some()
+        && app.update_release.as_ref().is_some_and(|release| !release.notes.is_empty())
+}
+
+fn open_release_notes(app: &mut App) {
+    if let Err(err) = super::config::open_release_notes(app) {
+        tracing::warn!("failed to open release notes: {err}");
+    }
+}
+
+pub(super) fn toggle_perf_hud(app: &mut App) {
+    if !cfg!(feature = "perf") {
+        show_toast(app, ToastKind::Info, "The perf HUD needs a build with `--features perf`");
+        return;
+    }
+    crate::perf::hud::toggle();
+    app.needs_redraw = true;
+}
+
 /// Handle shortcuts that should work regardless of current focus owner.
 fn handle_global_shortcuts(app: &mut App, key: KeyEvent) -> bool {
     // Session-only dismiss for update hint.
@@ -198,6 +404,11 @@ fn handle_global_shortcuts(app: &mut App, key: KeyEvent) -> bool {
         return true;
     }
 
+    if is_ctrl_char_shortcut(key, 'r') && has_release_notes(app) {
+        open_release_notes(app);
+        return true;
+    }
+
     // Permission quick shortcuts are global when permissions are pending.
     if !app.pending_permission_ids.is_empty() && is_permission_ctrl_shortcut(key) {
         return handle_inline_interaction_key(app, key);
@@ -212,6 +423,12 @@ fn handle_global_shortcuts(app: &mut App, key: KeyEvent) -> bool {
             toggle_all_tool_calls(app);
             true
         }
+        (KeyCode::Char('b'), m) if m == KeyModifiers::CONTROL => {
+            if let Err(err) = super::config::toggle_file_browser(app) {
+                tracing::warn!("failed to open file browser: {err}");
+            }
+            true
+        }
         (KeyCode::Char('l'), m) if m == KeyModifiers::CONTROL => {
             app.force_redraw = true;
             true
@@ -220,6 +437,30 @@ fn handle_global_shortcuts(app: &mut App, key: KeyEvent) -> bool {
             toggle_header(app);
             true
         }
+        (KeyCode::Char('k'), m) if m == KeyModifiers::CONTROL => {
+            super::config::toggle_show_message_usage(app);
+            true
+        }
+        (KeyCode::Char('g'), m) if m == KeyModifiers::CONTROL => {
+            super::status_log::toggle_status_log(app);
+            true
+        }
+        (KeyCode::Char('s'), m) if m == KeyModifiers::CONTROL => {
+            clear_selection(app);
+            enter_chat_visual_mode(app)
+        }
+        (KeyCode::Char('p'), m) if m == KeyModifiers::CONTROL => {
+            super::palette::open(app);
+            true
+        }
+        (KeyCode::F(1), _) => {
+            super::help_overlay::open(app);
+            true
+        }
+        (KeyCode::F(12), KeyModifiers::NONE) => {
+            toggle_perf_hud(app);
+            true
+        }
         (KeyCode::Up, m) if m == KeyModifiers::CONTROL => {
             app.viewport.scroll_up(1);
             true
@@ -253,6 +494,7 @@ pub(super) fn handle_normal_key(app: &mut App, key: KeyEvent) -> bool {
         mention::sync_with_cursor(app);
         slash::sync_with_cursor(app);
         subagent::sync_with_cursor(app);
+        emoji::sync_with_cursor(app);
     }
 
     sync_help_focus(app);
@@ -274,6 +516,12 @@ fn is_editing_like_key(key: KeyEvent) -> bool {
 }
 
 fn handle_normal_key_actions(app: &mut App, key: KeyEvent) -> bool {
+    if super::welcome::handle_start_screen_key(app, key) {
+        return true;
+    }
+    if super::suggestions::handle_suggestion_key(app, key) {
+        return true;
+    }
     if handle_turn_control_key(app, key) {
         return true;
     }
@@ -286,12 +534,21 @@ fn handle_normal_key_actions(app: &mut App, key: KeyEvent) -> bool {
     if handle_navigation_key(app, key) {
         return true;
     }
+    if handle_pasted_paths_key(app, key) {
+        return true;
+    }
+    if handle_failed_command_key(app, key) {
+        return true;
+    }
     if handle_focus_toggle_key(app, key) {
         return true;
     }
     if handle_mode_cycle_key(app, key) {
         return true;
     }
+    if handle_filter_key(app, key) {
+        return true;
+    }
     if handle_editing_key(app, key) {
         return true;
     }
@@ -306,10 +563,12 @@ fn handle_turn_control_key(app: &mut App, key: KeyEvent) -> bool {
         app.release_focus_target(FocusTarget::TodoList);
         return true;
     }
-    if matches!(app.status, AppStatus::Thinking | AppStatus::Running)
-        && let Err(message) = super::input_submit::request_cancel(app, CancelOrigin::Manual)
-    {
-        tracing::error!("Failed to send cancel: {message}");
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running) {
+        if let Err(message) = super::input_submit::request_cancel(app, CancelOrigin::Manual) {
+            tracing::error!("Failed to send cancel: {message}");
+        }
+    } else if super::turn_retry::cancel_turn_retry(app) || app.toasts.dismiss_newest() {
+        app.needs_redraw = true;
     }
     true
 }
@@ -403,6 +662,40 @@ fn handle_navigation_key(app: &mut App, key: KeyEvent) -> bool {
     }
 }
 
+/// Tab right after pasting file paths converts them to `@` mentions.
+fn handle_pasted_paths_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.code != KeyCode::Tab
+        || key.modifiers != KeyModifiers::NONE
+        || app.focus_owner() == FocusOwner::TodoList
+    {
+        return false;
+    }
+    super::pasted_paths::accept_offer(app)
+}
+
+fn handle_failed_command_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.code != KeyCode::Tab
+        || key.modifiers != KeyModifiers::NONE
+        || app.focus_owner() == FocusOwner::TodoList
+    {
+        return false;
+    }
+    super::test_run::accept_offer(app)
+}
+
+/// `\\` on an empty input opens the transcript filter bar.
+fn handle_filter_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.code != KeyCode::Char('\\')
+        || key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        || !app.input.is_empty()
+        || app.focus_owner() == FocusOwner::TodoList
+    {
+        return false;
+    }
+    super::transcript_filter::open(app);
+    true
+}
+
 fn handle_focus_toggle_key(app: &mut App, key: KeyEvent) -> bool {
     match (key.code, key.modifiers) {
         (KeyCode::Tab, m)
@@ -427,6 +720,22 @@ fn handle_mode_cycle_key(app: &mut App, key: KeyEvent) -> bool {
     if !matches!(key.code, KeyCode::BackTab) {
         return false;
     }
+    if app.read_only {
+        super::events::push_system_message_with_severity(
+            app,
+            Some(super::SystemSeverity::Info),
+            "Read-only mode: the permission mode stays on plan.",
+        );
+        return true;
+    }
+    if !app.agent_supports(|caps| caps.supports_modes) {
+        super::events::push_system_message_with_severity(
+            app,
+            Some(super::SystemSeverity::Info),
+            "The connected agent does not support permission modes.",
+        );
+        return true;
+    }
     let Some(ref mode) = app.mode else {
         return true;
     };
@@ -639,6 +948,9 @@ pub(super) fn handle_autocomplete_key(app: &mut App, key: KeyEvent) -> bool {
     if app.subagent.is_some() {
         return handle_subagent_key(app, key);
     }
+    if app.emoji.is_some() {
+        return handle_emoji_key(app, key);
+    }
     dispatch_key_by_focus(app, key)
 }
 
@@ -700,6 +1012,7 @@ fn sync_help_focus(app: &mut App) {
         && app.mention.is_none()
         && app.slash.is_none()
         && app.subagent.is_none()
+        && app.emoji.is_none()
     {
         app.claim_focus_target(FocusTarget::Help);
     } else {
@@ -718,7 +1031,13 @@ pub(super) fn handle_mention_key(app: &mut App, key: KeyEvent) -> bool {
             mention::move_down(app);
             true
         }
-        (KeyCode::Enter | KeyCode::Tab, _) => {
+        (KeyCode::Tab, _) => {
+            if !mention::toggle_mark(app) {
+                mention::confirm_selection(app);
+            }
+            true
+        }
+        (KeyCode::Enter, _) => {
             mention::confirm_selection(app);
             true
         }
@@ -820,17 +1139,68 @@ fn handle_subagent_key(app: &mut App, key: KeyEvent) -> bool {
     }
 }
 
-/// Toggle the session-level collapsed preference and apply to all tool calls.
+/// Handle keystrokes while `:` emoji autocomplete dropdown is active.
+fn handle_emoji_key(app: &mut App, key: KeyEvent) -> bool {
+    match (key.code, key.modifiers) {
+        (KeyCode::Up, _) => {
+            emoji::move_up(app);
+            true
+        }
+        (KeyCode::Down, _) => {
+            emoji::move_down(app);
+            true
+        }
+        (KeyCode::Enter | KeyCode::Tab, _) => {
+            emoji::confirm_selection(app);
+            true
+        }
+        (KeyCode::Esc, _) => {
+            emoji::deactivate(app);
+            true
+        }
+        (KeyCode::Backspace, _) => {
+            let changed = app.input.textarea_delete_char_before();
+            emoji::update_query(app);
+            changed
+        }
+        (KeyCode::Char(':'), m) if is_printable_text_modifiers(m) => emoji::close_with_colon(app),
+        (KeyCode::Char(c), m) if is_printable_text_modifiers(m) => {
+            let changed = app.input.textarea_insert_char(c);
+            emoji::update_query(app);
+            changed
+        }
+        _ => {
+            emoji::deactivate(app);
+            dispatch_key_by_focus(app, key)
+        }
+    }
+}
+
+/// Toggle the session-level collapsed preference and apply to all tool calls,
+/// thinking blocks and per-turn diff summaries.
 pub(super) fn toggle_all_tool_calls(app: &mut App) {
     app.tools_collapsed = !app.tools_collapsed;
     for msg in &mut app.messages {
         for block in &mut msg.blocks {
-            if let MessageBlock::ToolCall(tc) = block {
-                let tc = tc.as_mut();
-                if tc.collapsed != app.tools_collapsed {
-                    tc.collapsed = app.tools_collapsed;
-                    tc.mark_tool_call_layout_dirty();
+            match block {
+                MessageBlock::ToolCall(tc) => {
+                    let tc = tc.as_mut();
+                    if tc.collapsed != app.tools_collapsed {
+                        tc.collapsed = app.tools_collapsed;
+                        tc.mark_tool_call_layout_dirty();
+                    }
+                }
+                MessageBlock::Thinking(thinking) if thinking.expanded == app.tools_collapsed => {
+                    thinking.expanded = !app.tools_collapsed;
+                    thinking.cache.invalidate();
+                }
+                MessageBlock::DiffSummary(summary) => {
+                    for file in &mut summary.files {
+                        file.expanded = !app.tools_collapsed;
+                    }
+                    summary.cache.invalidate();
                 }
+                _ => {}
             }
         }
     }
@@ -0,0 +1,346 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! User hooks on turn lifecycle events, configured under `lifecycleHooks` in
+//! `~/.claude.json`:
+//!
+//! ```json
+//! "lifecycleHooks": [
+//!   { "event": "turnFinish", "command": "notify-send 'Done in {{duration_ms}} ms'" },
+//!   { "event": "fileEdited", "command": "prettier --write {{file}}" },
+//!   { "event": "error", "url": "https://example.com/hook", "body": { "text": "{{error}}" } }
+//! ]
+//! ```
+//!
+//! Events are `turnStart`, `turnFinish`, `fileEdited` and `error`. `{{name}}`
+//! placeholders expand to the event's variables: `event`, `session_id` and
+//! `cwd` always, plus `prompt` on turn start, `duration_ms` and `outcome` on
+//! turn finish, `file` on file edits and `error` on errors. In commands the
+//! values are shell-quoted and also exported as `CLAUDE_RS_HOOK_<NAME>`. A
+//! `url` hook POSTs its `body`: a JSON body has placeholders expanded inside
+//! its strings, a string body is sent as plain text, and without a body the
+//! variables go out as one JSON object.
+//!
+//! Hooks run in the background and never hold up the turn; failures are only
+//! logged. The key is separate from Claude Code's own `hooks` setting.
+
+use super::App;
+use super::changed_files;
+use crate::agent::model;
+use serde_json::{Map, Value};
+use std::time::{Duration, Instant};
+
+const CONFIG_KEY: &str = "lifecycleHooks";
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+const ENV_PREFIX: &str = "CLAUDE_RS_HOOK_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    TurnStart,
+    TurnFinish,
+    FileEdited,
+    Error,
+}
+
+impl HookEvent {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::TurnStart => "turnStart",
+            Self::TurnFinish => "turnFinish",
+            Self::FileEdited => "fileEdited",
+            Self::Error => "error",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        [Self::TurnStart, Self::TurnFinish, Self::FileEdited, Self::Error]
+            .into_iter()
+            .find(|event| event.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HookAction {
+    Command(String),
+    Post { url: String, body: Option<Value> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct LifecycleHook {
+    event: HookEvent,
+    action: HookAction,
+}
+
+/// Hooks configured in the preferences document. Malformed entries are
+/// skipped with a warning so one typo does not disable the rest.
+fn configured_hooks(preferences: &Value) -> Vec<LifecycleHook> {
+    let Some(entries) = preferences.get(CONFIG_KEY).and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let hook = parse_hook(entry);
+            if hook.is_none() {
+                tracing::warn!("ignoring malformed {CONFIG_KEY} entry: {entry}");
+            }
+            hook
+        })
+        .collect()
+}
+
+fn parse_hook(entry: &Value) -> Option<LifecycleHook> {
+    let event = HookEvent::parse(entry.get("event")?.as_str()?)?;
+    let action = if let Some(command) = entry.get("command").and_then(Value::as_str) {
+        HookAction::Command(command.to_owned())
+    } else {
+        let url = entry.get("url")?.as_str()?.to_owned();
+        HookAction::Post { url, body: entry.get("body").cloned() }
+    };
+    Some(LifecycleHook { event, action })
+}
+
+/// Variables of one event, in a stable order.
+type Vars = Vec<(&'static str, String)>;
+
+/// A user prompt went out to the agent.
+pub(crate) fn turn_started(app: &mut App, prompt: &str) {
+    app.turn_started_at = Some(Instant::now());
+    fire(app, HookEvent::TurnStart, vec![("prompt", prompt.to_owned())]);
+}
+
+/// The agent finished or the user cancelled the turn.
+pub(crate) fn turn_finished(app: &mut App, cancelled: bool) {
+    let duration_ms = app
+        .turn_started_at
+        .take()
+        .map_or(0, |start| u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX));
+    let outcome = if cancelled { "cancelled" } else { "completed" };
+    fire(
+        app,
+        HookEvent::TurnFinish,
+        vec![("duration_ms", duration_ms.to_string()), ("outcome", outcome.to_owned())],
+    );
+}
+
+/// The turn ended with an error shown to the user.
+pub(crate) fn turn_failed(app: &mut App, error: &str) {
+    app.turn_started_at = None;
+    fire(app, HookEvent::Error, vec![("error", error.to_owned())]);
+}
+
+/// An edit tool call completed; fires once per file it names.
+pub(crate) fn tool_call_completed(app: &App, tool_call_id: &str) {
+    let Some((mi, bi)) = app.lookup_tool_call(tool_call_id) else {
+        return;
+    };
+    let Some(super::MessageBlock::ToolCall(tc)) =
+        app.messages.get(mi).and_then(|m| m.blocks.get(bi))
+    else {
+        return;
+    };
+    if tc.status != model::ToolCallStatus::Completed {
+        return;
+    }
+    let mut paths = changed_files::edited_paths(tc, &app.cwd_raw);
+    paths.dedup();
+    for path in paths {
+        fire(app, HookEvent::FileEdited, vec![("file", path.to_string_lossy().into_owned())]);
+    }
+}
+
+fn fire(app: &App, event: HookEvent, extra: Vars) {
+    let hooks: Vec<_> = configured_hooks(&app.config.committed_preferences_document)
+        .into_iter()
+        .filter(|hook| hook.event == event)
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+    let mut vars: Vars = vec![
+        ("event", event.name().to_owned()),
+        ("session_id", app.session_id.as_ref().map(ToString::to_string).unwrap_or_default()),
+        ("cwd", app.cwd_raw.clone()),
+    ];
+    vars.extend(extra);
+    for hook in hooks {
+        match hook.action {
+            HookAction::Command(command) => {
+                spawn_command(expand(&command, &vars, shell_quote), &vars, &app.cwd_raw);
+            }
+            HookAction::Post { .. } if app.is_offline() => {
+                tracing::debug!("Skipping {} webhook (offline mode)", event.name());
+            }
+            HookAction::Post { url, body } => spawn_post(app, url, body.as_ref(), &vars),
+        }
+    }
+}
+
+fn spawn_command(command: String, vars: &Vars, cwd: &str) {
+    let mut process = shell(&command);
+    process
+        .current_dir(cwd)
+        .envs(vars.iter().map(|(name, value)| {
+            (format!("{ENV_PREFIX}{}", name.to_ascii_uppercase()), value.as_str())
+        }))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    tokio::task::spawn_local(async move {
+        match tokio::time::timeout(HOOK_TIMEOUT, process.output()).await {
+            Ok(Ok(output)) if output.status.success() => {}
+            Ok(Ok(output)) => tracing::warn!(
+                command = %command,
+                status = %output.status,
+                stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+                "lifecycle hook command failed"
+            ),
+            Ok(Err(err)) => {
+                tracing::warn!(command = %command, "cannot run lifecycle hook: {err}");
+            }
+            Err(_) => tracing::warn!(command = %command, "lifecycle hook timed out"),
+        }
+    });
+}
+
+#[cfg(windows)]
+pub(super) fn shell(command: &str) -> tokio::process::Command {
+    let mut process = tokio::process::Command::new("cmd");
+    process.arg("/C").arg(command);
+    process
+}
+
+#[cfg(not(windows))]
+pub(super) fn shell(command: &str) -> tokio::process::Command {
+    let mut process = tokio::process::Command::new("sh");
+    process.arg("-c").arg(command);
+    process
+}
+
+fn spawn_post(app: &App, url: String, body: Option<&Value>, vars: &Vars) {
+    let client = match app.http.client_builder(HOOK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            tracing::warn!("cannot build webhook client: {}", super::http::describe_error(&err));
+            return;
+        }
+    };
+    let request = match body {
+        Some(Value::String(text)) => client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(expand(text, vars, str::to_owned)),
+        Some(body) => client.post(&url).json(&expand_json(body, vars)),
+        None => client.post(&url).json(&vars_object(vars)),
+    };
+    tokio::task::spawn_local(async move {
+        if let Err(err) = request.send().await.and_then(reqwest::Response::error_for_status) {
+            tracing::warn!(url = %url, "lifecycle webhook failed: {}", super::http::describe_error(&err));
+        }
+    });
+}
+
+/// Replace `{{name}}` placeholders, passing each value through `escape`.
+/// Unknown names expand to nothing.
+fn expand(template: &str, vars: &Vars, escape: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        if let Some((_, value)) = vars.iter().find(|(var, _)| *var == name) {
+            out.push_str(&escape(value));
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn expand_json(template: &Value, vars: &Vars) -> Value {
+    match template {
+        Value::String(text) => Value::String(expand(text, vars, str::to_owned)),
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|item| expand_json(item, vars)).collect())
+        }
+        Value::Object(fields) => Value::Object(
+            fields.iter().map(|(key, value)| (key.clone(), expand_json(value, vars))).collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn vars_object(vars: &Vars) -> Value {
+    Value::Object(
+        vars.iter()
+            .map(|(name, value)| ((*name).to_owned(), Value::String(value.clone())))
+            .collect::<Map<_, _>>(),
+    )
+}
+
+#[cfg(windows)]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+#[cfg(not(windows))]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn config_parses_commands_and_webhooks_and_skips_bad_entries() {
+        let preferences = json!({
+            "lifecycleHooks": [
+                { "event": "turnFinish", "command": "say done" },
+                { "event": "error", "url": "https://example.com", "body": { "t": "{{error}}" } },
+                { "event": "turnEnd", "command": "nope" },
+                { "event": "fileEdited" }
+            ]
+        });
+
+        let hooks = configured_hooks(&preferences);
+
+        assert_eq!(
+            hooks,
+            vec![
+                LifecycleHook {
+                    event: HookEvent::TurnFinish,
+                    action: HookAction::Command("say done".to_owned()),
+                },
+                LifecycleHook {
+                    event: HookEvent::Error,
+                    action: HookAction::Post {
+                        url: "https://example.com".to_owned(),
+                        body: Some(json!({ "t": "{{error}}" })),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn templates_expand_known_variables_with_escaping() {
+        let vars: Vars =
+            vec![("file", "it's.rs".to_owned()), ("error", "bad \"quote\"".to_owned())];
+
+        assert_eq!(expand("fmt {{ file }} {{missing}}!", &vars, str::to_owned), "fmt it's.rs !");
+        assert_eq!(expand("unclosed {{file", &vars, str::to_owned), "unclosed {{file");
+        #[cfg(not(windows))]
+        assert_eq!(expand("fmt {{file}}", &vars, shell_quote), r"fmt 'it'\''s.rs'");
+        assert_eq!(
+            expand_json(&json!({ "text": "Error: {{error}}", "n": [1, "{{file}}"] }), &vars),
+            json!({ "text": "Error: bad \"quote\"", "n": [1, "it's.rs"] })
+        );
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Single-line text field for the small modal editors over the chat (message
+//! notes, bookmark names, the transcript filter bar).
+
+use super::char_to_byte_index;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineInput {
+    pub text: String,
+    /// Cursor position in characters.
+    pub cursor: usize,
+}
+
+impl LineInput {
+    /// A field holding `text` with the cursor at its end.
+    #[must_use]
+    pub fn new(text: String) -> Self {
+        let cursor = text.chars().count();
+        Self { text, cursor }
+    }
+
+    /// Apply an editing key. Returns false for keys the field does not use,
+    /// such as Enter and Esc.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('u') {
+            self.text.clear();
+            self.cursor = 0;
+            return true;
+        }
+        if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            return false;
+        }
+        let len = self.text.chars().count();
+        match key.code {
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    let start = char_to_byte_index(&self.text, self.cursor - 1);
+                    let end = char_to_byte_index(&self.text, self.cursor);
+                    self.text.replace_range(start..end, "");
+                    self.cursor -= 1;
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < len {
+                    let start = char_to_byte_index(&self.text, self.cursor);
+                    let end = char_to_byte_index(&self.text, self.cursor + 1);
+                    self.text.replace_range(start..end, "");
+                }
+            }
+            KeyCode::Char(ch) => {
+                let at = char_to_byte_index(&self.text, self.cursor);
+                self.text.insert(at, ch);
+                self.cursor += 1;
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn press(input: &mut LineInput, code: KeyCode) {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn edits_at_the_cursor_over_multibyte_text() {
+        let mut input = LineInput::new("héllo".to_owned());
+        press(&mut input, KeyCode::Home);
+        press(&mut input, KeyCode::Right);
+        press(&mut input, KeyCode::Delete);
+        press(&mut input, KeyCode::Char('e'));
+        press(&mut input, KeyCode::End);
+        press(&mut input, KeyCode::Backspace);
+
+        assert_eq!(input, LineInput { text: "hell".to_owned(), cursor: 4 });
+        assert!(!input.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+    }
+}
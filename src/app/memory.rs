@@ -0,0 +1,218 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `CLAUDE.md` memory files: discovery for the welcome screen and `/memory`,
+//! which opens one of them in the user's editor.
+
+use super::{App, InvalidationLevel, MessageBlock, MessageRole};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const MEMORY_FILE: &str = "CLAUDE.md";
+const LOCAL_MEMORY_FILE: &str = "CLAUDE.local.md";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MemoryScope {
+    /// `CLAUDE.md` in the project root (or `.claude/CLAUDE.md` when only that exists).
+    Project,
+    /// `~/.claude/CLAUDE.md`, loaded for every project.
+    User,
+}
+
+fn user_memory_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".claude").join(MEMORY_FILE))
+}
+
+pub(crate) fn memory_path(scope: MemoryScope, cwd: &Path) -> Option<PathBuf> {
+    match scope {
+        MemoryScope::User => user_memory_path(),
+        MemoryScope::Project => {
+            let root = cwd.join(MEMORY_FILE);
+            let nested = cwd.join(".claude").join(MEMORY_FILE);
+            Some(if !root.exists() && nested.exists() { nested } else { root })
+        }
+    }
+}
+
+/// Memory files the agent loads for a session in `cwd`: the user file, then
+/// `CLAUDE.md`, `.claude/CLAUDE.md` and `CLAUDE.local.md` from the filesystem
+/// root down to `cwd`.
+pub(crate) fn loaded_memory_files(cwd: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = user_memory_path().into_iter().collect();
+    let mut ancestors: Vec<&Path> = cwd.ancestors().collect();
+    ancestors.reverse();
+    for dir in ancestors {
+        files.extend([
+            dir.join(MEMORY_FILE),
+            dir.join(".claude").join(MEMORY_FILE),
+            dir.join(LOCAL_MEMORY_FILE),
+        ]);
+    }
+    let mut seen = HashSet::new();
+    files.retain(|path| path.is_file() && seen.insert(path.clone()));
+    files
+}
+
+/// Short label for a memory file: relative to `cwd` when inside it, `~/...`
+/// under the home directory, absolute otherwise.
+fn display_path(path: &Path, cwd: &Path) -> String {
+    if let Ok(relative) = path.strip_prefix(cwd) {
+        return relative.to_string_lossy().into_owned();
+    }
+    if let Some(home) = dirs::home_dir()
+        && let Ok(relative) = path.strip_prefix(&home)
+    {
+        return format!("~/{}", relative.to_string_lossy());
+    }
+    path.to_string_lossy().into_owned()
+}
+
+/// Refresh the memory indicator on the welcome banner.
+pub(crate) fn sync_welcome_memory_files(app: &mut App) {
+    let cwd = PathBuf::from(&app.cwd_raw);
+    let files: Vec<String> =
+        loaded_memory_files(&cwd).iter().map(|path| display_path(path, &cwd)).collect();
+    let Some(first) = app.messages.first_mut() else {
+        return;
+    };
+    if !matches!(first.role, MessageRole::Welcome) {
+        return;
+    }
+    let Some(MessageBlock::Welcome(welcome)) = first.blocks.first_mut() else {
+        return;
+    };
+    if welcome.memory_files == files {
+        return;
+    }
+    welcome.memory_files = files;
+    welcome.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::From(0));
+}
+
+/// Refresh the MCP server list on the welcome banner.
+pub(crate) fn sync_welcome_mcp_servers(app: &mut App) {
+    let names: Vec<String> = app.mcp.servers.iter().map(|server| server.name.clone()).collect();
+    let Some(first) = app.messages.first_mut() else {
+        return;
+    };
+    if !matches!(first.role, MessageRole::Welcome) {
+        return;
+    }
+    let Some(MessageBlock::Welcome(welcome)) = first.blocks.first_mut() else {
+        return;
+    };
+    if welcome.mcp_servers == names {
+        return;
+    }
+    welcome.mcp_servers = names;
+    welcome.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::From(0));
+}
+
+/// `$VISUAL`, then `$EDITOR`, then the platform default, split into program
+/// and arguments so values like `code --wait` work.
+fn editor_command(env: impl Fn(&str) -> Option<String>) -> (String, Vec<String>) {
+    let configured = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(env)
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_owned());
+    let mut parts = configured.split_whitespace().map(str::to_owned);
+    let program = parts.next().unwrap_or_default();
+    (program, parts.collect())
+}
+
+/// Open `path` in the user's editor, suspending the TUI until the editor exits.
+pub(crate) fn open_in_editor(app: &mut App, path: &Path) -> Result<(), String> {
+    let (program, args) = editor_command(|name| std::env::var(name).ok());
+    tracing::debug!(%program, path = %path.display(), "Opening file in editor");
+
+    super::suspend_terminal();
+    let status = std::process::Command::new(&program).args(&args).arg(path).status();
+    super::resume_terminal();
+    app.force_redraw = true;
+
+    let status = status.map_err(|err| format!("Failed to run editor `{program}`: {err}"))?;
+    if !status.success() {
+        return Err(format!(
+            "Editor `{program}` exited with code {}",
+            status.code().map_or("unknown".to_owned(), |code| code.to_string())
+        ));
+    }
+    Ok(())
+}
+
+/// Open the memory file for `scope` in the user's editor, suspending the TUI
+/// until the editor exits.
+pub(crate) fn edit(app: &mut App, scope: MemoryScope) -> Result<String, String> {
+    let cwd = PathBuf::from(&app.cwd_raw);
+    let path = memory_path(scope, &cwd)
+        .ok_or_else(|| "Cannot locate the home directory for user memory".to_owned())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    open_in_editor(app, &path)?;
+    sync_welcome_memory_files(app);
+    let label = display_path(&path, &cwd);
+    if path.is_file() {
+        Ok(format!(
+            "Saved {label}. Memory is read when a session starts; use /new-session to apply it."
+        ))
+    } else {
+        Ok(format!("{label} was not created."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn loaded_memory_files_walks_from_root_to_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let project = dir.path().join("repo");
+        let nested = project.join("crates").join("app");
+        std::fs::create_dir_all(nested.join(".claude")).expect("create dirs");
+        std::fs::write(project.join(MEMORY_FILE), "root").expect("write");
+        std::fs::write(nested.join(".claude").join(MEMORY_FILE), "nested").expect("write");
+        std::fs::write(nested.join(LOCAL_MEMORY_FILE), "local").expect("write");
+
+        let files: Vec<_> = loaded_memory_files(&nested)
+            .into_iter()
+            .filter(|path| path.starts_with(dir.path()))
+            .collect();
+
+        assert_eq!(
+            files,
+            vec![
+                project.join(MEMORY_FILE),
+                nested.join(".claude").join(MEMORY_FILE),
+                nested.join(LOCAL_MEMORY_FILE),
+            ]
+        );
+        assert_eq!(
+            display_path(&files[1], &nested),
+            Path::new(".claude").join(MEMORY_FILE).to_string_lossy()
+        );
+        assert_eq!(
+            memory_path(MemoryScope::Project, &nested),
+            Some(nested.join(".claude").join(MEMORY_FILE))
+        );
+        assert_eq!(memory_path(MemoryScope::Project, &project), Some(project.join(MEMORY_FILE)));
+    }
+
+    #[test]
+    fn editor_command_prefers_visual_and_splits_arguments() {
+        let env = |name: &str| match name {
+            "VISUAL" => Some("  ".to_owned()),
+            "EDITOR" => Some("code --wait".to_owned()),
+            _ => None,
+        };
+        assert_eq!(editor_command(env), ("code".to_owned(), vec!["--wait".to_owned()]));
+        let (program, args) = editor_command(|_| None);
+        assert!(!program.is_empty());
+        assert!(args.is_empty());
+    }
+}
@@ -1,22 +1,33 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+use super::attachments::{self, MentionFileKind};
+use super::file_index::FileIndex;
+use super::fuzzy::{FuzzyMatch, fuzzy_match};
+use super::symbols::{self, Symbol};
 use super::{App, FocusTarget, dialog::DialogState};
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use std::sync::mpsc as std_mpsc;
-use std::time::{Instant, SystemTime};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 /// Maximum candidates shown in the dropdown.
 pub const MAX_VISIBLE: usize = 8;
 
 /// Maximum total candidates kept after filtering.
 const MAX_CANDIDATES: usize = 50;
-/// Minimum query length before scanning the filesystem for matches.
+/// Minimum query length before filtering the file index for matches.
 pub const MIN_QUERY_CHARS: usize = 1;
-/// Maximum walker entries drained from the channel per tick.
-const DRAIN_BUDGET: usize = 500;
+/// Maximum files remembered as recently touched or mentioned.
+const MAX_RECENT_FILES: usize = 32;
+/// Maximum files inserted when expanding a directory mention.
+const MAX_EXPANDED_FILES: usize = 20;
+/// Files modified on disk within this window count as recent.
+const RECENT_MODIFIED_WINDOW: Duration = Duration::from_secs(15 * 60);
+/// Lines of the highlighted file shown in the preview pane.
+pub const PREVIEW_LINES: usize = 15;
+/// Preview lines are cut at this many characters; the pane is narrower anyway.
+const PREVIEW_MAX_LINE_CHARS: usize = 200;
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -33,10 +44,97 @@ pub struct MentionState {
     /// Shared autocomplete dialog navigation state.
     pub dialog: DialogState,
     search_status: MentionSearchStatus,
-    /// Cached file walker — persists across query edits.
-    file_walker: Option<FileWalker>,
-    /// Cache key: (cwd, `respect_gitignore`) that produced the walker.
-    walker_cache_key: Option<(String, bool)>,
+    /// Number of entries indexed so far, shown while the initial scan runs.
+    indexed_count: usize,
+    /// File whose symbols are being completed, for queries like `src/main.rs#ma`.
+    /// While set, `symbols` replaces `candidates` in the dropdown.
+    pub symbol_file: Option<String>,
+    /// Filtered + sorted symbol candidates for `symbol_file`.
+    pub symbols: Vec<Symbol>,
+    /// All symbols of the last scanned file, so typing after `#` does not re-read it.
+    symbol_cache: Option<(String, Vec<Symbol>)>,
+    /// Paths among `candidates` that were ranked up for recency (dropdown marker).
+    recent: HashSet<String>,
+    /// Recursive file counts for directory candidates, keyed by `rel_path`.
+    dir_file_counts: HashMap<String, usize>,
+    /// Candidates marked with Tab, in marking order. Enter inserts all of them.
+    /// Marks survive query edits so files can be gathered across searches.
+    pub marked: Vec<String>,
+    /// Footer notice for the highlighted file (image, binary, or large file).
+    selected_notice: Option<String>,
+    /// First lines of the highlighted text file, shown beside the dropdown.
+    preview: Option<MentionPreview>,
+    /// Pending "attach or expand" choice after selecting a directory.
+    /// While set, `directory_actions()` replaces `candidates` in the dropdown.
+    pub dir_choice: Option<DirectoryChoice>,
+}
+
+/// Head of the highlighted file for the preview pane.
+pub struct MentionPreview {
+    pub rel_path: String,
+    pub lines: Vec<String>,
+}
+
+/// What to insert for a selected directory mention.
+pub struct DirectoryChoice {
+    /// Directory path with trailing `/` (e.g. "src/").
+    pub dir: String,
+    /// Files directly inside `dir`, capped at `MAX_EXPANDED_FILES`.
+    pub top_level_files: Vec<String>,
+    /// Number of files directly inside `dir` before capping.
+    pub top_level_total: usize,
+    /// Number of files anywhere below `dir`.
+    pub total_files: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirectoryAction {
+    /// Insert `@dir/` as a single reference.
+    Attach,
+    /// Insert one `@file` mention per top-level file.
+    Expand,
+}
+
+impl DirectoryChoice {
+    #[must_use]
+    pub fn actions(&self) -> &'static [DirectoryAction] {
+        if self.top_level_files.is_empty() {
+            &[DirectoryAction::Attach]
+        } else {
+            &[DirectoryAction::Attach, DirectoryAction::Expand]
+        }
+    }
+}
+
+/// Files recently touched by tool calls or inserted as mentions, most recent first.
+/// Paths are relative to the session cwd, without a trailing `/`.
+#[derive(Default)]
+pub struct RecentFiles {
+    paths: VecDeque<String>,
+}
+
+impl RecentFiles {
+    pub fn touch(&mut self, rel_path: &str) {
+        let rel_path = rel_path.trim_end_matches('/');
+        if rel_path.is_empty() {
+            return;
+        }
+        self.paths.retain(|path| path != rel_path);
+        self.paths.push_front(rel_path.to_owned());
+        self.paths.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Remembered paths, most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.paths.iter().map(String::as_str)
+    }
+
+    /// Position in the recency list (0 = most recent).
+    #[must_use]
+    pub fn position(&self, rel_path: &str) -> Option<usize> {
+        let rel_path = rel_path.trim_end_matches('/');
+        self.paths.iter().position(|path| path == rel_path)
+    }
 }
 
 #[derive(Clone)]
@@ -44,10 +142,6 @@ pub struct FileCandidate {
     /// Relative path from cwd (forward slashes, e.g. "src/main.rs").
     /// Directories have a trailing `/` (e.g. "src/").
     pub rel_path: String,
-    /// Pre-computed lowercase of `rel_path`.
-    rel_path_lower: String,
-    /// Pre-computed lowercase of the basename portion.
-    basename_lower: String,
     /// Depth (number of `/` separators) for grouping.
     pub depth: usize,
     /// Last modified time for sorting within depth groups.
@@ -59,119 +153,12 @@ pub struct FileCandidate {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum MentionSearchStatus {
     Hint,
-    Searching,
+    /// The file index is still running its initial scan.
+    Indexing,
     Ready,
     NoMatches,
 }
 
-// ---------------------------------------------------------------------------
-// Background file walker
-// ---------------------------------------------------------------------------
-
-struct FileWalker {
-    entry_rx: std_mpsc::Receiver<FileCandidate>,
-    cancel: Arc<AtomicBool>,
-    /// All entries discovered so far (the full file cache).
-    all_entries: Vec<FileCandidate>,
-    /// Whether the background walker has finished.
-    finished: bool,
-}
-
-impl FileWalker {
-    fn spawn(root: PathBuf, respect_gitignore: bool) -> Self {
-        let (entry_tx, entry_rx) = std_mpsc::sync_channel(1024);
-        let cancel = Arc::new(AtomicBool::new(false));
-        let cancel_clone = Arc::clone(&cancel);
-
-        std::thread::spawn(move || {
-            let mut builder = ignore::WalkBuilder::new(&root);
-            builder
-                .hidden(false)
-                .git_ignore(respect_gitignore)
-                .git_global(respect_gitignore)
-                .git_exclude(respect_gitignore)
-                .sort_by_file_path(std::cmp::Ord::cmp);
-
-            for result in builder.build() {
-                if cancel_clone.load(AtomicOrdering::Relaxed) {
-                    break;
-                }
-                let Ok(entry) = result else { continue };
-
-                let Some(ft) = entry.file_type() else { continue };
-                let is_dir = ft.is_dir();
-                let is_file = ft.is_file();
-                if !is_dir && !is_file {
-                    continue;
-                }
-
-                let path = entry.path();
-                let Ok(rel) = path.strip_prefix(&root) else { continue };
-                let rel_str = rel.to_string_lossy().replace('\\', "/");
-                if rel_str.is_empty() {
-                    continue;
-                }
-
-                let depth = rel_str.matches('/').count();
-                let rel_path = if is_dir { format!("{rel_str}/") } else { rel_str };
-                let modified = entry
-                    .metadata()
-                    .ok()
-                    .and_then(|m| m.modified().ok())
-                    .unwrap_or(SystemTime::UNIX_EPOCH);
-
-                let rel_path_lower = rel_path.to_lowercase();
-                let basename_lower = candidate_basename(&rel_path).to_lowercase();
-
-                let candidate = FileCandidate {
-                    rel_path,
-                    rel_path_lower,
-                    basename_lower,
-                    depth,
-                    modified,
-                    is_dir,
-                };
-
-                if entry_tx.send(candidate).is_err() {
-                    break; // receiver dropped
-                }
-            }
-        });
-
-        Self { entry_rx, cancel, all_entries: Vec::new(), finished: false }
-    }
-
-    /// Drain new entries from the background thread (non-blocking).
-    /// Returns true if new entries were added.
-    fn drain(&mut self) -> bool {
-        if self.finished {
-            return false;
-        }
-
-        let mut added = false;
-        for _ in 0..DRAIN_BUDGET {
-            match self.entry_rx.try_recv() {
-                Ok(candidate) => {
-                    self.all_entries.push(candidate);
-                    added = true;
-                }
-                Err(std_mpsc::TryRecvError::Empty) => break,
-                Err(std_mpsc::TryRecvError::Disconnected) => {
-                    self.finished = true;
-                    break;
-                }
-            }
-        }
-        added
-    }
-}
-
-impl Drop for FileWalker {
-    fn drop(&mut self) {
-        self.cancel.store(true, AtomicOrdering::Relaxed);
-    }
-}
-
 // ---------------------------------------------------------------------------
 // MentionState implementation
 // ---------------------------------------------------------------------------
@@ -196,28 +183,89 @@ impl MentionState {
             candidates,
             dialog: DialogState::default(),
             search_status,
-            file_walker: None,
-            walker_cache_key: None,
+            indexed_count: 0,
+            symbol_file: None,
+            symbols: Vec::new(),
+            symbol_cache: None,
+            recent: HashSet::new(),
+            dir_file_counts: HashMap::new(),
+            dir_choice: None,
+            marked: Vec::new(),
+            selected_notice: None,
+            preview: None,
         }
     }
 
     #[must_use]
     pub fn placeholder_message(&self) -> Option<String> {
-        if !self.candidates.is_empty() {
+        if self.candidate_count() > 0 {
             return None;
         }
+        if let Some(file) = &self.symbol_file {
+            return Some(format!("No matching symbols in {file}"));
+        }
 
         match self.search_status {
             MentionSearchStatus::Hint => Some("Type to search files".to_owned()),
-            MentionSearchStatus::Searching => Some("Searching files...".to_owned()),
+            MentionSearchStatus::Indexing if self.indexed_count > 0 => {
+                Some(format!("Indexing files... ({} found)", self.indexed_count))
+            }
+            MentionSearchStatus::Indexing => Some("Indexing files...".to_owned()),
             MentionSearchStatus::NoMatches => Some("No matching files or folders".to_owned()),
             MentionSearchStatus::Ready => None,
         }
     }
 
+    /// Whether results may still grow because the initial file scan is running.
+    #[must_use]
+    pub fn is_indexing(&self) -> bool {
+        self.search_status == MentionSearchStatus::Indexing
+    }
+
     #[must_use]
     pub fn has_selectable_candidates(&self) -> bool {
-        !self.candidates.is_empty()
+        self.candidate_count() > 0
+    }
+
+    /// Number of rows in the dropdown: symbols in symbol mode, files otherwise.
+    #[must_use]
+    pub fn candidate_count(&self) -> usize {
+        if let Some(choice) = &self.dir_choice {
+            choice.actions().len()
+        } else if self.symbol_file.is_some() {
+            self.symbols.len()
+        } else {
+            self.candidates.len()
+        }
+    }
+
+    /// How the highlighted file will be sent, when that differs from a plain mention.
+    #[must_use]
+    pub fn selected_notice(&self) -> Option<&str> {
+        self.selected_notice.as_deref()
+    }
+
+    /// Preview of the highlighted text file, if any.
+    #[must_use]
+    pub fn preview(&self) -> Option<&MentionPreview> {
+        self.preview.as_ref()
+    }
+
+    #[must_use]
+    pub fn is_marked(&self, candidate: &FileCandidate) -> bool {
+        self.marked.contains(&candidate.rel_path)
+    }
+
+    /// Recursive file count for a directory candidate, once indexed.
+    #[must_use]
+    pub fn dir_file_count(&self, candidate: &FileCandidate) -> Option<usize> {
+        self.dir_file_counts.get(&candidate.rel_path).copied()
+    }
+
+    /// The part of the query after `#` while completing symbols.
+    #[must_use]
+    pub fn symbol_query(&self) -> &str {
+        split_symbol_query(&self.query).map_or("", |(_, symbol_query)| symbol_query)
     }
 
     fn mark_hint(&mut self) {
@@ -226,114 +274,216 @@ impl MentionState {
         self.dialog.clamp(0, MAX_VISIBLE);
     }
 
-    fn ensure_walker(&mut self, cwd: &str, respect_gitignore: bool) {
-        let key = (cwd.to_owned(), respect_gitignore);
-        if self.walker_cache_key.as_ref() == Some(&key) && self.file_walker.is_some() {
-            return; // reuse existing walker
+    fn show_symbols(&mut self, file: &str, path: &Path) {
+        if self.symbol_cache.as_ref().is_none_or(|(cached, _)| cached != file) {
+            self.symbol_cache = Some((file.to_owned(), symbols::load_symbols(path)));
         }
-        self.file_walker = Some(FileWalker::spawn(PathBuf::from(cwd), respect_gitignore));
-        self.walker_cache_key = Some(key);
+        let all = self.symbol_cache.as_ref().map_or(&[][..], |(_, all)| all.as_slice());
+        let query = split_symbol_query(&self.query).map_or("", |(_, symbol_query)| symbol_query);
+
+        let mut scored: Vec<(i32, &Symbol)> = all
+            .iter()
+            .filter_map(|symbol| {
+                fuzzy_match(&symbol.name, query).map(|found| (found.score, symbol))
+            })
+            .collect();
+        // Stable sort keeps file order among equal scores.
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+        scored.truncate(MAX_CANDIDATES);
+
+        self.symbols = scored.into_iter().map(|(_, symbol)| symbol.clone()).collect();
+        self.symbol_file = Some(file.to_owned());
+        self.candidates.clear();
+        self.search_status = if self.symbols.is_empty() {
+            MentionSearchStatus::NoMatches
+        } else {
+            MentionSearchStatus::Ready
+        };
+        self.dialog.clamp(self.symbols.len(), MAX_VISIBLE);
     }
 
-    fn start_search(&mut self, cwd: &str, respect_gitignore: bool) {
-        self.ensure_walker(cwd, respect_gitignore);
-        self.refilter();
+    fn leave_symbol_mode(&mut self) {
+        self.symbol_file = None;
+        self.symbols.clear();
     }
 
-    fn refilter(&mut self) {
-        let query_lower = self.query.to_lowercase();
-        let Some(walker) = self.file_walker.as_ref() else {
-            self.candidates.clear();
-            self.search_status = MentionSearchStatus::NoMatches;
-            self.dialog.clamp(0, MAX_VISIBLE);
-            return;
-        };
+    /// Whether `candidate` was ranked up because it was recently used or modified.
+    #[must_use]
+    pub fn is_recent(&self, candidate: &FileCandidate) -> bool {
+        self.recent.contains(&candidate.rel_path)
+    }
 
-        // Filter all cached entries against current query
-        let mut filtered: Vec<FileCandidate> = walker
-            .all_entries
+    /// Rank the entries of the session index and of any `/add-dir` indexes.
+    fn refilter(&mut self, indexes: &[&FileIndex], recent_files: &RecentFiles) {
+        let recency = Recency { files: recent_files, now: SystemTime::now() };
+        self.candidates = rank_and_truncate_candidates(
+            indexes.iter().flat_map(|index| index.entries()),
+            &self.query,
+            &recency,
+        );
+        self.recent = self
+            .candidates
             .iter()
-            .filter(|c| match_tier(c, &query_lower).is_some())
-            .cloned()
+            .filter(|candidate| recency.is_recent(candidate))
+            .map(|candidate| candidate.rel_path.clone())
             .collect();
+        self.dir_file_counts = self
+            .candidates
+            .iter()
+            .filter(|candidate| candidate.is_dir)
+            .map(|candidate| {
+                let count =
+                    indexes.iter().map(|index| index.file_count_under(&candidate.rel_path)).sum();
+                (candidate.rel_path.clone(), count)
+            })
+            .collect();
+        self.indexed_count = indexes.iter().map(|index| index.len()).sum();
 
-        rank_and_truncate_candidates(&mut filtered, &query_lower);
-        self.candidates = filtered;
-
-        self.search_status = if walker.finished {
-            if self.candidates.is_empty() {
-                MentionSearchStatus::NoMatches
-            } else {
-                MentionSearchStatus::Ready
-            }
+        self.search_status = if indexes.iter().any(|index| index.is_indexing()) {
+            MentionSearchStatus::Indexing
+        } else if self.candidates.is_empty() {
+            MentionSearchStatus::NoMatches
         } else {
-            MentionSearchStatus::Searching
+            MentionSearchStatus::Ready
         };
         self.dialog.clamp(self.candidates.len(), MAX_VISIBLE);
     }
+}
 
-    fn advance_search(&mut self) {
-        let Some(walker) = self.file_walker.as_mut() else {
-            self.search_status = MentionSearchStatus::NoMatches;
-            self.dialog.clamp(0, MAX_VISIBLE);
+/// Make sure `app.file_index` covers the current cwd and gitignore setting,
+/// and `app.extra_file_indexes` the `/add-dir` roots, (re)spawning the
+/// background indexers when any of them changed.
+pub(crate) fn ensure_file_index(app: &mut App) {
+    let root = Path::new(&app.cwd_raw);
+    let respect_gitignore = app.config.respect_gitignore_effective();
+    let extras_match = app.extra_file_indexes.len() == app.additional_dirs.len()
+        && app
+            .extra_file_indexes
+            .iter()
+            .zip(&app.additional_dirs)
+            .all(|(index, dir)| index.matches(Path::new(dir), respect_gitignore));
+    if app.file_index.as_ref().is_some_and(|index| index.matches(root, respect_gitignore)) {
+        if extras_match {
             return;
-        };
-
-        let added = walker.drain();
-        let finished = walker.finished;
-
-        if added || finished {
-            self.refilter();
         }
+    } else {
+        app.file_index = Some(FileIndex::spawn(PathBuf::from(&app.cwd_raw), respect_gitignore));
     }
-
-    fn invalidate_walker_cache(&mut self) {
-        if let Some(walker) = self.file_walker.take() {
-            walker.cancel.store(true, AtomicOrdering::Relaxed);
-        }
-        self.walker_cache_key = None;
-    }
+    // The prefixes depend on the cwd, so rebuild them along with the main index.
+    app.extra_file_indexes = app
+        .additional_dirs
+        .iter()
+        .map(|dir| {
+            let prefix = super::add_dir::mention_prefix(Path::new(&app.cwd_raw), Path::new(dir));
+            FileIndex::spawn_prefixed(PathBuf::from(dir), respect_gitignore, prefix)
+        })
+        .collect();
 }
 
 // ---------------------------------------------------------------------------
 // Matching and ranking
 // ---------------------------------------------------------------------------
 
-fn match_tier(candidate: &FileCandidate, query_lower: &str) -> Option<u8> {
-    if query_lower.is_empty() {
-        return Some(0);
+/// Extra score for matches that land entirely inside the basename, so `@main`
+/// prefers `src/main.rs` over paths that only match across directories.
+const BASENAME_BONUS: i32 = 24;
+
+/// Fuzzy-match a candidate path. Positions are byte offsets into `rel_path`.
+fn match_candidate(candidate: &FileCandidate, query: &str) -> Option<FuzzyMatch> {
+    let path_match = fuzzy_match(&candidate.rel_path, query)?;
+    if query.contains('/') {
+        return Some(path_match);
+    }
+    let basename = candidate_basename(&candidate.rel_path);
+    let offset = candidate.rel_path.trim_end_matches('/').len() - basename.len();
+    let basename_match = fuzzy_match(basename, query).map(|found| FuzzyMatch {
+        score: found.score + BASENAME_BONUS,
+        positions: found.positions.into_iter().map(|pos| pos + offset).collect(),
+    });
+    match basename_match {
+        Some(found) if found.score >= path_match.score => Some(found),
+        _ => Some(path_match),
     }
+}
 
-    if candidate.basename_lower.starts_with(query_lower) {
-        Some(0)
-    } else if candidate.rel_path_lower.starts_with(query_lower) {
-        Some(1)
-    } else if candidate.basename_lower.contains(query_lower) {
-        Some(2)
-    } else if candidate.rel_path_lower.contains(query_lower) {
-        Some(3)
-    } else {
-        None
+impl FileCandidate {
+    /// Byte offsets of the characters matched by `query`, for highlighting.
+    #[must_use]
+    pub fn match_positions(&self, query: &str) -> Vec<usize> {
+        match_candidate(self, query).map(|found| found.positions).unwrap_or_default()
     }
 }
 
-fn rank_and_truncate_candidates(candidates: &mut Vec<FileCandidate>, query_lower: &str) {
-    // Pre-compute tiers once to avoid repeated calls during sort
-    let tiers: Vec<Option<u8>> = candidates.iter().map(|c| match_tier(c, query_lower)).collect();
+/// Extra score for files recently touched by tools or mentioned, so they sort
+/// above otherwise-equal matches.
+const RECENT_USE_BONUS: i32 = 40;
+/// Extra score for files modified on disk within `RECENT_MODIFIED_WINDOW`.
+const RECENT_MODIFIED_BONUS: i32 = 16;
+
+struct Recency<'a> {
+    files: &'a RecentFiles,
+    now: SystemTime,
+}
+
+impl Recency<'_> {
+    fn bonus(&self, candidate: &FileCandidate) -> i32 {
+        let mut bonus = 0;
+        if self.files.position(&candidate.rel_path).is_some() {
+            bonus += RECENT_USE_BONUS;
+        }
+        if self.recently_modified(candidate) {
+            bonus += RECENT_MODIFIED_BONUS;
+        }
+        bonus
+    }
+
+    fn is_recent(&self, candidate: &FileCandidate) -> bool {
+        self.bonus(candidate) > 0
+    }
 
-    // Build index array and sort by tier + secondary criteria
-    let mut indices: Vec<usize> = (0..candidates.len()).collect();
-    indices.sort_by(|&i, &j| {
-        tiers[i]
-            .cmp(&tiers[j])
-            .then_with(|| candidates[i].depth.cmp(&candidates[j].depth))
-            .then_with(|| candidates[j].is_dir.cmp(&candidates[i].is_dir))
-            .then_with(|| candidates[j].modified.cmp(&candidates[i].modified))
-            .then_with(|| candidates[i].rel_path.cmp(&candidates[j].rel_path))
+    fn recently_modified(&self, candidate: &FileCandidate) -> bool {
+        !candidate.is_dir
+            && candidate.modified != SystemTime::UNIX_EPOCH
+            && self
+                .now
+                .duration_since(candidate.modified)
+                .is_ok_and(|age| age <= RECENT_MODIFIED_WINDOW)
+    }
+
+    fn position(&self, candidate: &FileCandidate) -> usize {
+        self.files.position(&candidate.rel_path).unwrap_or(usize::MAX)
+    }
+}
+
+/// Filter `entries` by fuzzy match and keep the best `MAX_CANDIDATES`,
+/// boosting recently used and recently modified files.
+fn rank_and_truncate_candidates<'a>(
+    entries: impl IntoIterator<Item = &'a FileCandidate>,
+    query: &str,
+    recency: &Recency<'_>,
+) -> Vec<FileCandidate> {
+    let mut scored: Vec<(i32, &FileCandidate)> = entries
+        .into_iter()
+        .filter_map(|c| match_candidate(c, query).map(|found| (found.score + recency.bonus(c), c)))
+        .collect();
+
+    scored.sort_by(|(score_a, a), (score_b, b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| recency.position(a).cmp(&recency.position(b)))
+            .then_with(|| a.depth.cmp(&b.depth))
+            .then_with(|| b.is_dir.cmp(&a.is_dir))
+            .then_with(|| b.modified.cmp(&a.modified))
+            .then_with(|| a.rel_path.cmp(&b.rel_path))
     });
 
-    indices.truncate(MAX_CANDIDATES);
-    *candidates = indices.into_iter().map(|i| candidates[i].clone()).collect();
+    scored.truncate(MAX_CANDIDATES);
+    scored.into_iter().map(|(_, c)| c.clone()).collect()
+}
+
+/// Split `path#symbol` into its parts. The path must be non-empty.
+fn split_symbol_query(query: &str) -> Option<(&str, &str)> {
+    query.split_once('#').filter(|(path, _)| !path.is_empty())
 }
 
 fn candidate_basename(rel_path: &str) -> &str {
@@ -390,6 +540,7 @@ pub fn activate(app: &mut App) {
     app.mention = Some(MentionState::new(trigger_row, trigger_col, query, Vec::new()));
     app.slash = None;
     app.subagent = None;
+    app.emoji = None;
     refresh_query_state(app, Instant::now());
 }
 
@@ -412,30 +563,37 @@ pub fn update_query(app: &mut App) {
     refresh_query_state(app, Instant::now());
 }
 
+/// Apply pending file index updates and refresh an active mention query.
 pub fn tick(app: &mut App, now: Instant) {
+    let _ = now;
+    let mut changed = app.file_index.as_mut().is_some_and(FileIndex::drain);
+    for index in &mut app.extra_file_indexes {
+        changed |= index.drain();
+    }
+    if !changed {
+        return;
+    }
     let Some(mention) = app.mention.as_mut() else {
         return;
     };
-
-    match mention.search_status {
-        MentionSearchStatus::Searching => {
-            mention.advance_search();
-            sync_focus(app);
-        }
-        MentionSearchStatus::Hint | MentionSearchStatus::Ready | MentionSearchStatus::NoMatches => {
-            let _ = now;
-        }
+    if mention.search_status == MentionSearchStatus::Hint
+        || mention.symbol_file.is_some()
+        || mention.dir_choice.is_some()
+    {
+        return;
     }
+    let indexes: Vec<&FileIndex> = app.file_index.iter().chain(&app.extra_file_indexes).collect();
+    mention.refilter(&indexes, &app.recent_files);
+    sync_focus(app);
 }
 
+/// Drop the file index (e.g. after a cwd or gitignore setting change) and
+/// rebuild it for the active mention, if any.
 pub fn invalidate_session_cache(app: &mut App) {
-    if let Some(mention) = app.mention.as_mut() {
-        mention.invalidate_walker_cache();
-        if mention.query.chars().count() < MIN_QUERY_CHARS {
-            mention.mark_hint();
-        } else {
-            mention.start_search(&app.cwd_raw, app.config.respect_gitignore_effective());
-        }
+    app.file_index = None;
+    app.extra_file_indexes.clear();
+    if app.mention.is_some() {
+        refresh_query_state(app, Instant::now());
     }
     sync_focus(app);
 }
@@ -444,18 +602,38 @@ fn refresh_query_state(app: &mut App, _now: Instant) {
     let Some(mention) = app.mention.as_mut() else {
         return;
     };
+    mention.dir_choice = None;
+
+    // `@path/to/file.rs#sym` completes symbols defined in that file.
+    if let Some((file, _)) = split_symbol_query(&mention.query) {
+        let path = Path::new(&app.cwd_raw).join(file);
+        if path.is_file() {
+            let file = file.to_owned();
+            mention.show_symbols(&file, &path);
+            sync_focus(app);
+            return;
+        }
+    }
+    mention.leave_symbol_mode();
+    // Start indexing as soon as `@` is typed so results are ready by the first keystroke.
+    ensure_file_index(app);
+    let Some(mention) = app.mention.as_mut() else {
+        return;
+    };
 
     if mention.query.chars().count() < MIN_QUERY_CHARS {
         mention.mark_hint();
-        sync_focus(app);
-        return;
+    } else {
+        let indexes: Vec<&FileIndex> =
+            app.file_index.iter().chain(&app.extra_file_indexes).collect();
+        mention.refilter(&indexes, &app.recent_files);
     }
-
-    mention.start_search(&app.cwd_raw, app.config.respect_gitignore_effective());
     sync_focus(app);
 }
 
+/// Sync focus ownership, the footer notice, and the preview with the current candidates.
 fn sync_focus(app: &mut App) {
+    refresh_selection_details(app);
     if app.mention.as_ref().is_some_and(MentionState::has_selectable_candidates) {
         app.claim_focus_target(FocusTarget::Mention);
     } else {
@@ -463,6 +641,57 @@ fn sync_focus(app: &mut App) {
     }
 }
 
+/// Refresh the footer notice and preview for the highlighted file.
+fn refresh_selection_details(app: &mut App) {
+    let Some(mention) = app.mention.as_mut() else {
+        return;
+    };
+    let selected = if mention.dir_choice.is_none() && mention.symbol_file.is_none() {
+        mention.candidates.get(mention.dialog.selected).filter(|candidate| !candidate.is_dir)
+    } else {
+        None
+    };
+    let Some(rel_path) = selected.map(|candidate| candidate.rel_path.clone()) else {
+        mention.selected_notice = None;
+        mention.preview = None;
+        return;
+    };
+    if mention.preview.as_ref().is_some_and(|preview| preview.rel_path == rel_path) {
+        return;
+    }
+
+    let path = Path::new(&app.cwd_raw).join(&rel_path);
+    let kind = attachments::classify_path(&path);
+    mention.selected_notice = kind.and_then(MentionFileKind::notice);
+    mention.preview = match kind {
+        Some(MentionFileKind::Text | MentionFileKind::Large { .. }) => {
+            Some(MentionPreview { rel_path, lines: load_preview(&path, PREVIEW_LINES) })
+        }
+        _ => None,
+    };
+}
+
+/// Read the first `max_lines` lines of `path`, with tabs expanded and long
+/// lines cut. Unreadable files yield an empty preview.
+pub(crate) fn load_preview(path: &Path, max_lines: usize) -> Vec<String> {
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    std::io::BufReader::new(file)
+        .split(b'\n')
+        .take(max_lines)
+        .map_while(Result::ok)
+        .map(|bytes| {
+            let line = String::from_utf8_lossy(&bytes);
+            line.trim_end_matches('\r')
+                .replace('\t', "    ")
+                .chars()
+                .take(PREVIEW_MAX_LINE_CHARS)
+                .collect()
+        })
+        .collect()
+}
+
 /// Keep mention state in sync with the current cursor location.
 /// - If cursor is inside a valid `@mention` token, activate/update autocomplete.
 /// - Otherwise, deactivate mention autocomplete.
@@ -480,16 +709,52 @@ pub fn sync_with_cursor(app: &mut App) {
 
 /// Confirm the selected candidate: replace `@query` in input with `@rel_path`.
 pub fn confirm_selection(app: &mut App) {
-    let Some(mention) = app.mention.take() else {
+    let Some(mut mention) = app.mention.take() else {
         return;
     };
     app.release_focus_target(FocusTarget::Mention);
 
-    let Some(candidate) = mention.candidates.get(mention.dialog.selected) else {
-        return;
+    let selected = mention.dialog.selected;
+    let (file, targets) = if !mention.marked.is_empty()
+        && mention.dir_choice.is_none()
+        && mention.symbol_file.is_none()
+    {
+        for path in &mention.marked {
+            app.recent_files.touch(path);
+        }
+        let last = mention.marked.last().cloned().unwrap_or_default();
+        (last, mention.marked.clone())
+    } else if let Some(choice) = &mention.dir_choice {
+        match choice.actions().get(selected) {
+            Some(DirectoryAction::Attach) => (choice.dir.clone(), vec![choice.dir.clone()]),
+            Some(DirectoryAction::Expand) => (choice.dir.clone(), choice.top_level_files.clone()),
+            None => return,
+        }
+    } else if let Some(file) = &mention.symbol_file {
+        let Some(symbol) = mention.symbols.get(selected) else {
+            return;
+        };
+        (file.clone(), vec![format!("{file}#{}", symbol.name)])
+    } else {
+        let Some(candidate) = mention.candidates.get(selected) else {
+            return;
+        };
+        if candidate.is_dir
+            && let Some(choice) = directory_choice(
+                app.file_index.iter().chain(&app.extra_file_indexes),
+                &candidate.rel_path,
+            )
+        {
+            // Ask whether to attach the directory or expand it into its files.
+            mention.dir_choice = Some(choice);
+            mention.dialog = DialogState::default();
+            app.mention = Some(mention);
+            sync_focus(app);
+            return;
+        }
+        (candidate.rel_path.clone(), vec![candidate.rel_path.clone()])
     };
 
-    let rel_path = candidate.rel_path.clone();
     let trigger_row = mention.trigger_row;
     let trigger_col = mention.trigger_col;
 
@@ -507,20 +772,67 @@ pub fn confirm_selection(app: &mut App) {
 
     let before: String = chars[..trigger_col].iter().collect();
     let after: String = chars[mention_end..].iter().collect();
-    let replacement =
-        if after.is_empty() { format!("@{rel_path} ") } else { format!("@{rel_path}") };
+    let inserted = targets.iter().map(|target| format!("@{target}")).collect::<Vec<_>>().join(" ");
+    let replacement = if after.is_empty() { format!("{inserted} ") } else { inserted };
 
     let new_line = format!("{before}{replacement}{after}");
     let new_cursor_col = trigger_col + replacement.chars().count();
 
     lines[trigger_row] = new_line;
     app.input.replace_lines_and_cursor(lines, trigger_row, new_cursor_col);
+    app.recent_files.touch(&file);
+}
+
+/// Toggle the multi-select mark on the highlighted file candidate.
+/// Returns `false` when marking does not apply (symbol or directory-choice rows).
+pub fn toggle_mark(app: &mut App) -> bool {
+    let Some(mention) = app.mention.as_mut() else {
+        return false;
+    };
+    if mention.dir_choice.is_some() || mention.symbol_file.is_some() {
+        return false;
+    }
+    let Some(candidate) = mention.candidates.get(mention.dialog.selected) else {
+        return false;
+    };
+    let rel_path = candidate.rel_path.clone();
+    if let Some(pos) = mention.marked.iter().position(|path| *path == rel_path) {
+        mention.marked.remove(pos);
+    } else {
+        mention.marked.push(rel_path);
+    }
+    mention.dialog.move_down(mention.candidates.len(), MAX_VISIBLE);
+    refresh_selection_details(app);
+    true
+}
+
+/// Build the attach/expand choice for a directory, or `None` when the
+/// directory has no indexed files (nothing to expand or count).
+fn directory_choice<'a>(
+    mut indexes: impl Iterator<Item = &'a FileIndex>,
+    dir: &str,
+) -> Option<DirectoryChoice> {
+    let (index, total_files) = indexes
+        .find_map(|index| Some((index, index.file_count_under(dir))).filter(|(_, n)| *n > 0))?;
+    let mut top_level_files = index.top_level_files(dir);
+    let top_level_total = top_level_files.len();
+    top_level_files.truncate(MAX_EXPANDED_FILES);
+    Some(DirectoryChoice { dir: dir.to_owned(), top_level_files, top_level_total, total_files })
+}
+
+/// Remember a file touched by a tool call so mention ranking prefers it.
+/// Paths outside the session cwd are ignored.
+pub fn record_touched_path(app: &mut App, path: &Path) {
+    let rel = if path.is_absolute() { path.strip_prefix(&app.cwd_raw).ok() } else { Some(path) };
+    if let Some(rel) = rel {
+        app.recent_files.touch(&rel.to_string_lossy().replace('\\', "/"));
+    }
 }
 
 /// Deactivate mention autocomplete.
 pub fn deactivate(app: &mut App) {
     app.mention = None;
-    if app.slash.is_none() && app.subagent.is_none() {
+    if app.slash.is_none() && app.subagent.is_none() && app.emoji.is_none() {
         app.release_focus_target(FocusTarget::Mention);
     }
 }
@@ -528,15 +840,17 @@ pub fn deactivate(app: &mut App) {
 /// Move selection up in the candidate list.
 pub fn move_up(app: &mut App) {
     if let Some(ref mut mention) = app.mention {
-        mention.dialog.move_up(mention.candidates.len(), MAX_VISIBLE);
+        mention.dialog.move_up(mention.candidate_count(), MAX_VISIBLE);
     }
+    refresh_selection_details(app);
 }
 
 /// Move selection down in the candidate list.
 pub fn move_down(app: &mut App) {
     if let Some(ref mut mention) = app.mention {
-        mention.dialog.move_down(mention.candidates.len(), MAX_VISIBLE);
+        mention.dialog.move_down(mention.candidate_count(), MAX_VISIBLE);
     }
+    refresh_selection_details(app);
 }
 
 /// Find all `@path` references in a text string. Returns `(start_byte, end_byte, path)` tuples.
@@ -573,6 +887,7 @@ pub fn find_mention_spans(text: &str) -> Vec<(usize, usize, String)> {
 mod tests {
     use super::*;
     use crate::app::App;
+    use std::fmt::Write as _;
     use std::time::Duration;
 
     fn app_with_temp_files(files: &[&str]) -> (App, tempfile::TempDir) {
@@ -595,7 +910,7 @@ mod tests {
             // Give the background thread time to discover files
             std::thread::sleep(Duration::from_millis(2));
             let is_settled = app.mention.as_ref().is_none_or(|mention| {
-                !matches!(mention.search_status, MentionSearchStatus::Searching)
+                !matches!(mention.search_status, MentionSearchStatus::Indexing)
             });
             if is_settled {
                 return;
@@ -644,6 +959,139 @@ mod tests {
         assert_eq!(app.input.lines()[0], "@src/main.rs ");
     }
 
+    #[test]
+    fn highlighted_text_file_loads_head_preview() {
+        let (mut app, tmp) = app_with_temp_files(&["notes.md", "logo.png"]);
+        let body = (1..=20).fold(String::new(), |mut body, n| {
+            let _ = write!(body, "\tline {n}\r\n");
+            body
+        });
+        std::fs::write(tmp.path().join("notes.md"), body).expect("write notes");
+        app.input.set_text("@notes");
+        let _ = app.input.set_cursor(0, 6);
+
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+
+        let mention = app.mention.as_ref().expect("mention should be active");
+        let preview = mention.preview().expect("text file preview");
+        assert_eq!(preview.rel_path, "notes.md");
+        assert_eq!(preview.lines.len(), PREVIEW_LINES);
+        assert_eq!(preview.lines[0], "    line 1");
+
+        app.input.set_text("@logo");
+        let _ = app.input.set_cursor(0, 5);
+        update_query(&mut app);
+        run_search(&mut app, Instant::now());
+
+        let mention = app.mention.as_ref().expect("mention should be active");
+        assert!(mention.preview().is_none());
+    }
+
+    #[test]
+    fn selecting_directory_offers_attach_or_expand() {
+        let (mut app, _tmp) = app_with_temp_files(&["src/a.rs", "src/b.rs", "src/nested/c.rs"]);
+        app.input.set_text("@src");
+        let _ = app.input.set_cursor(0, 4);
+
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+        let mention = app.mention.as_ref().expect("mention should be active");
+        assert_eq!(mention.candidates[0].rel_path, "src/");
+        assert_eq!(mention.dir_file_count(&mention.candidates[0]), Some(3));
+
+        confirm_selection(&mut app);
+        let choice = app
+            .mention
+            .as_ref()
+            .and_then(|mention| mention.dir_choice.as_ref())
+            .expect("directory choice should be offered");
+        assert_eq!(choice.actions(), &[DirectoryAction::Attach, DirectoryAction::Expand]);
+        assert_eq!(choice.top_level_files, vec!["src/a.rs", "src/b.rs"]);
+
+        move_down(&mut app);
+        confirm_selection(&mut app);
+        assert_eq!(app.input.lines()[0], "@src/a.rs @src/b.rs ");
+        assert!(app.mention.is_none());
+    }
+
+    #[test]
+    fn tab_marks_multiple_candidates_and_enter_inserts_all() {
+        let (mut app, _tmp) = app_with_temp_files(&["src/a.rs", "src/b.rs", "src/c.rs"]);
+        app.input.set_text("see @rs");
+        let _ = app.input.set_cursor(0, "see @rs".chars().count());
+
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+        let first = app.mention.as_ref().expect("mention").candidates[0].rel_path.clone();
+        assert!(toggle_mark(&mut app));
+        let second = app.mention.as_ref().expect("mention").candidates[1].rel_path.clone();
+        assert!(toggle_mark(&mut app));
+        assert_eq!(
+            app.mention.as_ref().expect("mention").marked,
+            vec![first.clone(), second.clone()]
+        );
+
+        confirm_selection(&mut app);
+
+        assert_eq!(app.input.lines()[0], format!("see @{first} @{second} "));
+        assert!(app.mention.is_none());
+    }
+
+    #[test]
+    fn attaching_directory_inserts_directory_reference() {
+        let (mut app, _tmp) = app_with_temp_files(&["src/a.rs"]);
+        app.input.set_text("@src");
+        let _ = app.input.set_cursor(0, 4);
+
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+        confirm_selection(&mut app);
+        confirm_selection(&mut app);
+
+        assert_eq!(app.input.lines()[0], "@src/ ");
+    }
+
+    #[test]
+    fn hash_after_file_path_completes_symbols_from_that_file() {
+        let (mut app, tmp) = app_with_temp_files(&[]);
+        std::fs::create_dir_all(tmp.path().join("src")).expect("create src");
+        std::fs::write(
+            tmp.path().join("src").join("lib.rs"),
+            "pub struct Config;\n\npub fn connect() {}\nfn render_frame() {}\n",
+        )
+        .expect("write lib.rs");
+        app.input.set_text("@src/lib.rs#c");
+        let _ = app.input.set_cursor(0, app.input.lines()[0].chars().count());
+
+        activate(&mut app);
+
+        let mention = app.mention.as_ref().expect("mention should be active");
+        assert_eq!(mention.symbol_file.as_deref(), Some("src/lib.rs"));
+        let names: Vec<&str> = mention.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Config", "connect"]);
+
+        app.input.set_text("@src/lib.rs#conn");
+        let _ = app.input.set_cursor(0, app.input.lines()[0].chars().count());
+        update_query(&mut app);
+        confirm_selection(&mut app);
+        assert_eq!(app.input.lines()[0], "@src/lib.rs#connect ");
+    }
+
+    #[test]
+    fn hash_after_unknown_path_falls_back_to_file_search() {
+        let (mut app, _tmp) = app_with_temp_files(&["src/main.rs"]);
+        app.input.set_text("@missing.rs#foo");
+        let _ = app.input.set_cursor(0, app.input.lines()[0].chars().count());
+
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+
+        let mention = app.mention.as_ref().expect("mention should be active");
+        assert!(mention.symbol_file.is_none());
+        assert!(mention.candidates.is_empty());
+    }
+
     #[test]
     fn activate_with_empty_query_keeps_empty_candidates_until_threshold() {
         let (mut app, _tmp) = app_with_temp_files(&["src/main.rs"]);
@@ -758,7 +1206,7 @@ mod tests {
 
         activate(&mut app);
 
-        // With the background walker, after full search all matching files appear
+        // Once the index finishes scanning, all matching files appear
         run_search(&mut app, Instant::now());
 
         let mention = app.mention.as_ref().expect("mention should be active");
@@ -782,41 +1230,94 @@ mod tests {
             mention.candidates.iter().any(|candidate| candidate.rel_path == "root.rs")
         }));
 
-        // Change query — should refilter from cache, not restart the walker
+        // Change query — should refilter from the index, not restart the scan
         app.input.set_text("@needle");
         let _ = app.input.set_cursor(0, "@needle".chars().count());
         update_query(&mut app);
 
-        // The walker cache should still be present (not restarted)
         let mention = app.mention.as_ref().expect("mention should remain active");
-        // Since walker finished and cache has all entries, refilter is instant
+        // Since the index finished its scan, refilter is instant
         assert_eq!(mention.candidates.len(), 1);
         assert_eq!(mention.candidates[0].rel_path, "src/nested/needle.rs");
     }
 
+    fn candidate(rel_path: &str) -> FileCandidate {
+        FileCandidate {
+            rel_path: rel_path.to_owned(),
+            depth: rel_path.trim_end_matches('/').matches('/').count(),
+            modified: SystemTime::UNIX_EPOCH,
+            is_dir: rel_path.ends_with('/'),
+        }
+    }
+
+    fn no_recency() -> Recency<'static> {
+        static EMPTY: std::sync::LazyLock<RecentFiles> =
+            std::sync::LazyLock::new(RecentFiles::default);
+        Recency { files: &EMPTY, now: SystemTime::now() }
+    }
+
+    #[test]
+    fn recently_touched_and_modified_files_rank_above_plain_matches() {
+        let mut recent_files = RecentFiles::default();
+        recent_files.touch("src/zeta.rs");
+        let mut modified = candidate("src/beta.rs");
+        modified.modified = SystemTime::now();
+        let candidates = vec![candidate("src/alpha.rs"), modified, candidate("src/zeta.rs")];
+        let recency = Recency { files: &recent_files, now: SystemTime::now() };
+
+        let ranked = rank_and_truncate_candidates(&candidates, "rs", &recency);
+
+        let paths: Vec<&str> = ranked.iter().map(|c| c.rel_path.as_str()).collect();
+        assert_eq!(paths, vec!["src/zeta.rs", "src/beta.rs", "src/alpha.rs"]);
+        assert!(recency.is_recent(&ranked[0]));
+        assert!(recency.is_recent(&ranked[1]));
+        assert!(!recency.is_recent(&ranked[2]));
+    }
+
+    #[test]
+    fn confirming_a_mention_and_tool_locations_mark_files_recent() {
+        let (mut app, tmp) = app_with_temp_files(&["src/main.rs"]);
+        app.input.set_text("@src/mai");
+        let _ = app.input.set_cursor(0, app.input.lines()[0].chars().count());
+        activate(&mut app);
+        run_search(&mut app, Instant::now());
+        confirm_selection(&mut app);
+        record_touched_path(&mut app, &tmp.path().join("Cargo.toml"));
+        record_touched_path(&mut app, Path::new("/elsewhere/file.rs"));
+
+        assert_eq!(app.recent_files.position("Cargo.toml"), Some(0));
+        assert_eq!(app.recent_files.position("src/main.rs"), Some(1));
+        assert_eq!(app.recent_files.position("file.rs"), None);
+    }
+
     #[test]
     fn basename_prefix_ranks_ahead_of_shallow_path_substring() {
-        let mut candidates = vec![
-            FileCandidate {
-                rel_path: "docs/guide-rs.txt".to_owned(),
-                rel_path_lower: "docs/guide-rs.txt".to_owned(),
-                basename_lower: "guide-rs.txt".to_owned(),
-                depth: 1,
-                modified: SystemTime::UNIX_EPOCH,
-                is_dir: false,
-            },
-            FileCandidate {
-                rel_path: "src/rs-helper.rs".to_owned(),
-                rel_path_lower: "src/rs-helper.rs".to_owned(),
-                basename_lower: "rs-helper.rs".to_owned(),
-                depth: 1,
-                modified: SystemTime::UNIX_EPOCH,
-                is_dir: false,
-            },
+        let candidates = vec![candidate("docs/guide-rs.txt"), candidate("src/rs-helper.rs")];
+
+        let ranked = rank_and_truncate_candidates(&candidates, "rs", &no_recency());
+
+        assert_eq!(ranked[0].rel_path, "src/rs-helper.rs");
+    }
+
+    #[test]
+    fn fuzzy_query_matches_non_contiguous_path_characters() {
+        let candidates = vec![
+            candidate("src/app/mention.rs"),
+            candidate("src/ui/message.rs"),
+            candidate("README.md"),
         ];
 
-        rank_and_truncate_candidates(&mut candidates, "rs");
+        let ranked = rank_and_truncate_candidates(&candidates, "amen", &no_recency());
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].rel_path, "src/app/mention.rs");
+        assert_eq!(ranked[0].match_positions("amen"), vec![4, 8, 9, 10]);
+    }
 
-        assert_eq!(candidates[0].rel_path, "src/rs-helper.rs");
+    #[test]
+    fn basename_match_positions_are_offset_into_full_path() {
+        let found = candidate("src/ui/chat.rs");
+        assert_eq!(found.match_positions("chat"), vec![7, 8, 9, 10]);
+        assert!(candidate("src/").match_positions("zzz").is_empty());
     }
 }
@@ -1,34 +1,88 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod add_dir;
+pub(crate) mod annotations;
+pub(crate) mod attachments;
 pub(crate) mod auth;
+pub(crate) mod bookmarks;
 mod cache_policy;
+mod change_dir;
+pub(crate) mod changed_files;
+pub(crate) mod claude_hooks;
+pub(crate) mod clipboard;
+mod code_blocks;
+mod commit;
 pub(crate) mod config;
+pub(crate) mod confirm;
 mod connect;
 mod dialog;
+pub(crate) mod diff_stats;
+mod edit_check;
+pub(crate) mod emoji;
 mod events;
+mod explain;
+pub(crate) mod file_index;
 mod focus;
+pub(crate) mod fuzzy;
+mod heading_nav;
+pub(crate) mod health;
+pub(crate) mod help_overlay;
+mod hscroll;
+pub(crate) mod http;
 mod inline_interactions;
 pub(crate) mod input;
 mod input_submit;
+mod issue;
 mod keys;
+mod lifecycle_hooks;
+pub(crate) mod line_input;
+mod memory;
 pub(crate) mod mention;
+pub(crate) mod model_params;
 mod notify;
+pub(crate) mod palette;
 pub(crate) mod paste_burst;
+pub(crate) mod pasted_paths;
 mod permissions;
+pub(crate) mod pinned_output;
 pub(crate) mod plugins;
+mod pull_request;
 mod questions;
+mod quit_summary;
+pub(crate) mod revisions;
+mod rewind;
+pub(crate) mod screen_reader;
 mod selection;
+mod self_update;
 mod service_status_check;
+mod session_env;
+mod share;
 pub(crate) mod slash;
 mod state;
+pub(crate) mod status_log;
 pub(crate) mod subagent;
+mod suggestions;
+pub(crate) mod symbols;
+pub(crate) mod task_lists;
 mod terminal;
+pub(crate) mod test_run;
+pub(crate) mod toast;
 mod todos;
+pub(crate) mod tool_retry;
+pub(crate) mod tool_timings;
+mod transcript;
+pub(crate) mod transcript_filter;
 mod trust;
+pub(crate) mod turn_folds;
+pub(crate) mod turn_retry;
+pub(crate) mod unhandled_events;
 mod update_check;
 pub(crate) mod usage;
 mod view;
+pub(crate) mod watch_patterns;
+pub(crate) mod web_tools;
+pub(crate) mod welcome;
 
 // Re-export all public types so `crate::app::App`, `crate::app::BlockCache`, etc. still work.
 pub use cache_policy::{
@@ -36,25 +90,33 @@ pub use cache_policy::{
     DEFAULT_TOOL_PREVIEW_LIMIT_BYTES, TextSplitDecision, TextSplitKind, default_cache_split_policy,
     find_text_split, find_text_split_index,
 };
-pub use config::{ConfigState, ConfigTab};
-pub use connect::{create_app, start_connection};
+pub use config::{
+    ConfigState, ConfigTab, ScrollAnimation, SystemMessageRouting, ThinkingDisplay, UpdateChannel,
+    WelcomeBanner,
+};
+pub use connect::{create_app, reconnect_bridge_if_due, start_connection};
 pub use events::{handle_client_event, handle_terminal_event};
 pub use focus::{FocusManager, FocusOwner, FocusTarget};
 pub use input::InputState;
+pub(crate) use keys::has_release_notes;
+pub use quit_summary::quit_summary;
 pub(crate) use selection::normalize_selection;
 pub use service_status_check::start_service_status_check;
 pub(crate) use state::cache_metrics;
 pub use state::{
-    App, AppStatus, BlockCache, CacheMetrics, CancelOrigin, ChatMessage, ChatViewport, ExtraUsage,
-    HelpView, IncrementalMarkdown, InlinePermission, InlineQuestion, InvalidationLevel, LoginHint,
-    McpState, MessageBlock, MessageRole, MessageUsage, ModeInfo, ModeState, PasteSessionState,
-    PendingCommandAck, RecentSessionInfo, SelectionKind, SelectionPoint, SelectionState,
-    SessionUsageState, SystemSeverity, TerminalSnapshotMode, TextBlock, TextBlockSpacing, TodoItem,
-    TodoStatus, ToolCallInfo, ToolCallScope, UsageSnapshot, UsageSourceKind, UsageSourceMode,
-    UsageState, UsageWindow, WelcomeBlock, is_execute_tool_name,
+    App, AppStatus, BlockCache, BridgeReconnect, CacheMetrics, CancelOrigin, ChatMessage,
+    ChatViewport, ChatVisualState, CodeView, DiffSummaryBlock, ExtraUsage, FileDiffStat, HelpView,
+    IncrementalMarkdown, InlinePermission, InlineQuestion, InvalidationLevel, LoginHint, McpState,
+    MemoryBudget, MemoryUsage, MessageBlock, MessageRole, MessageUsage, ModeInfo, ModeState,
+    PasteSessionState, PendingCommandAck, RecentSessionInfo, RecoveryHintBlock, SelectionKind,
+    SelectionPoint, SelectionState, SessionUsageState, SystemSeverity, TerminalSnapshotMode,
+    TextBlock, TextBlockSpacing, TextRevision, ThinkingBlock, TodoItem, TodoStatus, ToolCallInfo,
+    ToolCallScope, UsageSnapshot, UsageSourceKind, UsageSourceMode, UsageState, UsageWindow,
+    WelcomeBlock, is_execute_tool_name,
 };
+pub use transcript::transcript_markdown;
 pub use trust::TrustSelection;
-pub use update_check::start_update_check;
+pub use update_check::{ReleaseAsset, ReleaseInfo, start_update_check};
 pub use view::ActiveView;
 
 use crate::agent::model;
@@ -63,17 +125,27 @@ use crossterm::event::{
     PushKeyboardEnhancementFlags,
 };
 use futures::{FutureExt as _, StreamExt};
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 const SPINNER_FRAME_INTERVAL_NORMAL: Duration = Duration::from_millis(30);
-const SPINNER_FRAME_INTERVAL_REDUCED: Duration = Duration::from_millis(120);
+/// Frame pacing while the terminal window has focus.
+const FRAME_TICK: Duration = Duration::from_millis(16);
+/// Frame pacing while it is unfocused; input and agent events still wake the
+/// loop at once.
+const UNFOCUSED_FRAME_TICK: Duration = Duration::from_millis(250);
+/// With reduced motion the spinner holds still; this only paces redraws so
+/// elapsed-time counters keep ticking.
+const REDUCED_MOTION_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+/// How long quitting waits for a cancelled turn to end before exiting anyway.
+const SHUTDOWN_SETTLE_TIMEOUT: Duration = Duration::from_secs(2);
 
 // ---------------------------------------------------------------------------
-// Terminal suspend / resume helpers (reused by /login, /logout)
+// Terminal suspend / resume helpers (reused by /logout)
 // ---------------------------------------------------------------------------
 
 /// Disable raw mode and crossterm features so a child process can own the
-/// terminal (e.g. `claude auth login` which opens a browser flow).
+/// terminal (e.g. `claude auth logout`).
 pub(crate) fn suspend_terminal() {
     let _ = crossterm::execute!(
         std::io::stdout(),
@@ -114,13 +186,17 @@ pub async fn run_tui(app: &mut App) -> anyhow::Result<()> {
     resume_terminal();
 
     let mut events = EventStream::new();
-    let tick_duration = Duration::from_millis(16);
     let mut last_render = Instant::now();
 
     loop {
         start_connection(app);
+        reconnect_bridge_if_due(app, Instant::now());
+        turn_retry::retry_turn_if_due(app, Instant::now());
+        health::tick(app, Instant::now());
 
         // Phase 1: wait for at least one event or the next frame tick
+        let tick_duration =
+            if app.notifications.is_focused() { FRAME_TICK } else { UNFOCUSED_FRAME_TICK };
         let time_to_next = tick_duration.saturating_sub(last_render.elapsed());
         tokio::select! {
             Some(Ok(event)) = events.next() => {
@@ -195,10 +271,12 @@ pub async fn run_tui(app: &mut App) -> anyhow::Result<()> {
                 | AppStatus::CommandPending
                 | AppStatus::Thinking
                 | AppStatus::Running
-        ) || app.is_compacting;
+        ) || app.is_compacting
+            || turn_retry::is_waiting(app);
         if is_animating {
-            advance_spinner_frame(app, Instant::now());
-            app.needs_redraw = true;
+            if advance_spinner_frame(app, Instant::now()) || !reduced_motion(app) {
+                app.needs_redraw = true;
+            }
         } else {
             app.spinner_last_advance_at = None;
         }
@@ -210,6 +288,9 @@ pub async fn run_tui(app: &mut App) -> anyhow::Result<()> {
         if terminal::update_terminal_outputs(app) {
             app.needs_redraw = true;
         }
+        if app.toasts.prune(Instant::now()) {
+            app.needs_redraw = true;
+        }
         if app.force_redraw {
             terminal.clear()?;
             app.force_redraw = false;
@@ -219,13 +300,13 @@ pub async fn run_tui(app: &mut App) -> anyhow::Result<()> {
             if let Some(ref mut perf) = app.perf {
                 perf.next_frame();
             }
-            if app.perf.is_some() {
+            if app.perf.is_some() || crate::perf::hud::is_active() {
                 app.mark_frame_presented(Instant::now());
             }
             #[allow(clippy::drop_non_drop)]
             {
-                let timer = app.perf.as_ref().map(|p| p.start("frame_total"));
-                let draw_timer = app.perf.as_ref().map(|p| p.start("frame::terminal_draw"));
+                let timer = crate::perf::start("frame_total");
+                let draw_timer = crate::perf::start("frame::terminal_draw");
                 terminal.draw(|f| crate::ui::render(f, app))?;
                 drop(draw_timer);
                 drop(timer);
@@ -261,33 +342,62 @@ pub async fn run_tui(app: &mut App) -> anyhow::Result<()> {
         }
     }
 
-    // Cancel any active turn and give the adapter a moment to clean up
-    if matches!(app.status, AppStatus::Thinking | AppStatus::Running)
-        && let Some(ref conn) = app.conn
-        && let Some(sid) = app.session_id.clone()
-    {
-        let _ = conn.cancel(sid.to_string());
-    }
+    // Cancel any active turn
+    let cancel_sent = matches!(app.status, AppStatus::Thinking | AppStatus::Running)
+        && app
+            .conn
+            .as_ref()
+            .zip(app.session_id.as_ref())
+            .is_some_and(|(conn, sid)| conn.cancel(sid.to_string()).is_ok());
 
     // Restore terminal
     suspend_terminal();
     ratatui::restore();
 
+    // Let the adapter wind the cancelled turn down so the session it persists
+    // ends cleanly, then stop any background terminals it left running.
+    if cancel_sent {
+        wait_for_turn_to_settle(app).await;
+    }
+    crate::agent::events::kill_all_terminals(&app.terminals);
+
     Ok(())
 }
 
-fn advance_spinner_frame(app: &mut App, now: Instant) {
-    let interval = if app.config.prefers_reduced_motion_effective() {
-        SPINNER_FRAME_INTERVAL_REDUCED
-    } else {
-        SPINNER_FRAME_INTERVAL_NORMAL
-    };
+/// Drain client events until the cancelled turn ends or the timeout passes.
+async fn wait_for_turn_to_settle(app: &mut App) {
+    app.pending_auto_submit_after_cancel = false;
+    let deadline = tokio::time::Instant::now() + SHUTDOWN_SETTLE_TIMEOUT;
+    while matches!(app.status, AppStatus::Thinking | AppStatus::Running) {
+        match tokio::time::timeout_at(deadline, app.event_rx.recv()).await {
+            Ok(Some(event)) => events::handle_client_event(app, event),
+            Ok(None) | Err(_) => break,
+        }
+    }
+}
+
+/// Whether animation is off, from the reduce-motion setting or screen reader
+/// mode: spinners and the streaming cursor hold still and scrolling jumps.
+pub(crate) fn reduced_motion(app: &App) -> bool {
+    app.config.prefers_reduced_motion_effective() || screen_reader::is_enabled()
+}
+
+/// Step the spinner once its interval has passed. Returns whether a tick was
+/// due; under reduced motion or while the window is unfocused the frame stays
+/// put and ticks come once a second.
+fn advance_spinner_frame(app: &mut App, now: Instant) -> bool {
+    let still = reduced_motion(app) || !app.notifications.is_focused();
+    let interval =
+        if still { REDUCED_MOTION_REDRAW_INTERVAL } else { SPINNER_FRAME_INTERVAL_NORMAL };
 
     match app.spinner_last_advance_at {
-        Some(last_advance) if now.duration_since(last_advance) < interval => {}
+        Some(last_advance) if now.duration_since(last_advance) < interval => false,
         Some(_) | None => {
-            app.spinner_frame = app.spinner_frame.wrapping_add(1);
+            if !still {
+                app.spinner_frame = app.spinner_frame.wrapping_add(1);
+            }
             app.spinner_last_advance_at = Some(now);
+            true
         }
     }
 }
@@ -369,6 +479,20 @@ fn finalize_pending_paste_event(app: &mut App) {
         return;
     }
 
+    let pasted_mentions = pasted_paths::parse_pasted_paths(&pasted, Path::new(&app.cwd_raw));
+    if let Some(mentions) = &pasted_mentions
+        && app.config.paste_paths_as_mentions_effective()
+    {
+        app.input.insert_str(&pasted_paths::mention_text(mentions));
+        app.active_paste_session = None;
+        app.needs_redraw = true;
+        tracing::debug!(
+            count = mentions.len(),
+            "paste_finalize: inserted pasted paths as mentions"
+        );
+        return;
+    }
+
     let char_count = input::count_text_chars(&pasted);
     if char_count > input::PASTE_PLACEHOLDER_CHAR_THRESHOLD {
         app.input.insert_paste_block(&pasted);
@@ -379,8 +503,15 @@ fn finalize_pending_paste_event(app: &mut App) {
             Some(state::PasteSessionState { placeholder_index: idx, ..session });
         tracing::debug!(char_count, placeholder_index = ?idx, "paste_finalize: inserted placeholder");
     } else {
+        let start = SelectionPoint { row: app.input.cursor_row(), col: app.input.cursor_col() };
         app.input.insert_str(&pasted);
         app.active_paste_session = None;
+        app.pasted_paths_offer = pasted_mentions.map(|mentions| pasted_paths::PastedPathsOffer {
+            start,
+            end: SelectionPoint { row: app.input.cursor_row(), col: app.input.cursor_col() },
+            mentions,
+            input_version: app.input.version,
+        });
         tracing::debug!(
             char_count,
             lines = app.input.lines().len(),
@@ -567,6 +698,46 @@ mod tests {
         assert_eq!(app.input.lines(), vec!["hello", "world"]);
     }
 
+    #[test]
+    fn pasted_paths_are_offered_as_mentions_and_converted_on_tab() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join("src")).expect("create src");
+        std::fs::write(tmp.path().join("src/main.rs"), "").expect("write file");
+        let mut app = App::test_default();
+        app.cwd_raw = tmp.path().to_string_lossy().into_owned();
+        app.input.set_text("look at ");
+        let _ = app.input.set_cursor(0, 8);
+        app.pending_paste_text = format!("file://{}/src/main.rs", app.cwd_raw);
+
+        finalize_pending_paste_event(&mut app);
+
+        assert_eq!(app.input.text(), format!("look at file://{}/src/main.rs", app.cwd_raw));
+        assert!(pasted_paths::active_offer(&app).is_some());
+
+        keys::dispatch_key_by_focus(&mut app, KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+
+        assert_eq!(app.input.text(), "look at @src/main.rs");
+        assert!(app.pasted_paths_offer.is_none());
+    }
+
+    #[test]
+    fn pasted_paths_become_mentions_directly_when_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(tmp.path().join("notes.md"), "").expect("write file");
+        let mut app = App::test_default();
+        app.cwd_raw = tmp.path().to_string_lossy().into_owned();
+        config::store::set_paste_paths_as_mentions(
+            &mut app.config.committed_preferences_document,
+            true,
+        );
+        app.pending_paste_text = format!("'{}/notes.md'", app.cwd_raw);
+
+        finalize_pending_paste_event(&mut app);
+
+        assert_eq!(app.input.text(), "@notes.md");
+        assert!(app.pasted_paths_offer.is_none());
+    }
+
     #[test]
     fn suppressed_enter_preserves_multiline_inline_paste() {
         let mut app = App::test_default();
@@ -734,13 +905,13 @@ mod tests {
     }
 
     #[test]
-    fn spinner_advances_less_frequently_when_reduced_motion_enabled() {
+    fn spinner_holds_still_when_reduced_motion_enabled() {
         let mut app = App::test_default();
         let base = Instant::now();
 
-        advance_spinner_frame(&mut app, base);
+        assert!(advance_spinner_frame(&mut app, base));
         assert_eq!(app.spinner_frame, 1);
-        advance_spinner_frame(&mut app, base + Duration::from_millis(40));
+        assert!(advance_spinner_frame(&mut app, base + Duration::from_millis(40)));
         assert_eq!(app.spinner_frame, 2);
 
         crate::app::config::store::set_prefers_reduced_motion(
@@ -750,11 +921,24 @@ mod tests {
         app.spinner_last_advance_at = None;
         app.spinner_frame = 0;
 
-        advance_spinner_frame(&mut app, base);
-        assert_eq!(app.spinner_frame, 1);
-        advance_spinner_frame(&mut app, base + Duration::from_millis(95));
+        assert!(advance_spinner_frame(&mut app, base));
+        assert!(!advance_spinner_frame(&mut app, base + Duration::from_millis(500)));
+        assert!(advance_spinner_frame(&mut app, base + Duration::from_millis(1001)));
+        assert_eq!(app.spinner_frame, 0);
+    }
+
+    #[test]
+    fn spinner_pauses_while_the_terminal_is_unfocused() {
+        let mut app = App::test_default();
+        let base = Instant::now();
+        app.notifications.on_focus_lost();
+
+        assert!(advance_spinner_frame(&mut app, base));
+        assert!(!advance_spinner_frame(&mut app, base + Duration::from_millis(40)));
+        assert_eq!(app.spinner_frame, 0);
+
+        app.notifications.on_focus_gained();
+        assert!(advance_spinner_frame(&mut app, base + Duration::from_millis(80)));
         assert_eq!(app.spinner_frame, 1);
-        advance_spinner_frame(&mut app, base + Duration::from_millis(121));
-        assert_eq!(app.spinner_frame, 2);
     }
 }
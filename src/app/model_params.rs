@@ -0,0 +1,223 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-session generation parameters: `/set thinking-budget 10000`,
+//! `/set max-turns 40`, `/set <param> default`, or `/set` alone for the
+//! overlay. The values travel with the session launch settings, so changing
+//! them reloads the current session; `/status` and `/stats` show the current
+//! values.
+//! Parameters the agent does not expose (such as temperature) are rejected.
+
+use super::connect::reload_session;
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelParams {
+    /// Maximum tokens the model may spend thinking per turn.
+    pub thinking_budget: Option<u32>,
+    /// Maximum agentic turns per prompt.
+    pub max_turns: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelParam {
+    ThinkingBudget,
+    MaxTurns,
+}
+
+impl ModelParam {
+    pub const ALL: [Self; 2] = [Self::ThinkingBudget, Self::MaxTurns];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ThinkingBudget => "thinking-budget",
+            Self::MaxTurns => "max-turns",
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::ThinkingBudget => "Thinking budget",
+            Self::MaxTurns => "Max turns",
+        }
+    }
+
+    const fn range(self) -> (u32, u32) {
+        match self {
+            Self::ThinkingBudget => (1_024, 128_000),
+            Self::MaxTurns => (1, 500),
+        }
+    }
+
+    const fn step(self) -> u32 {
+        match self {
+            Self::ThinkingBudget => 1_024,
+            Self::MaxTurns => 5,
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|param| param.name() == name)
+    }
+
+    #[must_use]
+    pub const fn get(self, params: &ModelParams) -> Option<u32> {
+        match self {
+            Self::ThinkingBudget => params.thinking_budget,
+            Self::MaxTurns => params.max_turns,
+        }
+    }
+
+    pub const fn set(self, params: &mut ModelParams, value: Option<u32>) {
+        match self {
+            Self::ThinkingBudget => params.thinking_budget = value,
+            Self::MaxTurns => params.max_turns = value,
+        }
+    }
+
+    #[must_use]
+    pub fn display(self, value: Option<u32>) -> String {
+        match (self, value) {
+            (_, None) => "default".to_owned(),
+            (Self::ThinkingBudget, Some(tokens)) => format!("{tokens} tokens"),
+            (Self::MaxTurns, Some(turns)) => turns.to_string(),
+        }
+    }
+
+    /// Next value for the overlay's Left/Right keys. Stepping down from the
+    /// minimum returns to the default.
+    #[must_use]
+    pub fn stepped(self, value: Option<u32>, up: bool) -> Option<u32> {
+        let (min, max) = self.range();
+        match (value, up) {
+            (None, true) => Some(min),
+            (None, false) => None,
+            (Some(value), true) => Some(value.saturating_add(self.step()).min(max)),
+            (Some(value), false) if value <= min => None,
+            (Some(value), false) => Some(value.saturating_sub(self.step()).max(min)),
+        }
+    }
+
+    fn parse(self, text: &str) -> Result<Option<u32>, String> {
+        if matches!(text, "default" | "off" | "reset") {
+            return Ok(None);
+        }
+        let (min, max) = self.range();
+        let value: u32 = text.replace(['_', ','], "").parse().map_err(|_| {
+            format!("`{text}` is not a number. Usage: /set {} <n|default>", self.name())
+        })?;
+        if !(min..=max).contains(&value) {
+            return Err(format!("{} must be between {min} and {max}.", self.name()));
+        }
+        Ok(Some(value))
+    }
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    let [name, value] = args else {
+        if args.is_empty() {
+            return crate::app::config::open_model_params(app);
+        }
+        return Err(usage());
+    };
+    let param = ModelParam::from_name(name)
+        .ok_or_else(|| format!("The agent does not expose `{name}`. {}", usage()))?;
+    let mut params = app.model_params;
+    param.set(&mut params, param.parse(value)?);
+    apply(app, params)
+}
+
+/// Use `params` from now on, reloading the session when they changed.
+pub(crate) fn apply(app: &mut App, params: ModelParams) -> Result<(), String> {
+    if params == app.model_params {
+        return Ok(());
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err(
+            "Wait for the current turn to finish before changing model parameters.".to_owned()
+        );
+    }
+    app.model_params = params;
+    let summary = summary(&params);
+    if !reload_session(app, "Applying model parameters...".to_owned(), summary.clone()) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("{summary} They apply from the next session."),
+        );
+    }
+    Ok(())
+}
+
+fn values(params: &ModelParams) -> String {
+    let values: Vec<String> = ModelParam::ALL
+        .into_iter()
+        .map(|param| format!("{} {}", param.name(), param.display(param.get(params))))
+        .collect();
+    values.join(", ")
+}
+
+fn summary(params: &ModelParams) -> String {
+    format!("Model parameters: {}.", values(params))
+}
+
+/// Markdown line with the current values for `/stats`.
+#[must_use]
+pub fn stats_line(params: &ModelParams) -> String {
+    format!("**Model parameters:** {}", values(params))
+}
+
+fn usage() -> String {
+    let names: Vec<&str> = ModelParam::ALL.into_iter().map(ModelParam::name).collect();
+    format!("Usage: /set [{} <n|default>]", names.join("|"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn set_parses_values_and_rejects_unknown_params() {
+        let mut app = App::test_default();
+
+        start(&mut app, &["thinking-budget", "10_000"]).expect("set budget");
+        assert_eq!(app.model_params.thinking_budget, Some(10_000));
+        start(&mut app, &["thinking-budget", "default"]).expect("reset budget");
+        assert_eq!(app.model_params.thinking_budget, None);
+
+        assert_eq!(
+            start(&mut app, &["max-turns", "0"]),
+            Err("max-turns must be between 1 and 500.".to_owned())
+        );
+        assert_eq!(
+            start(&mut app, &["temperature", "0.2"]),
+            Err("The agent does not expose `temperature`. \
+                 Usage: /set [thinking-budget|max-turns <n|default>]"
+                .to_owned())
+        );
+    }
+
+    #[test]
+    fn stats_line_lists_every_parameter() {
+        let params = ModelParams { thinking_budget: Some(10_000), max_turns: None };
+        assert_eq!(
+            stats_line(&params),
+            "**Model parameters:** thinking-budget 10000 tokens, max-turns default"
+        );
+    }
+
+    #[test]
+    fn stepping_walks_the_range_and_back_to_default() {
+        let param = ModelParam::ThinkingBudget;
+
+        assert_eq!(param.stepped(None, true), Some(1_024));
+        assert_eq!(param.stepped(Some(1_024), true), Some(2_048));
+        assert_eq!(param.stepped(Some(127_999), true), Some(128_000));
+        assert_eq!(param.stepped(Some(1_024), false), None);
+        assert_eq!(ModelParam::MaxTurns.stepped(Some(3), false), Some(1));
+    }
+}
@@ -96,7 +96,7 @@ impl NotificationManager {
 
 /// Write the ASCII BEL character to stdout, causing a taskbar flash / dock
 /// bounce in most terminal emulators.
-fn ring_bell() {
+pub(crate) fn ring_bell() {
     use std::io::Write;
     let _ = std::io::stdout().write_all(b"\x07");
     let _ = std::io::stdout().flush();
@@ -0,0 +1,202 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command palette (Ctrl+p). Lists the named UI actions next to the key or
+//! slash command bound to them, so features can be found without knowing the
+//! shortcut. Typing fuzzy-filters the list by name or binding, Up/Down move,
+//! Enter runs the highlighted action and Esc closes. While open it takes every
+//! key press.
+
+use super::App;
+use super::config::{ConfigTab, SettingId, setting_specs};
+use super::fuzzy::{FuzzyMatch, fuzzy_match};
+use super::line_input::LineInput;
+use super::toast::{ToastKind, show_toast};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Debug, Clone, Copy)]
+enum Run {
+    Call(fn(&mut App)),
+    /// Submit this slash command as if it had been typed.
+    Slash(&'static str),
+}
+
+#[derive(Debug)]
+pub struct PaletteAction {
+    pub name: &'static str,
+    /// Key or slash command bound to the action.
+    pub binding: &'static str,
+    run: Run,
+}
+
+const fn call(name: &'static str, binding: &'static str, run: fn(&mut App)) -> PaletteAction {
+    PaletteAction { name, binding, run: Run::Call(run) }
+}
+
+const fn slash(name: &'static str, command: &'static str) -> PaletteAction {
+    PaletteAction { name, binding: command, run: Run::Slash(command) }
+}
+
+const ACTIONS: &[PaletteAction] = &[
+    call("Toggle todo panel", "Ctrl+t", super::keys::toggle_todo_panel_focus),
+    call("Collapse or expand tool calls", "Ctrl+o", super::keys::toggle_all_tool_calls),
+    call("Browse project files", "Ctrl+b", browse_files),
+    call("Toggle header", "Ctrl+h", super::keys::toggle_header),
+    call("Toggle token/cost annotations", "Ctrl+k", super::config::toggle_show_message_usage),
+    call("Toggle status log", "Ctrl+g", super::status_log::toggle_status_log),
+    call("Select chat text", "Ctrl+s", select_chat_text),
+    call("Filter transcript", "\\", super::transcript_filter::open),
+    call("Show all keys and commands", "F1", super::help_overlay::open),
+    call("Redraw screen", "Ctrl+l", redraw),
+    call("Toggle perf HUD", "F12", super::keys::toggle_perf_hud),
+    slash("Open settings", "/config"),
+    call("Switch theme", "/config", switch_theme),
+    slash("Open usage", "/usage"),
+    slash("Usage history, last 30 days", "/usage history"),
+    slash("Show session status", "/status"),
+    slash("Model parameters and tool timings", "/stats"),
+    slash("Open MCP servers", "/mcp"),
+    slash("Open plugins", "/plugins"),
+    slash("Show context usage", "/context"),
+    slash("Agent diagnostics", "/diagnostics"),
+    slash("Export transcript as HTML", "/share"),
+    slash("List bookmarks", "/bookmarks"),
+    slash("Cancel active turn", "/cancel"),
+    call("Quit", "Ctrl+q", super::confirm::request_quit),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    pub input: LineInput,
+    /// Index into the current matches.
+    pub selected: usize,
+}
+
+fn browse_files(app: &mut App) {
+    if let Err(err) = super::config::toggle_file_browser(app) {
+        tracing::warn!("failed to open file browser: {err}");
+    }
+}
+
+fn select_chat_text(app: &mut App) {
+    super::selection::clear_selection(app);
+    if !super::selection::enter_chat_visual_mode(app) {
+        show_toast(app, ToastKind::Info, "Nothing to select yet");
+    }
+}
+
+fn redraw(app: &mut App) {
+    app.force_redraw = true;
+}
+
+fn switch_theme(app: &mut App) {
+    if let Err(err) = super::config::open(app) {
+        show_toast(app, ToastKind::Error, err);
+        return;
+    }
+    super::config::activate_tab(app, ConfigTab::Settings);
+    if let Some(index) = setting_specs().iter().position(|spec| spec.id == SettingId::Theme) {
+        app.config.selected_setting_index = index;
+    }
+}
+
+/// Actions matching `query` by name or binding, best first. Name matches
+/// carry the matched positions for highlighting.
+#[must_use]
+pub fn matches(query: &str) -> Vec<(&'static PaletteAction, FuzzyMatch)> {
+    let mut found: Vec<_> = ACTIONS
+        .iter()
+        .filter_map(|action| {
+            let name = fuzzy_match(action.name, query);
+            let binding = fuzzy_match(action.binding, query)
+                .map(|found| FuzzyMatch { score: found.score, positions: Vec::new() });
+            let best = match (name, binding) {
+                (Some(name), Some(binding)) if binding.score > name.score => Some(binding),
+                (Some(name), _) => Some(name),
+                (None, binding) => binding,
+            };
+            best.map(|found| (action, found))
+        })
+        .collect();
+    // Stable: ties keep the table order.
+    found.sort_by_key(|(_, found)| std::cmp::Reverse(found.score));
+    found
+}
+
+pub(crate) fn open(app: &mut App) {
+    app.command_palette = Some(CommandPalette::default());
+    app.needs_redraw = true;
+}
+
+pub(crate) fn handle_key(app: &mut App, key: KeyEvent) {
+    let Some(palette) = app.command_palette.as_mut() else {
+        return;
+    };
+    app.needs_redraw = true;
+    match key.code {
+        KeyCode::Esc => app.command_palette = None,
+        KeyCode::Char('p') if key.modifiers == KeyModifiers::CONTROL => {
+            app.command_palette = None;
+        }
+        KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+        KeyCode::Down => {
+            let count = matches(&palette.input.text).len();
+            palette.selected = (palette.selected + 1).min(count.saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            let action = matches(&palette.input.text).get(palette.selected).map(|(a, _)| *a);
+            app.command_palette = None;
+            if let Some(action) = action {
+                run(app, action);
+            }
+        }
+        _ => {
+            if palette.input.handle_key(key) {
+                palette.selected = 0;
+            }
+        }
+    }
+}
+
+fn run(app: &mut App, action: &PaletteAction) {
+    match action.run {
+        Run::Call(call) => call(app),
+        Run::Slash(command) => {
+            // Commands that go on to the agent are left in the input to send.
+            if !super::slash::try_handle_submit(app, command) {
+                app.input.set_text(command);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_key(app, KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn queries_match_names_and_bindings() {
+        assert_eq!(matches("").len(), ACTIONS.len());
+        assert_eq!(matches("todo")[0].0.name, "Toggle todo panel");
+        assert_eq!(matches("ctrl+g")[0].0.name, "Toggle status log");
+        assert!(matches("zzzz").is_empty());
+    }
+
+    #[test]
+    fn enter_runs_the_selected_action_and_closes() {
+        let mut app = App::test_default();
+        open(&mut app);
+        for ch in "header".chars() {
+            press(&mut app, KeyCode::Char(ch));
+        }
+        let shown = app.show_header;
+        press(&mut app, KeyCode::Enter);
+        assert!(app.command_palette.is_none());
+        assert_eq!(app.show_header, !shown);
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detection of pasted file paths that can become `@` mentions.
+//!
+//! Dragging files from a file manager pastes their paths as plain text, in
+//! whatever form the terminal chooses: `file://` URLs, shell-escaped paths
+//! (`my\ file.txt`), or quoted paths (`"C:\Users\me\notes.md"`). A paste
+//! qualifies only when every token is an existing path; mentions under the
+//! session cwd are made relative so they match the `@` dropdown. Paths from
+//! the other side of a WSL boundary (`C:\Users\...` dropped into a Linux shell,
+//! `\\wsl$\Distro\...`, MSYS `/c/...`) are translated before the check.
+//! Dropped images become attachments once converted, like any image mention.
+
+use super::{App, SelectionPoint};
+use std::path::{Path, PathBuf};
+
+/// Pastes with more tokens than this are treated as ordinary text.
+const MAX_PASTED_PATHS: usize = 64;
+
+/// A recent paste of file paths that can still be converted to mentions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PastedPathsOffer {
+    /// Input position where the pasted text starts.
+    pub start: SelectionPoint,
+    /// Input position just past the pasted text.
+    pub end: SelectionPoint,
+    /// Mention targets (without `@`), in paste order.
+    pub mentions: Vec<String>,
+    /// Input version right after the paste; any later edit invalidates the offer.
+    pub input_version: u64,
+}
+
+impl PastedPathsOffer {
+    #[must_use]
+    pub fn hint(&self) -> String {
+        let images = self
+            .mentions
+            .iter()
+            .filter(|target| super::attachments::image_mime_type(Path::new(target)).is_some())
+            .count();
+        match (self.mentions.len(), images) {
+            (1, 1) => "Pasted an image \u{b7} Tab: attach as @ mention".to_owned(),
+            (1, _) => "Pasted a file path \u{b7} Tab: convert to @ mention".to_owned(),
+            (n, 0) => format!("Pasted {n} file paths \u{b7} Tab: convert to @ mentions"),
+            (n, images) => format!(
+                "Pasted {n} file paths ({images} image{}) \u{b7} Tab: convert to @ mentions",
+                if images == 1 { "" } else { "s" }
+            ),
+        }
+    }
+}
+
+/// Parse `text` as a list of existing file paths and return their mention
+/// targets. Returns `None` unless every token resolves to an existing path
+/// that can be written as a mention (mentions cannot contain whitespace).
+#[must_use]
+pub fn parse_pasted_paths(text: &str, cwd: &Path) -> Option<Vec<String>> {
+    let tokens = split_path_tokens(text.trim())?;
+    if tokens.is_empty() || tokens.len() > MAX_PASTED_PATHS {
+        return None;
+    }
+    let mut mentions: Vec<String> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let mention = token_to_mention(&token, cwd)?;
+        if !mentions.contains(&mention) {
+            mentions.push(mention);
+        }
+    }
+    Some(mentions)
+}
+
+/// The text inserted in place of the pasted paths.
+#[must_use]
+pub fn mention_text(mentions: &[String]) -> String {
+    mentions.iter().map(|target| format!("@{target}")).collect::<Vec<_>>().join(" ")
+}
+
+/// Return the pending offer if the input has not changed since the paste.
+#[must_use]
+pub fn active_offer(app: &App) -> Option<&PastedPathsOffer> {
+    app.pasted_paths_offer.as_ref().filter(|offer| offer.input_version == app.input.version)
+}
+
+/// Replace the pasted paths with `@` mentions. Returns `false` when there is
+/// no live offer.
+pub fn accept_offer(app: &mut App) -> bool {
+    let Some(offer) = app.pasted_paths_offer.take() else {
+        return false;
+    };
+    if offer.input_version != app.input.version {
+        return false;
+    }
+    super::strip_input_range(app, offer.start, offer.end);
+    app.input.insert_str(&mention_text(&offer.mentions));
+    app.needs_redraw = true;
+    true
+}
+
+/// Split pasted text on whitespace, honoring single and double quotes and
+/// backslash-escaped shell metacharacters. Backslashes before anything else
+/// are kept so Windows paths survive. Returns `None` on an unterminated quote.
+fn split_path_tokens(text: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if let Some(open) = quote {
+            if ch == open {
+                quote = None;
+            } else {
+                current.push(ch);
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => {
+                quote = Some(ch);
+                in_token = true;
+            }
+            '\\' if chars.peek().is_some_and(|next| is_shell_escaped(*next)) => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_token = true;
+            }
+            _ if ch.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            _ => {
+                current.push(ch);
+                in_token = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Some(tokens)
+}
+
+fn is_shell_escaped(ch: char) -> bool {
+    ch.is_whitespace() || "'\"()[]{}&;!$`#*?<>|~".contains(ch)
+}
+
+fn token_to_mention(token: &str, cwd: &Path) -> Option<String> {
+    let path = if let Some(url) = token.strip_prefix("file://") {
+        file_url_to_path(url)?
+    } else if let Some(rest) = token.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else {
+        // Bare words like `README.md` are more likely prose than a dragged file.
+        if !token.contains(['/', '\\']) {
+            return None;
+        }
+        PathBuf::from(token)
+    };
+    let resolved = if path.is_absolute() || is_windows_absolute(&path) {
+        path.clone()
+    } else {
+        cwd.join(&path)
+    };
+    let resolved = if resolved.exists() {
+        resolved
+    } else {
+        translate_foreign_path(&path).filter(|path| path.exists())?
+    };
+
+    let mention = match resolved.strip_prefix(cwd) {
+        Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+        _ => resolved.to_string_lossy().into_owned(),
+    };
+    (!mention.contains(char::is_whitespace)).then_some(mention)
+}
+
+/// `file:///home/me/a%20b.txt`, `file://localhost/tmp/x`, `file:///C:/dir/x`.
+fn file_url_to_path(rest: &str) -> Option<PathBuf> {
+    let path = match rest.find('/') {
+        Some(0) => rest,
+        Some(idx) if rest[..idx].eq_ignore_ascii_case("localhost") => &rest[idx..],
+        _ => return None,
+    };
+    let decoded = percent_decode(path)?;
+    // `/C:/dir` -> `C:/dir`
+    let bytes = decoded.as_bytes();
+    if bytes.len() >= 3 && bytes[0] == b'/' && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        return Some(PathBuf::from(&decoded[1..]));
+    }
+    Some(PathBuf::from(decoded))
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = text.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Map a path written for the other side of a WSL or MSYS boundary onto this
+/// host: `C:\dir` to `/mnt/c/dir` and `\\wsl$\Distro\home` to `/home` on
+/// Unix, `/c/dir` to `C:/dir` on Windows.
+fn translate_foreign_path(path: &Path) -> Option<PathBuf> {
+    let text = path.to_string_lossy().replace('\\', "/");
+    if cfg!(windows) {
+        let rest = text.strip_prefix('/')?;
+        let (drive, tail) = rest.split_at_checked(1)?;
+        if !drive.chars().all(|ch| ch.is_ascii_alphabetic()) || !tail.starts_with('/') {
+            return None;
+        }
+        return Some(PathBuf::from(format!("{}:{tail}", drive.to_ascii_uppercase())));
+    }
+    if is_windows_absolute(path) {
+        let drive = text[..1].to_ascii_lowercase();
+        return Some(PathBuf::from(format!("/mnt/{drive}{}", &text[2..])));
+    }
+    let unc = text.strip_prefix("//wsl$/").or_else(|| text.strip_prefix("//wsl.localhost/"))?;
+    let (_distro, rest) = unc.split_once('/')?;
+    Some(PathBuf::from(format!("/{rest}")))
+}
+
+/// `C:\dir` or `C:/dir`, which `Path::is_absolute` rejects on non-Windows hosts.
+fn is_windows_absolute(path: &Path) -> bool {
+    let bytes = path.as_os_str().as_encoded_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn cwd_with(files: &[&str]) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for file in files {
+            let path = tmp.path().join(file);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).expect("create parent");
+            }
+            std::fs::write(&path, "").expect("write file");
+        }
+        tmp
+    }
+
+    #[test]
+    fn absolute_relative_and_quoted_paths_become_mentions() {
+        let tmp = cwd_with(&["src/main.rs", "docs/guide.md"]);
+        let cwd = tmp.path();
+        let abs = cwd.join("src/main.rs").to_string_lossy().into_owned();
+
+        assert_eq!(
+            parse_pasted_paths(&format!("'{abs}'\n./docs/guide.md src/main.rs"), cwd),
+            Some(vec!["src/main.rs".to_owned(), "docs/guide.md".to_owned()])
+        );
+    }
+
+    #[test]
+    fn file_urls_are_percent_decoded() {
+        let tmp = cwd_with(&["a+b/c%d.txt"]);
+        let cwd = tmp.path();
+        let url = format!("file://{}/a%2Bb/c%25d.txt", cwd.to_string_lossy());
+
+        assert_eq!(parse_pasted_paths(&url, cwd), Some(vec!["a+b/c%d.txt".to_owned()]));
+        assert_eq!(file_url_to_path("server/share/x"), None);
+        assert_eq!(
+            file_url_to_path("/C:/Users/me/notes%20v2.md"),
+            Some(PathBuf::from("C:/Users/me/notes v2.md"))
+        );
+    }
+
+    #[test]
+    fn escapes_quotes_and_windows_backslashes_tokenize() {
+        assert_eq!(
+            split_path_tokens(r#"my\ file.txt "C:\Program Files\x.exe" C:\dir\y"#),
+            Some(vec![
+                "my file.txt".to_owned(),
+                r"C:\Program Files\x.exe".to_owned(),
+                r"C:\dir\y".to_owned(),
+            ])
+        );
+        assert_eq!(split_path_tokens("'unterminated"), None);
+        assert!(is_windows_absolute(Path::new(r"C:\dir\y")));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn windows_and_wsl_share_paths_translate_to_unix_mounts() {
+        assert_eq!(
+            translate_foreign_path(Path::new(r"C:\Users\me\shot.png")),
+            Some(PathBuf::from("/mnt/c/Users/me/shot.png"))
+        );
+        assert_eq!(
+            translate_foreign_path(Path::new(r"\\wsl$\Ubuntu\home\me\a.rs")),
+            Some(PathBuf::from("/home/me/a.rs"))
+        );
+        assert_eq!(
+            translate_foreign_path(Path::new("//wsl.localhost/Debian/etc/hosts")),
+            Some(PathBuf::from("/etc/hosts"))
+        );
+        assert_eq!(translate_foreign_path(Path::new("/home/me/a.rs")), None);
+
+        let offer = PastedPathsOffer {
+            start: SelectionPoint { row: 0, col: 0 },
+            end: SelectionPoint { row: 0, col: 0 },
+            mentions: vec!["shot.PNG".to_owned(), "src/main.rs".to_owned()],
+            input_version: 0,
+        };
+        assert_eq!(offer.hint(), "Pasted 2 file paths (1 image) \u{b7} Tab: convert to @ mentions");
+    }
+
+    #[test]
+    fn prose_missing_paths_and_paths_with_spaces_are_rejected() {
+        let tmp = cwd_with(&["src/main.rs", "with space/a.rs", "README.md"]);
+        let cwd = tmp.path();
+
+        assert_eq!(parse_pasted_paths("see src/main.rs", cwd), None);
+        assert_eq!(parse_pasted_paths("README.md", cwd), None);
+        assert_eq!(parse_pasted_paths("src/missing.rs", cwd), None);
+        assert_eq!(parse_pasted_paths(r"with\ space/a.rs", cwd), None);
+        assert_eq!(parse_pasted_paths("   ", cwd), None);
+    }
+}
@@ -347,12 +347,14 @@ mod tests {
             sdk_tool_name: "Read".to_owned(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::InProgress,
             content: Vec::new(),
             collapsed: false,
             hidden: false,
             terminal_id: None,
             terminal_command: None,
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -366,6 +368,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         }
     }
 
@@ -374,6 +379,7 @@ mod tests {
             role: MessageRole::Assistant,
             blocks: vec![MessageBlock::ToolCall(Box::new(tc))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -0,0 +1,87 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pinned terminal output. Pressing `p` in keyboard selection mode (Ctrl+s)
+//! on a message with a shell command pins that command's output to a pane
+//! under the chat, which keeps following the live output while the
+//! transcript scrolls or the conversation moves on. `p` on the same message,
+//! or on one without a command, unpins it.
+
+use super::toast::{ToastKind, show_toast};
+use super::{App, MessageBlock, ToolCallInfo, is_execute_tool_name};
+use crate::agent::model;
+
+/// Pin or unpin the command output of the message under the selection
+/// cursor. Returns false when there is nothing to pin or unpin there.
+pub(crate) fn toggle_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    match command_in_message(app, message_index) {
+        Some(id) if app.pinned_tool_call.as_deref() != Some(id.as_str()) => {
+            app.pinned_tool_call = Some(id);
+            show_toast(app, ToastKind::Info, "Output pinned below the chat");
+            true
+        }
+        _ if app.pinned_tool_call.is_some() => {
+            unpin(app);
+            true
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn unpin(app: &mut App) {
+    if app.pinned_tool_call.take().is_some() {
+        show_toast(app, ToastKind::Info, "Output unpinned");
+    }
+}
+
+/// The pinned tool call, while it is still in the transcript.
+#[must_use]
+pub fn pinned_tool_call(app: &App) -> Option<&ToolCallInfo> {
+    let id = app.pinned_tool_call.as_deref()?;
+    let (message_index, block_index) = app.lookup_tool_call(id)?;
+    match app.messages.get(message_index)?.blocks.get(block_index)? {
+        MessageBlock::ToolCall(tc) => Some(tc.as_ref()),
+        _ => None,
+    }
+}
+
+/// Id of the shell command in a message: the running one, else the last.
+fn command_in_message(app: &App, message_index: usize) -> Option<String> {
+    let commands: Vec<&ToolCallInfo> = app
+        .messages
+        .get(message_index)?
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            MessageBlock::ToolCall(tc) if !tc.hidden && is_execute_tool_name(&tc.sdk_tool_name) => {
+                Some(tc.as_ref())
+            }
+            _ => None,
+        })
+        .collect();
+    let running = commands.iter().find(|tc| tc.status == model::ToolCallStatus::InProgress);
+    running.or(commands.last()).map(|tc| tc.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_to_a_dropped_tool_call_shows_nothing_and_unpins() {
+        let mut app = App::test_default();
+        app.pinned_tool_call = Some("tool-gone".to_owned());
+
+        assert!(pinned_tool_call(&app).is_none());
+        unpin(&mut app);
+        assert!(app.pinned_tool_call.is_none());
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/pr`: send the commits and diff of the current branch against its base
+//! to the agent for a pull request title and description, open the draft in
+//! an editable overlay, then push the branch and create the pull request
+//! with the forge CLI once the user confirms it there.
+//!
+//! The CLI is `gh` unless `forgeCli` in `~/.claude.json` names another one;
+//! a `glab` binary gets GitLab's `mr create` arguments, anything else is
+//! called like `gh`.
+
+use super::commit::{PendingDraft, git, push_diff, writes_blocked};
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+use crate::agent::events::ClientEvent;
+use crate::app::config::store;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt as _;
+
+const DEFAULT_FORGE_CLI: &str = "gh";
+const DEFAULT_REMOTE: &str = "origin";
+/// Pull request templates the draft should follow, in lookup order.
+const TEMPLATE_PATHS: &[&str] = &[
+    ".github/pull_request_template.md",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PullRequestOptions {
+    /// Branch the pull request merges into.
+    pub base: String,
+    /// Branch being proposed.
+    pub head: String,
+    /// Remote the head branch is pushed to.
+    pub remote: String,
+    pub draft: bool,
+}
+
+/// A `/pr` waiting for the agent's draft.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPullRequestDraft {
+    pub options: PullRequestOptions,
+    /// Commit subjects and `git diff --stat` of the branch.
+    pub summary: String,
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    const USAGE: &str = "Usage: /pr [--draft] [--base <branch>]";
+    let mut draft = false;
+    let mut base = None;
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match *arg {
+            "--draft" => draft = true,
+            "--base" => base = Some((*rest.next().ok_or(USAGE)?).to_owned()),
+            other => match other.strip_prefix("--base=") {
+                Some(value) if !value.is_empty() => base = Some(value.to_owned()),
+                _ => return Err(USAGE.to_owned()),
+            },
+        }
+    }
+    if writes_blocked(app) {
+        return Err("/pr is unavailable in plan mode and read-only sessions.".to_owned());
+    }
+    if app.is_offline() {
+        return Err("/pr is unavailable in offline mode.".to_owned());
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /pr.".to_owned());
+    }
+
+    let cwd = app.cwd_raw.clone();
+    git(&cwd, &["rev-parse", "--is-inside-work-tree"])
+        .map_err(|_| "/pr needs a git repository.".to_owned())?;
+    let head = git(&cwd, &["rev-parse", "--abbrev-ref", "HEAD"])?.trim().to_owned();
+    if head == "HEAD" {
+        return Err("/pr needs a checked-out branch, not a detached HEAD.".to_owned());
+    }
+    let remote = git(&cwd, &["config", &format!("branch.{head}.remote")])
+        .map_or_else(|_| DEFAULT_REMOTE.to_owned(), |remote| remote.trim().to_owned());
+    let base = base.unwrap_or_else(|| default_base(&cwd, &remote));
+    if head == base {
+        return Err(format!("You are on {base}; switch to a feature branch before running /pr."));
+    }
+    // Compare with the remote's copy of the base when there is one, so a
+    // stale local base does not pull merged work into the diff.
+    let remote_base = format!("{remote}/{base}");
+    let base_ref = if git(&cwd, &["rev-parse", "--verify", "--quiet", &remote_base]).is_ok() {
+        remote_base
+    } else {
+        base.clone()
+    };
+    let commits =
+        git(&cwd, &["log", "--reverse", "--format=- %s", &format!("{base_ref}..HEAD")])
+            .map_err(|_| format!("Cannot compare {head} with {base}; pass --base <branch>."))?;
+    if commits.trim().is_empty() {
+        return Err(format!("{head} has no commits ahead of {base}."));
+    }
+    let range = format!("{base_ref}...HEAD");
+    let stat = git(&cwd, &["diff", "--stat", &range])?;
+    let diff = git(&cwd, &["diff", &range])?;
+
+    if app.conn.is_none() || app.session_id.is_none() {
+        return Err("/pr needs a connected session to draft the description.".to_owned());
+    }
+    let template = TEMPLATE_PATHS
+        .iter()
+        .find_map(|path| std::fs::read_to_string(Path::new(&cwd).join(path)).ok());
+    let options = PullRequestOptions { base, head, remote, draft };
+    let prompt = draft_prompt(&options, &commits, &stat, &diff, template.as_deref());
+    app.pending_draft = Some(PendingDraft::PullRequest(PendingPullRequestDraft {
+        options,
+        summary: format!("{}\n{}", commits.trim_end(), stat.trim_end()),
+    }));
+    let display = std::iter::once("/pr").chain(args.iter().copied()).collect::<Vec<_>>();
+    super::input_submit::dispatch_prompt_turn_as(app, &display.join(" "), prompt);
+    Ok(())
+}
+
+/// The branch `<remote>/HEAD` points at, else `main`.
+fn default_base(cwd: &str, remote: &str) -> String {
+    git(cwd, &["symbolic-ref", "--quiet", "--short", &format!("refs/remotes/{remote}/HEAD")])
+        .ok()
+        .and_then(|name| name.trim().strip_prefix(&format!("{remote}/")).map(str::to_owned))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "main".to_owned())
+}
+
+fn draft_prompt(
+    options: &PullRequestOptions,
+    commits: &str,
+    stat: &str,
+    diff: &str,
+    template: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "Write a pull request title and description for merging `{}` into `{}`. Reply with \
+         only the title on the first line (at most 72 characters), a blank line, then the \
+         description in Markdown: what changed, why, and how it was tested if the commits \
+         say so. No code fences around the reply, no commentary, and do not run any tools.\n",
+        options.head, options.base
+    );
+    if let Some(template) = template.map(str::trim).filter(|text| !text.is_empty()) {
+        let _ = write!(prompt, "\nFollow the repository's pull request template:\n{template}\n");
+    }
+    let _ = write!(prompt, "\nCommits:\n{}\n", commits.trim_end());
+    push_diff(&mut prompt, stat, diff);
+    prompt
+}
+
+/// Split edited text into title and body. The title is the first non-empty
+/// line with any Markdown heading or `Title:` prefix removed.
+#[must_use]
+pub(crate) fn split_title_body(text: &str) -> (String, String) {
+    let text = text.trim_start();
+    let (first, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = first.trim().trim_start_matches('#').trim_start();
+    let title = title.strip_prefix("Title:").unwrap_or(title).trim();
+    (title.to_owned(), body.trim().to_owned())
+}
+
+/// Push the branch and create the pull request in the background.
+pub(crate) fn create(app: &mut App, title: &str, body: &str, options: &PullRequestOptions) {
+    if writes_blocked(app) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Warning),
+            "Pull request skipped: the session switched to plan mode.",
+        );
+        return;
+    }
+    let forge = store::forge_cli(&app.config.committed_preferences_document)
+        .unwrap_or_else(|| DEFAULT_FORGE_CLI.to_owned());
+    if which::which(&forge).is_err() {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Error),
+            &format!("Pull request not created: `{forge}` is not installed or not on PATH."),
+        );
+        return;
+    }
+    let (args, stdin_body) = forge_args(&forge, title, body, options);
+    let mut push = tokio::process::Command::new("git");
+    push.args(["push", "--set-upstream", &options.remote, "HEAD"])
+        .current_dir(&app.cwd_raw)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut create = tokio::process::Command::new(&forge);
+    create
+        .args(&args)
+        .current_dir(&app.cwd_raw)
+        .stdin(if stdin_body.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some("Creating pull request...".to_owned());
+    let tx = app.event_tx.clone();
+    tokio::task::spawn_local(async move {
+        let result = run_create(push, create, stdin_body).await;
+        let _ = tx.send(ClientEvent::PullRequestFinished { result });
+    });
+}
+
+/// Arguments for `forge`, plus the body when it goes over stdin.
+fn forge_args(
+    forge: &str,
+    title: &str,
+    body: &str,
+    options: &PullRequestOptions,
+) -> (Vec<String>, Option<String>) {
+    let is_glab = Path::new(forge).file_stem().is_some_and(|stem| stem == "glab");
+    let mut args: Vec<String> = if is_glab {
+        [
+            "mr",
+            "create",
+            "--title",
+            title,
+            "--description",
+            body,
+            "--target-branch",
+            options.base.as_str(),
+            "--source-branch",
+            options.head.as_str(),
+            "--yes",
+        ]
+        .map(str::to_owned)
+        .to_vec()
+    } else {
+        [
+            "pr",
+            "create",
+            "--title",
+            title,
+            "--body-file",
+            "-",
+            "--base",
+            options.base.as_str(),
+            "--head",
+            options.head.as_str(),
+        ]
+        .map(str::to_owned)
+        .to_vec()
+    };
+    if options.draft {
+        args.push("--draft".to_owned());
+    }
+    let stdin_body = (!is_glab).then(|| body.to_owned());
+    (args, stdin_body)
+}
+
+async fn run_create(
+    mut push: tokio::process::Command,
+    mut create: tokio::process::Command,
+    body: Option<String>,
+) -> Result<String, String> {
+    let pushed = push.output().await.map_err(|err| format!("Failed to run git push: {err}"))?;
+    if !pushed.status.success() {
+        return Err(format!("git push failed: {}", String::from_utf8_lossy(&pushed.stderr).trim()));
+    }
+    let mut child = create.spawn().map_err(|err| format!("Failed to run the forge CLI: {err}"))?;
+    if let (Some(mut stdin), Some(body)) = (child.stdin.take(), body) {
+        stdin
+            .write_all(body.as_bytes())
+            .await
+            .map_err(|err| format!("Failed to pass the description: {err}"))?;
+    }
+    let output = child.wait_with_output().await.map_err(|err| err.to_string())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.success() {
+        Ok(created_url(&stdout).unwrap_or_else(|| stdout.trim().to_owned()))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = [stderr.trim(), stdout.trim()].into_iter().find(|text| !text.is_empty());
+        Err(detail.unwrap_or("the forge CLI failed").to_owned())
+    }
+}
+
+/// The last URL the forge CLI printed, which is the new pull request.
+fn created_url(stdout: &str) -> Option<String> {
+    stdout
+        .split_whitespace()
+        .rev()
+        .find(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn options(draft: bool) -> PullRequestOptions {
+        PullRequestOptions {
+            base: "main".to_owned(),
+            head: "fix-scroll".to_owned(),
+            remote: "origin".to_owned(),
+            draft,
+        }
+    }
+
+    #[test]
+    fn title_and_body_are_split_from_the_draft() {
+        assert_eq!(
+            split_title_body("\n# Fix scroll jump\n\nKeeps the viewport.\n"),
+            ("Fix scroll jump".to_owned(), "Keeps the viewport.".to_owned())
+        );
+        assert_eq!(split_title_body("Title: Add /pr"), ("Add /pr".to_owned(), String::new()));
+    }
+
+    #[test]
+    fn forge_arguments_match_the_cli() {
+        let (args, stdin) = forge_args("gh", "T", "B", &options(true));
+        assert_eq!(
+            args,
+            [
+                "pr",
+                "create",
+                "--title",
+                "T",
+                "--body-file",
+                "-",
+                "--base",
+                "main",
+                "--head",
+                "fix-scroll",
+                "--draft",
+            ]
+        );
+        assert_eq!(stdin.as_deref(), Some("B"));
+
+        let (args, stdin) = forge_args("/usr/local/bin/glab", "T", "B", &options(false));
+        assert_eq!(
+            args,
+            [
+                "mr",
+                "create",
+                "--title",
+                "T",
+                "--description",
+                "B",
+                "--target-branch",
+                "main",
+                "--source-branch",
+                "fix-scroll",
+                "--yes",
+            ]
+        );
+        assert_eq!(stdin, None);
+    }
+
+    #[test]
+    fn created_url_is_the_last_link_printed() {
+        let stdout =
+            "Creating pull request for fix-scroll into main\n\nhttps://github.com/o/r/pull/7\n";
+        assert_eq!(created_url(stdout).as_deref(), Some("https://github.com/o/r/pull/7"));
+        assert_eq!(created_url("done"), None);
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Short session recap printed to the normal screen after the TUI exits, so
+//! the resume command is still on screen once the alternate buffer is gone.
+
+use super::{App, MessageBlock, MessageRole};
+use crate::agent::model;
+use crate::ui::format_tokens;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// The recap for this run, or `None` when no session was started.
+#[must_use]
+pub fn quit_summary(app: &App) -> Option<String> {
+    let turns = count_turns(app);
+    let session_id = app.session_id.as_ref();
+    if session_id.is_none() && turns == 0 {
+        return None;
+    }
+
+    let mut out = String::from("Session summary\n");
+    let mut row = |label: &str, value: &str| {
+        let _ = writeln!(out, "  {label:<14}{value}");
+    };
+    row("Duration", &format_duration(app.launched_at.elapsed()));
+    row("Turns", &turns.to_string());
+    row("Files changed", &count_changed_files(app).to_string());
+    let usage = &app.session_usage;
+    let mut tokens = Vec::with_capacity(2);
+    if let Some(context) = usage.context_tokens() {
+        tokens.push(format!("{} in context", format_tokens(context)));
+    }
+    tokens.push(format!("{} output", format_tokens(usage.total_output_tokens)));
+    row("Tokens", &tokens.join(" \u{b7} "));
+    if let Some(cost) = usage.total_cost_usd {
+        row("Cost", &format!("${cost:.4}"));
+    }
+    if let Some(session_id) = session_id {
+        row("Session", &session_id.to_string());
+        let _ = write!(out, "Resume with: claude-rs --resume {session_id}");
+    }
+    Some(out.trim_end().to_owned())
+}
+
+/// Prompts the user sent; local slash commands are not turns.
+fn count_turns(app: &App) -> usize {
+    app.messages
+        .iter()
+        .filter(|message| matches!(message.role, MessageRole::User))
+        .filter(|message| {
+            !matches!(
+                message.blocks.first(),
+                Some(MessageBlock::Text(block)) if block.text.starts_with('/')
+            )
+        })
+        .count()
+}
+
+/// Distinct paths touched by completed edits still in the transcript.
+fn count_changed_files(app: &App) -> usize {
+    let mut paths = HashSet::new();
+    for message in &app.messages {
+        for block in &message.blocks {
+            let MessageBlock::ToolCall(tc) = block else {
+                continue;
+            };
+            if tc.status != model::ToolCallStatus::Completed {
+                continue;
+            }
+            for content in &tc.content {
+                if let model::ToolCallContent::Diff(diff) = content {
+                    paths.insert(diff.path.as_path());
+                }
+            }
+        }
+    }
+    paths.len()
+}
+
+/// `42s`, `12m 04s`, `1h 05m`.
+fn format_duration(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs / 60 % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ChatMessage {
+        ChatMessage {
+            role: MessageRole::User,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn summary_counts_prompts_and_includes_resume_command() {
+        let mut app = App::test_default();
+        assert_eq!(quit_summary(&app), None);
+
+        app.messages.push(user_message("fix the build"));
+        app.messages.push(user_message("/status"));
+        app.session_usage.total_output_tokens = 1_234;
+        app.session_usage.total_cost_usd = Some(0.5);
+        app.session_id = Some(model::SessionId::new("abc-123"));
+
+        let summary = quit_summary(&app).expect("summary");
+        assert!(summary.contains("  Turns         1"));
+        assert!(summary.contains("  Files changed 0"));
+        assert!(summary.contains("1.2k output"));
+        assert!(summary.contains("$0.5000"));
+        assert!(summary.ends_with("Resume with: claude-rs --resume abc-123"));
+        assert_eq!(format_duration(Duration::from_secs(725)), "12m 05s");
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Revised code blocks. When a finished reply contains a code block that is a
+//! small edit of a code block from an earlier reply (same language, mostly
+//! the same words), the block is marked as a revision and renders as a
+//! compact word diff against the earlier code. `o` in keyboard selection mode
+//! (Ctrl+s) switches a message's revised blocks between the diff and the full
+//! text. The "Diff revised code" setting turns the detection off.
+
+use super::{App, InvalidationLevel, MessageBlock, MessageRole, TextRevision};
+use similar::TextDiff;
+
+/// Smallest word similarity for a block to count as a revision.
+const MIN_SIMILARITY: f32 = 0.6;
+/// Code blocks with fewer lines are short enough to repeat in full.
+const MIN_LINES: usize = 4;
+/// How many earlier messages are searched for the original block.
+const SEARCH_WINDOW: usize = 60;
+
+/// Language and body of a text block that is exactly one fenced code block.
+#[must_use]
+pub fn fenced_code(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim();
+    let rest = text.strip_prefix("```")?;
+    let (lang, rest) = rest.split_once('\n')?;
+    let body = rest.strip_suffix("```")?;
+    if body.lines().any(|line| line.trim_start().starts_with("```")) {
+        return None;
+    }
+    Some((lang.trim(), body.strip_suffix('\n').unwrap_or(body)))
+}
+
+/// Mark revised code blocks in the replies of the turn that just finished.
+pub(crate) fn mark_last_turn(app: &mut App) {
+    if !app.config.revision_diffs_effective() {
+        return;
+    }
+    let start = app
+        .messages
+        .iter()
+        .rposition(|message| matches!(message.role, MessageRole::User))
+        .map_or(0, |index| index + 1);
+    for index in start..app.messages.len() {
+        mark_message(app, index);
+    }
+}
+
+/// Mark revised code blocks across the whole transcript, e.g. after a resume.
+pub(crate) fn mark_all(app: &mut App) {
+    if !app.config.revision_diffs_effective() {
+        return;
+    }
+    for index in 0..app.messages.len() {
+        mark_message(app, index);
+    }
+}
+
+/// Drop every revision mark so all blocks render in full again.
+pub(crate) fn clear_all(app: &mut App) {
+    for message in &mut app.messages {
+        for block in &mut message.blocks {
+            if let MessageBlock::Text(text) = block
+                && text.revision.take().is_some()
+            {
+                text.cache.invalidate();
+            }
+        }
+    }
+    app.invalidate_layout(InvalidationLevel::Global);
+}
+
+fn mark_message(app: &mut App, index: usize) {
+    if !matches!(app.messages[index].role, MessageRole::Assistant) {
+        return;
+    }
+    let mut changed = false;
+    for block_index in 0..app.messages[index].blocks.len() {
+        let MessageBlock::Text(block) = &app.messages[index].blocks[block_index] else {
+            continue;
+        };
+        if block.revision.is_some() {
+            continue;
+        }
+        let Some((lang, body)) = fenced_code(&block.text) else {
+            continue;
+        };
+        if body.lines().count() < MIN_LINES {
+            continue;
+        }
+        let Some(previous) = find_original(app, index, block_index, lang, body) else {
+            continue;
+        };
+        if let MessageBlock::Text(block) = &mut app.messages[index].blocks[block_index] {
+            block.revision = Some(Box::new(TextRevision { previous, expanded: false }));
+            block.cache.invalidate();
+            changed = true;
+        }
+    }
+    if changed {
+        app.invalidate_layout(InvalidationLevel::Single(index));
+    }
+}
+
+/// The closest earlier assistant code block in `lang` that `body` revises.
+fn find_original(
+    app: &App,
+    index: usize,
+    block_index: usize,
+    lang: &str,
+    body: &str,
+) -> Option<String> {
+    let first = index.saturating_sub(SEARCH_WINDOW);
+    let mut best: Option<(f32, &str)> = None;
+    for (candidate_index, message) in app.messages[first..=index].iter().enumerate().rev() {
+        if !matches!(message.role, MessageRole::Assistant) {
+            continue;
+        }
+        let blocks = if first + candidate_index == index {
+            &message.blocks[..block_index]
+        } else {
+            &message.blocks[..]
+        };
+        for block in blocks {
+            let MessageBlock::Text(text) = block else {
+                continue;
+            };
+            let Some((earlier_lang, earlier)) = fenced_code(&text.text) else {
+                continue;
+            };
+            if earlier_lang != lang || earlier == body {
+                continue;
+            }
+            let ratio = TextDiff::from_words(earlier, body).ratio();
+            if ratio >= MIN_SIMILARITY && best.is_none_or(|(best_ratio, _)| ratio > best_ratio) {
+                best = Some((ratio, earlier));
+            }
+        }
+    }
+    best.map(|(_, earlier)| earlier.to_owned())
+}
+
+/// Switch the revised blocks of the message under the selection cursor
+/// between the word diff and the full text. Returns false when it has none.
+pub(crate) fn toggle_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    let Some(message) = app.messages.get_mut(message_index) else {
+        return false;
+    };
+    let mut revisions: Vec<_> = message
+        .blocks
+        .iter_mut()
+        .filter_map(|block| match block {
+            MessageBlock::Text(text) if text.revision.is_some() => Some(text),
+            _ => None,
+        })
+        .collect();
+    if revisions.is_empty() {
+        return false;
+    }
+    let expand = revisions
+        .iter()
+        .any(|text| text.revision.as_ref().is_some_and(|revision| !revision.expanded));
+    for text in &mut revisions {
+        if let Some(revision) = text.revision.as_mut() {
+            revision.expanded = expand;
+        }
+        text.cache.invalidate();
+    }
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    fn reply(text: &str) -> ChatMessage {
+        ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    fn revision_of(app: &App, index: usize) -> Option<&str> {
+        match &app.messages[index].blocks[0] {
+            MessageBlock::Text(text) => text.revision.as_ref().map(|r| r.previous.as_str()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn fenced_code_requires_a_single_fence() {
+        assert_eq!(fenced_code("```rust\nfn a() {}\n```\n\n"), Some(("rust", "fn a() {}")));
+        assert_eq!(fenced_code("Here:\n```rust\nfn a() {}\n```"), None);
+        assert_eq!(fenced_code("```\na\n```\n```\nb\n```"), None);
+    }
+
+    #[test]
+    fn small_edits_of_earlier_code_are_marked() {
+        let original =
+            "```rust\nfn add(a: i32, b: i32) -> i32 {\n    let sum = a - b;\n    sum\n}\n```";
+        let fixed =
+            "```rust\nfn add(a: i32, b: i32) -> i32 {\n    let sum = a + b;\n    sum\n}\n```";
+        let unrelated = "```python\nimport os\nprint(os.getcwd())\nprint('done')\nexit(0)\n```";
+        let mut app = App::test_default();
+        app.messages = vec![reply(original), reply(unrelated), reply(fixed)];
+
+        mark_all(&mut app);
+
+        assert_eq!(revision_of(&app, 0), None);
+        assert_eq!(revision_of(&app, 1), None);
+        assert_eq!(revision_of(&app, 2), fenced_code(original).map(|(_, body)| body));
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversation rewind. Pressing `x` in keyboard selection mode (Ctrl+s)
+//! offers to cut the transcript back to the start of the turn under the
+//! cursor: that user message and everything after it are removed, its text
+//! returns to the input for editing, and the next prompt tells the agent to
+//! disregard the dropped turns.
+
+use super::confirm::{ConfirmAction, ConfirmDialog, open_confirm};
+use super::events::push_system_message_with_severity;
+use super::toast::{ToastKind, show_toast};
+use super::turn_folds::turn_start;
+use super::{App, AppStatus, MessageBlock, MessageRole, SystemSeverity};
+use crate::ui::width::truncate_with_ellipsis;
+
+/// Longest excerpt of the rewound message quoted in the note, in terminal
+/// cells.
+const MAX_EXCERPT_WIDTH: usize = 80;
+
+/// Ask to rewind to the turn under the selection cursor. Returns false when
+/// no user message starts at or above the cursor.
+pub(crate) fn rewind_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    let Some(index) = turn_start(app, message_index) else {
+        return false;
+    };
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        show_toast(app, ToastKind::Info, "Wait for the current turn to finish before rewinding");
+        return true;
+    }
+    let removed = app.messages.len() - index;
+    open_confirm(
+        app,
+        ConfirmDialog::yes_no(
+            "Rewind the conversation?",
+            format!(
+                "Removes {removed} message{} from \"{}\" onward and asks the agent to forget them. \
+                 The message text returns to the input.",
+                if removed == 1 { "" } else { "s" },
+                excerpt(&user_text(app, index)),
+            ),
+            "Rewind",
+            ConfirmAction::Rewind(index),
+        ),
+    );
+    true
+}
+
+/// Drop the user message at `index` and everything after it.
+pub(crate) fn rewind_to(app: &mut App, index: usize) {
+    if !app.messages.get(index).is_some_and(|message| matches!(message.role, MessageRole::User)) {
+        return;
+    }
+    let text = user_text(app, index);
+    let removed = app.messages.len() - index;
+    app.truncate_messages(index);
+    super::bookmarks::forget_from(app, index);
+    app.input.set_text(&text);
+    app.pending_rewind_note = Some(format!(
+        "The user rewound the conversation to before their message \"{}\". \
+         Disregard that message and every turn after it; continue from the state before it.",
+        excerpt(&text)
+    ));
+    push_system_message_with_severity(
+        app,
+        Some(SystemSeverity::Info),
+        &format!(
+            "Rewound {removed} message{}. The agent is told to disregard them with your next prompt.",
+            if removed == 1 { "" } else { "s" }
+        ),
+    );
+}
+
+/// `prompt` with any pending rewind note prepended. Consumes the note.
+pub(super) fn with_rewind_note(app: &mut App, prompt: String) -> String {
+    match app.pending_rewind_note.take() {
+        Some(note) => format!("<rewind>\n{note}\n</rewind>\n\n{prompt}"),
+        None => prompt,
+    }
+}
+
+fn user_text(app: &App, index: usize) -> String {
+    app.messages
+        .get(index)
+        .map(|message| {
+            message
+                .blocks
+                .iter()
+                .filter_map(|block| match block {
+                    MessageBlock::Text(text) => Some(text.text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+fn excerpt(text: &str) -> String {
+    let line = text.lines().find(|line| !line.trim().is_empty()).unwrap_or_default().trim();
+    truncate_with_ellipsis(line, MAX_EXCERPT_WIDTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, TextBlock};
+    use crate::ui::width::display_width;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: MessageRole, text: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn rewind_truncates_at_the_turn_and_restores_the_prompt() {
+        let mut app = App::test_default();
+        app.messages = vec![
+            message(MessageRole::User, "add a parser"),
+            message(MessageRole::Assistant, "done"),
+            message(MessageRole::User, "now rewrite it in assembly"),
+            message(MessageRole::Assistant, "sure"),
+        ];
+
+        assert_eq!(turn_start(&app, 3), Some(2));
+        rewind_to(&mut app, 2);
+
+        assert_eq!(app.messages.len(), 3);
+        assert!(matches!(app.messages[2].role, MessageRole::System(_)));
+        assert_eq!(app.input.text(), "now rewrite it in assembly");
+        let prompt = with_rewind_note(&mut app, "keep it in Rust".to_owned());
+        assert!(prompt.starts_with("<rewind>\nThe user rewound the conversation"));
+        assert!(prompt.ends_with("</rewind>\n\nkeep it in Rust"));
+        assert_eq!(with_rewind_note(&mut app, "next".to_owned()), "next");
+    }
+
+    #[test]
+    fn excerpt_uses_first_line_and_truncates() {
+        assert_eq!(excerpt("\n  fix the build \nplease"), "fix the build");
+        let long = "x".repeat(100);
+        assert_eq!(display_width(&excerpt(&long)), MAX_EXCERPT_WIDTH);
+        let wide = "\u{4F60}".repeat(60);
+        assert_eq!(display_width(&excerpt(&wide)), MAX_EXCERPT_WIDTH - 1);
+    }
+}
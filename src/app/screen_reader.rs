@@ -0,0 +1,154 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Screen reader mode. While it is on, spinners hold still, scrolling jumps
+//! instead of easing, tool and connection state is written out as text rather
+//! than told apart by color, and execute blocks drop their box drawing. With
+//! `--screen-reader-mirror`, finished replies are also appended as plain text
+//! to a file or tty the screen reader can follow outside the TUI.
+//!
+//! The effective flag is mirrored into a process-wide atomic so render code
+//! deep in the tool-call and footer paths can check it without an `App`.
+
+use super::{App, InvalidationLevel, MessageBlock, MessageRole};
+use crate::agent::model;
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+pub struct ScreenReader {
+    /// Set by `--screen-reader`; wins over the stored setting.
+    pub forced: bool,
+    mirror: Option<File>,
+}
+
+impl ScreenReader {
+    #[must_use]
+    pub fn new(forced: bool, mirror_path: Option<&Path>) -> Self {
+        let mirror = mirror_path.and_then(|path| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .inspect_err(|err| {
+                    tracing::warn!(path = %path.display(), "cannot open screen reader mirror: {err}");
+                })
+                .ok()
+        });
+        Self { forced, mirror }
+    }
+}
+
+/// Whether screen reader mode is on for this process.
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Apply the effective mode after startup or a settings change, re-rendering
+/// anything that drew state with glyphs or borders.
+pub(crate) fn sync(app: &mut App) {
+    let enabled = app.screen_reader.forced || app.config.screen_reader_effective();
+    if ENABLED.swap(enabled, Ordering::Relaxed) == enabled {
+        return;
+    }
+    for msg in &mut app.messages {
+        for block in &mut msg.blocks {
+            if let MessageBlock::ToolCall(tc) = block {
+                tc.mark_tool_call_layout_dirty();
+            }
+        }
+    }
+    app.invalidate_layout(InvalidationLevel::Global);
+    app.cached_footer_line = None;
+}
+
+/// Text shown in place of the colored status glyph of a tool call.
+#[must_use]
+pub fn status_label(status: model::ToolCallStatus) -> &'static str {
+    match status {
+        model::ToolCallStatus::Pending => "[pending]",
+        model::ToolCallStatus::InProgress => "[running]",
+        model::ToolCallStatus::Completed => "[done]",
+        model::ToolCallStatus::Failed => "[failed]",
+    }
+}
+
+/// Append the last assistant reply to the mirror, if one is open.
+pub(crate) fn mirror_last_reply(app: &mut App) {
+    let Some(mirror) = app.screen_reader.mirror.as_mut() else {
+        return;
+    };
+    let Some(reply) = app
+        .messages
+        .iter()
+        .rfind(|msg| matches!(msg.role, MessageRole::Assistant))
+        .map(reply_text)
+        .filter(|text| !text.is_empty())
+    else {
+        return;
+    };
+    if let Err(err) = writeln!(mirror, "{reply}\n").and_then(|()| mirror.flush()) {
+        tracing::warn!("screen reader mirror write failed, closing it: {err}");
+        app.screen_reader.mirror = None;
+    }
+}
+
+fn reply_text(msg: &super::ChatMessage) -> String {
+    let parts: Vec<&str> = msg
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            MessageBlock::Text(text) => Some(text.text.trim()),
+            _ => None,
+        })
+        .filter(|text| !text.is_empty())
+        .collect();
+    parts.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn mirror_receives_the_last_reply_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("mirror.txt");
+        let mut app = App::test_default();
+        app.screen_reader = ScreenReader::new(false, Some(&path));
+        app.messages = vec![ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![
+                MessageBlock::Text(TextBlock::from_complete("First paragraph.\n")),
+                MessageBlock::Text(TextBlock::from_complete("Second paragraph.")),
+            ],
+            usage: None,
+            annotation: None,
+        }];
+
+        mirror_last_reply(&mut app);
+
+        let written = std::fs::read_to_string(&path).expect("read mirror");
+        assert_eq!(written, "First paragraph.\n\nSecond paragraph.\n\n");
+    }
+
+    #[test]
+    fn every_status_has_a_distinct_label() {
+        let labels = [
+            status_label(model::ToolCallStatus::Pending),
+            status_label(model::ToolCallStatus::InProgress),
+            status_label(model::ToolCallStatus::Completed),
+            status_label(model::ToolCallStatus::Failed),
+        ];
+        for (i, label) in labels.iter().enumerate() {
+            assert!(!labels[i + 1..].contains(label), "duplicate label {label}");
+        }
+    }
+}
@@ -12,10 +12,106 @@ pub(crate) fn normalize_selection(
 
 pub(super) fn clear_selection(app: &mut App) {
     app.selection = None;
+    app.chat_visual = None;
     app.rendered_chat_lines.clear();
     app.rendered_input_lines.clear();
 }
 
+/// Start keyboard selection with the cursor at the start of the bottom chat row.
+pub(super) fn enter_chat_visual_mode(app: &mut App) -> bool {
+    let area = app.rendered_chat_area;
+    if area.height == 0 || area.width == 0 {
+        return false;
+    }
+    let cursor = super::SelectionPoint { row: usize::from(area.height) - 1, col: 0 };
+    app.chat_visual = Some(super::ChatVisualState { cursor, anchor: None });
+    sync_chat_visual_selection(app);
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum VisualMotion {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    LineEnd,
+    Top,
+    Bottom,
+}
+
+/// Move the keyboard cursor within the rendered chat area.
+pub(super) fn move_chat_visual_cursor(app: &mut App, motion: VisualMotion) {
+    let Some(visual) = &mut app.chat_visual else {
+        return;
+    };
+    let last_row = usize::from(app.rendered_chat_area.height).saturating_sub(1);
+    let last_col = usize::from(app.rendered_chat_area.width).saturating_sub(1);
+    let cursor = &mut visual.cursor;
+    match motion {
+        VisualMotion::Left => cursor.col = cursor.col.saturating_sub(1),
+        VisualMotion::Right => cursor.col = (cursor.col + 1).min(last_col),
+        VisualMotion::Up => cursor.row = cursor.row.saturating_sub(1),
+        VisualMotion::Down => cursor.row = (cursor.row + 1).min(last_row),
+        VisualMotion::LineStart => cursor.col = 0,
+        VisualMotion::LineEnd => {
+            let len = app
+                .rendered_chat_lines
+                .get(cursor.row)
+                .map_or(0, |line| crate::ui::width::display_width(line.trim_end()));
+            cursor.col = len.saturating_sub(1).min(last_col);
+        }
+        VisualMotion::Top => cursor.row = 0,
+        VisualMotion::Bottom => cursor.row = last_row,
+    }
+    sync_chat_visual_selection(app);
+}
+
+/// Anchor the selection at the cursor, or drop the anchor if already set.
+pub(super) fn toggle_chat_visual_anchor(app: &mut App) {
+    if let Some(visual) = &mut app.chat_visual {
+        visual.anchor = if visual.anchor.is_some() { None } else { Some(visual.cursor) };
+    }
+    sync_chat_visual_selection(app);
+}
+
+/// Select the cursor's whole row, used when copying without an anchor.
+pub(super) fn select_chat_visual_row(app: &mut App) {
+    let Some(visual) = app.chat_visual else {
+        return;
+    };
+    let row = visual.cursor.row;
+    let len = app
+        .rendered_chat_lines
+        .get(row)
+        .map_or(0, |line| crate::ui::width::display_width(line.trim_end()));
+    app.selection = Some(super::SelectionState {
+        kind: super::SelectionKind::Chat,
+        start: super::SelectionPoint { row, col: 0 },
+        end: super::SelectionPoint { row, col: len },
+        dragging: true,
+    });
+}
+
+/// Mirror the keyboard cursor and anchor into `selection`. `dragging` stays set
+/// so the chat keeps capturing rendered rows for the copy.
+pub(super) fn sync_chat_visual_selection(app: &mut App) {
+    let Some(visual) = app.chat_visual else {
+        return;
+    };
+    let (start, mut end) =
+        normalize_selection(visual.anchor.unwrap_or(visual.cursor), visual.cursor);
+    // The cursor cell itself is part of the selection.
+    end.col += 1;
+    app.selection = Some(super::SelectionState {
+        kind: super::SelectionKind::Chat,
+        start,
+        end,
+        dragging: true,
+    });
+}
+
 #[cfg(test)]
 fn slice_by_cols(text: &str, start_col: usize, end_col: usize) -> String {
     let mut out = String::new();
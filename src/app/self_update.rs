@@ -0,0 +1,267 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/update`: replace the running binary with the matching release asset after
+//! verifying it against the release's `SHA256SUMS`, or print the exact install
+//! command when a package manager owns the binary.
+
+use super::config::UpdateChannel;
+use super::http::{HttpSettings, describe_error};
+use super::update_check::{ReleaseInfo, fetch_latest_release, is_newer_version};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+const ASSET_PREFIX: &str = "claude-code-rust-";
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(120);
+const NPM_PACKAGE: &str = "claude-code-rust";
+const REPOSITORY_URL: &str = "https://github.com/srothgan/claude-code-rust";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InstallMethod {
+    /// Installed by the npm package's postinstall script.
+    Npm,
+    /// Built with `cargo install`.
+    Cargo,
+    /// A release binary placed on `PATH` by hand; safe to replace in place.
+    Standalone,
+}
+
+impl InstallMethod {
+    pub(crate) fn detect(exe: &Path) -> Self {
+        let names: Vec<_> = exe
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect();
+        if names.iter().any(|name| *name == "node_modules") {
+            Self::Npm
+        } else if names.windows(2).any(|pair| pair[0] == ".cargo" && pair[1] == "bin") {
+            Self::Cargo
+        } else {
+            Self::Standalone
+        }
+    }
+
+    pub(crate) fn install_command(self, version: &str) -> Option<String> {
+        match self {
+            Self::Npm => Some(format!("npm install -g {NPM_PACKAGE}@{version}")),
+            Self::Cargo => {
+                Some(format!("cargo install --locked --git {REPOSITORY_URL} --tag v{version}"))
+            }
+            Self::Standalone => None,
+        }
+    }
+}
+
+/// Release asset name for the platform this binary was built for, matching
+/// the names the release workflow publishes.
+fn platform_asset_name() -> Option<String> {
+    let (target, suffix) = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => ("aarch64-apple-darwin", ""),
+        ("macos", "x86_64") => ("x86_64-apple-darwin", ""),
+        ("linux", "x86_64") => ("x86_64-unknown-linux-gnu", ""),
+        ("windows", "x86_64") => ("x86_64-pc-windows-msvc", ".exe"),
+        _ => return None,
+    };
+    Some(format!("{ASSET_PREFIX}{target}{suffix}"))
+}
+
+/// `/update`: report whether a newer release exists on `channel` and how to
+/// install it.
+pub(crate) async fn check(
+    http: HttpSettings,
+    current_version: String,
+    channel: UpdateChannel,
+) -> Result<String, String> {
+    let release = fetch_latest_release(&http, channel).await?;
+    if !is_newer_version(&release.version, &current_version) {
+        return Ok(up_to_date_message(&current_version, channel));
+    }
+    let exe = std::env::current_exe().map_err(|err| format!("Cannot locate claude-rs: {err}"))?;
+    Ok(match InstallMethod::detect(&exe).install_command(&release.version) {
+        Some(command) => format!("v{} is available. Update with: {command}", release.version),
+        None => format!(
+            "v{} is available. Run /update install to download it and replace {}.",
+            release.version,
+            exe.display()
+        ),
+    })
+}
+
+/// `/update install`: download, verify and swap in the latest release binary.
+pub(crate) async fn install(
+    http: HttpSettings,
+    current_version: String,
+    channel: UpdateChannel,
+) -> Result<String, String> {
+    let exe = std::env::current_exe().map_err(|err| format!("Cannot locate claude-rs: {err}"))?;
+    let release = fetch_latest_release(&http, channel).await?;
+    if !is_newer_version(&release.version, &current_version) {
+        return Ok(up_to_date_message(&current_version, channel));
+    }
+    if let Some(command) = InstallMethod::detect(&exe).install_command(&release.version) {
+        return Err(format!(
+            "claude-rs at {} is managed by a package manager. Update with: {command}",
+            exe.display()
+        ));
+    }
+
+    let asset_name = platform_asset_name()
+        .ok_or_else(|| "No prebuilt release binary for this platform.".to_owned())?;
+    let asset_url = asset_url(&release, &asset_name)?;
+    let checksums_url = asset_url_or(&release, CHECKSUMS_ASSET, || {
+        format!("v{} does not publish checksums; refusing to self-update.", release.version)
+    })?;
+
+    let client = http
+        .client_builder(DOWNLOAD_TIMEOUT)
+        .build()
+        .map_err(|err| format!("HTTP client setup failed: {}", describe_error(&err)))?;
+    let checksums = String::from_utf8_lossy(&download(&client, checksums_url).await?).into_owned();
+    let expected = expected_checksum(&checksums, &asset_name)
+        .ok_or_else(|| format!("{CHECKSUMS_ASSET} has no entry for {asset_name}."))?;
+    let binary = download(&client, asset_url).await?;
+    let actual = sha256_hex(&binary);
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(format!(
+            "Checksum mismatch for {asset_name} (expected {expected}, got {actual}); \
+             the running binary was not changed."
+        ));
+    }
+
+    replace_executable(&exe, &binary)
+        .await
+        .map_err(|err| format!("Failed to replace {}: {err}", exe.display()))?;
+    tracing::info!(version = %release.version, path = %exe.display(), "Self-update installed");
+    Ok(format!("Updated to v{}. Restart claude-rs to use it.", release.version))
+}
+
+fn up_to_date_message(current_version: &str, channel: UpdateChannel) -> String {
+    format!("claude-rs v{current_version} is up to date on the {} channel.", channel.as_stored())
+}
+
+fn asset_url<'a>(release: &'a ReleaseInfo, name: &str) -> Result<&'a str, String> {
+    asset_url_or(release, name, || format!("v{} has no asset named {name}.", release.version))
+}
+
+fn asset_url_or<'a>(
+    release: &'a ReleaseInfo,
+    name: &str,
+    missing: impl FnOnce() -> String,
+) -> Result<&'a str, String> {
+    release
+        .assets
+        .iter()
+        .find(|asset| asset.name == name)
+        .map(|asset| asset.download_url.as_str())
+        .ok_or_else(missing)
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| format!("Download failed: {}", describe_error(&err)))?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed with status {} for {url}", response.status()));
+    }
+    response
+        .bytes()
+        .await
+        .map(|bytes| bytes.to_vec())
+        .map_err(|err| format!("Download failed: {}", describe_error(&err)))
+}
+
+/// Look up `asset_name` in `sha256sum` output (`<hex>  <name>`, with an
+/// optional `*` binary-mode marker before the name).
+fn expected_checksum<'a>(checksums: &'a str, asset_name: &str) -> Option<&'a str> {
+    checksums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim_start().trim_start_matches('*') == asset_name).then_some(hash)
+    })
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// Write the new binary next to the old one and rename it into place. The
+/// running process keeps its open image on Unix; Windows cannot overwrite a
+/// running executable, so the old one is moved aside first.
+async fn replace_executable(exe: &Path, binary: &[u8]) -> std::io::Result<()> {
+    let staged = sibling_path(exe, "update");
+    tokio::fs::write(&staged, binary).await?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755)).await?;
+    }
+    #[cfg(windows)]
+    {
+        let previous = sibling_path(exe, "old");
+        let _ = tokio::fs::remove_file(&previous).await;
+        tokio::fs::rename(exe, &previous).await?;
+    }
+    tokio::fs::rename(&staged, exe).await
+}
+
+fn sibling_path(exe: &Path, suffix: &str) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    exe.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn install_method_detects_npm_and_cargo_paths() {
+        assert_eq!(
+            InstallMethod::detect(Path::new(
+                "/usr/lib/node_modules/claude-code-rust/vendor/x86_64-unknown-linux-gnu/claude-rs"
+            )),
+            InstallMethod::Npm
+        );
+        assert_eq!(
+            InstallMethod::detect(Path::new("/home/me/.cargo/bin/claude-rs")),
+            InstallMethod::Cargo
+        );
+        assert_eq!(
+            InstallMethod::detect(Path::new("/usr/local/bin/claude-rs")),
+            InstallMethod::Standalone
+        );
+        assert_eq!(
+            InstallMethod::Npm.install_command("0.9.0").as_deref(),
+            Some("npm install -g claude-code-rust@0.9.0")
+        );
+    }
+
+    #[test]
+    fn checksum_lookup_matches_sha256sum_output() {
+        let sums = "\
+aaaa  claude-code-rust-x86_64-apple-darwin
+bbbb *claude-code-rust-x86_64-unknown-linux-gnu
+";
+        assert_eq!(
+            expected_checksum(sums, "claude-code-rust-x86_64-unknown-linux-gnu"),
+            Some("bbbb")
+        );
+        assert_eq!(expected_checksum(sums, "claude-code-rust-x86_64-pc-windows-msvc.exe"), None);
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
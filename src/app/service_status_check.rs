@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::App;
+use super::http::{HttpSettings, describe_error};
 use crate::agent::events::{ClientEvent, ServiceStatusSeverity};
 use serde::Deserialize;
 use std::time::Duration;
@@ -43,10 +44,15 @@ struct IncidentComponent {
 }
 
 pub fn start_service_status_check(app: &App) {
+    if app.is_offline() {
+        tracing::debug!("Skipping service status check (offline mode)");
+        return;
+    }
     let event_tx = app.event_tx.clone();
+    let http = app.http.clone();
 
     tokio::task::spawn_local(async move {
-        let Some(issue) = resolve_service_status_issue().await else {
+        let Some(issue) = resolve_service_status_issue(&http).await else {
             return;
         };
         let _ = event_tx
@@ -54,9 +60,15 @@ pub fn start_service_status_check(app: &App) {
     });
 }
 
-async fn resolve_service_status_issue() -> Option<ServiceStatusIssue> {
-    let client = reqwest::Client::builder().timeout(SERVICE_STATUS_TIMEOUT).build().ok()?;
-    let response = client.get(STATUSPAGE_SUMMARY_URL).send().await.ok()?;
+async fn resolve_service_status_issue(http: &HttpSettings) -> Option<ServiceStatusIssue> {
+    let client = http.client_builder(SERVICE_STATUS_TIMEOUT).build().ok()?;
+    let response = match client.get(STATUSPAGE_SUMMARY_URL).send().await {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::warn!("service-status request failed: {}", describe_error(&err));
+            return None;
+        }
+    };
     if !response.status().is_success() {
         tracing::debug!("service-status request failed with status {}", response.status());
         return None;
@@ -0,0 +1,113 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/env`: environment variables for the commands the agent runs.
+//!
+//! `/env NAME=value ...` sets variables, `/env NAME=` unsets one and `/env`
+//! alone lists them. They reach the agent with the session launch settings,
+//! so a change reloads the current session, and they last until the app
+//! exits. Expanded Execute tool blocks show the variables they ran with.
+
+use super::connect::reload_session;
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    if args.is_empty() {
+        let summary = summary(app);
+        push_system_message_with_severity(app, Some(SystemSeverity::Info), &summary);
+        return Ok(());
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /env.".to_owned());
+    }
+    let assignments = parse_assignments(args)?;
+    let before = app.session_env.clone();
+    for (name, value) in assignments {
+        if value.is_empty() {
+            app.session_env.remove(&name);
+        } else {
+            app.session_env.insert(name, value);
+        }
+    }
+    let summary = summary(app);
+    if app.session_env == before {
+        push_system_message_with_severity(app, Some(SystemSeverity::Info), &summary);
+        return Ok(());
+    }
+    if !reload_session(app, "Applying environment...".to_owned(), summary.clone()) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("{summary}\nIt applies from the next session."),
+        );
+    }
+    Ok(())
+}
+
+fn summary(app: &App) -> String {
+    if app.session_env.is_empty() {
+        return "No environment variables set for agent commands. \
+                Use /env NAME=value to add one."
+            .to_owned();
+    }
+    let vars: Vec<String> =
+        app.session_env.iter().map(|(name, value)| format!("- `{name}={value}`")).collect();
+    format!("Environment for agent commands:\n{}", vars.join("\n"))
+}
+
+fn parse_assignments(args: &[&str]) -> Result<Vec<(String, String)>, String> {
+    args.iter()
+        .map(|arg| {
+            let (name, value) = arg.split_once('=').ok_or_else(|| {
+                format!("Expected NAME=value, got `{arg}`. Usage: /env [NAME=value ...]")
+            })?;
+            if !is_valid_name(name) {
+                return Err(format!("`{name}` is not a valid variable name."));
+            }
+            Ok((name.to_owned(), value.to_owned()))
+        })
+        .collect()
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_assignments_and_rejects_bad_names() {
+        assert_eq!(
+            parse_assignments(&["RUST_LOG=debug", "URL=a=b", "OLD="]),
+            Ok(vec![
+                ("RUST_LOG".to_owned(), "debug".to_owned()),
+                ("URL".to_owned(), "a=b".to_owned()),
+                ("OLD".to_owned(), String::new()),
+            ])
+        );
+        assert_eq!(
+            parse_assignments(&["1X=y"]),
+            Err("`1X` is not a valid variable name.".to_owned())
+        );
+        assert!(parse_assignments(&["RUST_LOG"]).is_err());
+    }
+
+    #[test]
+    fn env_sets_and_unsets_variables_for_the_next_session() {
+        let mut app = App::test_default();
+
+        start(&mut app, &["RUST_LOG=debug", "FOO=1"]).expect("set");
+        start(&mut app, &["FOO="]).expect("unset");
+
+        assert_eq!(
+            app.session_env.into_iter().collect::<Vec<_>>(),
+            vec![("RUST_LOG".to_owned(), "debug".to_owned())]
+        );
+    }
+}
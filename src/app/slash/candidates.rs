@@ -8,6 +8,7 @@ use super::{
 };
 use crate::app::App;
 use crate::app::dialog::DialogState;
+use crate::app::fuzzy::fuzzy_match;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(super) fn detect_argument_at_cursor(
@@ -109,7 +110,7 @@ pub(super) fn find_advertised_command<'a>(
 }
 
 fn is_builtin_variable_input_command(command_name: &str) -> bool {
-    matches!(command_name, "/mode" | "/model" | "/resume")
+    matches!(command_name, "/help" | "/mode" | "/model" | "/resume")
 }
 
 pub(super) fn is_variable_input_command(app: &App, command_name: &str) -> bool {
@@ -122,35 +123,174 @@ pub(super) fn is_variable_input_command(app: &App, command_name: &str) -> bool {
         .is_some_and(|hint| !hint.trim().is_empty())
 }
 
+/// Metadata for a command handled by the client itself.
+pub(super) struct BuiltinCommand {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub arg_hint: Option<&'static str>,
+}
+
+pub(super) const BUILTIN_COMMANDS: &[BuiltinCommand] = &[
+    BuiltinCommand {
+        name: "/add-dir",
+        description: "Add a working directory for mentions and the agent, or list them",
+        arg_hint: Some("[path]"),
+    },
+    BuiltinCommand {
+        name: "/bookmarks",
+        description: "List bookmarks or jump back to one",
+        arg_hint: Some("[n|name | rm <n|name>]"),
+    },
+    BuiltinCommand { name: "/cancel", description: "Cancel active turn", arg_hint: None },
+    BuiltinCommand {
+        name: "/cd",
+        description: "Continue in another directory with a new session",
+        arg_hint: Some("<path>"),
+    },
+    BuiltinCommand {
+        name: "/commit",
+        description: "Draft a commit message for the current changes and commit",
+        arg_hint: Some("[--amend] [--signoff]"),
+    },
+    BuiltinCommand { name: "/compact", description: "Compact session context", arg_hint: None },
+    BuiltinCommand { name: "/config", description: "Open settings", arg_hint: None },
+    BuiltinCommand {
+        name: "/context",
+        description: "Show what fills the context window",
+        arg_hint: None,
+    },
+    BuiltinCommand {
+        name: "/diagnostics",
+        description: "Show agent stderr and unrecognized bridge events",
+        arg_hint: None,
+    },
+    BuiltinCommand {
+        name: "/env",
+        description: "Show or set environment variables for agent-run commands",
+        arg_hint: Some("[NAME=value ...]"),
+    },
+    BuiltinCommand {
+        name: "/files",
+        description: "List files changed this session; diff, edit or revert them",
+        arg_hint: None,
+    },
+    BuiltinCommand {
+        name: "/fold",
+        description: "Fold earlier turns to one-line summaries",
+        arg_hint: Some("[off]"),
+    },
+    BuiltinCommand {
+        name: "/help",
+        description: "Show the full description of a command",
+        arg_hint: Some("<command>"),
+    },
+    BuiltinCommand {
+        name: "/issue",
+        description: "Fetch a GitHub or GitLab issue into the prompt",
+        arg_hint: Some("<number|url>"),
+    },
+    BuiltinCommand {
+        name: "/log-level",
+        description: "Show or change the log filter",
+        arg_hint: Some("[level|filter]"),
+    },
+    BuiltinCommand { name: "/login", description: "Authenticate with Claude", arg_hint: None },
+    BuiltinCommand { name: "/logout", description: "Sign out of Claude", arg_hint: None },
+    BuiltinCommand { name: "/mcp", description: "Open MCP", arg_hint: None },
+    BuiltinCommand {
+        name: "/memory",
+        description: "Edit CLAUDE.md memory in your editor",
+        arg_hint: Some("[project|user]"),
+    },
+    BuiltinCommand { name: "/mode", description: "Set session mode", arg_hint: Some("<id>") },
+    BuiltinCommand { name: "/model", description: "Set session model", arg_hint: Some("<name>") },
+    BuiltinCommand { name: "/new-session", description: "Start a fresh session", arg_hint: None },
+    BuiltinCommand {
+        name: "/resume",
+        description: "Resume a session by ID",
+        arg_hint: Some("<session_id>"),
+    },
+    BuiltinCommand { name: "/plugins", description: "Open plugins", arg_hint: None },
+    BuiltinCommand {
+        name: "/pr",
+        description: "Draft a pull request for this branch and create it",
+        arg_hint: Some("[--draft] [--base <branch>]"),
+    },
+    BuiltinCommand {
+        name: "/set",
+        description: "Set model parameters such as the thinking budget for this session",
+        arg_hint: Some("[thinking-budget|max-turns <n|default>]"),
+    },
+    BuiltinCommand {
+        name: "/share",
+        description: "Export the session as redacted HTML or a secret gist",
+        arg_hint: Some("[--gist] [--full-output]"),
+    },
+    BuiltinCommand {
+        name: "/stats",
+        description: "Show model parameters and how long tool calls took, per tool",
+        arg_hint: None,
+    },
+    BuiltinCommand { name: "/status", description: "Show session status", arg_hint: None },
+    BuiltinCommand {
+        name: "/test",
+        description: "Run the project's tests; Tab sends a failure to Claude",
+        arg_hint: Some("[args]"),
+    },
+    BuiltinCommand {
+        name: "/transcript",
+        description: "Print the conversation to the terminal on quit",
+        arg_hint: Some("[on|off]"),
+    },
+    BuiltinCommand {
+        name: "/trust",
+        description: "List or manage trusted project folders",
+        arg_hint: Some("[add [path] | remove <path>]"),
+    },
+    BuiltinCommand {
+        name: "/update",
+        description: "Check for a newer claude-rs release and install it",
+        arg_hint: Some("[install]"),
+    },
+    BuiltinCommand {
+        name: "/usage",
+        description: "Open usage, or chart the last 30 days across sessions",
+        arg_hint: Some("[history]"),
+    },
+];
+
+pub(super) fn find_builtin_command(command_name: &str) -> Option<&'static BuiltinCommand> {
+    BUILTIN_COMMANDS.iter().find(|cmd| cmd.name == command_name)
+}
+
+/// First non-empty line of a description, used for the dropdown's help column.
+pub(super) fn one_line_help(description: &str) -> Option<String> {
+    description.lines().map(str::trim).find(|line| !line.is_empty()).map(str::to_owned)
+}
+
 pub(super) fn supported_command_candidates(app: &App) -> Vec<SlashCandidate> {
     use std::collections::BTreeMap;
 
-    let mut by_name: BTreeMap<String, String> = BTreeMap::new();
-    by_name.insert("/cancel".into(), "Cancel active turn".into());
-    by_name.insert("/compact".into(), "Compact session context".into());
-    by_name.insert("/config".into(), "Open settings".into());
-    by_name.insert("/login".into(), "Authenticate with Claude".into());
-    by_name.insert("/logout".into(), "Sign out of Claude".into());
-    by_name.insert("/mcp".into(), "Open MCP".into());
-    by_name.insert("/mode".into(), "Set session mode".into());
-    by_name.insert("/model".into(), "Set session model".into());
-    by_name.insert("/new-session".into(), "Start a fresh session".into());
-    by_name.insert("/resume".into(), "Resume a session by ID".into());
-    by_name.insert("/plugins".into(), "Open plugins".into());
-    by_name.insert("/status".into(), "Show session status".into());
-    by_name.insert("/usage".into(), "Open usage".into());
+    let mut by_name: BTreeMap<String, (String, Option<String>)> = BUILTIN_COMMANDS
+        .iter()
+        .map(|cmd| {
+            (cmd.name.to_owned(), (cmd.description.to_owned(), cmd.arg_hint.map(str::to_owned)))
+        })
+        .collect();
 
     for cmd in &app.available_commands {
         let name = normalize_slash_name(&cmd.name);
-        by_name.entry(name).or_insert_with(|| cmd.description.clone());
+        let hint = cmd.input_hint.clone().filter(|hint| !hint.trim().is_empty());
+        by_name.entry(name).or_insert_with(|| (cmd.description.clone(), hint));
     }
 
     by_name
         .into_iter()
-        .map(|(name, description)| SlashCandidate {
+        .map(|(name, (description, arg_hint))| SlashCandidate {
             insert_value: name.clone(),
             primary: name,
-            secondary: if description.trim().is_empty() { None } else { Some(description) },
+            secondary: one_line_help(&description),
+            arg_hint,
         })
         .collect()
 }
@@ -163,25 +303,34 @@ pub(super) fn filter_command_candidates(
         return candidates.iter().take(MAX_CANDIDATES).cloned().collect();
     }
 
-    let query_lower = query.to_lowercase();
-    candidates
-        .iter()
-        .filter(|candidate| {
-            let body = candidate.primary.strip_prefix('/').unwrap_or(&candidate.primary);
-            body.to_lowercase().contains(&query_lower)
-        })
-        .take(MAX_CANDIDATES)
-        .cloned()
-        .collect()
+    rank_by_score(candidates, |candidate| {
+        let body = candidate.primary.strip_prefix('/').unwrap_or(&candidate.primary);
+        fuzzy_match(body, query).map(|found| found.score)
+    })
 }
 
-fn candidate_matches(candidate: &SlashCandidate, query_lower: &str) -> bool {
-    candidate.primary.to_lowercase().contains(query_lower)
-        || candidate.insert_value.to_lowercase().contains(query_lower)
-        || candidate
-            .secondary
-            .as_ref()
-            .is_some_and(|secondary| secondary.to_lowercase().contains(query_lower))
+/// Best fuzzy score across the fields an argument candidate can be found by.
+/// The secondary column only matches as a contiguous substring to keep noise down.
+fn argument_candidate_score(candidate: &SlashCandidate, query: &str) -> Option<i32> {
+    let primary = fuzzy_match(&candidate.primary, query).map(|found| found.score);
+    let insert = fuzzy_match(&candidate.insert_value, query).map(|found| found.score);
+    let secondary = candidate
+        .secondary
+        .as_ref()
+        .filter(|secondary| secondary.to_lowercase().contains(&query.to_lowercase()))
+        .map(|_| 0);
+    primary.max(insert).max(secondary)
+}
+
+/// Keep candidates with a score, ordered best-first; ties keep their original order.
+fn rank_by_score(
+    candidates: &[SlashCandidate],
+    score: impl Fn(&SlashCandidate) -> Option<i32>,
+) -> Vec<SlashCandidate> {
+    let mut scored: Vec<(i32, &SlashCandidate)> =
+        candidates.iter().filter_map(|c| score(c).map(|s| (s, c))).collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().take(MAX_CANDIDATES).map(|(_, c)| c.clone()).collect()
 }
 
 pub(super) fn filter_argument_candidates(
@@ -192,13 +341,7 @@ pub(super) fn filter_argument_candidates(
         return candidates.iter().take(MAX_CANDIDATES).cloned().collect();
     }
 
-    let query_lower = query.to_lowercase();
-    candidates
-        .iter()
-        .filter(|candidate| candidate_matches(candidate, &query_lower))
-        .take(MAX_CANDIDATES)
-        .cloned()
-        .collect()
+    rank_by_score(candidates, |candidate| argument_candidate_score(candidate, query))
 }
 
 fn now_epoch_seconds() -> i64 {
@@ -232,7 +375,7 @@ fn format_relative_age(epoch_seconds: i64) -> String {
     format!("{days}d {hours}h")
 }
 
-fn session_age_label(last_modified_ms: Option<u64>) -> String {
+pub(crate) fn session_age_label(last_modified_ms: Option<u64>) -> String {
     let Some(last_modified_ms) = last_modified_ms else {
         return "--".to_owned();
     };
@@ -253,6 +396,14 @@ pub(super) fn argument_candidates(
     }
 
     match command_name {
+        "/help" => supported_command_candidates(app)
+            .into_iter()
+            .filter(|candidate| candidate.primary != "/help")
+            .map(|candidate| SlashCandidate {
+                insert_value: candidate.primary.trim_start_matches('/').to_owned(),
+                ..candidate
+            })
+            .collect(),
         "/resume" => app
             .recent_sessions
             .iter()
@@ -264,9 +415,29 @@ pub(super) fn argument_candidates(
                     insert_value: session.session_id.clone(),
                     primary: format!("{age} - {summary}"),
                     secondary: Some(session.session_id.clone()),
+                    arg_hint: None,
                 }
             })
             .collect(),
+        "/bookmarks" => app
+            .bookmarks
+            .iter()
+            .map(|bookmark| SlashCandidate {
+                insert_value: bookmark.name.clone(),
+                primary: bookmark.name.clone(),
+                secondary: (!bookmark.excerpt.is_empty()).then(|| bookmark.excerpt.clone()),
+                arg_hint: None,
+            })
+            .collect(),
+        "/set" => crate::app::model_params::ModelParam::ALL
+            .into_iter()
+            .map(|param| SlashCandidate {
+                insert_value: param.name().to_owned(),
+                primary: param.name().to_owned(),
+                secondary: Some(param.display(param.get(&app.model_params))),
+                arg_hint: None,
+            })
+            .collect(),
         "/mode" => app
             .mode
             .as_ref()
@@ -277,6 +448,7 @@ pub(super) fn argument_candidates(
                         insert_value: entry.id.clone(),
                         primary: entry.name.clone(),
                         secondary: Some(entry.id.clone()),
+                        arg_hint: None,
                     })
                     .collect()
             })
@@ -291,6 +463,7 @@ pub(super) fn argument_candidates(
                     .description
                     .clone()
                     .or_else(|| (model.display_name != model.id).then(|| model.id.clone())),
+                arg_hint: None,
             })
             .collect(),
         _ => Vec::new(),
@@ -329,19 +502,12 @@ pub(super) fn build_slash_state(app: &App) -> Option<SlashState> {
     })
 }
 
+/// Commands handled by the client itself; these shadow custom command files.
+pub(super) fn is_builtin_command(command_name: &str) -> bool {
+    find_builtin_command(command_name).is_some()
+}
+
 pub fn is_supported_command(app: &App, command_name: &str) -> bool {
-    matches!(
-        command_name,
-        "/cancel"
-            | "/compact"
-            | "/config"
-            | "/mcp"
-            | "/mode"
-            | "/model"
-            | "/new-session"
-            | "/resume"
-            | "/plugins"
-            | "/status"
-            | "/usage"
-    ) || advertised_commands(app).iter().any(|c| c == command_name)
+    (is_builtin_command(command_name) && !matches!(command_name, "/help" | "/login" | "/logout"))
+        || advertised_commands(app).iter().any(|c| c == command_name)
 }
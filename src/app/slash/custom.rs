@@ -0,0 +1,380 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Custom slash commands loaded from `.claude/commands/*.md`.
+//!
+//! Project commands live under `<project>/.claude/commands`, user commands under
+//! `~/.claude/commands`. Files in subdirectories are namespaced with `:`
+//! (`frontend/review.md` becomes `/frontend:review`). Project commands win over
+//! user commands with the same name.
+
+use super::normalize_slash_name;
+use crate::agent::model;
+use crate::app::App;
+use std::path::{Path, PathBuf};
+
+const COMMANDS_DIR: &str = "commands";
+const MAX_DIR_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomCommandScope {
+    Project,
+    User,
+}
+
+impl CustomCommandScope {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Project => "project",
+            Self::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomCommand {
+    /// Normalized command name including the leading `/`.
+    pub name: String,
+    pub description: String,
+    pub argument_hint: Option<String>,
+    /// Prompt template with frontmatter stripped.
+    pub body: String,
+    pub scope: CustomCommandScope,
+    pub path: PathBuf,
+}
+
+impl CustomCommand {
+    /// Expand the template with the raw argument string typed after the command name.
+    ///
+    /// `$ARGUMENTS` receives the whole argument string and `$1`..`$9` the
+    /// whitespace-separated positional arguments. When the template references
+    /// neither, non-empty arguments are appended on their own line.
+    #[must_use]
+    pub fn expand(&self, arguments: &str) -> String {
+        let arguments = arguments.trim();
+        let positional: Vec<&str> = arguments.split_whitespace().collect();
+        let mut used_placeholder = false;
+        let mut out = String::with_capacity(self.body.len() + arguments.len());
+        let mut rest = self.body.as_str();
+
+        while let Some(idx) = rest.find('$') {
+            out.push_str(&rest[..idx]);
+            let tail = &rest[idx + 1..];
+            if let Some(after) = tail.strip_prefix("ARGUMENTS") {
+                out.push_str(arguments);
+                used_placeholder = true;
+                rest = after;
+            } else if let Some(digit) = tail.chars().next().and_then(|c| c.to_digit(10))
+                && digit > 0
+            {
+                let index = usize::try_from(digit - 1).unwrap_or(usize::MAX);
+                out.push_str(positional.get(index).copied().unwrap_or_default());
+                used_placeholder = true;
+                rest = &tail[1..];
+            } else {
+                out.push('$');
+                rest = tail;
+            }
+        }
+        out.push_str(rest);
+
+        if !used_placeholder && !arguments.is_empty() {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("\nARGUMENTS: ");
+            out.push_str(arguments);
+        }
+        out.trim().to_owned()
+    }
+
+    fn to_available_command(&self) -> model::AvailableCommand {
+        let description = if self.description.is_empty() {
+            format!("({})", self.scope.label())
+        } else {
+            format!("{} ({})", self.description, self.scope.label())
+        };
+        let command = model::AvailableCommand::new(self.name.clone(), description);
+        match self.argument_hint.as_deref() {
+            Some(hint) => command.input_hint(hint),
+            None => command,
+        }
+    }
+}
+
+/// Discover custom commands for a project root and an optional home directory.
+#[must_use]
+pub fn discover(project_root: &Path, home: Option<&Path>) -> Vec<CustomCommand> {
+    let project_dir = project_root.join(".claude").join(COMMANDS_DIR);
+    let mut commands = Vec::new();
+    collect_commands(&project_dir, CustomCommandScope::Project, &mut commands);
+    if let Some(home) = home {
+        let user_dir = home.join(".claude").join(COMMANDS_DIR);
+        if user_dir != project_dir {
+            let mut user_commands = Vec::new();
+            collect_commands(&user_dir, CustomCommandScope::User, &mut user_commands);
+            user_commands.retain(|user| !commands.iter().any(|cmd| cmd.name == user.name));
+            commands.extend(user_commands);
+        }
+    }
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+fn collect_commands(root: &Path, scope: CustomCommandScope, out: &mut Vec<CustomCommand>) {
+    let mut stack: Vec<(PathBuf, Vec<String>)> = vec![(root.to_path_buf(), Vec::new())];
+    while let Some((dir, namespace)) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                if namespace.len() < MAX_DIR_DEPTH
+                    && let Some(segment) = path.file_name().and_then(|n| n.to_str())
+                {
+                    let mut nested = namespace.clone();
+                    nested.push(segment.to_owned());
+                    stack.push((path, nested));
+                }
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            match std::fs::read_to_string(&path) {
+                Ok(source) => {
+                    let mut segments = namespace.clone();
+                    segments.push(stem.to_owned());
+                    out.push(parse_command(&segments.join(":"), &source, scope, path));
+                }
+                Err(err) => {
+                    tracing::warn!(path = %path.display(), %err, "failed to read custom command");
+                }
+            }
+        }
+    }
+}
+
+fn parse_command(
+    name: &str,
+    source: &str,
+    scope: CustomCommandScope,
+    path: PathBuf,
+) -> CustomCommand {
+    let (frontmatter, body) = split_frontmatter(source);
+    let mut description = None;
+    let mut argument_hint = None;
+    for line in frontmatter.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = unquote(value.trim());
+        if value.is_empty() {
+            continue;
+        }
+        match key.trim() {
+            "description" => description = Some(value.to_owned()),
+            "argument-hint" => argument_hint = Some(value.to_owned()),
+            _ => {}
+        }
+    }
+    let description = description.unwrap_or_else(|| {
+        body.lines()
+            .map(|line| line.trim().trim_start_matches('#').trim())
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .to_owned()
+    });
+    CustomCommand {
+        name: normalize_slash_name(name),
+        description,
+        argument_hint,
+        body: body.trim().to_owned(),
+        scope,
+        path,
+    }
+}
+
+/// Split a leading `---` YAML frontmatter block from the markdown body.
+fn split_frontmatter(source: &str) -> (&str, &str) {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    let Some(after_open) = source.strip_prefix("---\n").or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return ("", source);
+    };
+    let mut offset = 0;
+    for line in after_open.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (&after_open[..offset], &after_open[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    ("", source)
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+/// Reload custom commands from disk and merge them into `available_commands`.
+pub fn reload_custom_commands(app: &mut App) {
+    let home = app.settings_home_override.clone().or_else(dirs::home_dir);
+    app.custom_commands = discover(Path::new(&app.cwd_raw), home.as_deref());
+    tracing::debug!("Loaded {} custom slash commands", app.custom_commands.len());
+    merge_custom_commands(app);
+}
+
+/// Append custom commands that the agent did not already advertise.
+pub fn merge_custom_commands(app: &mut App) {
+    for custom in &app.custom_commands {
+        let advertised =
+            app.available_commands.iter().any(|cmd| normalize_slash_name(&cmd.name) == custom.name);
+        if !advertised {
+            app.available_commands.push(custom.to_available_command());
+        }
+    }
+}
+
+/// Expand submitted text when it invokes a custom command.
+///
+/// Built-in commands always take precedence over custom command files.
+#[must_use]
+pub fn expand_custom_command(app: &App, text: &str) -> Option<String> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('/') {
+        return None;
+    }
+    let (name, arguments) = trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""));
+    if super::candidates::is_builtin_command(name) {
+        return None;
+    }
+    let command = app.custom_commands.iter().find(|cmd| cmd.name == name)?;
+    Some(command.expand(arguments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn command(body: &str) -> CustomCommand {
+        CustomCommand {
+            name: "/review".to_owned(),
+            description: String::new(),
+            argument_hint: None,
+            body: body.to_owned(),
+            scope: CustomCommandScope::Project,
+            path: PathBuf::from("review.md"),
+        }
+    }
+
+    #[test]
+    fn frontmatter_sets_description_and_argument_hint() {
+        let parsed = parse_command(
+            "review",
+            "---\ndescription: \"Review a PR\"\nargument-hint: <pr-number>\n---\nReview PR $ARGUMENTS\n",
+            CustomCommandScope::Project,
+            PathBuf::from("review.md"),
+        );
+        assert_eq!(parsed.name, "/review");
+        assert_eq!(parsed.description, "Review a PR");
+        assert_eq!(parsed.argument_hint.as_deref(), Some("<pr-number>"));
+        assert_eq!(parsed.body, "Review PR $ARGUMENTS");
+    }
+
+    #[test]
+    fn description_falls_back_to_first_body_line() {
+        let parsed = parse_command(
+            "explain",
+            "# Explain the code\n\nWalk through it.",
+            CustomCommandScope::User,
+            PathBuf::from("explain.md"),
+        );
+        assert_eq!(parsed.description, "Explain the code");
+        assert!(parsed.argument_hint.is_none());
+    }
+
+    #[test]
+    fn expand_substitutes_arguments_and_positionals() {
+        assert_eq!(
+            command("Fix issue $ARGUMENTS now").expand(" 42 fast "),
+            "Fix issue 42 fast now"
+        );
+        assert_eq!(command("From $1 to $2, not $3").expand("a b"), "From a to b, not");
+        assert_eq!(command("Cost is $ and $0").expand(""), "Cost is $ and $0");
+    }
+
+    #[test]
+    fn expand_appends_arguments_without_placeholder() {
+        assert_eq!(
+            command("Summarize the diff.").expand("briefly"),
+            "Summarize the diff.\n\nARGUMENTS: briefly"
+        );
+        assert_eq!(command("Summarize the diff.").expand(""), "Summarize the diff.");
+    }
+
+    #[test]
+    fn discover_namespaces_subdirectories_and_prefers_project() {
+        let project = tempfile::tempdir().expect("tempdir");
+        let home = tempfile::tempdir().expect("tempdir");
+        let project_dir = project.path().join(".claude").join("commands");
+        let user_dir = home.path().join(".claude").join("commands");
+        std::fs::create_dir_all(project_dir.join("frontend")).expect("mkdir");
+        std::fs::create_dir_all(&user_dir).expect("mkdir");
+        std::fs::write(project_dir.join("review.md"), "Project review").expect("write");
+        std::fs::write(project_dir.join("frontend").join("lint.md"), "Lint").expect("write");
+        std::fs::write(project_dir.join("notes.txt"), "ignored").expect("write");
+        std::fs::write(user_dir.join("review.md"), "User review").expect("write");
+        std::fs::write(user_dir.join("standup.md"), "Standup").expect("write");
+
+        let commands = discover(project.path(), Some(home.path()));
+        let names: Vec<&str> = commands.iter().map(|cmd| cmd.name.as_str()).collect();
+        assert_eq!(names, vec!["/frontend:lint", "/review", "/standup"]);
+        assert_eq!(commands[1].body, "Project review");
+        assert_eq!(commands[2].scope, CustomCommandScope::User);
+    }
+
+    #[test]
+    fn merge_skips_commands_already_advertised() {
+        let mut app = App::test_default();
+        app.available_commands = vec![model::AvailableCommand::new("review", "From agent")];
+        app.custom_commands = vec![
+            command("Review"),
+            CustomCommand {
+                name: "/standup".to_owned(),
+                argument_hint: Some("<team>".to_owned()),
+                ..command("Standup")
+            },
+        ];
+
+        merge_custom_commands(&mut app);
+
+        assert_eq!(app.available_commands.len(), 2);
+        assert_eq!(app.available_commands[0].description, "From agent");
+        assert_eq!(app.available_commands[1].name, "/standup");
+        assert_eq!(app.available_commands[1].input_hint.as_deref(), Some("<team>"));
+    }
+
+    #[test]
+    fn expand_submission_ignores_builtins_and_unknown_commands() {
+        let mut app = App::test_default();
+        app.custom_commands = vec![
+            command("Review $ARGUMENTS"),
+            CustomCommand { name: "/config".to_owned(), ..command("shadowed") },
+        ];
+
+        assert_eq!(expand_custom_command(&app, "/review 12").as_deref(), Some("Review 12"));
+        assert!(expand_custom_command(&app, "/config").is_none());
+        assert!(expand_custom_command(&app, "/other").is_none());
+        assert!(expand_custom_command(&app, "review").is_none());
+    }
+}
@@ -3,14 +3,16 @@
 
 //! Slash command executors: dispatching parsed commands to their handler functions.
 
+use super::candidates::{find_advertised_command, find_builtin_command};
 use super::{
-    parse, push_system_message, push_user_message, require_active_session, require_connection,
-    set_command_pending,
+    CustomCommandScope, normalize_slash_name, parse, push_system_message, push_user_message,
+    require_active_session, require_connection, set_command_pending,
 };
 use crate::agent::events::ClientEvent;
+use crate::app::confirm::{ConfirmAction, ConfirmDialog, open_confirm};
 use crate::app::connect::{SessionStartReason, resume_session, start_new_session};
 use crate::app::events::push_system_message_with_severity;
-use crate::app::{App, AppStatus, CancelOrigin, SystemSeverity};
+use crate::app::{App, AppStatus, CancelOrigin, MessageRole, SystemSeverity};
 
 /// Handle slash command submission.
 ///
@@ -22,13 +24,34 @@ pub fn try_handle_submit(app: &mut App, text: &str) -> bool {
     };
 
     match parsed.name {
+        "/add-dir" => handle_add_dir_submit(app, &parsed.args),
+        "/bookmarks" => handle_bookmarks_submit(app, &parsed.args),
         "/cancel" => handle_cancel_submit(app),
+        "/cd" => handle_cd_submit(app, &parsed.args),
+        "/commit" => handle_commit_submit(app, &parsed.args),
         "/compact" => handle_compact_submit(app, &parsed.args),
         "/config" => handle_config_submit(app, &parsed.args),
+        "/diagnostics" => handle_diagnostics_submit(app, &parsed.args),
+        "/env" => handle_env_submit(app, &parsed.args),
+        "/files" => handle_files_submit(app, &parsed.args),
+        "/fold" => handle_fold_submit(app, &parsed.args),
+        "/help" => handle_help_submit(app, &parsed.args),
+        "/issue" => handle_issue_submit(app, &parsed.args),
         "/mcp" => handle_mcp_submit(app, &parsed.args),
         "/plugins" => handle_plugins_submit(app, &parsed.args),
+        "/pr" => handle_pr_submit(app, &parsed.args),
+        "/context" => handle_context_submit(app, &parsed.args),
+        "/memory" => handle_memory_submit(app, &parsed.args),
+        "/set" => handle_set_submit(app, &parsed.args),
+        "/share" => handle_share_submit(app, &parsed.args),
+        "/stats" => handle_stats_submit(app, &parsed.args),
         "/status" => handle_status_submit(app, &parsed.args),
+        "/test" => handle_test_submit(app, &parsed.args),
+        "/transcript" => handle_transcript_submit(app, &parsed.args),
+        "/trust" => handle_trust_submit(app, &parsed.args),
+        "/update" => handle_update_submit(app, &parsed.args),
         "/usage" => handle_usage_submit(app, &parsed.args),
+        "/log-level" => handle_log_level_submit(app, &parsed.args),
         "/login" => handle_login_submit(app, &parsed.args),
         "/logout" => handle_logout_submit(app, &parsed.args),
         "/mode" => handle_mode_submit(app, &parsed.args),
@@ -68,6 +91,83 @@ fn handle_compact_submit(app: &mut App, args: &[&str]) -> bool {
     false
 }
 
+fn handle_add_dir_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::add_dir::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_cd_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::change_dir::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_commit_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::commit::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_env_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::session_env::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_issue_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::issue::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_bookmarks_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::bookmarks::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_fold_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::turn_folds::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_set_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::model_params::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_share_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::share::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_test_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::test_run::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
+fn handle_pr_submit(app: &mut App, args: &[&str]) -> bool {
+    if let Err(err) = crate::app::pull_request::start(app, args) {
+        push_system_message(app, err);
+    }
+    true
+}
+
 fn handle_config_submit(app: &mut App, args: &[&str]) -> bool {
     if !args.is_empty() {
         push_system_message(app, "Usage: /config");
@@ -80,6 +180,59 @@ fn handle_config_submit(app: &mut App, args: &[&str]) -> bool {
     true
 }
 
+fn handle_help_submit(app: &mut App, args: &[&str]) -> bool {
+    let [command_arg] = args else {
+        if args.is_empty() {
+            return handle_unknown_submit(app, "/help");
+        }
+        push_system_message(app, "Usage: /help <command>");
+        return true;
+    };
+    let command_name = normalize_slash_name(command_arg.trim());
+    match command_help_text(app, &command_name) {
+        Some(text) => push_system_message(app, text),
+        None => push_system_message(app, format!("Unknown command: {command_name}")),
+    }
+    true
+}
+
+/// Full help for a command: name with argument placeholder, description, and origin.
+fn command_help_text(app: &App, command_name: &str) -> Option<String> {
+    use std::fmt::Write;
+
+    let builtin = find_builtin_command(command_name);
+    let advertised = find_advertised_command(app, command_name);
+    let custom = app.custom_commands.iter().find(|cmd| cmd.name == command_name);
+
+    let arg_hint = builtin
+        .and_then(|cmd| cmd.arg_hint.map(str::to_owned))
+        .or_else(|| custom.and_then(|cmd| cmd.argument_hint.clone()))
+        .or_else(|| advertised.and_then(|cmd| cmd.input_hint.clone()))
+        .filter(|hint| !hint.trim().is_empty());
+    let description = custom
+        .map(|cmd| cmd.description.clone())
+        .or_else(|| builtin.map(|cmd| cmd.description.to_owned()))
+        .or_else(|| advertised.map(|cmd| cmd.description.clone()))?;
+
+    let mut text = match arg_hint {
+        Some(hint) => format!("**{command_name}** `{hint}`"),
+        None => format!("**{command_name}**"),
+    };
+    let description = description.trim();
+    text.push_str("\n\n");
+    text.push_str(if description.is_empty() { "No description provided" } else { description });
+    if let Some(cmd) = custom {
+        let scope = match cmd.scope {
+            CustomCommandScope::Project => "Project",
+            CustomCommandScope::User => "User",
+        };
+        let _ = write!(text, "\n\n{scope} command: `{}`", cmd.path.display());
+    } else if builtin.is_some() {
+        text.push_str("\n\nBuilt-in command");
+    }
+    Some(text)
+}
+
 fn handle_plugins_submit(app: &mut App, args: &[&str]) -> bool {
     let _ = args;
 
@@ -119,11 +272,185 @@ fn handle_status_submit(app: &mut App, args: &[&str]) -> bool {
     true
 }
 
-fn handle_usage_submit(app: &mut App, args: &[&str]) -> bool {
+fn handle_stats_submit(app: &mut App, args: &[&str]) -> bool {
+    if !args.is_empty() {
+        push_system_message(app, "Usage: /stats");
+        return true;
+    }
+    let params = crate::app::model_params::stats_line(&app.model_params);
+    let timings = crate::app::tool_timings::report(&app.tool_timings);
+    push_system_message(app, format!("{params}\n\n{timings}"));
+    true
+}
+
+fn handle_diagnostics_submit(app: &mut App, args: &[&str]) -> bool {
     if !args.is_empty() {
-        push_system_message(app, "Usage: /usage");
+        push_system_message(app, "Usage: /diagnostics");
+        return true;
+    }
+
+    if let Err(err) = crate::app::config::open_diagnostics(app) {
+        push_system_message(app, format!("Failed to open diagnostics: {err}"));
+    }
+    true
+}
+
+fn handle_log_level_submit(app: &mut App, args: &[&str]) -> bool {
+    match args {
+        [] => {
+            let message = match (crate::logging::current_filter(), crate::logging::log_path()) {
+                (Some(filter), Some(path)) => {
+                    format!("Log filter: {filter}\nLog file: {}", path.display())
+                }
+                _ => "Logging is not enabled.".to_owned(),
+            };
+            push_system_message(app, message);
+        }
+        [directives] => match crate::logging::set_filter(directives) {
+            Ok(()) => push_system_message(app, format!("Log filter set to {directives}.")),
+            Err(err) => push_system_message(app, err),
+        },
+        _ => push_system_message(app, "Usage: /log-level [level|filter]"),
+    }
+    true
+}
+
+fn handle_transcript_submit(app: &mut App, args: &[&str]) -> bool {
+    let enable = match args {
+        [] | ["on"] => true,
+        ["off"] => false,
+        _ => {
+            push_system_message(app, "Usage: /transcript [on|off]");
+            return true;
+        }
+    };
+    app.print_transcript_on_exit = enable;
+    push_system_message(
+        app,
+        if enable {
+            "The transcript will be printed to the terminal when you quit."
+        } else {
+            "The transcript will not be printed on quit."
+        },
+    );
+    true
+}
+
+fn handle_files_submit(app: &mut App, args: &[&str]) -> bool {
+    if !args.is_empty() {
+        push_system_message(app, "Usage: /files");
+        return true;
+    }
+
+    if let Err(err) = crate::app::config::open_changed_files(app) {
+        push_system_message(app, format!("Failed to open changed files: {err}"));
+    }
+    true
+}
+
+fn handle_context_submit(app: &mut App, args: &[&str]) -> bool {
+    if !args.is_empty() {
+        push_system_message(app, "Usage: /context");
+        return true;
+    }
+
+    if let Err(err) = crate::app::config::open_context(app) {
+        push_system_message(app, format!("Failed to open context: {err}"));
+    }
+    true
+}
+
+fn handle_memory_submit(app: &mut App, args: &[&str]) -> bool {
+    let scope = match args {
+        [] | ["project"] => crate::app::memory::MemoryScope::Project,
+        ["user"] => crate::app::memory::MemoryScope::User,
+        _ => {
+            push_system_message(app, "Usage: /memory [project|user]");
+            return true;
+        }
+    };
+    match crate::app::memory::edit(app, scope) {
+        Ok(message) => push_system_message(app, message),
+        Err(err) => push_system_message(app, format!("Failed to edit memory: {err}")),
+    }
+    true
+}
+
+fn handle_trust_submit(app: &mut App, args: &[&str]) -> bool {
+    let result = match args {
+        [] => Ok(crate::app::trust::describe(app)),
+        ["add"] => return confirm_trust_root(app, None),
+        ["add", path] => return confirm_trust_root(app, Some(path)),
+        ["remove", path] => crate::app::trust::remove_root(app, path),
+        _ => {
+            push_system_message(app, "Usage: /trust [add [path] | remove <path>]");
+            return true;
+        }
+    };
+    match result {
+        Ok(message) => push_system_message(app, message),
+        Err(err) => push_system_message(app, format!("Failed to update trust: {err}")),
+    }
+    true
+}
+
+fn confirm_trust_root(app: &mut App, path: Option<&str>) -> bool {
+    let shown = path.unwrap_or(app.cwd_raw.as_str());
+    let dialog = ConfirmDialog::yes_no(
+        "Trust this folder?",
+        format!("{shown} and every project below it will open without asking."),
+        "Trust",
+        ConfirmAction::TrustRoot(path.map(str::to_owned)),
+    );
+    open_confirm(app, dialog);
+    true
+}
+
+fn handle_update_submit(app: &mut App, args: &[&str]) -> bool {
+    let install = match args {
+        [] => false,
+        ["install"] => true,
+        _ => {
+            push_system_message(app, "Usage: /update [install]");
+            return true;
+        }
+    };
+
+    push_user_message(app, if install { "/update install" } else { "/update" });
+    if app.is_offline() {
+        push_system_message(app, "/update is unavailable in offline mode.");
         return true;
     }
+    let label = if install { "Installing update..." } else { "Checking for updates..." };
+    set_command_pending(app, label, None);
+
+    let tx = app.event_tx.clone();
+    let http = app.http.clone();
+    let channel = app.config.update_channel_effective();
+    let current_version = env!("CARGO_PKG_VERSION").to_owned();
+    tokio::task::spawn_local(async move {
+        let result = if install {
+            crate::app::self_update::install(http, current_version, channel).await
+        } else {
+            crate::app::self_update::check(http, current_version, channel).await
+        };
+        let _ = tx.send(ClientEvent::SelfUpdateFinished { result, installed: install });
+    });
+    true
+}
+
+fn handle_usage_submit(app: &mut App, args: &[&str]) -> bool {
+    match args {
+        [] => {}
+        ["history"] => {
+            push_system_message(app, crate::app::usage::history::report());
+            return true;
+        }
+        _ => {
+            push_system_message(app, "Usage: /usage [history]");
+            return true;
+        }
+    }
 
     if let Err(err) = crate::app::config::open(app) {
         push_system_message(app, format!("Failed to open usage: {err}"));
@@ -151,66 +478,12 @@ fn handle_login_submit(app: &mut App, args: &[&str]) -> bool {
         return true;
     }
 
-    let Some(claude_path) = resolve_claude_cli(app, "login") else {
+    if resolve_claude_cli(app, "login").is_none() {
         return true;
-    };
-
-    set_command_pending(app, "Authenticating...", None);
-
-    let tx = app.event_tx.clone();
-    let conn = app.conn.clone();
-    tokio::task::spawn_local(async move {
-        tracing::debug!("Suspending TUI for claude auth login");
-        crate::app::suspend_terminal();
-
-        let result = tokio::process::Command::new(&claude_path)
-            .args(["auth", "login"])
-            .stdin(std::process::Stdio::inherit())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .status()
-            .await;
-
-        crate::app::resume_terminal();
-
-        match result {
-            Ok(status) => {
-                tracing::debug!(
-                    success = status.success(),
-                    code = ?status.code(),
-                    "claude auth login exited"
-                );
-                if status.success() {
-                    if !crate::app::auth::has_credentials() {
-                        let _ = tx.send(ClientEvent::SlashCommandError(
-                            "Login exited successfully but no credentials were saved. \
-                             Try /login again or run `claude auth login` in another terminal."
-                                .to_owned(),
-                        ));
-                        return;
-                    }
-                    if let Some(conn) = conn {
-                        let _ = tx.send(ClientEvent::AuthCompleted { conn });
-                    } else {
-                        let _ = tx.send(ClientEvent::SlashCommandError(
-                            "Login succeeded but no connection available to start a session."
-                                .to_owned(),
-                        ));
-                    }
-                } else {
-                    let _ = tx.send(ClientEvent::SlashCommandError(format!(
-                        "/login failed (exit code: {})",
-                        status.code().map_or("unknown".to_owned(), |c| c.to_string())
-                    )));
-                }
-            }
-            Err(e) => {
-                let _ = tx.send(ClientEvent::SlashCommandError(format!(
-                    "Failed to run claude auth login: {e}"
-                )));
-            }
-        }
-    });
+    }
+    if let Err(err) = crate::app::config::open_login(app) {
+        push_system_message(app, format!("Failed to open login: {err}"));
+    }
     true
 }
 
@@ -314,6 +587,14 @@ fn handle_mode_submit(app: &mut App, args: &[&str]) -> bool {
         push_system_message(app, "Usage: /mode <id>");
         return true;
     }
+    if app.read_only {
+        push_system_message(app, "Cannot switch mode: claude-rs was started with --read-only.");
+        return true;
+    }
+    if !app.agent_supports(|caps| caps.supports_modes) {
+        push_system_message(app, "The connected agent does not support permission modes.");
+        return true;
+    }
 
     let Some((conn, sid)) = require_active_session(
         app,
@@ -397,11 +678,29 @@ fn handle_new_session_submit(app: &mut App, args: &[&str]) -> bool {
         return true;
     }
 
+    if app.messages.iter().any(|message| matches!(message.role, MessageRole::User)) {
+        open_confirm(
+            app,
+            ConfirmDialog::yes_no(
+                "Start a new session?",
+                "The current conversation ends here. It stays available through /resume.",
+                "Start new session",
+                ConfirmAction::NewSession,
+            ),
+        );
+        return true;
+    }
+    run_new_session(app);
+    true
+}
+
+/// Start a fresh session right away; `/new-session` asks first when there is a conversation.
+pub(crate) fn run_new_session(app: &mut App) {
     push_user_message(app, "/new-session");
 
     let Some(conn) = require_connection(app, "Cannot create new session: not connected yet.")
     else {
-        return true;
+        return;
     };
 
     set_command_pending(app, "Starting new session...", None);
@@ -411,7 +710,6 @@ fn handle_new_session_submit(app: &mut App, args: &[&str]) -> bool {
             .event_tx
             .send(ClientEvent::SlashCommandError(format!("Failed to run /new-session: {e}")));
     }
-    true
 }
 
 fn handle_resume_submit(app: &mut App, args: &[&str]) -> bool {
@@ -426,6 +724,10 @@ fn handle_resume_submit(app: &mut App, args: &[&str]) -> bool {
     }
 
     push_user_message(app, format!("/resume {session_id}"));
+    if !app.agent_supports(|caps| caps.supports_resume_session) {
+        push_system_message(app, "The connected agent does not support resuming sessions.");
+        return true;
+    }
     let Some(conn) = require_connection(app, "Cannot resume session: not connected yet.") else {
         return true;
     };
@@ -7,8 +7,10 @@
 //! - `candidates`: candidate detection, filtering, and building
 //! - `navigation`: autocomplete activation, movement, and confirm
 //! - `executors`: slash command execution handlers
+//! - `custom`: custom commands loaded from `.claude/commands`
 
 mod candidates;
+mod custom;
 mod executors;
 mod navigation;
 
@@ -22,6 +24,12 @@ pub const MAX_VISIBLE: usize = 8;
 const MAX_CANDIDATES: usize = 50;
 
 // Re-export public API
+pub(crate) use candidates::session_age_label;
+pub use custom::{
+    CustomCommand, CustomCommandScope, expand_custom_command, merge_custom_commands,
+    reload_custom_commands,
+};
+pub(crate) use executors::run_new_session;
 pub use executors::try_handle_submit;
 pub use navigation::{
     activate, confirm_selection, deactivate, move_down, move_up, sync_with_cursor, update_query,
@@ -32,6 +40,8 @@ pub struct SlashCandidate {
     pub insert_value: String,
     pub primary: String,
     pub secondary: Option<String>,
+    /// Argument placeholder shown next to command names (e.g. `<session_id>`).
+    pub arg_hint: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -93,6 +103,7 @@ fn push_system_message(app: &mut App, text: impl Into<String>) {
         role: MessageRole::System(None),
         blocks: vec![MessageBlock::Text(TextBlock::from_complete(&text))],
         usage: None,
+        annotation: None,
     });
     app.enforce_history_retention_tracked();
     app.viewport.engage_auto_scroll();
@@ -104,6 +115,7 @@ fn push_user_message(app: &mut App, text: impl Into<String>) {
         role: MessageRole::User,
         blocks: vec![MessageBlock::Text(TextBlock::from_complete(&text))],
         usage: None,
+        annotation: None,
     });
     app.enforce_history_retention_tracked();
     app.viewport.engage_auto_scroll();
@@ -147,7 +159,8 @@ mod tests {
 
     // Re-import submodule items needed by tests
     use super::candidates::{
-        argument_candidates, detect_slash_at_cursor, supported_command_candidates,
+        argument_candidates, detect_slash_at_cursor, filter_command_candidates,
+        supported_command_candidates,
     };
 
     #[test]
@@ -194,6 +207,80 @@ mod tests {
         assert!(names.iter().any(|n| n == "/usage"), "missing /usage");
     }
 
+    #[test]
+    fn command_candidates_carry_argument_hints_and_one_line_help() {
+        let mut app = App::test_default();
+        app.available_commands = vec![
+            model::AvailableCommand::new("review", "Review a pull request\nLong details here")
+                .input_hint("<pr>"),
+        ];
+
+        let candidates = supported_command_candidates(&app);
+        let resume = candidates.iter().find(|c| c.primary == "/resume").expect("/resume");
+        assert_eq!(resume.arg_hint.as_deref(), Some("<session_id>"));
+        let review = candidates.iter().find(|c| c.primary == "/review").expect("/review");
+        assert_eq!(review.arg_hint.as_deref(), Some("<pr>"));
+        assert_eq!(review.secondary.as_deref(), Some("Review a pull request"));
+        let config = candidates.iter().find(|c| c.primary == "/config").expect("/config");
+        assert!(config.arg_hint.is_none());
+    }
+
+    #[test]
+    fn command_filter_is_fuzzy_and_ranked() {
+        let app = App::test_default();
+        let all = supported_command_candidates(&app);
+
+        let names: Vec<String> =
+            filter_command_candidates(&all, "ns").into_iter().map(|c| c.primary).collect();
+        assert_eq!(names.first().map(String::as_str), Some("/new-session"));
+
+        let names: Vec<String> =
+            filter_command_candidates(&all, "cmp").into_iter().map(|c| c.primary).collect();
+        assert_eq!(names.first().map(String::as_str), Some("/compact"));
+        assert!(!names.iter().any(|n| n == "/config"));
+    }
+
+    #[test]
+    fn help_argument_candidates_list_commands_without_slash() {
+        let app = App::test_default();
+        let candidates = argument_candidates(&app, "/help", 0);
+        assert!(candidates.iter().any(|c| c.insert_value == "resume" && c.primary == "/resume"));
+        assert!(!candidates.iter().any(|c| c.primary == "/help"));
+    }
+
+    #[test]
+    fn help_with_command_shows_full_description() {
+        let mut app = App::test_default();
+        app.available_commands = vec![
+            model::AvailableCommand::new("/review", "Review a pull request\nLong details here")
+                .input_hint("<pr>"),
+        ];
+
+        let consumed = try_handle_submit(&mut app, "/help review");
+
+        assert!(consumed);
+        let Some(MessageBlock::Text(block)) = app.messages.last().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected help message");
+        };
+        assert!(block.text.starts_with("**/review** `<pr>`"));
+        assert!(block.text.contains("Long details here"));
+    }
+
+    #[test]
+    fn help_with_unknown_command_reports_it() {
+        let mut app = App::test_default();
+
+        let consumed = try_handle_submit(&mut app, "/help nope");
+
+        assert!(consumed);
+        let Some(MessageBlock::Text(block)) = app.messages.last().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected message");
+        };
+        assert_eq!(block.text, "Unknown command: /nope");
+    }
+
     #[test]
     fn config_without_args_opens_settings_view() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -414,6 +501,7 @@ mod tests {
                 insert_value: "new-id".to_owned(),
                 primary: "New".to_owned(),
                 secondary: None,
+                arg_hint: None,
             }],
             dialog: DialogState::default(),
         });
@@ -468,6 +556,27 @@ mod tests {
         assert_eq!(block.text, "Usage: /resume <session_id>");
     }
 
+    #[test]
+    fn resume_is_refused_when_agent_lacks_resume_support() {
+        let mut app = App::test_default();
+        app.agent_info = Some(crate::agent::types::InitializeResult {
+            agent_name: "local-agent".to_owned(),
+            agent_version: "0.1.0".to_owned(),
+            auth_methods: Vec::new(),
+            capabilities: crate::agent::types::AgentCapabilities::default(),
+            protocol_version: Some(crate::agent::wire::BRIDGE_PROTOCOL_VERSION),
+        });
+
+        assert!(try_handle_submit(&mut app, "/resume old-id"));
+
+        assert!(app.resuming_session_id.is_none());
+        let Some(MessageBlock::Text(block)) = app.messages.last().and_then(|m| m.blocks.first())
+        else {
+            panic!("expected text block");
+        };
+        assert_eq!(block.text, "The connected agent does not support resuming sessions.");
+    }
+
     #[test]
     fn resume_command_is_rendered_as_user_message() {
         let mut app = App::test_default();
@@ -649,6 +758,7 @@ mod tests {
             role: MessageRole::User,
             blocks: vec![MessageBlock::Text(TextBlock::from_complete("keep"))],
             usage: None,
+            annotation: None,
         });
 
         let consumed = try_handle_submit(&mut app, "/compact now");
@@ -693,6 +803,7 @@ mod tests {
                 insert_value: "/mode".into(),
                 primary: "/mode".into(),
                 secondary: None,
+                arg_hint: None,
             }],
             dialog: DialogState::default(),
         });
@@ -715,6 +826,20 @@ mod tests {
         assert_eq!(app.config.active_tab, super::super::ConfigTab::Status);
     }
 
+    #[test]
+    fn diagnostics_opens_stderr_overlay_over_status_tab() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut app = App::test_default();
+        app.settings_home_override = Some(dir.path().to_path_buf());
+
+        let consumed = try_handle_submit(&mut app, "/diagnostics");
+
+        assert!(consumed);
+        assert_eq!(app.active_view, super::super::ActiveView::Config);
+        assert_eq!(app.config.active_tab, super::super::ConfigTab::Status);
+        assert!(app.config.diagnostics_overlay().is_some());
+    }
+
     #[test]
     fn usage_opens_config_at_usage_tab() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -757,7 +882,7 @@ mod tests {
         let Some(MessageBlock::Text(block)) = last.blocks.first() else {
             panic!("expected text block");
         };
-        assert_eq!(block.text, "Usage: /usage");
+        assert_eq!(block.text, "Usage: /usage [history]");
     }
 
     #[test]
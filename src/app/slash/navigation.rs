@@ -16,6 +16,7 @@ pub fn activate(app: &mut App) {
     app.slash = Some(state);
     app.mention = None;
     app.subagent = None;
+    app.emoji = None;
     app.claim_focus_target(FocusTarget::Mention);
 }
 
@@ -52,7 +53,7 @@ pub fn sync_with_cursor(app: &mut App) {
 
 pub fn deactivate(app: &mut App) {
     app.slash = None;
-    if app.mention.is_none() && app.subagent.is_none() {
+    if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
         app.release_focus_target(FocusTarget::Mention);
     }
 }
@@ -76,7 +77,7 @@ pub fn confirm_selection(app: &mut App) {
     };
 
     let Some(candidate) = slash.candidates.get(slash.dialog.selected) else {
-        if app.mention.is_none() && app.subagent.is_none() {
+        if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
             app.release_focus_target(FocusTarget::Mention);
         }
         return;
@@ -89,7 +90,7 @@ pub fn confirm_selection(app: &mut App) {
             line_count = app.input.lines().len(),
             "Slash confirm aborted: trigger row out of bounds"
         );
-        if app.mention.is_none() && app.subagent.is_none() {
+        if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
             app.release_focus_target(FocusTarget::Mention);
         }
         return;
@@ -104,7 +105,7 @@ pub fn confirm_selection(app: &mut App) {
                     line_len = chars.len(),
                     "Slash confirm aborted: trigger column out of bounds"
                 );
-                if app.mention.is_none() && app.subagent.is_none() {
+                if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
                     app.release_focus_target(FocusTarget::Mention);
                 }
                 return;
@@ -115,7 +116,7 @@ pub fn confirm_selection(app: &mut App) {
                     found = ?chars[slash.trigger_col],
                     "Slash confirm aborted: trigger column is not slash"
                 );
-                if app.mention.is_none() && app.subagent.is_none() {
+                if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
                     app.release_focus_target(FocusTarget::Mention);
                 }
                 return;
@@ -135,7 +136,7 @@ pub fn confirm_selection(app: &mut App) {
                     line_len = chars.len(),
                     "Slash confirm aborted: invalid argument token range"
                 );
-                if app.mention.is_none() && app.subagent.is_none() {
+                if app.mention.is_none() && app.subagent.is_none() && app.emoji.is_none() {
                     app.release_focus_target(FocusTarget::Mention);
                 }
                 return;
@@ -165,7 +166,12 @@ pub fn confirm_selection(app: &mut App) {
     app.input.replace_lines_and_cursor(lines, slash.trigger_row, new_cursor_col.min(new_line_len));
 
     sync_with_cursor(app);
-    if app.slash.is_none() && app.mention.is_none() && app.subagent.is_none() {
+    if app.slash.is_none()
+        && app.mention.is_none()
+        && app.subagent.is_none()
+        && app.emoji.is_none()
+        && app.emoji.is_none()
+    {
         app.release_focus_target(FocusTarget::Mention);
     }
 }
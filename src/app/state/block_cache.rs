@@ -10,6 +10,12 @@ fn next_cache_access_tick() -> u64 {
     CACHE_ACCESS_TICK.fetch_add(1, Ordering::Relaxed)
 }
 
+/// Tick the next cache access will get. Caches with a lower
+/// `last_access_tick` have not been read or written since this was taken.
+pub fn current_cache_access_tick() -> u64 {
+    CACHE_ACCESS_TICK.load(Ordering::Relaxed)
+}
+
 /// Cached rendered lines for a block. Stores a version counter so the cache
 /// is only recomputed when the block content actually changes.
 ///
@@ -6,7 +6,8 @@ use std::collections::HashSet;
 use std::mem::size_of;
 
 use super::messages::{
-    ChatMessage, IncrementalMarkdown, MessageBlock, MessageRole, TextBlock, WelcomeBlock,
+    ChatMessage, FileDiffStat, IncrementalMarkdown, MessageBlock, MessageRole, TextBlock,
+    WelcomeBlock,
 };
 use super::tool_call_info::{InlinePermission, InlineQuestion, ToolCallInfo};
 use super::types::{HistoryRetentionStats, MessageUsage, RecentSessionInfo};
@@ -92,6 +93,12 @@ impl super::App {
             .saturating_add(tc.terminal_id.as_ref().map_or(0, String::capacity))
             .saturating_add(tc.terminal_command.as_ref().map_or(0, String::capacity))
             .saturating_add(tc.terminal_output.as_ref().map_or(0, String::capacity))
+            .saturating_add(
+                tc.terminal_env
+                    .iter()
+                    .map(|(name, value)| name.capacity() + value.capacity())
+                    .sum(),
+            )
             .saturating_add(
                 tc.content.capacity().saturating_mul(size_of::<model::ToolCallContent>()),
             );
@@ -157,9 +164,27 @@ impl super::App {
                         .saturating_add(block.text.capacity())
                         .saturating_add(block.markdown.text_capacity());
                 }
+                MessageBlock::Thinking(block) => {
+                    total = total.saturating_add(block.text.capacity());
+                }
+                MessageBlock::DiffSummary(summary) => {
+                    total = total.saturating_add(
+                        summary.files.capacity().saturating_mul(size_of::<FileDiffStat>()),
+                    );
+                    for file in &summary.files {
+                        total = total.saturating_add(file.path.capacity());
+                        for diff in &file.diffs {
+                            total = total
+                                .saturating_add(size_of::<model::Diff>())
+                                .saturating_add(diff.new_text.capacity())
+                                .saturating_add(diff.old_text.as_ref().map_or(0, String::capacity));
+                        }
+                    }
+                }
                 MessageBlock::ToolCall(tc) => {
                     total = total.saturating_add(Self::measure_tool_call_bytes(tc));
                 }
+                MessageBlock::RecoveryHint(_) => {}
                 MessageBlock::Welcome(welcome) => {
                     total = total
                         .saturating_add(size_of::<WelcomeBlock>())
@@ -272,6 +297,8 @@ impl super::App {
         if self.history_retention_stats.total_dropped_messages == 0 {
             if let Some(idx) = marker_idx {
                 self.messages.remove(idx);
+                self.shift_reading_anchor_for(idx, 1, 0);
+                self.shift_folded_turns_for(idx, 1, 0);
                 self.invalidate_layout(InvalidationLevel::From(idx));
                 self.rebuild_tool_indices_and_terminal_refs();
             }
@@ -305,12 +332,45 @@ impl super::App {
                 role: MessageRole::System(None),
                 blocks: vec![MessageBlock::Text(TextBlock::from_complete(&marker_text))],
                 usage: None,
+                annotation: None,
             },
         );
+        self.shift_reading_anchor_for(insert_idx, 0, 1);
+        self.shift_folded_turns_for(insert_idx, 0, 1);
         self.invalidate_layout(InvalidationLevel::From(insert_idx));
         self.rebuild_tool_indices_and_terminal_refs();
     }
 
+    /// Shift the viewport's reading anchor after `removed` messages were taken
+    /// out at `idx` or `inserted` messages were put there.
+    fn shift_reading_anchor_for(&mut self, idx: usize, removed: usize, inserted: usize) {
+        let Some(anchor) = self.viewport.reading_anchor else {
+            return;
+        };
+        if anchor.message_index < idx {
+            return;
+        }
+        let anchor_removed = anchor.message_index < idx + removed;
+        self.viewport.shift_reading_anchor(removed, inserted, anchor_removed);
+    }
+
+    /// Shift folded turn starts the same way; a fold whose start message was
+    /// removed is dropped.
+    fn shift_folded_turns_for(&mut self, idx: usize, removed: usize, inserted: usize) {
+        self.folded_turns = std::mem::take(&mut self.folded_turns)
+            .into_iter()
+            .filter_map(|start| {
+                if start < idx {
+                    Some(start)
+                } else if start < idx + removed {
+                    None
+                } else {
+                    Some(start - removed + inserted)
+                }
+            })
+            .collect();
+    }
+
     #[allow(clippy::cast_precision_loss)]
     pub fn enforce_history_retention(&mut self) -> HistoryRetentionStats {
         let mut stats = HistoryRetentionStats::default();
@@ -379,6 +439,17 @@ impl super::App {
                         0.0
                     };
                 }
+                if let Some(anchor) = self.viewport.reading_anchor {
+                    let removed_before =
+                        drop_set.iter().filter(|&&idx| idx < anchor.message_index).count();
+                    let anchor_removed = drop_set.contains(&anchor.message_index);
+                    self.viewport.shift_reading_anchor(removed_before, 0, anchor_removed);
+                }
+                self.folded_turns = std::mem::take(&mut self.folded_turns)
+                    .into_iter()
+                    .filter(|start| !drop_set.contains(start))
+                    .map(|start| start - drop_set.iter().filter(|&&idx| idx < start).count())
+                    .collect();
                 self.rebuild_tool_indices_and_terminal_refs();
                 self.invalidate_layout(InvalidationLevel::From(0));
                 self.needs_redraw = true;
@@ -0,0 +1,308 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Overall memory budget. The render-cache budget and history retention each
+//! cap their own share; this pass adds up messages, block caches and terminal
+//! buffers and, once the total is over `--memory-budget`, evicts cold caches
+//! and then cuts the output of old finished commands down to its tail.
+
+use super::block_cache::current_cache_access_tick;
+use super::messages::MessageBlock;
+use super::types::{MemoryTrimStats, MemoryUsage};
+use super::{App, InvalidationLevel};
+use crate::agent::model;
+use std::collections::HashSet;
+
+/// Bytes kept from the end of a finished command's output when trimming.
+const TERMINAL_TAIL_BYTES: usize = 16 * 1024;
+
+const TERMINAL_TRIM_MARKER: &str = "[earlier output trimmed to stay within the memory budget]\n";
+
+impl App {
+    /// Current footprint. `cache_bytes` comes from the render-cache pass,
+    /// which already walked every block this frame.
+    #[must_use]
+    pub fn measure_memory_usage(&self, cache_bytes: usize) -> MemoryUsage {
+        let terminal_bytes = self
+            .terminals
+            .borrow()
+            .values()
+            .map(|terminal| terminal.output_buffer.lock().map_or(0, |buf| buf.capacity()))
+            .fold(0, usize::saturating_add);
+        MemoryUsage {
+            message_bytes: self.history_retention_stats.total_after_bytes,
+            cache_bytes,
+            terminal_bytes,
+        }
+    }
+
+    pub fn enforce_memory_budget(&mut self, cache_bytes: usize) -> MemoryTrimStats {
+        let mut usage = self.measure_memory_usage(cache_bytes);
+        let mut stats = MemoryTrimStats::default();
+        let max_bytes = self.memory_budget.max_bytes;
+
+        if usage.total() > max_bytes {
+            self.evict_cold_caches(usage.total() - max_bytes, &mut stats);
+            usage.cache_bytes = usage.cache_bytes.saturating_sub(stats.evicted_cache_bytes);
+        }
+        if usage.total() > max_bytes {
+            let freed = self.drop_finished_terminals(&mut stats);
+            usage.terminal_bytes = usage.terminal_bytes.saturating_sub(freed);
+        }
+        if usage.total() > max_bytes {
+            self.trim_terminal_output(usage.total() - max_bytes, &mut stats);
+            usage.message_bytes = usage.message_bytes.saturating_sub(stats.trimmed_terminal_bytes);
+            self.history_retention_stats.total_after_bytes = usage.message_bytes;
+        }
+
+        let freed = stats.freed_bytes();
+        if freed > 0 || stats.dropped_terminals > 0 {
+            self.memory_budget.trim_passes = self.memory_budget.trim_passes.saturating_add(1);
+            self.memory_budget.total_trimmed_bytes =
+                self.memory_budget.total_trimmed_bytes.saturating_add(freed);
+            tracing::debug!(
+                target: "diagnostics",
+                evicted_cache_bytes = stats.evicted_cache_bytes,
+                trimmed_terminal_bytes = stats.trimmed_terminal_bytes,
+                dropped_terminals = stats.dropped_terminals,
+                total_bytes = usage.total(),
+                max_bytes,
+                "memory budget trim"
+            );
+        }
+        self.memory_budget.usage = usage;
+        self.memory_budget.last_pass_tick = current_cache_access_tick();
+        stats
+    }
+
+    /// Evict caches not touched since the previous pass, least recently used
+    /// first, until `target` bytes are freed.
+    fn evict_cold_caches(&mut self, target: usize, stats: &mut MemoryTrimStats) {
+        let cold_before = self.memory_budget.last_pass_tick;
+        let (_, mut slots) = self.cache_slot_candidates();
+        slots.retain(|slot| slot.last_access_tick < cold_before);
+        slots.sort_by_key(|slot| (slot.last_access_tick, std::cmp::Reverse(slot.bytes)));
+        for slot in slots {
+            if stats.evicted_cache_bytes >= target {
+                break;
+            }
+            let removed = self.evict_cache_slot(slot.msg_idx, slot.block_idx);
+            if removed > 0 {
+                stats.evicted_cache_bytes = stats.evicted_cache_bytes.saturating_add(removed);
+                stats.evicted_cache_blocks = stats.evicted_cache_blocks.saturating_add(1);
+            }
+        }
+        if stats.evicted_cache_blocks > 0 {
+            self.render_cache_budget.total_evictions =
+                self.render_cache_budget.total_evictions.saturating_add(stats.evicted_cache_blocks);
+        }
+    }
+
+    /// Forget terminal processes that have exited and are no longer attached
+    /// to a running tool call. Returns the buffer bytes released.
+    fn drop_finished_terminals(&mut self, stats: &mut MemoryTrimStats) -> usize {
+        let attached: HashSet<&str> =
+            self.terminal_tool_calls.iter().map(|(id, _, _)| id.as_str()).collect();
+        let mut freed = 0usize;
+        self.terminals.borrow_mut().retain(|id, terminal| {
+            let running = terminal
+                .child
+                .as_mut()
+                .is_some_and(|child| !matches!(child.try_wait(), Ok(Some(_))));
+            if attached.contains(id.as_str()) || running {
+                return true;
+            }
+            freed =
+                freed.saturating_add(terminal.output_buffer.lock().map_or(0, |buf| buf.capacity()));
+            stats.dropped_terminals += 1;
+            false
+        });
+        freed
+    }
+
+    /// Cut captured output of finished commands to its last
+    /// [`TERMINAL_TAIL_BYTES`], oldest messages first, until `target` bytes
+    /// are freed.
+    fn trim_terminal_output(&mut self, target: usize, stats: &mut MemoryTrimStats) {
+        let mut dirty_from: Option<usize> = None;
+        'messages: for (msg_idx, msg) in self.messages.iter_mut().enumerate() {
+            for block in &mut msg.blocks {
+                if stats.trimmed_terminal_bytes >= target {
+                    break 'messages;
+                }
+                let MessageBlock::ToolCall(tc) = block else {
+                    continue;
+                };
+                if matches!(
+                    tc.status,
+                    model::ToolCallStatus::Pending | model::ToolCallStatus::InProgress
+                ) {
+                    continue;
+                }
+                let Some(output) = tc.terminal_output.as_mut() else {
+                    continue;
+                };
+                let Some(freed) = trim_to_tail(output, TERMINAL_TAIL_BYTES) else {
+                    continue;
+                };
+                stats.trimmed_terminal_bytes = stats.trimmed_terminal_bytes.saturating_add(freed);
+                stats.trimmed_tool_calls += 1;
+                tc.mark_tool_call_layout_dirty();
+                dirty_from.get_or_insert(msg_idx);
+            }
+        }
+        if let Some(msg_idx) = dirty_from {
+            self.invalidate_layout(InvalidationLevel::From(msg_idx));
+        }
+    }
+}
+
+/// Keep the last `keep` bytes of `output`, starting at a line boundary, behind
+/// a marker line. Returns the bytes freed, or `None` if it was already short.
+fn trim_to_tail(output: &mut String, keep: usize) -> Option<usize> {
+    if output.len() <= keep.saturating_add(TERMINAL_TRIM_MARKER.len()) {
+        return None;
+    }
+    let mut start = output.len() - keep;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    if let Some(newline) = output[start..].find('\n')
+        && start + newline + 1 < output.len()
+    {
+        start += newline + 1;
+    }
+    let before = output.capacity();
+    let trimmed = format!("{TERMINAL_TRIM_MARKER}{}", &output[start..]);
+    *output = trimmed;
+    Some(before.saturating_sub(output.capacity()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::{
+        BlockCache, ChatMessage, MessageRole, TerminalSnapshotMode, TextBlock, ToolCallInfo,
+    };
+    use pretty_assertions::assert_eq;
+    use ratatui::text::Line;
+
+    fn text_message(text: &str) -> ChatMessage {
+        ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    fn execute_message(id: &str, status: model::ToolCallStatus, output: &str) -> ChatMessage {
+        let tc = ToolCallInfo {
+            id: id.to_owned(),
+            title: "cargo test".to_owned(),
+            sdk_tool_name: "Bash".to_owned(),
+            raw_input: None,
+            output_metadata: None,
+            web_output: None,
+            status,
+            content: Vec::new(),
+            collapsed: false,
+            hidden: false,
+            terminal_id: None,
+            terminal_command: Some("cargo test".to_owned()),
+            terminal_env: Vec::new(),
+            terminal_output: Some(output.to_owned()),
+            terminal_output_len: output.len(),
+            terminal_bytes_seen: output.len(),
+            terminal_snapshot_mode: TerminalSnapshotMode::AppendOnly,
+            render_epoch: 0,
+            layout_epoch: 0,
+            last_measured_width: 0,
+            last_measured_height: 0,
+            last_measured_layout_epoch: 0,
+            last_measured_layout_generation: 0,
+            cache: BlockCache::default(),
+            pending_permission: None,
+            pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
+        };
+        ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![MessageBlock::ToolCall(Box::new(tc))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    fn cache_of(app: &mut App, msg_idx: usize) -> &mut BlockCache {
+        match &mut app.messages[msg_idx].blocks[0] {
+            MessageBlock::Text(block) => &mut block.cache,
+            _ => panic!("expected text block"),
+        }
+    }
+
+    #[test]
+    fn evicts_only_caches_not_drawn_since_the_last_pass() {
+        let mut app = App::test_default();
+        app.messages = vec![text_message("cold"), text_message("hot")];
+        cache_of(&mut app, 0).store(vec![Line::from("x".repeat(4000))]);
+        cache_of(&mut app, 1).store(vec![Line::from("y".repeat(4000))]);
+        app.memory_budget.last_pass_tick = current_cache_access_tick();
+        let _ = cache_of(&mut app, 1).get();
+        let cold = cache_of(&mut app, 0).cached_bytes();
+        let hot = cache_of(&mut app, 1).cached_bytes();
+        app.memory_budget.max_bytes = 1;
+
+        let stats = app.enforce_memory_budget(cold + hot);
+
+        assert_eq!(stats.evicted_cache_blocks, 1);
+        assert_eq!(stats.evicted_cache_bytes, cold);
+        assert_eq!(cache_of(&mut app, 0).cached_bytes(), 0);
+        assert_eq!(cache_of(&mut app, 1).cached_bytes(), hot);
+        assert_eq!(app.memory_budget.usage.cache_bytes, hot);
+    }
+
+    #[test]
+    fn trims_finished_command_output_to_its_tail() {
+        let mut app = App::test_default();
+        let output = "line of output\n".repeat(10_000);
+        app.messages = vec![
+            execute_message("done", model::ToolCallStatus::Completed, &output),
+            execute_message("running", model::ToolCallStatus::InProgress, &output),
+        ];
+        app.history_retention_stats.total_after_bytes = output.len() * 2;
+        app.memory_budget.max_bytes = 1;
+
+        let stats = app.enforce_memory_budget(0);
+
+        assert_eq!(stats.trimmed_tool_calls, 1);
+        let outputs: Vec<&str> = app
+            .messages
+            .iter()
+            .filter_map(|msg| match &msg.blocks[0] {
+                MessageBlock::ToolCall(tc) => tc.terminal_output.as_deref(),
+                _ => None,
+            })
+            .collect();
+        assert!(outputs[0].starts_with(TERMINAL_TRIM_MARKER));
+        assert!(outputs[0].len() <= TERMINAL_TRIM_MARKER.len() + TERMINAL_TAIL_BYTES);
+        assert!(outputs[0][TERMINAL_TRIM_MARKER.len()..].starts_with("line of output\n"));
+        assert_eq!(outputs[1], output);
+        assert!(app.memory_budget.usage.message_bytes < output.len() * 2);
+    }
+
+    #[test]
+    fn stays_idle_under_budget() {
+        let mut app = App::test_default();
+        app.messages = vec![text_message("small")];
+        cache_of(&mut app, 0).store(vec![Line::from("z".repeat(100))]);
+
+        let stats = app.enforce_memory_budget(100);
+
+        assert_eq!(stats, MemoryTrimStats::default());
+        assert_eq!(app.memory_budget.trim_passes, 0);
+        assert_eq!(app.memory_budget.usage.cache_bytes, 100);
+    }
+}
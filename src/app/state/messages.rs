@@ -4,11 +4,17 @@
 use super::block_cache::BlockCache;
 use super::tool_call_info::ToolCallInfo;
 use super::types::{MessageUsage, RecentSessionInfo};
+use crate::agent::error_handling::TurnErrorClass;
+use crate::agent::model;
+use crate::app::config::WelcomeBanner;
 
 pub struct ChatMessage {
     pub role: MessageRole,
     pub blocks: Vec<MessageBlock>,
     pub usage: Option<MessageUsage>,
+    /// Local-only note attached with `m` in keyboard selection mode; shown
+    /// dimmed under the message and included in exports, never sent.
+    pub annotation: Option<String>,
 }
 
 impl ChatMessage {
@@ -29,9 +35,15 @@ impl ChatMessage {
                 model_name: model_name.to_owned(),
                 cwd: cwd.to_owned(),
                 recent_sessions: recent_sessions.to_vec(),
+                memory_files: Vec::new(),
+                mcp_servers: Vec::new(),
+                selected: None,
+                banner: WelcomeBanner::default(),
+                banner_art: Vec::new(),
                 cache: BlockCache::default(),
             })],
             usage: None,
+            annotation: None,
         }
     }
 }
@@ -121,6 +133,31 @@ pub struct TextBlock {
     /// metadata directly so spacing, height measurement, and scroll skipping all
     /// agree without mutating source text.
     pub trailing_spacing: TextBlockSpacing,
+    /// Set when this block is a code block revising one from an earlier reply;
+    /// it then renders as a word diff against that code.
+    pub revision: Option<Box<TextRevision>>,
+    /// Layout of the fenced code in this block.
+    pub code_view: CodeView,
+}
+
+/// How fenced code blocks in a text block are laid out; toggled from keyboard
+/// selection mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CodeView {
+    /// Number the lines in a gutter.
+    pub line_numbers: bool,
+    /// Scroll long lines horizontally instead of soft-wrapping them.
+    pub scroll: bool,
+    /// First column shown while `scroll` is set.
+    pub offset: usize,
+}
+
+/// Earlier version of a revised code block.
+pub struct TextRevision {
+    /// Code body of the earlier block, without its fences.
+    pub previous: String,
+    /// Show the full text instead of the word diff.
+    pub expanded: bool,
 }
 
 impl TextBlock {
@@ -131,6 +168,8 @@ impl TextBlock {
             text,
             cache: BlockCache::default(),
             trailing_spacing: TextBlockSpacing::None,
+            revision: None,
+            code_view: CodeView::default(),
         }
     }
 
@@ -151,11 +190,73 @@ impl TextBlock {
     }
 }
 
+/// The agent's thinking text, shown dimmed above the reply it led to.
+#[derive(Default)]
+pub struct ThinkingBlock {
+    pub text: String,
+    pub cache: BlockCache,
+    /// Shown in full even when the thinking display setting is `collapsed`.
+    pub expanded: bool,
+}
+
+impl ThinkingBlock {
+    #[must_use]
+    pub fn new(text: String) -> Self {
+        Self { text, ..Self::default() }
+    }
+}
+
+/// Line counts for one file edited during a turn, with the edits behind them.
+pub struct FileDiffStat {
+    /// Path as shown in the summary, relative to the session cwd when inside it.
+    pub path: String,
+    pub added: usize,
+    pub removed: usize,
+    /// The turn's edits to this file, in order.
+    pub diffs: Vec<model::Diff>,
+    /// Show the diffs under the file's row.
+    pub expanded: bool,
+}
+
+/// "3 files changed, +120 -45" summary appended to a turn that edited files.
+#[derive(Default)]
+pub struct DiffSummaryBlock {
+    pub files: Vec<FileDiffStat>,
+    pub cache: BlockCache,
+}
+
+impl DiffSummaryBlock {
+    /// Lines added and removed across all files.
+    #[must_use]
+    pub fn totals(&self) -> (usize, usize) {
+        self.files
+            .iter()
+            .fold((0, 0), |(added, removed), file| (added + file.added, removed + file.removed))
+    }
+}
+
+/// Recovery hint closing a turn error; its class also picks the icon, title,
+/// and color of the error message.
+pub struct RecoveryHintBlock {
+    pub class: TurnErrorClass,
+    pub cache: BlockCache,
+}
+
+impl RecoveryHintBlock {
+    #[must_use]
+    pub fn new(class: TurnErrorClass) -> Self {
+        Self { class, cache: BlockCache::default() }
+    }
+}
+
 /// Ordered content block - text and tool calls interleaved as they arrive.
 pub enum MessageBlock {
     Text(TextBlock),
+    Thinking(ThinkingBlock),
     ToolCall(Box<ToolCallInfo>),
     Welcome(WelcomeBlock),
+    DiffSummary(DiffSummaryBlock),
+    RecoveryHint(RecoveryHintBlock),
 }
 
 #[derive(Debug)]
@@ -177,5 +278,14 @@ pub struct WelcomeBlock {
     pub model_name: String,
     pub cwd: String,
     pub recent_sessions: Vec<RecentSessionInfo>,
+    /// Display paths of the `CLAUDE.md` memory files loaded for this project.
+    pub memory_files: Vec<String>,
+    /// Names of the configured MCP servers, once a snapshot has arrived.
+    pub mcp_servers: Vec<String>,
+    /// Highlighted start-screen entry; `None` until Up/Down is pressed.
+    pub selected: Option<usize>,
+    pub banner: WelcomeBanner,
+    /// Lines from the configured banner art file; empty draws Ferris.
+    pub banner_art: Vec<String>,
     pub cache: BlockCache,
 }
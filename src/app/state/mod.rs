@@ -4,6 +4,7 @@
 pub mod block_cache;
 pub mod cache_metrics;
 mod history_retention;
+mod memory_budget;
 pub mod messages;
 mod render_budget;
 pub mod tool_call_info;
@@ -14,24 +15,26 @@ pub mod viewport;
 pub use block_cache::BlockCache;
 pub use cache_metrics::CacheMetrics;
 pub use messages::{
-    ChatMessage, IncrementalMarkdown, MessageBlock, MessageRole, SystemSeverity, TextBlock,
-    TextBlockSpacing, WelcomeBlock,
+    ChatMessage, CodeView, DiffSummaryBlock, FileDiffStat, IncrementalMarkdown, MessageBlock,
+    MessageRole, RecoveryHintBlock, SystemSeverity, TextBlock, TextBlockSpacing, TextRevision,
+    ThinkingBlock, WelcomeBlock,
 };
 pub use tool_call_info::{
     InlinePermission, InlineQuestion, TerminalSnapshotMode, ToolCallInfo, is_execute_tool_name,
 };
 pub use types::{
-    AppStatus, CancelOrigin, ExtraUsage, HelpView, HistoryRetentionPolicy, HistoryRetentionStats,
-    LoginHint, McpState, MessageUsage, ModeInfo, ModeState, PasteSessionState, PendingCommandAck,
-    RecentSessionInfo, RenderCacheBudget, SUBAGENT_THINKING_DEBOUNCE, ScrollbarDragState,
-    SelectionKind, SelectionPoint, SelectionState, SessionUsageState, TodoItem, TodoStatus,
-    ToolCallScope, UsageSnapshot, UsageSourceKind, UsageSourceMode, UsageState, UsageWindow,
+    AppStatus, BridgeReconnect, CancelOrigin, ChatVisualState, ExtraUsage, HelpView,
+    HistoryRetentionPolicy, HistoryRetentionStats, LoginHint, McpState, MemoryBudget, MemoryUsage,
+    MessageUsage, ModeInfo, ModeState, PasteSessionState, PendingCommandAck, RecentSessionInfo,
+    RenderCacheBudget, SUBAGENT_THINKING_DEBOUNCE, ScrollbarDragState, SelectionKind,
+    SelectionPoint, SelectionState, SessionUsageState, TodoItem, TodoStatus, ToolCallScope,
+    UsageSnapshot, UsageSourceKind, UsageSourceMode, UsageState, UsageWindow,
 };
 pub use viewport::{ChatViewport, InvalidationLevel};
 
 use crate::agent::events::ClientEvent;
 use crate::agent::model;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::time::Instant;
@@ -39,6 +42,8 @@ use tokio::sync::mpsc;
 
 use super::config::ConfigState;
 use super::dialog;
+use super::emoji;
+use super::file_index::FileIndex;
 use super::focus::{FocusContext, FocusManager, FocusOwner, FocusTarget};
 use super::input::{InputSnapshot, InputState, parse_paste_placeholder_before_cursor};
 use super::mention;
@@ -132,6 +137,50 @@ pub struct App {
     /// Whether the header bar is visible.
     /// Toggled by Ctrl+H.
     pub show_header: bool,
+    /// Timestamped status changes shown in the Ctrl+g log pane.
+    pub status_log: crate::app::status_log::StatusLog,
+    /// Transient notifications stacked in the top-right corner.
+    pub toasts: crate::app::toast::ToastQueue,
+    /// Open confirmation prompt; takes all key input while set.
+    pub confirm: Option<crate::app::confirm::ConfirmDialog>,
+    /// Open message note editor; takes all key input while set.
+    pub annotation_editor: Option<crate::app::annotations::AnnotationEditor>,
+    /// Active transcript filter; messages it rejects are hidden.
+    pub transcript_filter: Option<crate::app::transcript_filter::TranscriptFilter>,
+    /// Open filter bar editor; takes all key input while set.
+    pub filter_editor: Option<crate::app::transcript_filter::FilterEditor>,
+    /// Open command palette (Ctrl+p); takes all key input while set.
+    pub command_palette: Option<crate::app::palette::CommandPalette>,
+    /// Open full-screen help (F1); takes all key input while set.
+    pub help_overlay: Option<crate::app::help_overlay::HelpOverlay>,
+    /// Indices of the user messages whose turns are folded to a summary row.
+    pub folded_turns: BTreeSet<usize>,
+    /// Named bookmarks of the current session, ordered by message.
+    pub bookmarks: Vec<crate::app::bookmarks::Bookmark>,
+    /// Tool call whose terminal output is pinned to a pane under the chat.
+    pub pinned_tool_call: Option<String>,
+    /// When Ctrl+C was last pressed without a selection; a second press soon after quits.
+    pub ctrl_c_armed_at: Option<Instant>,
+    /// When this run started; the quit summary reports time since.
+    pub launched_at: Instant,
+    /// Follow-up prompts offered under the last finished turn (Alt+1..3).
+    pub suggestions: Vec<String>,
+    /// Text of the latest prompt sent to the agent, kept for automatic retries.
+    pub last_prompt: Option<String>,
+    /// Pending or running retry after a rate-limit or overload error.
+    pub turn_retry: Option<crate::app::turn_retry::TurnRetry>,
+    /// A flaky tool call was already retried since the last typed prompt.
+    pub tool_retry_sent: bool,
+    /// Durations of this session's tool calls, for `/stats`.
+    pub tool_timings: crate::app::tool_timings::ToolTimings,
+    /// Compiled `watchPatterns` and how far each command's output was scanned.
+    pub watch: crate::app::watch_patterns::WatchState,
+    /// Heartbeat round trips to the bridge, shown as a footer glyph.
+    pub health: crate::app::health::ConnectionHealth,
+    /// Bridge events this build could not decode, for `/diagnostics`.
+    pub unhandled_events: crate::app::unhandled_events::UnhandledEvents,
+    /// Files edit tools touched this session, with their content before the first edit.
+    pub changed_files: crate::app::changed_files::ChangedFiles,
     /// Whether the todo panel is expanded (true) or shows compact status line (false).
     /// Toggled by Ctrl+T.
     pub show_todo_panel: bool,
@@ -143,6 +192,28 @@ pub struct App {
     pub focus: FocusManager,
     /// Commands advertised by the agent via `AvailableCommandsUpdate`.
     pub available_commands: Vec<model::AvailableCommand>,
+    /// Custom slash commands discovered in `.claude/commands` (project and user).
+    pub custom_commands: Vec<slash::CustomCommand>,
+    /// Background project file index backing `@` mention autocomplete.
+    pub file_index: Option<FileIndex>,
+    /// Indexes of the `/add-dir` roots, in `additional_dirs` order.
+    pub extra_file_indexes: Vec<FileIndex>,
+    /// Extra working directories added with `/add-dir` (absolute paths),
+    /// passed to the agent and covered by `@` mentions.
+    pub additional_dirs: Vec<String>,
+    /// Posted to the transcript once a session reloaded to apply new launch
+    /// settings (e.g. by `/add-dir`) has replaced the old one.
+    pub pending_session_note: Option<String>,
+    /// Prepended to the next prompt after a rewind so the agent drops the
+    /// turns that were removed from the transcript.
+    pub pending_rewind_note: Option<String>,
+    /// Variables set with `/env` for the commands the agent runs; kept for
+    /// this run of the app only.
+    pub session_env: BTreeMap<String, String>,
+    /// Generation parameters set with `/set`, sent with the launch settings.
+    pub model_params: crate::app::model_params::ModelParams,
+    /// Files recently touched by tools or mentioned, ranked first in `@` completion.
+    pub recent_files: mention::RecentFiles,
     /// Plugin inventory and UI state for the Config > Plugins view.
     pub plugins: PluginsState,
     /// Subagents advertised by the agent via `AvailableAgentsUpdate`.
@@ -155,12 +226,16 @@ pub struct App {
     pub cached_frame_area: ratatui::layout::Rect,
     /// Current selection state for mouse-based selection.
     pub selection: Option<SelectionState>,
+    /// Keyboard-driven chat selection (Ctrl+s); drives `selection` while active.
+    pub chat_visual: Option<ChatVisualState>,
     /// Active scrollbar drag state while left mouse button is held on the rail.
     pub scrollbar_drag: Option<ScrollbarDragState>,
     /// Cached rendered chat lines for selection/copy.
     pub rendered_chat_lines: Vec<String>,
     /// Area where chat content was rendered (for selection mapping).
     pub rendered_chat_area: ratatui::layout::Rect,
+    /// Minimap marks drawn last frame, with the message each one jumps to.
+    pub minimap_marks: Vec<(ratatui::layout::Position, usize)>,
     /// Cached rendered input lines for selection/copy.
     pub rendered_input_lines: Vec<String>,
     /// Area where input content was rendered (for selection mapping).
@@ -171,6 +246,8 @@ pub struct App {
     pub slash: Option<slash::SlashState>,
     /// Active subagent autocomplete state (`&name`).
     pub subagent: Option<subagent::SubagentState>,
+    /// Active emoji autocomplete state (`:name`).
+    pub emoji: Option<emoji::EmojiState>,
     /// Deferred plain-Enter submit. Stores the exact input state from before the
     /// Enter key so submission can restore and use the original draft text.
     ///
@@ -191,6 +268,10 @@ pub struct App {
     pub active_paste_session: Option<PasteSessionState>,
     /// Monotonic counter for paste session identifiers.
     pub next_paste_session_id: u64,
+    /// Pasted file paths that can still be converted to `@` mentions with Tab.
+    pub pasted_paths_offer: Option<super::pasted_paths::PastedPathsOffer>,
+    /// Failed `/test` run that can still be sent to the agent with Tab.
+    pub failed_command_offer: Option<super::test_run::FailedCommandOffer>,
     /// Cached todo compact line (invalidated on `set_todos()`).
     pub cached_todo_compact: Option<ratatui::text::Line<'static>>,
     /// Current git branch (refreshed on focus gain + turn complete).
@@ -201,6 +282,8 @@ pub struct App {
     pub cached_footer_line: Option<ratatui::text::Line<'static>>,
     /// Optional startup update-check hint rendered at the footer's right edge.
     pub update_check_hint: Option<String>,
+    /// Release behind `update_check_hint`, for the release-notes overlay.
+    pub update_release: Option<super::ReleaseInfo>,
     /// Session-wide usage and cost telemetry from the bridge.
     pub session_usage: SessionUsageState,
     /// Config > Usage snapshot and refresh lifecycle.
@@ -215,6 +298,8 @@ pub struct App {
     pub is_compacting: bool,
     /// Account info from the bridge status snapshot (email, org, subscription).
     pub account_info: Option<crate::agent::types::AccountInfo>,
+    /// Name, version, and capabilities the agent advertised at initialize.
+    pub agent_info: Option<crate::agent::types::InitializeResult>,
 
     /// Indexed terminal tool calls: `(terminal_id, msg_idx, block_idx)`.
     /// Avoids O(n*m) scan of all messages/blocks every frame.
@@ -233,6 +318,12 @@ pub struct App {
     pub history_retention: HistoryRetentionPolicy,
     /// Last history-retention enforcement statistics.
     pub history_retention_stats: HistoryRetentionStats,
+    /// Overall memory budget across messages, block caches and terminal buffers.
+    pub memory_budget: MemoryBudget,
+    /// Terminal background assumed for the contrast check and high-contrast theme.
+    pub terminal_background: crate::ui::theme::Background,
+    /// Screen reader mode flag from the CLI and the optional reply mirror.
+    pub screen_reader: crate::app::screen_reader::ScreenReader,
     /// Cross-cutting cache metrics accumulator (enforcement counts, watermarks, rate limits).
     pub cache_metrics: CacheMetrics,
     /// Smoothed frames-per-second (EMA of presented frame cadence).
@@ -242,8 +333,36 @@ pub struct App {
     pub startup_connection_requested: bool,
     pub connection_started: bool,
     pub startup_bridge_script: Option<PathBuf>,
+    /// Agent profile from `--agent`; `None` launches the bundled Claude bridge.
+    pub startup_agent_profile: Option<String>,
+    /// `--mock`: connect to the scripted in-process agent.
+    pub startup_mock_agent: bool,
     pub startup_resume_id: Option<String>,
     pub startup_resume_requested: bool,
+    /// Set while the bridge is being respawned after exiting mid-session.
+    pub bridge_reconnect: Option<BridgeReconnect>,
+    /// Recent stderr output of the agent process, shown by `/diagnostics`.
+    pub bridge_stderr: crate::agent::stderr_log::SharedStderrBuffer,
+    /// `--tee-events` JSONL copy of the bridge traffic, shared across restarts.
+    pub event_tee: Option<crate::agent::event_tee::SharedEventTee>,
+    /// Background `claude auth login` started from the /login dialog.
+    pub login_flow: Option<crate::app::config::LoginFlowHandle>,
+    /// Proxy-aware client settings for update, status and usage requests.
+    pub http: crate::app::http::HttpSettings,
+    /// `--offline` was passed; see [`App::is_offline`].
+    pub offline_flag: bool,
+    /// `--read-only`: session pinned to plan mode, mutating tool calls auto-denied.
+    pub read_only: bool,
+    /// Print the transcript to stdout on exit (`--print-transcript`, `/transcript`).
+    pub print_transcript_on_exit: bool,
+    /// When the current prompt went out; feeds `duration_ms` of lifecycle hooks.
+    pub turn_started_at: Option<Instant>,
+    /// Claude Code hooks configured at startup, for `/status`.
+    pub hook_summary: Option<String>,
+    /// `/commit` or `/pr` waiting for the agent's draft.
+    pub pending_draft: Option<crate::app::commit::PendingDraft>,
+    /// Background `checkCommand` runs after edits.
+    pub edit_check: crate::app::edit_check::EditCheck,
 }
 
 impl App {
@@ -319,6 +438,16 @@ impl App {
         self.trust.is_trusted()
     }
 
+    /// Whether the agent advertised a capability. Before the initialize
+    /// handshake the Claude bridge defaults are assumed, so nothing is gated.
+    #[must_use]
+    pub fn agent_supports(
+        &self,
+        capability: impl Fn(&crate::agent::types::AgentCapabilities) -> bool,
+    ) -> bool {
+        self.agent_info.as_ref().is_none_or(|info| capability(&info.capabilities))
+    }
+
     #[must_use]
     pub fn frame_fps(&self) -> Option<f32> {
         self.fps_ema
@@ -516,6 +645,33 @@ impl App {
         }
     }
 
+    /// Drop every message from `len` onward and re-index what remains.
+    pub fn truncate_messages(&mut self, len: usize) {
+        if len >= self.messages.len() {
+            return;
+        }
+        self.messages.truncate(len);
+        self.folded_turns.retain(|&start| start < len);
+        self.viewport.reading_anchor = None;
+        self.rebuild_tool_indices_and_terminal_refs();
+        self.invalidate_layout(InvalidationLevel::From(0));
+        self.viewport.engage_auto_scroll();
+        self.needs_redraw = true;
+    }
+
+    /// Drop rendered thinking blocks after the display setting or their
+    /// expansion changed, and re-measure every message.
+    pub fn invalidate_thinking_blocks(&mut self) {
+        for msg in &mut self.messages {
+            for block in &mut msg.blocks {
+                if let MessageBlock::Thinking(thinking) = block {
+                    thinking.cache.invalidate();
+                }
+            }
+        }
+        self.invalidate_layout(InvalidationLevel::Global);
+    }
+
     /// Enforce history retention and record metrics.
     ///
     /// Wrapper around [`enforce_history_retention`] that feeds the returned stats
@@ -644,36 +800,73 @@ impl App {
             tool_call_index: HashMap::default(),
             todos: Vec::new(),
             show_header: true,
+            status_log: crate::app::status_log::StatusLog::new(),
+            toasts: crate::app::toast::ToastQueue::default(),
+            confirm: None,
+            annotation_editor: None,
+            transcript_filter: None,
+            filter_editor: None,
+            command_palette: None,
+            help_overlay: None,
+            folded_turns: BTreeSet::new(),
+            bookmarks: Vec::new(),
+            pinned_tool_call: None,
+            ctrl_c_armed_at: None,
+            launched_at: Instant::now(),
+            suggestions: Vec::new(),
+            last_prompt: None,
+            turn_retry: None,
+            tool_retry_sent: false,
+            tool_timings: crate::app::tool_timings::ToolTimings::default(),
+            watch: crate::app::watch_patterns::WatchState::default(),
+            health: crate::app::health::ConnectionHealth::default(),
+            unhandled_events: crate::app::unhandled_events::UnhandledEvents::default(),
+            changed_files: crate::app::changed_files::ChangedFiles::default(),
             show_todo_panel: false,
             todo_scroll: 0,
             todo_selected: 0,
             focus: FocusManager::default(),
             available_commands: Vec::new(),
+            custom_commands: Vec::new(),
+            file_index: None,
+            extra_file_indexes: Vec::new(),
+            additional_dirs: Vec::new(),
+            pending_session_note: None,
+            pending_rewind_note: None,
+            session_env: BTreeMap::new(),
+            model_params: crate::app::model_params::ModelParams::default(),
+            recent_files: mention::RecentFiles::default(),
             plugins: PluginsState::default(),
             available_agents: Vec::new(),
             available_models: Vec::new(),
             recent_sessions: Vec::new(),
             cached_frame_area: ratatui::layout::Rect::default(),
             selection: None,
+            chat_visual: None,
             scrollbar_drag: None,
             rendered_chat_lines: Vec::new(),
             rendered_chat_area: ratatui::layout::Rect::default(),
+            minimap_marks: Vec::new(),
             rendered_input_lines: Vec::new(),
             rendered_input_area: ratatui::layout::Rect::default(),
             mention: None,
             slash: None,
             subagent: None,
+            emoji: None,
             pending_submit: None,
             paste_burst: super::paste_burst::PasteBurstDetector::new(),
             pending_paste_text: String::new(),
             pending_paste_session: None,
             active_paste_session: None,
             next_paste_session_id: 1,
+            pasted_paths_offer: None,
+            failed_command_offer: None,
             cached_todo_compact: None,
             git_branch: None,
             cached_header_line: None,
             cached_footer_line: None,
             update_check_hint: None,
+            update_release: None,
             session_usage: SessionUsageState::default(),
             usage: UsageState::default(),
             mcp: McpState::default(),
@@ -681,12 +874,16 @@ impl App {
             last_rate_limit_update: None,
             is_compacting: false,
             account_info: None,
+            agent_info: None,
             terminal_tool_calls: Vec::new(),
             needs_redraw: true,
             notifications: super::notify::NotificationManager::new(),
             perf: None,
             render_cache_budget: RenderCacheBudget::default(),
             history_retention: HistoryRetentionPolicy::default(),
+            memory_budget: MemoryBudget::default(),
+            terminal_background: crate::ui::theme::Background::default(),
+            screen_reader: crate::app::screen_reader::ScreenReader::default(),
             history_retention_stats: HistoryRetentionStats::default(),
             cache_metrics: CacheMetrics::default(),
             fps_ema: None,
@@ -694,11 +891,32 @@ impl App {
             startup_connection_requested: false,
             connection_started: false,
             startup_bridge_script: None,
+            startup_agent_profile: None,
+            startup_mock_agent: false,
             startup_resume_id: None,
             startup_resume_requested: false,
+            bridge_reconnect: None,
+            bridge_stderr: std::rc::Rc::default(),
+            event_tee: None,
+            login_flow: None,
+            http: crate::app::http::HttpSettings::default(),
+            offline_flag: false,
+            read_only: false,
+            print_transcript_on_exit: false,
+            turn_started_at: None,
+            hook_summary: None,
+            pending_draft: None,
+            edit_check: crate::app::edit_check::EditCheck::default(),
         }
     }
 
+    /// Offline mode, from `--offline` or the persisted setting: no update,
+    /// status or usage requests and no links opened in the browser.
+    #[must_use]
+    pub fn is_offline(&self) -> bool {
+        self.offline_flag || self.config.offline_mode_effective()
+    }
+
     /// Detect the current git branch and invalidate the header cache if it changed.
     pub fn refresh_git_branch(&mut self) {
         let new_branch = std::process::Command::new("git")
@@ -754,7 +972,10 @@ impl App {
     fn focus_context(&self) -> FocusContext {
         FocusContext::new(
             self.show_todo_panel && !self.todos.is_empty(),
-            self.mention.is_some() || self.slash.is_some() || self.subagent.is_some(),
+            self.mention.is_some()
+                || self.slash.is_some()
+                || self.subagent.is_some()
+                || self.emoji.is_some(),
             !self.pending_permission_ids.is_empty(),
         )
         .with_help(self.is_help_active())
@@ -1097,6 +1318,7 @@ mod tests {
             role: MessageRole::User,
             blocks: vec![assistant_text_block(text)],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1109,12 +1331,14 @@ mod tests {
                 sdk_tool_name: "Read".to_owned(),
                 raw_input: None,
                 output_metadata: None,
+                web_output: None,
                 status,
                 content: Vec::new(),
                 collapsed: false,
                 hidden: false,
                 terminal_id: None,
                 terminal_command: None,
+                terminal_env: Vec::new(),
                 terminal_output: Some("x".repeat(1024)),
                 terminal_output_len: 1024,
                 terminal_bytes_seen: 1024,
@@ -1128,8 +1352,12 @@ mod tests {
                 cache: BlockCache::default(),
                 pending_permission: None,
                 pending_question: None,
+                h_scroll: None,
+                auto_retry: None,
+                watch_hits: Vec::new(),
             }))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1146,12 +1374,14 @@ mod tests {
                 sdk_tool_name: "Bash".to_owned(),
                 raw_input: None,
                 output_metadata: None,
+                web_output: None,
                 status,
                 content: Vec::new(),
                 collapsed: false,
                 hidden: false,
                 terminal_id: Some(terminal_id.to_owned()),
                 terminal_command: Some("echo hi".to_owned()),
+                terminal_env: Vec::new(),
                 terminal_output: Some("x".repeat(1024)),
                 terminal_output_len: 1024,
                 terminal_bytes_seen: 1024,
@@ -1165,8 +1395,12 @@ mod tests {
                 cache: BlockCache::default(),
                 pending_permission: None,
                 pending_question: None,
+                h_scroll: None,
+                auto_retry: None,
+                watch_hits: Vec::new(),
             }))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1180,12 +1414,14 @@ mod tests {
                 sdk_tool_name: "Read".to_owned(),
                 raw_input: None,
                 output_metadata: None,
+                web_output: None,
                 status: model::ToolCallStatus::Completed,
                 content: Vec::new(),
                 collapsed: false,
                 hidden: false,
                 terminal_id: None,
                 terminal_command: None,
+                terminal_env: Vec::new(),
                 terminal_output: Some("x".repeat(1024)),
                 terminal_output_len: 1024,
                 terminal_bytes_seen: 1024,
@@ -1208,8 +1444,12 @@ mod tests {
                     focused: false,
                 }),
                 pending_question: None,
+                h_scroll: None,
+                auto_retry: None,
+                watch_hits: Vec::new(),
             }))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1221,11 +1461,13 @@ mod tests {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("a")],
                 usage: None,
+                annotation: None,
             },
             ChatMessage {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("b")],
                 usage: None,
+                annotation: None,
             },
         ];
 
@@ -1270,6 +1512,7 @@ mod tests {
             role: MessageRole::Assistant,
             blocks: vec![assistant_text_block("streaming tail")],
             usage: None,
+            annotation: None,
         }];
 
         let before = if let MessageBlock::Text(block) = &mut app.messages[0].blocks[0] {
@@ -1300,11 +1543,13 @@ mod tests {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("old message")],
                 usage: None,
+                annotation: None,
             },
             ChatMessage {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("streaming tail")],
                 usage: None,
+                annotation: None,
             },
         ];
 
@@ -1349,16 +1594,19 @@ mod tests {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("old-a")],
                 usage: None,
+                annotation: None,
             },
             ChatMessage {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("old-b")],
                 usage: None,
+                annotation: None,
             },
             ChatMessage {
                 role: MessageRole::Assistant,
                 blocks: vec![assistant_text_block("streaming")],
                 usage: None,
+                annotation: None,
             },
         ];
 
@@ -1403,6 +1651,7 @@ mod tests {
             role: MessageRole::Assistant,
             blocks: vec![assistant_text_block("done")],
             usage: None,
+            annotation: None,
         }];
 
         if let MessageBlock::Text(block) = &mut app.messages[0].blocks[0] {
@@ -1524,6 +1773,30 @@ mod tests {
         assert_eq!(app.lookup_tool_call("tool-idx"), Some((2, 0)));
     }
 
+    #[test]
+    fn enforce_history_retention_moves_folded_turns_with_their_prompts() {
+        let mut app = make_test_app();
+        app.messages = vec![
+            ChatMessage::welcome("model", "/cwd"),
+            user_text_message("first old message"),
+            user_text_message("second old message"),
+            user_text_message("kept turn"),
+            assistant_tool_message("tool-keep", model::ToolCallStatus::InProgress),
+        ];
+        app.folded_turns = BTreeSet::from([2, 3]);
+        app.history_retention.max_bytes = app.measure_history_bytes()
+            - App::measure_message_bytes(&app.messages[1])
+            - App::measure_message_bytes(&app.messages[2]);
+
+        let stats = app.enforce_history_retention();
+        assert_eq!(stats.dropped_messages, 2);
+        assert!(App::is_history_hidden_marker_message(&app.messages[1]));
+        // The fold on a dropped prompt is gone; the other follows its prompt
+        // past the drop and the inserted marker.
+        assert_eq!(app.folded_turns, BTreeSet::from([2]));
+        assert_eq!(crate::app::turn_folds::prompt_line(&app, 2), "kept turn");
+    }
+
     #[test]
     fn enforce_history_retention_keeps_single_marker_on_repeat() {
         let mut app = make_test_app();
@@ -1950,6 +2223,41 @@ mod tests {
         assert_eq!(vp.find_first_visible(vp.scroll_offset), 1);
     }
 
+    #[test]
+    fn viewport_reading_anchor_holds_position_when_content_above_grows() {
+        let mut vp = ChatViewport::new();
+        vp.on_frame(80);
+        for idx in 0..3 {
+            vp.set_message_height(idx, 10);
+        }
+        vp.rebuild_prefix_sums();
+        vp.scroll_up(0);
+        vp.scroll_target = 13;
+        vp.scroll_pos = 13.0;
+        vp.scroll_offset = 13;
+        vp.record_reading_anchor();
+
+        vp.set_message_height(0, 15);
+        vp.prefix_sums_width = 0;
+        vp.rebuild_prefix_sums();
+        vp.restore_reading_anchor();
+        assert_eq!(vp.scroll_target, 18);
+        assert_eq!(vp.scroll_offset, 18);
+
+        // Once the user scrolls, the stale anchor no longer applies.
+        vp.record_reading_anchor();
+        vp.scroll_up(4);
+        vp.set_message_height(0, 20);
+        vp.prefix_sums_width = 0;
+        vp.rebuild_prefix_sums();
+        vp.restore_reading_anchor();
+        assert_eq!(vp.scroll_target, 14);
+
+        vp.record_reading_anchor();
+        vp.shift_reading_anchor(0, 1, false);
+        assert_eq!(vp.reading_anchor.map(|anchor| anchor.message_index), Some(1));
+    }
+
     #[test]
     fn viewport_prefix_sums_basic() {
         let mut vp = ChatViewport::new();
@@ -15,6 +15,50 @@ pub(super) struct CacheSlotCandidate {
 
 impl super::App {
     pub fn enforce_render_cache_budget(&mut self) -> CacheBudgetEnforceStats {
+        let (mut stats, mut evictable) = self.cache_slot_candidates();
+
+        // Budget comparison uses only non-protected (evictable) bytes.
+        let budgeted_bytes = stats.total_before_bytes.saturating_sub(stats.protected_bytes);
+
+        if budgeted_bytes <= self.render_cache_budget.max_bytes {
+            self.render_cache_budget.last_total_bytes = budgeted_bytes;
+            self.render_cache_budget.last_evicted_bytes = 0;
+            stats.total_after_bytes = stats.total_before_bytes;
+            return stats;
+        }
+
+        evictable.sort_by_key(|slot| (slot.last_access_tick, std::cmp::Reverse(slot.bytes)));
+        let mut current_budgeted = budgeted_bytes;
+        stats.total_after_bytes = stats.total_before_bytes;
+
+        for slot in evictable {
+            if current_budgeted <= self.render_cache_budget.max_bytes {
+                break;
+            }
+            let removed = self.evict_cache_slot(slot.msg_idx, slot.block_idx);
+            if removed == 0 {
+                continue;
+            }
+            current_budgeted = current_budgeted.saturating_sub(removed);
+            stats.total_after_bytes = stats.total_after_bytes.saturating_sub(removed);
+            stats.evicted_bytes = stats.evicted_bytes.saturating_add(removed);
+            stats.evicted_blocks = stats.evicted_blocks.saturating_add(1);
+        }
+
+        self.render_cache_budget.last_total_bytes = current_budgeted;
+        self.render_cache_budget.last_evicted_bytes = stats.evicted_bytes;
+        self.render_cache_budget.total_evictions =
+            self.render_cache_budget.total_evictions.saturating_add(stats.evicted_blocks);
+
+        stats
+    }
+
+    /// Populated block caches, split into protected bytes (streaming tail,
+    /// running tools) and evictable slots. Only `total_before_bytes` and
+    /// `protected_bytes` are filled in on the returned stats.
+    pub(super) fn cache_slot_candidates(
+        &self,
+    ) -> (CacheBudgetEnforceStats, Vec<CacheSlotCandidate>) {
         let mut stats = CacheBudgetEnforceStats::default();
         let is_streaming = matches!(self.status, AppStatus::Thinking | AppStatus::Running);
         let msg_count = self.messages.len();
@@ -25,6 +69,9 @@ impl super::App {
             for (block_idx, block) in msg.blocks.iter().enumerate() {
                 let (cache, protect_block) = match block {
                     MessageBlock::Text(block) => (&block.cache, false),
+                    MessageBlock::Thinking(block) => (&block.cache, false),
+                    MessageBlock::DiffSummary(summary) => (&summary.cache, false),
+                    MessageBlock::RecoveryHint(hint) => (&hint.cache, false),
                     MessageBlock::Welcome(welcome) => (&welcome.cache, false),
                     MessageBlock::ToolCall(tc) => (
                         &tc.cache,
@@ -54,43 +101,10 @@ impl super::App {
             }
         }
 
-        // Budget comparison uses only non-protected (evictable) bytes.
-        let budgeted_bytes = stats.total_before_bytes.saturating_sub(stats.protected_bytes);
-
-        if budgeted_bytes <= self.render_cache_budget.max_bytes {
-            self.render_cache_budget.last_total_bytes = budgeted_bytes;
-            self.render_cache_budget.last_evicted_bytes = 0;
-            stats.total_after_bytes = stats.total_before_bytes;
-            return stats;
-        }
-
-        evictable.sort_by_key(|slot| (slot.last_access_tick, std::cmp::Reverse(slot.bytes)));
-        let mut current_budgeted = budgeted_bytes;
-        stats.total_after_bytes = stats.total_before_bytes;
-
-        for slot in evictable {
-            if current_budgeted <= self.render_cache_budget.max_bytes {
-                break;
-            }
-            let removed = self.evict_cache_slot(slot.msg_idx, slot.block_idx);
-            if removed == 0 {
-                continue;
-            }
-            current_budgeted = current_budgeted.saturating_sub(removed);
-            stats.total_after_bytes = stats.total_after_bytes.saturating_sub(removed);
-            stats.evicted_bytes = stats.evicted_bytes.saturating_add(removed);
-            stats.evicted_blocks = stats.evicted_blocks.saturating_add(1);
-        }
-
-        self.render_cache_budget.last_total_bytes = current_budgeted;
-        self.render_cache_budget.last_evicted_bytes = stats.evicted_bytes;
-        self.render_cache_budget.total_evictions =
-            self.render_cache_budget.total_evictions.saturating_add(stats.evicted_blocks);
-
-        stats
+        (stats, evictable)
     }
 
-    fn evict_cache_slot(&mut self, msg_idx: usize, block_idx: usize) -> usize {
+    pub(super) fn evict_cache_slot(&mut self, msg_idx: usize, block_idx: usize) -> usize {
         let Some(msg) = self.messages.get_mut(msg_idx) else {
             return 0;
         };
@@ -99,6 +113,9 @@ impl super::App {
         };
         match block {
             MessageBlock::Text(block) => block.cache.evict_cached_render(),
+            MessageBlock::Thinking(block) => block.cache.evict_cached_render(),
+            MessageBlock::DiffSummary(summary) => summary.cache.evict_cached_render(),
+            MessageBlock::RecoveryHint(hint) => hint.cache.evict_cached_render(),
             MessageBlock::Welcome(welcome) => welcome.cache.evict_cached_render(),
             MessageBlock::ToolCall(tc) => tc.cache.evict_cached_render(),
         }
@@ -3,6 +3,7 @@
 
 use super::block_cache::BlockCache;
 use crate::agent::model;
+use crate::app::web_tools::WebToolOutput;
 
 pub struct ToolCallInfo {
     pub id: String,
@@ -12,6 +13,8 @@ pub struct ToolCallInfo {
     pub sdk_tool_name: String,
     pub raw_input: Option<serde_json::Value>,
     pub output_metadata: Option<model::ToolOutputMetadata>,
+    /// Structured `WebSearch` / `WebFetch` result parsed from `raw_output`.
+    pub web_output: Option<WebToolOutput>,
     pub status: model::ToolCallStatus,
     pub content: Vec<model::ToolCallContent>,
     pub collapsed: bool,
@@ -21,6 +24,8 @@ pub struct ToolCallInfo {
     pub terminal_id: Option<String>,
     /// The shell command that was executed (e.g. "echo hello && ls -la").
     pub terminal_command: Option<String>,
+    /// `/env` variables in effect when the agent started this command.
+    pub terminal_env: Vec<(String, String)>,
     /// Snapshot of terminal output, updated each frame while `InProgress`.
     pub terminal_output: Option<String>,
     /// Length of terminal buffer at last snapshot - used to skip O(n) re-snapshots
@@ -48,6 +53,13 @@ pub struct ToolCallInfo {
     pub pending_permission: Option<InlinePermission>,
     /// Inline question prompt from `AskUserQuestion`.
     pub pending_question: Option<InlineQuestion>,
+    /// First column shown when the output is scrolled sideways instead of
+    /// wrapped; `None` wraps.
+    pub h_scroll: Option<usize>,
+    /// Set once the agent was asked to run this failed call again.
+    pub auto_retry: Option<crate::app::tool_retry::TransientToolFailure>,
+    /// Output lines that matched a `watchPatterns` entry.
+    pub watch_hits: Vec<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -62,6 +74,11 @@ impl ToolCallInfo {
         is_execute_tool_name(&self.sdk_tool_name)
     }
 
+    #[must_use]
+    pub fn is_mutating_tool(&self) -> bool {
+        is_mutating_tool_name(&self.sdk_tool_name)
+    }
+
     #[must_use]
     pub fn is_ask_question_tool(&self) -> bool {
         is_ask_question_tool_name(&self.sdk_tool_name)
@@ -146,6 +163,17 @@ pub fn is_execute_tool_name(tool_name: &str) -> bool {
     tool_name.eq_ignore_ascii_case("bash")
 }
 
+/// Tools that change files or run commands, plus leaving plan mode; denied
+/// outright under `--read-only`.
+#[must_use]
+pub fn is_mutating_tool_name(tool_name: &str) -> bool {
+    is_execute_tool_name(tool_name)
+        || is_exit_plan_mode_tool_name(tool_name)
+        || ["edit", "multiedit", "write", "notebookedit", "delete", "move"]
+            .iter()
+            .any(|name| tool_name.eq_ignore_ascii_case(name))
+}
+
 #[must_use]
 pub fn is_ask_question_tool_name(tool_name: &str) -> bool {
     tool_name.eq_ignore_ascii_case("askuserquestion")
@@ -3,7 +3,7 @@
 
 use crate::agent::model;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModeInfo {
@@ -26,6 +26,18 @@ pub enum HelpView {
     Subagents,
 }
 
+/// Respawn bookkeeping after the bridge process exits mid-session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BridgeReconnect {
+    /// Respawn attempts started so far.
+    pub attempt: u32,
+    /// Session to resume on the new bridge; cleared when resuming fails.
+    pub resume_session_id: Option<String>,
+    /// When the next attempt starts. `None` while an attempt is in flight or
+    /// after the attempts are exhausted.
+    pub next_attempt_at: Option<Instant>,
+}
+
 /// Login hint displayed when authentication is required during connection.
 /// Rendered as a banner above the input field.
 pub struct LoginHint {
@@ -72,6 +84,10 @@ pub struct MessageUsage {
     pub input_tokens: Option<u64>,
     pub cache_read_tokens: Option<u64>,
     pub cache_write_tokens: Option<u64>,
+    /// Output tokens for the whole turn, set when the turn finishes.
+    pub output_tokens: Option<u64>,
+    /// Cost of this turn alone, derived from the session's running total.
+    pub cost_usd: Option<f64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -138,6 +154,25 @@ pub struct UsageState {
 pub struct SessionUsageState {
     pub last_compaction_trigger: Option<model::CompactionTrigger>,
     pub last_compaction_pre_tokens: Option<u64>,
+    /// Prompt-side tokens of the latest main-agent request: what the context holds.
+    pub last_request: Option<MessageUsage>,
+    /// Output tokens summed over finished turns.
+    pub total_output_tokens: u64,
+    pub context_window: Option<u64>,
+    pub total_cost_usd: Option<f64>,
+}
+
+impl SessionUsageState {
+    /// Tokens occupying the context window as of the latest request.
+    #[must_use]
+    pub fn context_tokens(&self) -> Option<u64> {
+        let usage = self.last_request.as_ref()?;
+        Some(
+            usage.input_tokens.unwrap_or(0)
+                + usage.cache_read_tokens.unwrap_or(0)
+                + usage.cache_write_tokens.unwrap_or(0),
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -150,6 +185,7 @@ pub struct McpState {
 
 pub const DEFAULT_RENDER_CACHE_BUDGET_BYTES: usize = 24 * 1024 * 1024;
 pub const DEFAULT_HISTORY_RETENTION_MAX_BYTES: usize = 64 * 1024 * 1024;
+pub const DEFAULT_MEMORY_BUDGET_BYTES: usize = 128 * 1024 * 1024;
 pub const SUBAGENT_THINKING_DEBOUNCE: Duration = Duration::from_millis(1_500);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -171,6 +207,75 @@ impl Default for RenderCacheBudget {
     }
 }
 
+/// Approximate heap footprint of the transcript, refreshed once per frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[allow(clippy::struct_field_names)]
+pub struct MemoryUsage {
+    /// Message content including captured tool and terminal output, as of the
+    /// last history-retention pass.
+    pub message_bytes: usize,
+    /// Rendered lines held in block caches.
+    pub cache_bytes: usize,
+    /// Output buffers of terminal processes still in the terminal map.
+    pub terminal_bytes: usize,
+}
+
+impl MemoryUsage {
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.message_bytes.saturating_add(self.cache_bytes).saturating_add(self.terminal_bytes)
+    }
+}
+
+/// Overall memory budget. When the measured total exceeds `max_bytes`, cold
+/// block caches are evicted first, then output of finished terminal commands
+/// is cut down to its tail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    pub max_bytes: usize,
+    pub usage: MemoryUsage,
+    pub total_trimmed_bytes: usize,
+    pub trim_passes: usize,
+    /// Cache access tick at the end of the previous pass. Caches touched since
+    /// then were drawn this frame and are never treated as cold.
+    pub last_pass_tick: u64,
+}
+
+impl MemoryBudget {
+    #[must_use]
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            usage: MemoryUsage::default(),
+            total_trimmed_bytes: 0,
+            trim_passes: 0,
+            last_pass_tick: 0,
+        }
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::with_max_bytes(DEFAULT_MEMORY_BUDGET_BYTES)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryTrimStats {
+    pub evicted_cache_bytes: usize,
+    pub evicted_cache_blocks: usize,
+    pub trimmed_terminal_bytes: usize,
+    pub trimmed_tool_calls: usize,
+    pub dropped_terminals: usize,
+}
+
+impl MemoryTrimStats {
+    #[must_use]
+    pub fn freed_bytes(&self) -> usize {
+        self.evicted_cache_bytes.saturating_add(self.trimmed_terminal_bytes)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct HistoryRetentionPolicy {
     pub max_bytes: usize,
@@ -247,6 +352,14 @@ pub struct SelectionState {
     pub dragging: bool,
 }
 
+/// Keyboard selection in the chat area: a cursor over the rendered rows and,
+/// once the user starts extending, the point the selection is anchored at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChatVisualState {
+    pub cursor: SelectionPoint,
+    pub anchor: Option<SelectionPoint>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ScrollbarDragState {
     /// Row offset from thumb top where the initial click happened.
@@ -73,6 +73,16 @@ impl ResizeRemeasureState {
     }
 }
 
+/// Where the user is reading while scrolled up: a row within a message plus the
+/// absolute target it resolved to when recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingAnchor {
+    pub message_index: usize,
+    pub row_offset: usize,
+    /// `scroll_target` at record time; any other value means the user scrolled.
+    pub scroll_target: usize,
+}
+
 /// Single owner of all chat layout state: scroll, per-message heights, and prefix sums.
 ///
 /// Consolidates state previously scattered across `App` (scroll fields, prefix sums),
@@ -89,12 +99,17 @@ pub struct ChatViewport {
     pub scroll_target: usize,
     /// Smooth scroll position (fractional) for animation.
     pub scroll_pos: f32,
+    /// When the current scroll animation started; `None` while settled.
+    pub scroll_animation_started: Option<std::time::Instant>,
     /// Smoothed scrollbar thumb top row (fractional) for animation.
     pub scrollbar_thumb_top: f32,
     /// Smoothed scrollbar thumb height (fractional) for animation.
     pub scrollbar_thumb_size: f32,
     /// Whether to auto-scroll to bottom on new content.
     pub auto_scroll: bool,
+    /// Message-local position of `scroll_target` recorded at the end of the last
+    /// frame while scrolled up, so height changes above it don't move the view.
+    pub reading_anchor: Option<ReadingAnchor>,
 
     // --- Layout ---
     /// Current terminal width. Set by `on_frame()` each render cycle.
@@ -133,9 +148,11 @@ impl ChatViewport {
             scroll_offset: 0,
             scroll_target: 0,
             scroll_pos: 0.0,
+            scroll_animation_started: None,
             scrollbar_thumb_top: 0.0,
             scrollbar_thumb_size: 0.0,
             auto_scroll: true,
+            reading_anchor: None,
             width: 0,
             layout_generation: 1,
             message_heights: Vec::new(),
@@ -375,6 +392,67 @@ impl ChatViewport {
         self.scroll_offset = scroll;
     }
 
+    /// Remember the message-local position of `scroll_target`. Called once the
+    /// frame's prefix sums are final; cleared while auto-scroll is engaged.
+    pub fn record_reading_anchor(&mut self) {
+        if self.auto_scroll || self.height_prefix_sums.is_empty() {
+            self.reading_anchor = None;
+            return;
+        }
+        let message_index = self.find_first_visible(self.scroll_target);
+        let row_offset =
+            self.scroll_target.saturating_sub(self.cumulative_height_before(message_index));
+        self.reading_anchor =
+            Some(ReadingAnchor { message_index, row_offset, scroll_target: self.scroll_target });
+    }
+
+    /// Re-resolve the reading anchor against freshly rebuilt prefix sums, shifting
+    /// the scroll position by however much the content above it grew or shrank.
+    /// Skipped once the user has scrolled since the anchor was recorded.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn restore_reading_anchor(&mut self) {
+        let Some(anchor) = self.reading_anchor else {
+            return;
+        };
+        if self.auto_scroll
+            || anchor.scroll_target != self.scroll_target
+            || anchor.message_index >= self.message_heights.len()
+        {
+            return;
+        }
+        let height = self.message_height(anchor.message_index);
+        let row_offset = anchor.row_offset.min(height.saturating_sub(1));
+        let target = self.cumulative_height_before(anchor.message_index).saturating_add(row_offset);
+        if target == self.scroll_target {
+            return;
+        }
+        // Move the in-flight animation by the same amount so easing isn't restarted.
+        let delta = target as f32 - self.scroll_target as f32;
+        self.scroll_target = target;
+        self.scroll_pos = (self.scroll_pos + delta).max(0.0);
+        self.scroll_offset = self.scroll_pos.round() as usize;
+    }
+
+    /// Keep the reading anchor on the same message after `removed_before`
+    /// messages ahead of it were dropped and `inserted_before` were added. The
+    /// anchor is cleared when its own message went away.
+    pub fn shift_reading_anchor(
+        &mut self,
+        removed_before: usize,
+        inserted_before: usize,
+        anchor_removed: bool,
+    ) {
+        if anchor_removed {
+            self.reading_anchor = None;
+            return;
+        }
+        if let Some(anchor) = &mut self.reading_anchor {
+            anchor.message_index =
+                anchor.message_index.saturating_sub(removed_before).saturating_add(inserted_before);
+            anchor.scroll_target = self.scroll_target;
+        }
+    }
+
     // --- Prefix sums ---
 
     /// Rebuild prefix sums from `message_heights`.
@@ -0,0 +1,100 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timestamped record of status changes: reconnects, compaction results,
+//! permission denials, update hints and errors. Shown in a toggleable pane
+//! under the chat (Ctrl+g); the "System messages" setting decides whether
+//! informational entries also appear in the transcript.
+
+use super::{App, SystemSeverity};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Oldest entries are dropped beyond this many.
+const MAX_STATUS_LOG_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusLogEntry {
+    /// Time since the app started.
+    pub elapsed: Duration,
+    pub severity: Option<SystemSeverity>,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct StatusLog {
+    started: Instant,
+    entries: VecDeque<StatusLogEntry>,
+    pub visible: bool,
+    /// Entries recorded while the pane was hidden.
+    pub unseen: usize,
+}
+
+impl StatusLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { started: Instant::now(), entries: VecDeque::new(), visible: false, unseen: 0 }
+    }
+
+    pub fn record(&mut self, severity: Option<SystemSeverity>, text: &str) {
+        if self.entries.len() == MAX_STATUS_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(StatusLogEntry {
+            elapsed: self.started.elapsed(),
+            severity,
+            text: text.to_owned(),
+        });
+        if !self.visible {
+            self.unseen = self.unseen.saturating_add(1);
+        }
+    }
+
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &StatusLogEntry> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for StatusLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `+HH:MM:SS` since launch.
+#[must_use]
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("+{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+pub(crate) fn toggle_status_log(app: &mut App) {
+    app.status_log.visible = !app.status_log.visible;
+    if app.status_log.visible {
+        app.status_log.unseen = 0;
+    }
+    app.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn log_caps_entries_and_counts_unseen_while_hidden() {
+        let mut log = StatusLog::new();
+        for idx in 0..=MAX_STATUS_LOG_ENTRIES {
+            log.record(Some(SystemSeverity::Info), &format!("entry {idx}"));
+        }
+        assert_eq!(log.entries().len(), MAX_STATUS_LOG_ENTRIES);
+        assert_eq!(log.entries().next().map(|entry| entry.text.as_str()), Some("entry 1"));
+        assert_eq!(log.unseen, MAX_STATUS_LOG_ENTRIES + 1);
+        assert_eq!(format_elapsed(Duration::from_secs(3_725)), "+01:02:05");
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Follow-up prompts offered under a finished turn. They come from simple
+//! heuristics over the turn's tool calls (failed commands, TODOs added by
+//! edits, edits without a test run) and Alt+1..3 copies one into the input.
+
+use super::{App, ChatMessage, MessageBlock, MessageRole, is_execute_tool_name};
+use crate::agent::model;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Most suggestions shown at once; each gets an Alt+digit shortcut.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Commands longer than this are cut short when quoted in a suggestion.
+const MAX_COMMAND_CHARS: usize = 60;
+
+/// Words that mark a shell command as a test run.
+const TEST_COMMAND_WORDS: [&str; 7] =
+    ["test", "tests", "pytest", "jest", "vitest", "nextest", "rspec"];
+
+/// Recompute suggestions for the turn that just finished.
+pub(crate) fn refresh_suggestions(app: &mut App) {
+    app.suggestions = if app.config.follow_up_suggestions_effective() {
+        follow_up_prompts(app)
+    } else {
+        Vec::new()
+    };
+    app.needs_redraw = true;
+}
+
+pub(crate) fn clear_suggestions(app: &mut App) {
+    if !app.suggestions.is_empty() {
+        app.suggestions.clear();
+        app.needs_redraw = true;
+    }
+}
+
+/// Alt+1..3 replaces the input with the matching suggestion.
+pub(crate) fn handle_suggestion_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.modifiers != KeyModifiers::ALT {
+        return false;
+    }
+    let index = match key.code {
+        KeyCode::Char('1') => 0,
+        KeyCode::Char('2') => 1,
+        KeyCode::Char('3') => 2,
+        _ => return false,
+    };
+    let Some(prompt) = app.suggestions.get(index).cloned() else {
+        return false;
+    };
+    app.input.set_text(&prompt);
+    true
+}
+
+fn follow_up_prompts(app: &App) -> Vec<String> {
+    let turn = last_turn(&app.messages);
+    let mut failures = Vec::new();
+    let mut todo_paths = Vec::new();
+    let mut edited = false;
+    let mut ran_tests = false;
+
+    for tc in turn.iter().flat_map(|message| &message.blocks).filter_map(|block| match block {
+        MessageBlock::ToolCall(tc) if !tc.hidden => Some(tc.as_ref()),
+        _ => None,
+    }) {
+        if is_execute_tool_name(&tc.sdk_tool_name)
+            && let Some(command) = tc.terminal_command.as_deref()
+        {
+            let is_test = is_test_command(command);
+            ran_tests |= is_test;
+            if tc.status == model::ToolCallStatus::Failed {
+                let command = short_command(command);
+                failures.push(if is_test {
+                    format!("Fix the failing tests from `{command}`")
+                } else {
+                    format!("Find out why `{command}` failed and fix it")
+                });
+            }
+        }
+        if tc.status != model::ToolCallStatus::Completed {
+            continue;
+        }
+        for content in &tc.content {
+            let model::ToolCallContent::Diff(diff) = content else {
+                continue;
+            };
+            edited = true;
+            if adds_todo(diff) {
+                let path = super::explain::mention_path(app, &diff.path);
+                todo_paths.push(format!("Resolve the TODOs added to @{path}"));
+            }
+        }
+    }
+
+    let mut prompts = failures;
+    prompts.extend(todo_paths);
+    if edited && !ran_tests {
+        prompts.push("Run the tests covering these changes".to_owned());
+    }
+    if edited {
+        prompts.push("Review this turn's edits for bugs and missed edge cases".to_owned());
+    }
+    let mut seen = std::collections::HashSet::new();
+    prompts.retain(|prompt| seen.insert(prompt.clone()));
+    prompts.truncate(MAX_SUGGESTIONS);
+    prompts
+}
+
+/// Messages after the latest user prompt.
+fn last_turn(messages: &[ChatMessage]) -> &[ChatMessage] {
+    let start = messages
+        .iter()
+        .rposition(|message| matches!(message.role, MessageRole::User))
+        .map_or(0, |idx| idx + 1);
+    &messages[start..]
+}
+
+fn is_test_command(command: &str) -> bool {
+    command
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .any(|word| TEST_COMMAND_WORDS.contains(&word))
+}
+
+fn short_command(command: &str) -> String {
+    let first_line = command.lines().next().unwrap_or_default().trim();
+    if first_line.chars().count() <= MAX_COMMAND_CHARS && !command.trim().contains('\n') {
+        return first_line.to_owned();
+    }
+    let mut short: String = first_line.chars().take(MAX_COMMAND_CHARS).collect();
+    short.push('\u{2026}');
+    short
+}
+
+/// Whether the edit leaves more TODO/FIXME markers than it found.
+fn adds_todo(diff: &model::Diff) -> bool {
+    fn markers(text: &str) -> usize {
+        text.lines().filter(|line| line.contains("TODO") || line.contains("FIXME")).count()
+    }
+    markers(&diff.new_text) > diff.old_text.as_deref().map_or(0, markers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn command_heuristics_spot_tests_and_shorten_long_commands() {
+        assert!(is_test_command("cargo test --workspace"));
+        assert!(is_test_command("npx vitest run"));
+        assert!(!is_test_command("cargo build"));
+
+        assert_eq!(short_command("  ls -la  "), "ls -la");
+        assert_eq!(short_command("cd app\nmake"), "cd app\u{2026}");
+        let long = "x".repeat(MAX_COMMAND_CHARS + 10);
+        assert_eq!(short_command(&long).chars().count(), MAX_COMMAND_CHARS + 1);
+
+        let mut diff = model::Diff::new("src/lib.rs", "// TODO: handle errors\nfn a() {}");
+        assert!(adds_todo(&diff));
+        diff.old_text = Some("// TODO: handle errors".to_owned());
+        assert!(!adds_todo(&diff));
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lightweight, ctags-style symbol extraction for `@file#symbol` mentions.
+//!
+//! This is a line-oriented scanner, not a parser: it recognizes top-level and
+//! nested definitions by their leading keywords (`fn`, `struct`, `class`,
+//! `def`, `func`, ...) after stripping visibility and other modifiers. That is
+//! enough to complete symbol names without pulling in a grammar per language.
+
+use std::path::Path;
+
+/// Files larger than this are not scanned for symbols.
+pub const MAX_SYMBOL_FILE_BYTES: u64 = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    Type,
+    Module,
+    Const,
+    Macro,
+    Class,
+    Interface,
+}
+
+impl SymbolKind {
+    /// Short label shown next to the symbol in the dropdown.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Function => "fn",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Trait => "trait",
+            Self::Type => "type",
+            Self::Module => "mod",
+            Self::Const => "const",
+            Self::Macro => "macro",
+            Self::Class => "class",
+            Self::Interface => "interface",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// 1-based line number of the definition.
+    pub line: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Language {
+    Rust,
+    Python,
+    JavaScript,
+    Go,
+    Other,
+}
+
+impl Language {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()).unwrap_or_default() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Self::JavaScript,
+            "go" => Self::Go,
+            _ => Self::Other,
+        }
+    }
+
+    fn modifiers(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &["pub", "async", "unsafe", "default", "extern"],
+            Self::Python => &["async"],
+            Self::JavaScript => &["export", "default", "declare", "abstract", "async"],
+            Self::Go => &[],
+            Self::Other => &["public", "private", "protected", "static", "export", "async"],
+        }
+    }
+
+    fn keywords(self) -> &'static [(&'static str, SymbolKind)] {
+        match self {
+            Self::Rust => &[
+                ("fn", SymbolKind::Function),
+                ("struct", SymbolKind::Struct),
+                ("union", SymbolKind::Struct),
+                ("enum", SymbolKind::Enum),
+                ("trait", SymbolKind::Trait),
+                ("type", SymbolKind::Type),
+                ("mod", SymbolKind::Module),
+                ("const", SymbolKind::Const),
+                ("static", SymbolKind::Const),
+                ("macro_rules!", SymbolKind::Macro),
+            ],
+            Self::Python => &[("def", SymbolKind::Function), ("class", SymbolKind::Class)],
+            Self::JavaScript => &[
+                ("function", SymbolKind::Function),
+                ("function*", SymbolKind::Function),
+                ("class", SymbolKind::Class),
+                ("interface", SymbolKind::Interface),
+                ("type", SymbolKind::Type),
+                ("enum", SymbolKind::Enum),
+                ("namespace", SymbolKind::Module),
+            ],
+            Self::Go => &[("func", SymbolKind::Function), ("type", SymbolKind::Type)],
+            Self::Other => &[
+                ("function", SymbolKind::Function),
+                ("def", SymbolKind::Function),
+                ("fn", SymbolKind::Function),
+                ("func", SymbolKind::Function),
+                ("class", SymbolKind::Class),
+                ("struct", SymbolKind::Struct),
+                ("interface", SymbolKind::Interface),
+                ("enum", SymbolKind::Enum),
+            ],
+        }
+    }
+}
+
+/// Extract symbol definitions from `source`, using `path` to pick the language.
+#[must_use]
+pub fn extract_symbols(path: &Path, source: &str) -> Vec<Symbol> {
+    let language = Language::from_path(path);
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            parse_definition(language, line).map(|(kind, name)| Symbol {
+                name: name.to_owned(),
+                kind,
+                line: idx + 1,
+            })
+        })
+        .collect()
+}
+
+/// Read `path` and extract its symbols. Missing, oversized, or non-UTF-8
+/// files yield no symbols.
+#[must_use]
+pub fn load_symbols(path: &Path) -> Vec<Symbol> {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Vec::new();
+    };
+    if !metadata.is_file() || metadata.len() > MAX_SYMBOL_FILE_BYTES {
+        return Vec::new();
+    }
+    match std::fs::read_to_string(path) {
+        Ok(source) => extract_symbols(path, &source),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn parse_definition(language: Language, line: &str) -> Option<(SymbolKind, &str)> {
+    let mut rest = strip_modifiers(language, line.trim_start());
+
+    if matches!(language, Language::JavaScript)
+        && let Some(name) = arrow_function_name(rest)
+    {
+        return Some((SymbolKind::Function, name));
+    }
+    // `const fn` / `const unsafe fn` are functions, not constants.
+    if matches!(language, Language::Rust)
+        && let Some(after) = strip_word(rest, "const")
+    {
+        let after = strip_modifiers(language, after);
+        if strip_word(after, "fn").is_some() {
+            rest = after;
+        }
+    }
+
+    for &(keyword, kind) in language.keywords() {
+        let Some(mut after) = strip_word(rest, keyword) else {
+            continue;
+        };
+        // Go methods: `func (r *Recv) Name(`.
+        if matches!(language, Language::Go) && after.starts_with('(') {
+            after = after.split_once(')')?.1.trim_start();
+        }
+        if kind == SymbolKind::Const {
+            after = strip_word(after, "mut").unwrap_or(after);
+        }
+        let name = identifier_prefix(after);
+        if name.is_empty() || name == "_" {
+            return None;
+        }
+        return Some((kind, name));
+    }
+    None
+}
+
+fn strip_modifiers(language: Language, mut rest: &str) -> &str {
+    loop {
+        let before = rest;
+        for &modifier in language.modifiers() {
+            if let Some(after) = strip_word(rest, modifier) {
+                rest = after;
+            }
+        }
+        // `pub(crate)`, `pub(in path)`, `extern "C"`.
+        if let Some((_, after)) = rest.strip_prefix("pub(").and_then(|r| r.split_once(')')) {
+            rest = after.trim_start();
+        }
+        if let Some((_, after)) = rest.strip_prefix('"').and_then(|r| r.split_once('"')) {
+            rest = after.trim_start();
+        }
+        if rest == before {
+            return rest;
+        }
+    }
+}
+
+/// Strip `word` from the start of `text` when it is followed by whitespace.
+fn strip_word<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    let after = text.strip_prefix(word)?;
+    after.starts_with(char::is_whitespace).then_some(after.trim_start())
+}
+
+/// `const name = (...) =>`, `let name = async function`, `const name = x =>`.
+fn arrow_function_name(rest: &str) -> Option<&str> {
+    let after = ["const", "let", "var"].iter().find_map(|kw| strip_word(rest, kw))?;
+    let name = identifier_prefix(after);
+    if name.is_empty() {
+        return None;
+    }
+    let (annotation, value) = after[name.len()..].split_once('=')?;
+    // Allow an optional type annotation (`name: Handler = ...`) but not `==`.
+    let annotation = annotation.trim();
+    if !(annotation.is_empty() || annotation.starts_with(':')) || value.starts_with('=') {
+        return None;
+    }
+    let value = value.trim_start();
+    let value = value.strip_prefix("async").map_or(value, str::trim_start);
+    let param = identifier_prefix(value);
+    let is_function = value.starts_with('(')
+        || value.starts_with("function")
+        || (!param.is_empty() && value[param.len()..].trim_start().starts_with("=>"));
+    is_function.then_some(name)
+}
+
+fn identifier_prefix(text: &str) -> &str {
+    let end = text
+        .char_indices()
+        .find(|&(_, ch)| !(ch.is_alphanumeric() || ch == '_' || ch == '$'))
+        .map_or(text.len(), |(idx, _)| idx);
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(path: &str, source: &str) -> Vec<(String, &'static str, usize)> {
+        extract_symbols(Path::new(path), source)
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.kind.label(), symbol.line))
+            .collect()
+    }
+
+    #[test]
+    fn rust_definitions_with_modifiers() {
+        let source = "\
+pub struct App {
+    field: u8,
+}
+
+impl App {
+    pub(crate) async fn connect(&self) {}
+    const fn size() -> usize { 0 }
+}
+
+pub(in crate::app) enum Mode {}
+const MAX: usize = 8;
+let not_a_symbol = 1;
+macro_rules! hello { () => {} }
+pub unsafe extern \"C\" fn ffi() {}
+";
+        assert_eq!(
+            names("src/app.rs", source),
+            vec![
+                ("App".to_owned(), "struct", 1),
+                ("connect".to_owned(), "fn", 6),
+                ("size".to_owned(), "fn", 7),
+                ("Mode".to_owned(), "enum", 10),
+                ("MAX".to_owned(), "const", 11),
+                ("hello".to_owned(), "macro", 13),
+                ("ffi".to_owned(), "fn", 14),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_and_go_definitions() {
+        assert_eq!(
+            names("tool.py", "class Tool:\n    async def run(self):\n        pass\n"),
+            vec![("Tool".to_owned(), "class", 1), ("run".to_owned(), "fn", 2)]
+        );
+        assert_eq!(
+            names("main.go", "type Server struct{}\nfunc (s *Server) Serve() {}\nfunc main() {}\n"),
+            vec![
+                ("Server".to_owned(), "type", 1),
+                ("Serve".to_owned(), "fn", 2),
+                ("main".to_owned(), "fn", 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_functions_classes_and_arrow_consts() {
+        let source = "\
+export default class Bridge {}
+export interface Options {}
+export const handler: Handler = async (req) => {};
+const double = x => x * 2;
+const LIMIT = 10;
+if (a == b) {}
+export async function main() {}
+";
+        assert_eq!(
+            names("src/index.ts", source),
+            vec![
+                ("Bridge".to_owned(), "class", 1),
+                ("Options".to_owned(), "interface", 2),
+                ("handler".to_owned(), "fn", 3),
+                ("double".to_owned(), "fn", 4),
+                ("main".to_owned(), "fn", 7),
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Markdown task lists. `- [ ]` and `- [x]` items render with checkbox glyphs.
+//! `t` in keyboard selection mode (Ctrl+s) on an item's row ticks or unticks
+//! it in the transcript and pre-fills a follow-up prompt ("Step 2 (...) is
+//! done. Next, do step 3: ..." or "Do step 3: ..."), a lightweight way to walk
+//! a plan without the todo panel.
+
+use super::{App, IncrementalMarkdown, InvalidationLevel, MessageBlock};
+use crate::ui::markdown::is_fence;
+use std::borrow::Cow;
+
+pub const UNCHECKED: &str = "\u{2610}";
+pub const CHECKED: &str = "\u{2611}";
+
+/// A task list item on one source line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TaskItem<'a> {
+    /// Byte offset of the `[` of the checkbox in the line.
+    offset: usize,
+    checked: bool,
+    text: &'a str,
+}
+
+/// Parse `line` as a list item that starts with a checkbox.
+fn task_item(line: &str) -> Option<TaskItem<'_>> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    let after_marker = if let Some(rest) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        rest
+    } else {
+        let digits = trimmed.find(|c: char| !c.is_ascii_digit())?;
+        if digits == 0 {
+            return None;
+        }
+        trimmed[digits..].strip_prefix(". ").or_else(|| trimmed[digits..].strip_prefix(") "))?
+    };
+    let spaces = after_marker.len() - after_marker.trim_start().len();
+    let boxed = &after_marker[spaces..];
+    let checked = match boxed.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let text = &boxed[3..];
+    if !text.is_empty() && !text.starts_with(' ') {
+        return None;
+    }
+    Some(TaskItem { offset: indent + (trimmed.len() - boxed.len()), checked, text: text.trim() })
+}
+
+/// Task items of `text` outside code fences, with the byte offset of their line.
+fn task_items(text: &str) -> Vec<(usize, TaskItem<'_>)> {
+    let mut items = Vec::new();
+    let mut in_fence = false;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        if is_fence(line) {
+            in_fence = !in_fence;
+        } else if !in_fence && let Some(item) = task_item(line.trim_end_matches('\n')) {
+            items.push((line_start, item));
+        }
+        line_start += line.len();
+    }
+    items
+}
+
+/// Markdown with task list checkboxes replaced by glyphs.
+#[must_use]
+pub fn with_checkbox_glyphs(text: &str) -> Cow<'_, str> {
+    if !text.contains("[ ]") && !text.contains("[x]") && !text.contains("[X]") {
+        return Cow::Borrowed(text);
+    }
+    let items = task_items(text);
+    if items.is_empty() {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (line_start, item) in items {
+        let at = line_start + item.offset;
+        out.push_str(&text[copied..at]);
+        out.push_str(if item.checked { CHECKED } else { UNCHECKED });
+        copied = at + 3;
+    }
+    out.push_str(&text[copied..]);
+    Cow::Owned(out)
+}
+
+/// Letters and digits only, so rendered rows compare with markdown source.
+fn normalized(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Checkbox state and text shown on a rendered chat row.
+fn row_item(row: &str) -> Option<(bool, &str)> {
+    let (at, checked) = match (row.find(UNCHECKED), row.find(CHECKED)) {
+        (Some(u), Some(c)) => (u.min(c), c < u),
+        (Some(u), None) => (u, false),
+        (None, Some(c)) => (c, true),
+        (None, None) => return None,
+    };
+    if row[..at].chars().any(char::is_alphabetic) {
+        return None;
+    }
+    Some((checked, row[at + UNCHECKED.len()..].trim()))
+}
+
+/// Tick or untick the task item on the selection cursor's row and pre-fill a
+/// follow-up prompt. Returns false when the row is not a task item.
+pub(crate) fn toggle_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let Some((checked, label)) =
+        app.rendered_chat_lines.get(visual.cursor.row).map(String::as_str).and_then(row_item)
+    else {
+        return false;
+    };
+    let label = normalized(label);
+    let message_index =
+        app.viewport.find_first_visible(app.viewport.scroll_offset + visual.cursor.row);
+    let Some(message) = app.messages.get_mut(message_index) else {
+        return false;
+    };
+
+    // (block, byte offset of the box, checked, text) for every item of the message.
+    let mut items = Vec::new();
+    for (block_index, block) in message.blocks.iter().enumerate() {
+        if let MessageBlock::Text(block) = block {
+            items.extend(task_items(&block.text).into_iter().map(|(line_start, item)| {
+                (block_index, line_start + item.offset, item.checked, item.text.to_owned())
+            }));
+        }
+    }
+    let Some(step) = items
+        .iter()
+        .position(|(_, _, done, text)| *done == checked && normalized(text).starts_with(&label))
+    else {
+        return false;
+    };
+    let (block_index, offset, _, ref text) = items[step];
+    if let Some(MessageBlock::Text(block)) = message.blocks.get_mut(block_index) {
+        block.text.replace_range(offset..offset + 3, if checked { "[ ]" } else { "[x]" });
+        block.markdown = IncrementalMarkdown::from_complete(&block.text);
+        block.cache.invalidate();
+    }
+
+    let prompt = if checked {
+        format!("Do step {}: {text}", step + 1)
+    } else {
+        let next = items.iter().enumerate().skip(step + 1).find(|(_, (_, _, done, _))| !done);
+        match next {
+            Some((next_step, (_, _, _, next_text))) => format!(
+                "Step {} ({text}) is done. Next, do step {}: {next_text}",
+                step + 1,
+                next_step + 1
+            ),
+            None => format!("Step {} ({text}) is done.", step + 1),
+        }
+    };
+    app.input.set_text(&prompt);
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.needs_redraw = true;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn checkboxes_become_glyphs_outside_code() {
+        let text =
+            "Plan:\n- [ ] read\n  * [x] parse\n1. [X] done\n- [link](x)\n```\n- [ ] code\n```";
+        assert_eq!(
+            with_checkbox_glyphs(text),
+            "Plan:\n- \u{2610} read\n  * \u{2611} parse\n1. \u{2611} done\n- [link](x)\n```\n- [ ] code\n```"
+        );
+        assert_eq!(with_checkbox_glyphs("a [ ] b"), "a [ ] b");
+    }
+
+    #[test]
+    fn rendered_rows_map_back_to_items() {
+        assert_eq!(row_item("\u{2022} \u{2610} Run the tests"), Some((false, "Run the tests")));
+        assert_eq!(row_item("2. \u{2611} Ship"), Some((true, "Ship")));
+        assert_eq!(row_item("see \u{2610} here"), None);
+        assert_eq!(
+            task_item("- [x] **Bold** step"),
+            Some(TaskItem { offset: 2, checked: true, text: "**Bold** step" })
+        );
+    }
+}
@@ -44,7 +44,7 @@ fn apply_terminal_payload(tc: &mut ToolCallInfo, payload: TerminalUpdatePayload)
 /// Uses append-only deltas when possible, with full-snapshot fallback when
 /// invariants are broken (truncate/reset/replace mode).
 pub(super) fn update_terminal_outputs(app: &mut App) -> bool {
-    let _t = app.perf.as_ref().map(|p| p.start("terminal::update"));
+    let _t = crate::perf::start("terminal::update");
     let terminals = app.terminals.borrow();
     if terminals.is_empty() {
         return false;
@@ -52,6 +52,7 @@ pub(super) fn update_terminal_outputs(app: &mut App) -> bool {
 
     let mut changed = false;
     let mut dirty_from: Option<usize> = None;
+    let mut updated = Vec::new();
 
     // Use the indexed terminal tool calls instead of scanning all messages/blocks.
     for &(ref tid, mi, bi) in &app.terminal_tool_calls {
@@ -96,11 +97,15 @@ pub(super) fn update_terminal_outputs(app: &mut App) -> bool {
         if apply_terminal_payload(tc, payload) {
             tc.mark_tool_call_layout_dirty();
             dirty_from = Some(dirty_from.map_or(mi, |oldest| oldest.min(mi)));
+            updated.push((mi, bi));
             changed = true;
         }
     }
 
     drop(terminals);
+    for (mi, bi) in updated {
+        super::watch_patterns::scan(app, mi, bi);
+    }
 
     if let Some(mi) = dirty_from {
         app.invalidate_layout(InvalidationLevel::Single(mi));
@@ -0,0 +1,235 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `/test`: run the project's tests in the background and show the result.
+//!
+//! The command is `testCommand` from `~/.claude.json`, else one guessed from
+//! the files in the session directory (`cargo test`, `npm test`, ...); extra
+//! `/test` arguments are appended to it. When it fails, the footer offers Tab
+//! to send the command, its exit code and the tail of its output to the
+//! agent as one prompt, until the input is edited.
+
+use super::config::store;
+use super::events::push_system_message_with_severity;
+use super::lifecycle_hooks::shell;
+use super::toast::{ToastKind, show_toast};
+use super::{App, AppStatus, SystemSeverity};
+use crate::agent::events::ClientEvent;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+const TEST_TIMEOUT: Duration = Duration::from_secs(600);
+/// Output lines shown in the transcript when the command fails.
+const SHOWN_TAIL_LINES: usize = 12;
+/// Output lines sent to the agent with the failure.
+const SENT_TAIL_LINES: usize = 80;
+/// Marker files and the test command they suggest, in lookup order.
+const DETECTED_COMMANDS: &[(&str, &str)] = &[
+    ("Cargo.toml", "cargo test"),
+    ("package.json", "npm test"),
+    ("go.mod", "go test ./..."),
+    ("pyproject.toml", "pytest"),
+    ("pytest.ini", "pytest"),
+    ("Makefile", "make test"),
+];
+
+/// How a finished command ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutcome {
+    /// Exit code; `None` when the process was killed by a signal.
+    pub exit_code: Option<i32>,
+    /// Combined stdout and stderr.
+    pub output: String,
+}
+
+/// A failed command that Tab can still send to the agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedCommandOffer {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    /// Last lines of the output.
+    pub output_tail: String,
+    /// Input version when the failure was reported; any later edit withdraws the offer.
+    pub input_version: u64,
+}
+
+impl FailedCommandOffer {
+    #[must_use]
+    pub fn hint(&self) -> String {
+        format!("`{}` failed \u{b7} Tab: send to Claude", self.command)
+    }
+
+    fn prompt(&self) -> String {
+        let mut prompt = format!(
+            "The command `{}` failed with {}. Find the cause and fix it.\n\n<failed_command>\n",
+            self.command,
+            exit_description(self.exit_code)
+        );
+        let _ = writeln!(prompt, "command: {}", self.command);
+        if let Some(code) = self.exit_code {
+            let _ = writeln!(prompt, "exit_code: {code}");
+        }
+        let _ = write!(
+            prompt,
+            "output (last {SENT_TAIL_LINES} lines):\n{}\n</failed_command>",
+            self.output_tail
+        );
+        prompt
+    }
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        return Err("Wait for the current turn to finish before running /test.".to_owned());
+    }
+    let base = store::test_command(&app.config.committed_preferences_document)
+        .or_else(|| detect_command(Path::new(&app.cwd_raw)).map(str::to_owned))
+        .ok_or("No test command found for this project. Set `testCommand` in ~/.claude.json.")?;
+    let command = std::iter::once(base.as_str()).chain(args.iter().copied()).collect::<Vec<_>>();
+    let command = command.join(" ");
+
+    let mut process = shell(&command);
+    process
+        .current_dir(&app.cwd_raw)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    app.failed_command_offer = None;
+    app.status = AppStatus::CommandPending;
+    app.pending_command_label = Some(format!("Running {command}..."));
+    let tx = app.event_tx.clone();
+    tokio::task::spawn_local(async move {
+        let result = match tokio::time::timeout(TEST_TIMEOUT, process.output()).await {
+            Ok(Ok(output)) => {
+                let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                text.push_str(&String::from_utf8_lossy(&output.stderr));
+                Ok(CommandOutcome { exit_code: output.status.code(), output: text })
+            }
+            Ok(Err(err)) => Err(format!("cannot run `{command}`: {err}")),
+            Err(_) => Err(format!("`{command}` timed out")),
+        };
+        let _ = tx.send(ClientEvent::TestRunFinished { command, result });
+    });
+    Ok(())
+}
+
+fn detect_command(cwd: &Path) -> Option<&'static str> {
+    DETECTED_COMMANDS
+        .iter()
+        .find(|(marker, _)| cwd.join(marker).is_file())
+        .map(|(_, command)| *command)
+}
+
+/// Report a finished `/test` run; a failure becomes the pending offer.
+pub(crate) fn finished(app: &mut App, command: String, outcome: &CommandOutcome) {
+    if outcome.exit_code == Some(0) {
+        push_system_message_with_severity(
+            app,
+            Some(SystemSeverity::Info),
+            &format!("Tests passed: `{command}`"),
+        );
+        return;
+    }
+    let mut message = format!("`{command}` failed with {}.", exit_description(outcome.exit_code));
+    let shown = tail(&outcome.output, SHOWN_TAIL_LINES);
+    if !shown.is_empty() {
+        let _ = write!(message, "\n```\n{shown}\n```");
+    }
+    message.push_str("\nPress Tab to send the failure to Claude.");
+    push_system_message_with_severity(app, Some(SystemSeverity::Error), &message);
+    app.failed_command_offer = Some(FailedCommandOffer {
+        command,
+        exit_code: outcome.exit_code,
+        output_tail: tail(&outcome.output, SENT_TAIL_LINES),
+        input_version: app.input.version,
+    });
+}
+
+/// Return the pending offer if the input has not changed since the failure.
+#[must_use]
+pub fn active_offer(app: &App) -> Option<&FailedCommandOffer> {
+    app.failed_command_offer.as_ref().filter(|offer| offer.input_version == app.input.version)
+}
+
+/// Send the failed command to the agent. Returns `false` when there is no
+/// live offer.
+pub(crate) fn accept_offer(app: &mut App) -> bool {
+    if active_offer(app).is_none() {
+        return false;
+    }
+    if matches!(app.status, AppStatus::Thinking | AppStatus::Running | AppStatus::CommandPending) {
+        show_toast(
+            app,
+            ToastKind::Info,
+            "Wait for the current turn to finish before sending the failure.",
+        );
+        return true;
+    }
+    let Some(offer) = app.failed_command_offer.take() else {
+        return false;
+    };
+    let prompt = offer.prompt();
+    super::input_submit::dispatch_prompt_turn_as(app, &prompt.clone(), prompt);
+    true
+}
+
+fn exit_description(exit_code: Option<i32>) -> String {
+    exit_code.map_or_else(
+        || "no exit code (killed by a signal)".to_owned(),
+        |code| format!("exit code {code}"),
+    )
+}
+
+fn tail(output: &str, lines: usize) -> String {
+    let all: Vec<&str> = output.trim_end().lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn failure_offers_structured_prompt_until_input_changes() {
+        let mut app = App::test_default();
+        let output = (1..=100).fold(String::new(), |mut output, n| {
+            let _ = writeln!(output, "line {n}");
+            output
+        });
+
+        finished(
+            &mut app,
+            "cargo test".to_owned(),
+            &CommandOutcome { exit_code: Some(101), output },
+        );
+
+        let offer = active_offer(&app).expect("failure offer");
+        assert_eq!(offer.hint(), "`cargo test` failed \u{b7} Tab: send to Claude");
+        let prompt = offer.prompt();
+        assert!(prompt.starts_with(
+            "The command `cargo test` failed with exit code 101. Find the cause and fix it."
+        ));
+        assert!(prompt.contains("exit_code: 101\noutput (last 80 lines):\nline 21\n"));
+        assert!(prompt.ends_with("line 100\n</failed_command>"));
+
+        app.input.insert_str("x");
+        assert!(active_offer(&app).is_none());
+        assert!(!accept_offer(&mut app));
+    }
+
+    #[test]
+    fn passing_run_leaves_no_offer() {
+        let mut app = App::test_default();
+
+        finished(
+            &mut app,
+            "npm test".to_owned(),
+            &CommandOutcome { exit_code: Some(0), output: "ok".to_owned() },
+        );
+
+        assert!(app.failed_command_offer.is_none());
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Short-lived notifications stacked in the top-right corner ("Copied to
+//! clipboard", "Reconnected", ...). Each toast expires on its own; Esc while
+//! idle dismisses the newest one early.
+
+use super::App;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays up unless dismissed.
+pub const TOAST_TIMEOUT: Duration = Duration::from_secs(3);
+/// Older toasts are dropped when more than this many are stacked.
+const MAX_VISIBLE_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub text: String,
+    pub expires_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct ToastQueue {
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastQueue {
+    pub fn push(&mut self, kind: ToastKind, text: impl Into<String>, now: Instant) {
+        let text = text.into();
+        // Repeating the same feedback only refreshes its timer.
+        self.toasts.retain(|toast| toast.kind != kind || toast.text != text);
+        if self.toasts.len() == MAX_VISIBLE_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast { kind, text, expires_at: now + TOAST_TIMEOUT });
+    }
+
+    /// Drop expired toasts. Returns true when anything was removed.
+    pub fn prune(&mut self, now: Instant) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| toast.expires_at > now);
+        self.toasts.len() != before
+    }
+
+    /// Dismiss the most recent toast. Returns false when none are shown.
+    pub fn dismiss_newest(&mut self) -> bool {
+        self.toasts.pop_back().is_some()
+    }
+
+    /// Newest first, matching the top-down stacking order.
+    pub fn iter_newest_first(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter().rev()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+pub(crate) fn show_toast(app: &mut App, kind: ToastKind, text: impl Into<String>) {
+    app.toasts.push(kind, text, Instant::now());
+    app.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn texts(queue: &ToastQueue) -> Vec<&str> {
+        queue.iter_newest_first().map(|toast| toast.text.as_str()).collect()
+    }
+
+    #[test]
+    fn toasts_stack_expire_and_dismiss() {
+        let now = Instant::now();
+        let mut queue = ToastQueue::default();
+        for idx in 0..=MAX_VISIBLE_TOASTS {
+            queue.push(ToastKind::Info, format!("toast {idx}"), now);
+        }
+        assert_eq!(texts(&queue), vec!["toast 4", "toast 3", "toast 2", "toast 1"]);
+
+        queue.push(ToastKind::Info, "toast 2", now + Duration::from_secs(2));
+        assert_eq!(texts(&queue), vec!["toast 2", "toast 4", "toast 3", "toast 1"]);
+
+        assert!(!queue.prune(now + Duration::from_secs(1)));
+        assert!(queue.prune(now + TOAST_TIMEOUT));
+        assert_eq!(texts(&queue), vec!["toast 2"]);
+
+        assert!(queue.dismiss_newest());
+        assert!(queue.is_empty());
+        assert!(!queue.dismiss_newest());
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Automatic retries of tool calls that failed for a transient reason. With
+//! the "Retry flaky tools" setting on, a turn whose last failed call hit a
+//! network error, or ran a test suite that failed without reporting an
+//! assertion or build error, is followed by one prompt asking the agent to run
+//! that call again. The failed block is badged as retried, and
+//! the follow-up turn is never retried itself, so a real failure stands after
+//! one extra attempt.
+
+use super::events::push_system_message_with_severity;
+use super::{App, InvalidationLevel, MessageBlock, MessageRole, SystemSeverity, ToolCallInfo};
+use crate::agent::model;
+
+/// Lowercase output fragments of failures worth another try.
+const NETWORK_MARKERS: &[&str] = &[
+    "connection reset",
+    "connection refused",
+    "connection closed",
+    "timed out",
+    "could not resolve host",
+    "temporary failure in name resolution",
+    "network is unreachable",
+    "econnreset",
+    "econnrefused",
+    "etimedout",
+    "enotfound",
+    "eai_again",
+    "502 bad gateway",
+    "503 service unavailable",
+    "504 gateway",
+];
+/// Lowercase output fragments of a test run that failed for its environment
+/// or timing rather than its code.
+const FLAKY_MARKERS: &[&str] = &[
+    "flaky",
+    "intermittent",
+    "test timed out",
+    "exceeded timeout",
+    "timeout of ",
+    "has been running for over",
+    "address already in use",
+];
+/// Lowercase output fragments of a test run that failed on an assertion or a
+/// build error, which a second run would only repeat.
+const REAL_FAILURE_MARKERS: &[&str] = &[
+    "assertion failed",
+    "assertion `",
+    "assertionerror",
+    "expect(received)",
+    "expected:",
+    "panicked at",
+    "--- fail:",
+    "error[e",
+    "could not compile",
+    "syntaxerror",
+    "typeerror",
+    "referenceerror",
+    "cannot find module",
+    "modulenotfounderror",
+    "importerror",
+];
+/// Command prefixes that run a test suite.
+const TEST_COMMANDS: &[&str] = &[
+    "cargo test",
+    "cargo nextest",
+    "npm test",
+    "npm run test",
+    "yarn test",
+    "pnpm test",
+    "bun test",
+    "pytest",
+    "python -m pytest",
+    "go test",
+    "jest",
+    "npx jest",
+    "vitest",
+    "npx vitest",
+    "make test",
+    "mvn test",
+    "gradle test",
+    "./gradlew test",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientToolFailure {
+    Network,
+    FlakyTest,
+}
+
+impl TransientToolFailure {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Network => "network error",
+            Self::FlakyTest => "flaky test run",
+        }
+    }
+}
+
+fn is_test_command(command: &str) -> bool {
+    // `cd dir && cargo test`, `RUST_LOG=x cargo test -p foo`, ...
+    command.split(['&', ';', '|']).any(|part| {
+        let words: Vec<&str> =
+            part.split_whitespace().skip_while(|word| word.contains('=')).collect();
+        let part = words.join(" ");
+        TEST_COMMANDS.iter().any(|test| {
+            part.strip_prefix(test).is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+    })
+}
+
+fn failure_text(tc: &ToolCallInfo) -> String {
+    let mut text = tc.terminal_output.clone().unwrap_or_default();
+    for content in &tc.content {
+        if let model::ToolCallContent::Content(model::Content {
+            content: model::ContentBlock::Text(block),
+        }) = content
+        {
+            text.push('\n');
+            text.push_str(&block.text);
+        }
+    }
+    text.to_lowercase()
+}
+
+/// Why a failed tool call looks worth one more try, if it does.
+#[must_use]
+pub fn classify(tc: &ToolCallInfo) -> Option<TransientToolFailure> {
+    if tc.status != model::ToolCallStatus::Failed || tc.pending_permission.is_some() {
+        return None;
+    }
+    let output = failure_text(tc);
+    let contains = |markers: &[&str]| markers.iter().any(|marker| output.contains(marker));
+    let test_run =
+        tc.is_execute_tool() && tc.terminal_command.as_deref().is_some_and(is_test_command);
+    // An assertion or build error wins over any hint of flakiness: the test
+    // name or message may well mention "flaky" or a timeout.
+    if test_run && contains(REAL_FAILURE_MARKERS) {
+        return None;
+    }
+    if test_run && contains(FLAKY_MARKERS) {
+        return Some(TransientToolFailure::FlakyTest);
+    }
+    if contains(NETWORK_MARKERS) {
+        return Some(TransientToolFailure::Network);
+    }
+    // A test run that exited nonzero without reporting why, e.g. killed or
+    // crashed, is worth one more try.
+    test_run.then_some(TransientToolFailure::FlakyTest)
+}
+
+/// What the agent is asked to run again: the command, else the title.
+fn subject(tc: &ToolCallInfo) -> &str {
+    tc.terminal_command.as_deref().unwrap_or(&tc.title)
+}
+
+/// The last transient failure of the finished turn that no later call of the
+/// same command already got past.
+fn retry_candidate(blocks: &mut [MessageBlock]) -> Option<&mut ToolCallInfo> {
+    let calls: Vec<&ToolCallInfo> = blocks
+        .iter()
+        .filter_map(|block| match block {
+            MessageBlock::ToolCall(tc) if !tc.hidden => Some(tc.as_ref()),
+            _ => None,
+        })
+        .collect();
+    let index = calls.iter().rposition(|tc| classify(tc).is_some())?;
+    let failed = calls[index];
+    let recovered = calls[index + 1..].iter().any(|later| {
+        later.status == model::ToolCallStatus::Completed && subject(later) == subject(failed)
+    });
+    if recovered || failed.auto_retry.is_some() {
+        return None;
+    }
+    let id = failed.id.clone();
+    blocks.iter_mut().find_map(|block| match block {
+        MessageBlock::ToolCall(tc) if tc.id == id => Some(tc.as_mut()),
+        _ => None,
+    })
+}
+
+/// After a finished turn, ask the agent to run its last transient tool
+/// failure once more. Returns whether a retry was sent, in which case the
+/// turn carries on instead of ending here.
+pub(crate) fn retry_transient_failure(app: &mut App) -> bool {
+    if !app.config.auto_retry_tools_effective()
+        || app.tool_retry_sent
+        || app.conn.is_none()
+        || app.session_id.is_none()
+    {
+        return false;
+    }
+    let Some(message_index) =
+        app.messages.iter().rposition(|message| matches!(message.role, MessageRole::Assistant))
+    else {
+        return false;
+    };
+    let Some(tc) = retry_candidate(&mut app.messages[message_index].blocks) else {
+        return false;
+    };
+    let Some(kind) = classify(tc) else {
+        return false;
+    };
+    tc.auto_retry = Some(kind);
+    tc.mark_tool_call_layout_dirty();
+    let subject = subject(tc).to_owned();
+    let tool = tc.sdk_tool_name.clone();
+    app.invalidate_layout(InvalidationLevel::Single(message_index));
+    app.tool_retry_sent = true;
+    tracing::info!(tool = %tool, kind = ?kind, "auto-retrying failed tool call");
+
+    push_system_message_with_severity(
+        app,
+        Some(SystemSeverity::Info),
+        &format!(
+            "`{subject}` failed with what looks like a {}; asking Claude to retry it once.",
+            kind.label()
+        ),
+    );
+    let prompt = format!(
+        "The {tool} call `{subject}` failed with what looks like a {}. Run it once more exactly as before, then carry on with the task.",
+        kind.label()
+    );
+    super::input_submit::retry_prompt(app, prompt);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{BlockCache, TerminalSnapshotMode};
+    use pretty_assertions::assert_eq;
+
+    fn bash(id: &str, command: &str, status: model::ToolCallStatus, output: &str) -> ToolCallInfo {
+        ToolCallInfo {
+            id: id.into(),
+            title: command.into(),
+            sdk_tool_name: "Bash".into(),
+            raw_input: None,
+            output_metadata: None,
+            web_output: None,
+            status,
+            content: vec![],
+            collapsed: false,
+            hidden: false,
+            terminal_id: Some(id.into()),
+            terminal_command: Some(command.into()),
+            terminal_env: Vec::new(),
+            terminal_output: Some(output.into()),
+            terminal_output_len: output.len(),
+            terminal_bytes_seen: output.len(),
+            terminal_snapshot_mode: TerminalSnapshotMode::AppendOnly,
+            render_epoch: 0,
+            layout_epoch: 0,
+            last_measured_width: 0,
+            last_measured_height: 0,
+            last_measured_layout_epoch: 0,
+            last_measured_layout_generation: 0,
+            cache: BlockCache::default(),
+            pending_permission: None,
+            pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn network_errors_and_unexplained_test_failures_count_as_transient() {
+        let failed = model::ToolCallStatus::Failed;
+        let fetch =
+            bash("1", "curl -sf https://example.com", failed, "curl: (6) Could not resolve host");
+        assert_eq!(classify(&fetch), Some(TransientToolFailure::Network));
+        let killed = bash("2", "cd crate && RUST_LOG=off cargo test -p core", failed, "Killed");
+        assert_eq!(classify(&killed), Some(TransientToolFailure::FlakyTest));
+        let timeout =
+            bash("6", "npx jest", failed, "thrown: \"Exceeded timeout of 5000 ms for a test.\"");
+        assert_eq!(classify(&timeout), Some(TransientToolFailure::FlakyTest));
+        assert_eq!(classify(&bash("3", "cargo build", failed, "error[E0308]")), None);
+        assert_eq!(classify(&bash("4", "jestify", failed, "")), None);
+        let passed = bash("5", "cargo test", model::ToolCallStatus::Completed, "ok");
+        assert_eq!(classify(&passed), None);
+    }
+
+    #[test]
+    fn assertion_and_build_failures_in_test_runs_are_not_retried() {
+        let failed = model::ToolCallStatus::Failed;
+        let outputs = [
+            (
+                "cargo test",
+                "thread 'parses' panicked at src/lib.rs:9:5:\nassertion `left == right` failed\n  left: 1\n right: 2\ntest result: FAILED",
+            ),
+            ("pytest -q", "E       AssertionError: assert 1 == 2\n1 failed in 0.12s"),
+            ("npm test", "Expected: 2\nReceived: 1\nTests: 1 failed, 4 passed"),
+            ("go test ./...", "--- FAIL: TestParse (0.00s)\n    parse_test.go:12: got 1, want 2"),
+            ("cargo test", "error[E0308]: mismatched types\nerror: could not compile `core`"),
+            // A test that checks connection errors still failed on its assertion.
+            (
+                "cargo test",
+                "panicked at tests/net.rs:4:5:\nassertion failed: err.contains(\"connection refused\")",
+            ),
+            // Nor do the words "flaky" or "timeout of " make an assertion transient.
+            (
+                "cargo test",
+                "thread 'flaky_retry_backoff' panicked at src/retry.rs:30:9:\nassertion failed: timeout of 5s",
+            ),
+            (
+                "npx jest",
+                "thrown: \"Exceeded timeout of 5000 ms\"\nexpect(received).toBe(expected)",
+            ),
+        ];
+        for (command, output) in outputs {
+            assert_eq!(classify(&bash("1", command, failed, output)), None, "{output}");
+        }
+    }
+
+    #[test]
+    fn a_failure_the_agent_already_got_past_is_not_retried() {
+        let failed = model::ToolCallStatus::Failed;
+        let mut blocks = vec![
+            MessageBlock::ToolCall(Box::new(bash("1", "npm test", failed, "1 failing"))),
+            MessageBlock::ToolCall(Box::new(bash(
+                "2",
+                "npm test",
+                model::ToolCallStatus::Completed,
+                "",
+            ))),
+        ];
+        assert!(retry_candidate(&mut blocks).is_none());
+
+        blocks.pop();
+        assert_eq!(retry_candidate(&mut blocks).map(|tc| tc.id.clone()), Some("1".to_owned()));
+        if let MessageBlock::ToolCall(tc) = &mut blocks[0] {
+            tc.auto_retry = Some(TransientToolFailure::FlakyTest);
+        }
+        assert!(retry_candidate(&mut blocks).is_none());
+    }
+}
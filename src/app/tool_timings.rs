@@ -0,0 +1,194 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wall-clock time of the session's tool calls, from the first `ToolCall`
+//! event to the update that completes or fails the call. `/stats` shows a
+//! histogram per tool and the slowest calls, to see where long turns spend
+//! their time.
+
+use super::{App, MessageBlock};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+/// Slowest calls listed by `/stats`.
+const SLOWEST_SHOWN: usize = 10;
+/// Upper bounds of the histogram buckets; the last bucket is open.
+const BUCKETS: [(Duration, &str); 4] = [
+    (Duration::from_secs(1), "<1s"),
+    (Duration::from_secs(5), "1-5s"),
+    (Duration::from_secs(30), "5-30s"),
+    (Duration::from_secs(120), "30s-2m"),
+];
+const LAST_BUCKET: &str = ">2m";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolTiming {
+    /// SDK tool name, e.g. `Bash` or `Read`.
+    pub tool: String,
+    pub title: String,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct ToolTimings {
+    /// Calls still running, by tool call id.
+    started: HashMap<String, Instant>,
+    pub finished: Vec<ToolTiming>,
+}
+
+impl ToolTimings {
+    pub fn clear(&mut self) {
+        self.started.clear();
+        self.finished.clear();
+    }
+}
+
+/// Note when a tool call first shows up.
+pub(crate) fn started(app: &mut App, id: &str, now: Instant) {
+    app.tool_timings.started.entry(id.to_owned()).or_insert(now);
+}
+
+/// Record the duration of a tool call that just completed or failed.
+pub(crate) fn finished(app: &mut App, id: &str, now: Instant) {
+    let Some(start) = app.tool_timings.started.remove(id) else {
+        return;
+    };
+    let Some(MessageBlock::ToolCall(tc)) = app
+        .lookup_tool_call(id)
+        .and_then(|(mi, bi)| app.messages.get(mi).and_then(|message| message.blocks.get(bi)))
+    else {
+        return;
+    };
+    let timing = ToolTiming {
+        tool: tc.sdk_tool_name.clone(),
+        title: tc.terminal_command.clone().unwrap_or_else(|| tc.title.clone()),
+        duration: now.saturating_duration_since(start),
+    };
+    app.tool_timings.finished.push(timing);
+}
+
+/// `850ms`, `4.2s`, `3m 05s`.
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    match millis {
+        0..1_000 => format!("{millis}ms"),
+        1_000..60_000 => format!("{:.1}s", duration.as_secs_f64()),
+        _ => format!("{}m {:02}s", millis / 60_000, millis / 1_000 % 60),
+    }
+}
+
+fn bucket(duration: Duration) -> usize {
+    BUCKETS.iter().position(|&(bound, _)| duration < bound).unwrap_or(BUCKETS.len())
+}
+
+/// One row per tool, most total time first, with call counts per bucket.
+fn histogram(timings: &[ToolTiming]) -> String {
+    let mut by_tool: BTreeMap<&str, Vec<Duration>> = BTreeMap::new();
+    for timing in timings {
+        by_tool.entry(&timing.tool).or_default().push(timing.duration);
+    }
+    let mut rows: Vec<(&str, Vec<Duration>)> = by_tool.into_iter().collect();
+    rows.sort_by_key(|(_, durations)| std::cmp::Reverse(durations.iter().sum::<Duration>()));
+
+    let name_width = rows.iter().map(|(tool, _)| tool.len()).max().unwrap_or(0).max(4);
+    let labels: Vec<&str> =
+        BUCKETS.iter().map(|&(_, label)| label).chain(std::iter::once(LAST_BUCKET)).collect();
+    let mut out = format!("{:name_width$}  calls     total    median", "tool");
+    for label in &labels {
+        let _ = write!(out, " {label:>6}");
+    }
+    for (tool, mut durations) in rows {
+        durations.sort();
+        let total: Duration = durations.iter().sum();
+        let median = durations[durations.len() / 2];
+        let mut counts = vec![0usize; labels.len()];
+        for &duration in &durations {
+            counts[bucket(duration)] += 1;
+        }
+        let _ = write!(
+            out,
+            "\n{tool:name_width$}  {:>5} {:>9} {:>9}",
+            durations.len(),
+            format_duration(total),
+            format_duration(median)
+        );
+        for count in counts {
+            let _ = write!(out, " {count:>6}");
+        }
+    }
+    out
+}
+
+/// Markdown report of the session's tool call timings for `/stats`.
+#[must_use]
+pub fn report(timings: &ToolTimings) -> String {
+    let finished = &timings.finished;
+    if finished.is_empty() {
+        return "No finished tool calls in this session yet.".to_owned();
+    }
+    let total: Duration = finished.iter().map(|timing| timing.duration).sum();
+    let mut out = format!(
+        "**Tool call timings:** {} calls, {} in tools\n\n```text\n{}\n```\n\nSlowest:",
+        finished.len(),
+        format_duration(total),
+        histogram(finished)
+    );
+    let mut slowest: Vec<&ToolTiming> = finished.iter().collect();
+    slowest.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+    for (number, timing) in slowest.iter().take(SLOWEST_SHOWN).enumerate() {
+        let title = timing.title.lines().next().unwrap_or_default();
+        let _ = write!(
+            out,
+            "\n{}. **{}** - {} `{title}`",
+            number + 1,
+            format_duration(timing.duration),
+            timing.tool
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn timing(tool: &str, title: &str, millis: u64) -> ToolTiming {
+        ToolTiming {
+            tool: tool.to_owned(),
+            title: title.to_owned(),
+            duration: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn durations_fall_into_buckets_and_format_by_size() {
+        assert_eq!(bucket(Duration::from_millis(999)), 0);
+        assert_eq!(bucket(Duration::from_secs(5)), 2);
+        assert_eq!(bucket(Duration::from_secs(600)), 4);
+        assert_eq!(format_duration(Duration::from_millis(850)), "850ms");
+        assert_eq!(format_duration(Duration::from_millis(4_250)), "4.2s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m 05s");
+    }
+
+    #[test]
+    fn report_ranks_tools_by_total_time_and_lists_the_slowest() {
+        let timings = ToolTimings {
+            started: HashMap::new(),
+            finished: vec![
+                timing("Read", "src/main.rs", 40),
+                timing("Bash", "cargo test", 48_000),
+                timing("Read", "src/lib.rs", 60),
+                timing("Bash", "ls", 300),
+            ],
+        };
+        let text = report(&timings);
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows[0], "**Tool call timings:** 4 calls, 48.4s in tools");
+        assert!(rows[4].starts_with("Bash      2     48.3s     48.0s      1      0"));
+        assert!(rows[5].starts_with("Read      2     100ms      60ms      2"));
+        assert!(text.contains("Slowest:\n1. **48.0s** - Bash `cargo test`\n2. **300ms**"));
+        assert_eq!(report(&ToolTimings::default()), "No finished tool calls in this session yet.");
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plain markdown transcript of the conversation, printed to stdout after the
+//! TUI leaves the alternate screen when `--print-transcript` or `/transcript`
+//! asked for it, so the session stays in the terminal scrollback.
+
+use super::{App, MessageBlock, MessageRole, SystemSeverity};
+use crate::ui::theme;
+use std::fmt::Write as _;
+
+/// The conversation as markdown, or `None` when nothing was said.
+#[must_use]
+pub fn transcript_markdown(app: &App) -> Option<String> {
+    let mut out = String::new();
+    for message in &app.messages {
+        let mut section = match message.role {
+            MessageRole::User => user_section(&message.blocks),
+            MessageRole::Assistant => assistant_section(&message.blocks),
+            MessageRole::System(severity) => system_section(&message.blocks, severity),
+            MessageRole::Welcome => continue,
+        };
+        if let Some(note) = &message.annotation {
+            if !section.is_empty() {
+                section.push_str("\n\n");
+            }
+            let _ = write!(section, "_Note: {note}_");
+        }
+        if section.is_empty() {
+            continue;
+        }
+        out.push_str(&section);
+        out.push_str("\n\n");
+    }
+    let out = out.trim_end();
+    (!out.is_empty()).then(|| out.to_owned())
+}
+
+fn text_blocks(blocks: &[MessageBlock]) -> impl Iterator<Item = &str> {
+    blocks.iter().filter_map(|block| match block {
+        MessageBlock::Text(text) => Some(text.text.trim()).filter(|text| !text.is_empty()),
+        _ => None,
+    })
+}
+
+fn user_section(blocks: &[MessageBlock]) -> String {
+    let text = text_blocks(blocks).collect::<Vec<_>>().join("\n\n");
+    text.lines()
+        .map(|line| if line.is_empty() { ">".to_owned() } else { format!("> {line}") })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn assistant_section(blocks: &[MessageBlock]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut tools = String::new();
+    for block in blocks {
+        match block {
+            MessageBlock::Text(text) if !text.text.trim().is_empty() => {
+                if !tools.is_empty() {
+                    parts.push(std::mem::take(&mut tools).trim_end().to_owned());
+                }
+                parts.push(text.text.trim().to_owned());
+            }
+            MessageBlock::ToolCall(tc) if !tc.hidden => {
+                let (_, label) = theme::tool_name_label(&tc.sdk_tool_name);
+                let status = super::screen_reader::status_label(tc.status);
+                let _ = writeln!(tools, "- {status} {label}: {}", tc.title.trim());
+            }
+            _ => {}
+        }
+    }
+    if !tools.is_empty() {
+        parts.push(tools.trim_end().to_owned());
+    }
+    parts.join("\n\n")
+}
+
+fn system_section(blocks: &[MessageBlock], severity: Option<SystemSeverity>) -> String {
+    let prefix = match severity {
+        Some(SystemSeverity::Warning) => "Warning: ",
+        Some(SystemSeverity::Error) => "Error: ",
+        Some(SystemSeverity::Info) | None => "",
+    };
+    let text = text_blocks(blocks).collect::<Vec<_>>().join("\n");
+    if text.is_empty() { text } else { format!("_{prefix}{text}_") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    fn message(role: MessageRole, text: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn transcript_quotes_prompts_and_keeps_replies_as_markdown() {
+        let mut app = App::test_default();
+        assert_eq!(transcript_markdown(&app), None);
+
+        app.messages.push(message(MessageRole::User, "fix the build\n\nplease"));
+        app.messages.push(message(MessageRole::Assistant, "Done. See `main.rs`."));
+        app.messages.push(message(MessageRole::System(Some(SystemSeverity::Warning)), "Slow"));
+
+        assert_eq!(
+            transcript_markdown(&app).as_deref(),
+            Some("> fix the build\n>\n> please\n\nDone. See `main.rs`.\n\n_Warning: Slow_")
+        );
+
+        app.messages[1].annotation = Some("worked first try".to_owned());
+        assert_eq!(
+            transcript_markdown(&app).as_deref(),
+            Some(
+                "> fix the build\n>\n> please\n\nDone. See `main.rs`.\n\n\
+                 _Note: worked first try_\n\n_Warning: Slow_"
+            )
+        );
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transcript filter bar. `\` (on an empty input, or in keyboard selection
+//! mode) opens it; Enter applies the query and hides every message that does
+//! not match, Enter on an empty query clears the filter. Queries combine
+//! structured terms with a regex over the remaining words:
+//!
+//! - `role:user|assistant|system`
+//! - `tool:<name>`, e.g. `tool:bash`, matched against the tool name
+//! - `status:failed|running|done|pending`, e.g. `status:failed` for failures only
+//! - anything else is a case-insensitive regex over message text, tool
+//!   titles, commands and notes
+//!
+//! Hidden messages measure as zero rows, so the chat layout and scrolling
+//! work on the filtered transcript; the bar stays visible while a filter is on.
+
+use super::line_input::LineInput;
+use super::{App, ChatMessage, InvalidationLevel, MessageBlock, MessageRole, ToolCallInfo};
+use crate::agent::model::ToolCallStatus;
+use crossterm::event::{KeyCode, KeyEvent};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoleFilter {
+    User,
+    Assistant,
+    System,
+}
+
+#[derive(Debug, Clone)]
+pub struct TranscriptFilter {
+    /// The query as typed, shown in the bar.
+    pub query: String,
+    role: Option<RoleFilter>,
+    tool: Option<String>,
+    status: Option<ToolCallStatus>,
+    pattern: Option<Regex>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FilterEditor {
+    pub input: LineInput,
+    pub error: Option<String>,
+}
+
+impl TranscriptFilter {
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut filter = Self {
+            query: query.trim().to_owned(),
+            role: None,
+            tool: None,
+            status: None,
+            pattern: None,
+        };
+        let mut words = Vec::new();
+        for word in query.split_whitespace() {
+            match word.split_once(':') {
+                Some(("role", value)) => filter.role = Some(parse_role(value)?),
+                Some(("tool", value)) if !value.is_empty() => {
+                    filter.tool = Some(value.to_lowercase());
+                }
+                Some(("status", value)) => filter.status = Some(parse_status(value)?),
+                _ => words.push(word),
+            }
+        }
+        if !words.is_empty() {
+            let pattern = words.join(" ");
+            filter.pattern = Some(
+                RegexBuilder::new(&pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|err| format!("Invalid regex: {err}"))?,
+            );
+        }
+        Ok(filter)
+    }
+
+    /// Whether `message` stays visible under this filter.
+    #[must_use]
+    pub fn matches(&self, message: &ChatMessage) -> bool {
+        let role_ok = match (self.role, &message.role) {
+            (_, MessageRole::Welcome) => return false,
+            (None, _)
+            | (Some(RoleFilter::User), MessageRole::User)
+            | (Some(RoleFilter::Assistant), MessageRole::Assistant)
+            | (Some(RoleFilter::System), MessageRole::System(_)) => true,
+            _ => false,
+        };
+        if !role_ok {
+            return false;
+        }
+        if (self.tool.is_some() || self.status.is_some())
+            && !tool_calls(message).any(|tc| self.tool_matches(tc))
+        {
+            return false;
+        }
+        let Some(pattern) = &self.pattern else {
+            return true;
+        };
+        message.annotation.as_deref().is_some_and(|note| pattern.is_match(note))
+            || message.blocks.iter().any(|block| match block {
+                MessageBlock::Text(text) => pattern.is_match(&text.text),
+                MessageBlock::ToolCall(tc) if !tc.hidden => {
+                    pattern.is_match(&tc.title)
+                        || tc.terminal_command.as_deref().is_some_and(|cmd| pattern.is_match(cmd))
+                }
+                _ => false,
+            })
+    }
+
+    fn tool_matches(&self, tc: &ToolCallInfo) -> bool {
+        self.tool.as_deref().is_none_or(|tool| tc.sdk_tool_name.to_lowercase().contains(tool))
+            && self.status.is_none_or(|status| tc.status == status)
+    }
+}
+
+fn tool_calls(message: &ChatMessage) -> impl Iterator<Item = &ToolCallInfo> {
+    message.blocks.iter().filter_map(|block| match block {
+        MessageBlock::ToolCall(tc) if !tc.hidden => Some(tc.as_ref()),
+        _ => None,
+    })
+}
+
+fn parse_role(value: &str) -> Result<RoleFilter, String> {
+    match value {
+        "user" | "you" => Ok(RoleFilter::User),
+        "assistant" | "claude" => Ok(RoleFilter::Assistant),
+        "system" => Ok(RoleFilter::System),
+        _ => Err(format!("Unknown role `{value}`; use user, assistant or system.")),
+    }
+}
+
+fn parse_status(value: &str) -> Result<ToolCallStatus, String> {
+    match value {
+        "failed" | "error" => Ok(ToolCallStatus::Failed),
+        "running" | "in-progress" => Ok(ToolCallStatus::InProgress),
+        "done" | "completed" => Ok(ToolCallStatus::Completed),
+        "pending" => Ok(ToolCallStatus::Pending),
+        _ => Err(format!("Unknown status `{value}`; use failed, running, done or pending.")),
+    }
+}
+
+/// Whether message `index` is shown; always true without a filter.
+#[must_use]
+pub fn is_visible(app: &App, index: usize) -> bool {
+    match (&app.transcript_filter, app.messages.get(index)) {
+        (Some(filter), Some(message)) => filter.matches(message),
+        _ => true,
+    }
+}
+
+/// Open the filter bar, pre-filled with the active query.
+pub(crate) fn open(app: &mut App) {
+    let query = app.transcript_filter.as_ref().map(|filter| filter.query.clone());
+    app.filter_editor =
+        Some(FilterEditor { input: LineInput::new(query.unwrap_or_default()), error: None });
+    app.needs_redraw = true;
+}
+
+pub(crate) fn handle_key(app: &mut App, key: KeyEvent) {
+    let Some(editor) = app.filter_editor.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => app.filter_editor = None,
+        KeyCode::Enter => {
+            let query = editor.input.text.trim().to_owned();
+            if query.is_empty() {
+                app.filter_editor = None;
+                set_filter(app, None);
+                return;
+            }
+            match TranscriptFilter::parse(&query) {
+                Ok(filter) => {
+                    app.filter_editor = None;
+                    set_filter(app, Some(filter));
+                }
+                Err(err) => editor.error = Some(err),
+            }
+        }
+        _ => {
+            if editor.input.handle_key(key) {
+                editor.error = None;
+            }
+        }
+    }
+}
+
+fn set_filter(app: &mut App, filter: Option<TranscriptFilter>) {
+    if app.transcript_filter.is_none() && filter.is_none() {
+        return;
+    }
+    app.transcript_filter = filter;
+    app.viewport.reading_anchor = None;
+    app.viewport.engage_auto_scroll();
+    app.invalidate_layout(InvalidationLevel::Global);
+    app.needs_redraw = true;
+}
+
+/// Messages shown under the active filter, and the total.
+#[must_use]
+pub fn shown_counts(app: &App) -> (usize, usize) {
+    let total = app.messages.iter().filter(|m| !matches!(m.role, MessageRole::Welcome)).count();
+    let shown = match &app.transcript_filter {
+        Some(filter) => app.messages.iter().filter(|m| filter.matches(m)).count(),
+        None => total,
+    };
+    (shown, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TextBlock;
+
+    fn message(role: MessageRole, text: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn structured_terms_and_regex_combine() {
+        let user = message(MessageRole::User, "Fix the parser");
+        let reply = message(MessageRole::Assistant, "The lexer was wrong");
+
+        let filter = TranscriptFilter::parse("role:user pars(er|ing)").expect("parse");
+        assert!(filter.matches(&user));
+        assert!(!filter.matches(&reply));
+
+        let filter = TranscriptFilter::parse("LEXER").expect("parse");
+        assert!(filter.matches(&reply));
+
+        let filter = TranscriptFilter::parse("status:failed").expect("parse");
+        assert!(!filter.matches(&reply));
+        assert!(!filter.matches(&ChatMessage::welcome("model", "/repo")));
+    }
+
+    #[test]
+    fn bad_queries_report_errors() {
+        assert!(TranscriptFilter::parse("role:robot").is_err());
+        assert!(TranscriptFilter::parse("status:weird").is_err());
+        assert!(
+            TranscriptFilter::parse("(unclosed").is_err_and(|err| err.starts_with("Invalid regex"))
+        );
+    }
+}
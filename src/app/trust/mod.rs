@@ -95,6 +95,82 @@ pub fn accept(app: &mut App) -> Result<(), String> {
     Ok(())
 }
 
+/// `/trust` summary: whether the current project is trusted and which roots
+/// are trusted wholesale.
+#[must_use]
+pub fn describe(app: &App) -> String {
+    let document = &app.config.committed_preferences_document;
+    let status = match store::trusted_root_for(document, &app.trust.project_key) {
+        Some(root) => format!("{} is trusted via root {root}.", app.trust.project_key),
+        None if app.trust.is_trusted() => format!("{} is trusted.", app.trust.project_key),
+        None => format!("{} is not trusted.", app.trust.project_key),
+    };
+    let roots = store::trusted_roots(document);
+    if roots.is_empty() {
+        return format!(
+            "{status}\nNo trusted roots. Use /trust add [path] to trust a folder and everything in it."
+        );
+    }
+    let mut lines = vec![status, "Trusted roots:".to_owned()];
+    lines.extend(roots.iter().map(|root| format!("  {root}")));
+    lines.push("Use /trust remove <path> to stop trusting a root.".to_owned());
+    lines.join("\n")
+}
+
+/// Trust `path` (the current directory when `None`) and every directory below it.
+pub fn add_root(app: &mut App, path: Option<&str>) -> Result<String, String> {
+    let root = path.map_or_else(
+        || std::path::PathBuf::from(&app.cwd_raw),
+        |path| resolve_user_path(&app.cwd_raw, path),
+    );
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", root.display()));
+    }
+    let mut next_document = app.config.committed_preferences_document.clone();
+    let (key, added) = store::add_trusted_root(&mut next_document, &root);
+    if !added {
+        return Ok(format!("{key} is already a trusted root."));
+    }
+    save_preferences(app, next_document)?;
+    Ok(format!("Trusted {key} and everything below it."))
+}
+
+pub fn remove_root(app: &mut App, path: &str) -> Result<String, String> {
+    let root = resolve_user_path(&app.cwd_raw, path);
+    let mut next_document = app.config.committed_preferences_document.clone();
+    let removed = store::remove_trusted_root(&mut next_document, &root.to_string_lossy())
+        .ok_or_else(|| format!("{} is not a trusted root", root.display()))?;
+    save_preferences(app, next_document)?;
+    let note = if store::read_status(
+        &app.config.committed_preferences_document,
+        std::path::Path::new(&app.cwd_raw),
+    )
+    .trusted
+    {
+        ""
+    } else {
+        " This project is no longer trusted and will ask again on next launch."
+    };
+    Ok(format!("Removed trusted root {removed}.{note}"))
+}
+
+fn resolve_user_path(cwd: &str, path: &str) -> std::path::PathBuf {
+    let expanded = match path.strip_prefix("~/").or_else(|| (path == "~").then_some("")) {
+        Some(rest) => dirs::home_dir().map_or_else(|| path.into(), |home| home.join(rest)),
+        None => std::path::PathBuf::from(path),
+    };
+    if expanded.is_absolute() { expanded } else { std::path::Path::new(cwd).join(expanded) }
+}
+
+fn save_preferences(app: &mut App, document: serde_json::Value) -> Result<(), String> {
+    let Some(path) = app.config.preferences_path.clone() else {
+        return Err("Trust preferences path is not available".to_owned());
+    };
+    crate::app::config::store::save(&path, &document)?;
+    app.config.committed_preferences_document = document;
+    Ok(())
+}
+
 pub fn decline(app: &mut App) {
     app.should_quit = true;
 }
@@ -217,4 +293,28 @@ mod tests {
 
         assert!(app.should_quit);
     }
+
+    #[test]
+    fn add_and_remove_root_persist_trusted_roots() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("work");
+        std::fs::create_dir_all(root.join("project")).expect("create project");
+        let prefs = dir.path().join("prefs.json");
+        let mut app = App::test_default();
+        app.cwd_raw = root.join("project").to_string_lossy().into_owned();
+        app.config.preferences_path = Some(prefs.clone());
+        app.config.committed_preferences_document = json!({});
+
+        add_root(&mut app, Some(&root.to_string_lossy())).expect("add root");
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&prefs).expect("read prefs"))
+                .expect("parse prefs");
+        assert_eq!(store::trusted_roots(&saved).len(), 1);
+        assert!(store::read_status(&saved, std::path::Path::new(&app.cwd_raw)).trusted);
+
+        let message = remove_root(&mut app, &root.to_string_lossy()).expect("remove root");
+        assert!(message.contains("no longer trusted"), "{message}");
+        assert!(store::trusted_roots(&app.config.committed_preferences_document).is_empty());
+        assert!(remove_root(&mut app, &root.to_string_lossy()).is_err());
+    }
 }
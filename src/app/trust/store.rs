@@ -3,6 +3,8 @@ use std::path::{Path, PathBuf};
 
 const TRUST_FIELD: &str = "hasTrustDialogAccepted";
 const PROJECTS_FIELD: &str = "projects";
+/// Directories whose whole subtree is trusted, managed with `/trust`.
+const TRUSTED_ROOTS_FIELD: &str = "trustedRoots";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TrustLookup {
@@ -17,11 +19,73 @@ pub fn read_status(document: &Value, project_root: &Path) -> TrustLookup {
         projects.iter().any(|(key, value)| {
             project_keys_match(key, &project_key) && trust_value(value).unwrap_or(false)
         })
-    });
+    }) || trusted_root_for(document, &project_key).is_some();
 
     TrustLookup { project_key, trusted }
 }
 
+#[must_use]
+pub fn trusted_roots(document: &Value) -> Vec<String> {
+    document
+        .get(TRUSTED_ROOTS_FIELD)
+        .and_then(Value::as_array)
+        .map(|roots| roots.iter().filter_map(Value::as_str).map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// The trusted root containing `project_key`, if any.
+#[must_use]
+pub fn trusted_root_for(document: &Value, project_key: &str) -> Option<String> {
+    trusted_roots(document).into_iter().find(|root| {
+        let root = normalize_project_key_string(root);
+        let prefix = if root.ends_with('/') { root.clone() } else { format!("{root}/") };
+        same_os_path_key(&root, project_key)
+            || project_key.get(..prefix.len()).is_some_and(|head| same_os_path_key(head, &prefix))
+    })
+}
+
+/// Trust `root` and everything below it. Returns the normalized key and
+/// whether it was newly added.
+pub fn add_trusted_root(document: &mut Value, root: &Path) -> (String, bool) {
+    let key = normalize_project_key(root);
+    let mut roots = trusted_roots(document);
+    let added = !roots
+        .iter()
+        .any(|existing| same_os_path_key(&normalize_project_key_string(existing), &key));
+    if added {
+        roots.push(key.clone());
+        write_trusted_roots(document, roots);
+    }
+    (key, added)
+}
+
+/// Remove the trusted root matching `root`. Returns the removed entry.
+pub fn remove_trusted_root(document: &mut Value, root: &str) -> Option<String> {
+    let key = normalize_project_key_string(root);
+    let canonical = canonical_project_key(Path::new(root));
+    let mut roots = trusted_roots(document);
+    let index = roots.iter().position(|existing| {
+        let existing = normalize_project_key_string(existing);
+        same_os_path_key(&existing, &key)
+            || canonical.as_deref().is_some_and(|canonical| same_os_path_key(&existing, canonical))
+    })?;
+    let removed = roots.remove(index);
+    write_trusted_roots(document, roots);
+    Some(removed)
+}
+
+fn write_trusted_roots(document: &mut Value, roots: Vec<String>) {
+    let root = ensure_object_mut(document);
+    if roots.is_empty() {
+        root.remove(TRUSTED_ROOTS_FIELD);
+    } else {
+        root.insert(
+            TRUSTED_ROOTS_FIELD.to_owned(),
+            Value::Array(roots.into_iter().map(Value::String).collect()),
+        );
+    }
+}
+
 pub fn set_trusted(document: &mut Value, project_root: &Path) -> String {
     let project_key = normalize_project_key(project_root);
     let root = ensure_object_mut(document);
@@ -219,6 +283,27 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[cfg(not(windows))]
+    #[test]
+    fn trusted_root_covers_descendants_but_not_siblings() {
+        let mut document = json!({ "projects": {} });
+        let (key, added) = add_trusted_root(&mut document, Path::new("/home/user/work"));
+        assert_eq!(key, "/home/user/work");
+        assert!(added);
+        assert!(!add_trusted_root(&mut document, Path::new("/home/user/work/")).1);
+
+        assert!(read_status(&document, Path::new("/home/user/work")).trusted);
+        assert!(read_status(&document, Path::new("/home/user/work/project/sub")).trusted);
+        assert!(!read_status(&document, Path::new("/home/user/workspace")).trusted);
+
+        assert_eq!(
+            remove_trusted_root(&mut document, "/home/user/work/"),
+            Some("/home/user/work".to_owned())
+        );
+        assert!(trusted_roots(&document).is_empty());
+        assert!(document.get(TRUSTED_ROOTS_FIELD).is_none());
+    }
+
     #[cfg(windows)]
     #[test]
     fn read_status_accepts_equivalent_backslash_entry() {
@@ -0,0 +1,265 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Folding whole turns. A folded turn (the user prompt, the reply and its tool
+//! calls, up to the next prompt) shows as a single summary row such as
+//! "Turn 12: Fix login bug - 3 edits, 2 commands". `z` in keyboard selection
+//! mode (Ctrl+s) toggles the turn under the cursor, `Z` and `/fold` collapse
+//! every turn before the latest one, and `/fold off` expands them all.
+
+use super::toast::{ToastKind, show_toast};
+use super::{App, InvalidationLevel, MessageBlock, MessageRole};
+use crate::ui::width::truncate_with_ellipsis;
+
+/// Longest prompt excerpt in a summary row, in terminal cells.
+const MAX_EXCERPT_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldState {
+    Open,
+    /// First message of a folded turn; renders as the summary row.
+    Summary,
+    /// Any later message of a folded turn.
+    Hidden,
+}
+
+/// How message `index` renders given the folded turns.
+#[must_use]
+pub fn fold_state(app: &App, index: usize) -> FoldState {
+    let Some(&start) = app.folded_turns.range(..=index).next_back() else {
+        return FoldState::Open;
+    };
+    if start == index {
+        return FoldState::Summary;
+    }
+    let next_turn = app.messages[start + 1..=index.min(app.messages.len().saturating_sub(1))]
+        .iter()
+        .any(|message| matches!(message.role, MessageRole::User));
+    if next_turn { FoldState::Open } else { FoldState::Hidden }
+}
+
+/// Index of the user message that starts the turn containing `index`.
+#[must_use]
+pub fn turn_start(app: &App, index: usize) -> Option<usize> {
+    let last = index.min(app.messages.len().checked_sub(1)?);
+    (0..=last).rev().find(|&i| matches!(app.messages[i].role, MessageRole::User))
+}
+
+/// First non-empty line of the prompt that starts the turn at `start`.
+#[must_use]
+pub fn prompt_line(app: &App, start: usize) -> &str {
+    app.messages
+        .get(start)
+        .and_then(|message| {
+            message.blocks.iter().find_map(|block| match block {
+                MessageBlock::Text(text) => {
+                    text.text.lines().map(str::trim).find(|line| !line.is_empty())
+                }
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Summary row text for the folded turn starting at `start`.
+#[must_use]
+pub fn summary(app: &App, start: usize) -> String {
+    let number = app.messages[..=start]
+        .iter()
+        .filter(|message| matches!(message.role, MessageRole::User))
+        .count();
+    let (mut edits, mut commands, mut others) = (0usize, 0usize, 0usize);
+    for message in &app.messages[start..turn_end(app, start)] {
+        for block in &message.blocks {
+            let MessageBlock::ToolCall(tc) = block else {
+                continue;
+            };
+            if tc.hidden {
+                continue;
+            }
+            match tc.sdk_tool_name.as_str() {
+                "Write" | "Edit" | "MultiEdit" | "NotebookEdit" => edits += 1,
+                "Bash" => commands += 1,
+                _ => others += 1,
+            }
+        }
+    }
+    let counts: Vec<String> = [(edits, "edit"), (commands, "command"), (others, "other tool")]
+        .into_iter()
+        .filter(|&(count, _)| count > 0)
+        .map(|(count, noun)| format!("{count} {noun}{}", if count == 1 { "" } else { "s" }))
+        .collect();
+    let excerpt = truncate_with_ellipsis(prompt_line(app, start), MAX_EXCERPT_WIDTH);
+    if counts.is_empty() {
+        format!("Turn {number}: {excerpt}")
+    } else {
+        format!("Turn {number}: {excerpt} - {}", counts.join(", "))
+    }
+}
+
+/// Index one past the last message of the turn starting at `start`.
+fn turn_end(app: &App, start: usize) -> usize {
+    (start + 1..app.messages.len())
+        .find(|&i| matches!(app.messages[i].role, MessageRole::User))
+        .unwrap_or(app.messages.len())
+}
+
+/// Fold or unfold the turn under the selection cursor. Returns false when no
+/// turn starts at or above the cursor.
+pub(crate) fn toggle_at_visual_cursor(app: &mut App) -> bool {
+    let Some(visual) = app.chat_visual else {
+        return false;
+    };
+    if app.viewport.height_prefix_sums.is_empty() {
+        return false;
+    }
+    let content_row = app.viewport.scroll_offset + visual.cursor.row;
+    let message_index = app.viewport.find_first_visible(content_row);
+    let Some(start) = turn_start(app, message_index) else {
+        return false;
+    };
+    if !app.folded_turns.remove(&start) {
+        app.folded_turns.insert(start);
+    }
+    app.invalidate_layout(InvalidationLevel::From(start));
+    app.viewport.auto_scroll = false;
+    app.viewport.restore_scroll_anchor(start, 0);
+    app.needs_redraw = true;
+    true
+}
+
+/// Collapse every turn before the latest one; when they already are, expand
+/// them all instead.
+pub(crate) fn toggle_previous(app: &mut App) {
+    let starts = previous_turn_starts(app);
+    if !starts.is_empty() && starts.iter().all(|start| app.folded_turns.contains(start)) {
+        expand_all(app);
+    } else {
+        fold_previous(app);
+    }
+}
+
+fn previous_turn_starts(app: &App) -> Vec<usize> {
+    let mut starts: Vec<usize> = (0..app.messages.len())
+        .filter(|&i| matches!(app.messages[i].role, MessageRole::User))
+        .collect();
+    starts.pop();
+    starts
+}
+
+fn fold_previous(app: &mut App) {
+    let starts = previous_turn_starts(app);
+    if starts.is_empty() {
+        show_toast(app, ToastKind::Info, "No earlier turns to fold");
+        return;
+    }
+    let count = starts.len();
+    app.folded_turns.extend(starts);
+    refresh(app);
+    show_toast(
+        app,
+        ToastKind::Info,
+        format!("Folded {count} earlier turn{}", if count == 1 { "" } else { "s" }),
+    );
+}
+
+fn expand_all(app: &mut App) {
+    if app.folded_turns.is_empty() {
+        show_toast(app, ToastKind::Info, "No turns are folded");
+        return;
+    }
+    app.folded_turns.clear();
+    refresh(app);
+    show_toast(app, ToastKind::Info, "Expanded all turns");
+}
+
+fn refresh(app: &mut App) {
+    app.viewport.reading_anchor = None;
+    app.invalidate_layout(InvalidationLevel::From(0));
+    app.viewport.engage_auto_scroll();
+    app.needs_redraw = true;
+}
+
+pub(crate) fn start(app: &mut App, args: &[&str]) -> Result<(), String> {
+    match args {
+        [] => fold_previous(app),
+        ["off" | "none" | "expand"] => expand_all(app),
+        _ => return Err("Usage: /fold [off]".to_owned()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::model;
+    use crate::app::{BlockCache, ChatMessage, TextBlock, ToolCallInfo};
+    use pretty_assertions::assert_eq;
+
+    fn message(role: MessageRole, text: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    fn tool(name: &str) -> MessageBlock {
+        MessageBlock::ToolCall(Box::new(ToolCallInfo {
+            id: format!("tc-{name}"),
+            title: name.to_owned(),
+            sdk_tool_name: name.to_owned(),
+            raw_input: None,
+            output_metadata: None,
+            web_output: None,
+            status: model::ToolCallStatus::Completed,
+            content: Vec::new(),
+            collapsed: false,
+            hidden: false,
+            terminal_id: None,
+            terminal_command: None,
+            terminal_env: Vec::new(),
+            terminal_output: None,
+            terminal_output_len: 0,
+            terminal_bytes_seen: 0,
+            terminal_snapshot_mode: crate::app::TerminalSnapshotMode::AppendOnly,
+            render_epoch: 0,
+            layout_epoch: 0,
+            last_measured_width: 0,
+            last_measured_height: 0,
+            last_measured_layout_epoch: 0,
+            last_measured_layout_generation: 0,
+            cache: BlockCache::default(),
+            pending_permission: None,
+            pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
+        }))
+    }
+
+    #[test]
+    fn folded_turn_summarizes_and_hides_its_messages() {
+        let mut app = App::test_default();
+        let mut reply = message(MessageRole::Assistant, "fixed");
+        reply.blocks.extend([tool("Edit"), tool("Edit"), tool("Bash"), tool("Read")]);
+        app.messages = vec![
+            message(MessageRole::User, "Fix login bug\nit 500s"),
+            reply,
+            message(MessageRole::User, "thanks"),
+            message(MessageRole::Assistant, "welcome"),
+        ];
+
+        toggle_previous(&mut app);
+        assert_eq!(app.folded_turns.iter().copied().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(fold_state(&app, 0), FoldState::Summary);
+        assert_eq!(fold_state(&app, 1), FoldState::Hidden);
+        assert_eq!(fold_state(&app, 2), FoldState::Open);
+        assert_eq!(fold_state(&app, 3), FoldState::Open);
+        assert_eq!(summary(&app, 0), "Turn 1: Fix login bug - 2 edits, 1 command, 1 other tool");
+
+        toggle_previous(&mut app);
+        assert!(app.folded_turns.is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Automatic retries for turns that fail with a rate-limit or overload error.
+//! The prompt is re-sent after an exponential backoff (or the wait the server
+//! asked for) while the footer counts down; Esc or a new prompt stops it.
+
+use super::events::push_system_message_with_severity;
+use super::{App, AppStatus, SystemSeverity};
+use crate::agent::error_handling::{TransientError, TransientErrorKind};
+use std::time::{Duration, Instant};
+
+/// Retries of one prompt before the error is shown as a normal turn error.
+pub const MAX_TURN_RETRIES: u32 = 4;
+
+/// Upper bound on any single wait, including server-suggested ones.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnRetry {
+    /// Retries scheduled so far for this prompt (1-based).
+    pub attempt: u32,
+    pub kind: TransientErrorKind,
+    pub prompt: String,
+    /// When the prompt is re-sent. `None` while the retried turn is running.
+    pub next_attempt_at: Option<Instant>,
+}
+
+/// Delay before retry `attempt` (1-based): 2s, 4s, 8s, ... unless the server
+/// suggested one.
+#[must_use]
+pub fn retry_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    retry_after
+        .unwrap_or_else(|| Duration::from_secs(2 << attempt.saturating_sub(1).min(5)))
+        .min(MAX_RETRY_DELAY)
+}
+
+/// Schedule another try of the last prompt. Returns `false` when there is
+/// nothing to retry or the retries are used up.
+pub(crate) fn schedule_retry(app: &mut App, error: TransientError) -> bool {
+    let attempt = app.turn_retry.as_ref().map_or(0, |retry| retry.attempt) + 1;
+    let Some(prompt) = app.last_prompt.clone() else {
+        return false;
+    };
+    if attempt > MAX_TURN_RETRIES {
+        app.turn_retry = None;
+        return false;
+    }
+    let delay = retry_delay(attempt, error.retry_after);
+    app.turn_retry = Some(TurnRetry {
+        attempt,
+        kind: error.kind,
+        prompt,
+        next_attempt_at: Some(Instant::now() + delay),
+    });
+    tracing::warn!(attempt, delay_secs = delay.as_secs(), kind = ?error.kind, "scheduling turn retry");
+    let message = format!(
+        "{}. Retrying in {}s (attempt {attempt} of {MAX_TURN_RETRIES}); press Esc to stop.",
+        reason(error.kind),
+        delay.as_secs()
+    );
+    push_system_message_with_severity(app, Some(SystemSeverity::Warning), &message);
+    true
+}
+
+/// Re-send the prompt once its retry is due and no other turn is running.
+pub fn retry_turn_if_due(app: &mut App, now: Instant) {
+    let Some(retry) = app.turn_retry.as_mut() else {
+        return;
+    };
+    if retry.next_attempt_at.is_none_or(|due| now < due) || app.status != AppStatus::Ready {
+        return;
+    }
+    retry.next_attempt_at = None;
+    let prompt = retry.prompt.clone();
+    super::input_submit::retry_prompt(app, prompt);
+}
+
+/// Drop a pending retry. Returns `false` when none was waiting.
+pub(crate) fn cancel_turn_retry(app: &mut App) -> bool {
+    if app.turn_retry.as_ref().is_none_or(|retry| retry.next_attempt_at.is_none()) {
+        return false;
+    }
+    app.turn_retry = None;
+    push_system_message_with_severity(app, Some(SystemSeverity::Info), "Stopped retrying.");
+    true
+}
+
+/// Whether a retry is counting down, so the footer needs redrawing.
+#[must_use]
+pub fn is_waiting(app: &App) -> bool {
+    app.turn_retry.as_ref().is_some_and(|retry| retry.next_attempt_at.is_some())
+}
+
+/// Countdown shown on the right of the footer while a retry is waiting.
+#[must_use]
+pub fn footer_hint(app: &App, now: Instant) -> Option<String> {
+    let retry = app.turn_retry.as_ref()?;
+    let due = retry.next_attempt_at?;
+    let remaining = due.saturating_duration_since(now).as_secs() + 1;
+    Some(format!(
+        "{} \u{b7} retrying in {remaining}s ({}/{MAX_TURN_RETRIES}) \u{b7} Esc to stop",
+        reason(retry.kind),
+        retry.attempt
+    ))
+}
+
+fn reason(kind: TransientErrorKind) -> &'static str {
+    match kind {
+        TransientErrorKind::RateLimited => "Rate limited",
+        TransientErrorKind::Overloaded => "Agent API overloaded",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn delay_doubles_and_honours_capped_server_hints() {
+        assert_eq!(retry_delay(1, None), Duration::from_secs(2));
+        assert_eq!(retry_delay(3, None), Duration::from_secs(8));
+        assert_eq!(retry_delay(10, None), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(1, Some(Duration::from_secs(15))), Duration::from_secs(15));
+        assert_eq!(retry_delay(1, Some(Duration::from_secs(600))), MAX_RETRY_DELAY);
+    }
+}
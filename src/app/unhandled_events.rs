@@ -0,0 +1,115 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bridge events this build could not decode. They are kept as raw JSON for
+//! the `/diagnostics` overlay, so a newer agent degrades to a notice instead
+//! of a dropped connection or a silently missing update.
+
+use super::events::push_system_message_with_severity;
+use super::{App, SystemSeverity};
+use std::collections::{BTreeSet, VecDeque};
+
+/// Events kept before the oldest is dropped.
+pub const MAX_UNHANDLED_EVENTS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnhandledEvent {
+    pub kind: String,
+    pub reason: String,
+    pub raw: String,
+}
+
+#[derive(Debug, Default)]
+pub struct UnhandledEvents {
+    entries: VecDeque<UnhandledEvent>,
+    dropped: usize,
+    /// Kinds already announced in the transcript.
+    seen_kinds: BTreeSet<String>,
+}
+
+impl UnhandledEvents {
+    /// Store an event. Returns `true` the first time its kind shows up.
+    pub fn push(&mut self, event: UnhandledEvent) -> bool {
+        let first = self.seen_kinds.insert(event.kind.clone());
+        if self.entries.len() == MAX_UNHANDLED_EVENTS {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(event);
+        first
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &UnhandledEvent> {
+        self.entries.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Rows the overlay needs: a header, the raw JSON, and a blank separator
+    /// per event.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.entries.iter().map(|event| event.raw.lines().count() + 2).sum()
+    }
+
+    /// Forget the stored events; kinds stay announced.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+}
+
+pub(crate) fn record(app: &mut App, kind: String, reason: String, raw: String) {
+    tracing::warn!(%kind, %reason, "unhandled bridge event");
+    let notice = format!(
+        "Ignored a `{kind}` event this version does not recognize. \
+         /diagnostics (Tab) shows the raw event."
+    );
+    if app.unhandled_events.push(UnhandledEvent { kind, reason, raw }) {
+        push_system_message_with_severity(app, Some(SystemSeverity::Warning), &notice);
+    }
+    app.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn event(kind: &str) -> UnhandledEvent {
+        UnhandledEvent {
+            kind: kind.to_owned(),
+            reason: "unknown variant".to_owned(),
+            raw: "{\n}".to_owned(),
+        }
+    }
+
+    #[test]
+    fn buffer_announces_each_kind_once_and_drops_the_oldest() {
+        let mut events = UnhandledEvents::default();
+        assert!(events.push(event("session_update/hologram")));
+        assert!(!events.push(event("session_update/hologram")));
+        for _ in 0..MAX_UNHANDLED_EVENTS {
+            events.push(event("mystery"));
+        }
+        assert_eq!(events.len(), MAX_UNHANDLED_EVENTS);
+        assert_eq!(events.dropped(), 2);
+        assert_eq!(events.line_count(), MAX_UNHANDLED_EVENTS * 4);
+        events.clear();
+        assert!(events.is_empty());
+        assert!(!events.push(event("mystery")));
+    }
+}
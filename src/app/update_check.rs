@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::App;
+use super::config::UpdateChannel;
+use super::http::{HttpSettings, describe_error};
 use crate::Cli;
 use crate::agent::events::ClientEvent;
 use reqwest::header::{ACCEPT, HeaderMap, HeaderValue, USER_AGENT};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -14,28 +18,115 @@ const UPDATE_CHECK_TTL_SECS: u64 = 24 * 60 * 60;
 const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(4);
 const GITHUB_LATEST_RELEASE_API_URL: &str =
     "https://api.github.com/repos/srothgan/claude-code-rust/releases/latest";
+/// `/releases/latest` never returns prereleases, so the prerelease channel
+/// scans the newest page of the list instead.
+const GITHUB_RELEASES_API_URL: &str =
+    "https://api.github.com/repos/srothgan/claude-code-rust/releases?per_page=30";
 const GITHUB_API_ACCEPT_VALUE: &str = "application/vnd.github+json";
 const GITHUB_API_VERSION_VALUE: &str = "2022-11-28";
 const GITHUB_USER_AGENT_VALUE: &str = "claude-code-rust-update-check";
 const CACHE_FILE: &str = "update-check.json";
+const PRERELEASE_CACHE_FILE: &str = "update-check-prerelease.json";
 const CACHE_DIR_NAME: &str = "claude-code-rust";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct SimpleVersion {
     major: u64,
     minor: u64,
     patch: u64,
+    /// Suffix after `-`, e.g. `rc1`. A prerelease sorts before its release.
+    pre: Option<String>,
+}
+
+impl Ord for SimpleVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(left), Some(right)) => prerelease_key(left).cmp(&prerelease_key(right)),
+            })
+    }
+}
+
+impl PartialOrd for SimpleVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum PrereleasePart {
+    Number(u64),
+    Text(String),
+}
+
+/// Split a prerelease suffix into digit and non-digit runs so `rc10` sorts
+/// after `rc9`.
+fn prerelease_key(pre: &str) -> Vec<PrereleasePart> {
+    let mut parts = Vec::new();
+    let mut rest = pre;
+    while let Some(first) = rest.chars().next() {
+        let digits = first.is_ascii_digit();
+        let end = rest.find(|c: char| c.is_ascii_digit() != digits).unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(end);
+        parts.push(match run.parse() {
+            Ok(number) if digits => PrereleasePart::Number(number),
+            _ => PrereleasePart::Text(run.to_owned()),
+        });
+        rest = tail;
+    }
+    parts
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct UpdateCheckCache {
     checked_at_unix_secs: u64,
     latest_version: String,
+    /// Absent in caches written before release notes were shown.
+    #[serde(default)]
+    release: Option<ReleaseInfo>,
+}
+
+/// The newest published release, as reported by GitHub.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    /// Normalized `major.minor.patch[-pre]`, without the `v` prefix.
+    pub version: String,
+    /// Markdown body of the GitHub release (the changelog entry).
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub download_url: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct GithubLatestRelease {
+struct GithubRelease {
     tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GithubReleaseAsset {
+    name: String,
+    browser_download_url: String,
 }
 
 pub fn start_update_check(app: &App, cli: &Cli) {
@@ -43,18 +134,24 @@ pub fn start_update_check(app: &App, cli: &Cli) {
         tracing::debug!("Skipping update check (disabled by flag/env)");
         return;
     }
+    if app.is_offline() {
+        tracing::debug!("Skipping update check (offline mode)");
+        return;
+    }
 
     let event_tx = app.event_tx.clone();
+    let http = app.http.clone();
+    let channel = app.config.update_channel_effective();
     let current_version = env!("CARGO_PKG_VERSION").to_owned();
 
     tokio::task::spawn_local(async move {
-        let latest_version = resolve_latest_version().await;
-        let Some(latest_version) = latest_version else {
+        let Some(release) = resolve_latest_release(&http, channel).await else {
             return;
         };
 
-        if is_newer_version(&latest_version, &current_version) {
-            let _ = event_tx.send(ClientEvent::UpdateAvailable { latest_version, current_version });
+        if is_newer_version(&release.version, &current_version) {
+            let _ =
+                event_tx.send(ClientEvent::UpdateAvailable { release, current_version, channel });
         }
     });
 }
@@ -68,8 +165,11 @@ fn update_check_disabled(no_update_check_flag: bool) -> bool {
         .is_some_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
 }
 
-async fn resolve_latest_version() -> Option<String> {
-    let cache_path = update_cache_path()?;
+async fn resolve_latest_release(
+    http: &HttpSettings,
+    channel: UpdateChannel,
+) -> Option<ReleaseInfo> {
+    let cache_path = update_cache_path(channel)?;
     let now = unix_now_secs()?;
     let cached = read_cache(&cache_path).await;
 
@@ -77,25 +177,45 @@ async fn resolve_latest_version() -> Option<String> {
         && now.saturating_sub(cache.checked_at_unix_secs) <= UPDATE_CHECK_TTL_SECS
         && is_valid_version(&cache.latest_version)
     {
-        return Some(cache.latest_version.clone());
+        return Some(cached_release(cache));
     }
 
-    match fetch_latest_release_tag().await {
-        Some(latest_version) => {
-            let cache = UpdateCheckCache { checked_at_unix_secs: now, latest_version };
+    match fetch_latest_release(http, channel).await {
+        Ok(release) => {
+            let cache = UpdateCheckCache {
+                checked_at_unix_secs: now,
+                latest_version: release.version.clone(),
+                release: Some(release.clone()),
+            };
             if let Err(err) = write_cache(&cache_path, &cache).await {
                 tracing::debug!("update-check cache write failed: {err}");
             }
-            Some(cache.latest_version)
+            Some(release)
+        }
+        Err(err) => {
+            tracing::warn!("update check failed: {err}");
+            cached
+                .filter(|cache| is_valid_version(&cache.latest_version))
+                .map(|cache| cached_release(&cache))
         }
-        None => cached.and_then(|cache| {
-            is_valid_version(&cache.latest_version).then_some(cache.latest_version)
-        }),
     }
 }
 
-fn update_cache_path() -> Option<PathBuf> {
-    dirs::cache_dir().map(|dir| dir.join(CACHE_DIR_NAME).join(CACHE_FILE))
+fn cached_release(cache: &UpdateCheckCache) -> ReleaseInfo {
+    cache.release.clone().unwrap_or_else(|| ReleaseInfo {
+        version: cache.latest_version.clone(),
+        ..ReleaseInfo::default()
+    })
+}
+
+/// Channels are cached separately so switching channel takes effect at the
+/// next startup instead of after the TTL.
+fn update_cache_path(channel: UpdateChannel) -> Option<PathBuf> {
+    let file = match channel {
+        UpdateChannel::Stable => CACHE_FILE,
+        UpdateChannel::Prerelease => PRERELEASE_CACHE_FILE,
+    };
+    dirs::cache_dir().map(|dir| dir.join(CACHE_DIR_NAME).join(file))
 }
 
 fn unix_now_secs() -> Option<u64> {
@@ -116,23 +236,73 @@ async fn write_cache(path: &Path, cache: &UpdateCheckCache) -> anyhow::Result<()
     Ok(())
 }
 
-async fn fetch_latest_release_tag() -> Option<String> {
-    let client = reqwest::Client::builder().timeout(UPDATE_CHECK_TIMEOUT).build().ok()?;
+/// Fetch the newest release on `channel`, bypassing the cache.
+pub(crate) async fn fetch_latest_release(
+    http: &HttpSettings,
+    channel: UpdateChannel,
+) -> Result<ReleaseInfo, String> {
+    let client = http
+        .client_builder(UPDATE_CHECK_TIMEOUT)
+        .build()
+        .map_err(|err| format!("HTTP client setup failed: {}", describe_error(&err)))?;
+
+    let release = match channel {
+        UpdateChannel::Stable => {
+            get_github_json::<GithubRelease>(&client, GITHUB_LATEST_RELEASE_API_URL).await?
+        }
+        UpdateChannel::Prerelease => {
+            let releases =
+                get_github_json::<Vec<GithubRelease>>(&client, GITHUB_RELEASES_API_URL).await?;
+            newest_release(releases).ok_or_else(|| "no published releases".to_owned())?
+        }
+    };
+    release_info(release)
+}
 
+async fn get_github_json<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<T, String> {
     let response = client
-        .get(GITHUB_LATEST_RELEASE_API_URL)
+        .get(url)
         .headers(github_api_headers())
         .send()
         .await
-        .ok()?;
+        .map_err(|err| format!("release request failed: {}", describe_error(&err)))?;
 
     if !response.status().is_success() {
-        tracing::debug!("update-check request failed with status {}", response.status());
-        return None;
+        return Err(format!("release request failed with status {}", response.status()));
     }
 
-    let release = response.json::<GithubLatestRelease>().await.ok()?;
-    normalize_version_string(&release.tag_name)
+    response.json::<T>().await.map_err(|err| format!("invalid release payload: {err}"))
+}
+
+/// Highest-versioned published release in the list, prereleases included.
+fn newest_release(releases: Vec<GithubRelease>) -> Option<GithubRelease> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| Some((parse_simple_version(&release.tag_name)?, release)))
+        .max_by(|(left, _), (right, _)| left.cmp(right))
+        .map(|(_, release)| release)
+}
+
+fn release_info(release: GithubRelease) -> Result<ReleaseInfo, String> {
+    let version = normalize_version_string(&release.tag_name)
+        .ok_or_else(|| format!("unrecognized release tag {}", release.tag_name))?;
+    Ok(ReleaseInfo {
+        version,
+        notes: release.body.unwrap_or_default().trim().to_owned(),
+        html_url: release.html_url,
+        assets: release
+            .assets
+            .into_iter()
+            .map(|asset| ReleaseAsset {
+                name: asset.name,
+                download_url: asset.browser_download_url,
+            })
+            .collect(),
+    })
 }
 
 fn github_api_headers() -> HeaderMap {
@@ -144,13 +314,19 @@ fn github_api_headers() -> HeaderMap {
 }
 
 fn normalize_version_string(raw: &str) -> Option<String> {
-    parse_simple_version(raw).map(|v| format!("{}.{}.{}", v.major, v.minor, v.patch))
+    parse_simple_version(raw).map(|v| match v.pre {
+        Some(pre) => format!("{}.{}.{}-{pre}", v.major, v.minor, v.patch),
+        None => format!("{}.{}.{}", v.major, v.minor, v.patch),
+    })
 }
 
 fn parse_simple_version(raw: &str) -> Option<SimpleVersion> {
     let trimmed = raw.trim();
     let without_prefix = trimmed.strip_prefix('v').unwrap_or(trimmed);
-    let core = without_prefix.split_once('-').map_or(without_prefix, |(c, _)| c);
+    let (core, pre) = match without_prefix.split_once('-') {
+        Some((core, pre)) => (core, Some(pre).filter(|pre| !pre.is_empty())),
+        None => (without_prefix, None),
+    };
 
     let mut parts = core.split('.');
     let major = parts.next()?.parse().ok()?;
@@ -159,14 +335,14 @@ fn parse_simple_version(raw: &str) -> Option<SimpleVersion> {
     if parts.next().is_some() {
         return None;
     }
-    Some(SimpleVersion { major, minor, patch })
+    Some(SimpleVersion { major, minor, patch, pre: pre.map(str::to_owned) })
 }
 
 fn is_valid_version(version: &str) -> bool {
     parse_simple_version(version).is_some()
 }
 
-fn is_newer_version(candidate: &str, current: &str) -> bool {
+pub(crate) fn is_newer_version(candidate: &str, current: &str) -> bool {
     let Some(candidate) = parse_simple_version(candidate) else {
         return false;
     };
@@ -184,7 +360,7 @@ mod tests {
     fn parse_simple_version_accepts_v_prefix() {
         assert_eq!(
             parse_simple_version("v1.2.3"),
-            Some(SimpleVersion { major: 1, minor: 2, patch: 3 })
+            Some(SimpleVersion { major: 1, minor: 2, patch: 3, pre: None })
         );
     }
 
@@ -196,11 +372,12 @@ mod tests {
     }
 
     #[test]
-    fn parse_simple_version_ignores_prerelease_suffix() {
+    fn parse_simple_version_keeps_prerelease_suffix() {
         assert_eq!(
             parse_simple_version("v2.4.6-rc1"),
-            Some(SimpleVersion { major: 2, minor: 4, patch: 6 })
+            Some(SimpleVersion { major: 2, minor: 4, patch: 6, pre: Some("rc1".to_owned()) })
         );
+        assert_eq!(normalize_version_string("v2.4.6-rc1").as_deref(), Some("2.4.6-rc1"));
     }
 
     #[test]
@@ -211,10 +388,47 @@ mod tests {
     #[test]
     fn github_release_payload_parses_tag_name() {
         let payload = r#"{"tag_name":"v0.11.0"}"#;
-        let parsed = serde_json::from_str::<GithubLatestRelease>(payload).ok();
+        let parsed = serde_json::from_str::<GithubRelease>(payload).ok();
         assert_eq!(parsed.map(|r| r.tag_name), Some("v0.11.0".to_owned()));
     }
 
+    #[test]
+    fn github_release_payload_keeps_notes_and_assets() {
+        let payload = r#"{
+            "tag_name": "v0.9.0",
+            "body": "Fixes:\n\n- Faster startup\n",
+            "html_url": "https://github.com/srothgan/claude-code-rust/releases/tag/v0.9.0",
+            "assets": [{
+                "name": "claude-code-rust-x86_64-unknown-linux-gnu",
+                "browser_download_url": "https://example.invalid/linux"
+            }]
+        }"#;
+        let release = serde_json::from_str::<GithubRelease>(payload)
+            .map_err(|err| err.to_string())
+            .and_then(release_info)
+            .expect("release info");
+
+        assert_eq!(release.version, "0.9.0");
+        assert_eq!(release.notes, "Fixes:\n\n- Faster startup");
+        assert_eq!(
+            release.assets,
+            vec![ReleaseAsset {
+                name: "claude-code-rust-x86_64-unknown-linux-gnu".to_owned(),
+                download_url: "https://example.invalid/linux".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn legacy_cache_without_release_still_yields_version() {
+        let cache = serde_json::from_str::<UpdateCheckCache>(
+            r#"{"checked_at_unix_secs":1,"latest_version":"0.4.0"}"#,
+        )
+        .expect("cache");
+        assert_eq!(cached_release(&cache).version, "0.4.0");
+        assert!(cached_release(&cache).notes.is_empty());
+    }
+
     #[test]
     fn update_check_disabled_prefers_flag() {
         assert!(update_check_disabled(true));
@@ -226,4 +440,26 @@ mod tests {
         assert!(!is_newer_version("0.2.9", "0.3.0"));
         assert!(!is_newer_version("bad", "0.3.0"));
     }
+
+    #[test]
+    fn is_newer_version_orders_prereleases_before_their_release() {
+        assert!(is_newer_version("0.3.0-rc1", "0.2.9"));
+        assert!(is_newer_version("0.3.0", "0.3.0-rc2"));
+        assert!(is_newer_version("0.3.0-rc10", "0.3.0-rc9"));
+        assert!(!is_newer_version("0.3.0-rc1", "0.3.0"));
+    }
+
+    #[test]
+    fn newest_release_picks_highest_non_draft_tag() {
+        let releases = serde_json::from_str::<Vec<GithubRelease>>(
+            r#"[
+                {"tag_name": "v0.4.0-rc2", "draft": true, "prerelease": true},
+                {"tag_name": "v0.4.0-rc1", "prerelease": true},
+                {"tag_name": "v0.3.1"},
+                {"tag_name": "nightly"}
+            ]"#,
+        )
+        .expect("releases");
+        assert_eq!(newest_release(releases).map(|r| r.tag_name).as_deref(), Some("v0.4.0-rc1"));
+    }
 }
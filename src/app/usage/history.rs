@@ -0,0 +1,351 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Token and cost history across sessions. Every usage update of a live
+//! session is added to `usage_history.json` under the user config directory,
+//! aggregated per UTC day, project folder and model, and kept for
+//! `KEEP_DAYS`. `/usage history` summarizes the last 30 days as a bar chart
+//! with a per-project and per-model breakdown. Writes happen on a background
+//! thread and replace the file atomically.
+
+use super::super::App;
+use crate::agent::model;
+use crate::ui::format_tokens;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::mpsc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Days of history kept on disk.
+const KEEP_DAYS: u64 = 90;
+/// Days shown by `/usage history`.
+const CHART_DAYS: u64 = 30;
+/// Height of the bar chart in rows.
+const CHART_ROWS: u64 = 4;
+const BAR_GLYPHS: [char; 9] = [
+    ' ', '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}',
+    '\u{2588}',
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct UsageRecord {
+    /// Days since the Unix epoch, UTC.
+    pub day: u64,
+    pub project: String,
+    pub model: String,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    #[serde(default)]
+    pub cache_write_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+impl UsageRecord {
+    fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("claude-code-rust").join("usage_history.json"))
+}
+
+fn today() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs() / 86_400)
+}
+
+/// Records on disk. A missing file is an empty history; a file that does not
+/// parse is an error, so it is never overwritten with a fresh one.
+fn read_file(path: &Path) -> Result<Vec<UsageRecord>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map_err(|err| format!("{} is not valid usage history: {err}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(format!("Failed to read {}: {err}", path.display())),
+    }
+}
+
+/// Fold `entry` into the record for its day, project and model, dropping
+/// records older than `KEEP_DAYS`.
+fn merge(records: &mut Vec<UsageRecord>, entry: &UsageRecord) {
+    let oldest = entry.day.saturating_sub(KEEP_DAYS);
+    records.retain(|record| record.day >= oldest);
+    match records.iter_mut().find(|record| {
+        record.day == entry.day && record.project == entry.project && record.model == entry.model
+    }) {
+        Some(record) => record.add(entry),
+        None => records.push(entry.clone()),
+    }
+}
+
+fn append(path: &Path, entry: &UsageRecord) -> Result<(), String> {
+    let mut records = read_file(path)?;
+    merge(&mut records, entry);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let raw = serde_json::to_string_pretty(&records).map_err(|err| err.to_string())?;
+    // A crash mid-write leaves the temp file behind, never a truncated history.
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+    std::fs::write(&tmp, raw).map_err(|err| err.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|err| {
+        let _ = std::fs::remove_file(&tmp);
+        err.to_string()
+    })
+}
+
+/// Sender to the thread that appends to the history file. One thread keeps
+/// the UI off the disk and applies updates in order, so they never race.
+fn writer() -> Option<&'static mpsc::Sender<UsageRecord>> {
+    static WRITER: OnceLock<Option<mpsc::Sender<UsageRecord>>> = OnceLock::new();
+    WRITER
+        .get_or_init(|| {
+            let path = history_path()?;
+            let (tx, rx) = mpsc::channel::<UsageRecord>();
+            std::thread::Builder::new()
+                .name("usage-history".to_owned())
+                .spawn(move || {
+                    for entry in rx {
+                        if let Err(err) = append(&path, &entry) {
+                            tracing::warn!(
+                                "failed to save usage history to {}: {err}",
+                                path.display()
+                            );
+                        }
+                    }
+                })
+                .ok()?;
+            Some(tx)
+        })
+        .as_ref()
+}
+
+/// Add one usage update to the history. `turn_cost` is the cost the update
+/// added to the session total.
+pub(crate) fn record(app: &App, update: &model::UsageUpdate, turn_cost: Option<f64>) {
+    if app.session_id.is_none() {
+        return;
+    }
+    let entry = UsageRecord {
+        day: today(),
+        project: project_name(&app.cwd_raw),
+        model: if app.model_name.is_empty() {
+            "unknown".to_owned()
+        } else {
+            app.model_name.clone()
+        },
+        input_tokens: update.input_tokens.unwrap_or(0),
+        output_tokens: update.output_tokens.unwrap_or(0),
+        cache_read_tokens: update.cache_read_tokens.unwrap_or(0),
+        cache_write_tokens: update.cache_write_tokens.unwrap_or(0),
+        cost_usd: turn_cost.unwrap_or(0.0),
+    };
+    if entry.tokens() == 0 && entry.cost_usd <= 0.0 {
+        return;
+    }
+    if let Some(writer) = writer() {
+        let _ = writer.send(entry);
+    }
+}
+
+fn project_name(cwd: &str) -> String {
+    Path::new(cwd)
+        .file_name()
+        .map_or_else(|| cwd.to_owned(), |name| name.to_string_lossy().into_owned())
+}
+
+/// `MM-DD` of a day number (proleptic Gregorian, UTC).
+fn month_day(day: u64) -> String {
+    // Howard Hinnant's civil_from_days, shifted so the era starts in March.
+    let z = day + 719_468;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    format!("{month:02}-{day_of_month:02}")
+}
+
+/// Bar chart of `daily` token totals, oldest day first.
+fn chart(daily: &[u64], first_day: u64) -> String {
+    let max = daily.iter().copied().max().unwrap_or(0).max(1);
+    let eighths: Vec<u64> = daily
+        .iter()
+        .map(|&tokens| {
+            let scaled = tokens.saturating_mul(CHART_ROWS * 8) / max;
+            if tokens > 0 { scaled.max(1) } else { 0 }
+        })
+        .collect();
+    let label = format_tokens(max);
+    let axis_width = label.len();
+    let mut out = String::new();
+    for row in (0..CHART_ROWS).rev() {
+        let axis = if row + 1 == CHART_ROWS { label.as_str() } else { "" };
+        let _ = write!(out, "{axis:>axis_width$} \u{2502}");
+        for &height in &eighths {
+            let fill = height.saturating_sub(row * 8).min(8);
+            out.push(BAR_GLYPHS[usize::try_from(fill).unwrap_or(8)]);
+        }
+        out.push('\n');
+    }
+    let last_day = first_day + u64::try_from(daily.len().saturating_sub(1)).unwrap_or(0);
+    let (start, end) = (month_day(first_day), month_day(last_day));
+    let gap = daily.len().saturating_sub(start.len() + end.len());
+    let _ = write!(out, "{:axis_width$}  {start}{:gap$}{end}", "", "");
+    out
+}
+
+/// Totals per key, largest first, with each key's share of all tokens.
+fn breakdown(records: &[&UsageRecord], key: fn(&UsageRecord) -> &str, total: u64) -> String {
+    let mut groups: BTreeMap<&str, UsageRecord> = BTreeMap::new();
+    for record in records {
+        groups.entry(key(record)).or_default().add(record);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, usage)| std::cmp::Reverse(usage.tokens()));
+    let mut out = String::new();
+    for (number, (name, usage)) in groups.iter().enumerate() {
+        let share = usage.tokens() * 100 / total.max(1);
+        let _ = write!(
+            out,
+            "\n{}. **{name}** - {} tokens, ${:.2} ({share}%)",
+            number + 1,
+            format_tokens(usage.tokens()),
+            usage.cost_usd
+        );
+    }
+    out
+}
+
+fn summary(records: &[UsageRecord], today: u64) -> String {
+    let first_day = today.saturating_sub(CHART_DAYS - 1);
+    let recent: Vec<&UsageRecord> =
+        records.iter().filter(|record| (first_day..=today).contains(&record.day)).collect();
+    if recent.is_empty() {
+        return "No usage recorded in the last 30 days.".to_owned();
+    }
+    let mut daily = vec![0u64; usize::try_from(CHART_DAYS).unwrap_or(30)];
+    for record in &recent {
+        if let Some(slot) =
+            usize::try_from(record.day - first_day).ok().and_then(|i| daily.get_mut(i))
+        {
+            *slot += record.tokens();
+        }
+    }
+    let total: u64 = recent.iter().map(|record| record.tokens()).sum();
+    let cost: f64 = recent.iter().map(|record| record.cost_usd).sum();
+
+    let mut out = format!(
+        "**Usage, last 30 days:** {} tokens, ${cost:.2}\n\n```text\n{}\n```\n\nBy project:",
+        format_tokens(total),
+        chart(&daily, first_day)
+    );
+    out.push_str(&breakdown(&recent, |record| &record.project, total));
+    out.push_str("\n\nBy model:");
+    out.push_str(&breakdown(&recent, |record| &record.model, total));
+    out
+}
+
+/// Markdown summary of the last 30 days for `/usage history`.
+pub(crate) fn report() -> String {
+    match history_path().map(|path| read_file(&path)) {
+        Some(Ok(records)) => summary(&records, today()),
+        Some(Err(err)) => format!("Could not read usage history: {err}"),
+        None => summary(&[], today()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(day: u64, project: &str, model: &str, input_tokens: u64) -> UsageRecord {
+        UsageRecord {
+            day,
+            project: project.to_owned(),
+            model: model.to_owned(),
+            input_tokens,
+            cost_usd: 0.5,
+            ..UsageRecord::default()
+        }
+    }
+
+    #[test]
+    fn records_merge_per_day_project_and_model() {
+        let mut records = Vec::new();
+        merge(&mut records, &entry(100, "app", "opus", 10));
+        merge(&mut records, &entry(100, "app", "opus", 5));
+        merge(&mut records, &entry(100, "app", "sonnet", 1));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].input_tokens, 15);
+        assert!((records[0].cost_usd - 1.0).abs() < f64::EPSILON);
+
+        merge(&mut records, &entry(100 + KEEP_DAYS + 1, "app", "opus", 1));
+        assert_eq!(records.len(), 1);
+    }
+
+    #[test]
+    fn chart_scales_bars_and_labels_the_days() {
+        assert_eq!(month_day(0), "01-01");
+        assert_eq!(month_day(20_377), "10-16");
+
+        let chart = chart(&[0, 400, 800], 0);
+        let rows: Vec<&str> = chart.lines().collect();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], "800 \u{2502}  \u{2588}");
+        assert_eq!(rows[2], "    \u{2502} \u{2588}\u{2588}");
+        assert_eq!(rows[4], "     01-0101-03");
+    }
+
+    #[test]
+    fn summary_breaks_usage_down_by_project_and_model() {
+        let records = vec![
+            entry(50, "old", "opus", 999),
+            entry(95, "app", "opus", 300),
+            entry(99, "lib", "sonnet", 100),
+        ];
+        let text = summary(&records, 100);
+        assert!(text.starts_with("**Usage, last 30 days:** 400 tokens, $1.00"));
+        assert!(text.contains("By project:\n1. **app** - 300 tokens, $0.50 (75%)\n2. **lib**"));
+        assert!(text.contains("By model:\n1. **opus**"));
+        assert!(!text.contains("old"));
+        assert_eq!(summary(&[], 100), "No usage recorded in the last 30 days.");
+    }
+
+    #[test]
+    fn append_replaces_the_file_and_keeps_unparsable_history() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("usage_history.json");
+
+        append(&path, &entry(100, "app", "opus", 10)).expect("first append");
+        append(&path, &entry(100, "app", "opus", 5)).expect("second append");
+        let records = read_file(&path).expect("read");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].input_tokens, 15);
+
+        std::fs::write(&path, "{ not json").expect("corrupt");
+        assert!(append(&path, &entry(100, "app", "opus", 1)).is_err());
+        assert_eq!(std::fs::read_to_string(&path).expect("read"), "{ not json");
+        let leftovers = std::fs::read_dir(dir.path()).expect("list").count();
+        assert_eq!(leftovers, 1);
+    }
+}
@@ -1,7 +1,9 @@
 mod cli;
+pub(crate) mod history;
 mod oauth;
 
 use crate::agent::events::ClientEvent;
+use crate::app::http::HttpSettings;
 use crate::app::{App, UsageSnapshot, UsageSourceKind, UsageSourceMode, UsageWindow};
 use std::time::{Duration, SystemTime};
 
@@ -26,16 +28,21 @@ pub(crate) fn request_refresh(app: &mut App) {
     if app.usage.in_flight || tokio::runtime::Handle::try_current().is_err() {
         return;
     }
+    if app.is_offline() {
+        app.usage.last_error = Some("Usage requests are disabled in offline mode.".to_owned());
+        return;
+    }
 
     apply_refresh_started(app);
 
     let event_tx = app.event_tx.clone();
     let source_mode = app.usage.active_source;
     let cwd_raw = app.cwd_raw.clone();
+    let http = app.http.clone();
 
     tokio::task::spawn_local(async move {
         let _ = event_tx.send(ClientEvent::UsageRefreshStarted);
-        match refresh_snapshot(source_mode, cwd_raw).await {
+        match refresh_snapshot(source_mode, cwd_raw, &http).await {
             Ok(snapshot) => {
                 let _ = event_tx.send(ClientEvent::UsageSnapshotReceived { snapshot });
             }
@@ -127,20 +134,24 @@ fn format_remaining_until(target: SystemTime) -> String {
 async fn refresh_snapshot(
     source_mode: UsageSourceMode,
     cwd_raw: String,
+    http: &HttpSettings,
 ) -> Result<UsageSnapshot, UsageRefreshFailure> {
     match source_mode {
-        UsageSourceMode::Oauth => oauth::fetch_snapshot().await.map_err(|error| {
+        UsageSourceMode::Oauth => oauth::fetch_snapshot(http).await.map_err(|error| {
             UsageRefreshFailure { source: UsageSourceKind::Oauth, message: error.into_message() }
         }),
         UsageSourceMode::Cli => cli::fetch_snapshot(cwd_raw)
             .await
             .map_err(|message| UsageRefreshFailure { source: UsageSourceKind::Cli, message }),
-        UsageSourceMode::Auto => refresh_snapshot_auto(cwd_raw).await,
+        UsageSourceMode::Auto => refresh_snapshot_auto(cwd_raw, http).await,
     }
 }
 
-async fn refresh_snapshot_auto(cwd_raw: String) -> Result<UsageSnapshot, UsageRefreshFailure> {
-    match oauth::fetch_snapshot().await {
+async fn refresh_snapshot_auto(
+    cwd_raw: String,
+    http: &HttpSettings,
+) -> Result<UsageSnapshot, UsageRefreshFailure> {
+    match oauth::fetch_snapshot(http).await {
         Ok(snapshot) => Ok(snapshot),
         Err(error) if error.should_fallback_to_cli() => {
             let oauth_message = error.into_message();
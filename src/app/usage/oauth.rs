@@ -1,4 +1,5 @@
 use crate::app::auth;
+use crate::app::http::{HttpSettings, describe_error};
 use crate::app::{ExtraUsage, UsageSnapshot, UsageSourceKind, UsageWindow};
 use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, USER_AGENT};
 use serde::Deserialize;
@@ -56,7 +57,7 @@ struct OAuthExtraUsagePayload {
     currency: Option<String>,
 }
 
-pub(super) async fn fetch_snapshot() -> Result<UsageSnapshot, OauthFetchError> {
+pub(super) async fn fetch_snapshot(http: &HttpSettings) -> Result<UsageSnapshot, OauthFetchError> {
     let credentials = auth::load_oauth_credentials().ok_or_else(|| {
         OauthFetchError::Unavailable(
             "No Claude OAuth credentials found. Run /login to authenticate.".to_owned(),
@@ -69,18 +70,17 @@ pub(super) async fn fetch_snapshot() -> Result<UsageSnapshot, OauthFetchError> {
         ));
     }
 
-    let client = reqwest::Client::builder()
-        .timeout(OAUTH_TIMEOUT)
+    let client = http
+        .client_builder(OAUTH_TIMEOUT)
         .default_headers(oauth_headers(&credentials.access_token)?)
         .build()
         .map_err(|error| {
             OauthFetchError::Failed(format!("Failed to create OAuth client: {error}"))
         })?;
 
-    let response =
-        client.get(OAUTH_USAGE_URL).send().await.map_err(|error| {
-            OauthFetchError::Failed(format!("Claude OAuth network error: {error}"))
-        })?;
+    let response = client.get(OAUTH_USAGE_URL).send().await.map_err(|error| {
+        OauthFetchError::Failed(format!("Claude OAuth network error: {}", describe_error(&error)))
+    })?;
 
     let status = response.status();
     let body = response.bytes().await.map_err(|error| {
@@ -31,6 +31,7 @@ fn clear_transient_view_state(app: &mut App) {
     app.mention = None;
     app.slash = None;
     app.subagent = None;
+    app.emoji = None;
     app.release_focus_target(crate::app::FocusTarget::TodoList);
     app.release_focus_target(crate::app::FocusTarget::Permission);
     app.release_focus_target(crate::app::FocusTarget::Help);
@@ -0,0 +1,239 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Watch patterns for command output, configured under `watchPatterns` in
+//! `~/.claude.json`:
+//!
+//! ```json
+//! "watchPatterns": [
+//!   "FAILED",
+//!   { "pattern": "error\\[E\\d+\\]", "bell": true }
+//! ]
+//! ```
+//!
+//! Each pattern is a regex matched against the lines of Execute output as
+//! they stream in. Matching lines are highlighted in the tool block, and the
+//! first match of each pattern in a command raises a toast and, with
+//! `"bell": true`, rings the terminal bell.
+
+use super::toast::{ToastKind, show_toast};
+use super::{App, InvalidationLevel, MessageBlock};
+use crate::ui::highlight::strip_ansi;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+
+const CONFIG_KEY: &str = "watchPatterns";
+/// Longest matched line quoted in the toast, in characters.
+const MAX_TOAST_LINE: usize = 80;
+
+#[derive(Debug, Clone)]
+struct WatchPattern {
+    regex: Regex,
+    bell: bool,
+}
+
+/// How far one command's output has been scanned.
+#[derive(Debug, Clone, Default)]
+struct Progress {
+    /// Bytes of the output already split into lines.
+    bytes: usize,
+    /// Lines before `bytes`.
+    lines: usize,
+    /// Patterns that already raised a toast for this command.
+    alerted: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct WatchState {
+    /// Compiled patterns and the config value they came from.
+    compiled: Option<(Value, Vec<WatchPattern>)>,
+    progress: HashMap<String, Progress>,
+}
+
+impl WatchState {
+    pub fn clear(&mut self) {
+        self.progress.clear();
+    }
+}
+
+fn parse_pattern(entry: &Value) -> Option<WatchPattern> {
+    let (source, bell) = match entry {
+        Value::String(source) => (source.as_str(), false),
+        Value::Object(_) => (
+            entry.get("pattern")?.as_str()?,
+            entry.get("bell").and_then(Value::as_bool).unwrap_or(false),
+        ),
+        _ => return None,
+    };
+    match Regex::new(source) {
+        Ok(regex) => Some(WatchPattern { regex, bell }),
+        Err(err) => {
+            tracing::warn!("ignoring {CONFIG_KEY} entry {source:?}: {err}");
+            None
+        }
+    }
+}
+
+/// Patterns configured in the preferences document, compiled once per change
+/// of the setting.
+fn patterns(app: &mut App) -> Vec<WatchPattern> {
+    let config =
+        app.config.committed_preferences_document.get(CONFIG_KEY).cloned().unwrap_or(Value::Null);
+    if let Some((source, compiled)) = &app.watch.compiled
+        && *source == config
+    {
+        return compiled.clone();
+    }
+    let compiled: Vec<WatchPattern> = config
+        .as_array()
+        .map(|entries| entries.iter().filter_map(parse_pattern).collect())
+        .unwrap_or_default();
+    app.watch.compiled = Some((config, compiled.clone()));
+    compiled
+}
+
+/// A line that matched: its index in the output and the patterns it matched.
+#[derive(Debug, PartialEq, Eq)]
+struct Hit {
+    line: usize,
+    text: String,
+    patterns: Vec<usize>,
+}
+
+/// Match the complete lines of `output` past `progress`, plus the trailing
+/// partial line once the command has `finished`.
+fn scan_output(
+    output: &str,
+    progress: &mut Progress,
+    patterns: &[WatchPattern],
+    finished: bool,
+) -> Vec<Hit> {
+    if output.len() < progress.bytes || !output.is_char_boundary(progress.bytes) {
+        // The output was replaced by a shorter snapshot; start over.
+        *progress =
+            Progress { alerted: std::mem::take(&mut progress.alerted), ..Progress::default() };
+    }
+    let rest = &output[progress.bytes..];
+    let complete = if finished { rest.len() } else { rest.rfind('\n').map_or(0, |end| end + 1) };
+    if complete == 0 {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    for (offset, raw) in rest[..complete].split_terminator('\n').enumerate() {
+        let text = strip_ansi(raw);
+        let matched: Vec<usize> = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, pattern)| pattern.regex.is_match(&text))
+            .map(|(index, _)| index)
+            .collect();
+        if !matched.is_empty() {
+            hits.push(Hit { line: progress.lines + offset, text, patterns: matched });
+        }
+    }
+    progress.lines += rest[..complete].split_terminator('\n').count();
+    progress.bytes += complete;
+    hits
+}
+
+/// Scan the new output of the Execute tool call at `(mi, bi)`. Call after its
+/// output changed; once the call has finished, a last line without a newline
+/// is taken too.
+pub(crate) fn scan(app: &mut App, mi: usize, bi: usize) {
+    let patterns = patterns(app);
+    if patterns.is_empty() {
+        return;
+    }
+    let Some(MessageBlock::ToolCall(tc)) =
+        app.messages.get_mut(mi).and_then(|message| message.blocks.get_mut(bi))
+    else {
+        return;
+    };
+    let Some(output) = tc.terminal_output.as_deref() else {
+        return;
+    };
+    let finished = matches!(
+        tc.status,
+        crate::agent::model::ToolCallStatus::Completed
+            | crate::agent::model::ToolCallStatus::Failed
+    );
+    let progress = app.watch.progress.entry(tc.id.clone()).or_default();
+    let restarted = output.len() < progress.bytes;
+    let hits = scan_output(output, progress, &patterns, finished);
+    if restarted {
+        tc.watch_hits.clear();
+    }
+    if hits.is_empty() {
+        return;
+    }
+    tc.watch_hits.extend(hits.iter().map(|hit| hit.line));
+    tc.mark_tool_call_layout_dirty();
+    let command = tc.terminal_command.clone().unwrap_or_else(|| tc.title.clone());
+
+    let mut alerts = Vec::new();
+    for hit in hits {
+        for index in hit.patterns {
+            if !progress.alerted.contains(&index) {
+                progress.alerted.push(index);
+                alerts.push((index, hit.text.clone()));
+            }
+        }
+    }
+    app.invalidate_layout(InvalidationLevel::Single(mi));
+    for (index, text) in alerts {
+        let line: String = text.trim().chars().take(MAX_TOAST_LINE).collect();
+        show_toast(app, ToastKind::Warning, format!("`{command}`: {line}"));
+        if patterns[index].bell {
+            super::notify::ring_bell();
+        }
+    }
+}
+
+/// Scan the output of the tool call `id`, wherever it sits in the transcript.
+pub(crate) fn scan_tool_call(app: &mut App, id: &str) {
+    if let Some((mi, bi)) = app.lookup_tool_call(id) {
+        scan(app, mi, bi);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn compiled(config: &Value) -> Vec<WatchPattern> {
+        config.as_array().into_iter().flatten().filter_map(parse_pattern).collect()
+    }
+
+    #[test]
+    fn patterns_accept_strings_and_objects_and_skip_bad_regexes() {
+        let patterns =
+            compiled(&json!(["FAILED", { "pattern": "error\\[E\\d+\\]", "bell": true }, "(", 3]));
+        assert_eq!(patterns.len(), 2);
+        assert!(!patterns[0].bell);
+        assert!(patterns[1].bell);
+        assert!(patterns[1].regex.is_match("error[E0308]: mismatched types"));
+    }
+
+    #[test]
+    fn streaming_output_is_scanned_one_complete_line_at_a_time() {
+        let patterns = compiled(&json!(["FAILED", "E\\d+"]));
+        let mut progress = Progress::default();
+
+        let hits = scan_output("ok\ntest a ... FAI", &mut progress, &patterns, false);
+        assert!(hits.is_empty());
+        assert_eq!((progress.bytes, progress.lines), (3, 1));
+
+        let output = "ok\ntest a ... FAILED\n\u{1b}[31merror[E0308]\u{1b}[0m";
+        let hits = scan_output(output, &mut progress, &patterns, false);
+        assert_eq!(
+            hits,
+            vec![Hit { line: 1, text: "test a ... FAILED".to_owned(), patterns: vec![0] }]
+        );
+
+        let hits = scan_output(output, &mut progress, &patterns, true);
+        assert_eq!(hits, vec![Hit { line: 2, text: "error[E0308]".to_owned(), patterns: vec![1] }]);
+    }
+}
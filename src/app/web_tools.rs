@@ -0,0 +1,275 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured views of `WebSearch` / `WebFetch` tool results.
+//!
+//! The bridge forwards both tools' results as normalized text in `raw_output`.
+//! This module recovers the structure (result links, fetched URL, summary) so
+//! the tool block can render a result list instead of the raw payload.
+
+/// Parsed `WebSearch` / `WebFetch` output attached to a tool call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebToolOutput {
+    Search(WebSearchOutput),
+    Fetch(WebFetchOutput),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WebSearchOutput {
+    pub query: Option<String>,
+    pub results: Vec<WebSearchHit>,
+    /// Free-form text that accompanied the links (model-facing summary).
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchHit {
+    pub title: String,
+    pub url: String,
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebFetchOutput {
+    pub url: Option<String>,
+    pub summary: String,
+}
+
+const SEARCH_HEADER_PREFIX: &str = "Web search results for query:";
+const LINKS_PREFIX: &str = "Links:";
+const REMINDER_PREFIX: &str = "REMINDER:";
+
+#[must_use]
+pub fn is_web_tool_name(sdk_tool_name: &str) -> bool {
+    matches!(sdk_tool_name, "WebSearch" | "WebFetch")
+}
+
+/// Parse a web tool `raw_output` payload. Returns `None` for other tools and
+/// for payloads that carry nothing worth structuring.
+#[must_use]
+pub fn parse_web_tool_output(
+    sdk_tool_name: &str,
+    raw_input: Option<&serde_json::Value>,
+    raw_output: &serde_json::Value,
+) -> Option<WebToolOutput> {
+    match sdk_tool_name {
+        "WebSearch" => parse_search_output(raw_input, raw_output).map(WebToolOutput::Search),
+        "WebFetch" => parse_fetch_output(raw_input, raw_output).map(WebToolOutput::Fetch),
+        _ => None,
+    }
+}
+
+fn parse_search_output(
+    raw_input: Option<&serde_json::Value>,
+    raw_output: &serde_json::Value,
+) -> Option<WebSearchOutput> {
+    let input_query = input_str(raw_input, "query");
+    let mut out = match raw_output {
+        serde_json::Value::String(text) => parse_search_text(text),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            WebSearchOutput { results: collect_hits(raw_output), ..WebSearchOutput::default() }
+        }
+        _ => return None,
+    };
+    if out.query.is_none() {
+        out.query = input_query;
+    }
+    (!out.results.is_empty() || !out.summary.is_empty()).then_some(out)
+}
+
+fn parse_search_text(text: &str) -> WebSearchOutput {
+    let mut out = WebSearchOutput::default();
+    let mut summary_lines: Vec<&str> = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let (line, tail) = rest.split_once('\n').unwrap_or((rest, ""));
+        let trimmed = line.trim_start();
+        if let Some(query) = trimmed.strip_prefix(SEARCH_HEADER_PREFIX) {
+            let query = query.trim().trim_matches('"').trim();
+            if !query.is_empty() {
+                out.query = Some(query.to_owned());
+            }
+            rest = tail;
+            continue;
+        }
+        if let Some(links) = trimmed.strip_prefix(LINKS_PREFIX) {
+            // The links array may be pretty-printed across lines, so decode
+            // one JSON value from the remaining text rather than this line.
+            let offset = line.len() - links.len();
+            let mut stream = serde_json::Deserializer::from_str(&rest[offset..])
+                .into_iter::<serde_json::Value>();
+            if let Some(Ok(value)) = stream.next() {
+                out.results.extend(collect_hits(&value));
+                rest = &rest[offset + stream.byte_offset()..];
+                continue;
+            }
+        }
+        if trimmed.starts_with(REMINDER_PREFIX) {
+            rest = tail;
+            continue;
+        }
+        summary_lines.push(line);
+        rest = tail;
+    }
+
+    summary_lines.join("\n").trim().clone_into(&mut out.summary);
+    out
+}
+
+/// Collect `{title, url}` records from a links array or an SDK result object.
+fn collect_hits(value: &serde_json::Value) -> Vec<WebSearchHit> {
+    let mut hits = Vec::new();
+    collect_hits_into(value, &mut hits, 0);
+    hits
+}
+
+fn collect_hits_into(value: &serde_json::Value, hits: &mut Vec<WebSearchHit>, depth: usize) {
+    const MAX_DEPTH: usize = 4;
+    if depth > MAX_DEPTH {
+        return;
+    }
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_hits_into(item, hits, depth + 1);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            if let Some(url) = map.get("url").and_then(serde_json::Value::as_str) {
+                let title = map
+                    .get("title")
+                    .and_then(serde_json::Value::as_str)
+                    .filter(|title| !title.trim().is_empty())
+                    .unwrap_or(url);
+                let snippet = ["snippet", "description", "page_age"].iter().find_map(|key| {
+                    map.get(*key)
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_owned)
+                });
+                hits.push(WebSearchHit {
+                    title: title.trim().to_owned(),
+                    url: url.to_owned(),
+                    snippet,
+                });
+                return;
+            }
+            for key in ["results", "content", "links"] {
+                if let Some(nested) = map.get(key) {
+                    collect_hits_into(nested, hits, depth + 1);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_fetch_output(
+    raw_input: Option<&serde_json::Value>,
+    raw_output: &serde_json::Value,
+) -> Option<WebFetchOutput> {
+    let (output_url, summary) = match raw_output {
+        serde_json::Value::String(text) => (None, text.trim().to_owned()),
+        serde_json::Value::Object(map) => (
+            map.get("url").and_then(serde_json::Value::as_str).map(str::to_owned),
+            map.get("result")
+                .or_else(|| map.get("text"))
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .trim()
+                .to_owned(),
+        ),
+        _ => return None,
+    };
+    if summary.is_empty() {
+        return None;
+    }
+    Some(WebFetchOutput { url: output_url.or_else(|| input_str(raw_input, "url")), summary })
+}
+
+fn input_str(raw_input: Option<&serde_json::Value>, key: &str) -> Option<String> {
+    raw_input
+        .and_then(|input| input.get(key))
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn search_text_extracts_query_links_and_summary() {
+        let raw = serde_json::json!(
+            "Web search results for query: \"ratatui tables\"\n\n\
+             Links: [{\"title\":\"Ratatui docs\",\"url\":\"https://ratatui.rs\"},\
+             {\"title\":\"Table widget\",\"url\":\"https://docs.rs/ratatui/table\"}]\n\n\
+             Ratatui ships a Table widget.\n\n\
+             REMINDER: You MUST include the sources above in your response."
+        );
+        let Some(WebToolOutput::Search(out)) = parse_web_tool_output("WebSearch", None, &raw)
+        else {
+            panic!("expected search output");
+        };
+        assert_eq!(out.query.as_deref(), Some("ratatui tables"));
+        assert_eq!(out.results.len(), 2);
+        assert_eq!(out.results[0].title, "Ratatui docs");
+        assert_eq!(out.results[1].url, "https://docs.rs/ratatui/table");
+        assert_eq!(out.summary, "Ratatui ships a Table widget.");
+    }
+
+    #[test]
+    fn search_text_handles_multiple_link_blocks() {
+        let raw = serde_json::json!(
+            "Links: [{\"title\":\"A\",\"url\":\"https://a.test\"}]\n\n\
+             Links: [{\"title\":\"B\",\"url\":\"https://b.test\",\"snippet\":\"about b\"}]"
+        );
+        let input = serde_json::json!({ "query": "ab" });
+        let Some(WebToolOutput::Search(out)) =
+            parse_web_tool_output("WebSearch", Some(&input), &raw)
+        else {
+            panic!("expected search output");
+        };
+        assert_eq!(out.query.as_deref(), Some("ab"));
+        assert_eq!(out.results.len(), 2);
+        assert_eq!(out.results[1].snippet.as_deref(), Some("about b"));
+        assert!(out.summary.is_empty());
+    }
+
+    #[test]
+    fn search_json_result_object_is_flattened() {
+        let raw = serde_json::json!({
+            "results": [{ "tool_use_id": "x", "content": [{ "title": "", "url": "https://c.test" }] }]
+        });
+        let Some(WebToolOutput::Search(out)) = parse_web_tool_output("WebSearch", None, &raw)
+        else {
+            panic!("expected search output");
+        };
+        assert_eq!(out.results.len(), 1);
+        assert_eq!(out.results[0].title, "https://c.test");
+    }
+
+    #[test]
+    fn fetch_uses_input_url_and_trimmed_summary() {
+        let input = serde_json::json!({ "url": "https://example.com/page", "prompt": "summarize" });
+        let raw = serde_json::json!("\n# Example\n\nBody text\n");
+        let Some(WebToolOutput::Fetch(out)) = parse_web_tool_output("WebFetch", Some(&input), &raw)
+        else {
+            panic!("expected fetch output");
+        };
+        assert_eq!(out.url.as_deref(), Some("https://example.com/page"));
+        assert_eq!(out.summary, "# Example\n\nBody text");
+    }
+
+    #[test]
+    fn empty_or_unrelated_payloads_are_ignored() {
+        assert!(parse_web_tool_output("WebFetch", None, &serde_json::json!("  ")).is_none());
+        assert!(parse_web_tool_output("WebSearch", None, &serde_json::Value::Null).is_none());
+        assert!(parse_web_tool_output("Read", None, &serde_json::json!("text")).is_none());
+    }
+}
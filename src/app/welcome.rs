@@ -0,0 +1,165 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Start screen on the welcome banner: the most recent sessions and a few
+//! quick actions, picked with Up/Down and Enter while the transcript is still
+//! empty and nothing is typed. Each entry runs the slash command it stands for.
+
+use super::{
+    App, AppStatus, FocusOwner, InvalidationLevel, MessageBlock, MessageRole, WelcomeBanner,
+    WelcomeBlock,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Custom banner art beyond this many lines is cut off.
+const MAX_BANNER_ART_LINES: usize = 24;
+
+/// Recent sessions listed on the start screen.
+pub const MAX_WELCOME_SESSIONS: usize = 5;
+
+/// Label and slash command for each quick action, in display order.
+pub const QUICK_ACTIONS: [(&str, &str); 3] = [
+    ("New session", "/new-session"),
+    ("Open settings", "/config"),
+    ("Run diagnostics", "/diagnostics"),
+];
+
+/// Number of selectable start-screen entries: sessions first, then quick actions.
+#[must_use]
+pub fn start_item_count(block: &WelcomeBlock) -> usize {
+    block.recent_sessions.len().min(MAX_WELCOME_SESSIONS) + QUICK_ACTIONS.len()
+}
+
+fn start_item_command(block: &WelcomeBlock, index: usize) -> Option<String> {
+    let sessions = block.recent_sessions.len().min(MAX_WELCOME_SESSIONS);
+    if let Some(session) = block.recent_sessions.get(index).filter(|_| index < sessions) {
+        return Some(format!("/resume {}", session.session_id));
+    }
+    let (_, command) = QUICK_ACTIONS.get(index.checked_sub(sessions)?)?;
+    Some((*command).to_owned())
+}
+
+fn welcome_block(app: &mut App) -> Option<&mut WelcomeBlock> {
+    let message = app.messages.first_mut()?;
+    if !matches!(message.role, MessageRole::Welcome) {
+        return None;
+    }
+    match message.blocks.first_mut() {
+        Some(MessageBlock::Welcome(block)) => Some(block),
+        _ => None,
+    }
+}
+
+/// The welcome block while it is the whole transcript and visible.
+fn start_screen(app: &mut App) -> Option<&mut WelcomeBlock> {
+    if app.messages.len() != 1 {
+        return None;
+    }
+    welcome_block(app).filter(|block| block.banner != WelcomeBanner::Off)
+}
+
+/// Apply the "Welcome banner" setting and custom art file to the welcome block.
+pub(crate) fn sync_welcome_banner(app: &mut App) {
+    let banner = app.config.welcome_banner_effective();
+    let art = match app.config.welcome_banner_file() {
+        Some(path) if banner == WelcomeBanner::Full => match std::fs::read_to_string(&path) {
+            Ok(text) => text.lines().take(MAX_BANNER_ART_LINES).map(str::to_owned).collect(),
+            Err(err) => {
+                tracing::warn!("failed to read welcome banner art {}: {err}", path.display());
+                Vec::new()
+            }
+        },
+        _ => Vec::new(),
+    };
+    let Some(block) = welcome_block(app) else {
+        return;
+    };
+    block.banner = banner;
+    block.banner_art = art;
+    block.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::From(0));
+}
+
+pub(crate) fn handle_start_screen_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.modifiers != KeyModifiers::NONE
+        || !app.input.is_empty()
+        || app.focus_owner() != FocusOwner::Input
+        || !matches!(app.status, AppStatus::Ready)
+    {
+        return false;
+    }
+    let Some(block) = start_screen(app) else {
+        return false;
+    };
+    let count = start_item_count(block);
+    let next = match (key.code, block.selected) {
+        (KeyCode::Down, None) => Some(0),
+        (KeyCode::Up, None) => Some(count - 1),
+        (KeyCode::Down, Some(idx)) => Some((idx + 1) % count),
+        (KeyCode::Up, Some(idx)) => Some((idx + count - 1) % count),
+        (KeyCode::Esc, Some(_)) => None,
+        (KeyCode::Enter, Some(idx)) => {
+            let command = start_item_command(block, idx);
+            block.selected = None;
+            block.cache.invalidate();
+            app.invalidate_layout(InvalidationLevel::From(0));
+            if let Some(command) = command {
+                super::slash::try_handle_submit(app, &command);
+            }
+            return true;
+        }
+        _ => return false,
+    };
+    block.selected = next;
+    block.cache.invalidate();
+    app.invalidate_layout(InvalidationLevel::From(0));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ChatMessage, RecentSessionInfo};
+    use pretty_assertions::assert_eq;
+
+    fn session(id: &str) -> RecentSessionInfo {
+        RecentSessionInfo {
+            session_id: id.to_owned(),
+            summary: format!("summary {id}"),
+            last_modified_ms: 0,
+            file_size_bytes: 0,
+            cwd: None,
+            git_branch: None,
+            custom_title: None,
+            first_prompt: None,
+        }
+    }
+
+    fn selected(app: &mut App) -> Option<usize> {
+        start_screen(app).and_then(|block| block.selected)
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> bool {
+        handle_start_screen_key(app, KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn arrows_cycle_sessions_then_quick_actions_until_typing() {
+        let mut app = App::test_default();
+        let sessions: Vec<_> = (0..7).map(|idx| session(&format!("s{idx}"))).collect();
+        app.messages = vec![ChatMessage::welcome_with_recent("sonnet", "/cwd", &sessions)];
+
+        assert!(!press(&mut app, KeyCode::Enter));
+        assert!(press(&mut app, KeyCode::Up));
+        assert_eq!(selected(&mut app), Some(MAX_WELCOME_SESSIONS + QUICK_ACTIONS.len() - 1));
+        assert!(press(&mut app, KeyCode::Down));
+        assert_eq!(selected(&mut app), Some(0));
+
+        let block = start_screen(&mut app).expect("start screen");
+        assert_eq!(start_item_command(block, 4).as_deref(), Some("/resume s4"));
+        assert_eq!(start_item_command(block, 5).as_deref(), Some("/new-session"));
+
+        app.input.set_text("draft");
+        assert!(!press(&mut app, KeyCode::Down));
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! `claude-rs doctor`: check the environment the TUI depends on and print an
+//! actionable fix for every problem found. Read-only apart from a probe file
+//! in the cache directory.
+
+use crate::Cli;
+use crate::agent::bridge::{
+    DEFAULT_AGENT_PROFILE, agent_profiles_path, parse_agent_profiles, resolve_agent_launcher,
+};
+use crate::app::config::store::{self, SettingsPaths};
+use crate::app::config::{SettingFile, setting_specs};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+
+/// Oldest Node.js release the bridge supports (`engines.node` in package.json).
+const MIN_NODE_MAJOR: u64 = 18;
+const CACHE_DIR_NAME: &str = "claude-code-rust";
+const PROBE_FILE: &str = ".doctor-probe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    /// Optional capability that is not available; nothing to fix.
+    Info,
+    Warn,
+    Fail,
+}
+
+impl Status {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Fail => "FAIL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Ok, detail: detail.into(), fix: None }
+    }
+
+    fn info(name: &'static str, detail: impl Into<String>) -> Self {
+        Self { name, status: Status::Info, detail: detail.into(), fix: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Warn, detail: detail.into(), fix: Some(fix.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self { name, status: Status::Fail, detail: detail.into(), fix: Some(fix.into()) }
+    }
+}
+
+/// Run every check and print the report. Returns the number of failed checks.
+pub fn run(cli: &Cli) -> usize {
+    let uses_node = matches!(cli.agent.as_deref(), None | Some(DEFAULT_AGENT_PROFILE));
+    let mut checks = vec![agent_check(cli), node_check(uses_node), claude_cli_check()];
+    checks
+        .extend(terminal_checks(|name| std::env::var(name).ok(), std::io::stdout().is_terminal()));
+    checks.extend(config_checks(cli));
+    checks.push(cache_dir_check(dirs::cache_dir().map(|dir| dir.join(CACHE_DIR_NAME)).as_deref()));
+
+    println!("claude-rs {} doctor\n", env!("CARGO_PKG_VERSION"));
+    print!("{}", render_report(&checks));
+    checks.iter().filter(|check| check.status == Status::Fail).count()
+}
+
+fn render_report(checks: &[Check]) -> String {
+    let name_width = checks.iter().map(|check| check.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for check in checks {
+        let _ = writeln!(
+            out,
+            "  {:<4}  {:<name_width$}  {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+        if let Some(fix) = &check.fix {
+            let _ = writeln!(out, "  {:<4}  {:<name_width$}  fix: {fix}", "", "");
+        }
+    }
+    let problems = checks.iter().filter(|check| check.status == Status::Fail).count();
+    let warnings = checks.iter().filter(|check| check.status == Status::Warn).count();
+    out.push('\n');
+    let _ = match (problems, warnings) {
+        (0, 0) => writeln!(out, "All checks passed."),
+        (0, warnings) => writeln!(out, "No problems found, {warnings} warning(s)."),
+        (problems, warnings) => writeln!(out, "{problems} problem(s), {warnings} warning(s)."),
+    };
+    out
+}
+
+fn agent_check(cli: &Cli) -> Check {
+    match resolve_agent_launcher(cli.agent.as_deref(), cli.bridge_script.as_deref()) {
+        Ok(launcher) => Check::ok("Agent", launcher.describe()),
+        Err(err) => {
+            let fix = match cli.agent.as_deref() {
+                None | Some(DEFAULT_AGENT_PROFILE) => {
+                    "Reinstall claude-code-rust, or point --bridge-script / \
+                     CLAUDE_RS_AGENT_BRIDGE at agent-sdk/dist/bridge.js."
+                        .to_owned()
+                }
+                Some(name) => format!(
+                    "Check the `{name}` entry in {}.",
+                    agent_profiles_path().map_or_else(
+                        || "agents.json".to_owned(),
+                        |path| path.display().to_string()
+                    )
+                ),
+            };
+            Check::fail("Agent", format!("{err:#}"), fix)
+        }
+    }
+}
+
+fn node_check(required: bool) -> Check {
+    let Ok(node) = which::which("node") else {
+        let fix =
+            format!("Install Node.js {MIN_NODE_MAJOR} or newer and make sure `node` is on PATH.");
+        return if required {
+            Check::fail("Node.js", "not found on PATH", fix)
+        } else {
+            Check::info("Node.js", "not found on PATH (not needed by this agent profile)")
+        };
+    };
+    let Some(version) = command_version(&node, "--version") else {
+        return Check::warn(
+            "Node.js",
+            format!("{} did not report a version", node.display()),
+            "Run `node --version` to check the installation.",
+        );
+    };
+    match node_major(&version) {
+        Some(major) if major < MIN_NODE_MAJOR => Check::fail(
+            "Node.js",
+            format!("{version} at {} is too old", node.display()),
+            format!("Upgrade to Node.js {MIN_NODE_MAJOR} or newer."),
+        ),
+        _ => Check::ok("Node.js", format!("{version} ({})", node.display())),
+    }
+}
+
+fn claude_cli_check() -> Check {
+    let Ok(claude) = which::which("claude") else {
+        return Check::warn(
+            "Claude CLI",
+            "not found on PATH",
+            "Install it with `npm install -g @anthropic-ai/claude-code`; /login, /usage \
+             and plugin commands need it.",
+        );
+    };
+    match command_version(&claude, "--version") {
+        Some(version) => Check::ok("Claude CLI", format!("{version} ({})", claude.display())),
+        None => Check::warn(
+            "Claude CLI",
+            format!("{} did not report a version", claude.display()),
+            "Run `claude --version` to check the installation.",
+        ),
+    }
+}
+
+fn command_version(program: &Path, flag: &str) -> Option<String> {
+    let output = Command::new(program).arg(flag).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).lines().next()?.trim().to_owned();
+    (!version.is_empty()).then_some(version)
+}
+
+fn node_major(version: &str) -> Option<u64> {
+    version.trim().trim_start_matches('v').split('.').next()?.parse().ok()
+}
+
+fn terminal_checks(env: impl Fn(&str) -> Option<String>, stdout_is_tty: bool) -> Vec<Check> {
+    let term = env("TERM").unwrap_or_default();
+    let term_program = env("TERM_PROGRAM").unwrap_or_default();
+    let usable_term = !term.is_empty() && term != "dumb";
+
+    let terminal = if !stdout_is_tty {
+        Check::warn("Terminal", "stdout is not a terminal", "Run claude-rs directly in a terminal.")
+    } else if !usable_term {
+        Check::warn(
+            "Terminal",
+            format!("TERM={}", if term.is_empty() { "<unset>" } else { &term }),
+            "Set TERM to your terminal's type, e.g. xterm-256color.",
+        )
+    } else if term_program.is_empty() {
+        Check::ok("Terminal", format!("TERM={term}"))
+    } else {
+        Check::ok("Terminal", format!("TERM={term}, TERM_PROGRAM={term_program}"))
+    };
+
+    let colorterm = env("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+    let truecolor = if matches!(colorterm.as_str(), "truecolor" | "24bit") {
+        Check::ok("Truecolor", format!("COLORTERM={colorterm}"))
+    } else {
+        Check::warn(
+            "Truecolor",
+            "COLORTERM does not advertise 24-bit color; colors may be approximated",
+            "Set COLORTERM=truecolor if your terminal supports 24-bit color.",
+        )
+    };
+
+    let kitty_graphics = term.contains("kitty")
+        || env("KITTY_WINDOW_ID").is_some()
+        || matches!(term_program.as_str(), "WezTerm" | "ghostty");
+    let graphics = if kitty_graphics {
+        Check::ok("Kitty graphics", "supported")
+    } else {
+        Check::info("Kitty graphics", "not detected")
+    };
+
+    // Bracketed paste is requested on startup; only terminals without escape
+    // sequence support ignore it, and large pastes then arrive as keystrokes.
+    let paste = if stdout_is_tty && usable_term {
+        Check::ok("Bracketed paste", "enabled at startup")
+    } else {
+        Check::warn(
+            "Bracketed paste",
+            "unlikely to work in this terminal",
+            "Use a terminal emulator that supports bracketed paste (most do).",
+        )
+    };
+    vec![terminal, truecolor, graphics, paste]
+}
+
+fn config_checks(cli: &Cli) -> Vec<Check> {
+    let paths = match store::resolve_paths(None, cli.dir.as_deref()) {
+        Ok(paths) => paths,
+        Err(err) => {
+            return vec![Check::fail(
+                "Settings",
+                err,
+                "Set HOME and run from a readable directory.",
+            )];
+        }
+    };
+    let SettingsPaths { settings, local_settings, preferences } = paths;
+    let mut checks = vec![
+        settings_file_check("Settings", &settings, SettingFile::Settings),
+        settings_file_check("Local settings", &local_settings, SettingFile::LocalSettings),
+        settings_file_check("Preferences", &preferences, SettingFile::Preferences),
+    ];
+    if let Some(path) = agent_profiles_path()
+        && let Ok(raw) = std::fs::read_to_string(&path)
+    {
+        checks.push(match parse_agent_profiles(&raw) {
+            Ok(profiles) => Check::ok(
+                "Agent profiles",
+                format!("{} profile(s) in {}", profiles.len(), path.display()),
+            ),
+            Err(err) => Check::fail(
+                "Agent profiles",
+                format!("{}: {err:#}", path.display()),
+                "Fix the JSON; each profile needs a `command` and optional `args` and `env`.",
+            ),
+        });
+    }
+    checks
+}
+
+fn settings_file_check(name: &'static str, path: &Path, file: SettingFile) -> Check {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Check::ok(name, format!("{} (not present, using defaults)", path.display()));
+        }
+        Err(err) => {
+            return Check::fail(
+                name,
+                format!("cannot read {}: {err}", path.display()),
+                "Fix the file permissions.",
+            );
+        }
+    };
+    let document = match serde_json::from_str::<Value>(&raw) {
+        Ok(document @ Value::Object(_)) => document,
+        Ok(_) => {
+            return Check::fail(
+                name,
+                format!("{} is not a JSON object", path.display()),
+                "Replace its contents with an object such as {}.",
+            );
+        }
+        Err(err) => {
+            return Check::fail(
+                name,
+                format!("{} is not valid JSON: {err}", path.display()),
+                "Fix the syntax; otherwise claude-rs backs the file up and starts with defaults.",
+            );
+        }
+    };
+    let invalid: Vec<String> = setting_specs()
+        .iter()
+        .filter(|spec| spec.file == file)
+        .filter(|spec| store::read_persisted_setting(&document, spec).is_err())
+        .map(|spec| spec.json_path.join("."))
+        .collect();
+    if invalid.is_empty() {
+        Check::ok(name, path.display().to_string())
+    } else {
+        Check::warn(
+            name,
+            format!("{} has invalid values for {}", path.display(), invalid.join(", ")),
+            "Change them in /config or remove them to use the defaults.",
+        )
+    }
+}
+
+fn cache_dir_check(dir: Option<&Path>) -> Check {
+    let Some(dir) = dir else {
+        return Check::warn(
+            "Cache directory",
+            "no cache directory for this platform",
+            "Set XDG_CACHE_HOME (or HOME) so update checks can be cached.",
+        );
+    };
+    let probe = dir.join(PROBE_FILE);
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|()| std::fs::write(&probe, b"ok"))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::ok("Cache directory", format!("{} (writable)", dir.display())),
+        Err(err) => Check::fail(
+            "Cache directory",
+            format!("{} is not writable: {err}", dir.display()),
+            format!("Make {} writable by your user.", dir.display()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn statuses(checks: &[Check]) -> Vec<(&'static str, Status)> {
+        checks.iter().map(|check| (check.name, check.status)).collect()
+    }
+
+    #[test]
+    fn terminal_checks_read_term_and_colorterm() {
+        let env = |name: &str| match name {
+            "TERM" => Some("xterm-kitty".to_owned()),
+            "COLORTERM" => Some("truecolor".to_owned()),
+            _ => None,
+        };
+        assert_eq!(
+            statuses(&terminal_checks(env, true)),
+            vec![
+                ("Terminal", Status::Ok),
+                ("Truecolor", Status::Ok),
+                ("Kitty graphics", Status::Ok),
+                ("Bracketed paste", Status::Ok),
+            ]
+        );
+
+        let dumb = |name: &str| (name == "TERM").then(|| "dumb".to_owned());
+        assert_eq!(
+            statuses(&terminal_checks(dumb, true)),
+            vec![
+                ("Terminal", Status::Warn),
+                ("Truecolor", Status::Warn),
+                ("Kitty graphics", Status::Info),
+                ("Bracketed paste", Status::Warn),
+            ]
+        );
+    }
+
+    #[test]
+    fn settings_file_check_flags_bad_json_and_wrong_types() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".claude.json");
+
+        assert_eq!(
+            settings_file_check("Preferences", &path, SettingFile::Preferences).status,
+            Status::Ok
+        );
+
+        std::fs::write(&path, "{ not json").expect("write");
+        assert_eq!(
+            settings_file_check("Preferences", &path, SettingFile::Preferences).status,
+            Status::Fail
+        );
+
+        std::fs::write(&path, r#"{"respectGitignore": "yes"}"#).expect("write");
+        let check = settings_file_check("Preferences", &path, SettingFile::Preferences);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.detail.contains("respectGitignore"), "{}", check.detail);
+    }
+
+    #[test]
+    fn node_major_parses_version_output() {
+        assert_eq!(node_major("v20.11.1"), Some(20));
+        assert_eq!(node_major("garbage"), None);
+    }
+
+    #[test]
+    fn report_lists_fixes_and_summary() {
+        let report = render_report(&[
+            Check::ok("Agent", "node bridge.js"),
+            Check::fail("Node.js", "not found on PATH", "Install Node.js."),
+        ]);
+        assert!(report.contains("  FAIL  Node.js  not found on PATH\n"), "{report}");
+        assert!(report.contains("fix: Install Node.js."), "{report}");
+        assert!(report.ends_with("1 problem(s), 0 warning(s).\n"), "{report}");
+    }
+}
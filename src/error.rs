@@ -44,9 +44,7 @@ impl AppError {
                 "Failed to establish or maintain the Agent SDK bridge connection."
             }
             Self::SessionNotFound => "The requested session was not found.",
-            Self::AuthRequired => {
-                "Authentication required. Type /login to authenticate, or run `claude auth login` in a terminal."
-            }
+            Self::AuthRequired => "Authentication required. Type /login to authenticate.",
         }
     }
 }
@@ -3,16 +3,21 @@
 
 pub mod agent;
 pub mod app;
+pub mod doctor;
 pub mod error;
+pub mod logging;
 pub mod perf;
 pub mod ui;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "claude-rs", about = "Native Rust terminal for Claude Code")]
 #[allow(clippy::struct_excessive_bools)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+
     /// Resume a previous session by ID
     #[arg(long)]
     pub resume: Option<String>,
@@ -21,6 +26,33 @@ pub struct Cli {
     #[arg(long)]
     pub no_update_check: bool,
 
+    /// Skip update and status checks, usage requests and opening links in the
+    /// browser. Also settable as `offlineMode` in the settings.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Review without changes: pin the session to plan mode and deny every
+    /// edit, write or shell command the agent asks to run.
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Print the conversation as markdown to stdout after exiting, so it
+    /// stays in the terminal scrollback. `/transcript` turns this on from
+    /// inside a session.
+    #[arg(long)]
+    pub print_transcript: bool,
+
+    /// Screen reader mode: no spinners or animations, tool and connection
+    /// state spelled out as text. Also settable as `screenReaderMode` in the
+    /// settings.
+    #[arg(long)]
+    pub screen_reader: bool,
+
+    /// Append each finished assistant reply as plain text to this file, tty
+    /// or FIFO, so a screen reader can follow it outside the TUI.
+    #[arg(long, value_name = "PATH")]
+    pub screen_reader_mirror: Option<std::path::PathBuf>,
+
     /// Working directory (defaults to cwd)
     #[arg(long, short = 'C')]
     pub dir: Option<std::path::PathBuf>,
@@ -29,19 +61,50 @@ pub struct Cli {
     #[arg(long)]
     pub bridge_script: Option<std::path::PathBuf>,
 
-    /// Write tracing diagnostics to a file (disabled unless explicitly set).
+    /// Agent profile to launch instead of the bundled Claude bridge
+    /// (configured in `agents.json` under the config directory).
+    #[arg(long, value_name = "PROFILE", conflicts_with = "bridge_script")]
+    pub agent: Option<String>,
+
+    /// Run against a built-in scripted agent instead of Claude Code, for
+    /// demos, screenshots and end-to-end tests.
+    #[arg(long, conflicts_with_all = ["bridge_script", "agent", "resume"])]
+    pub mock: bool,
+
+    /// Write plain-text tracing diagnostics to this file instead of the
+    /// rotating JSON log under the cache directory.
     #[arg(long, value_name = "PATH")]
     pub log_file: Option<std::path::PathBuf>,
 
     /// Tracing filter directives (example: `info,claude_code_rust::ui=trace`).
-    /// Falls back to `RUST_LOG` when omitted.
+    /// Falls back to `RUST_LOG`, then `info`, when neither this nor
+    /// `--log-level` is given.
     #[arg(long, value_name = "FILTER")]
     pub log_filter: Option<String>,
 
+    /// Log level for all targets. `/log-level` changes it at runtime.
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        conflicts_with = "log_filter",
+        value_parser = clap::builder::PossibleValuesParser::new(logging::LOG_LEVELS)
+    )]
+    pub log_level: Option<String>,
+
     /// Append to `--log-file` instead of truncating on startup.
     #[arg(long)]
     pub log_append: bool,
 
+    /// Memory budget in MiB for messages, render caches and terminal output.
+    /// Cold caches and old terminal output are trimmed once it is exceeded.
+    #[arg(long, value_name = "MIB", value_parser = clap::value_parser!(u64).range(16..))]
+    pub memory_budget: Option<u64>,
+
+    /// Append every command sent to the agent and every event received from
+    /// it to this file as JSON lines, for dashboards and auditing.
+    #[arg(long, value_name = "PATH")]
+    pub tee_events: Option<std::path::PathBuf>,
+
     /// Write frame performance events to a file (requires `--features perf` build).
     #[arg(long, value_name = "PATH")]
     pub perf_log: Option<std::path::PathBuf>,
@@ -49,4 +112,31 @@ pub struct Cli {
     /// Append to `--perf-log` instead of truncating on startup.
     #[arg(long)]
     pub perf_append: bool,
+
+    /// Write perf spans as a Chrome trace, viewable in `chrome://tracing` or
+    /// Perfetto (requires `--features perf` build).
+    #[arg(long, value_name = "PATH")]
+    pub perf_trace: Option<std::path::PathBuf>,
+
+    /// PEM bundle of extra CA certificates to trust for outbound HTTPS.
+    /// Falls back to `CLAUDE_RS_CA_BUNDLE`, then `NODE_EXTRA_CA_CERTS`.
+    #[arg(long, value_name = "PATH")]
+    pub ca_bundle: Option<std::path::PathBuf>,
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CliCommand {
+    /// Check the agent, Node.js, terminal, settings and cache directory, and
+    /// print fixes for any problems found.
+    Doctor,
+    /// Render a canned UI state to stdout, for reproducing layout reports.
+    #[command(hide = true)]
+    RenderFixture {
+        #[arg(value_enum)]
+        fixture: ui::render_fixture::Fixture,
+        #[arg(long, default_value_t = 80)]
+        width: u16,
+        #[arg(long, default_value_t = 24)]
+        height: u16,
+    },
 }
@@ -0,0 +1,229 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracing setup. By default logs are written as JSON lines to a size-rotated
+//! file under the cache directory; `--log-file` writes plain text to the given
+//! path instead. The level filter sits behind a reload handle so `/log-level`
+//! can change it without a restart. Bridge and client events run inside spans
+//! carrying the session id, so one session can be followed across both sides.
+
+use crate::Cli;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing_subscriber::layer::SubscriberExt as _;
+use tracing_subscriber::util::SubscriberInitExt as _;
+use tracing_subscriber::{EnvFilter, Registry, fmt, reload};
+
+pub const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Levels accepted by `--log-level`.
+pub const LOG_LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+const CACHE_DIR_NAME: &str = "claude-code-rust";
+const LOG_DIR_NAME: &str = "logs";
+const LOG_FILE_NAME: &str = "claude-rs.log";
+
+/// Size at which the active log file is rotated.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept next to the active one (`claude-rs.log.1` is newest).
+const KEPT_LOG_FILES: usize = 3;
+
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Install the global subscriber. Fails only for an explicit `--log-file`
+/// that cannot be opened or an invalid filter; when the cache directory is
+/// unusable the app runs without a log.
+pub fn init(cli: &Cli) -> anyhow::Result<()> {
+    let directives = cli
+        .log_filter
+        .clone()
+        .or_else(|| cli.log_level.clone())
+        .or_else(|| std::env::var("RUST_LOG").ok())
+        .unwrap_or_else(|| DEFAULT_LOG_FILTER.to_owned());
+    let filter = EnvFilter::try_new(directives.as_str())
+        .map_err(|e| anyhow::anyhow!("invalid tracing filter `{directives}`: {e}"))?;
+    let (filter, handle) = reload::Layer::new(filter);
+    let registry = tracing_subscriber::registry().with(filter);
+
+    let path = if let Some(path) = cli.log_file.as_ref() {
+        let mut options = OpenOptions::new();
+        options.create(true).write(true);
+        if cli.log_append {
+            options.append(true);
+        } else {
+            options.truncate(true);
+        }
+        let file = options
+            .open(path)
+            .map_err(|e| anyhow::anyhow!("failed to open log file {}: {e}", path.display()))?;
+        registry
+            .with(
+                fmt::layer()
+                    .with_writer(file)
+                    .with_ansi(false)
+                    .with_file(true)
+                    .with_line_number(true)
+                    .with_target(true),
+            )
+            .try_init()
+            .map_err(|e| anyhow::anyhow!("failed to initialize tracing subscriber: {e}"))?;
+        path.clone()
+    } else {
+        let Some(dir) = dirs::cache_dir().map(|dir| dir.join(CACHE_DIR_NAME).join(LOG_DIR_NAME))
+        else {
+            return Ok(());
+        };
+        let path = dir.join(LOG_FILE_NAME);
+        let writer = match std::fs::create_dir_all(&dir)
+            .and_then(|()| RotatingFile::open(&path, MAX_LOG_BYTES, KEPT_LOG_FILES))
+        {
+            Ok(writer) => writer,
+            Err(err) => {
+                eprintln!("Logging disabled: cannot write {}: {err}", path.display());
+                return Ok(());
+            }
+        };
+        registry
+            .with(
+                fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(false)
+                    .with_file(true)
+                    .with_line_number(true)
+                    .with_writer(move || writer.clone()),
+            )
+            .try_init()
+            .map_err(|e| anyhow::anyhow!("failed to initialize tracing subscriber: {e}"))?;
+        path
+    };
+
+    tracing::info!(
+        target: "diagnostics",
+        version = env!("CARGO_PKG_VERSION"),
+        log_file = %path.display(),
+        log_filter = %directives,
+        log_append = cli.log_append,
+        "tracing enabled"
+    );
+    let _ = FILTER.set(handle);
+    let _ = LOG_PATH.set(path);
+    Ok(())
+}
+
+/// Replace the active filter, e.g. `debug` or `info,claude_code_rust::ui=trace`.
+pub fn set_filter(directives: &str) -> Result<(), String> {
+    let handle = FILTER.get().ok_or_else(|| "Logging is not enabled.".to_owned())?;
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| format!("Invalid log filter `{directives}`: {e}"))?;
+    handle.reload(filter).map_err(|e| format!("Failed to change the log filter: {e}"))?;
+    tracing::info!(target: "diagnostics", log_filter = directives, "log filter changed");
+    Ok(())
+}
+
+/// The filter in effect, or `None` while logging is off.
+#[must_use]
+pub fn current_filter() -> Option<String> {
+    FILTER.get()?.with_current(ToString::to_string).ok()
+}
+
+/// File the log is written to, or `None` while logging is off.
+#[must_use]
+pub fn log_path() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+/// Append-only log file that rolls over to `<name>.1`, `<name>.2`, ... once
+/// it reaches `max_bytes`, keeping at most `keep` old files.
+#[derive(Clone)]
+struct RotatingFile(Arc<Mutex<RotatingState>>);
+
+struct RotatingState {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    fn open(path: &Path, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self(Arc::new(Mutex::new(RotatingState {
+            path: path.to_path_buf(),
+            file,
+            written,
+            max_bytes,
+            keep,
+        }))))
+    }
+}
+
+impl RotatingState {
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for index in (1..self.keep).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.0.lock().map_err(|_| io::Error::other("log file lock poisoned"))?;
+        let len = u64::try_from(buf.len()).unwrap_or(u64::MAX);
+        if state.written > 0 && state.written.saturating_add(len) > state.max_bytes {
+            state.rotate()?;
+        }
+        let written = state.file.write(buf)?;
+        state.written += u64::try_from(written).unwrap_or(u64::MAX);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().map_err(|_| io::Error::other("log file lock poisoned"))?.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn rotating_file_rolls_over_and_keeps_the_newest_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("app.log");
+        let mut log = RotatingFile::open(&path, 10, 2).expect("open log");
+        for line in ["first-line\n", "second-line\n", "third-line\n", "fourth-line\n"] {
+            log.write_all(line.as_bytes()).expect("write");
+        }
+        log.flush().expect("flush");
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).expect("read");
+        assert_eq!(read("app.log"), "fourth-line\n");
+        assert_eq!(read("app.log.1"), "third-line\n");
+        assert_eq!(read("app.log.2"), "second-line\n");
+        assert!(!dir.path().join("app.log.3").exists());
+    }
+}
@@ -2,9 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
-use claude_code_rust::Cli;
 use claude_code_rust::error::AppError;
-use std::fs::OpenOptions;
+use claude_code_rust::{Cli, CliCommand};
 use std::time::Instant;
 
 #[allow(clippy::exit)]
@@ -21,21 +20,52 @@ fn main() {
 
 fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    init_tracing(&cli)?;
+    claude_code_rust::logging::init(&cli)?;
+
+    if cli.command == Some(CliCommand::Doctor) {
+        return match claude_code_rust::doctor::run(&cli) {
+            0 => Ok(()),
+            failures => Err(anyhow::anyhow!("doctor: {failures} check(s) failed")),
+        };
+    }
+
+    if let Some(CliCommand::RenderFixture { fixture, width, height }) = cli.command {
+        let mut app = fixture.build();
+        print!(
+            "{}",
+            claude_code_rust::ui::render_fixture::render_to_string(&mut app, width, height)
+        );
+        return Ok(());
+    }
 
     #[cfg(not(feature = "perf"))]
     if cli.perf_log.is_some() {
         return Err(anyhow::anyhow!("`--perf-log` requires a binary built with `--features perf`"));
     }
+    #[cfg(not(feature = "perf"))]
+    if cli.perf_trace.is_some() {
+        return Err(anyhow::anyhow!(
+            "`--perf-trace` requires a binary built with `--features perf`"
+        ));
+    }
+    if let Some(path) = cli.perf_trace.as_deref() {
+        claude_code_rust::perf::trace::open(path).map_err(|e| {
+            anyhow::anyhow!("failed to create perf trace `{}`: {e}", path.display())
+        })?;
+    }
 
-    let resolve_started = Instant::now();
-    let bridge_launcher =
-        claude_code_rust::agent::bridge::resolve_bridge_launcher(cli.bridge_script.as_deref())?;
-    tracing::info!(
-        "Resolved agent bridge launcher in {:?}: {}",
-        resolve_started.elapsed(),
-        bridge_launcher.describe()
-    );
+    if !cli.mock {
+        let resolve_started = Instant::now();
+        let bridge_launcher = claude_code_rust::agent::bridge::resolve_agent_launcher(
+            cli.agent.as_deref(),
+            cli.bridge_script.as_deref(),
+        )?;
+        tracing::info!(
+            "Resolved agent bridge launcher in {:?}: {}",
+            resolve_started.elapsed(),
+            bridge_launcher.describe()
+        );
+    }
 
     let rt = tokio::runtime::Runtime::new()?;
     let local_set = tokio::task::LocalSet::new();
@@ -49,11 +79,16 @@ fn run() -> anyhow::Result<()> {
         claude_code_rust::app::start_update_check(&app, &cli);
         claude_code_rust::app::start_service_status_check(&app);
         let result = claude_code_rust::app::run_tui(&mut app).await;
-        maybe_print_resume_hint(&app, result.is_ok());
+        maybe_print_transcript(&app, result.is_ok());
+        maybe_print_quit_summary(&app, result.is_ok());
 
         // Kill any spawned terminal child processes before exiting
         claude_code_rust::agent::events::kill_all_terminals(&app.terminals);
 
+        if let Err(err) = claude_code_rust::perf::trace::finish() {
+            tracing::warn!("failed to finish perf trace: {err}");
+        }
+
         if let Some(app_error) = app.exit_error.take() {
             return Err(anyhow::Error::new(app_error));
         }
@@ -66,64 +101,20 @@ fn extract_app_error(err: &anyhow::Error) -> Option<AppError> {
     err.chain().find_map(|cause| cause.downcast_ref::<AppError>().cloned())
 }
 
-fn init_tracing(cli: &Cli) -> anyhow::Result<()> {
-    let Some(path) = cli.log_file.as_ref() else {
-        if std::env::var_os("RUST_LOG").is_some() {
-            eprintln!(
-                "RUST_LOG is set, but tracing is disabled without --log-file <PATH>. \
-Use --log-file to enable diagnostics."
-            );
-        }
-        return Ok(());
-    };
-
-    let directives = cli
-        .log_filter
-        .clone()
-        .or_else(|| std::env::var("RUST_LOG").ok())
-        .unwrap_or_else(|| "info".to_owned());
-    let filter = tracing_subscriber::EnvFilter::try_new(directives.as_str())
-        .map_err(|e| anyhow::anyhow!("invalid tracing filter `{directives}`: {e}"))?;
-
-    let mut options = OpenOptions::new();
-    options.create(true).write(true);
-    if cli.log_append {
-        options.append(true);
-    } else {
-        options.truncate(true);
+fn maybe_print_transcript(app: &claude_code_rust::app::App, success: bool) {
+    if !success || !app.print_transcript_on_exit {
+        return;
+    }
+    if let Some(transcript) = claude_code_rust::app::transcript_markdown(app) {
+        println!("{transcript}\n");
     }
-    let file = options
-        .open(path)
-        .map_err(|e| anyhow::anyhow!("failed to open log file {}: {e}", path.display()))?;
-
-    tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_writer(file)
-        .with_ansi(false)
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(true)
-        .try_init()
-        .map_err(|e| anyhow::anyhow!("failed to initialize tracing subscriber: {e}"))?;
-
-    tracing::info!(
-        target: "diagnostics",
-        version = env!("CARGO_PKG_VERSION"),
-        log_file = %path.display(),
-        log_filter = %directives,
-        log_append = cli.log_append,
-        "tracing enabled"
-    );
-
-    Ok(())
 }
 
-fn maybe_print_resume_hint(app: &claude_code_rust::app::App, success: bool) {
+fn maybe_print_quit_summary(app: &claude_code_rust::app::App, success: bool) {
     if !success {
         return;
     }
-    let Some(session_id) = app.session_id.as_ref() else {
-        return;
-    };
-    eprintln!("Resume this session: claude-rs --resume {session_id}");
+    if let Some(summary) = claude_code_rust::app::quit_summary(app) {
+        println!("{summary}");
+    }
 }
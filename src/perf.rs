@@ -13,6 +13,10 @@
 //! # Writes JSON lines:
 //! # {"run":"...","frame":1234,"ts_ms":1739599900793,"fn":"chat::render_msgs","ms":2.345,"n":42}
 //! ```
+//!
+//! The same timers and markers also feed the in-app HUD (F12, see [`hud`]),
+//! which needs no log file, and `--perf-trace <PATH>` (see [`trace`]), which
+//! writes them as a Chrome trace for `chrome://tracing` or Perfetto.
 
 #[cfg(feature = "perf")]
 mod enabled {
@@ -123,12 +127,12 @@ mod enabled {
 
         /// Log an instant marker for the current frame (`ms = 0`).
         pub fn mark(&self, name: &'static str) {
-            write_entry(name, 0.0, None);
+            super::mark(name);
         }
 
         /// Log an instant marker with an extra numeric field (`ms = 0`).
         pub fn mark_with(&self, name: &'static str, extra_name: &'static str, extra_val: usize) {
-            write_entry(name, 0.0, Some((extra_name, extra_val)));
+            super::mark_with(name, extra_name, extra_val);
         }
     }
 
@@ -150,6 +154,8 @@ mod enabled {
         fn drop(&mut self) {
             let ms = self.start.elapsed().as_secs_f64() * 1000.0;
             write_entry(self.name, ms, self.extra);
+            super::hud::record_span(self.name, ms);
+            super::trace::record_span(self.name, self.start, ms, self.extra);
         }
     }
 }
@@ -198,37 +204,31 @@ mod disabled {
     }
 }
 
+/// Whether the log file, the HUD or the trace wants timings.
+#[cfg(feature = "perf")]
+fn is_collecting() -> bool {
+    enabled::LOG_FILE.with(|f| f.borrow().is_some()) || hud::is_active() || trace::is_active()
+}
+
 /// Start a timer without needing a `PerfLogger` reference.
-/// Uses the thread-local log file directly. Returns `None` (and is a no-op)
-/// when the `perf` feature is disabled or no logger has been opened.
+/// Returns `None` (and is a no-op) when the `perf` feature is disabled or
+/// neither the log, the HUD nor the trace is collecting.
 #[cfg(feature = "perf")]
 #[must_use]
 #[inline]
 pub fn start(name: &'static str) -> Option<Timer> {
-    // Only create a timer if the log file is actually open
-    enabled::LOG_FILE.with(|f| {
-        if f.borrow().is_some() {
-            Some(Timer { name, start: std::time::Instant::now(), extra: None })
-        } else {
-            None
-        }
-    })
+    // Only create a timer if some sink is collecting
+    is_collecting().then(|| Timer { name, start: std::time::Instant::now(), extra: None })
 }
 
 #[cfg(feature = "perf")]
 #[must_use]
 #[inline]
 pub fn start_with(name: &'static str, extra_name: &'static str, extra_val: usize) -> Option<Timer> {
-    enabled::LOG_FILE.with(|f| {
-        if f.borrow().is_some() {
-            Some(Timer {
-                name,
-                start: std::time::Instant::now(),
-                extra: Some((extra_name, extra_val)),
-            })
-        } else {
-            None
-        }
+    is_collecting().then(|| Timer {
+        name,
+        start: std::time::Instant::now(),
+        extra: Some((extra_name, extra_val)),
     })
 }
 
@@ -255,6 +255,8 @@ pub fn start_with(
 #[inline]
 pub fn mark(name: &'static str) {
     enabled::write_entry(name, 0.0, None);
+    hud::record_mark(name, None);
+    trace::record_mark(name, None);
 }
 
 #[cfg(not(feature = "perf"))]
@@ -266,6 +268,8 @@ pub fn mark(_name: &'static str) {}
 #[inline]
 pub fn mark_with(name: &'static str, extra_name: &'static str, extra_val: usize) {
     enabled::write_entry(name, 0.0, Some((extra_name, extra_val)));
+    hud::record_mark(name, Some(extra_val));
+    trace::record_mark(name, Some((extra_name, extra_val)));
 }
 
 #[cfg(not(feature = "perf"))]
@@ -277,3 +281,375 @@ pub use enabled::{PerfLogger, Timer};
 
 #[cfg(not(feature = "perf"))]
 pub use disabled::{PerfLogger, Timer};
+
+/// Rolling stats for the in-app perf HUD. Collection only runs in `perf`
+/// builds and only while the HUD is open; otherwise every call is a no-op.
+pub mod hud {
+    #[cfg(feature = "perf")]
+    use std::cell::RefCell;
+    use std::collections::{HashMap, VecDeque};
+
+    /// Samples kept per span, about two seconds of frames at 60 fps.
+    pub const WINDOW: usize = 120;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SpanSummary {
+        pub avg_ms: f64,
+        pub max_ms: f64,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct HudStats {
+        spans: HashMap<&'static str, VecDeque<f64>>,
+        counts: HashMap<&'static str, u64>,
+        values: HashMap<&'static str, usize>,
+    }
+
+    impl HudStats {
+        pub fn record_span(&mut self, name: &'static str, ms: f64) {
+            let samples = self.spans.entry(name).or_default();
+            if samples.len() == WINDOW {
+                samples.pop_front();
+            }
+            samples.push_back(ms);
+        }
+
+        /// Count a marker and remember its numeric field, if any.
+        pub fn record_mark(&mut self, name: &'static str, value: Option<usize>) {
+            *self.counts.entry(name).or_default() += 1;
+            if let Some(value) = value {
+                self.values.insert(name, value);
+            }
+        }
+
+        /// Average and maximum over the rolling window.
+        #[must_use]
+        pub fn span(&self, name: &str) -> Option<SpanSummary> {
+            let samples = self.spans.get(name).filter(|samples| !samples.is_empty())?;
+            let len = f64::from(u32::try_from(samples.len()).unwrap_or(u32::MAX));
+            Some(SpanSummary {
+                avg_ms: samples.iter().sum::<f64>() / len,
+                max_ms: samples.iter().copied().fold(0.0, f64::max),
+            })
+        }
+
+        /// Times the marker fired since the HUD was opened.
+        #[must_use]
+        pub fn count(&self, name: &str) -> u64 {
+            self.counts.get(name).copied().unwrap_or(0)
+        }
+
+        /// Numeric field of the marker's latest firing.
+        #[must_use]
+        pub fn value(&self, name: &str) -> Option<usize> {
+            self.values.get(name).copied()
+        }
+
+        /// Hit rate in tenths of a percent, summing counts across names.
+        /// `None` until any of the markers fired.
+        #[must_use]
+        pub fn hit_rate_permille(&self, hits: &[&str], misses: &[&str]) -> Option<u64> {
+            let hits: u64 = hits.iter().map(|name| self.count(name)).sum();
+            let misses: u64 = misses.iter().map(|name| self.count(name)).sum();
+            let total = hits + misses;
+            (total > 0).then(|| hits * 1000 / total)
+        }
+    }
+
+    #[cfg(feature = "perf")]
+    thread_local! {
+        static STATS: RefCell<Option<HudStats>> = const { RefCell::new(None) };
+    }
+
+    /// Open or close the HUD. Returns whether it is now open; always `false`
+    /// without the `perf` feature.
+    #[cfg(feature = "perf")]
+    pub fn toggle() -> bool {
+        STATS.with(|stats| {
+            let mut stats = stats.borrow_mut();
+            if stats.take().is_some() {
+                false
+            } else {
+                *stats = Some(HudStats::default());
+                true
+            }
+        })
+    }
+
+    #[cfg(not(feature = "perf"))]
+    pub fn toggle() -> bool {
+        false
+    }
+
+    #[cfg(feature = "perf")]
+    #[must_use]
+    pub fn is_active() -> bool {
+        STATS.with(|stats| stats.borrow().is_some())
+    }
+
+    #[cfg(not(feature = "perf"))]
+    #[must_use]
+    pub fn is_active() -> bool {
+        false
+    }
+
+    /// Run `f` on the collected stats while the HUD is open.
+    #[cfg(feature = "perf")]
+    pub fn with_stats<R>(f: impl FnOnce(&HudStats) -> R) -> Option<R> {
+        STATS.with(|stats| stats.borrow().as_ref().map(f))
+    }
+
+    #[cfg(not(feature = "perf"))]
+    pub fn with_stats<R>(_f: impl FnOnce(&HudStats) -> R) -> Option<R> {
+        None
+    }
+
+    #[cfg(feature = "perf")]
+    pub(crate) fn record_span(name: &'static str, ms: f64) {
+        STATS.with(|stats| {
+            if let Some(stats) = stats.borrow_mut().as_mut() {
+                stats.record_span(name, ms);
+            }
+        });
+    }
+
+    #[cfg(feature = "perf")]
+    pub(crate) fn record_mark(name: &'static str, value: Option<usize>) {
+        STATS.with(|stats| {
+            if let Some(stats) = stats.borrow_mut().as_mut() {
+                stats.record_mark(name, value);
+            }
+        });
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn spans_roll_over_the_window_and_hit_rates_sum_markers() {
+            let mut stats = HudStats::default();
+            assert_eq!(stats.span("frame_total"), None);
+            for _ in 0..WINDOW {
+                stats.record_span("frame_total", 10.0);
+            }
+            stats.record_span("frame_total", 4.0);
+            let summary = stats.span("frame_total").map(|s| (s.max_ms, s.avg_ms < 10.0));
+            assert_eq!(summary, Some((10.0, true)));
+
+            assert_eq!(stats.hit_rate_permille(&["tc::cache_hit"], &["tc::cache_miss"]), None);
+            stats.record_mark("tc::cache_hit", Some(12));
+            stats.record_mark("tc::cache_hit_execute", None);
+            stats.record_mark("tc::cache_hit", Some(7));
+            stats.record_mark("tc::cache_miss", None);
+            let rate = stats.hit_rate_permille(
+                &["tc::cache_hit", "tc::cache_hit_execute"],
+                &["tc::cache_miss"],
+            );
+            assert_eq!(rate, Some(750));
+            assert_eq!(stats.value("tc::cache_hit"), Some(7));
+            assert_eq!(stats.count("tc::cache_miss"), 1);
+        }
+    }
+}
+
+/// Chrome trace export for `--perf-trace <PATH>`. Timers become complete
+/// (`"ph":"X"`) events and markers become counters or instant events, so a
+/// trace attached to an issue opens in `chrome://tracing` or
+/// <https://ui.perfetto.dev> as is.
+pub mod trace {
+    use std::io;
+    use std::path::Path;
+    #[cfg(feature = "perf")]
+    use std::{
+        cell::RefCell,
+        fs::File,
+        io::{BufWriter, Write},
+        time::Instant,
+    };
+
+    /// Events written between flushes.
+    #[cfg(feature = "perf")]
+    const FLUSH_EVERY: u64 = 1024;
+
+    #[cfg(feature = "perf")]
+    struct TraceWriter {
+        out: BufWriter<File>,
+        /// Trace timestamps are microseconds since this instant.
+        origin: Instant,
+        pid: u32,
+        events: u64,
+    }
+
+    #[cfg(feature = "perf")]
+    impl TraceWriter {
+        fn timestamp_us(&self, at: Instant) -> f64 {
+            at.saturating_duration_since(self.origin).as_secs_f64() * 1_000_000.0
+        }
+
+        /// Append one event. Every event follows the metadata record written on
+        /// open, so each is prefixed with a separator.
+        fn push(&mut self, event: &str) {
+            let _ = write!(self.out, ",\n{event}");
+            self.events += 1;
+            if self.events.is_multiple_of(FLUSH_EVERY) {
+                let _ = self.out.flush();
+            }
+        }
+    }
+
+    #[cfg(feature = "perf")]
+    thread_local! {
+        static TRACE: RefCell<Option<TraceWriter>> = const { RefCell::new(None) };
+    }
+
+    /// Create the trace file and start recording on this thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error when the file cannot be created.
+    #[cfg(feature = "perf")]
+    pub fn open(path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        let pid = std::process::id();
+        write!(
+            out,
+            r#"[
+{{"name":"process_name","ph":"M","pid":{pid},"tid":1,"args":{{"name":"claude-rs {}"}}}}"#,
+            env!("CARGO_PKG_VERSION")
+        )?;
+        TRACE.with(|trace| {
+            *trace.borrow_mut() = Some(TraceWriter { out, origin: Instant::now(), pid, events: 0 });
+        });
+        Ok(())
+    }
+
+    /// No-op without the `perf` feature.
+    ///
+    /// # Errors
+    ///
+    /// Never fails.
+    #[cfg(not(feature = "perf"))]
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn open(_path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Close the JSON array and flush. A trace cut short by a crash still
+    /// loads, since both viewers accept an unterminated array.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error when the tail cannot be written.
+    #[cfg(feature = "perf")]
+    pub fn finish() -> io::Result<()> {
+        let Some(mut writer) = TRACE.with(|trace| trace.borrow_mut().take()) else {
+            return Ok(());
+        };
+        writer.out.write_all(b"\n]\n")?;
+        writer.out.flush()
+    }
+
+    /// No-op without the `perf` feature.
+    ///
+    /// # Errors
+    ///
+    /// Never fails.
+    #[cfg(not(feature = "perf"))]
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn finish() -> io::Result<()> {
+        Ok(())
+    }
+
+    #[cfg(feature = "perf")]
+    #[must_use]
+    pub fn is_active() -> bool {
+        TRACE.with(|trace| trace.borrow().is_some())
+    }
+
+    #[cfg(not(feature = "perf"))]
+    #[must_use]
+    pub fn is_active() -> bool {
+        false
+    }
+
+    /// Category shown in the viewer: the span name's module prefix.
+    #[cfg(feature = "perf")]
+    fn category(name: &str) -> &str {
+        name.split_once("::").map_or(name, |(prefix, _)| prefix)
+    }
+
+    #[cfg(feature = "perf")]
+    pub(crate) fn record_span(
+        name: &'static str,
+        start: Instant,
+        ms: f64,
+        extra: Option<(&'static str, usize)>,
+    ) {
+        TRACE.with(|trace| {
+            let mut trace = trace.borrow_mut();
+            let Some(writer) = trace.as_mut() else {
+                return;
+            };
+            let ts = writer.timestamp_us(start);
+            let dur = ms * 1000.0;
+            let args = extra.map_or_else(String::new, |(k, v)| format!(r#","args":{{"{k}":{v}}}"#));
+            let event = format!(
+                r#"{{"name":"{name}","cat":"{}","ph":"X","ts":{ts:.3},"dur":{dur:.3},"pid":{},"tid":1{args}}}"#,
+                category(name),
+                writer.pid
+            );
+            writer.push(&event);
+        });
+    }
+
+    #[cfg(feature = "perf")]
+    pub(crate) fn record_mark(name: &'static str, extra: Option<(&'static str, usize)>) {
+        TRACE.with(|trace| {
+            let mut trace = trace.borrow_mut();
+            let Some(writer) = trace.as_mut() else {
+                return;
+            };
+            let ts = writer.timestamp_us(Instant::now());
+            let pid = writer.pid;
+            let event = match extra {
+                Some((k, v)) => format!(
+                    r#"{{"name":"{name}","ph":"C","ts":{ts:.3},"pid":{pid},"tid":1,"args":{{"{k}":{v}}}}}"#
+                ),
+                None => format!(
+                    r#"{{"name":"{name}","cat":"{}","ph":"i","s":"t","ts":{ts:.3},"pid":{pid},"tid":1}}"#,
+                    category(name)
+                ),
+            };
+            writer.push(&event);
+        });
+    }
+
+    #[cfg(all(test, feature = "perf"))]
+    mod tests {
+        use super::*;
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn trace_file_is_a_json_array_of_chrome_events() {
+            let dir = tempfile::tempdir().expect("tempdir");
+            let path = dir.path().join("trace.json");
+            open(&path).expect("open trace");
+            record_span("chat::update_heights", Instant::now(), 1.5, Some(("msgs", 3)));
+            record_mark("chat::content_height", Some(("rows", 40)));
+            record_mark("msg::cache_miss", None);
+            finish().expect("finish trace");
+            assert!(!is_active());
+
+            let raw = std::fs::read_to_string(&path).expect("read trace");
+            let events: Vec<serde_json::Value> = serde_json::from_str(&raw).expect("valid json");
+            let phases: Vec<&str> = events.iter().filter_map(|e| e["ph"].as_str()).collect();
+            assert_eq!(phases, vec!["M", "X", "C", "i"]);
+            assert_eq!(events[1]["cat"], "chat");
+            assert_eq!(events[1]["dur"], 1500.0);
+            assert_eq!(events[1]["args"]["msgs"], 3);
+            assert_eq!(events[2]["args"]["rows"], 40);
+        }
+    }
+}
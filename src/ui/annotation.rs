@@ -0,0 +1,62 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::config::text_input_line;
+use crate::app::App;
+use crate::app::annotations::AnnotationKind;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
+
+const MAX_EDITOR_WIDTH: u16 = 72;
+
+/// Draw the open message note or bookmark name editor centered over the
+/// current view.
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(editor) = app.annotation_editor.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    let width = MAX_EDITOR_WIDTH.min(area.width.saturating_sub(4));
+    if width < 16 || area.height < 3 {
+        return;
+    }
+    let editor_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - 3) / 2,
+        width,
+        height: 3,
+    };
+    let (title, hint, placeholder) = match editor.kind {
+        AnnotationKind::Note => (
+            " Note ",
+            " Enter save \u{b7} Esc cancel \u{b7} empty removes ",
+            "Local note, never sent to the agent",
+        ),
+        AnnotationKind::Bookmark => {
+            (" Bookmark ", " Enter save \u{b7} Esc cancel ", "Bookmark name")
+        }
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(hint, Style::default().fg(theme::DIM)))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::RUST_ORANGE))
+        .padding(Padding::horizontal(1));
+
+    // Keep the cursor in view on long notes.
+    let visible = usize::from(width.saturating_sub(5));
+    let skip = editor.input.cursor.saturating_sub(visible);
+    let draft: String = editor.input.text.chars().skip(skip).collect();
+    let line: Line<'static> = text_input_line(&draft, editor.input.cursor - skip, placeholder);
+
+    frame.render_widget(Clear, editor_area);
+    frame.render_widget(Paragraph::new(line).block(block), editor_area);
+}
@@ -2,15 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::app::App;
+use crate::app::fuzzy::{fuzzy_match, highlight_ranges};
 use crate::app::mention::MAX_VISIBLE;
-use crate::app::{mention, slash, subagent};
+use crate::app::{emoji, mention, slash, subagent, symbols};
 use crate::ui::theme;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
-use unicode_width::UnicodeWidthChar;
 
 /// Horizontal padding to match input inset.
 const INPUT_PAD: u16 = 2;
@@ -24,11 +24,17 @@ const MAX_WIDTH: u16 = 60;
 const MIN_WIDTH: u16 = 20;
 /// Vertical gap (in rows) between the trigger line and the dropdown.
 const ANCHOR_VERTICAL_GAP: u16 = 1;
+/// Preview pane width bounds; below the minimum the pane is not shown.
+const MAX_PREVIEW_WIDTH: u16 = 60;
+const MIN_PREVIEW_WIDTH: u16 = 24;
+/// Columns between the dropdown and the preview pane.
+const PREVIEW_GAP: u16 = 1;
 
 enum Dropdown<'a> {
     Mention(&'a mention::MentionState),
     Slash(&'a slash::SlashState),
     Subagent(&'a subagent::SubagentState),
+    Emoji(&'a emoji::EmojiState),
 }
 
 struct DropdownMeta {
@@ -36,22 +42,27 @@ struct DropdownMeta {
     start: usize,
     end: usize,
     title: String,
+    /// Bottom-border notice (e.g. how a binary or image mention will be sent).
+    footer: Option<String>,
 }
 
 pub fn is_active(app: &App) -> bool {
     app.mention.is_some()
         || app.slash.as_ref().is_some_and(|s| !s.candidates.is_empty())
         || app.subagent.as_ref().is_some_and(|s| !s.candidates.is_empty())
+        || app.emoji.as_ref().is_some_and(|e| !e.candidates.is_empty())
 }
 
 #[allow(clippy::cast_possible_truncation)]
 pub fn compute_height(app: &App) -> u16 {
     let count = if let Some(m) = &app.mention {
-        m.candidates.len().max(1)
+        m.candidate_count().max(1)
     } else if let Some(s) = &app.slash {
         s.candidates.len()
     } else if let Some(s) = &app.subagent {
         s.candidates.len()
+    } else if let Some(e) = &app.emoji {
+        e.candidates.len()
     } else {
         0
     };
@@ -98,15 +109,104 @@ pub fn render(frame: &mut Frame, input_area: Rect, app: &App) {
     let meta = dropdown_meta(&dropdown);
     let lines = dropdown_lines(&dropdown, &meta);
 
-    let block = Block::default()
+    let mut block = Block::default()
         .title(Span::styled(meta.title, Style::default().fg(theme::DIM)))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_style(Style::default().fg(theme::DIM));
+    if let Some(footer) = meta.footer {
+        block = block.title_bottom(Span::styled(
+            format!(" {footer} "),
+            Style::default().fg(theme::STATUS_WARNING),
+        ));
+    }
 
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(ratatui::widgets::Clear, dropdown_area);
     frame.render_widget(paragraph, dropdown_area);
+
+    if let Dropdown::Mention(m) = &dropdown
+        && let Some(preview) = m.preview()
+    {
+        render_preview(frame, dropdown_area, y < anchor_y, preview);
+    }
+}
+
+/// Render the highlighted file's first lines beside the dropdown, when there is room.
+fn render_preview(
+    frame: &mut Frame,
+    dropdown_area: Rect,
+    above_anchor: bool,
+    preview: &mention::MentionPreview,
+) {
+    let line_count = u16::try_from(preview.lines.len()).unwrap_or(u16::MAX).max(1);
+    let Some(area) = choose_preview_area(
+        dropdown_area,
+        frame.area(),
+        line_count.saturating_add(2),
+        above_anchor,
+    ) else {
+        return;
+    };
+
+    let number_width = preview.lines.len().max(1).to_string().len();
+    let lines: Vec<Line<'static>> = if preview.lines.is_empty() {
+        vec![Line::from(Span::styled(" (empty file)", Style::default().fg(theme::DIM)))]
+    } else {
+        preview
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(idx, text)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>number_width$} ", idx + 1),
+                        Style::default().fg(theme::DIM),
+                    ),
+                    Span::raw(text.clone()),
+                ])
+            })
+            .collect()
+    };
+    let file_name = preview.rel_path.rsplit('/').next().unwrap_or(&preview.rel_path);
+    let block = Block::default()
+        .title(Span::styled(format!(" {file_name} "), Style::default().fg(theme::DIM)))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::DIM));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Place the preview right of the dropdown, or left when the right side is too
+/// narrow. It grows away from the input: upward when the dropdown sits above
+/// the trigger line, downward otherwise.
+fn choose_preview_area(
+    dropdown: Rect,
+    frame_area: Rect,
+    height: u16,
+    above_anchor: bool,
+) -> Option<Rect> {
+    let right_space =
+        frame_area.right().saturating_sub(dropdown.right()).saturating_sub(PREVIEW_GAP);
+    let left_space = dropdown.x.saturating_sub(frame_area.x).saturating_sub(PREVIEW_GAP);
+    let (x, width) = if right_space >= MIN_PREVIEW_WIDTH {
+        (dropdown.right().saturating_add(PREVIEW_GAP), right_space.min(MAX_PREVIEW_WIDTH))
+    } else if left_space >= MIN_PREVIEW_WIDTH {
+        let width = left_space.min(MAX_PREVIEW_WIDTH);
+        (dropdown.x.saturating_sub(PREVIEW_GAP).saturating_sub(width), width)
+    } else {
+        return None;
+    };
+
+    let height = height.min(frame_area.height);
+    let y = if above_anchor {
+        dropdown.bottom().saturating_sub(height).max(frame_area.y)
+    } else {
+        dropdown.y.min(frame_area.bottom().saturating_sub(height))
+    };
+    Some(Rect { x, y, width, height })
 }
 
 fn active_dropdown(app: &App) -> Option<Dropdown<'_>> {
@@ -123,6 +223,11 @@ fn active_dropdown(app: &App) -> Option<Dropdown<'_>> {
     {
         return Some(Dropdown::Subagent(s));
     }
+    if let Some(e) = &app.emoji
+        && !e.candidates.is_empty()
+    {
+        return Some(Dropdown::Emoji(e));
+    }
     None
 }
 
@@ -131,19 +236,32 @@ fn dropdown_trigger(dropdown: &Dropdown<'_>) -> (usize, usize) {
         Dropdown::Mention(m) => (m.trigger_row, m.trigger_col),
         Dropdown::Slash(s) => (s.trigger_row, s.trigger_col),
         Dropdown::Subagent(s) => (s.trigger_row, s.trigger_col),
+        Dropdown::Emoji(e) => (e.trigger_row, e.trigger_col),
     }
 }
 
 fn dropdown_meta(dropdown: &Dropdown<'_>) -> DropdownMeta {
     match dropdown {
         Dropdown::Mention(m) => {
-            let visible_count = m.candidates.len().clamp(1, MAX_VISIBLE);
-            let (start, end) = if m.candidates.is_empty() {
-                (0, 0)
+            let count = m.candidate_count();
+            let visible_count = count.clamp(1, MAX_VISIBLE);
+            let (start, end) =
+                if count == 0 { (0, 0) } else { m.dialog.visible_range(count, MAX_VISIBLE) };
+            let title = if let Some(choice) = &m.dir_choice {
+                format!(" {} ({} files) ", choice.dir, choice.total_files)
+            } else if let Some(file) = &m.symbol_file {
+                format!(" Symbols in {file} ")
             } else {
-                m.dialog.visible_range(m.candidates.len(), MAX_VISIBLE)
+                let selected = if m.marked.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({} selected)", m.marked.len())
+                };
+                let indexing = if m.is_indexing() { " (indexing…)" } else { "" };
+                format!(" Files & Folders{selected}{indexing} ")
             };
-            DropdownMeta { visible_count, start, end, title: " Files & Folders ".to_owned() }
+            let footer = m.selected_notice().map(str::to_owned);
+            DropdownMeta { visible_count, start, end, title, footer }
         }
         Dropdown::Slash(s) => {
             let visible_count = s.candidates.len().min(MAX_VISIBLE);
@@ -154,7 +272,7 @@ fn dropdown_meta(dropdown: &Dropdown<'_>) -> DropdownMeta {
                     format!(" {} Args ({}) ", command, s.candidates.len())
                 }
             };
-            DropdownMeta { visible_count, start, end, title }
+            DropdownMeta { visible_count, start, end, title, footer: None }
         }
         Dropdown::Subagent(s) => {
             let visible_count = s.candidates.len().min(MAX_VISIBLE);
@@ -164,6 +282,18 @@ fn dropdown_meta(dropdown: &Dropdown<'_>) -> DropdownMeta {
                 start,
                 end,
                 title: format!(" Subagents ({}) ", s.candidates.len()),
+                footer: None,
+            }
+        }
+        Dropdown::Emoji(e) => {
+            let visible_count = e.candidates.len().min(MAX_VISIBLE);
+            let (start, end) = e.dialog.visible_range(e.candidates.len(), MAX_VISIBLE);
+            DropdownMeta {
+                visible_count,
+                start,
+                end,
+                title: format!(" Emoji ({}) ", e.candidates.len()),
+                footer: None,
             }
         }
     }
@@ -173,8 +303,16 @@ fn dropdown_lines(dropdown: &Dropdown<'_>, meta: &DropdownMeta) -> Vec<Line<'sta
     let mut lines: Vec<Line<'static>> = Vec::with_capacity(meta.visible_count);
     match dropdown {
         Dropdown::Mention(m) => {
-            if m.candidates.is_empty() {
+            if m.candidate_count() == 0 {
                 lines.push(mention_placeholder_line(m));
+            } else if let Some(choice) = &m.dir_choice {
+                for (i, action) in choice.actions()[meta.start..meta.end].iter().enumerate() {
+                    lines.push(mention_directory_action_line(m, choice, *action, meta.start + i));
+                }
+            } else if m.symbol_file.is_some() {
+                for (i, symbol) in m.symbols[meta.start..meta.end].iter().enumerate() {
+                    lines.push(mention_symbol_line(m, symbol, meta.start + i));
+                }
             } else {
                 for (i, candidate) in m.candidates[meta.start..meta.end].iter().enumerate() {
                     lines.push(mention_candidate_line(m, candidate, meta.start + i));
@@ -191,6 +329,11 @@ fn dropdown_lines(dropdown: &Dropdown<'_>, meta: &DropdownMeta) -> Vec<Line<'sta
                 lines.push(subagent_candidate_line(s, candidate, meta.start + i));
             }
         }
+        Dropdown::Emoji(e) => {
+            for (i, candidate) in e.candidates[meta.start..meta.end].iter().enumerate() {
+                lines.push(emoji_candidate_line(e, candidate, meta.start + i));
+            }
+        }
     }
     lines
 }
@@ -207,16 +350,80 @@ fn mention_candidate_line(
 ) -> Line<'static> {
     let mut spans: Vec<Span<'static>> = Vec::new();
     push_selection_prefix(&mut spans, global_idx == mention.dialog.selected);
+    if !mention.marked.is_empty() {
+        if mention.is_marked(candidate) {
+            spans.push(Span::styled(
+                "\u{2713} ",
+                Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.push(Span::raw("  "));
+        }
+    }
 
     let path = &candidate.rel_path;
-    let query = &mention.query;
-    if query.is_empty() {
-        spans.push(Span::raw(path.clone()));
-    } else if let Some((match_start, match_end)) = find_case_insensitive_range(path, query) {
-        push_highlighted_text(&mut spans, path, match_start, match_end);
-    } else {
-        spans.push(Span::raw(path.clone()));
+    push_fuzzy_highlighted_text(&mut spans, path, &candidate.match_positions(&mention.query));
+    if let Some(count) = mention.dir_file_count(candidate) {
+        let noun = if count == 1 { "file" } else { "files" };
+        spans.push(Span::styled(format!("  {count} {noun}"), Style::default().fg(theme::DIM)));
     }
+    if mention.is_recent(candidate) {
+        spans.push(Span::styled(
+            "  recent",
+            Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC),
+        ));
+    }
+
+    Line::from(spans)
+}
+
+fn mention_directory_action_line(
+    mention: &mention::MentionState,
+    choice: &mention::DirectoryChoice,
+    action: mention::DirectoryAction,
+    global_idx: usize,
+) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    push_selection_prefix(&mut spans, global_idx == mention.dialog.selected);
+
+    let (label, detail) = match action {
+        mention::DirectoryAction::Attach => {
+            (format!("Attach @{}", choice.dir), "as a directory reference".to_owned())
+        }
+        mention::DirectoryAction::Expand => {
+            let shown = choice.top_level_files.len();
+            let detail = if shown < choice.top_level_total {
+                format!("first {shown} of {} top-level files", choice.top_level_total)
+            } else {
+                let noun = if shown == 1 { "file" } else { "files" };
+                format!("{shown} top-level {noun}")
+            };
+            ("Expand into files".to_owned(), detail)
+        }
+    };
+    spans.push(Span::raw(label));
+    spans.push(Span::styled(format!("  {detail}"), Style::default().fg(theme::DIM)));
+
+    Line::from(spans)
+}
+
+fn mention_symbol_line(
+    mention: &mention::MentionState,
+    symbol: &symbols::Symbol,
+    global_idx: usize,
+) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    push_selection_prefix(&mut spans, global_idx == mention.dialog.selected);
+
+    spans.push(Span::raw("#"));
+    let positions = fuzzy_match(&symbol.name, mention.symbol_query())
+        .map(|found| found.positions)
+        .unwrap_or_default();
+    push_fuzzy_highlighted_text(&mut spans, &symbol.name, &positions);
+    spans.push(Span::styled(
+        format!("  {} \u{b7} L{}", symbol.kind.label(), symbol.line),
+        Style::default().fg(theme::DIM),
+    ));
 
     Line::from(spans)
 }
@@ -229,29 +436,26 @@ fn slash_candidate_line(
     let mut spans: Vec<Span<'static>> = Vec::new();
     push_selection_prefix(&mut spans, global_idx == slash.dialog.selected);
 
-    if slash.query.is_empty() {
-        spans.push(Span::raw(candidate.primary.clone()));
+    let primary = &candidate.primary;
+    let positions = if slash.query.is_empty() {
+        Vec::new()
     } else if matches!(slash.context, slash::SlashContext::CommandName) {
-        let command_name = &candidate.primary;
-        let command_body = command_name.strip_prefix('/').unwrap_or(command_name);
-        if let Some((match_start, match_end)) =
-            find_case_insensitive_range(command_body, &slash.query)
-        {
-            let prefix_len = command_name.len().saturating_sub(command_body.len());
-            let start_idx = prefix_len + match_start;
-            let end_idx = prefix_len + match_end;
-            push_highlighted_text(&mut spans, command_name, start_idx, end_idx);
-        } else {
-            spans.push(Span::raw(command_name.clone()));
-        }
-    } else if let Some((match_start, match_end)) =
-        find_case_insensitive_range(&candidate.primary, &slash.query)
-    {
-        push_highlighted_text(&mut spans, &candidate.primary, match_start, match_end);
+        let command_body = primary.strip_prefix('/').unwrap_or(primary);
+        let prefix_len = primary.len().saturating_sub(command_body.len());
+        fuzzy_match(command_body, &slash.query)
+            .map(|found| found.positions.into_iter().map(|pos| pos + prefix_len).collect())
+            .unwrap_or_default()
     } else {
-        spans.push(Span::raw(candidate.primary.clone()));
-    }
+        fuzzy_match(primary, &slash.query).map(|found| found.positions).unwrap_or_default()
+    };
+    push_fuzzy_highlighted_text(&mut spans, primary, &positions);
 
+    if let Some(arg_hint) = &candidate.arg_hint {
+        spans.push(Span::styled(
+            format!(" {arg_hint}"),
+            Style::default().fg(theme::SLASH_COMMAND).add_modifier(Modifier::ITALIC),
+        ));
+    }
     if let Some(secondary) = &candidate.secondary {
         spans.push(Span::styled("  ", Style::default().fg(theme::DIM)));
         spans.push(Span::styled(secondary.clone(), Style::default().fg(theme::DIM)));
@@ -293,6 +497,24 @@ fn subagent_candidate_line(
     Line::from(spans)
 }
 
+fn emoji_candidate_line(
+    emoji: &emoji::EmojiState,
+    candidate: &emoji::EmojiCandidate,
+    global_idx: usize,
+) -> Line<'static> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    push_selection_prefix(&mut spans, global_idx == emoji.dialog.selected);
+
+    spans.push(Span::raw(format!("{} ", candidate.glyph)));
+    spans.push(Span::raw(":"));
+    let positions =
+        fuzzy_match(candidate.name, &emoji.query).map(|found| found.positions).unwrap_or_default();
+    push_fuzzy_highlighted_text(&mut spans, candidate.name, &positions);
+    spans.push(Span::raw(":"));
+
+    Line::from(spans)
+}
+
 fn push_selection_prefix(spans: &mut Vec<Span<'static>>, is_selected: bool) {
     if is_selected {
         spans.push(Span::styled(
@@ -370,6 +592,24 @@ fn push_highlighted_text(
     }
 }
 
+/// Render `text` with the characters at `positions` (byte offsets) highlighted.
+fn push_fuzzy_highlighted_text(spans: &mut Vec<Span<'static>>, text: &str, positions: &[usize]) {
+    let mut cursor = 0;
+    for (start, end) in highlight_ranges(text, positions) {
+        if start > cursor {
+            spans.push(Span::raw(text[cursor..start].to_owned()));
+        }
+        spans.push(Span::styled(
+            text[start..end].to_owned(),
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ));
+        cursor = end;
+    }
+    if cursor < text.len() {
+        spans.push(Span::raw(text[cursor..].to_owned()));
+    }
+}
+
 fn compute_text_area(input_area: Rect, hint_lines: u16) -> Rect {
     let input_main_area = if hint_lines > 0 {
         let [_hint, main] = Layout::vertical([Constraint::Length(hint_lines), Constraint::Min(1)])
@@ -435,12 +675,13 @@ fn wrapped_visual_pos(
             return (visual_row, 0);
         }
 
-        for ch in line.chars() {
-            if row == target_row && char_idx == target_col {
+        // A cursor inside a grapheme cluster is drawn after the whole cluster.
+        for (grapheme, w) in super::width::graphemes(line) {
+            if row == target_row && char_idx >= target_col {
                 return (visual_row, col_width as u16);
             }
 
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+            let chars = grapheme.chars().count();
             if w > 0 && col_width + w > width && col_width > 0 {
                 visual_row = visual_row.saturating_add(1);
                 col_width = 0;
@@ -448,17 +689,17 @@ fn wrapped_visual_pos(
 
             if w > width && col_width == 0 {
                 visual_row = visual_row.saturating_add(1);
-                char_idx += 1;
+                char_idx += chars;
                 continue;
             }
 
             if w > 0 {
                 col_width += w;
             }
-            char_idx += 1;
+            char_idx += chars;
         }
 
-        if row == target_row && char_idx == target_col {
+        if row == target_row && char_idx >= target_col {
             if col_width >= width {
                 return (visual_row.saturating_add(1), 0);
             }
@@ -515,10 +756,11 @@ fn choose_dropdown_y(anchor_y: u16, height: u16, frame_top: u16, frame_bottom: u
 #[cfg(test)]
 mod tests {
     use super::{
-        choose_dropdown_x, choose_dropdown_y, compute_height, find_case_insensitive_range,
-        is_active,
+        choose_dropdown_x, choose_dropdown_y, choose_preview_area, compute_height,
+        find_case_insensitive_range, is_active, push_fuzzy_highlighted_text, wrapped_visual_pos,
     };
     use crate::app::{App, mention};
+    use ratatui::layout::Rect;
 
     #[test]
     fn dropdown_keeps_preferred_width_and_shifts_left_near_right_edge() {
@@ -538,6 +780,24 @@ mod tests {
         assert_eq!((x, width), (12, 60));
     }
 
+    #[test]
+    fn preview_sits_right_of_dropdown_then_falls_back_left() {
+        let frame = Rect::new(0, 0, 120, 40);
+        let dropdown = Rect::new(4, 20, 60, 10);
+        assert_eq!(
+            choose_preview_area(dropdown, frame, 17, false),
+            Some(Rect::new(65, 20, 55, 17))
+        );
+        assert_eq!(choose_preview_area(dropdown, frame, 17, true), Some(Rect::new(65, 13, 55, 17)));
+
+        let near_right = Rect::new(58, 20, 60, 10);
+        assert_eq!(choose_preview_area(near_right, frame, 5, false), Some(Rect::new(0, 20, 57, 5)));
+        assert_eq!(
+            choose_preview_area(Rect::new(20, 20, 60, 10), Rect::new(0, 0, 100, 40), 5, false),
+            None
+        );
+    }
+
     #[test]
     fn dropdown_prefers_below_with_gap_when_space_available() {
         let y = choose_dropdown_y(10, 4, 0, 30);
@@ -578,4 +838,73 @@ mod tests {
         assert!(is_active(&app));
         assert_eq!(compute_height(&app), 3);
     }
+
+    #[test]
+    fn fuzzy_highlight_marks_non_contiguous_matches() {
+        let mut spans = Vec::new();
+        push_fuzzy_highlighted_text(&mut spans, "/new-session", &[1, 5]);
+        let parts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(parts, vec!["/", "n", "ew-", "s", "ession"]);
+        assert!(spans[1].style.add_modifier.contains(ratatui::style::Modifier::BOLD));
+        assert!(!spans[2].style.add_modifier.contains(ratatui::style::Modifier::BOLD));
+    }
+
+    const PROP_TEXT: &str = "[a-zA-Z0-9 _\u{df}\u{e9}\u{301}\u{65e5}\u{1f600}-]{0,16}";
+
+    proptest::proptest! {
+        #[test]
+        fn case_insensitive_range_lands_on_char_boundaries(
+            haystack in PROP_TEXT,
+            needle in PROP_TEXT,
+        ) {
+            if let Some((start, end)) = find_case_insensitive_range(&haystack, &needle) {
+                proptest::prop_assert!(start < end && end <= haystack.len());
+                let matched = haystack.get(start..end);
+                proptest::prop_assert!(matched.is_some(), "not a char range: {start}..{end}");
+                proptest::prop_assert!(
+                    matched.unwrap_or_default().to_lowercase().contains(&needle.to_lowercase())
+                );
+            }
+        }
+
+        #[test]
+        fn case_insensitive_range_finds_recased_substrings(
+            haystack in PROP_TEXT,
+            from in 0usize..16,
+            len in 1usize..8,
+        ) {
+            let chars: Vec<char> = haystack.chars().collect();
+            proptest::prop_assume!(from + len <= chars.len());
+            let needle: String = chars[from..from + len].iter().collect();
+            let found = find_case_insensitive_range(&haystack, &needle.to_ascii_uppercase());
+            proptest::prop_assert!(found.is_some(), "{needle:?} not found in {haystack:?}");
+        }
+
+        #[test]
+        fn wrapped_visual_pos_is_monotonic_and_within_width(
+            lines in proptest::collection::vec(PROP_TEXT, 1..4),
+            width in 1u16..12,
+        ) {
+            let mut previous = (0u16, 0u16);
+            for (row, line) in lines.iter().enumerate() {
+                for col in 0..=line.chars().count() {
+                    let pos = wrapped_visual_pos(&lines, row, col, width);
+                    proptest::prop_assert!(pos.1 <= width, "{pos:?} wider than {width}");
+                    proptest::prop_assert!(pos >= previous, "{pos:?} before {previous:?}");
+                    previous = pos;
+                }
+            }
+        }
+
+        #[test]
+        fn wrapped_visual_pos_is_identity_without_wrapping(
+            lines in proptest::collection::vec("[a-z ]{0,10}", 1..4),
+            row in 0usize..4,
+            col in 0usize..11,
+        ) {
+            proptest::prop_assume!(row < lines.len() && col <= lines[row].len());
+            let expected = (u16::try_from(row).unwrap_or(u16::MAX), u16::try_from(col).unwrap_or(u16::MAX));
+            proptest::prop_assert_eq!(wrapped_visual_pos(&lines, row, col, 20), expected);
+        }
+    }
 }
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::app::cache_metrics;
-use crate::app::{App, AppStatus, MessageBlock, MessageRole, SelectionKind, SelectionState};
+use crate::app::turn_folds::{self, FoldState};
+use crate::app::{
+    App, AppStatus, MessageBlock, MessageRole, ScrollAnimation, SelectionKind, SelectionState,
+};
 use crate::ui::message::{self, SpinnerState};
 use crate::ui::theme;
 use ratatui::Frame;
@@ -21,6 +24,8 @@ const SCROLLBAR_TOP_EASE: f32 = 0.35;
 const SCROLLBAR_SIZE_EASE: f32 = 0.2;
 const SCROLLBAR_EASE_EPSILON: f32 = 0.01;
 const OVERSCROLL_CLAMP_EASE: f32 = 0.2;
+/// Jumps longer than this many screens skip straight to the last stretch.
+const MAX_ANIMATED_SCROLL_SCREENS: usize = 2;
 
 #[derive(Clone, Copy, Default)]
 struct HeightUpdateStats {
@@ -29,6 +34,14 @@ struct HeightUpdateStats {
     reused_msgs: usize,
 }
 
+impl HeightUpdateStats {
+    fn mark(self) {
+        crate::perf::mark_with("chat::update_heights_measured_msgs", "msgs", self.measured_msgs);
+        crate::perf::mark_with("chat::update_heights_reused_msgs", "msgs", self.reused_msgs);
+        crate::perf::mark_with("chat::update_heights_measured_lines", "lines", self.measured_lines);
+    }
+}
+
 #[derive(Clone, Copy, Default)]
 struct ResizeMeasureBudget {
     remaining_msgs: usize,
@@ -112,8 +125,7 @@ fn update_visual_heights(
     width: u16,
     viewport_height: usize,
 ) -> HeightUpdateStats {
-    let _t =
-        app.perf.as_ref().map(|p| p.start_with("chat::update_heights", "msgs", app.messages.len()));
+    let _t = crate::perf::start_with("chat::update_heights", "msgs", app.messages.len());
     app.viewport.sync_message_count(app.messages.len());
 
     if app.viewport.resize_remeasure_active() {
@@ -266,6 +278,23 @@ fn measure_message_height_at(
     stats: &mut HeightUpdateStats,
 ) {
     let msg_count = app.messages.len();
+    let fixed_height = if crate::app::transcript_filter::is_visible(app, idx) {
+        match turn_folds::fold_state(app, idx) {
+            FoldState::Open => None,
+            FoldState::Summary => Some(1),
+            FoldState::Hidden => Some(0),
+        }
+    } else {
+        Some(0)
+    };
+    if let Some(h) = fixed_height {
+        app.viewport.set_message_height(idx, h);
+        app.viewport.mark_message_height_measured(idx);
+        if idx + 1 < msg_count {
+            app.viewport.prefix_sums_width = 0;
+        }
+        return;
+    }
     let sp =
         msg_spinner(base, idx, msg_count, is_thinking, show_subagent_thinking, &app.messages[idx]);
     let (h, rendered_lines) =
@@ -318,25 +347,25 @@ fn render_scrolled(
     content_height: usize,
     viewport_height: usize,
 ) {
-    let _t = app.perf.as_ref().map(|p| p.start("chat::render_scrolled"));
+    let _t = crate::perf::start("chat::render_scrolled");
+    let reduced_motion = crate::app::reduced_motion(app);
     let vp = &mut app.viewport;
-    let reduced_motion = app.config.prefers_reduced_motion_effective();
+    let animation = if reduced_motion {
+        ScrollAnimation::Instant
+    } else {
+        app.config.scroll_animation_effective()
+    };
     let max_scroll = content_height.saturating_sub(viewport_height);
     if vp.auto_scroll {
         vp.scroll_target = max_scroll;
         // Auto-scroll should stay pinned to the latest content without easing lag.
         vp.scroll_pos = vp.scroll_target as f32;
+        vp.scroll_animation_started = None;
     }
     vp.scroll_target = vp.scroll_target.min(max_scroll);
 
     if !vp.auto_scroll {
-        let target = vp.scroll_target as f32;
-        let delta = target - vp.scroll_pos;
-        if reduced_motion || delta.abs() < 0.01 {
-            vp.scroll_pos = target;
-        } else {
-            vp.scroll_pos += delta * 0.3;
-        }
+        ease_scroll_toward_target(vp, animation, viewport_height, Instant::now());
     }
     vp.scroll_offset = vp.scroll_pos.round() as usize;
     clamp_scroll_to_content(vp, max_scroll, reduced_motion);
@@ -347,10 +376,7 @@ fn render_scrolled(
 
     let mut all_lines = Vec::new();
     let render_stats = {
-        let _t = app
-            .perf
-            .as_ref()
-            .map(|p| p.start_with("chat::render_msgs", "msgs", app.messages.len()));
+        let _t = crate::perf::start_with("chat::render_msgs", "msgs", app.messages.len());
         render_culled_messages(
             app,
             base,
@@ -372,28 +398,51 @@ fn render_scrolled(
     crate::perf::mark_with("chat::render_scrolled_start", "idx", render_stats.render_start);
 
     let paragraph = {
-        let _t = app
-            .perf
-            .as_ref()
-            .map(|p| p.start_with("chat::paragraph_build", "lines", all_lines.len()));
+        let _t = crate::perf::start_with("chat::paragraph_build", "lines", all_lines.len());
         Paragraph::new(Text::from(all_lines)).wrap(Wrap { trim: false })
     };
 
     app.rendered_chat_area = area;
     if app.selection.is_some_and(|s| s.dragging) {
-        let _t = app.perf.as_ref().map(|p| p.start("chat::selection_capture"));
+        let _t = crate::perf::start("chat::selection_capture");
         app.rendered_chat_lines =
             render_lines_from_paragraph(&paragraph, area, render_stats.local_scroll);
     }
     {
-        let _t = app
-            .perf
-            .as_ref()
-            .map(|p| p.start_with("chat::render_widget", "scroll", render_stats.local_scroll));
+        let _t =
+            crate::perf::start_with("chat::render_widget", "scroll", render_stats.local_scroll);
         frame.render_widget(paragraph.scroll((render_stats.local_scroll as u16, 0)), area);
     }
 }
 
+/// Advance `scroll_pos` one frame toward `scroll_target`. Long jumps only
+/// animate their last few screens, and an animation running past the mode's
+/// duration cap snaps to the target.
+#[allow(clippy::cast_precision_loss)]
+fn ease_scroll_toward_target(
+    viewport: &mut crate::app::ChatViewport,
+    animation: ScrollAnimation,
+    viewport_height: usize,
+    now: Instant,
+) {
+    let target = viewport.scroll_target as f32;
+    let delta = target - viewport.scroll_pos;
+    let started = *viewport.scroll_animation_started.get_or_insert(now);
+    if animation == ScrollAnimation::Instant
+        || delta.abs() < SCROLLBAR_EASE_EPSILON
+        || now.duration_since(started) >= animation.max_duration()
+    {
+        viewport.scroll_pos = target;
+        viewport.scroll_animation_started = None;
+        return;
+    }
+    let max_animated = (viewport_height.max(1) * MAX_ANIMATED_SCROLL_SCREENS) as f32;
+    if delta.abs() > max_animated {
+        viewport.scroll_pos = target - max_animated.copysign(delta);
+    }
+    viewport.scroll_pos += (target - viewport.scroll_pos) * animation.easing_factor();
+}
+
 #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)]
 fn clamp_scroll_to_content(
     viewport: &mut crate::app::ChatViewport,
@@ -512,8 +561,8 @@ fn render_scrollbar_overlay(
     for row in 0..area.height as usize {
         let y = area.y.saturating_add(row as u16);
         if let Some(cell) = buf.cell_mut((rail_x, y)) {
-            cell.set_symbol("\u{2595}");
-            cell.set_style(rail_style);
+            // The rail may sit inside a hyperlinked URL run; draw it anyway.
+            cell.set_symbol("\u{2595}").set_style(rail_style).set_skip(false);
         }
     }
     let thumb_top = geometry.thumb_top.min(area.height.saturating_sub(1) as usize);
@@ -521,8 +570,7 @@ fn render_scrollbar_overlay(
     for row in thumb_top..thumb_end {
         let y = area.y.saturating_add(row as u16);
         if let Some(cell) = buf.cell_mut((rail_x, y)) {
-            cell.set_symbol("\u{2590}");
-            cell.set_style(thumb_style);
+            cell.set_symbol("\u{2590}").set_style(thumb_style).set_skip(false);
         }
     }
 }
@@ -557,6 +605,22 @@ fn render_culled_messages(
     let mut local_scroll = scroll.saturating_sub(height_before_start);
     let mut consume_skip_in_messages = true;
     for i in render_start..msg_count {
+        let height = app.viewport.message_height(i);
+        if height == 0 && (app.transcript_filter.is_some() || !app.folded_turns.is_empty()) {
+            continue;
+        }
+        if height == 1 && turn_folds::fold_state(app, i) == FoldState::Summary {
+            if local_scroll > 0 && consume_skip_in_messages {
+                local_scroll -= 1;
+            } else {
+                out.push(message::folded_turn_line(&turn_folds::summary(app, i), width));
+                rendered_msgs += 1;
+            }
+            if out.len() > lines_needed {
+                break;
+            }
+            continue;
+        }
         let sp =
             msg_spinner(base, i, msg_count, is_thinking, show_subagent_thinking, &app.messages[i]);
         let before = out.len();
@@ -592,7 +656,7 @@ fn render_culled_messages(
 
 #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)]
 pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
-    let _t = app.perf.as_ref().map(|p| p.start("chat::render"));
+    let _t = crate::perf::start("chat::render");
     crate::perf::mark_with("chat::message_count", "msgs", app.messages.len());
     let is_thinking = matches!(app.status, AppStatus::Thinking);
     let show_subagent_thinking = app.should_show_subagent_thinking(Instant::now());
@@ -606,11 +670,13 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
         is_thinking_mid_turn: false,
         is_subagent_thinking: false,
         is_compacting: app.is_compacting,
+        show_usage: app.config.show_message_usage_effective(),
+        thinking_display: app.config.thinking_display_effective(),
     };
 
     // Detect width change and invalidate layout caches
     {
-        let _t = app.perf.as_ref().map(|p| p.start("chat::on_frame"));
+        let _t = crate::perf::start("chat::on_frame");
         if app.viewport.on_frame(width) {
             app.cache_metrics.record_resize();
         }
@@ -618,44 +684,41 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
     let resize_anchor = app.viewport.resize_scroll_anchor();
 
     // Update per-message visual heights
-    let height_stats = update_visual_heights(
+    update_visual_heights(
         app,
         base_spinner,
         is_thinking,
         show_subagent_thinking,
         width,
         viewport_height,
-    );
-    crate::perf::mark_with(
-        "chat::update_heights_measured_msgs",
-        "msgs",
-        height_stats.measured_msgs,
-    );
-    crate::perf::mark_with("chat::update_heights_reused_msgs", "msgs", height_stats.reused_msgs);
-    crate::perf::mark_with(
-        "chat::update_heights_measured_lines",
-        "lines",
-        height_stats.measured_lines,
-    );
+    )
+    .mark();
 
     // Rebuild prefix sums (O(1) fast path when only last message changed)
     {
-        let _t = app.perf.as_ref().map(|p| p.start("chat::prefix_sums"));
+        let _t = crate::perf::start("chat::prefix_sums");
         app.viewport.rebuild_prefix_sums();
     }
     if let Some((anchor_idx, anchor_offset)) = resize_anchor {
         app.viewport.restore_scroll_anchor(anchor_idx, anchor_offset);
+    } else {
+        // Keep what the user is reading in place while messages above it change height.
+        app.viewport.restore_reading_anchor();
     }
 
+    // The sticky prompt takes the top row, so the transcript scrolls in the
+    // rows below it instead of being drawn over.
+    let sticky = sticky_prompt(app, area);
+    let area = if sticky.is_some() {
+        Rect { y: area.y + 1, height: area.height - 1, ..area }
+    } else {
+        area
+    };
+    let viewport_height = usize::from(area.height);
+
     // O(1) via prefix sums instead of O(n) sum every frame
     let content_height: usize = app.viewport.total_message_height();
-    crate::perf::mark_with("chat::content_height", "rows", content_height);
-    crate::perf::mark_with("chat::viewport_height", "rows", viewport_height);
-    crate::perf::mark_with(
-        "chat::content_overflow_rows",
-        "rows",
-        content_height.saturating_sub(viewport_height),
-    );
+    mark_content_fit(content_height, viewport_height);
 
     tracing::trace!(
         "RENDER: width={}, content_height={}, viewport_height={}, scroll_target={}, auto_scroll={}",
@@ -666,12 +729,6 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
         app.viewport.auto_scroll
     );
 
-    if content_height <= viewport_height {
-        crate::perf::mark_with("chat::path_short", "active", 1);
-    } else {
-        crate::perf::mark_with("chat::path_scrolled", "active", 1);
-    }
-
     render_scrolled(
         frame,
         area,
@@ -683,6 +740,10 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
         content_height,
         viewport_height,
     );
+    app.viewport.record_reading_anchor();
+    if let Some(text) = sticky {
+        render_sticky_prompt(frame, text, Rect { y: area.y - 1, height: 1, ..area });
+    }
 
     if let Some(sel) = app.selection
         && sel.kind == SelectionKind::Chat
@@ -690,10 +751,15 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
         frame.render_widget(SelectionOverlay { selection: sel }, app.rendered_chat_area);
     }
 
+    let chat_area = app.rendered_chat_area;
+    super::hyperlinks::link_urls(frame.buffer_mut(), chat_area);
+    super::minimap::render(frame, app, chat_area, content_height);
+
+    let reduced_motion = crate::app::reduced_motion(app);
     render_scrollbar_overlay(
         frame,
         &mut app.viewport,
-        app.config.prefers_reduced_motion_effective(),
+        reduced_motion,
         area,
         content_height,
         viewport_height,
@@ -702,6 +768,50 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
     enforce_and_emit_cache_metrics(app);
 }
 
+fn mark_content_fit(content_height: usize, viewport_height: usize) {
+    crate::perf::mark_with("chat::content_height", "rows", content_height);
+    crate::perf::mark_with("chat::viewport_height", "rows", viewport_height);
+    crate::perf::mark_with(
+        "chat::content_overflow_rows",
+        "rows",
+        content_height.saturating_sub(viewport_height),
+    );
+    if content_height <= viewport_height {
+        crate::perf::mark_with("chat::path_short", "active", 1);
+    } else {
+        crate::perf::mark_with("chat::path_scrolled", "active", 1);
+    }
+}
+
+/// Prompt of the turn being read, fitted to the chat width, once the prompt
+/// itself has scrolled out of view, so a long answer keeps its question in
+/// sight.
+fn sticky_prompt(app: &App, area: Rect) -> Option<String> {
+    let vp = &app.viewport;
+    if area.height < 4 || vp.height_prefix_sums.is_empty() {
+        return None;
+    }
+    let first_visible = vp.find_first_visible(vp.scroll_offset);
+    let start = turn_folds::turn_start(app, first_visible)?;
+    if vp.cumulative_height_before(start + 1) > vp.scroll_offset {
+        return None;
+    }
+    let prompt = turn_folds::prompt_line(app, start);
+    if prompt.is_empty() {
+        return None;
+    }
+    let text = format!("{} {prompt}", theme::PROMPT_CHAR);
+    Some(crate::ui::width::truncate_with_ellipsis(&text, usize::from(area.width)))
+}
+
+fn render_sticky_prompt(frame: &mut Frame, text: String, row: Rect) {
+    frame.render_widget(
+        Paragraph::new(Line::from(text))
+            .style(Style::default().bg(theme::USER_MSG_BG).add_modifier(Modifier::ITALIC)),
+        row,
+    );
+}
+
 #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 fn enforce_and_emit_cache_metrics(app: &mut App) {
     let budget_stats = app.enforce_render_cache_budget();
@@ -711,6 +821,10 @@ fn enforce_and_emit_cache_metrics(app: &mut App) {
     crate::perf::mark_with("cache::evicted_bytes", "bytes", budget_stats.evicted_bytes);
     crate::perf::mark_with("cache::evicted_blocks", "count", budget_stats.evicted_blocks);
 
+    let memory_stats = app.enforce_memory_budget(budget_stats.total_after_bytes);
+    crate::perf::mark_with("mem::total_bytes", "bytes", app.memory_budget.usage.total());
+    crate::perf::mark_with("mem::trimmed_bytes", "bytes", memory_stats.freed_bytes());
+
     // -- Accumulate and conditionally log render cache metrics --
     let should_log =
         app.cache_metrics.record_render_enforcement(&budget_stats, &app.render_cache_budget);
@@ -772,6 +886,9 @@ fn count_populated_cache_slots(messages: &[crate::app::ChatMessage]) -> usize {
         .flat_map(|m| m.blocks.iter())
         .filter(|block| match block {
             MessageBlock::Text(block) => block.cache.cached_bytes() > 0,
+            MessageBlock::Thinking(block) => block.cache.cached_bytes() > 0,
+            MessageBlock::DiffSummary(summary) => summary.cache.cached_bytes() > 0,
+            MessageBlock::RecoveryHint(hint) => hint.cache.cached_bytes() > 0,
             MessageBlock::Welcome(w) => w.cache.cached_bytes() > 0,
             MessageBlock::ToolCall(tc) => tc.cache.cached_bytes() > 0,
         })
@@ -816,37 +933,65 @@ fn render_lines_from_paragraph(
     let mut buf = Buffer::empty(area);
     let widget = paragraph.clone().scroll((scroll_offset as u16, 0));
     widget.render(area, &mut buf);
-    let mut lines = Vec::with_capacity(area.height as usize);
-    for y in 0..area.height {
-        let mut line = String::new();
-        for x in 0..area.width {
-            if let Some(cell) = buf.cell((area.x + x, area.y + y)) {
-                line.push_str(cell.symbol());
-            }
-        }
-        lines.push(line.trim_end().to_owned());
-    }
-    lines
+    (0..area.height).map(|y| super::width::buffer_row_text(&buf, area, y)).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        SCROLLBAR_MIN_THUMB_HEIGHT, ScrollbarGeometry, clamp_scroll_to_content,
-        compute_scrollbar_geometry, smooth_scrollbar_geometry, update_visual_heights,
+        Instant, SCROLLBAR_MIN_THUMB_HEIGHT, ScrollbarGeometry, clamp_scroll_to_content,
+        compute_scrollbar_geometry, ease_scroll_toward_target, render, smooth_scrollbar_geometry,
+        update_visual_heights,
     };
     use crate::app::{
         App, AppStatus, ChatMessage, ChatViewport, InvalidationLevel, MessageBlock, MessageRole,
-        TextBlock,
+        ScrollAnimation, TextBlock, ThinkingDisplay,
     };
     use crate::ui::message::SpinnerState;
+    use crate::ui::theme::PROMPT_CHAR;
+    use crate::ui::width::buffer_row_text;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
 
     fn assistant_text_message(text: &str) -> ChatMessage {
         ChatMessage {
             role: MessageRole::Assistant,
             blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
             usage: None,
+            annotation: None,
+        }
+    }
+
+    #[test]
+    fn sticky_prompt_takes_its_own_row_and_ends_in_an_ellipsis() {
+        let mut app = App::test_default();
+        let answer: Vec<String> = (1..=40).map(|n| format!("para {n}")).collect();
+        app.messages = vec![
+            ChatMessage {
+                role: MessageRole::User,
+                blocks: vec![MessageBlock::Text(TextBlock::from_complete(
+                    "Explain the borrow checker in great detail please",
+                ))],
+                usage: None,
+                annotation: None,
+            },
+            assistant_text_message(&answer.join("\n\n")),
+        ];
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).expect("terminal");
+        for _ in 0..3 {
+            terminal.draw(|frame| render(frame, frame.area(), &mut app)).expect("draw");
         }
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..8).map(|y| buffer_row_text(buffer, buffer.area, y)).collect();
+
+        assert!(rows[0].starts_with(&format!("{PROMPT_CHAR} Explain the borrow")), "{rows:?}");
+        assert!(rows[0].ends_with('\u{2026}'), "{rows:?}");
+        // The transcript is laid out below the prompt row, down to its last
+        // paragraph.
+        assert_eq!(app.rendered_chat_area, Rect::new(0, 1, 30, 7));
+        assert!(rows[1..].iter().any(|row| row.contains("para 40")), "{rows:?}");
+        assert!(!rows[1..].iter().any(|row| row.contains("Explain")), "{rows:?}");
     }
 
     #[test]
@@ -906,6 +1051,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         update_visual_heights(&mut app, spinner, false, false, 12, 8);
@@ -944,6 +1091,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         app.viewport.on_frame(48);
@@ -982,6 +1131,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         app.viewport.on_frame(48);
@@ -1022,6 +1173,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         app.viewport.on_frame(48);
@@ -1046,6 +1199,32 @@ mod tests {
         assert_eq!(app.viewport.message_heights_width, 18);
     }
 
+    #[test]
+    fn scroll_easing_follows_animation_mode_and_clamps_long_jumps() {
+        let now = Instant::now();
+        let mut viewport = ChatViewport::new();
+        viewport.auto_scroll = false;
+        viewport.scroll_target = 5_000;
+
+        ease_scroll_toward_target(&mut viewport, ScrollAnimation::Smooth, 20, now);
+        // Only the last two screens are animated: 5000 - 40 + 40 * 0.3.
+        assert!((viewport.scroll_pos - 4_972.0).abs() < 0.01);
+        assert_eq!(viewport.scroll_animation_started, Some(now));
+
+        ease_scroll_toward_target(
+            &mut viewport,
+            ScrollAnimation::Smooth,
+            20,
+            now + ScrollAnimation::Smooth.max_duration(),
+        );
+        assert!((viewport.scroll_pos - 5_000.0).abs() < f32::EPSILON);
+        assert_eq!(viewport.scroll_animation_started, None);
+
+        viewport.scroll_target = 0;
+        ease_scroll_toward_target(&mut viewport, ScrollAnimation::Instant, 20, now);
+        assert!(viewport.scroll_pos.abs() < f32::EPSILON);
+    }
+
     #[test]
     fn clamp_scroll_to_content_snaps_overscroll_after_shrink() {
         let mut viewport = ChatViewport::new();
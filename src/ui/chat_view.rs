@@ -1,10 +1,13 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{autocomplete, chat, footer, header, help, input, layout, theme, todo};
+use super::{
+    autocomplete, chat, filter_bar, footer, header, help, input, layout, perf_hud, pinned_output,
+    status_log, suggestions, theme, todo,
+};
 use crate::app::App;
 use ratatui::Frame;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 #[cfg(feature = "perf")]
 use ratatui::style::Color;
 use ratatui::style::Style;
@@ -12,72 +15,134 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
-    let _t = app.perf.as_ref().map(|p| p.start("ui::render"));
+    let _t = crate::perf::start("ui::render");
     let frame_area = frame.area();
     app.cached_frame_area = frame_area;
     crate::perf::mark_with("ui::frame_width", "cols", usize::from(frame_area.width));
     crate::perf::mark_with("ui::frame_height", "rows", usize::from(frame_area.height));
 
     let todo_height = {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::todo_height"));
+        let _t = crate::perf::start("ui::todo_height");
         todo::compute_height(app)
     };
     let help_height = {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::help_height"));
+        let _t = crate::perf::start("ui::help_height");
         help::compute_height(app, frame_area.width)
     };
     let input_visual_lines = {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::input_visual_lines"));
+        let _t = crate::perf::start("ui::input_visual_lines");
         input::visual_line_count(app, frame_area.width)
     };
     let areas = {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::layout"));
+        let _t = crate::perf::start("ui::layout");
         layout::compute(frame_area, input_visual_lines, app.show_header, todo_height, help_height)
     };
 
     if areas.header.height > 0 {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::header"));
+        let _t = crate::perf::start("ui::header");
         render_separator(frame, areas.header_top_sep);
         header::render(frame, areas.header, app);
         render_separator(frame, areas.header_bot_sep);
     }
 
-    {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::chat"));
-        chat::render(frame, areas.body, app);
-    }
+    render_body(frame, areas.body, app);
 
     render_separator(frame, areas.input_sep);
 
     if areas.todo.height > 0 {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::todo"));
+        let _t = crate::perf::start("ui::todo");
         todo::render(frame, areas.todo, app);
     }
 
     {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::input"));
+        let _t = crate::perf::start("ui::input");
         input::render(frame, areas.input, app);
     }
 
     if autocomplete::is_active(app) {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::autocomplete"));
+        let _t = crate::perf::start("ui::autocomplete");
         autocomplete::render(frame, areas.input, app);
     }
 
     render_separator(frame, areas.input_bottom_sep);
 
     if areas.help.height > 0 {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::help"));
+        let _t = crate::perf::start("ui::help");
         help::render(frame, areas.help, app);
     }
 
     if let Some(footer_area) = areas.footer {
-        let _t = app.perf.as_ref().map(|p| p.start("ui::footer"));
+        let _t = crate::perf::start("ui::footer");
         footer::render(frame, footer_area, app);
     }
 
-    let fps_y = if areas.header.height > 0 { areas.header.y } else { frame_area.y };
-    render_perf_fps_overlay(frame, frame_area, fps_y, app);
+    if perf_hud::is_visible() {
+        perf_hud::render(frame, frame_area, app);
+    } else {
+        let fps_y = if areas.header.height > 0 { areas.header.y } else { frame_area.y };
+        render_perf_fps_overlay(frame, frame_area, fps_y, app);
+    }
+}
+
+/// Chat plus the panes that share the body with it: status log, pinned
+/// output, filter bar and follow-up suggestions.
+fn render_body(frame: &mut Frame, body: Rect, app: &mut App) {
+    let log_height = status_log::compute_height(app, body.height);
+    let (chat_area, log_area) = if log_height > 0 {
+        let [chat_area, log_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(log_height)]).areas(body);
+        (chat_area, Some(log_area))
+    } else {
+        (body, None)
+    };
+    let pinned_height = pinned_output::compute_height(app, chat_area.height);
+    let (chat_area, pinned_area) = if pinned_height > 0 {
+        let [chat_area, pinned_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(pinned_height)])
+                .areas(chat_area);
+        (chat_area, Some(pinned_area))
+    } else {
+        (chat_area, None)
+    };
+    let filter_height = filter_bar::compute_height(app);
+    let (filter_area, chat_area) = if filter_height > 0 {
+        let [filter_area, chat_area] =
+            Layout::vertical([Constraint::Length(filter_height), Constraint::Min(1)])
+                .areas(chat_area);
+        (Some(filter_area), chat_area)
+    } else {
+        (None, chat_area)
+    };
+    let suggestion_height = suggestions::compute_height(app, chat_area.height);
+    let (chat_area, suggestion_area) = if suggestion_height > 0 {
+        let [chat_area, suggestion_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(suggestion_height)])
+                .areas(chat_area);
+        (chat_area, Some(suggestion_area))
+    } else {
+        (chat_area, None)
+    };
+
+    {
+        let _t = crate::perf::start("ui::chat");
+        chat::render(frame, chat_area, app);
+    }
+
+    if let Some(filter_area) = filter_area {
+        filter_bar::render(frame, filter_area, app);
+    }
+
+    if let Some(suggestion_area) = suggestion_area {
+        suggestions::render(frame, suggestion_area, app);
+    }
+
+    if let Some(pinned_area) = pinned_area {
+        pinned_output::render(frame, pinned_area, app);
+    }
+
+    if let Some(log_area) = log_area {
+        status_log::render(frame, log_area, app);
+    }
 }
 
 fn render_separator(frame: &mut Frame, area: Rect) {
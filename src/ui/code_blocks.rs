@@ -0,0 +1,122 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fenced code blocks. Top-level code is split out of the markdown next to
+//! tables and drawn with a header row naming the language and the copy key,
+//! syntax highlighting, optional line numbers, and either soft wrap or
+//! horizontally scrolled lines per `CodeView`. The lines land in the text
+//! block's width-keyed cache, so scroll math sees the same rows that are drawn.
+
+use super::{highlight, hscroll, tables, theme, width};
+use crate::app::CodeView;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const COPY_HINT: &str = "Ctrl+s c copies";
+
+fn with_bg(style: Style, bg: Option<Color>) -> Style {
+    bg.map_or(style, |bg| style.bg(bg))
+}
+
+fn header_line(language: &str, width: usize, bg: Option<Color>) -> Line<'static> {
+    let dim = with_bg(Style::default().fg(theme::DIM), bg);
+    let label = if language.is_empty() { "code" } else { language };
+    let rule = theme::SEPARATOR_CHAR;
+    let left = format!("{rule}{rule} ");
+    let used = width::display_width(&left) + width::display_width(label) + 1;
+    let hint = format!(" {COPY_HINT} {rule}{rule}");
+    let mut spans = vec![
+        Span::styled(left, dim),
+        Span::styled(label.to_owned(), dim.add_modifier(Modifier::BOLD)),
+        Span::styled(" ", dim),
+    ];
+    let hint_width = width::display_width(&hint);
+    if used + hint_width < width {
+        spans.push(Span::styled(rule.repeat(width - used - hint_width), dim));
+        spans.push(Span::styled(hint, dim));
+    } else {
+        spans.push(Span::styled(rule.repeat(width.saturating_sub(used)), dim));
+    }
+    Line::from(spans)
+}
+
+pub(super) fn render_code_block(
+    language: &str,
+    code: &str,
+    width: u16,
+    bg: Option<Color>,
+    view: CodeView,
+) -> Vec<Line<'static>> {
+    let width = usize::from(width).max(1);
+    let code = code.strip_suffix('\n').unwrap_or(code);
+    let highlighted = highlight::highlight_code(code, (!language.is_empty()).then_some(language));
+    let number_width = highlighted.len().to_string().len();
+    let gutter_width = if view.line_numbers { number_width + 3 } else { 0 };
+    let text_width = width.saturating_sub(gutter_width).max(1);
+    let gutter_style = with_bg(Style::default().fg(theme::DIM), bg);
+
+    let mut out = vec![header_line(language, width, bg)];
+    for (index, line) in highlighted.into_iter().enumerate() {
+        let rows = if view.scroll {
+            vec![hscroll::clip_line(&line, view.offset, text_width)]
+        } else if line.width() > text_width {
+            tables::wrap_line(&line, text_width)
+        } else {
+            vec![line]
+        };
+        for (row_index, row) in rows.into_iter().enumerate() {
+            let mut spans = Vec::with_capacity(row.spans.len() + 1);
+            if view.line_numbers {
+                let gutter = if row_index == 0 {
+                    format!("{:>number_width$} \u{2502} ", index + 1)
+                } else {
+                    format!("{:>number_width$} \u{2502} ", "")
+                };
+                spans.push(Span::styled(gutter, gutter_style));
+            }
+            spans.extend(row.spans.into_iter().map(|span| {
+                let style = with_bg(span.style, bg);
+                Span::styled(span.content, style)
+            }));
+            out.push(Line::from(spans).style(with_bg(row.style, bg)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn strings(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn header_names_the_language_and_fills_the_width() {
+        let lines = render_code_block("rust", "fn main() {}\n", 30, None, CodeView::default());
+        let header = &strings(&lines)[0];
+        assert!(header.starts_with("\u{2500}\u{2500} rust \u{2500}"));
+        assert!(header.ends_with(&format!("{COPY_HINT} \u{2500}\u{2500}")));
+        assert_eq!(width::display_width(header), 30);
+        assert_eq!(strings(&lines)[1], "fn main() {}");
+    }
+
+    #[test]
+    fn line_numbers_and_clipping_follow_the_view() {
+        let code = "short\nthis line is far too long\n";
+        let view = CodeView { line_numbers: true, scroll: true, offset: 0 };
+        let lines = strings(&render_code_block("", code, 16, None, view));
+        assert_eq!(lines[1], "1 \u{2502} short");
+        assert_eq!(lines[2], "2 \u{2502} this line i\u{203a}");
+        assert_eq!(lines.len(), 3);
+
+        let wrapped = render_code_block("", code, 16, None, CodeView::default());
+        assert!(wrapped.len() > 3);
+        assert!(wrapped.iter().all(|line| line.width() <= 16));
+    }
+}
@@ -1,10 +1,19 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+mod changed_files;
+mod commit;
+mod context;
+mod diagnostics;
+mod file_browser;
 mod input;
+mod login;
 mod mcp;
+mod model_params;
 mod overlay;
 mod plugins;
+mod pull_request;
+mod release_notes;
 mod settings;
 mod status;
 mod usage;
@@ -22,6 +31,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
 use super::theme;
+pub(super) use input::text_input_line;
 use input::{add_marketplace_example_lines, render_text_input_field};
 use overlay::{
     OverlayChrome, OverlayLayoutSpec, overlay_line_style, render_overlay_header,
@@ -86,6 +96,24 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         mcp::render_auth_redirect_overlay(frame, frame_area, app);
     } else if app.config.mcp_elicitation_overlay().is_some() {
         mcp::render_elicitation_overlay(frame, frame_area, app);
+    } else if app.config.diagnostics_overlay().is_some() {
+        diagnostics::render_overlay(frame, frame_area, app);
+    } else if app.config.login_overlay().is_some() {
+        login::render_overlay(frame, frame_area, app);
+    } else if app.config.release_notes_overlay().is_some() {
+        release_notes::render_overlay(frame, frame_area, app);
+    } else if app.config.context_overlay().is_some() {
+        context::render_overlay(frame, frame_area, app);
+    } else if app.config.changed_files_overlay().is_some() {
+        changed_files::render_overlay(frame, frame_area, app);
+    } else if app.config.commit_overlay().is_some() {
+        commit::render_overlay(frame, frame_area, app);
+    } else if app.config.pull_request_overlay().is_some() {
+        pull_request::render_overlay(frame, frame_area, app);
+    } else if app.config.file_browser_overlay().is_some() {
+        file_browser::render_overlay(frame, frame_area, app);
+    } else if app.config.model_params_overlay().is_some() {
+        model_params::render_overlay(frame, frame_area, app);
     }
 
     let (message, is_error) = if let Some(error) = app.config.last_error.clone() {
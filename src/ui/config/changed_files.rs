@@ -0,0 +1,114 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, render_overlay_shell};
+use crate::app::App;
+use crate::app::changed_files::{ChangeKind, ChangedFileEntry};
+use crate::app::config::ChangedFilesOverlayState;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.changed_files_overlay() else {
+        return;
+    };
+    let diff_entry = overlay.selected_entry().filter(|_| overlay.show_diff);
+    let subtitle = if let Some(entry) = diff_entry {
+        format!("{} compared with the session start", entry.label)
+    } else {
+        let count = overlay.entries.len();
+        format!("{count} file{} changed this session", if count == 1 { "" } else { "s" })
+    };
+    let help = if diff_entry.is_some() {
+        "Up/Down/PgUp/PgDn scroll | e edit | r revert | Esc back"
+    } else {
+        "Up/Down select | Enter diff | e edit | r revert | Esc close"
+    };
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 72,
+            min_height: 12,
+            width_percent: 90,
+            height_percent: 85,
+            preferred_height: 40,
+            fullscreen_below: Some((80, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome { title: "Changed files", subtitle: Some(&subtitle), help: Some(help) },
+    );
+
+    if let Some(entry) = diff_entry {
+        let lines = crate::ui::diff::render_diff(&entry.diff);
+        // The subtitle already names the file, so skip the diff's own header.
+        let scroll = overlay.diff_scroll.min(lines.len().saturating_sub(2));
+        let lines: Vec<Line<'static>> = lines.into_iter().skip(1 + scroll).collect();
+        frame.render_widget(Paragraph::new(lines), rendered.body_area);
+        return;
+    }
+    if overlay.entries.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No files have been changed by tool calls in this session.",
+                Style::default().fg(theme::DIM),
+            ))),
+            rendered.body_area,
+        );
+        return;
+    }
+    let lines = list_lines(overlay);
+    // Keep the selected row in view.
+    let height = usize::from(rendered.body_area.height).max(1);
+    let skip = (overlay.selected + 1).saturating_sub(height);
+    frame.render_widget(
+        Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()),
+        rendered.body_area,
+    );
+}
+
+fn kind_color(kind: ChangeKind) -> Color {
+    match kind {
+        ChangeKind::Created => Color::Green,
+        ChangeKind::Modified => Color::Yellow,
+        ChangeKind::Deleted => Color::Red,
+        ChangeKind::Unknown => theme::DIM,
+    }
+}
+
+fn list_lines(overlay: &ChangedFilesOverlayState) -> Vec<Line<'static>> {
+    let label_width = overlay
+        .entries
+        .iter()
+        .map(|entry| unicode_width::UnicodeWidthStr::width(entry.label.as_str()))
+        .max()
+        .unwrap_or(0);
+    overlay
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| entry_line(entry, label_width, idx == overlay.selected))
+        .collect()
+}
+
+fn entry_line(entry: &ChangedFileEntry, label_width: usize, selected: bool) -> Line<'static> {
+    let pad =
+        label_width.saturating_sub(unicode_width::UnicodeWidthStr::width(entry.label.as_str()));
+    let label_style = if selected {
+        Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    Line::from(vec![
+        Span::styled(if selected { "\u{276f} " } else { "  " }, label_style),
+        Span::styled(format!("{} ", entry.kind.tag()), Style::default().fg(kind_color(entry.kind))),
+        Span::styled(format!("{}{}  ", entry.label, " ".repeat(pad)), label_style),
+        Span::styled(format!("+{}", entry.added), Style::default().fg(Color::Green)),
+        Span::raw(" "),
+        Span::styled(format!("\u{2212}{}", entry.removed), Style::default().fg(Color::Red)),
+    ])
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::input::text_input_line;
+use super::overlay::{
+    OverlayChrome, OverlayLayoutSpec, render_overlay_separator, render_overlay_shell,
+};
+use crate::app::App;
+use crate::app::config::{CommitOverlayState, MessageEditor};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// Summary rows shown above the editor before the rest is cut.
+const MAX_SUMMARY_LINES: usize = 8;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.commit_overlay() else {
+        return;
+    };
+    let subtitle = subtitle(overlay);
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 64,
+            min_height: 14,
+            width_percent: 80,
+            height_percent: 80,
+            preferred_height: 32,
+            fullscreen_below: Some((72, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Commit",
+            subtitle: Some(&subtitle),
+            help: Some(
+                "Type to edit | Enter newline | Alt+a amend | Alt+s sign-off | Ctrl+s commit | Esc cancel",
+            ),
+        },
+    );
+
+    let summary = summary_lines(&overlay.summary);
+    let summary_height = u16::try_from(summary.len()).unwrap_or(u16::MAX);
+    let [summary_area, separator, editor] = Layout::vertical([
+        Constraint::Length(summary_height),
+        Constraint::Length(1),
+        Constraint::Min(1),
+    ])
+    .areas(rendered.body_area);
+    frame.render_widget(Paragraph::new(summary), summary_area);
+    render_overlay_separator(frame, separator);
+
+    render_editor(frame, editor, &overlay.message);
+}
+
+/// The editor's lines, scrolled so the cursor line stays in view.
+pub(super) fn render_editor(frame: &mut Frame, area: Rect, editor: &MessageEditor) {
+    let (cursor_line, _) = editor.cursor_line_col();
+    let height = usize::from(area.height).max(1);
+    let skip = (cursor_line + 1).saturating_sub(height);
+    let lines = editor_lines(editor).into_iter().skip(skip).collect::<Vec<_>>();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn subtitle(overlay: &CommitOverlayState) -> String {
+    let mut flags = Vec::new();
+    if overlay.options.amend {
+        flags.push("--amend");
+    }
+    if overlay.options.signoff {
+        flags.push("--signoff");
+    }
+    if overlay.options.all {
+        flags.push("--all");
+    }
+    if flags.is_empty() {
+        "git commit".to_owned()
+    } else {
+        format!("git commit {}", flags.join(" "))
+    }
+}
+
+pub(super) fn summary_lines(summary: &str) -> Vec<Line<'static>> {
+    let style = Style::default().fg(theme::DIM);
+    let rows: Vec<&str> = summary.lines().collect();
+    let mut lines: Vec<Line<'static>> = rows
+        .iter()
+        .take(MAX_SUMMARY_LINES)
+        .map(|row| Line::from(Span::styled((*row).to_owned(), style)))
+        .collect();
+    if rows.len() > MAX_SUMMARY_LINES {
+        // `git diff --stat` ends with the totals line; keep it visible.
+        lines.pop();
+        lines.push(Line::from(Span::styled(
+            format!("  ... {} more", rows.len() - MAX_SUMMARY_LINES + 1),
+            style,
+        )));
+        if let Some(totals) = rows.last() {
+            lines.push(Line::from(Span::styled((*totals).to_owned(), style)));
+        }
+    }
+    lines
+}
+
+fn editor_lines(editor: &MessageEditor) -> Vec<Line<'static>> {
+    let (cursor_line, cursor_col) = editor.cursor_line_col();
+    editor
+        .text
+        .split('\n')
+        .enumerate()
+        .map(|(index, text)| {
+            if index == cursor_line {
+                text_input_line(text, cursor_col, "")
+            } else {
+                Line::from(Span::styled(text.to_owned(), Style::default().fg(Color::White)))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,164 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, render_overlay_shell};
+use crate::app::App;
+use crate::app::config::{ContextBreakdown, ContextCategoryKind};
+use crate::ui::message::format_tokens;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Wrap};
+
+const BAR_FILLED: &str = "\u{2588}";
+const BAR_FREE: &str = "\u{2591}";
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.context_overlay() else {
+        return;
+    };
+    let breakdown = &overlay.breakdown;
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 56,
+            min_height: 14,
+            width_percent: 70,
+            height_percent: 60,
+            preferred_height: 20,
+            fullscreen_below: Some((64, 16)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Context",
+            subtitle: Some("What the agent currently holds in its context window"),
+            help: Some("r refresh | Esc close"),
+        },
+    );
+    let lines = context_lines(breakdown, rendered.body_area.width);
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), rendered.body_area);
+}
+
+fn category_color(kind: ContextCategoryKind) -> Color {
+    match kind {
+        ContextCategoryKind::SystemPrompt => Color::LightBlue,
+        ContextCategoryKind::Memory => Color::LightMagenta,
+        ContextCategoryKind::Conversation => theme::RUST_ORANGE,
+        ContextCategoryKind::ToolResults => Color::Green,
+    }
+}
+
+fn context_lines(breakdown: &ContextBreakdown, width: u16) -> Vec<Line<'static>> {
+    let used = breakdown.used_tokens();
+    // Percentages and the bar are relative to the window when it is known.
+    let scale = breakdown.context_window.unwrap_or(used).max(used).max(1);
+
+    let summary = match breakdown.context_window {
+        Some(window) => format!(
+            "{} / {} tokens ({}%)",
+            format_tokens(used),
+            format_tokens(window),
+            percent(used, window)
+        ),
+        None => format!("{} tokens (window size not reported yet)", format_tokens(used)),
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(summary, Style::default().add_modifier(Modifier::BOLD))),
+        Line::default(),
+        bar_line(breakdown, scale, usize::from(width)),
+        Line::default(),
+    ];
+
+    for category in &breakdown.categories {
+        let marker = if category.estimated { "~" } else { " " };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{BAR_FILLED} "),
+                Style::default().fg(category_color(category.kind)),
+            ),
+            Span::raw(format!("{:<24}", category.kind.label())),
+            Span::styled(
+                format!(
+                    "{marker}{:>7}  {:>3}%",
+                    format_tokens(category.tokens),
+                    percent(category.tokens, scale)
+                ),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    }
+    if let Some(window) = breakdown.context_window {
+        let free = window.saturating_sub(used);
+        lines.push(Line::from(vec![
+            Span::styled(format!("{BAR_FREE} "), Style::default().fg(theme::DIM)),
+            Span::raw(format!("{:<24}", "Free space")),
+            Span::styled(
+                format!(" {:>7}  {:>3}%", format_tokens(free), percent(free, window)),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    }
+
+    lines.push(Line::default());
+    let mut notes = vec!["~ estimated locally at about 4 characters per token."];
+    if breakdown.reported_tokens.is_none() {
+        notes.push("The agent reports exact usage after its first reply.");
+    }
+    if breakdown.compacted {
+        notes.push("The session was compacted; estimates include messages no longer in context.");
+    }
+    lines.extend(
+        notes
+            .into_iter()
+            .map(|note| Line::from(Span::styled(note, Style::default().fg(theme::DIM)))),
+    );
+    lines
+}
+
+/// One row of `width` cells split between the categories in proportion to
+/// their tokens, with the unused part of the window drawn as free space.
+fn bar_line(breakdown: &ContextBreakdown, scale: u64, width: usize) -> Line<'static> {
+    let cells =
+        |tokens: u64| usize::try_from(tokens.saturating_mul(width as u64) / scale).unwrap_or(width);
+    let mut spans = Vec::with_capacity(breakdown.categories.len() + 1);
+    let mut filled = 0;
+    for category in &breakdown.categories {
+        let count = cells(category.tokens).min(width - filled);
+        if count == 0 {
+            continue;
+        }
+        filled += count;
+        spans.push(Span::styled(
+            BAR_FILLED.repeat(count),
+            Style::default().fg(category_color(category.kind)),
+        ));
+    }
+    spans.push(Span::styled(BAR_FREE.repeat(width - filled), Style::default().fg(theme::DIM)));
+    Line::from(spans)
+}
+
+fn percent(part: u64, whole: u64) -> u64 {
+    part.saturating_mul(100).checked_div(whole).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn bar_fills_width_with_free_space_remainder() {
+        let breakdown = ContextBreakdown {
+            context_window: Some(1_000),
+            reported_tokens: Some(500),
+            categories: Vec::new(),
+            compacted: false,
+        };
+        let line = bar_line(&breakdown, 1_000, 20);
+        let cells: usize = line.spans.iter().map(|span| span.content.chars().count()).sum();
+        assert_eq!(cells, 20);
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, RenderedOverlay, render_overlay_shell};
+use crate::agent::stderr_log::StderrLevel;
+use crate::app::App;
+use crate::app::config::DiagnosticsView;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use std::ops::Range;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.diagnostics_overlay() else {
+        return;
+    };
+    if overlay.view == DiagnosticsView::UnhandledEvents {
+        render_unhandled_events(frame, area, app, overlay.scroll_from_bottom);
+        return;
+    }
+    let buffer = app.bridge_stderr.borrow();
+    let subtitle = match buffer.dropped() {
+        0 => format!("Agent stderr \u{b7} {} lines", buffer.len()),
+        dropped => {
+            format!("Agent stderr \u{b7} {} lines ({dropped} older dropped)", buffer.len())
+        }
+    };
+    let rendered = render_shell(frame, area, &subtitle);
+
+    if buffer.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "The agent has not written anything to stderr.",
+                Style::default().fg(theme::DIM),
+            ))),
+            rendered.body_area,
+        );
+        return;
+    }
+
+    let window = visible_window(
+        buffer.len(),
+        usize::from(rendered.body_area.height),
+        overlay.scroll_from_bottom,
+    );
+    let lines: Vec<Line<'_>> = buffer
+        .lines()
+        .skip(window.start)
+        .take(window.len())
+        .map(|line| {
+            Line::from(Span::styled(
+                line.text.as_str(),
+                Style::default().fg(level_color(line.level)),
+            ))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), rendered.body_area);
+}
+
+fn render_shell(frame: &mut Frame, area: Rect, subtitle: &str) -> RenderedOverlay {
+    render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 72,
+            min_height: 12,
+            width_percent: 90,
+            height_percent: 85,
+            preferred_height: 40,
+            fullscreen_below: Some((80, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Diagnostics",
+            subtitle: Some(subtitle),
+            help: Some(
+                "Tab stderr/events | Up/Down/PgUp/PgDn scroll | Home/End jump | c clear | Esc close",
+            ),
+        },
+    )
+}
+
+fn render_unhandled_events(frame: &mut Frame, area: Rect, app: &App, scroll_from_bottom: usize) {
+    let events = &app.unhandled_events;
+    let subtitle = match events.dropped() {
+        0 => format!("Unhandled bridge events \u{b7} {}", events.len()),
+        dropped => {
+            format!("Unhandled bridge events \u{b7} {} ({dropped} older dropped)", events.len())
+        }
+    };
+    let rendered = render_shell(frame, area, &subtitle);
+    if events.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Every event from the agent was recognized.",
+                Style::default().fg(theme::DIM),
+            ))),
+            rendered.body_area,
+        );
+        return;
+    }
+
+    let mut lines: Vec<Line<'_>> = Vec::with_capacity(events.line_count());
+    for event in events.events() {
+        lines.push(Line::from(vec![
+            Span::styled(event.kind.as_str(), Style::default().fg(theme::STATUS_WARNING)),
+            Span::styled(format!(" \u{b7} {}", event.reason), Style::default().fg(theme::DIM)),
+        ]));
+        lines.extend(event.raw.lines().map(Line::raw));
+        lines.push(Line::default());
+    }
+    let window =
+        visible_window(lines.len(), usize::from(rendered.body_area.height), scroll_from_bottom);
+    let lines: Vec<Line<'_>> = lines.into_iter().skip(window.start).take(window.len()).collect();
+    frame.render_widget(Paragraph::new(lines), rendered.body_area);
+}
+
+fn level_color(level: StderrLevel) -> Color {
+    match level {
+        StderrLevel::Error => theme::STATUS_ERROR,
+        StderrLevel::Warn => theme::STATUS_WARNING,
+        StderrLevel::Info => Color::White,
+        StderrLevel::Debug => theme::DIM,
+    }
+}
+
+/// Indices of the buffered lines shown in a body `height` rows tall, ending
+/// `scroll_from_bottom` lines above the newest one.
+fn visible_window(total: usize, height: usize, scroll_from_bottom: usize) -> Range<usize> {
+    let end = total.saturating_sub(scroll_from_bottom.min(total.saturating_sub(1)));
+    end.saturating_sub(height)..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visible_window;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn window_follows_tail_and_clamps_when_scrolled_past_the_top() {
+        assert_eq!(visible_window(100, 10, 0), 90..100);
+        assert_eq!(visible_window(100, 10, 5), 85..95);
+        assert_eq!(visible_window(100, 10, 500), 0..1);
+        assert_eq!(visible_window(4, 10, 0), 0..4);
+    }
+}
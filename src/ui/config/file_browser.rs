@@ -0,0 +1,127 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, render_overlay_shell};
+use crate::app::App;
+use crate::app::config::{FilePreview, FileTouch, FileTreeRow, visible_rows};
+use crate::app::file_index::FileIndex;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.file_browser_overlay() else {
+        return;
+    };
+    let preview = overlay.preview.as_ref();
+    let subtitle = match preview {
+        Some(preview) => preview.rel_path.clone(),
+        None if app.file_index.as_ref().is_some_and(FileIndex::is_indexing) => {
+            format!("{} (indexing...)", app.cwd)
+        }
+        None => app.cwd.clone(),
+    };
+    let help = if preview.is_some() {
+        "Up/Down/PgUp/PgDn scroll | @ mention | e edit | Esc back"
+    } else {
+        "Enter open/preview | Left/Right fold | @ mention | e edit | t next touched | Esc close"
+    };
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 60,
+            min_height: 12,
+            width_percent: 80,
+            height_percent: 85,
+            preferred_height: 40,
+            fullscreen_below: Some((72, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome { title: "Files", subtitle: Some(&subtitle), help: Some(help) },
+    );
+
+    if let Some(preview) = preview {
+        frame.render_widget(Paragraph::new(preview_lines(preview)), rendered.body_area);
+        return;
+    }
+    let rows = visible_rows(app, overlay);
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No files indexed yet.",
+                Style::default().fg(theme::DIM),
+            ))),
+            rendered.body_area,
+        );
+        return;
+    }
+    let selected = overlay
+        .selected
+        .as_ref()
+        .and_then(|selected| rows.iter().position(|row| row.rel_path == *selected))
+        .unwrap_or(0);
+    // Keep the selected row in view.
+    let height = usize::from(rendered.body_area.height).max(1);
+    let skip = (selected + 1).saturating_sub(height);
+    let lines: Vec<Line<'static>> = rows
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(height)
+        .map(|(idx, row)| row_line(row, idx == selected))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), rendered.body_area);
+}
+
+fn row_line(row: &FileTreeRow, selected: bool) -> Line<'static> {
+    let name_style = match (selected, row.is_dir) {
+        (true, _) => Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        (false, true) => Style::default().fg(Color::Blue),
+        (false, false) => Style::default(),
+    };
+    let marker = match (row.is_dir, row.expanded) {
+        (true, true) => "\u{25be} ",
+        (true, false) => "\u{25b8} ",
+        (false, _) => "  ",
+    };
+    let mut spans = vec![
+        Span::styled(if selected { "\u{276f} " } else { "  " }, name_style),
+        Span::raw("  ".repeat(row.depth)),
+        Span::styled(marker, Style::default().fg(theme::DIM)),
+        Span::styled(row.name.clone(), name_style),
+    ];
+    if let Some(touch) = row.touch {
+        let color = match touch {
+            FileTouch::Edited => theme::RUST_ORANGE,
+            FileTouch::Seen => Color::Cyan,
+        };
+        spans.push(Span::styled(" \u{25cf}", Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
+
+fn preview_lines(preview: &FilePreview) -> Vec<Line<'static>> {
+    if let Some(notice) = &preview.notice {
+        return vec![Line::from(Span::styled(notice.clone(), Style::default().fg(theme::DIM)))];
+    }
+    let number_width = preview.lines.len().to_string().len();
+    preview
+        .lines
+        .iter()
+        .enumerate()
+        .skip(preview.scroll)
+        .map(|(idx, line)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>number_width$} ", idx + 1),
+                    Style::default().fg(theme::DIM),
+                ),
+                Span::raw(line.clone()),
+            ])
+        })
+        .collect()
+}
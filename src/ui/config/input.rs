@@ -5,7 +5,7 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
-pub(super) fn text_input_line(draft: &str, cursor: usize, placeholder: &str) -> Line<'static> {
+pub(crate) fn text_input_line(draft: &str, cursor: usize, placeholder: &str) -> Line<'static> {
     let cursor_style =
         Style::default().fg(Color::Black).bg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD);
     let text_style = Style::default().fg(Color::White);
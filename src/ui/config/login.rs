@@ -0,0 +1,160 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::input::render_text_input_field;
+use super::overlay::{
+    OverlayChrome, OverlayLayoutSpec, overlay_line_style, render_overlay_separator,
+    render_overlay_shell,
+};
+use crate::app::App;
+use crate::app::config::{LoginOverlayState, LoginPhase, LoginProgress};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Wrap};
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.login_overlay() else {
+        return;
+    };
+    let help = match &overlay.phase {
+        LoginPhase::ChooseMethod => "Up/Down select | Enter log in | Esc close",
+        LoginPhase::Waiting(progress) if progress.code_prompted => {
+            "Enter submit code | Esc cancel login"
+        }
+        LoginPhase::Waiting(_) => "Esc cancel login",
+        LoginPhase::Failed(_) => "Enter retry | Esc close",
+    };
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 64,
+            min_height: 14,
+            width_percent: 75,
+            height_percent: 70,
+            preferred_height: 22,
+            fullscreen_below: Some((80, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Log in",
+            subtitle: Some("Authenticate the agent"),
+            help: Some(help),
+        },
+    );
+
+    match &overlay.phase {
+        LoginPhase::ChooseMethod => {
+            frame.render_widget(
+                Paragraph::new(method_lines(overlay)).wrap(Wrap { trim: false }),
+                rendered.body_area,
+            );
+        }
+        LoginPhase::Waiting(progress) if progress.code_prompted => {
+            let [body, separator, input] = Layout::vertical([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .areas(rendered.body_area);
+            frame.render_widget(
+                Paragraph::new(waiting_lines(progress)).wrap(Wrap { trim: false }),
+                body,
+            );
+            render_overlay_separator(frame, separator);
+            render_text_input_field(
+                frame,
+                input,
+                &overlay.code_draft,
+                overlay.code_cursor,
+                "Paste the code shown in the browser",
+            );
+        }
+        LoginPhase::Waiting(progress) => {
+            frame.render_widget(
+                Paragraph::new(waiting_lines(progress)).wrap(Wrap { trim: false }),
+                rendered.body_area,
+            );
+        }
+        LoginPhase::Failed(message) => {
+            let lines = vec![
+                Line::from(Span::styled(message.clone(), Style::default().fg(theme::STATUS_ERROR))),
+                Line::default(),
+                Line::from(Span::styled(
+                    "Press Enter to try again.",
+                    Style::default().fg(theme::DIM),
+                )),
+            ];
+            frame.render_widget(
+                Paragraph::new(lines).wrap(Wrap { trim: false }),
+                rendered.body_area,
+            );
+        }
+    }
+}
+
+fn method_lines(overlay: &LoginOverlayState) -> Vec<Line<'static>> {
+    let mut lines = vec![heading("Available methods"), Line::default()];
+    for (index, method) in overlay.methods.iter().enumerate() {
+        let selected = index == overlay.selected_index;
+        lines.push(Line::from(Span::styled(
+            format!("{} {}", if selected { ">" } else { " " }, method.name),
+            overlay_line_style(selected, true),
+        )));
+        if !method.description.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", method.description),
+                Style::default().fg(theme::DIM),
+            )));
+        }
+        lines.push(Line::default());
+    }
+    lines
+}
+
+fn waiting_lines(progress: &LoginProgress) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        heading(&progress.method_name),
+        Line::from(Span::styled(
+            "Waiting for login to complete...",
+            Style::default().fg(Color::White),
+        )),
+    ];
+    if let Some(url) = progress.url.as_deref() {
+        lines.push(Line::default());
+        lines.push(heading("URL"));
+        lines.push(Line::from(Span::styled(url.to_owned(), Style::default().fg(Color::Cyan))));
+    }
+    if progress.browser_opened {
+        lines.push(Line::from(Span::styled(
+            "Opened your browser automatically. Finish signing in there.",
+            Style::default().fg(theme::DIM),
+        )));
+    }
+    if let Some(error) = progress.browser_open_error.as_deref() {
+        lines.push(Line::from(Span::styled(
+            format!("{error}. Open the URL above manually."),
+            Style::default().fg(theme::STATUS_WARNING),
+        )));
+    }
+    if !progress.output.is_empty() {
+        lines.push(Line::default());
+        lines.push(heading("Output"));
+        lines.extend(
+            progress.output.iter().map(|line| {
+                Line::from(Span::styled(line.clone(), Style::default().fg(theme::DIM)))
+            }),
+        );
+    }
+    lines
+}
+
+fn heading(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_owned(),
+        Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+    ))
+}
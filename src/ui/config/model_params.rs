@@ -0,0 +1,59 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, render_overlay_shell};
+use crate::app::App;
+use crate::app::model_params::ModelParam;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.model_params_overlay() else {
+        return;
+    };
+    let subtitle = if overlay.draft == app.model_params { "" } else { "modified" };
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 48,
+            min_height: 8,
+            width_percent: 50,
+            height_percent: 40,
+            preferred_height: 10,
+            fullscreen_below: Some((56, 12)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Model parameters",
+            subtitle: (!subtitle.is_empty()).then_some(subtitle),
+            help: Some("Left/Right change | Backspace default | Enter apply | Esc cancel"),
+        },
+    );
+
+    let selected = overlay.selected_param();
+    let lines: Vec<Line<'static>> = ModelParam::ALL
+        .into_iter()
+        .map(|param| {
+            let is_selected = param == selected;
+            let label_style = if is_selected {
+                Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme::DIM)
+            };
+            Line::from(vec![
+                Span::styled(if is_selected { "\u{276f} " } else { "  " }, label_style),
+                Span::styled(format!("{:<18}", param.label()), label_style),
+                Span::styled(
+                    format!("\u{2039} {} \u{203a}", param.display(param.get(&overlay.draft))),
+                    Style::default().fg(Color::White),
+                ),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), rendered.body_area);
+}
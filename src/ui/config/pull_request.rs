@@ -0,0 +1,56 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::commit::{render_editor, summary_lines};
+use super::overlay::{
+    OverlayChrome, OverlayLayoutSpec, render_overlay_separator, render_overlay_shell,
+};
+use crate::app::App;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
+use ratatui::widgets::Paragraph;
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(overlay) = app.config.pull_request_overlay() else {
+        return;
+    };
+    let options = &overlay.options;
+    let subtitle = format!(
+        "{}{} into {}",
+        if options.draft { "Draft: " } else { "" },
+        options.head,
+        options.base
+    );
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 64,
+            min_height: 14,
+            width_percent: 80,
+            height_percent: 85,
+            preferred_height: 36,
+            fullscreen_below: Some((72, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome {
+            title: "Pull request",
+            subtitle: Some(&subtitle),
+            help: Some(
+                "First line is the title | Enter newline | Alt+d draft | Ctrl+s push and create | Esc cancel",
+            ),
+        },
+    );
+
+    let summary = summary_lines(&overlay.summary);
+    let summary_height = u16::try_from(summary.len()).unwrap_or(u16::MAX);
+    let [summary_area, separator, editor] = Layout::vertical([
+        Constraint::Length(summary_height),
+        Constraint::Length(1),
+        Constraint::Min(1),
+    ])
+    .areas(rendered.body_area);
+    frame.render_widget(Paragraph::new(summary), summary_area);
+    render_overlay_separator(frame, separator);
+    render_editor(frame, editor, &overlay.message);
+}
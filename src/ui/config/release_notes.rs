@@ -0,0 +1,70 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::overlay::{OverlayChrome, OverlayLayoutSpec, render_overlay_shell};
+use crate::app::App;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Margin, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Wrap};
+
+pub(super) fn render_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let (Some(overlay), Some(release)) =
+        (app.config.release_notes_overlay(), app.update_release.as_ref())
+    else {
+        return;
+    };
+    let subtitle = format!("v{} (current v{})", release.version, env!("CARGO_PKG_VERSION"));
+    let help = if release.html_url.is_empty() {
+        "Up/Down/PgUp/PgDn scroll | Esc close"
+    } else {
+        "Up/Down/PgUp/PgDn scroll | o open release page | Esc close"
+    };
+    let rendered = render_overlay_shell(
+        frame,
+        area,
+        OverlayLayoutSpec {
+            min_width: 64,
+            min_height: 12,
+            width_percent: 80,
+            height_percent: 80,
+            preferred_height: 36,
+            fullscreen_below: Some((80, 18)),
+            inner_margin: Margin { vertical: 1, horizontal: 2 },
+        },
+        OverlayChrome { title: "Release notes", subtitle: Some(&subtitle), help: Some(help) },
+    );
+
+    let mut lines: Vec<Line<'_>> =
+        release.notes.lines().skip(overlay.scroll).map(note_line).collect();
+    if release.notes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "This release has no notes. Run /update to install it.",
+            Style::default().fg(theme::DIM),
+        )));
+    } else {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            "Run /update to install this release.",
+            Style::default().fg(theme::DIM),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), rendered.body_area);
+}
+
+/// Markdown headings are shown bold without their `#` markers; everything
+/// else is passed through as plain text.
+fn note_line(line: &str) -> Line<'_> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') {
+        let heading = trimmed.trim_start_matches('#').trim_start();
+        Line::from(Span::styled(
+            heading,
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(Span::styled(line, Style::default().fg(Color::White)))
+    }
+}
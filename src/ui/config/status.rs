@@ -3,6 +3,7 @@
 
 use super::theme;
 use crate::app::App;
+use crate::app::model_params::ModelParam;
 use ratatui::Frame;
 use ratatui::layout::{Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -68,6 +69,9 @@ pub(crate) fn status_lines(app: &App) -> Vec<Line<'static>> {
     if let Some(ref mode) = app.mode {
         kv_line(&mut lines, "Mode", &mode.current_mode_name);
     }
+    for param in ModelParam::ALL {
+        kv_line(&mut lines, param.label(), &param.display(param.get(&app.model_params)));
+    }
 
     lines.push(Line::default());
 
@@ -80,6 +84,10 @@ pub(crate) fn status_lines(app: &App) -> Vec<Line<'static>> {
     let sources = setting_sources(app);
     kv_line(&mut lines, "Setting sources", &sources);
 
+    if let Some(ref hooks) = app.hook_summary {
+        kv_line(&mut lines, "Hooks", hooks);
+    }
+
     lines
 }
 
@@ -0,0 +1,106 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::app::App;
+use crate::app::confirm::ConfirmDialog;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap};
+
+const MAX_DIALOG_WIDTH: u16 = 64;
+
+/// Draw the open confirmation prompt centered over the current view.
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(dialog) = app.confirm.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    let width = MAX_DIALOG_WIDTH.min(area.width.saturating_sub(4));
+    if width < 12 {
+        return;
+    }
+    let inner_width = usize::from(width.saturating_sub(4)).max(1);
+    let body_rows = dialog
+        .body
+        .lines()
+        .map(|line| unicode_width::UnicodeWidthStr::width(line).div_ceil(inner_width).max(1))
+        .sum::<usize>();
+    // Border, body, blank row, options row, border.
+    let height = u16::try_from(body_rows + 4).unwrap_or(u16::MAX).min(area.height);
+    let dialog_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} ", dialog.title),
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " Enter select \u{b7} Esc cancel ",
+            Style::default().fg(theme::DIM),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::RUST_ORANGE))
+        .padding(Padding::horizontal(1));
+
+    let mut lines: Vec<Line<'_>> = dialog.body.lines().map(Line::from).collect();
+    lines.push(Line::default());
+    lines.push(options_line(dialog));
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), dialog_area);
+}
+
+fn options_line(dialog: &ConfirmDialog) -> Line<'static> {
+    let mut spans = Vec::with_capacity(dialog.options.len() * 2);
+    for (idx, option) in dialog.options.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::raw("  "));
+        }
+        let style = if idx == dialog.selected {
+            Style::default().fg(Color::White).bg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme::DIM)
+        };
+        spans.push(Span::styled(format!(" {} ({}) ", option.label, option.key), style));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::confirm::ConfirmAction;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn dialog_renders_title_body_and_highlighted_option() {
+        let mut app = App::test_default();
+        app.confirm = Some(ConfirmDialog::yes_no(
+            "Start a new session?",
+            "The current conversation ends here.",
+            "Start new session",
+            ConfirmAction::NewSession,
+        ));
+        let mut terminal = Terminal::new(TestBackend::new(70, 12)).expect("terminal");
+        terminal.draw(|frame| render(frame, &app)).expect("draw");
+
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|pos| buffer[pos].symbol().to_owned())
+            .collect();
+        assert!(text.contains("Start a new session?"));
+        assert!(text.contains("The current conversation ends here."));
+        assert!(text.contains("Cancel (n)"));
+    }
+}
@@ -59,6 +59,79 @@ pub fn render_diff(diff: &model::Diff) -> Vec<Line<'static>> {
     lines
 }
 
+/// Unchanged lines kept around each changed line of a word diff.
+const WORD_DIFF_CONTEXT: usize = 1;
+
+/// Word-level diff of a revised code block: changed lines with a line of
+/// context, removed words struck through in red, added words in green, and
+/// runs of unchanged lines folded to a count.
+pub fn render_word_diff(old: &str, new: &str) -> Vec<Line<'static>> {
+    let removed = Style::default().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT);
+    let added = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+    let mut rows: Vec<(Vec<Span<'static>>, bool)> = vec![(Vec::new(), false)];
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let tag = change.tag();
+        if tag == similar::ChangeTag::Delete {
+            // Removed line breaks stay on the current row as a visible marker.
+            if let Some(row) = rows.last_mut() {
+                row.0.push(Span::styled(change.value().replace('\n', "\u{21b5}"), removed));
+                row.1 = true;
+            }
+            continue;
+        }
+        let style = if tag == similar::ChangeTag::Insert { added } else { Style::default() };
+        for (i, piece) in change.value().split('\n').enumerate() {
+            if i > 0 {
+                rows.push((Vec::new(), false));
+            }
+            if let Some(row) = rows.last_mut()
+                && !piece.is_empty()
+            {
+                row.0.push(Span::styled(piece.to_owned(), style));
+                row.1 |= tag == similar::ChangeTag::Insert;
+            }
+        }
+    }
+
+    let gutter = || Span::styled("\u{2502} ", Style::default().fg(theme::DIM));
+    let mut lines = vec![Line::from(Span::styled(
+        "\u{b1} Revised code from an earlier reply (Ctrl+s, o shows the full text)",
+        Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC),
+    ))];
+    let shown: Vec<bool> = (0..rows.len())
+        .map(|i| {
+            let from = i.saturating_sub(WORD_DIFF_CONTEXT);
+            let to = (i + WORD_DIFF_CONTEXT).min(rows.len() - 1);
+            rows[from..=to].iter().any(|(_, changed)| *changed)
+        })
+        .collect();
+    let mut hidden = 0usize;
+    for ((spans, _), shown) in rows.into_iter().zip(shown) {
+        if !shown {
+            hidden += 1;
+            continue;
+        }
+        if hidden > 0 {
+            lines.push(folded_rows_line(hidden));
+            hidden = 0;
+        }
+        let mut line = vec![gutter()];
+        line.extend(spans);
+        lines.push(Line::from(line));
+    }
+    if hidden > 0 {
+        lines.push(folded_rows_line(hidden));
+    }
+    lines
+}
+
+fn folded_rows_line(count: usize) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("\u{22ef} {count} unchanged line{}", if count == 1 { "" } else { "s" }),
+        Style::default().fg(theme::DIM),
+    ))
+}
+
 pub fn looks_like_unified_diff(text: &str) -> bool {
     let mut saw_hunk = false;
     let mut saw_file_header = false;
@@ -176,6 +249,28 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    // render_word_diff
+
+    #[test]
+    fn word_diff_keeps_changed_lines_with_context() {
+        let lines = render_word_diff("one\ntwo\nthree\nfour\nfive", "one\ntwo\nthree\nFOUR\nfive");
+        let text: Vec<String> = lines
+            .iter()
+            .skip(1)
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "\u{22ef} 2 unchanged lines",
+                "\u{2502} three",
+                "\u{2502} fourFOUR",
+                "\u{2502} five"
+            ]
+        );
+        assert!(lines[3].spans[1].style.add_modifier.contains(Modifier::CROSSED_OUT));
+    }
+
     // strip_outer_code_fence
 
     #[test]
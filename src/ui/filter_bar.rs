@@ -0,0 +1,63 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::config::text_input_line;
+use crate::app::App;
+use crate::app::transcript_filter::shown_counts;
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// Rows taken above the chat: one while editing or while a filter is active.
+pub fn compute_height(app: &App) -> u16 {
+    u16::from(app.filter_editor.is_some() || app.transcript_filter.is_some())
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    if area.height == 0 {
+        return;
+    }
+    let label = Span::styled(
+        " Filter ",
+        Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+    );
+    let line = if let Some(editor) = app.filter_editor.as_ref() {
+        let hint = editor.error.as_deref().map_or_else(
+            || {
+                Span::styled(
+                    "  Enter apply \u{b7} Esc cancel \u{b7} empty clears",
+                    Style::default().fg(theme::DIM),
+                )
+            },
+            |err| Span::styled(format!("  {err}"), Style::default().fg(theme::STATUS_ERROR)),
+        );
+        let visible = usize::from(area.width).saturating_sub(40).max(8);
+        let skip = editor.input.cursor.saturating_sub(visible);
+        let draft: String = editor.input.text.chars().skip(skip).collect();
+        let input = text_input_line(
+            &draft,
+            editor.input.cursor - skip,
+            "regex, role:user, tool:bash, status:failed",
+        );
+        let mut spans = vec![label];
+        spans.extend(input.spans);
+        spans.push(hint);
+        Line::from(spans)
+    } else if let Some(filter) = app.transcript_filter.as_ref() {
+        let (shown, total) = shown_counts(app);
+        Line::from(vec![
+            label,
+            Span::styled(filter.query.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("  {shown} of {total} messages \u{b7} \\ to change"),
+                Style::default().fg(theme::DIM),
+            ),
+        ])
+    } else {
+        return;
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
@@ -3,18 +3,22 @@
 
 use crate::agent::model;
 use crate::app::App;
+use crate::app::health::{self, HealthState};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Layout, Rect};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph};
+use std::time::Instant;
 
-use super::theme;
+use super::{theme, width};
 
 const FOOTER_PAD: u16 = 2;
 const FOOTER_COLUMN_GAP: u16 = 1;
 type FooterItem = Option<(String, Color)>;
+/// Rate-limit window utilization from which the footer badge turns yellow,
+/// unless the agent reports its own threshold.
+const RATE_LIMIT_WARN_UTILIZATION: f64 = 0.8;
 
 pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
     let padded = Rect {
@@ -23,12 +27,13 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
         width: area.width.saturating_sub(FOOTER_PAD * 2),
         height: area.height,
     };
+    let padded = render_health_glyph(frame, padded, app);
 
     if app.cached_footer_line.is_none() {
-        let line = if let Some(ref mode) = app.mode {
+        let mut spans = if let Some(ref mode) = app.mode {
             let color = mode_color(&mode.current_mode_id);
             let (fast_mode_text, fast_mode_color) = fast_mode_badge(app.fast_mode_state);
-            Line::from(vec![
+            vec![
                 Span::styled("[", Style::default().fg(color)),
                 Span::styled(mode.current_mode_name.clone(), Style::default().fg(color)),
                 Span::styled("]", Style::default().fg(color)),
@@ -37,32 +42,161 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
                 Span::styled(fast_mode_text, Style::default().fg(fast_mode_color)),
                 Span::styled("]", Style::default().fg(fast_mode_color)),
                 Span::raw("  "),
-                Span::styled("?", Style::default().fg(Color::White)),
-                Span::styled(" : Help", Style::default().fg(theme::DIM)),
-            ])
+            ]
         } else {
-            Line::from(vec![
-                Span::styled("?", Style::default().fg(Color::White)),
-                Span::styled(" : Help", Style::default().fg(theme::DIM)),
-            ])
+            Vec::new()
         };
-        app.cached_footer_line = Some(line);
+        if app.read_only {
+            spans.push(Span::styled("[READ-ONLY]", Style::default().fg(theme::STATUS_WARNING)));
+            spans.push(Span::raw("  "));
+        }
+        if app.is_offline() {
+            spans.push(Span::styled("[OFFLINE]", Style::default().fg(theme::STATUS_WARNING)));
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled("F1", Style::default().fg(Color::White)));
+        spans.push(Span::styled(" : Help", Style::default().fg(theme::DIM)));
+        app.cached_footer_line = Some(Line::from(spans));
     }
 
-    if let Some(line) = &app.cached_footer_line {
+    if let Some(cached) = &app.cached_footer_line {
+        // The rate-limit badge is checked every frame so that it goes away
+        // once its window resets, without waiting for the next rebuild.
+        let mut line = cached.clone();
+        if let Some((text, color)) = rate_limit_badge(app.last_rate_limit_update.as_ref()) {
+            let help = line.spans.len().saturating_sub(2);
+            line.spans.insert(help, Span::raw("  "));
+            line.spans.insert(help, Span::styled(text, Style::default().fg(color)));
+        }
         let left_min = u16::try_from(line.width()).unwrap_or(u16::MAX);
 
         if let Some((hint_text, hint_color)) = footer_update_hint(app) {
             let (left_area, right_area) = split_footer_columns_hint(padded, left_min);
-            frame.render_widget(Paragraph::new(line.clone()), left_area);
+            frame.render_widget(Paragraph::new(line), left_area);
             render_footer_right_info(frame, right_area, &hint_text, hint_color);
         } else {
-            frame.render_widget(Paragraph::new(line.clone()), padded);
+            frame.render_widget(Paragraph::new(line), padded);
         }
     }
 }
 
+/// Draw the connection health glyph at the left edge and return the rest.
+fn render_health_glyph(frame: &mut Frame, area: Rect, app: &mut App) -> Rect {
+    let Some(state) = health::footer_state(app, Instant::now()) else {
+        app.health.glyph_area = Rect::default();
+        app.health.tooltip_visible = false;
+        return area;
+    };
+    if area.width < 2 || area.height == 0 {
+        return area;
+    }
+    let symbol =
+        if crate::app::screen_reader::is_enabled() { health_label(state) } else { "\u{25cf}" };
+    let symbol_width = u16::try_from(symbol.len()).unwrap_or(u16::MAX);
+    if area.width < symbol_width + 1 {
+        return area;
+    }
+    let glyph = Rect { width: symbol_width, height: 1, ..area };
+    frame.render_widget(
+        Paragraph::new(Span::styled(symbol, Style::default().fg(health_color(state)))),
+        glyph,
+    );
+    app.health.glyph_area = glyph;
+    Rect { x: area.x + symbol_width + 1, width: area.width - symbol_width - 1, ..area }
+}
+
+/// Text form of the health glyph for screen reader mode.
+fn health_label(state: HealthState) -> &'static str {
+    match state {
+        HealthState::Ok => "[connected]",
+        HealthState::Degraded => "[slow]",
+        HealthState::Lost => "[disconnected]",
+    }
+}
+
+fn health_color(state: HealthState) -> Color {
+    match state {
+        HealthState::Ok => Color::Green,
+        HealthState::Degraded => theme::STATUS_WARNING,
+        HealthState::Lost => theme::STATUS_ERROR,
+    }
+}
+
+/// Latency tooltip above the health glyph while the mouse hovers it.
+pub fn render_health_tooltip(frame: &mut Frame, app: &App) {
+    let glyph = app.health.glyph_area;
+    if !app.health.tooltip_visible || glyph.width == 0 {
+        return;
+    }
+    let lines = app.health.tooltip(Instant::now());
+    let text_width = lines.iter().map(|line| width::display_width(line)).max();
+    let width = u16::try_from(text_width.unwrap_or(0) + 4).unwrap_or(u16::MAX);
+    let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX);
+    let frame_area = frame.area();
+    let area = Rect {
+        x: glyph.x.min(frame_area.right().saturating_sub(width)),
+        y: glyph.y.saturating_sub(height),
+        width: width.min(frame_area.width),
+        height: height.min(glyph.y),
+    };
+    if area.height == 0 {
+        return;
+    }
+    let lines: Vec<Line<'_>> = lines.into_iter().map(Line::from).collect();
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme::DIM))
+                .padding(Padding::horizontal(1)),
+        ),
+        area,
+    );
+}
+
+/// `[5h 82%]` badge for the rate-limit window the agent last reported: dim
+/// while there is headroom, yellow past the warning threshold, red once
+/// requests are rejected. Hidden after the window resets.
+fn rate_limit_badge(update: Option<&model::RateLimitUpdate>) -> FooterItem {
+    let update = update?;
+    let utilization = update.utilization?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |since| since.as_secs_f64());
+    if update.resets_at.is_some_and(|resets_at| resets_at <= now) {
+        return None;
+    }
+    let window = match update.rate_limit_type.as_deref() {
+        Some("five_hour") => "5h",
+        Some("daily") => "day",
+        Some("minute") => "min",
+        Some("seven_day") => "7d",
+        Some("seven_day_opus") => "7d Opus",
+        Some("seven_day_sonnet") => "7d Sonnet",
+        Some(other) => other,
+        None => "limit",
+    };
+    let threshold = update.surpassed_threshold.unwrap_or(RATE_LIMIT_WARN_UTILIZATION);
+    let color = match update.status {
+        model::RateLimitStatus::Rejected => theme::STATUS_ERROR,
+        model::RateLimitStatus::AllowedWarning => theme::STATUS_WARNING,
+        model::RateLimitStatus::Allowed if utilization >= threshold => theme::STATUS_WARNING,
+        model::RateLimitStatus::Allowed => theme::DIM,
+    };
+    Some((format!("[{window} {:.0}%]", utilization * 100.0), color))
+}
+
 fn footer_update_hint(app: &App) -> FooterItem {
+    if let Some(hint) = crate::app::turn_retry::footer_hint(app, Instant::now()) {
+        return Some((hint, theme::STATUS_WARNING));
+    }
+    if let Some(offer) = crate::app::pasted_paths::active_offer(app) {
+        return Some((offer.hint(), theme::RUST_ORANGE));
+    }
+    if let Some(offer) = crate::app::test_run::active_offer(app) {
+        return Some((offer.hint(), theme::STATUS_WARNING));
+    }
     app.update_check_hint.as_ref().map(|hint| (hint.clone(), theme::RUST_ORANGE))
 }
 
@@ -83,7 +217,7 @@ fn fit_footer_right_text(text: &str, max_width: usize) -> Option<String> {
         return None;
     }
 
-    if UnicodeWidthStr::width(text) <= max_width {
+    if width::display_width(text) <= max_width {
         return Some(text.to_owned());
     }
 
@@ -91,22 +225,8 @@ fn fit_footer_right_text(text: &str, max_width: usize) -> Option<String> {
         return Some(".".repeat(max_width));
     }
 
-    let mut fitted = String::new();
-    let mut width: usize = 0;
-    for ch in text.chars() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if width.saturating_add(ch_width).saturating_add(3) > max_width {
-            break;
-        }
-        fitted.push(ch);
-        width = width.saturating_add(ch_width);
-    }
-
-    if fitted.is_empty() {
-        return Some("...".to_owned());
-    }
-    fitted.push_str("...");
-    Some(fitted)
+    let (fitted, _) = width::prefix_within(text, max_width - 3);
+    Some(format!("{fitted}..."))
 }
 
 fn render_footer_right_info(frame: &mut Frame, area: Rect, right_text: &str, right_color: Color) {
@@ -176,7 +296,7 @@ mod tests {
         let text = "Update available: v9.9.9 (current v0.2.0)";
         let fitted = fit_footer_right_text(text, 12).expect("fitted text");
         assert!(fitted.ends_with("..."));
-        assert!(UnicodeWidthStr::width(fitted.as_str()) <= 12);
+        assert!(width::display_width(&fitted) <= 12);
     }
 
     #[test]
@@ -184,7 +304,7 @@ mod tests {
         let text = "Compacting context now and applying update hint";
         let fitted = fit_footer_right_text(text, 20).expect("fitted text");
         assert!(fitted.starts_with("Compacting"));
-        assert!(UnicodeWidthStr::width(fitted.as_str()) <= 20);
+        assert!(width::display_width(&fitted) <= 20);
     }
 
     #[test]
@@ -203,6 +323,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn footer_shows_offline_badge_in_offline_mode() {
+        let mut app = App::test_default();
+        app.offline_flag = true;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 1)).expect("terminal");
+        terminal.draw(|frame| render(frame, frame.area(), &mut app)).expect("draw");
+
+        let line = app.cached_footer_line.as_ref().expect("footer line");
+        assert!(line.spans.iter().any(|span| span.content == "[OFFLINE]"));
+    }
+
+    #[test]
+    fn footer_shows_read_only_badge() {
+        let mut app = App::test_default();
+        app.read_only = true;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 1)).expect("terminal");
+        terminal.draw(|frame| render(frame, frame.area(), &mut app)).expect("draw");
+
+        let line = app.cached_footer_line.as_ref().expect("footer line");
+        assert!(line.spans.iter().any(|span| span.content == "[READ-ONLY]"));
+    }
+
+    #[test]
+    fn rate_limit_badge_warns_past_the_threshold() {
+        let mut update = model::RateLimitUpdate {
+            status: model::RateLimitStatus::Allowed,
+            resets_at: None,
+            utilization: Some(0.42),
+            rate_limit_type: Some("five_hour".to_owned()),
+            overage_status: None,
+            overage_resets_at: None,
+            overage_disabled_reason: None,
+            is_using_overage: None,
+            surpassed_threshold: None,
+        };
+        assert_eq!(rate_limit_badge(Some(&update)), Some(("[5h 42%]".to_owned(), theme::DIM)));
+
+        update.utilization = Some(0.85);
+        assert_eq!(
+            rate_limit_badge(Some(&update)),
+            Some(("[5h 85%]".to_owned(), theme::STATUS_WARNING))
+        );
+
+        update.status = model::RateLimitStatus::Rejected;
+        assert_eq!(
+            rate_limit_badge(Some(&update)).map(|(_, color)| color),
+            Some(theme::STATUS_ERROR)
+        );
+
+        update.resets_at = Some(1.0);
+        assert_eq!(rate_limit_badge(Some(&update)), None);
+    }
+
+    #[test]
+    fn rate_limit_badge_disappears_once_the_window_resets() {
+        let mut app = App::test_default();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |since| since.as_secs_f64());
+        app.last_rate_limit_update = Some(model::RateLimitUpdate {
+            status: model::RateLimitStatus::Allowed,
+            resets_at: Some(now + 3_600.0),
+            utilization: Some(0.9),
+            rate_limit_type: Some("five_hour".to_owned()),
+            overage_status: None,
+            overage_resets_at: None,
+            overage_disabled_reason: None,
+            is_using_overage: None,
+            surpassed_threshold: None,
+        });
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 1)).expect("terminal");
+        let mut draw = |app: &mut App| {
+            terminal.draw(|frame| render(frame, frame.area(), app)).expect("draw");
+            let buffer = terminal.backend().buffer();
+            (0..buffer.area.width).map(|x| buffer[(x, 0)].symbol().to_owned()).collect::<String>()
+        };
+        assert!(draw(&mut app).contains("[5h 90%]  F1 : Help"));
+
+        // The window resets without any event rebuilding the cached line.
+        if let Some(update) = &mut app.last_rate_limit_update {
+            update.resets_at = Some(now - 1.0);
+        }
+        assert!(app.cached_footer_line.is_some());
+        let text = draw(&mut app);
+        assert!(!text.contains("[5h"), "{text}");
+        assert!(text.contains("F1 : Help"));
+    }
+
     #[test]
     fn fast_mode_badge_maps_cooldown_to_cd() {
         let (label, _) = fast_mode_badge(model::FastModeState::Cooldown);
@@ -0,0 +1,223 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Markdown footnotes. `[^label]` references render as superscript markers and
+//! `[^label]: text` definitions are lifted out of the text into a references
+//! list under the message. The bridge turns the agent's citations into
+//! footnotes, so cited sources end up in the same list; their URLs become
+//! clickable through `hyperlinks::link_urls`.
+
+use crate::app::{ChatMessage, MessageBlock};
+use crate::ui::markdown::is_fence;
+use crate::ui::theme;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub label: String,
+    pub text: String,
+}
+
+/// Superscript marker for `label`: digits map to superscript digits, any
+/// other label keeps a caret, e.g. `^note`.
+#[must_use]
+pub fn superscript(label: &str) -> String {
+    if label.is_empty() || !label.chars().all(|c| c.is_ascii_digit()) {
+        return format!("^{label}");
+    }
+    label
+        .chars()
+        .map(|c| match c {
+            '0' => '\u{2070}',
+            '1' => '\u{b9}',
+            '2' => '\u{b2}',
+            '3' => '\u{b3}',
+            '4' => '\u{2074}',
+            '5' => '\u{2075}',
+            '6' => '\u{2076}',
+            '7' => '\u{2077}',
+            '8' => '\u{2078}',
+            _ => '\u{2079}',
+        })
+        .collect()
+}
+
+fn valid_label(label: &str) -> bool {
+    !label.is_empty() && !label.contains(|c: char| c.is_whitespace() || c == '[' || c == ']')
+}
+
+/// `(label, text)` when `line` is a footnote definition.
+fn definition(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix("[^")?;
+    let (label, text) = rest.split_once("]:")?;
+    valid_label(label).then_some((label, text.trim()))
+}
+
+/// Replace `[^label]` references with superscript markers.
+fn replace_markers(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("[^") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find(']').map(|end| (&after[..end], end)) {
+            Some((label, end)) if valid_label(label) => {
+                out.push_str(&superscript(label));
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push_str("[^");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Markdown with footnote references turned into superscript markers and the
+/// definitions removed. Code fences are left untouched.
+#[must_use]
+pub fn inline_markers(text: &str) -> Cow<'_, str> {
+    if !text.contains("[^") {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut in_fence = false;
+    for line in text.lines() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if in_fence || is_fence(line) {
+            out.push_str(line);
+        } else if definition(line).is_some() {
+            continue;
+        } else {
+            out.push_str(&replace_markers(line));
+        }
+        out.push('\n');
+    }
+    if !text.ends_with('\n') {
+        out.pop();
+    }
+    Cow::Owned(out)
+}
+
+/// Footnote definitions across the text blocks of `msg`, in order, first
+/// definition of a label winning.
+#[must_use]
+pub fn collect_references(msg: &ChatMessage) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    for block in &msg.blocks {
+        let MessageBlock::Text(block) = block else {
+            continue;
+        };
+        if !block.text.contains("[^") {
+            continue;
+        }
+        let mut in_fence = false;
+        for line in block.text.lines() {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                continue;
+            }
+            if in_fence {
+                continue;
+            }
+            if let Some((label, text)) = definition(line)
+                && !references.iter().any(|r| r.label == label)
+            {
+                references.push(Reference { label: label.to_owned(), text: text.to_owned() });
+            }
+        }
+    }
+    references
+}
+
+/// Title and URL of a definition written as `[title](url)`, `<url>` or a bare
+/// URL.
+fn split_link(text: &str) -> (Option<&str>, Option<&str>) {
+    if let Some(rest) = text.strip_prefix('[')
+        && let Some((title, tail)) = rest.split_once("](")
+        && let Some(url) = tail.strip_suffix(')')
+    {
+        return (Some(title), Some(url));
+    }
+    let bare = text.strip_prefix('<').and_then(|t| t.strip_suffix('>')).unwrap_or(text);
+    if (bare.starts_with("https://") || bare.starts_with("http://"))
+        && !bare.contains(char::is_whitespace)
+    {
+        return (None, Some(bare));
+    }
+    (Some(text), None)
+}
+
+/// Lines listing `references` under a message.
+#[must_use]
+pub fn reference_lines(references: &[Reference]) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(theme::DIM);
+    references
+        .iter()
+        .map(|reference| {
+            let mut spans = vec![Span::styled(format!("{} ", superscript(&reference.label)), dim)];
+            let (title, url) = split_link(&reference.text);
+            if let Some(title) = title {
+                spans.push(Span::raw(title.to_owned()));
+            }
+            if let Some(url) = url {
+                if title.is_some() {
+                    spans.push(Span::raw("  "));
+                }
+                spans.push(Span::styled(url.to_owned(), dim.add_modifier(Modifier::UNDERLINED)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{MessageRole, TextBlock};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn markers_become_superscripts_and_definitions_move_out() {
+        let text = "Rust is fast[^1] and safe[^12].\n\n[^1]: [Benchmarks](https://example.com/b)\n";
+        assert_eq!(inline_markers(text), "Rust is fast\u{b9} and safe\u{b9}\u{b2}.\n\n");
+        assert_eq!(inline_markers("```\nlet a = b[^1];\n```"), "```\nlet a = b[^1];\n```");
+        assert_eq!(inline_markers("x[^]"), "x[^]");
+    }
+
+    #[test]
+    fn references_are_collected_across_blocks() {
+        let msg = ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![
+                MessageBlock::Text(TextBlock::from_complete("Claim[^1], other[^note].\n\n")),
+                MessageBlock::Text(TextBlock::from_complete(
+                    "[^1]: [Docs](https://docs.rs)\n[^note]: From memory\n[^1]: duplicate",
+                )),
+            ],
+            usage: None,
+            annotation: None,
+        };
+
+        let references = collect_references(&msg);
+        assert_eq!(
+            references,
+            vec![
+                Reference { label: "1".into(), text: "[Docs](https://docs.rs)".into() },
+                Reference { label: "note".into(), text: "From memory".into() },
+            ]
+        );
+        let line: String = reference_lines(&references)[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(line, "\u{b9} Docs  https://docs.rs");
+    }
+}
@@ -37,6 +37,14 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
             Span::styled(app.cwd.clone(), white),
         ];
 
+        let extra_roots = crate::app::add_dir::root_labels(app);
+        if !extra_roots.is_empty() {
+            spans.push(Span::styled(
+                format!(" + {}", extra_roots.join(", ")),
+                Style::default().fg(theme::DIM),
+            ));
+        }
+
         if let Some(branch) = &app.git_branch {
             spans.push(sep());
             spans.push(Span::styled("Branch: ", Style::default().fg(theme::DIM)));
@@ -0,0 +1,124 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Markdown headings and horizontal rules. Top-level headings are split out of
+//! the markdown next to tables: H1 and H2 get a `§` ornament and an underline
+//! (H1 in the accent color), H3 a `›`, deeper levels are bold italic. Rules
+//! draw as a full-width separator line.
+
+use super::markdown;
+use crate::ui::theme;
+use pulldown_cmark::HeadingLevel;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+/// Heading text of an ATX (`## Title`) or setext (`Title` over `---`) heading.
+pub(super) fn heading_text(source: &str) -> String {
+    let source = source.trim();
+    if let Some(rest) = source.strip_prefix('#') {
+        let content = rest.trim_start_matches('#').trim();
+        // An optional closing run of `#` after a space.
+        let without_closing = content.trim_end_matches('#');
+        return if without_closing.is_empty() || without_closing.ends_with(' ') {
+            without_closing.trim_end().to_owned()
+        } else {
+            content.to_owned()
+        };
+    }
+    let mut lines: Vec<&str> = source.lines().map(str::trim).collect();
+    lines.pop();
+    lines.join(" ")
+}
+
+/// Escape a leading list or quote marker so heading text stays inline.
+fn escape_block_start(text: &str) -> String {
+    if text.starts_with(['-', '+', '*', '>', '#']) {
+        return format!("\\{text}");
+    }
+    let digits = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    if digits > 0 && (text[digits..].starts_with(". ") || text[digits..].starts_with(") ")) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_owned()
+}
+
+fn heading_style(level: HeadingLevel) -> (Option<&'static str>, Style) {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    match level {
+        HeadingLevel::H1 => (
+            Some(theme::HEADING_MARK),
+            bold.fg(theme::ROLE_ASSISTANT).add_modifier(Modifier::UNDERLINED),
+        ),
+        HeadingLevel::H2 => (Some(theme::HEADING_MARK), bold.add_modifier(Modifier::UNDERLINED)),
+        HeadingLevel::H3 => (Some(theme::SUBHEADING_MARK), bold),
+        _ => (None, bold.add_modifier(Modifier::ITALIC)),
+    }
+}
+
+pub(super) fn render_heading(
+    level: HeadingLevel,
+    text: &str,
+    bg: Option<Color>,
+) -> Vec<Line<'static>> {
+    let (mark, style) = heading_style(level);
+    let mut mark_style = if level == HeadingLevel::H1 {
+        Style::default().fg(theme::ROLE_ASSISTANT).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme::DIM)
+    };
+    if let Some(bg) = bg {
+        mark_style = mark_style.bg(bg);
+    }
+    let mut lines = markdown::render_markdown_safe(&escape_block_start(text), bg);
+    while lines.last().is_some_and(|line| line.width() == 0) {
+        lines.pop();
+    }
+    for (index, line) in lines.iter_mut().enumerate() {
+        for span in &mut line.spans {
+            span.style = style.patch(span.style);
+        }
+        if index == 0
+            && let Some(mark) = mark
+        {
+            line.spans.insert(0, Span::styled(format!("{mark} "), mark_style));
+        }
+    }
+    lines
+}
+
+pub(super) fn render_rule(width: u16, bg: Option<Color>) -> Line<'static> {
+    let mut style = Style::default().fg(theme::DIM);
+    if let Some(bg) = bg {
+        style = style.bg(bg);
+    }
+    Line::from(Span::styled(theme::SEPARATOR_CHAR.repeat(usize::from(width)), style))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::tables::render_markdown_with_tables;
+    use pretty_assertions::assert_eq;
+
+    fn render_strings(text: &str, width: u16) -> Vec<String> {
+        render_markdown_with_tables(text, width, None)
+            .into_iter()
+            .map(|line| line.spans.into_iter().map(|span| span.content.into_owned()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn heading_text_handles_atx_and_setext() {
+        assert_eq!(heading_text("## Install ##\n"), "Install");
+        assert_eq!(heading_text("# C#"), "C#");
+        assert_eq!(heading_text("Setup\nsteps\n---\n"), "Setup steps");
+    }
+
+    #[test]
+    fn headings_get_ornaments_and_rules_span_the_width() {
+        let rendered = render_strings("# Title\n\ntext\n\n---\n\n### 1. Step", 8);
+        assert_eq!(rendered[0], "\u{a7} Title");
+        assert!(rendered.contains(&"\u{2500}".repeat(8)));
+        assert_eq!(rendered.last().map(String::as_str), Some("\u{203a} 1. Step"));
+    }
+}
@@ -212,48 +212,45 @@ fn build_help_items(app: &App) -> Vec<(String, String)> {
     }
 }
 
-fn build_key_help_items(app: &App) -> Vec<(String, String)> {
-    if app.status == AppStatus::Connecting {
-        let mut items = blocked_input_help_items("Unavailable while connecting");
-        if app.update_check_hint.is_some() {
-            items.push(("Ctrl+u".to_owned(), "Hide update hint".to_owned()));
-        }
-        return items;
+fn push_update_hint_items(app: &App, items: &mut Vec<(String, String)>) {
+    if crate::app::has_release_notes(app) {
+        items.push(("Ctrl+r".to_owned(), "Update release notes".to_owned()));
     }
-    if app.status == AppStatus::CommandPending {
-        let mut items = blocked_input_help_items(&format!(
-            "Unavailable while command runs ({})",
-            pending_command_help_label(app)
-        ));
-        if app.update_check_hint.is_some() {
-            items.push(("Ctrl+u".to_owned(), "Hide update hint".to_owned()));
-        }
-        return items;
+    if app.update_check_hint.is_some() {
+        items.push(("Ctrl+u".to_owned(), "Hide update hint".to_owned()));
     }
-    if app.status == AppStatus::Error {
-        let mut items = blocked_input_help_items("Unavailable after error");
-        if app.update_check_hint.is_some() {
-            items.push(("Ctrl+u".to_owned(), "Hide update hint".to_owned()));
-        }
+}
+
+fn build_key_help_items(app: &App) -> Vec<(String, String)> {
+    if let Some(items) = blocked_key_help_items(app) {
         return items;
     }
 
     let mut items: Vec<(String, String)> = vec![
         // Global
-        ("Ctrl+c".to_owned(), "Quit".to_owned()),
+        ("Ctrl+c".to_owned(), "Quit (press twice)".to_owned()),
         ("Ctrl+q".to_owned(), "Quit".to_owned()),
+        ("Ctrl+p".to_owned(), "Command palette".to_owned()),
+        ("F1".to_owned(), "All keys and commands, searchable".to_owned()),
+        ("Ctrl+g".to_owned(), "Toggle status log".to_owned()),
         ("Ctrl+h".to_owned(), "Toggle header".to_owned()),
+        ("Ctrl+k".to_owned(), "Toggle token/cost annotations".to_owned()),
         ("Ctrl+l".to_owned(), "Redraw screen".to_owned()),
+        (
+            "Ctrl+s".to_owned(),
+            "Select chat text (hjkl, v, y; e explains, b bookmarks, m notes, o expands, p pins output, c/w/n copy/wrap/number code, Shift+\u{2190}/\u{2192} scrolls sideways, t ticks tasks, [/] headings, x rewinds, z/Z folds turns, \\ filters)"
+                .to_owned(),
+        ),
         ("Shift+Tab".to_owned(), "Cycle mode".to_owned()),
-        ("Ctrl+o".to_owned(), "Toggle tool collapse".to_owned()),
+        ("Ctrl+o".to_owned(), "Toggle tool/thinking collapse".to_owned()),
         ("Ctrl+t".to_owned(), "Toggle todos (when available)".to_owned()),
+        ("Ctrl+b".to_owned(), "Browse project files".to_owned()),
         // Chat scrolling
         ("Ctrl+Up/Down".to_owned(), "Scroll chat".to_owned()),
         ("Mouse wheel".to_owned(), "Scroll chat".to_owned()),
+        ("F12".to_owned(), "Toggle perf HUD (perf builds)".to_owned()),
     ];
-    if app.update_check_hint.is_some() {
-        items.push(("Ctrl+u".to_owned(), "Hide update hint".to_owned()));
-    }
+    push_update_hint_items(app, &mut items);
     if app.is_compacting {
         items.push(("Status".to_owned(), "Compacting context".to_owned()));
     }
@@ -262,6 +259,9 @@ fn build_key_help_items(app: &App) -> Vec<(String, String)> {
     if app.show_todo_panel && !app.todos.is_empty() {
         items.push(("Tab".to_owned(), "Toggle todo focus".to_owned()));
     }
+    if !app.suggestions.is_empty() {
+        items.push(("Alt+1..3".to_owned(), "Use a follow-up suggestion".to_owned()));
+    }
 
     // Input + navigation (active outside todo-list and mention focus)
     if focus_owner != FocusOwner::TodoList
@@ -279,6 +279,7 @@ fn build_key_help_items(app: &App) -> Vec<(String, String)> {
         items.push(("Ctrl+Backspace/Delete".to_owned(), "Delete word".to_owned()));
         items.push(("Ctrl+z/y".to_owned(), "Undo/redo".to_owned()));
         items.push(("Paste".to_owned(), "Insert text".to_owned()));
+        items.push(("\\".to_owned(), "Filter transcript (on empty input)".to_owned()));
     }
 
     // Turn control
@@ -290,6 +291,31 @@ fn build_key_help_items(app: &App) -> Vec<(String, String)> {
         items.push(("Esc".to_owned(), "No-op (idle)".to_owned()));
     }
 
+    push_focus_key_help_items(app, focus_owner, &mut items);
+    items
+}
+
+/// Help while input is blocked by connecting, a pending command or an error.
+fn blocked_key_help_items(app: &App) -> Option<Vec<(String, String)>> {
+    let reason = match app.status {
+        AppStatus::Connecting => "Unavailable while connecting".to_owned(),
+        AppStatus::CommandPending => {
+            format!("Unavailable while command runs ({})", pending_command_help_label(app))
+        }
+        AppStatus::Error => "Unavailable after error".to_owned(),
+        AppStatus::Ready | AppStatus::Thinking | AppStatus::Running => return None,
+    };
+    let mut items = blocked_input_help_items(&reason);
+    push_update_hint_items(app, &mut items);
+    Some(items)
+}
+
+/// Keys of whatever currently holds focus: inline prompts, todos or mentions.
+fn push_focus_key_help_items(
+    app: &App,
+    focus_owner: FocusOwner,
+    items: &mut Vec<(String, String)>,
+) {
     // Inline interactions (permissions or questions)
     if !app.pending_permission_ids.is_empty() && focus_owner == FocusOwner::Permission {
         if app.pending_permission_ids.len() > 1 {
@@ -310,8 +336,15 @@ fn build_key_help_items(app: &App) -> Vec<(String, String)> {
     if focus_owner == FocusOwner::TodoList {
         items.push(("Up/Down".to_owned(), "Select todo (todo focus)".to_owned()));
     }
-
-    items
+    if focus_owner == FocusOwner::Mention && app.emoji.is_some() {
+        items.push(("Up/Down".to_owned(), "Select emoji".to_owned()));
+        items.push(("Enter/Tab".to_owned(), "Insert emoji".to_owned()));
+        items.push((":".to_owned(), "Insert exact name match".to_owned()));
+    } else if focus_owner == FocusOwner::Mention {
+        items.push(("Up/Down".to_owned(), "Select mention".to_owned()));
+        items.push(("Tab".to_owned(), "Mark for multi-insert".to_owned()));
+        items.push(("Enter".to_owned(), "Insert selected or marked".to_owned()));
+    }
 }
 
 fn focused_question_prompt(app: &App) -> bool {
@@ -332,13 +365,15 @@ fn focused_question_prompt(app: &App) -> bool {
 fn blocked_input_help_items(input_line: &str) -> Vec<(String, String)> {
     vec![
         ("?".to_owned(), "Toggle help".to_owned()),
-        ("Ctrl+c".to_owned(), "Quit".to_owned()),
+        ("F1".to_owned(), "All keys and commands, searchable".to_owned()),
+        ("Ctrl+c".to_owned(), "Quit (press twice)".to_owned()),
         ("Ctrl+q".to_owned(), "Quit".to_owned()),
         ("Up/Down".to_owned(), "Scroll chat".to_owned()),
         ("Ctrl+Up/Down".to_owned(), "Scroll chat".to_owned()),
         ("Mouse wheel".to_owned(), "Scroll chat".to_owned()),
         ("Ctrl+h".to_owned(), "Toggle header".to_owned()),
         ("Ctrl+l".to_owned(), "Redraw screen".to_owned()),
+        ("Ctrl+d".to_owned(), "Agent diagnostics".to_owned()),
         ("Input keys".to_owned(), input_line.to_owned()),
     ]
 }
@@ -347,18 +382,134 @@ fn pending_command_help_label(app: &App) -> String {
     app.pending_command_label.clone().unwrap_or_else(|| "Processing command...".to_owned())
 }
 
-fn builtin_slash_help_commands() -> [(&'static str, &'static str); 7] {
+fn builtin_slash_help_commands() -> [(&'static str, &'static str); 27] {
     [
+        ("/add-dir", "Add a working directory for mentions and the agent, or list them"),
+        ("/bookmarks", "List bookmarks or jump back to one"),
+        ("/cd", "Continue in another directory with a new session"),
+        ("/commit", "Draft a commit message for the current changes and commit"),
         ("/config", "Open settings"),
+        ("/context", "Show what fills the context window"),
+        ("/diagnostics", "Show agent stderr and unrecognized bridge events"),
+        ("/env", "Show or set environment variables for agent-run commands"),
+        ("/files", "List files changed this session; diff, edit or revert them"),
+        ("/fold", "Fold earlier turns to one-line summaries"),
+        ("/issue", "Fetch a GitHub or GitLab issue into the prompt"),
+        ("/log-level", "Show or change the log filter and log file"),
         ("/login", "Authenticate with Claude"),
         ("/logout", "Sign out of Claude"),
         ("/mcp", "Open MCP"),
+        ("/memory", "Edit CLAUDE.md memory in your editor"),
         ("/plugins", "Open plugins"),
+        ("/pr", "Draft a pull request for this branch and create it"),
+        ("/set", "Set model parameters such as the thinking budget for this session"),
+        ("/share", "Export the session as redacted HTML or a secret gist"),
+        ("/stats", "Show model parameters and how long tool calls took, per tool"),
         ("/status", "Show session status"),
-        ("/usage", "Open usage"),
+        ("/test", "Run the project's tests; Tab sends a failure to Claude"),
+        ("/transcript", "Print the conversation to the terminal on quit"),
+        ("/trust", "List or manage trusted project folders"),
+        ("/update", "Check for a newer claude-rs release and install it"),
+        ("/usage", "Open usage, or chart the last 30 days across sessions"),
     ]
 }
 
+/// Every key binding, independent of the current focus, for the F1 overlay.
+const OVERLAY_KEYS: &[(&str, &str)] = &[
+    ("F1", "Full help (this screen)"),
+    ("?", "Quick help above the input (on empty input)"),
+    ("Ctrl+p", "Command palette"),
+    ("Ctrl+c", "Copy selection, or quit (press twice)"),
+    ("Ctrl+q", "Quit"),
+    ("Enter", "Send message"),
+    ("Shift+Enter", "Insert newline"),
+    ("Esc", "Cancel the running turn or dismiss a toast"),
+    ("Shift+Tab", "Cycle mode"),
+    ("Ctrl+o", "Toggle tool/thinking collapse"),
+    ("Ctrl+t", "Toggle todos (when available)"),
+    ("Tab", "Toggle todo focus"),
+    ("Ctrl+b", "Browse project files"),
+    ("Ctrl+g", "Toggle status log"),
+    ("Ctrl+h", "Toggle header"),
+    ("Ctrl+k", "Toggle token/cost annotations"),
+    ("Ctrl+l", "Redraw screen"),
+    ("Ctrl+d", "Agent diagnostics (while input is blocked)"),
+    ("Ctrl+r", "Update release notes (with an update hint)"),
+    ("Ctrl+u", "Hide update hint"),
+    ("Ctrl+y/a/n", "Quick select in a permission prompt"),
+    ("Alt+1..3", "Use a follow-up suggestion"),
+    ("\\", "Filter transcript (on empty input)"),
+    ("Up/Down", "Move cursor / scroll chat"),
+    ("Ctrl+Up/Down", "Scroll chat"),
+    ("Left/Right", "Move cursor"),
+    ("Ctrl+Left/Right", "Word left/right"),
+    ("Home/End", "Line start/end"),
+    ("Ctrl+Backspace/Delete", "Delete word"),
+    ("Ctrl+z/y", "Undo/redo"),
+    ("F12", "Toggle perf HUD (perf builds)"),
+];
+
+/// Keys of keyboard selection mode, entered with Ctrl+s.
+const OVERLAY_SELECTION_KEYS: &[(&str, &str)] = &[
+    ("h/j/k/l, arrows", "Move the cursor"),
+    ("0/$, g/G", "Line start/end, top/bottom"),
+    ("v", "Start or drop the selection anchor"),
+    ("y, Enter", "Copy and leave"),
+    ("Esc", "Leave selection mode"),
+    ("e", "Explain the text under the cursor"),
+    ("b", "Bookmark the message"),
+    ("m", "Add a note to the message"),
+    ("o", "Expand a file diff or revision"),
+    ("p", "Pin command output"),
+    ("c", "Copy the code block"),
+    ("w", "Wrap or clip the code block"),
+    ("n", "Toggle code line numbers"),
+    ("Shift+Left/Right", "Scroll wide output sideways"),
+    ("t", "Tick a task list item"),
+    ("[ / ]", "Previous/next heading"),
+    ("x", "Rewind to this turn"),
+    ("z / Z", "Fold this turn / the previous turn"),
+    ("\\", "Filter transcript"),
+];
+
+const OVERLAY_MOUSE: &[(&str, &str)] = &[
+    ("Wheel", "Scroll chat"),
+    ("Drag", "Select text; Ctrl+c copies"),
+    ("Drag scrollbar", "Scroll to a position"),
+    ("Click minimap mark", "Jump to that message"),
+    ("Hover status dot", "Show connection latency"),
+];
+
+/// Sections of the F1 help overlay, with entries matching `query`. Sections
+/// without matches are left out.
+pub(crate) fn overlay_sections(
+    app: &App,
+    query: &str,
+) -> Vec<(&'static str, Vec<(String, String)>)> {
+    let owned = |entries: &[(&str, &str)]| {
+        entries.iter().map(|&(key, desc)| (key.to_owned(), desc.to_owned())).collect::<Vec<_>>()
+    };
+    let slash =
+        build_slash_help_items(app).into_iter().filter(|(name, _)| name.starts_with('/')).collect();
+    let sections = vec![
+        ("Keys", owned(OVERLAY_KEYS)),
+        ("Selection mode (Ctrl+s)", owned(OVERLAY_SELECTION_KEYS)),
+        ("Mouse", owned(OVERLAY_MOUSE)),
+        ("Slash commands", slash),
+    ];
+    sections
+        .into_iter()
+        .map(|(title, entries)| {
+            let entries: Vec<_> = entries
+                .into_iter()
+                .filter(|(key, desc)| crate::app::help_overlay::entry_matches(key, desc, query))
+                .collect();
+            (title, entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect()
+}
+
 fn build_slash_help_items(app: &App) -> Vec<(String, String)> {
     use std::collections::BTreeMap;
 
@@ -650,42 +801,19 @@ fn take_prefix_by_width(text: &str, width: usize) -> (String, String) {
         return (String::new(), text.to_owned());
     }
 
-    let mut used = 0usize;
-    let mut split_at = 0usize;
-    for (idx, ch) in text.char_indices() {
-        let w = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
-        if used + w > width {
-            break;
-        }
-        used += w;
-        split_at = idx + ch.len_utf8();
-    }
-
-    if split_at == 0 {
+    let (prefix, _) = super::width::prefix_within(text, width);
+    if prefix.is_empty() {
         return (String::new(), text.to_owned());
     }
 
-    (text[..split_at].to_owned(), text[split_at..].to_owned())
+    (prefix.to_owned(), text[prefix.len()..].to_owned())
 }
 
 fn truncate_to_width(text: &str, width: usize) -> String {
     if width == 0 {
         return String::new();
     }
-    if UnicodeWidthStr::width(text) <= width {
-        return text.to_owned();
-    }
-    let mut out = String::new();
-    let mut used = 0usize;
-    for ch in text.chars() {
-        let w = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
-        if used + w > width {
-            break;
-        }
-        out.push(ch);
-        used += w;
-    }
-    out
+    super::width::prefix_within(text, width).0.to_owned()
 }
 
 #[cfg(test)]
@@ -753,12 +881,12 @@ mod tests {
         app.help_view = HelpView::SlashCommands;
         app.available_commands = vec![
             crate::agent::model::AvailableCommand::new("/help", "Open help"),
-            crate::agent::model::AvailableCommand::new("memory", ""),
+            crate::agent::model::AvailableCommand::new("review", ""),
         ];
 
         let items = build_help_items(&app);
         assert!(has_item(&items, "/help", "Open help"));
-        assert!(has_item(&items, "/memory", "No description provided"));
+        assert!(has_item(&items, "/review", "No description provided"));
     }
 
     #[test]
@@ -771,7 +899,11 @@ mod tests {
         assert!(has_item(&items, "/login", "Authenticate with Claude"));
         assert!(has_item(&items, "/logout", "Sign out of Claude"));
         assert!(has_item(&items, "/mcp", "Open MCP"));
-        assert!(has_item(&items, "/usage", "Open usage"));
+        assert!(has_item(
+            &items,
+            "/usage",
+            "Open usage, or chart the last 30 days across sessions"
+        ));
         assert!(!has_item(
             &items,
             "No slash commands advertised",
@@ -825,7 +957,7 @@ mod tests {
 
         let items = build_help_items(&app);
         assert!(has_item(&items, "?", "Toggle help"));
-        assert!(has_item(&items, "Ctrl+c", "Quit"));
+        assert!(has_item(&items, "Ctrl+c", "Quit (press twice)"));
         assert!(has_item(&items, "Ctrl+q", "Quit"));
         assert!(has_item(&items, "Up/Down", "Scroll chat"));
         assert!(has_item(&items, "Input keys", "Unavailable while connecting"));
@@ -838,7 +970,7 @@ mod tests {
         app.status = AppStatus::Error;
 
         let items = build_help_items(&app);
-        assert!(has_item(&items, "Ctrl+c", "Quit"));
+        assert!(has_item(&items, "Ctrl+c", "Quit (press twice)"));
         assert!(has_item(&items, "Ctrl+q", "Quit"));
         assert!(has_item(&items, "Up/Down", "Scroll chat"));
         assert!(has_item(&items, "Input keys", "Unavailable after error"));
@@ -0,0 +1,167 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::config::text_input_line;
+use super::{help, theme, width};
+use crate::app::App;
+use ratatui::Frame;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
+
+/// Narrowest column before the entries fall back to fewer columns.
+const MIN_COLUMN_WIDTH: usize = 44;
+const MAX_COLUMNS: usize = 3;
+const COLUMN_GAP: usize = 3;
+
+/// `text` cut or padded to exactly `cells` columns.
+fn fit(text: &str, cells: usize) -> String {
+    let (prefix, used) = width::prefix_within(text, cells);
+    format!("{prefix}{}", " ".repeat(cells.saturating_sub(used)))
+}
+
+fn entry_spans(key: &str, description: &str, key_width: usize, cell: usize) -> Vec<Span<'static>> {
+    vec![
+        Span::styled(fit(key, key_width), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" "),
+        Span::raw(fit(description, cell.saturating_sub(key_width + 1))),
+    ]
+}
+
+/// Help entries laid out in as many columns as the width allows, filled
+/// top to bottom.
+fn content_lines(
+    sections: &[(&str, Vec<(String, String)>)],
+    inner_width: usize,
+) -> Vec<Line<'static>> {
+    let columns = (inner_width / MIN_COLUMN_WIDTH).clamp(1, MAX_COLUMNS);
+    let cell = inner_width.saturating_sub(COLUMN_GAP * (columns - 1)) / columns;
+    let mut lines = Vec::new();
+    for (title, entries) in sections {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(Span::styled(
+            format!("{title} ({})", entries.len()),
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        )));
+        let key_width = entries
+            .iter()
+            .map(|(key, _)| width::display_width(key))
+            .max()
+            .unwrap_or(0)
+            .min(cell / 2);
+        let rows = entries.len().div_ceil(columns);
+        for row in 0..rows {
+            let mut spans = Vec::new();
+            for column in 0..columns {
+                let Some((key, description)) = entries.get(column * rows + row) else {
+                    break;
+                };
+                if column > 0 {
+                    spans.push(Span::raw(" ".repeat(COLUMN_GAP)));
+                }
+                spans.extend(entry_spans(key, description, key_width, cell));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
+}
+
+/// Draw the F1 help over the whole screen. Clamps the overlay's scroll to
+/// the content.
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let Some(overlay) = app.help_overlay.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    if area.width < 20 || area.height < 6 {
+        return;
+    }
+    let inner_width = usize::from(area.width.saturating_sub(4));
+    let sections = help::overlay_sections(app, &overlay.input.text);
+    let content = content_lines(&sections, inner_width);
+    // Borders, the query row and the rule below it.
+    let body_rows = usize::from(area.height.saturating_sub(4));
+    let scroll = overlay.scroll.min(content.len().saturating_sub(body_rows));
+
+    let visible = inner_width.saturating_sub(1);
+    let skip = overlay.input.cursor.saturating_sub(visible);
+    let draft: String = overlay.input.text.chars().skip(skip).collect();
+    let mut lines = vec![
+        text_input_line(&draft, overlay.input.cursor - skip, "Type to search keys and commands"),
+        Line::from(Span::styled(
+            theme::SEPARATOR_CHAR.repeat(inner_width),
+            Style::default().fg(theme::DIM),
+        )),
+    ];
+    if content.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nothing matches the search",
+            Style::default().fg(theme::DIM),
+        )));
+    }
+    let position = if content.len() > body_rows {
+        format!(" {}-{} of {} ", scroll + 1, (scroll + body_rows).min(content.len()), content.len())
+    } else {
+        String::new()
+    };
+    lines.extend(content.into_iter().skip(scroll).take(body_rows));
+
+    let block = Block::default()
+        .title(Span::styled(
+            " Help ",
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            format!(
+                " Type to search \u{b7} \u{2191}\u{2193} PgUp/PgDn scroll \u{b7} Esc close {position}"
+            ),
+            Style::default().fg(theme::DIM),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::RUST_ORANGE))
+        .padding(Padding::horizontal(1));
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    if let Some(overlay) = app.help_overlay.as_mut() {
+        overlay.scroll = scroll;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn entries_fill_columns_top_to_bottom() {
+        let entries = vec![("a".to_owned(), "one".to_owned()), ("b".to_owned(), "two".to_owned())];
+        let sections = vec![("Keys", entries)];
+
+        let wide = content_lines(&sections, 2 * MIN_COLUMN_WIDTH + COLUMN_GAP);
+        assert_eq!(wide.len(), 2);
+        assert_eq!(text(&wide[0]), "Keys (2)");
+        assert!(text(&wide[1]).starts_with("a one"));
+        assert!(text(&wide[1]).contains("   b two"));
+
+        let narrow = content_lines(&sections, MIN_COLUMN_WIDTH);
+        assert_eq!(narrow.len(), 3);
+        assert!(text(&narrow[2]).starts_with("b two"));
+    }
+
+    #[test]
+    fn search_keeps_only_matching_sections() {
+        let app = App::test_default();
+        let sections = help::overlay_sections(&app, "palette");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].1, vec![("Ctrl+p".to_owned(), "Command palette".to_owned())]);
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
-use super::diff;
+use super::{diff, theme};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use std::sync::LazyLock;
@@ -138,12 +138,98 @@ fn highlight_line(line: &str, highlighter: &mut HighlightLines<'_>) -> Line<'sta
     }
 }
 
+/// How a line of command output reads, judged from its text alone so logs
+/// are colored even when the command printed no ANSI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogLevel {
+    Error,
+    Warn,
+    /// An `INFO` line; only the level word is colored.
+    Info,
+    /// Debug and trace output, stack frames.
+    Quiet,
+}
+
+/// Level words are looked for among this many leading words, to get past
+/// timestamps and logger names.
+const LEVEL_WORD_WINDOW: usize = 3;
+
+fn level_of_word(word: &str) -> Option<LogLevel> {
+    let word = word.trim_matches(|ch: char| !ch.is_ascii_alphabetic());
+    match word {
+        "ERROR" | "ERR" | "FATAL" | "CRITICAL" | "PANIC" | "FAIL" | "FAILED" => {
+            Some(LogLevel::Error)
+        }
+        "WARN" | "WARNING" => Some(LogLevel::Warn),
+        "INFO" => Some(LogLevel::Info),
+        "DEBUG" | "TRACE" => Some(LogLevel::Quiet),
+        _ => None,
+    }
+}
+
+fn log_level(line: &str) -> Option<LogLevel> {
+    let trimmed = line.trim_start();
+    // Compiler diagnostics, panics and tracebacks.
+    if trimmed.starts_with("error:")
+        || trimmed.starts_with("error[")
+        || trimmed.starts_with("Traceback (most recent call last)")
+        || trimmed.starts_with("Caused by:")
+        || (trimmed.starts_with("thread '") && trimmed.contains("' panicked at"))
+        || trimmed.ends_with(" FAILED")
+        || trimmed.split_once(": ").is_some_and(|(name, _)| {
+            name.ends_with("Error") && name.chars().all(|ch| ch.is_alphanumeric() || ch == '.')
+        })
+    {
+        return Some(LogLevel::Error);
+    }
+    if trimmed.starts_with("warning:") || trimmed.starts_with("warning[") {
+        return Some(LogLevel::Warn);
+    }
+    // Stack frames: JS/Java `at ...`, Python `File "...", line N`, Rust `N: ...`.
+    let indented = trimmed.len() < line.len();
+    if indented
+        && (trimmed.starts_with("at ")
+            || trimmed.starts_with("File \"")
+            || trimmed.split_once(": ").is_some_and(|(frame, _)| {
+                !frame.is_empty() && frame.bytes().all(|byte| byte.is_ascii_digit())
+            }))
+    {
+        return Some(LogLevel::Quiet);
+    }
+    trimmed.split_whitespace().take(LEVEL_WORD_WINDOW).find_map(level_of_word)
+}
+
+/// One line of command output, colored by its log level.
+fn log_line(line: &str) -> Line<'static> {
+    let color = match log_level(line) {
+        None => return Line::from(line.to_owned()),
+        Some(LogLevel::Error) => theme::STATUS_ERROR,
+        Some(LogLevel::Warn) => theme::STATUS_WARNING,
+        Some(LogLevel::Quiet) => theme::DIM,
+        Some(LogLevel::Info) => {
+            let word = line
+                .split_whitespace()
+                .take(LEVEL_WORD_WINDOW)
+                .find(|word| level_of_word(word) == Some(LogLevel::Info));
+            let Some(start) = word.and_then(|word| line.find(word)) else {
+                return Line::from(line.to_owned());
+            };
+            let end = start + word.map_or(0, str::len);
+            return Line::from(vec![
+                Span::raw(line[..start].to_owned()),
+                Span::styled(line[start..end].to_owned(), Style::default().fg(Color::Green)),
+                Span::raw(line[end..].to_owned()),
+            ]);
+        }
+    };
+    Line::from(Span::styled(line.to_owned(), Style::default().fg(color)))
+}
+
 fn plain_text_lines(text: &str) -> Vec<Line<'static>> {
     if text.is_empty() {
         return vec![Line::default()];
     }
-    let mut lines: Vec<Line<'static>> =
-        text.split('\n').map(|line| Line::from(line.to_owned())).collect();
+    let mut lines: Vec<Line<'static>> = text.split('\n').map(log_line).collect();
     if lines.is_empty() {
         lines.push(Line::default());
     }
@@ -201,6 +287,27 @@ mod tests {
         assert_eq!(strip_ansi(input), "prefixsuffix");
     }
 
+    #[test]
+    fn command_output_is_colored_by_log_level() {
+        let color = |line: &str| log_line(line).spans.first().and_then(|span| span.style.fg);
+        assert_eq!(color("error[E0308]: mismatched types"), Some(theme::STATUS_ERROR));
+        assert_eq!(color("2025-01-02T03:04:05Z ERROR db: gone"), Some(theme::STATUS_ERROR));
+        assert_eq!(color("thread 'main' panicked at src/main.rs:2:5:"), Some(theme::STATUS_ERROR));
+        assert_eq!(color("ValueError: bad input"), Some(theme::STATUS_ERROR));
+        assert_eq!(color("test parses ... FAILED"), Some(theme::STATUS_ERROR));
+        assert_eq!(color("[WARN] disk almost full"), Some(theme::STATUS_WARNING));
+        assert_eq!(color("warning: unused variable: `x`"), Some(theme::STATUS_WARNING));
+        assert_eq!(color("    at run (index.js:3:9)"), Some(theme::DIM));
+        assert_eq!(color("   4: std::rt::lang_start"), Some(theme::DIM));
+        assert_eq!(color("DEBUG polling"), Some(theme::DIM));
+        assert_eq!(color("compiled 3 errors in total"), None);
+
+        let info = log_line("12:00:01 INFO server: ready");
+        assert_eq!(info.spans[1].content, "INFO");
+        assert_eq!(info.spans[1].style.fg, Some(Color::Green));
+        assert_eq!(info.spans[2].content, " server: ready");
+    }
+
     #[test]
     fn highlight_code_preserves_text() {
         let rendered = highlight_code("fn main() {}\n", Some("rs"));
@@ -0,0 +1,85 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Horizontal scrolling for preformatted rows (code blocks, command output,
+//! diffs). A scrolled block shows a window of each row instead of wrapping it:
+//! `‹` marks text hidden to the left and `›` text hidden to the right.
+
+use super::{theme, width};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+pub(crate) const LEFT_MARK: &str = "\u{2039}";
+pub(crate) const RIGHT_MARK: &str = "\u{203a}";
+
+/// Columns `offset..offset + max_width` of `line`, with scroll marks where
+/// text is cut off. Lines that fit unscrolled are returned as they are.
+pub(super) fn clip_line(line: &Line<'static>, offset: usize, max_width: usize) -> Line<'static> {
+    let line_width = line.width();
+    if offset == 0 && line_width <= max_width {
+        return line.clone();
+    }
+    let mark_style = Style::default().fg(theme::DIM);
+    let hidden_left = offset > 0 && line_width > 0;
+    let mut room = max_width.saturating_sub(usize::from(hidden_left));
+    let hidden_right = line_width > offset + room;
+    if hidden_right {
+        room = room.saturating_sub(1);
+    }
+
+    let mut spans = Vec::new();
+    if hidden_left {
+        spans.push(Span::styled(LEFT_MARK, mark_style));
+    }
+    let mut col = 0usize;
+    let mut used = 0usize;
+    for span in &line.spans {
+        let mut text = String::new();
+        for (grapheme, grapheme_width) in width::graphemes(&span.content) {
+            let start = col;
+            col += grapheme_width;
+            if col <= offset {
+                continue;
+            }
+            if used + grapheme_width > room {
+                break;
+            }
+            if start < offset {
+                // A wide glyph cut by the left edge.
+                text.push_str(&" ".repeat(col - offset));
+                used += col - offset;
+            } else {
+                text.push_str(grapheme);
+                used += grapheme_width;
+            }
+        }
+        if !text.is_empty() {
+            spans.push(Span::styled(text, span.style));
+        }
+    }
+    if hidden_right {
+        spans.push(Span::raw(" ".repeat(room - used)));
+        spans.push(Span::styled(RIGHT_MARK, mark_style));
+    }
+    Line::from(spans).style(line.style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn scrolled_rows_show_a_window_with_marks() {
+        let line = Line::from(vec![Span::raw("0123"), Span::raw("456789")]);
+        assert_eq!(text(&clip_line(&line, 0, 10)), "0123456789");
+        assert_eq!(text(&clip_line(&line, 0, 6)), "01234\u{203a}");
+        assert_eq!(text(&clip_line(&line, 3, 6)), "\u{2039}3456\u{203a}");
+        assert_eq!(text(&clip_line(&line, 6, 6)), "\u{2039}6789");
+        assert_eq!(text(&clip_line(&Line::from("a\u{4f60}b"), 2, 4)), "\u{2039} b");
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! OSC 8 hyperlinks for URLs drawn in the chat. After the chat is rendered,
+//! every `http(s)://` run in the buffer is rewritten so its first cell carries
+//! the whole run wrapped in an OSC 8 sequence and the rest of the run is
+//! skipped when the frame is flushed. A URL wrapped onto the next row links
+//! both pieces to the full address. Terminals without OSC 8 print the text.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+
+/// A URL piece on one row: where it starts and the text of its cells.
+struct Run {
+    x: u16,
+    y: u16,
+    text: String,
+}
+
+fn is_url_char(symbol: &str) -> bool {
+    let mut chars = symbol.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None)
+        if c.is_ascii_graphic() && !matches!(c, '<' | '>' | '"' | '`'))
+}
+
+fn cell_symbol(buf: &Buffer, x: u16, y: u16) -> &str {
+    buf.cell((x, y)).map_or(" ", ratatui::buffer::Cell::symbol)
+}
+
+/// Length of the URL-character run starting at `x`, capped at the area edge.
+fn run_len(buf: &Buffer, area: Rect, x: u16, y: u16) -> u16 {
+    (x..area.right()).take_while(|&cx| is_url_char(cell_symbol(buf, cx, y))).fold(0, |n, _| n + 1)
+}
+
+fn run_text(buf: &Buffer, x: u16, y: u16, len: u16) -> String {
+    (x..x + len).map(|cx| cell_symbol(buf, cx, y)).collect()
+}
+
+/// Trailing punctuation that usually ends the sentence rather than the URL.
+fn trim_url_end(text: &str) -> &str {
+    text.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '\''])
+}
+
+/// URL runs on row `y` from column `x`, each followed by its continuation rows.
+fn url_runs(buf: &Buffer, area: Rect, mut x: u16, y: u16) -> Vec<Vec<Run>> {
+    let mut urls = Vec::new();
+    while x < area.right() {
+        let len = run_len(buf, area, x, y);
+        if len == 0 {
+            x += 1;
+            continue;
+        }
+        let text = run_text(buf, x, y, len);
+        let Some(offset) = text.find("https://").or_else(|| text.find("http://")) else {
+            x += len;
+            continue;
+        };
+        let start = x + u16::try_from(offset).unwrap_or(0);
+        let mut pieces = vec![Run { x: start, y, text: text[offset..].to_owned() }];
+        let mut end = x + len;
+        let mut row = y;
+        while end == area.right() && row + 1 < area.bottom() {
+            row += 1;
+            let next = run_len(buf, area, area.x, row);
+            if next == 0 {
+                break;
+            }
+            pieces.push(Run { x: area.x, y: row, text: run_text(buf, area.x, row, next) });
+            end = area.x + next;
+        }
+        x += len;
+        urls.push(pieces);
+    }
+    urls
+}
+
+/// Turn every URL inside `area` into an OSC 8 hyperlink.
+pub fn link_urls(buf: &mut Buffer, area: Rect) {
+    let (mut x, mut y) = (area.x, area.y);
+    while y < area.bottom() {
+        let mut next = (area.x, y + 1);
+        for mut pieces in url_runs(buf, area, x, y) {
+            if let Some(last) = pieces.last_mut() {
+                // A URL that wrapped onto later rows; scanning resumes after it.
+                if last.y > y {
+                    next = (last.x + run_len(buf, area, last.x, last.y), last.y);
+                }
+                last.text = trim_url_end(&last.text).to_owned();
+            }
+            let url: String = pieces.iter().map(|piece| piece.text.as_str()).collect();
+            for piece in &pieces {
+                apply_link(buf, piece, &url);
+            }
+        }
+        (x, y) = next;
+    }
+}
+
+fn apply_link(buf: &mut Buffer, piece: &Run, url: &str) {
+    if piece.text.is_empty() {
+        return;
+    }
+    let symbol = format!("\u{1b}]8;;{url}\u{7}{}\u{1b}]8;;\u{7}", piece.text);
+    if let Some(cell) = buf.cell_mut((piece.x, piece.y)) {
+        cell.set_symbol(&symbol);
+    }
+    let width = u16::try_from(piece.text.len()).unwrap_or(u16::MAX);
+    for x in piece.x + 1..piece.x.saturating_add(width) {
+        if let Some(cell) = buf.cell_mut((x, piece.y)) {
+            cell.set_skip(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn wrapped_urls_link_to_the_full_address() {
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buf = Buffer::empty(area);
+        buf.set_string(0, 0, "see https://", ratatui::style::Style::default());
+        buf.set_string(0, 1, "a.io/x. done", ratatui::style::Style::default());
+
+        link_urls(&mut buf, area);
+
+        assert_eq!(buf[(4, 0)].symbol(), "\u{1b}]8;;https://a.io/x\u{7}https://\u{1b}]8;;\u{7}");
+        assert!(buf[(5, 0)].skip);
+        assert_eq!(buf[(0, 1)].symbol(), "\u{1b}]8;;https://a.io/x\u{7}a.io/x\u{1b}]8;;\u{7}");
+        assert!(buf[(5, 1)].skip);
+        assert!(!buf[(6, 1)].skip);
+        assert_eq!(buf[(8, 1)].symbol(), "d");
+    }
+}
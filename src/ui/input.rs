@@ -88,7 +88,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &mut App) {
                     Style::default().fg(Color::Yellow),
                 )),
                 Line::from(Span::styled(
-                    "Type /login to authenticate, or run `claude auth login` in another terminal",
+                    "Type /login to authenticate",
                     Style::default().fg(theme::DIM),
                 )),
             ];
@@ -302,17 +302,7 @@ impl Widget for SelectionOverlay {
 fn render_lines_from_textarea(textarea: &TextArea<'_>, area: Rect) -> Vec<String> {
     let mut buf = Buffer::empty(area);
     textarea.render(area, &mut buf);
-    let mut lines = Vec::with_capacity(area.height as usize);
-    for y in 0..area.height {
-        let mut line = String::new();
-        for x in 0..area.width {
-            if let Some(cell) = buf.cell((area.x + x, area.y + y)) {
-                line.push_str(cell.symbol());
-            }
-        }
-        lines.push(line.trim_end().to_owned());
-    }
-    lines
+    (0..area.height).map(|y| super::width::buffer_row_text(&buf, area, y)).collect()
 }
 
 /// Total visual height for the input area: input lines + hint banners.
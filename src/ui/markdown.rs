@@ -5,6 +5,13 @@ use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use std::panic::{self, AssertUnwindSafe};
 
+/// Whether `line` opens or closes a fenced code block.
+#[must_use]
+pub(crate) fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
 pub(super) fn render_markdown_safe(text: &str, bg: Option<Color>) -> Vec<Line<'static>> {
     render_markdown_safe_with(text, bg, render_with_tui_markdown)
 }
@@ -1,10 +1,17 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::agent::error_handling::TurnErrorClass;
+use crate::app::diff_stats::file_row_marker;
+use crate::app::welcome::{MAX_WELCOME_SESSIONS, QUICK_ACTIONS};
 use crate::app::{
-    BlockCache, ChatMessage, IncrementalMarkdown, MessageBlock, MessageRole, SystemSeverity,
-    TextBlock, WelcomeBlock,
+    BlockCache, ChatMessage, CodeView, DiffSummaryBlock, IncrementalMarkdown, MessageBlock,
+    MessageRole, RecoveryHintBlock, SystemSeverity, TextBlock, ThinkingBlock, ThinkingDisplay,
+    WelcomeBanner, WelcomeBlock,
 };
+use crate::ui::diff::{render_diff, render_word_diff};
+use crate::ui::footnotes;
+use crate::ui::markdown;
 use crate::ui::tables;
 use crate::ui::theme;
 use crate::ui::tool_call;
@@ -29,6 +36,16 @@ const FERRIS_SAYS: &[&str] = &[
     r"          / '-----' \ ",
 ];
 
+const STREAMING_CURSOR: &str = "\u{258c}";
+
+/// Orange shades the streaming cursor cycles through as the spinner advances.
+const STREAMING_CURSOR_SHIMMER: [Color; 4] = [
+    theme::RUST_ORANGE,
+    Color::Rgb(250, 160, 70),
+    Color::Rgb(255, 200, 140),
+    Color::Rgb(250, 160, 70),
+];
+
 /// Snapshot of the app state needed by the spinner -- extracted before
 /// the message loop so we don't need `&App` (which conflicts with `&mut msg`).
 #[derive(Clone, Copy)]
@@ -47,6 +64,9 @@ pub struct SpinnerState {
     pub is_subagent_thinking: bool,
     /// True while the SDK reports active compaction.
     pub is_compacting: bool,
+    /// True when finished assistant messages end with their token/cost line.
+    pub show_usage: bool,
+    pub thinking_display: ThinkingDisplay,
 }
 
 fn assistant_role_label_line() -> Line<'static> {
@@ -86,11 +106,15 @@ pub fn render_message(
             }
         }
         MessageRole::System(_) => {
-            let severity = system_severity_from_role(&msg.role);
-            out.push(system_role_label_line(severity));
-            render_system_blocks(msg, width, system_severity_color(severity), out);
+            let (label, color) = system_label_and_color(msg);
+            out.push(label);
+            render_system_blocks(msg, width, color, out);
         }
     }
+    out.extend(footnote_lines(msg));
+    if let Some(line) = note_annotation_line(msg) {
+        out.push(line);
+    }
 
     // Blank separator between messages
     out.push(Line::default());
@@ -126,15 +150,18 @@ fn render_assistant_message(
     }
 
     let show_subagent_thinking = spinner.is_subagent_thinking && !show_compacting;
+    let cursor_idx = streaming_cursor_block(msg, spinner);
     let mut prev_was_tool = false;
     let mut has_visible_content = false;
-    for block in &mut msg.blocks {
+    for (block_idx, block) in msg.blocks.iter_mut().enumerate() {
         match block {
             MessageBlock::Text(block) => {
                 if prev_was_tool {
                     out.push(Line::default());
                 }
-                let rendered = assistant_text_block_render(block, width, !has_visible_content);
+                let cursor = (cursor_idx == Some(block_idx)).then_some(spinner.frame);
+                let rendered =
+                    assistant_text_block_render(block, width, !has_visible_content, cursor);
                 let trailing_gap = if !has_visible_content && rendered.height == 0 {
                     0
                 } else {
@@ -159,10 +186,33 @@ fn render_assistant_message(
                 has_visible_content = true;
                 prev_was_tool = true;
             }
-            MessageBlock::Welcome(_) => {}
+            MessageBlock::Thinking(block) => {
+                let (h, _) = thinking_block_height_cached(block, spinner.thinking_display, width);
+                if h == 0 {
+                    continue;
+                }
+                if !prev_was_tool && out.len() > 1 {
+                    out.push(Line::default());
+                }
+                render_thinking_cached(block, spinner.thinking_display, width, out);
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::DiffSummary(block) => {
+                if out.len() > 1 {
+                    out.push(Line::default());
+                }
+                render_diff_summary_cached(block, width, out);
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::Welcome(_) | MessageBlock::RecoveryHint(_) => {}
         }
     }
 
+    if let Some(line) = usage_annotation_line(msg, spinner) {
+        out.push(line);
+    }
     if show_compacting {
         out.push(Line::default());
         out.push(compacting_line(spinner.frame));
@@ -184,16 +234,91 @@ fn render_system_blocks(
     out: &mut Vec<Line<'static>>,
 ) {
     for block in &mut msg.blocks {
-        if let MessageBlock::Text(block) = block {
-            let mut lines = Vec::new();
-            render_text_block_cached(block, width, None, false, &mut lines);
-            emit_blank_lines(&mut lines, block.trailing_blank_lines());
-            tint_lines(&mut lines, color);
-            out.extend(lines);
+        match block {
+            MessageBlock::Text(block) => {
+                let mut lines = Vec::new();
+                render_text_block_cached(block, width, None, false, &mut lines);
+                emit_blank_lines(&mut lines, block.trailing_blank_lines());
+                tint_lines(&mut lines, color);
+                out.extend(lines);
+            }
+            MessageBlock::RecoveryHint(block) => render_recovery_hint_cached(block, width, out),
+            _ => {}
         }
     }
 }
 
+/// Label line and text color of a system message. Turn errors carrying a
+/// recovery hint show their class instead of the bare severity.
+fn system_label_and_color(msg: &ChatMessage) -> (Line<'static>, Color) {
+    let class = msg.blocks.iter().find_map(|block| match block {
+        MessageBlock::RecoveryHint(hint) => Some(hint.class),
+        _ => None,
+    });
+    if let Some(class) = class {
+        let color = turn_error_color(class);
+        let label = Line::from(Span::styled(
+            format!("{} {}", class.icon(), class.title()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        return (label, color);
+    }
+    let severity = system_severity_from_role(&msg.role);
+    (system_role_label_line(severity), system_severity_color(severity))
+}
+
+fn turn_error_color(class: TurnErrorClass) -> Color {
+    match class {
+        TurnErrorClass::PlanLimit => theme::STATUS_WARNING,
+        TurnErrorClass::AuthRequired => Color::Magenta,
+        TurnErrorClass::Network => Color::Cyan,
+        TurnErrorClass::ToolFailure => Color::LightRed,
+        TurnErrorClass::ProtocolMismatch => Color::LightYellow,
+        TurnErrorClass::Crash | TurnErrorClass::Internal | TurnErrorClass::Other => {
+            theme::STATUS_ERROR
+        }
+    }
+}
+
+fn recovery_hint_lines(block: &RecoveryHintBlock) -> Vec<Line<'static>> {
+    let color = turn_error_color(block.class);
+    vec![Line::from(vec![
+        Span::styled("\u{2192} ", Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(block.class.recovery_hint(), Style::default().fg(color)),
+    ])]
+}
+
+fn render_recovery_hint_cached(
+    block: &mut RecoveryHintBlock,
+    width: u16,
+    out: &mut Vec<Line<'static>>,
+) {
+    if block.cache.get().is_none() {
+        let fresh = recovery_hint_lines(block);
+        let h =
+            Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+        block.cache.store(fresh);
+        block.cache.set_height(h, width);
+    }
+    if let Some(stored) = block.cache.get() {
+        out.extend_from_slice(stored);
+    }
+}
+
+fn recovery_hint_height_cached(block: &mut RecoveryHintBlock, width: u16) -> usize {
+    if let Some(h) = block.cache.height_at(width) {
+        return h;
+    }
+    if let Some(h) = block.cache.measure_and_set_height(width) {
+        return h;
+    }
+    let fresh = recovery_hint_lines(block);
+    let h = Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+    block.cache.store(fresh);
+    block.cache.set_height(h, width);
+    h
+}
+
 fn system_severity_color(severity: SystemSeverity) -> Color {
     match severity {
         SystemSeverity::Info => theme::DIM,
@@ -257,8 +382,21 @@ pub fn measure_message_height_cached(
             let (h, lines) = measure_text_blocks_height(msg, width, None, false);
             height += h;
             wrapped_lines += lines;
+            for block in &mut msg.blocks {
+                if let MessageBlock::RecoveryHint(block) = block {
+                    height += recovery_hint_height_cached(block, width);
+                }
+            }
         }
     }
+    let references = footnote_lines(msg);
+    if !references.is_empty() {
+        height +=
+            Paragraph::new(Text::from(references)).wrap(Wrap { trim: false }).line_count(width);
+    }
+    if let Some(line) = note_annotation_line(msg) {
+        height += Paragraph::new(line).wrap(Wrap { trim: false }).line_count(width);
+    }
 
     // Blank separator between messages
     (height + 1, wrapped_lines)
@@ -290,20 +428,23 @@ fn measure_assistant_blocks_height(
 ) -> (usize, usize) {
     let show_compacting = spinner.is_compacting && spinner.is_last_message;
     let show_subagent_thinking = spinner.is_subagent_thinking && !show_compacting;
+    let cursor_idx = streaming_cursor_block(msg, spinner);
     let mut prev_was_tool = false;
     let mut lines_after_label = 0usize;
     let mut height = 0usize;
     let mut wrapped_lines = 0usize;
     let mut has_visible_content = false;
 
-    for block in &mut msg.blocks {
+    for (block_idx, block) in msg.blocks.iter_mut().enumerate() {
         match block {
             MessageBlock::Text(block) => {
                 if prev_was_tool {
                     height += 1;
                     lines_after_label += 1;
                 }
-                let rendered = assistant_text_block_render(block, width, !has_visible_content);
+                let cursor = (cursor_idx == Some(block_idx)).then_some(spinner.frame);
+                let rendered =
+                    assistant_text_block_render(block, width, !has_visible_content, cursor);
                 let trailing_gap = if !has_visible_content && rendered.height == 0 {
                     0
                 } else {
@@ -340,10 +481,41 @@ fn measure_assistant_blocks_height(
                 has_visible_content = true;
                 prev_was_tool = true;
             }
-            MessageBlock::Welcome(_) => {}
+            MessageBlock::Thinking(block) => {
+                let (h, lines) =
+                    thinking_block_height_cached(block, spinner.thinking_display, width);
+                if h == 0 {
+                    continue;
+                }
+                if !prev_was_tool && lines_after_label > 0 {
+                    height += 1;
+                    lines_after_label += 1;
+                }
+                height += h;
+                lines_after_label += h;
+                wrapped_lines += lines;
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::DiffSummary(block) => {
+                if lines_after_label > 0 {
+                    height += 1;
+                    lines_after_label += 1;
+                }
+                let (h, lines) = diff_summary_height_cached(block, width);
+                height += h;
+                lines_after_label += h;
+                wrapped_lines += lines;
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::Welcome(_) | MessageBlock::RecoveryHint(_) => {}
         }
     }
 
+    if let Some(line) = usage_annotation_line(msg, spinner) {
+        height += Paragraph::new(line).wrap(Wrap { trim: false }).line_count(width);
+    }
     if show_compacting || show_subagent_thinking {
         height += 2;
     }
@@ -372,7 +544,7 @@ pub fn render_message_from_offset(
 
     let role_line = match msg.role {
         MessageRole::Assistant => assistant_role_label_line(),
-        MessageRole::System(_) => system_role_label_line(system_severity_from_role(&msg.role)),
+        MessageRole::System(_) => system_label_and_color(msg).0,
         _ => role_label_line(&msg.role),
     };
     emit_line_with_skip(role_line, out, &mut remaining_skip, can_consume_skip);
@@ -406,6 +578,21 @@ pub fn render_message_from_offset(
             render_system_from_offset(msg, width, out, &mut remaining_skip, &mut can_consume_skip);
         }
     }
+    let references = footnote_lines(msg);
+    if !references.is_empty() {
+        let h = Paragraph::new(Text::from(references.clone()))
+            .wrap(Wrap { trim: false })
+            .line_count(width);
+        if !should_skip_whole_block(h, &mut remaining_skip, &mut can_consume_skip) {
+            out.extend(references);
+        }
+    }
+    if let Some(line) = note_annotation_line(msg) {
+        let h = Paragraph::new(line.clone()).wrap(Wrap { trim: false }).line_count(width);
+        if !should_skip_whole_block(h, &mut remaining_skip, &mut can_consume_skip) {
+            out.push(line);
+        }
+    }
 
     emit_line_with_skip(Line::default(), out, &mut remaining_skip, can_consume_skip);
     remaining_skip
@@ -478,48 +665,62 @@ fn render_assistant_from_offset(
         return;
     }
 
-    let show_subagent_thinking = spinner.is_subagent_thinking && !show_compacting;
+    render_assistant_blocks_from_offset(
+        msg,
+        spinner,
+        width,
+        layout_generation,
+        out,
+        remaining_skip,
+        can_consume_skip,
+    );
+    render_assistant_trailer_from_offset(
+        msg,
+        spinner,
+        width,
+        out,
+        remaining_skip,
+        can_consume_skip,
+    );
+}
+
+fn render_assistant_blocks_from_offset(
+    msg: &mut ChatMessage,
+    spinner: &SpinnerState,
+    width: u16,
+    layout_generation: u64,
+    out: &mut Vec<Line<'static>>,
+    remaining_skip: &mut usize,
+    can_consume_skip: &mut bool,
+) {
+    let cursor_idx = streaming_cursor_block(msg, spinner);
     let mut prev_was_tool = false;
     let mut lines_after_label = 0usize;
     let mut has_visible_content = false;
-    for block in &mut msg.blocks {
+    for (block_idx, block) in msg.blocks.iter_mut().enumerate() {
         match block {
             MessageBlock::Text(block) => {
                 if prev_was_tool {
                     emit_line_with_skip(Line::default(), out, remaining_skip, *can_consume_skip);
                     lines_after_label += 1;
                 }
-                let rendered = assistant_text_block_render(block, width, !has_visible_content);
+                let cursor = (cursor_idx == Some(block_idx)).then_some(spinner.frame);
+                let rendered =
+                    assistant_text_block_render(block, width, !has_visible_content, cursor);
                 let trailing_gap = if !has_visible_content && rendered.height == 0 {
                     0
                 } else {
                     block.trailing_blank_lines()
                 };
                 let h = rendered.height;
-                let total_h = h + trailing_gap;
-                if h == 0 {
-                    emit_blank_lines_with_skip(
-                        trailing_gap,
-                        out,
-                        remaining_skip,
-                        *can_consume_skip,
-                    );
-                } else if *can_consume_skip && *remaining_skip >= total_h {
-                    *remaining_skip -= total_h;
-                } else if *can_consume_skip && *remaining_skip >= h {
-                    *remaining_skip -= h;
-                    emit_blank_lines_with_skip(trailing_gap, out, remaining_skip, true);
-                } else {
-                    if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
-                        out.extend(rendered.lines);
-                    }
-                    emit_blank_lines_with_skip(
-                        trailing_gap,
-                        out,
-                        remaining_skip,
-                        *can_consume_skip,
-                    );
-                }
+                emit_text_block_with_skip(
+                    rendered.lines,
+                    h,
+                    trailing_gap,
+                    out,
+                    remaining_skip,
+                    can_consume_skip,
+                );
                 lines_after_label += h + trailing_gap;
                 if h > 0 {
                     has_visible_content = true;
@@ -548,10 +749,83 @@ fn render_assistant_from_offset(
                 has_visible_content = true;
                 prev_was_tool = true;
             }
-            MessageBlock::Welcome(_) => {}
+            MessageBlock::Thinking(block) => {
+                let (h, _) = thinking_block_height_cached(block, spinner.thinking_display, width);
+                if h == 0 {
+                    continue;
+                }
+                if !prev_was_tool && lines_after_label > 0 {
+                    emit_line_with_skip(Line::default(), out, remaining_skip, *can_consume_skip);
+                    lines_after_label += 1;
+                }
+                if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
+                    render_thinking_cached(block, spinner.thinking_display, width, out);
+                }
+                lines_after_label += h;
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::DiffSummary(block) => {
+                if lines_after_label > 0 {
+                    emit_line_with_skip(Line::default(), out, remaining_skip, *can_consume_skip);
+                    lines_after_label += 1;
+                }
+                let (h, _) = diff_summary_height_cached(block, width);
+                if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
+                    render_diff_summary_cached(block, width, out);
+                }
+                lines_after_label += h;
+                has_visible_content = true;
+                prev_was_tool = true;
+            }
+            MessageBlock::Welcome(_) | MessageBlock::RecoveryHint(_) => {}
         }
     }
+}
 
+/// Emit a rendered text block of height `h` plus its trailing gap, consuming
+/// the skip budget a whole block at a time where possible.
+fn emit_text_block_with_skip(
+    lines: Vec<Line<'static>>,
+    h: usize,
+    trailing_gap: usize,
+    out: &mut Vec<Line<'static>>,
+    remaining_skip: &mut usize,
+    can_consume_skip: &mut bool,
+) {
+    let total_h = h + trailing_gap;
+    if h == 0 {
+        emit_blank_lines_with_skip(trailing_gap, out, remaining_skip, *can_consume_skip);
+    } else if *can_consume_skip && *remaining_skip >= total_h {
+        *remaining_skip -= total_h;
+    } else if *can_consume_skip && *remaining_skip >= h {
+        *remaining_skip -= h;
+        emit_blank_lines_with_skip(trailing_gap, out, remaining_skip, true);
+    } else {
+        if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
+            out.extend(lines);
+        }
+        emit_blank_lines_with_skip(trailing_gap, out, remaining_skip, *can_consume_skip);
+    }
+}
+
+/// Usage annotation and activity indicators that close an assistant message.
+fn render_assistant_trailer_from_offset(
+    msg: &ChatMessage,
+    spinner: &SpinnerState,
+    width: u16,
+    out: &mut Vec<Line<'static>>,
+    remaining_skip: &mut usize,
+    can_consume_skip: &mut bool,
+) {
+    let show_compacting = spinner.is_compacting && spinner.is_last_message;
+    let show_subagent_thinking = spinner.is_subagent_thinking && !show_compacting;
+    if let Some(line) = usage_annotation_line(msg, spinner) {
+        let h = Paragraph::new(line.clone()).wrap(Wrap { trim: false }).line_count(width);
+        if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
+            out.push(line);
+        }
+    }
     if show_compacting {
         emit_line_with_skip(Line::default(), out, remaining_skip, *can_consume_skip);
         emit_line_with_skip(compacting_line(spinner.frame), out, remaining_skip, *can_consume_skip);
@@ -577,8 +851,15 @@ fn render_system_from_offset(
     remaining_skip: &mut usize,
     can_consume_skip: &mut bool,
 ) {
-    let color = system_severity_color(system_severity_from_role(&msg.role));
+    let (_, color) = system_label_and_color(msg);
     for block in &mut msg.blocks {
+        if let MessageBlock::RecoveryHint(block) = block {
+            let h = recovery_hint_height_cached(block, width);
+            if !should_skip_whole_block(h, remaining_skip, can_consume_skip) {
+                render_recovery_hint_cached(block, width, out);
+            }
+            continue;
+        }
         if let MessageBlock::Text(block) = block {
             let trailing_gap = block.trailing_blank_lines();
             let (h, _) = text_block_height_cached(block, width, None, false);
@@ -639,10 +920,31 @@ struct RenderedTextBlock {
     height: usize,
 }
 
+/// The text block still receiving chunks: the tail of the last message while a
+/// turn streams and no spinner line follows it.
+fn streaming_cursor_block(msg: &ChatMessage, spinner: &SpinnerState) -> Option<usize> {
+    let spinner_shown =
+        spinner.is_thinking_mid_turn || spinner.is_subagent_thinking || spinner.is_compacting;
+    if !spinner.is_active || !spinner.is_last_message || spinner_shown {
+        return None;
+    }
+    let idx = msg.blocks.len().checked_sub(1)?;
+    matches!(msg.blocks.get(idx), Some(MessageBlock::Text(_))).then_some(idx)
+}
+
+fn streaming_cursor_span(frame: usize) -> Span<'static> {
+    let color = STREAMING_CURSOR_SHIMMER[frame / 2 % STREAMING_CURSOR_SHIMMER.len()];
+    Span::styled(STREAMING_CURSOR, Style::default().fg(color))
+}
+
+/// `cursor_frame` appends the streaming cursor to the block's last row. It is
+/// added after the cached render so the cache keeps the plain text, and the
+/// height is re-measured in case the cursor wraps onto a new row.
 fn assistant_text_block_render(
     block: &mut TextBlock,
     width: u16,
     trim_leading_blank_lines: bool,
+    cursor_frame: Option<usize>,
 ) -> RenderedTextBlock {
     let mut lines = Vec::new();
     render_text_block_cached(block, width, None, false, &mut lines);
@@ -659,6 +961,16 @@ fn assistant_text_block_render(
         }
     }
 
+    if let Some(frame) = cursor_frame {
+        let cursor = streaming_cursor_span(frame);
+        match lines.last_mut() {
+            Some(line) => line.spans.push(cursor),
+            None => lines.push(Line::from(cursor)),
+        }
+        height =
+            Paragraph::new(Text::from(lines.clone())).wrap(Wrap { trim: false }).line_count(width);
+    }
+
     RenderedTextBlock { lines, height }
 }
 
@@ -717,6 +1029,72 @@ fn system_role_label_line(severity: SystemSeverity) -> Line<'static> {
     Line::from(Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD)))
 }
 
+/// `950`, `45.2k`, `1.2M`.
+pub(crate) fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..1_000 => tokens.to_string(),
+        1_000..1_000_000 => format!("{}.{}k", tokens / 1_000, tokens % 1_000 / 100),
+        _ => format!("{}.{}M", tokens / 1_000_000, tokens % 1_000_000 / 100_000),
+    }
+}
+
+/// Dim `in 45.2k · out 1.2k · $0.0123` line closing a finished assistant message.
+fn usage_annotation_line(msg: &ChatMessage, spinner: &SpinnerState) -> Option<Line<'static>> {
+    let streaming = spinner.is_active && spinner.is_last_message;
+    if !spinner.show_usage || streaming {
+        return None;
+    }
+    let usage = msg.usage.as_ref()?;
+    let input = [usage.input_tokens, usage.cache_read_tokens, usage.cache_write_tokens]
+        .into_iter()
+        .flatten()
+        .reduce(|total, tokens| total + tokens);
+    let mut parts = Vec::with_capacity(3);
+    if let Some(input) = input {
+        parts.push(format!("in {}", format_tokens(input)));
+    }
+    if let Some(output) = usage.output_tokens {
+        parts.push(format!("out {}", format_tokens(output)));
+    }
+    if let Some(cost) = usage.cost_usd {
+        parts.push(format!("${cost:.4}"));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(Line::from(Span::styled(
+        parts.join(" \u{00b7} "),
+        Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC),
+    )))
+}
+
+/// Footnote references defined in the message's text, after a blank line.
+fn footnote_lines(msg: &ChatMessage) -> Vec<Line<'static>> {
+    let references = footnotes::collect_references(msg);
+    if references.is_empty() {
+        return Vec::new();
+    }
+    let mut lines = vec![Line::default()];
+    lines.extend(footnotes::reference_lines(&references));
+    lines
+}
+
+/// Dim `✎ note` line under a message carrying a local annotation.
+fn note_annotation_line(msg: &ChatMessage) -> Option<Line<'static>> {
+    let note = msg.annotation.as_deref()?;
+    Some(Line::from(Span::styled(
+        format!("\u{270e} {note}"),
+        Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC),
+    )))
+}
+
+/// One-row summary standing in for a folded turn, cut to `width` columns.
+pub fn folded_turn_line(summary: &str, width: u16) -> Line<'static> {
+    let text = format!("\u{25b8} {summary}");
+    let text = super::width::slice_columns(&text, 0, usize::from(width));
+    Line::from(Span::styled(text, Style::default().fg(theme::DIM)))
+}
+
 fn thinking_line(frame: usize) -> Line<'static> {
     let ch = SPINNER_FRAMES[frame % SPINNER_FRAMES.len()];
     Line::from(Span::styled(format!("{ch} Thinking..."), Style::default().fg(theme::DIM)))
@@ -738,17 +1116,36 @@ fn subagent_thinking_line(frame: usize) -> Line<'static> {
     ])
 }
 
-fn welcome_lines(block: &WelcomeBlock, _width: u16) -> Vec<Line<'static>> {
+fn welcome_lines(block: &WelcomeBlock, width: u16) -> Vec<Line<'static>> {
     let pad = "  ";
     let mut lines = Vec::new();
-    for art_line in FERRIS_SAYS {
+    if block.banner == WelcomeBanner::Off {
+        return lines;
+    }
+    // Recent sessions take Ferris's place once there are any; custom art stays.
+    let full = block.banner == WelcomeBanner::Full;
+    if full && !block.banner_art.is_empty() {
+        for art_line in &block.banner_art {
+            lines.push(Line::from(Span::styled(
+                format!("{pad}{art_line}"),
+                Style::default().fg(theme::RUST_ORANGE),
+            )));
+        }
+        lines.push(Line::default());
+    } else if full && block.recent_sessions.is_empty() {
+        for art_line in FERRIS_SAYS {
+            lines.push(Line::from(Span::styled(
+                format!("{pad}{art_line}"),
+                Style::default().fg(theme::RUST_ORANGE),
+            )));
+        }
+        lines.push(Line::default());
+    } else {
         lines.push(Line::from(Span::styled(
-            format!("{pad}{art_line}"),
-            Style::default().fg(theme::RUST_ORANGE),
+            format!("{pad}Welcome back to Claude, in Rust!"),
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
         )));
     }
-
-    lines.push(Line::default());
     lines.push(Line::default());
 
     lines.push(Line::from(vec![
@@ -762,19 +1159,95 @@ fn welcome_lines(block: &WelcomeBlock, _width: u16) -> Vec<Line<'static>> {
         format!("{pad}cwd:   {}", block.cwd),
         Style::default().fg(theme::DIM),
     )));
+    if !block.memory_files.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("{pad}memory: {} (/memory to edit)", block.memory_files.join(", ")),
+            Style::default().fg(theme::DIM),
+        )));
+    }
+    if !block.mcp_servers.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("{pad}mcp:    {} (/mcp to manage)", block.mcp_servers.join(", ")),
+            Style::default().fg(theme::DIM),
+        )));
+    }
+
+    lines.extend(start_screen_lines(block, width));
+
+    if full {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{pad}Tips: Enter to send, Shift+Enter for newline, Ctrl+C copies selection, twice quits"
+            ),
+            Style::default().fg(theme::DIM),
+        )));
+    }
+    lines.push(Line::default());
+
+    lines
+}
 
+/// Selectable recent sessions and quick actions under the welcome header.
+fn start_screen_lines(block: &WelcomeBlock, width: u16) -> Vec<Line<'static>> {
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            format!("  {text}"),
+            Style::default().fg(theme::DIM).add_modifier(Modifier::BOLD),
+        ))
+    };
+    let entry = |index: usize, text: String| {
+        let selected = block.selected == Some(index);
+        let (marker, style) = if selected {
+            ("\u{25b8}", Style::default().fg(Color::White).bg(theme::RUST_ORANGE))
+        } else {
+            (" ", Style::default())
+        };
+        let text = truncate_welcome_entry(&text, usize::from(width).saturating_sub(6));
+        Line::from(vec![Span::raw(format!("  {marker} ")), Span::styled(text, style)])
+    };
+
+    let mut lines = vec![Line::default()];
+    let sessions = &block.recent_sessions[..block.recent_sessions.len().min(MAX_WELCOME_SESSIONS)];
+    if !sessions.is_empty() {
+        lines.push(heading("Recent sessions"));
+        for (index, session) in sessions.iter().enumerate() {
+            let title = session
+                .custom_title
+                .as_deref()
+                .or_else(|| Some(session.summary.trim()).filter(|summary| !summary.is_empty()))
+                .or(session.first_prompt.as_deref())
+                .unwrap_or("(no summary)");
+            let age = crate::app::slash::session_age_label(Some(session.last_modified_ms));
+            let branch = session
+                .git_branch
+                .as_deref()
+                .map(|branch| format!(" \u{b7} {branch}"))
+                .unwrap_or_default();
+            lines.push(entry(index, format!("{age:>6}  {title}{branch}")));
+        }
+        lines.push(Line::default());
+    }
+    lines.push(heading("Quick actions"));
+    for (offset, (label, command)) in QUICK_ACTIONS.iter().enumerate() {
+        lines.push(entry(sessions.len() + offset, format!("{label} ({command})")));
+    }
     lines.push(Line::default());
     lines.push(Line::from(Span::styled(
-        format!(
-            "{pad}Tips: Enter to send, Shift+Enter for newline, Ctrl+C copies selection or quits"
-        ),
+        "  Up/Down to choose, Enter to open, or just start typing",
         Style::default().fg(theme::DIM),
     )));
-    lines.push(Line::default());
-
     lines
 }
 
+fn truncate_welcome_entry(text: &str, max_width: usize) -> String {
+    if super::width::display_width(text) <= max_width {
+        return text.to_owned();
+    }
+    let (prefix, _) = super::width::prefix_within(text, max_width.saturating_sub(1));
+    format!("{prefix}\u{2026}")
+}
+
 fn render_welcome_cached(block: &mut WelcomeBlock, width: u16, out: &mut Vec<Line<'static>>) {
     if let Some(cached_lines) = block.cache.get() {
         out.extend_from_slice(cached_lines);
@@ -810,6 +1283,154 @@ fn welcome_block_height_cached(block: &mut WelcomeBlock, width: u16) -> (usize,
     (h, lines)
 }
 
+/// Thinking rows for `display`: nothing when hidden, a one-line summary while
+/// collapsed, otherwise a header followed by the dimmed text.
+fn thinking_lines(block: &ThinkingBlock, display: ThinkingDisplay) -> Vec<Line<'static>> {
+    let style = Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC);
+    let text = block.text.trim();
+    match display {
+        ThinkingDisplay::Hide => Vec::new(),
+        ThinkingDisplay::Collapsed if !block.expanded => {
+            let count = text.lines().count();
+            let plural = if count == 1 { "" } else { "s" };
+            vec![Line::from(Span::styled(
+                format!("\u{2234} Thinking\u{2026} ({count} line{plural}, Ctrl+o to expand)"),
+                style,
+            ))]
+        }
+        ThinkingDisplay::Collapsed | ThinkingDisplay::Full => {
+            let mut lines = vec![Line::from(Span::styled("\u{2234} Thinking\u{2026}", style))];
+            lines.extend(
+                text.lines().map(|line| Line::from(Span::styled(format!("  {line}"), style))),
+            );
+            lines
+        }
+    }
+}
+
+fn render_thinking_cached(
+    block: &mut ThinkingBlock,
+    display: ThinkingDisplay,
+    width: u16,
+    out: &mut Vec<Line<'static>>,
+) {
+    if block.cache.get().is_none() {
+        let fresh = thinking_lines(block, display);
+        let h =
+            Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+        block.cache.store(fresh);
+        block.cache.set_height(h, width);
+    }
+    if let Some(stored) = block.cache.get() {
+        out.extend_from_slice(stored);
+    }
+}
+
+fn thinking_block_height_cached(
+    block: &mut ThinkingBlock,
+    display: ThinkingDisplay,
+    width: u16,
+) -> (usize, usize) {
+    if let Some(h) = block.cache.height_at(width) {
+        return (h, 0);
+    }
+
+    if let Some(h) = block.cache.measure_and_set_height(width) {
+        return (h, block.cache.get().map_or(0, Vec::len));
+    }
+
+    let fresh = thinking_lines(block, display);
+    let lines = fresh.len();
+    let h = Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+    block.cache.store(fresh);
+    block.cache.set_height(h, width);
+    (h, lines)
+}
+
+/// Totals row, then one row per file with its diffs below when expanded.
+fn diff_summary_lines(block: &DiffSummaryBlock) -> Vec<Line<'static>> {
+    let added_style = Style::default().fg(Color::Green);
+    let removed_style = Style::default().fg(Color::Red);
+    let dim = Style::default().fg(theme::DIM);
+    let (added, removed) = block.totals();
+    let count = block.files.len();
+    let plural = if count == 1 { "" } else { "s" };
+    let mut header = vec![
+        Span::styled(
+            format!("{count} file{plural} changed, "),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("+{added}"), added_style),
+        Span::raw(" "),
+        Span::styled(format!("\u{2212}{removed}"), removed_style),
+    ];
+    if block.files.iter().all(|file| !file.expanded) {
+        header.push(Span::styled("  (Ctrl+o to expand)", dim));
+    }
+    let mut lines = vec![Line::from(header)];
+
+    let path_width = block
+        .files
+        .iter()
+        .map(|file| unicode_width::UnicodeWidthStr::width(file.path.as_str()))
+        .max()
+        .unwrap_or(0);
+    for file in &block.files {
+        let pad =
+            path_width.saturating_sub(unicode_width::UnicodeWidthStr::width(file.path.as_str()));
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", file_row_marker(file.expanded)), dim),
+            Span::raw(format!("{}{}  ", file.path, " ".repeat(pad))),
+            Span::styled(format!("+{}", file.added), added_style),
+            Span::raw(" "),
+            Span::styled(format!("\u{2212}{}", file.removed), removed_style),
+        ]));
+        if !file.expanded {
+            continue;
+        }
+        // The row above already names the file, so drop the diff's own header.
+        for mut line in file.diffs.iter().flat_map(|edit| render_diff(edit).into_iter().skip(1)) {
+            line.spans.insert(0, Span::raw("    "));
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+fn render_diff_summary_cached(
+    block: &mut DiffSummaryBlock,
+    width: u16,
+    out: &mut Vec<Line<'static>>,
+) {
+    if block.cache.get().is_none() {
+        let fresh = diff_summary_lines(block);
+        let h =
+            Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+        block.cache.store(fresh);
+        block.cache.set_height(h, width);
+    }
+    if let Some(stored) = block.cache.get() {
+        out.extend_from_slice(stored);
+    }
+}
+
+fn diff_summary_height_cached(block: &mut DiffSummaryBlock, width: u16) -> (usize, usize) {
+    if let Some(h) = block.cache.height_at(width) {
+        return (h, 0);
+    }
+
+    if let Some(h) = block.cache.measure_and_set_height(width) {
+        return (h, block.cache.get().map_or(0, Vec::len));
+    }
+
+    let fresh = diff_summary_lines(block);
+    let lines = fresh.len();
+    let h = Paragraph::new(Text::from(fresh.clone())).wrap(Wrap { trim: false }).line_count(width);
+    block.cache.store(fresh);
+    block.cache.set_height(h, width);
+    (h, lines)
+}
+
 fn text_block_height_cached(
     block: &mut TextBlock,
     width: u16,
@@ -846,16 +1467,22 @@ fn tint_lines(lines: &mut [Line<'static>], color: Color) {
 }
 
 /// Preprocess markdown that `tui_markdown` doesn't handle well.
-/// Headings (`# Title`) become `**Title**` (bold) with a blank line before.
-/// Handles variations: `#Title`, `#  Title`, `  ## Title  `, etc.
+/// Headings are normalized to `## Title` with a blank line before, so loose
+/// variations (`#Title`, `#  Title`, `  ## Title  `) still reach the heading
+/// renderer in `headings`. Lines inside code fences are left alone.
 /// Links are left as-is -- `tui_markdown` handles `[title](url)` natively.
 fn preprocess_markdown(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
+    let mut in_fence = false;
     for line in text.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with('#') {
+        if markdown::is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if !in_fence && trimmed.starts_with('#') {
             // Strip all leading '#' characters
             let after_hashes = trimmed.trim_start_matches('#');
+            let level = (trimmed.len() - after_hashes.len()).min(6);
             // Extract heading content (trim spaces between # and text, and trailing)
             let content = after_hashes.trim();
             if !content.is_empty() {
@@ -863,9 +1490,10 @@ fn preprocess_markdown(text: &str) -> String {
                 if !result.is_empty() && !result.ends_with("\n\n") {
                     result.push('\n');
                 }
-                result.push_str("**");
+                result.push_str(&"#".repeat(level));
+                result.push(' ');
                 result.push_str(content);
-                result.push_str("**\n");
+                result.push('\n');
                 continue;
             }
         }
@@ -885,16 +1513,19 @@ fn preprocess_markdown(text: &str) -> String {
 /// 1. `BlockCache` (full block) -- hit for completed messages (no changes).
 /// 2. `IncrementalMarkdown` (per-paragraph) -- only tail paragraph re-parsed during streaming.
 pub(super) fn render_text_cached(
-    text: &str,
     cache: &mut BlockCache,
     incr: &mut IncrementalMarkdown,
     width: u16,
     bg: Option<Color>,
     preserve_newlines: bool,
+    code_view: CodeView,
     out: &mut Vec<Line<'static>>,
 ) {
-    // Fast path: full block cache is valid (completed message, no changes)
-    if let Some(cached_lines) = cache.get() {
+    // Fast path: full block cache is valid (completed message, no changes) and
+    // was laid out at this width -- tables and code blocks depend on it.
+    if cache.height_at(width).is_some()
+        && let Some(cached_lines) = cache.get()
+    {
         crate::perf::mark_with("msg::cache_hit", "lines", cached_lines.len());
         out.extend_from_slice(cached_lines);
         return;
@@ -905,15 +1536,15 @@ pub(super) fn render_text_cached(
 
     // Build a render function that handles preprocessing + tui_markdown
     let render_fn = |src: &str| -> Vec<Line<'static>> {
-        let mut preprocessed = preprocess_markdown(src);
+        let src = crate::app::task_lists::with_checkbox_glyphs(src);
+        let mut preprocessed = preprocess_markdown(&footnotes::inline_markers(&src));
         if preserve_newlines {
             preprocessed = force_markdown_line_breaks(&preprocessed);
         }
-        tables::render_markdown_with_tables(&preprocessed, width, bg)
+        tables::render_markdown_blocks(&preprocessed, width, bg, code_view)
     };
 
     // Ensure any previously invalidated paragraph caches are re-rendered
-    let _ = text;
     incr.ensure_rendered(&render_fn);
 
     // Render: cached paragraphs + fresh tail
@@ -940,13 +1571,33 @@ fn render_text_block_cached(
     preserve_newlines: bool,
     out: &mut Vec<Line<'static>>,
 ) {
+    if block.revision.as_ref().is_some_and(|revision| !revision.expanded) {
+        if let Some(cached_lines) = block.cache.get() {
+            out.extend_from_slice(cached_lines);
+            return;
+        }
+        if let (Some(revision), Some((_, body))) =
+            (block.revision.as_deref(), crate::app::revisions::fenced_code(&block.text))
+        {
+            let lines = render_word_diff(&revision.previous, body);
+            let h = Paragraph::new(Text::from(lines.clone()))
+                .wrap(Wrap { trim: false })
+                .line_count(width);
+            block.cache.store(lines);
+            block.cache.set_height(h, width);
+            if let Some(stored) = block.cache.get() {
+                out.extend_from_slice(stored);
+            }
+            return;
+        }
+    }
     render_text_cached(
-        &block.text,
         &mut block.cache,
         &mut block.markdown,
         width,
         bg,
         preserve_newlines,
+        block.code_view,
         out,
     );
 }
@@ -982,14 +1633,13 @@ mod tests {
     #[test]
     fn preprocess_h1_heading() {
         let result = preprocess_markdown("# Hello");
-        assert!(result.contains("**Hello**"));
-        assert!(!result.contains('#'));
+        assert_eq!(result, "# Hello");
     }
 
     #[test]
     fn preprocess_h3_heading() {
         let result = preprocess_markdown("### Deeply Nested");
-        assert!(result.contains("**Deeply Nested**"));
+        assert_eq!(result, "### Deeply Nested");
     }
 
     #[test]
@@ -1003,28 +1653,25 @@ mod tests {
     fn preprocess_mixed_headings_and_text() {
         let input = "# Title\nSome text\n## Subtitle\nMore text";
         let result = preprocess_markdown(input);
-        assert!(result.contains("**Title**"));
-        assert!(result.contains("Some text"));
-        assert!(result.contains("**Subtitle**"));
-        assert!(result.contains("More text"));
+        assert_eq!(result, "# Title\nSome text\n\n## Subtitle\nMore text");
     }
 
     #[test]
     fn preprocess_heading_no_space() {
         let result = preprocess_markdown("#Title");
-        assert!(result.contains("**Title**"));
+        assert_eq!(result, "# Title");
     }
 
     #[test]
     fn preprocess_heading_extra_spaces() {
         let result = preprocess_markdown("#   Spaced Out   ");
-        assert!(result.contains("**Spaced Out**"));
+        assert_eq!(result, "# Spaced Out");
     }
 
     #[test]
     fn preprocess_indented_heading() {
         let result = preprocess_markdown("  ## Indented");
-        assert!(result.contains("**Indented**"));
+        assert_eq!(result, "## Indented");
     }
 
     #[test]
@@ -1055,43 +1702,43 @@ mod tests {
         let input = "text\n\n# Heading";
         let result = preprocess_markdown(input);
         assert!(!result.contains("\n\n\n"));
-        assert!(result.contains("**Heading**"));
+        assert!(result.contains("# Heading"));
     }
 
     #[test]
     fn preprocess_consecutive_headings() {
         let input = "# First\n# Second";
         let result = preprocess_markdown(input);
-        assert!(result.contains("**First**"));
-        assert!(result.contains("**Second**"));
+        assert_eq!(result, "# First\n\n# Second");
     }
 
     #[test]
     fn preprocess_hash_in_code_not_heading() {
         let result = preprocess_markdown("# actual heading");
-        assert!(result.contains("**actual heading**"));
+        assert_eq!(result, "# actual heading");
+        let fenced = "```sh\n#comment\n## not a heading\n```";
+        assert_eq!(preprocess_markdown(fenced), fenced);
     }
 
     /// H6 heading (6 `#` chars).
     #[test]
     fn preprocess_h6_heading() {
         let result = preprocess_markdown("###### Deep H6");
-        assert!(result.contains("**Deep H6**"));
-        assert!(!result.contains('#'));
+        assert_eq!(result, "###### Deep H6");
     }
 
     /// Heading with markdown formatting inside.
     #[test]
     fn preprocess_heading_with_bold_inside() {
         let result = preprocess_markdown("# **bold** and *italic*");
-        assert!(result.contains("****bold** and *italic***"));
+        assert_eq!(result, "# **bold** and *italic*");
     }
 
     /// Heading at end of file with no trailing newline.
     #[test]
     fn preprocess_heading_at_eof_no_newline() {
         let result = preprocess_markdown("text\n# Final");
-        assert!(result.contains("**Final**"));
+        assert!(result.contains("# Final"));
         assert!(!result.ends_with('\n'));
     }
 
@@ -1108,15 +1755,14 @@ mod tests {
         let long_text = "A".repeat(1000);
         let input = format!("# {long_text}");
         let result = preprocess_markdown(&input);
-        assert!(result.starts_with("**"));
-        assert!(result.contains(&long_text));
+        assert_eq!(result, input);
     }
 
     /// Unicode emoji in heading.
     #[test]
     fn preprocess_unicode_heading() {
         let result = preprocess_markdown("# \u{1F680} Launch \u{4F60}\u{597D}");
-        assert!(result.contains("**\u{1F680} Launch \u{4F60}\u{597D}**"));
+        assert_eq!(result, "# \u{1F680} Launch \u{4F60}\u{597D}");
     }
 
     /// Quoted heading: `> # Heading` - starts with `>` not `#`, so passthrough.
@@ -1133,13 +1779,14 @@ mod tests {
     fn preprocess_all_heading_levels() {
         let input = "# H1\n## H2\n### H3\n#### H4\n##### H5\n###### H6";
         let result = preprocess_markdown(input);
-        for label in ["H1", "H2", "H3", "H4", "H5", "H6"] {
-            assert!(result.contains(&format!("**{label}**")), "missing {label}");
+        for (level, label) in ["H1", "H2", "H3", "H4", "H5", "H6"].into_iter().enumerate() {
+            let heading = format!("{} {label}", "#".repeat(level + 1));
+            assert!(result.contains(&heading), "missing {label}");
         }
     }
 
     #[test]
-    fn welcome_lines_do_not_render_recent_sessions_section() {
+    fn welcome_lines_list_recent_sessions_in_place_of_banner() {
         let message = ChatMessage::welcome_with_recent(
             "claude-sonnet-4-5",
             "/cwd",
@@ -1162,7 +1809,51 @@ mod tests {
             .into_iter()
             .map(|line| line.spans.into_iter().map(|s| s.content).collect())
             .collect();
-        assert!(!lines.iter().any(|line| line.contains("Recent sessions")));
+        assert!(lines.iter().any(|line| line.contains("Recent sessions")));
+        assert!(lines.iter().any(|line| line.ends_with("  Title")));
+        assert!(lines.iter().any(|line| line.contains("New session (/new-session)")));
+        assert!(!lines.iter().any(|line| line.contains("_~^~^~_")));
+
+        let message = ChatMessage::welcome("claude-sonnet-4-5", "/cwd");
+        let MessageBlock::Welcome(block) = &message.blocks[0] else {
+            panic!("expected welcome block");
+        };
+        let banner: Vec<String> = welcome_lines(block, 120)
+            .into_iter()
+            .map(|line| line.spans.into_iter().map(|s| s.content).collect())
+            .collect();
+        assert!(banner.iter().any(|line| line.contains("_~^~^~_")));
+        assert!(!banner.iter().any(|line| line.contains("Recent sessions")));
+    }
+
+    #[test]
+    fn welcome_banner_setting_swaps_art_and_drops_tips() {
+        fn text(block: &WelcomeBlock) -> Vec<String> {
+            welcome_lines(block, 120)
+                .into_iter()
+                .map(|line| line.spans.into_iter().map(|s| s.content).collect())
+                .collect()
+        }
+
+        let mut message = ChatMessage::welcome("claude-sonnet-4-5", "/cwd");
+        let MessageBlock::Welcome(block) = &mut message.blocks[0] else {
+            panic!("expected welcome block");
+        };
+
+        block.banner_art = vec!["<custom art>".to_owned()];
+        let full = text(block);
+        assert!(full.iter().any(|line| line.contains("<custom art>")));
+        assert!(!full.iter().any(|line| line.contains("_~^~^~_")));
+        assert!(full.iter().any(|line| line.contains("Tips:")));
+
+        block.banner = WelcomeBanner::Minimal;
+        let minimal = text(block);
+        assert!(!minimal.iter().any(|line| line.contains("<custom art>")));
+        assert!(!minimal.iter().any(|line| line.contains("Tips:")));
+        assert!(minimal.iter().any(|line| line.contains("cwd:   /cwd")));
+
+        block.banner = WelcomeBanner::Off;
+        assert!(text(block).is_empty());
     }
 
     // force_markdown_line_breaks
@@ -1242,6 +1933,7 @@ mod tests {
             role,
             blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1256,6 +1948,7 @@ mod tests {
                 MessageBlock::Text(TextBlock::from_complete(second)),
             ],
             usage: None,
+            annotation: None,
         }
     }
 
@@ -1271,6 +1964,7 @@ mod tests {
             sdk_tool_name: sdk_tool_name.to_owned(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status,
             content: if text.is_empty() {
                 Vec::new()
@@ -1281,6 +1975,7 @@ mod tests {
             hidden: false,
             terminal_id: None,
             terminal_command: None,
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -1294,6 +1989,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         }
     }
 
@@ -1324,6 +2022,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         let mut measured_msg = make_text_message(MessageRole::User, &text);
@@ -1335,6 +2035,200 @@ mod tests {
         assert_eq!(h, truth);
     }
 
+    #[test]
+    fn usage_annotation_closes_finished_assistant_message_when_enabled() {
+        let mut spinner = SpinnerState {
+            frame: 0,
+            is_active: false,
+            is_last_message: true,
+            is_thinking_mid_turn: false,
+            is_subagent_thinking: false,
+            is_compacting: false,
+            show_usage: true,
+            thinking_display: ThinkingDisplay::Collapsed,
+        };
+        let mut msg = make_text_message(MessageRole::Assistant, "Done");
+        msg.usage = Some(crate::app::MessageUsage {
+            input_tokens: Some(200),
+            cache_read_tokens: Some(45_000),
+            output_tokens: Some(950),
+            cost_usd: Some(0.0123),
+            ..crate::app::MessageUsage::default()
+        });
+
+        let mut lines = Vec::new();
+        render_message(&mut msg, &spinner, 80, &mut lines);
+        assert_eq!(
+            render_lines_to_strings(&lines),
+            vec![
+                "Claude".to_owned(),
+                "Done".to_owned(),
+                "in 45.2k \u{00b7} out 950 \u{00b7} $0.0123".to_owned(),
+                String::new(),
+            ]
+        );
+        let (h, _) = measure_message_height_cached(&mut msg, &spinner, 80, 1);
+        assert_eq!(h, lines.len());
+
+        spinner.is_active = true;
+        let mut streaming = Vec::new();
+        render_message(&mut msg, &spinner, 80, &mut streaming);
+        assert_eq!(streaming.len(), 3);
+        assert_eq!(format_tokens(1_200_000), "1.2M");
+    }
+
+    #[test]
+    fn streaming_cursor_trails_tail_text_only_while_active() {
+        let mut spinner = SpinnerState {
+            frame: 0,
+            is_active: true,
+            is_last_message: true,
+            is_thinking_mid_turn: false,
+            is_subagent_thinking: false,
+            is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
+        };
+        let text = "B".repeat(20);
+
+        let mut lines = Vec::new();
+        render_message(
+            &mut make_text_message(MessageRole::Assistant, &text),
+            &spinner,
+            20,
+            &mut lines,
+        );
+        assert_eq!(
+            render_lines_to_strings(&lines),
+            vec!["Claude".to_owned(), format!("{text}{STREAMING_CURSOR}"), String::new()]
+        );
+        // The cursor wraps onto its own row at this width; measurement agrees.
+        let (h, _) = measure_message_height_cached(
+            &mut make_text_message(MessageRole::Assistant, &text),
+            &spinner,
+            20,
+            1,
+        );
+        assert_eq!(
+            h,
+            ground_truth_height(
+                &mut make_text_message(MessageRole::Assistant, &text),
+                &spinner,
+                20
+            )
+        );
+        assert_eq!(h, 4);
+
+        spinner.is_active = false;
+        let mut done = Vec::new();
+        render_message(
+            &mut make_text_message(MessageRole::Assistant, &text),
+            &spinner,
+            20,
+            &mut done,
+        );
+        assert_eq!(render_lines_to_strings(&done), vec!["Claude".to_owned(), text, String::new()]);
+    }
+
+    #[test]
+    fn thinking_block_follows_display_setting() {
+        let mut spinner = SpinnerState {
+            frame: 0,
+            is_active: false,
+            is_last_message: false,
+            is_thinking_mid_turn: false,
+            is_subagent_thinking: false,
+            is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
+        };
+        let make_msg = || ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![
+                MessageBlock::Thinking(ThinkingBlock::new("first\nsecond".to_owned())),
+                MessageBlock::Text(TextBlock::from_complete("Answer")),
+            ],
+            usage: None,
+            annotation: None,
+        };
+
+        let mut lines = Vec::new();
+        render_message(&mut make_msg(), &spinner, 80, &mut lines);
+        assert_eq!(
+            render_lines_to_strings(&lines),
+            vec![
+                "Claude".to_owned(),
+                "\u{2234} Thinking\u{2026} (2 lines, Ctrl+o to expand)".to_owned(),
+                String::new(),
+                "Answer".to_owned(),
+                String::new(),
+            ]
+        );
+
+        spinner.thinking_display = ThinkingDisplay::Full;
+        let (h, _) = measure_message_height_cached(&mut make_msg(), &spinner, 80, 1);
+        assert_eq!(h, ground_truth_height(&mut make_msg(), &spinner, 80));
+        assert_eq!(h, 7);
+
+        spinner.thinking_display = ThinkingDisplay::Hide;
+        let mut hidden = Vec::new();
+        render_message(&mut make_msg(), &spinner, 80, &mut hidden);
+        assert_eq!(
+            render_lines_to_strings(&hidden),
+            vec!["Claude".to_owned(), "Answer".to_owned(), String::new()]
+        );
+    }
+
+    #[test]
+    fn diff_summary_lists_files_and_expands_to_their_diffs() {
+        let spinner = SpinnerState {
+            frame: 0,
+            is_active: false,
+            is_last_message: false,
+            is_thinking_mid_turn: false,
+            is_subagent_thinking: false,
+            is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
+        };
+        let make_msg = |expanded: bool| ChatMessage {
+            role: MessageRole::Assistant,
+            blocks: vec![
+                MessageBlock::Text(TextBlock::from_complete("Done.")),
+                MessageBlock::DiffSummary(DiffSummaryBlock {
+                    files: vec![crate::app::FileDiffStat {
+                        path: "src/lib.rs".to_owned(),
+                        added: 1,
+                        removed: 0,
+                        diffs: vec![crate::agent::model::Diff::new("src/lib.rs", "x\n")],
+                        expanded,
+                    }],
+                    ..DiffSummaryBlock::default()
+                }),
+            ],
+            usage: None,
+            annotation: None,
+        };
+
+        let mut lines = Vec::new();
+        render_message(&mut make_msg(false), &spinner, 80, &mut lines);
+        assert_eq!(
+            render_lines_to_strings(&lines),
+            vec![
+                "Claude".to_owned(),
+                "Done.".to_owned(),
+                String::new(),
+                "1 file changed, +1 \u{2212}0  (Ctrl+o to expand)".to_owned(),
+                "  \u{25b8} src/lib.rs  +1 \u{2212}0".to_owned(),
+                String::new(),
+            ]
+        );
+
+        let (h, _) = measure_message_height_cached(&mut make_msg(true), &spinner, 80, 1);
+        assert_eq!(h, ground_truth_height(&mut make_msg(true), &spinner, 80));
+        assert_eq!(h, 8);
+    }
+
     #[test]
     fn assistant_split_paragraph_renders_visible_blank_line() {
         let spinner = SpinnerState {
@@ -1344,6 +2238,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_assistant_split_message("First paragraph", "Second paragraph");
         let mut lines = Vec::new();
@@ -1370,6 +2266,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut measured = make_assistant_split_message("First paragraph", "Second paragraph");
         let mut truth = make_assistant_split_message("First paragraph", "Second paragraph");
@@ -1389,6 +2287,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_assistant_split_message("First paragraph", "Second paragraph");
         let mut out = Vec::new();
@@ -1414,6 +2314,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
 
         let mut measured_msg = make_text_message(MessageRole::Assistant, &text);
@@ -1439,6 +2341,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(MessageRole::User, "hello\nworld");
         let mut truth_msg = make_text_message(MessageRole::User, "hello\nworld");
@@ -1460,6 +2364,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut measured_msg = make_welcome_message("claude-sonnet-4-5", "~/project");
         let mut truth_msg = make_welcome_message("claude-sonnet-4-5", "~/project");
@@ -1478,6 +2384,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(
             MessageRole::System(Some(SystemSeverity::Warning)),
@@ -1500,6 +2408,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: true,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = ChatMessage {
             role: MessageRole::Assistant,
@@ -1510,6 +2420,7 @@ mod tests {
                 "Research project",
             )))],
             usage: None,
+            annotation: None,
         };
 
         let mut lines = Vec::new();
@@ -1528,6 +2439,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(MessageRole::Assistant, "\n# Heading\nBody");
 
@@ -1549,6 +2462,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut measured = make_text_message(MessageRole::Assistant, "\n# Heading\nBody");
         let mut truth = make_text_message(MessageRole::Assistant, "\n# Heading\nBody");
@@ -1568,6 +2483,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(MessageRole::Assistant, "\n# Heading\nBody");
         let mut out = Vec::new();
@@ -1590,6 +2507,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: false,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = ChatMessage {
             role: MessageRole::Assistant,
@@ -1608,6 +2527,7 @@ mod tests {
                 ))),
             ],
             usage: None,
+            annotation: None,
         };
 
         let mut lines = Vec::new();
@@ -1626,6 +2546,8 @@ mod tests {
             is_thinking_mid_turn: false,
             is_subagent_thinking: true,
             is_compacting: false,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = ChatMessage {
             role: MessageRole::Assistant,
@@ -1644,6 +2566,7 @@ mod tests {
                 ))),
             ],
             usage: None,
+            annotation: None,
         };
 
         let mut lines = Vec::new();
@@ -1668,6 +2591,8 @@ mod tests {
             is_thinking_mid_turn: true,
             is_subagent_thinking: false,
             is_compacting: true,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(MessageRole::Assistant, "done");
 
@@ -1688,6 +2613,8 @@ mod tests {
             is_thinking_mid_turn: true,
             is_subagent_thinking: false,
             is_compacting: true,
+            show_usage: false,
+            thinking_display: ThinkingDisplay::Collapsed,
         };
         let mut msg = make_text_message(MessageRole::Assistant, "done");
 
@@ -0,0 +1,175 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional minimap beside the chat scrollbar: one column scaled to the whole
+//! transcript, marking user prompts, errors and failed tool calls. Clicking a
+//! mark jumps to its message (see `app::events::mouse`).
+
+use crate::agent::model::ToolCallStatus;
+use crate::app::{App, ChatMessage, MessageBlock, MessageRole, SystemSeverity};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MarkKind {
+    Prompt,
+    FailedTool,
+    Error,
+}
+
+impl MarkKind {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Prompt => "\u{25b8}",
+            Self::FailedTool => theme::ICON_FAILED,
+            Self::Error => "!",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Self::Prompt => Style::default().add_modifier(Modifier::DIM),
+            Self::FailedTool | Self::Error => {
+                Style::default().fg(theme::STATUS_ERROR).add_modifier(Modifier::BOLD)
+            }
+        }
+    }
+}
+
+fn mark_kind(message: &ChatMessage) -> Option<MarkKind> {
+    match message.role {
+        MessageRole::User => Some(MarkKind::Prompt),
+        MessageRole::System(Some(SystemSeverity::Error)) => Some(MarkKind::Error),
+        MessageRole::Assistant => message
+            .blocks
+            .iter()
+            .any(|block| {
+                matches!(block, MessageBlock::ToolCall(tc)
+                    if !tc.hidden && tc.status == ToolCallStatus::Failed)
+            })
+            .then_some(MarkKind::FailedTool),
+        _ => None,
+    }
+}
+
+/// Draw the minimap in the column left of the scrollbar rail and remember
+/// where each mark landed. Draws nothing when the transcript fits on screen.
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect, content_height: usize) {
+    app.minimap_marks.clear();
+    if !app.config.show_minimap_effective()
+        || area.width < 3
+        || area.height == 0
+        || content_height <= usize::from(area.height)
+    {
+        return;
+    }
+    let column = area.right().saturating_sub(2);
+    let rows = usize::from(area.height);
+    // Strongest mark per row, with the first message that produced it.
+    let mut marks: Vec<Option<(MarkKind, usize)>> = vec![None; rows];
+    for (index, message) in app.messages.iter().enumerate() {
+        if app.viewport.message_height(index) == 0 {
+            continue;
+        }
+        let Some(kind) = mark_kind(message) else {
+            continue;
+        };
+        let row =
+            (app.viewport.cumulative_height_before(index) * rows / content_height).min(rows - 1);
+        if marks[row].is_none_or(|(existing, _)| kind > existing) {
+            marks[row] = Some((kind, index));
+        }
+    }
+
+    let buf = frame.buffer_mut();
+    for (row, mark) in marks.into_iter().enumerate() {
+        let Some((kind, index)) = mark else {
+            continue;
+        };
+        let Ok(offset) = u16::try_from(row) else {
+            break;
+        };
+        let position = Position::new(column, area.y.saturating_add(offset));
+        if let Some(cell) = buf.cell_mut(position) {
+            // Unskip in case the mark lands inside a hyperlinked URL run.
+            cell.set_symbol(kind.symbol()).set_style(kind.style()).set_skip(false);
+        }
+        app.minimap_marks.push((position, index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TextBlock;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn message(role: MessageRole) -> ChatMessage {
+        ChatMessage {
+            role,
+            blocks: vec![MessageBlock::Text(TextBlock::from_complete("x"))],
+            usage: None,
+            annotation: None,
+        }
+    }
+
+    /// Append messages with the given heights and draw the minimap for a
+    /// 20x10 chat area, returning where its marks landed.
+    fn draw(app: &mut App, added: Vec<(MessageRole, usize)>) -> Vec<(Position, usize)> {
+        for (role, height) in added {
+            app.viewport.set_message_height(app.messages.len(), height);
+            app.messages.push(message(role));
+        }
+        app.viewport.rebuild_prefix_sums();
+        let content_height = app.viewport.total_message_height();
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).expect("terminal");
+        terminal
+            .draw(|frame| render(frame, app, Rect::new(0, 0, 20, 10), content_height))
+            .expect("draw");
+        app.minimap_marks.clone()
+    }
+
+    #[test]
+    fn marks_prompts_and_errors_only() {
+        assert_eq!(mark_kind(&message(MessageRole::User)), Some(MarkKind::Prompt));
+        assert_eq!(
+            mark_kind(&message(MessageRole::System(Some(SystemSeverity::Error)))),
+            Some(MarkKind::Error)
+        );
+        assert_eq!(mark_kind(&message(MessageRole::System(Some(SystemSeverity::Info)))), None);
+        assert_eq!(mark_kind(&message(MessageRole::Assistant)), None);
+        assert!(MarkKind::Error > MarkKind::Prompt);
+    }
+
+    #[test]
+    fn marks_move_up_the_column_as_the_transcript_grows() {
+        let mut app = App::test_default();
+        crate::app::config::store::set_show_minimap(
+            &mut app.config.committed_preferences_document,
+            true,
+        );
+        let error = MessageRole::System(Some(SystemSeverity::Error));
+
+        // Fits on screen: no minimap.
+        assert!(
+            draw(&mut app, vec![(MessageRole::User, 2), (MessageRole::Assistant, 4)]).is_empty()
+        );
+
+        // 20 rows in a 10-row column: one column row per two content rows.
+        let marks = draw(&mut app, vec![(MessageRole::User, 2), (MessageRole::Assistant, 12)]);
+        assert_eq!(marks, vec![(Position::new(18, 0), 0), (Position::new(18, 3), 2)]);
+
+        // 40 rows: the earlier prompts move up the column, and the error
+        // outranks the prompt that shares its row.
+        let marks =
+            draw(&mut app, vec![(MessageRole::User, 1), (error, 1), (MessageRole::Assistant, 18)]);
+        assert_eq!(
+            marks,
+            vec![(Position::new(18, 0), 0), (Position::new(18, 1), 2), (Position::new(18, 5), 5)]
+        );
+    }
+}
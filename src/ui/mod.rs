@@ -1,25 +1,45 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+mod annotation;
 mod autocomplete;
 mod chat;
 mod chat_view;
+mod code_blocks;
 mod config;
+mod confirm;
 mod diff;
+mod filter_bar;
 mod footer;
+mod footnotes;
 mod header;
+mod headings;
 pub(crate) mod help;
-mod highlight;
+mod help_overlay;
+pub(crate) mod highlight;
+pub(crate) mod hscroll;
+mod hyperlinks;
 mod input;
 mod layout;
-mod markdown;
+pub(crate) mod markdown;
 mod message;
+mod minimap;
+mod palette;
+mod perf_hud;
+mod pinned_output;
+mod quotes;
+pub mod render_fixture;
+mod status_log;
+mod suggestions;
 mod tables;
 pub mod theme;
+mod toast;
 mod todo;
 mod tool_call;
 mod trusted;
+pub(crate) mod width;
 
+pub(crate) use message::format_tokens;
 pub use message::{SpinnerState, measure_message_height_cached};
 
 use crate::app::ActiveView;
@@ -32,4 +52,17 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         ActiveView::Config => config::render(frame, app),
         ActiveView::Trusted => trusted::render(frame, app),
     }
+    if app.active_view == ActiveView::Chat {
+        footer::render_health_tooltip(frame, app);
+    }
+    confirm::render(frame, app);
+    annotation::render(frame, app);
+    palette::render(frame, app);
+    help_overlay::render(frame, app);
+    toast::render(frame, app);
+    if app.config.high_contrast_effective() {
+        theme::apply_high_contrast(frame.buffer_mut(), app.terminal_background);
+    } else if !app.notifications.is_focused() {
+        theme::dim_accents(frame.buffer_mut(), app.terminal_background);
+    }
 }
@@ -0,0 +1,115 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use super::config::text_input_line;
+use crate::app::App;
+use crate::app::fuzzy::highlight_ranges;
+use crate::app::palette::matches;
+use crate::ui::{theme, width};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
+
+const MAX_PALETTE_WIDTH: u16 = 64;
+const MAX_VISIBLE_ACTIONS: usize = 12;
+
+/// Draw the open command palette in the upper part of the screen.
+pub fn render(frame: &mut Frame, app: &App) {
+    let Some(palette) = app.command_palette.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    let width = MAX_PALETTE_WIDTH.min(area.width.saturating_sub(4));
+    if width < 24 || area.height < 5 {
+        return;
+    }
+    let found = matches(&palette.input.text);
+    let list_rows = found.len().clamp(1, MAX_VISIBLE_ACTIONS);
+    // Border, query, separator, list, border.
+    let height = u16::try_from(list_rows + 4).unwrap_or(u16::MAX).min(area.height);
+    let palette_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 4,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            " Command palette ",
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " \u{2191}\u{2193} select \u{b7} Enter run \u{b7} Esc close ",
+            Style::default().fg(theme::DIM),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::RUST_ORANGE))
+        .padding(Padding::horizontal(1));
+    let inner_width = usize::from(width.saturating_sub(4));
+
+    let visible = usize::from(width.saturating_sub(5));
+    let skip = palette.input.cursor.saturating_sub(visible);
+    let draft: String = palette.input.text.chars().skip(skip).collect();
+    let mut lines = vec![
+        text_input_line(&draft, palette.input.cursor - skip, "Type to search actions"),
+        Line::from(Span::styled(
+            theme::SEPARATOR_CHAR.repeat(inner_width),
+            Style::default().fg(theme::DIM),
+        )),
+    ];
+    if found.is_empty() {
+        lines.push(Line::from(Span::styled("No matching action", Style::default().fg(theme::DIM))));
+    }
+    let start = palette.selected.saturating_sub(MAX_VISIBLE_ACTIONS - 1);
+    for (index, (action, hit)) in found.iter().enumerate().skip(start).take(MAX_VISIBLE_ACTIONS) {
+        let selected = index == palette.selected;
+        let base = if selected {
+            Style::default().fg(Color::White).bg(theme::RUST_ORANGE)
+        } else {
+            Style::default()
+        };
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for (from, to) in highlight_ranges(action.name, &hit.positions) {
+            spans.push(Span::styled(&action.name[cursor..from], base));
+            spans.push(Span::styled(&action.name[from..to], base.add_modifier(Modifier::BOLD)));
+            cursor = to;
+        }
+        spans.push(Span::styled(&action.name[cursor..], base));
+        let used = width::display_width(action.name) + width::display_width(action.binding);
+        spans.push(Span::styled(" ".repeat(inner_width.saturating_sub(used).max(1)), base));
+        let binding_style = if selected { base } else { base.fg(theme::DIM) };
+        spans.push(Span::styled(action.binding, binding_style));
+        lines.push(Line::from(spans));
+    }
+
+    frame.render_widget(Clear, palette_area);
+    frame.render_widget(Paragraph::new(lines).block(block), palette_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn palette_lists_actions_with_their_keys() {
+        let mut app = App::test_default();
+        crate::app::palette::open(&mut app);
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).expect("terminal");
+        terminal.draw(|frame| render(frame, &app)).expect("draw");
+
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .map(|pos| buffer[pos].symbol().to_owned())
+            .collect();
+        assert!(text.contains("Command palette"));
+        assert!(text.contains("Toggle todo panel"));
+        assert!(text.contains("Ctrl+t"));
+    }
+}
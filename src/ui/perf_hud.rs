@@ -0,0 +1,175 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! F12 perf HUD: rolling frame and stage timings, block-cache hit rates,
+//! transcript sizes and memory use, drawn over the top-right corner of the
+//! screen.
+
+use crate::app::App;
+use crate::perf::hud::{self, HudStats};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+
+const HUD_WIDTH: u16 = 46;
+
+/// Stage timers shown below the frame row, as (label, span name).
+const STAGES: [(&str, &str); 4] = [
+    ("heights", "chat::update_heights"),
+    ("prefix", "chat::prefix_sums"),
+    ("render", "chat::render_scrolled"),
+    ("terminal", "terminal::update"),
+];
+
+/// Whether the HUD is drawn this frame.
+#[must_use]
+pub fn is_visible() -> bool {
+    hud::is_active()
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(lines) = hud::with_stats(|stats| hud_lines(stats, app)) else {
+        return;
+    };
+    let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX).min(area.height);
+    let width = HUD_WIDTH.min(area.width);
+    if width < 3 || height < 3 {
+        return;
+    }
+    let hud_area = Rect { x: area.x + area.width - width, y: area.y, width, height };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme::DIM))
+        .title(Span::styled(
+            " perf (F12) ",
+            Style::default().fg(theme::RUST_ORANGE).add_modifier(Modifier::BOLD),
+        ));
+    frame.render_widget(Clear, hud_area);
+    frame.render_widget(Paragraph::new(lines).block(block), hud_area);
+}
+
+fn hud_lines(stats: &HudStats, app: &App) -> Vec<Line<'static>> {
+    let mut lines = Vec::with_capacity(STAGES.len() + 7);
+    let fps = app.frame_fps().map_or_else(|| "  -".to_owned(), |fps| format!("{fps:>5.1}"));
+    let mut frame_line = span_line("frame", stats, "frame_total");
+    frame_line.spans.push(Span::styled(format!(" {fps} fps"), Style::default().fg(theme::DIM)));
+    lines.push(frame_line);
+    for (label, name) in STAGES {
+        lines.push(span_line(label, stats, name));
+    }
+    lines.push(hit_rate_line(
+        "msg cache",
+        stats.hit_rate_permille(&["msg::cache_hit"], &["msg::cache_miss"]),
+    ));
+    lines.push(hit_rate_line(
+        "tool cache",
+        stats.hit_rate_permille(
+            &["tc::cache_hit", "tc::cache_hit_execute", "tc::cache_hit_body"],
+            &["tc::cache_miss", "tc::cache_miss_execute", "tc::cache_miss_body"],
+        ),
+    ));
+    let count = |name: &str| stats.value(name).map_or_else(|| "-".to_owned(), |v| v.to_string());
+    lines.push(Line::from(vec![
+        Span::styled(format!("{:<10}", "msgs"), Style::default().fg(theme::DIM)),
+        Span::raw(app.messages.len().to_string()),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(format!("{:<10}", "lines"), Style::default().fg(theme::DIM)),
+        Span::raw(format!(
+            "{} drawn \u{b7} {} rows",
+            count("chat::render_scrolled_lines"),
+            count("chat::content_height")
+        )),
+    ]));
+    lines.extend(memory_lines(app));
+    lines
+}
+
+fn memory_lines(app: &App) -> [Line<'static>; 2] {
+    let budget = &app.memory_budget;
+    let usage = budget.usage;
+    let color = if usage.total() > budget.max_bytes {
+        Color::Red
+    } else if usage.total().saturating_mul(4) > budget.max_bytes.saturating_mul(3) {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    [
+        Line::from(vec![
+            Span::styled(format!("{:<10}", "memory"), Style::default().fg(theme::DIM)),
+            Span::styled(format!("{:>6.1}", mib(usage.total())), Style::default().fg(color)),
+            Span::styled(
+                format!(" / {:.0} MiB \u{b7} {} trims", mib(budget.max_bytes), budget.trim_passes),
+                Style::default().fg(theme::DIM),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:<10}", ""), Style::default().fg(theme::DIM)),
+            Span::raw(format!(
+                "msgs {:.1} \u{b7} cache {:.1} \u{b7} term {:.1}",
+                mib(usage.message_bytes),
+                mib(usage.cache_bytes),
+                mib(usage.terminal_bytes)
+            )),
+        ]),
+    ]
+}
+
+fn mib(bytes: usize) -> f64 {
+    f64::from(u32::try_from(bytes / 1024).unwrap_or(u32::MAX)) / 1024.0
+}
+
+fn span_line(label: &str, stats: &HudStats, name: &str) -> Line<'static> {
+    let label = Span::styled(format!("{label:<10}"), Style::default().fg(theme::DIM));
+    let Some(summary) = stats.span(name) else {
+        return Line::from(vec![label, Span::raw("     -")]);
+    };
+    Line::from(vec![
+        label,
+        Span::styled(
+            format!("{:>6.2}", summary.avg_ms),
+            Style::default().fg(timing_color(summary.avg_ms)),
+        ),
+        Span::styled(" avg ", Style::default().fg(theme::DIM)),
+        Span::styled(
+            format!("{:>6.2}", summary.max_ms),
+            Style::default().fg(timing_color(summary.max_ms)),
+        ),
+        Span::styled(" max ms", Style::default().fg(theme::DIM)),
+    ])
+}
+
+/// Green within a 60 fps frame budget, yellow within 30 fps, red beyond.
+fn timing_color(ms: f64) -> Color {
+    if ms <= 16.7 {
+        Color::Green
+    } else if ms <= 33.3 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+fn hit_rate_line(label: &str, permille: Option<u64>) -> Line<'static> {
+    let label = Span::styled(format!("{label:<10}"), Style::default().fg(theme::DIM));
+    let Some(permille) = permille else {
+        return Line::from(vec![label, Span::raw("     -")]);
+    };
+    let color = match permille {
+        900.. => Color::Green,
+        600..900 => Color::Yellow,
+        _ => Color::Red,
+    };
+    Line::from(vec![
+        label,
+        Span::styled(
+            format!("{:>3}.{}% hits", permille / 10, permille % 10),
+            Style::default().fg(color),
+        ),
+    ])
+}
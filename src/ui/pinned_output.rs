@@ -0,0 +1,62 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::agent::model;
+use crate::app::App;
+use crate::app::pinned_output::pinned_tool_call;
+use crate::ui::{highlight, theme};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// Rows the pane takes below the chat, including its title row.
+const PANE_ROWS: u16 = 10;
+
+/// Height carved out of the chat body; at most half of it.
+pub fn compute_height(app: &App, body_height: u16) -> u16 {
+    if pinned_tool_call(app).is_some() { PANE_ROWS.min(body_height / 2) } else { 0 }
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(tc) = pinned_tool_call(app) else {
+        return;
+    };
+    if area.height == 0 {
+        return;
+    }
+    let [title_area, body_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    let command = tc.terminal_command.as_deref().unwrap_or(tc.title.as_str());
+    let command = command.lines().next().unwrap_or_default();
+    let status = crate::app::screen_reader::status_label(tc.status);
+    let title =
+        format!("{} $ {command} {status} (Ctrl+s, p to unpin) ", theme::SEPARATOR_CHAR.repeat(2));
+    let title: String = title.chars().take(usize::from(area.width)).collect();
+    let fill = usize::from(area.width).saturating_sub(title.chars().count());
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!("{title}{}", theme::SEPARATOR_CHAR.repeat(fill)),
+            Style::default().fg(theme::DIM),
+        ))),
+        title_area,
+    );
+
+    let lines = match tc.terminal_output.as_deref() {
+        Some(output) if !output.is_empty() => {
+            let lines = highlight::render_terminal_output(output);
+            let skip = lines.len().saturating_sub(usize::from(body_area.height));
+            lines.into_iter().skip(skip).collect()
+        }
+        _ => {
+            let placeholder = if tc.status == model::ToolCallStatus::InProgress {
+                "running..."
+            } else {
+                "No output."
+            };
+            vec![Line::from(Span::styled(placeholder, Style::default().fg(theme::DIM)))]
+        }
+    };
+    frame.render_widget(Paragraph::new(lines), body_area);
+}
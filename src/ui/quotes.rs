@@ -0,0 +1,129 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Blockquotes and GitHub-style callouts (`> [!NOTE]`). Top-level quotes are
+//! split out of the markdown next to tables; their body is rendered
+//! recursively and every row gets a bar, so nested quotes stack bars. Plain
+//! quotes are dim, callouts get a colored bar and an icon header.
+
+use super::tables;
+use crate::ui::theme;
+use pulldown_cmark::BlockQuoteKind;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const BAR: &str = "\u{2502} ";
+
+fn callout(kind: BlockQuoteKind) -> (&'static str, &'static str, Color) {
+    match kind {
+        BlockQuoteKind::Note => ("\u{2139}", "Note", Color::LightBlue),
+        BlockQuoteKind::Tip => ("\u{2605}", "Tip", Color::Green),
+        BlockQuoteKind::Important => ("\u{203c}", "Important", Color::LightMagenta),
+        BlockQuoteKind::Warning => ("\u{26a0}", "Warning", theme::STATUS_WARNING),
+        BlockQuoteKind::Caution => ("\u{2716}", "Caution", theme::STATUS_ERROR),
+    }
+}
+
+/// Body of a quote with one level of `>` markers removed, and the `[!KIND]`
+/// line dropped for callouts.
+pub(super) fn strip_markers(source: &str, is_callout: bool) -> String {
+    let mut body = String::with_capacity(source.len());
+    for (index, line) in source.lines().enumerate() {
+        let inner = line
+            .trim_start()
+            .strip_prefix('>')
+            .map_or(line, |rest| rest.strip_prefix(' ').unwrap_or(rest));
+        if is_callout && index == 0 && inner.trim_start().starts_with("[!") {
+            continue;
+        }
+        body.push_str(inner);
+        body.push('\n');
+    }
+    body
+}
+
+pub(super) fn render_quote(
+    kind: Option<BlockQuoteKind>,
+    body: &str,
+    width: u16,
+    bg: Option<Color>,
+) -> Vec<Line<'static>> {
+    let inner_width = width.saturating_sub(2).max(1);
+    let mut bar_style = Style::default().fg(kind.map_or(theme::DIM, |kind| callout(kind).2));
+    if let Some(bg) = bg {
+        bar_style = bar_style.bg(bg);
+    }
+    let mut lines = Vec::new();
+    if let Some(kind) = kind {
+        let (icon, title, color) = callout(kind);
+        lines.push(Line::from(Span::styled(
+            format!("{icon} {title}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )));
+    }
+    let mut body_lines = tables::render_markdown_with_tables(body, inner_width, bg);
+    while body_lines.last().is_some_and(|line| line.width() == 0) {
+        body_lines.pop();
+    }
+    for mut line in body_lines {
+        if kind.is_none() {
+            for span in &mut line.spans {
+                span.style = span.style.add_modifier(Modifier::DIM);
+            }
+        }
+        lines.push(line);
+    }
+
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines {
+        let rows = if line.width() <= usize::from(inner_width) {
+            vec![line]
+        } else {
+            tables::wrap_line(&line, usize::from(inner_width))
+        };
+        for row in rows {
+            let style = row.style;
+            let mut spans = vec![Span::styled(BAR, bar_style)];
+            spans.extend(row.spans);
+            out.push(Line::from(spans).style(style));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn render_strings(text: &str, width: u16) -> Vec<String> {
+        tables::render_markdown_with_tables(text, width, None)
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect::<String>())
+            .map(|line| line.trim_end().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn nested_quotes_stack_bars() {
+        let lines = render_strings("> outer\n>\n> > inner", 40);
+        assert_eq!(lines.first().map(String::as_str), Some("\u{2502} outer"));
+        assert!(lines.iter().any(|line| line == "\u{2502} \u{2502} inner"));
+    }
+
+    #[test]
+    fn callouts_get_a_header_and_wrap_under_the_bar() {
+        let lines = render_strings("> [!WARNING]\n> Back up the database first", 16);
+        assert_eq!(lines[0], "\u{2502} \u{26a0} Warning");
+        assert!(
+            lines[1..].iter().filter(|line| !line.is_empty()).all(|line| line.starts_with(BAR))
+        );
+        assert!(lines.len() > 3);
+    }
+
+    #[test]
+    fn strip_markers_removes_one_level() {
+        assert_eq!(strip_markers("> a\n> > b\nlazy", false), "a\n> b\nlazy\n");
+        assert_eq!(strip_markers("> [!NOTE]\n> text", true), "text\n");
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Canned UI states rendered into ratatui's `TestBackend`. The snapshot tests
+//! in `tests/integration/render_snapshots.rs` cover every fixture at several
+//! widths, and the hidden `claude-rs render-fixture` command prints one so a
+//! layout report can be reproduced at the reporter's terminal size.
+
+use crate::agent::events::ClientEvent;
+use crate::agent::model;
+use crate::app::{
+    App, AppStatus, ChatMessage, MessageBlock, MessageRole, TextBlock, handle_client_event,
+};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Fixture {
+    /// Fresh session with only the welcome banner.
+    Welcome,
+    /// Agent mid-turn: thinking done, reply still streaming.
+    Streaming,
+    /// Turn with a completed, a running and a failed tool call.
+    ToolCalls,
+    /// Wide, combining and emoji characters in prose, code and tables.
+    Unicode,
+}
+
+impl Fixture {
+    pub const ALL: [Self; 4] = [Self::Welcome, Self::Streaming, Self::ToolCalls, Self::Unicode];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Welcome => "welcome",
+            Self::Streaming => "streaming",
+            Self::ToolCalls => "tool-calls",
+            Self::Unicode => "unicode",
+        }
+    }
+
+    /// Build the app state for this fixture. No bridge is attached.
+    #[must_use]
+    pub fn build(self) -> App {
+        let mut app = App::test_default();
+        app.ensure_welcome_message();
+        match self {
+            Self::Welcome => {}
+            Self::Streaming => {
+                push_user(&mut app, "How does the chat layout decide the footer height?");
+                send(&mut app, thought_chunk("Looking at the layout code first."));
+                send(
+                    &mut app,
+                    message_chunk(
+                        "The footer is a single row. `layout::compute` reserves it after the \
+                         input area, so a tall input pushes the chat up instead of",
+                    ),
+                );
+            }
+            Self::ToolCalls => {
+                push_user(&mut app, "Run the tests and fix the failing one.");
+                send(&mut app, message_chunk("Reading the test first."));
+                send(&mut app, tool_call("tc-read", "Read src/lib.rs", model::ToolKind::Read));
+                update_tool(
+                    &mut app,
+                    "tc-read",
+                    model::ToolCallStatus::Completed,
+                    Some("pub mod agent;\npub mod app;"),
+                );
+                send(
+                    &mut app,
+                    ClientEvent::SessionUpdate(model::SessionUpdate::ToolCall(
+                        model::ToolCall::new("tc-test", "cargo test")
+                            .kind(model::ToolKind::Execute)
+                            .status(model::ToolCallStatus::InProgress)
+                            .raw_input(serde_json::json!({ "command": "cargo test" })),
+                    )),
+                );
+                send(&mut app, tool_call("tc-edit", "Edit src/app/mod.rs", model::ToolKind::Edit));
+                update_tool(
+                    &mut app,
+                    "tc-edit",
+                    model::ToolCallStatus::Failed,
+                    Some("old_string not found in file"),
+                );
+                send(&mut app, message_chunk("The edit did not apply; retrying."));
+            }
+            Self::Unicode => {
+                push_user(&mut app, "日本語のテストを表示して 🚀 — café, naïve, e\u{301}");
+                send(
+                    &mut app,
+                    message_chunk(
+                        "全角文字と絵文字 👩‍💻 を含む段落です。\n\n\
+                         ```rust\nlet greeting = \"こんにちは 🌏\";\n```\n\n\
+                         | 名前 | 状態 |\n|------|------|\n| テスト | ✅ 成功 |\n| ビルド | ❌ 失敗 |\n",
+                    ),
+                );
+                send(&mut app, ClientEvent::TurnComplete);
+            }
+        }
+        app
+    }
+}
+
+fn push_user(app: &mut App, text: &str) {
+    app.messages.push(ChatMessage {
+        role: MessageRole::User,
+        blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
+        usage: None,
+        annotation: None,
+    });
+    app.status = AppStatus::Thinking;
+    app.viewport.engage_auto_scroll();
+}
+
+fn send(app: &mut App, event: ClientEvent) {
+    handle_client_event(app, event);
+}
+
+fn text_chunk(text: &str) -> model::ContentChunk {
+    model::ContentChunk::new(model::ContentBlock::Text(model::TextContent::new(text)))
+}
+
+fn thought_chunk(text: &str) -> ClientEvent {
+    ClientEvent::SessionUpdate(model::SessionUpdate::AgentThoughtChunk(text_chunk(text)))
+}
+
+fn message_chunk(text: &str) -> ClientEvent {
+    ClientEvent::SessionUpdate(model::SessionUpdate::AgentMessageChunk(text_chunk(text)))
+}
+
+fn tool_call(id: &str, title: &str, kind: model::ToolKind) -> ClientEvent {
+    ClientEvent::SessionUpdate(model::SessionUpdate::ToolCall(
+        model::ToolCall::new(id, title).kind(kind).status(model::ToolCallStatus::InProgress),
+    ))
+}
+
+fn update_tool(app: &mut App, id: &str, status: model::ToolCallStatus, output: Option<&str>) {
+    let mut fields = model::ToolCallUpdateFields::new().status(status);
+    if let Some(output) = output {
+        fields = fields.content(vec![model::ToolCallContent::from(output)]);
+    }
+    send(
+        app,
+        ClientEvent::SessionUpdate(model::SessionUpdate::ToolCallUpdate(
+            model::ToolCallUpdate::new(id, fields),
+        )),
+    );
+}
+
+/// Draw the full UI at `width` x `height` and return the buffer as text, one
+/// row per line with trailing blanks trimmed. Two frames are drawn so heights
+/// measured in the first are settled in the second.
+pub fn render_to_string(app: &mut App, width: u16, height: u16) -> String {
+    let Ok(mut terminal) = Terminal::new(TestBackend::new(width, height));
+    for _ in 0..2 {
+        let _ = terminal.draw(|frame| super::render(frame, app));
+    }
+    buffer_text(terminal.backend().buffer())
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let mut row = String::new();
+        // Cells covered by a wide glyph hold a blank placeholder; skip them.
+        let mut covered = 0;
+        for x in 0..buffer.area.width {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let symbol = buffer[(x, y)].symbol();
+            row.push_str(symbol);
+            covered = UnicodeWidthStr::width(symbol).saturating_sub(1);
+        }
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
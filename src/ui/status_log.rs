@@ -0,0 +1,66 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::app::status_log::format_elapsed;
+use crate::app::{App, SystemSeverity};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// Rows the pane takes below the chat, including its title row.
+const PANE_ROWS: u16 = 8;
+
+/// Height carved out of the chat body; at most half of it.
+pub fn compute_height(app: &App, body_height: u16) -> u16 {
+    if app.status_log.visible { PANE_ROWS.min(body_height / 2) } else { 0 }
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    if area.height == 0 {
+        return;
+    }
+    let [title_area, body_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    let title = format!("{} Status log (Ctrl+g to hide) ", theme::SEPARATOR_CHAR.repeat(2));
+    let fill = usize::from(area.width).saturating_sub(title.chars().count());
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!("{title}{}", theme::SEPARATOR_CHAR.repeat(fill)),
+            Style::default().fg(theme::DIM),
+        ))),
+        title_area,
+    );
+
+    let lines = if app.status_log.is_empty() {
+        vec![Line::from(Span::styled("No status changes yet.", Style::default().fg(theme::DIM)))]
+    } else {
+        let entries = app.status_log.entries();
+        let skip = entries.len().saturating_sub(usize::from(body_area.height));
+        entries.skip(skip).map(entry_line).collect()
+    };
+    frame.render_widget(Paragraph::new(lines), body_area);
+}
+
+fn entry_line(entry: &crate::app::status_log::StatusLogEntry) -> Line<'static> {
+    let color = match entry.severity {
+        Some(SystemSeverity::Info) => theme::DIM,
+        Some(SystemSeverity::Warning) => theme::STATUS_WARNING,
+        Some(SystemSeverity::Error) => theme::STATUS_ERROR,
+        None => Color::Reset,
+    };
+    let mut lines = entry.text.lines();
+    let mut text = lines.next().unwrap_or_default().to_owned();
+    if lines.next().is_some() {
+        text.push_str(" \u{2026}");
+    }
+    Line::from(vec![
+        Span::styled(
+            format!("{} ", format_elapsed(entry.elapsed)),
+            Style::default().fg(theme::DIM),
+        ),
+        Span::styled(text, Style::default().fg(color)),
+    ])
+}
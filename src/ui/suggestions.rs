@@ -0,0 +1,61 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::app::{App, AppStatus};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+/// Horizontal padding to match header/input/footer inset.
+const SUGGESTION_PAD: u16 = 2;
+
+/// Rows taken from the bottom of the chat body; never more than a third of it.
+pub fn compute_height(app: &App, body_height: u16) -> u16 {
+    if app.suggestions.is_empty() || !matches!(app.status, AppStatus::Ready) {
+        return 0;
+    }
+    u16::try_from(app.suggestions.len()).unwrap_or(u16::MAX).min(body_height / 3)
+}
+
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let area = Rect {
+        x: area.x + SUGGESTION_PAD,
+        width: area.width.saturating_sub(SUGGESTION_PAD * 2),
+        ..area
+    };
+    let dim = Style::default().fg(theme::DIM);
+    let lines: Vec<Line<'_>> = app
+        .suggestions
+        .iter()
+        .enumerate()
+        .map(|(idx, prompt)| {
+            Line::from(vec![
+                Span::styled(format!("Alt+{} ", idx + 1), dim.add_modifier(Modifier::BOLD)),
+                Span::styled(prompt.as_str(), dim.add_modifier(Modifier::ITALIC)),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn suggestions_only_take_rows_once_the_turn_is_over() {
+        let mut app = App::test_default();
+        assert_eq!(compute_height(&app, 30), 0);
+
+        app.suggestions = vec!["Run the tests covering these changes".to_owned(); 2];
+        assert_eq!(compute_height(&app, 30), 2);
+        assert_eq!(compute_height(&app, 3), 1);
+
+        app.status = AppStatus::Thinking;
+        assert_eq!(compute_height(&app, 30), 0);
+    }
+}
@@ -1,11 +1,17 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
+use super::code_blocks;
+use super::headings;
 use super::markdown;
-use pulldown_cmark::{Alignment, Event, Options, Parser, Tag, TagEnd};
+use super::quotes;
+use super::width::display_width;
+use crate::app::CodeView;
+use pulldown_cmark::{
+    Alignment, BlockQuoteKind, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd,
+};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum ColumnAlignment {
@@ -42,6 +48,10 @@ struct StyledChunk {
 enum MarkdownBlock {
     Text(String),
     Table(TableAst),
+    Quote { kind: Option<BlockQuoteKind>, body: String },
+    Heading { level: HeadingLevel, text: String },
+    Rule,
+    Code { language: String, code: String },
 }
 
 #[derive(Clone, Copy)]
@@ -73,6 +83,16 @@ pub fn render_markdown_with_tables(
     text: &str,
     width: u16,
     bg: Option<Color>,
+) -> Vec<Line<'static>> {
+    render_markdown_blocks(text, width, bg, CodeView::default())
+}
+
+/// Like `render_markdown_with_tables`, laying out fenced code per `code_view`.
+pub fn render_markdown_blocks(
+    text: &str,
+    width: u16,
+    bg: Option<Color>,
+    code_view: CodeView,
 ) -> Vec<Line<'static>> {
     let blocks = split_markdown_tables(text);
     let mut out = Vec::new();
@@ -91,6 +111,27 @@ pub fn render_markdown_with_tables(
                 out.extend(render_table_lines(&table, width, bg));
                 out.push(Line::default());
             }
+            MarkdownBlock::Quote { kind, body } => {
+                if !out.is_empty() {
+                    out.push(Line::default());
+                }
+                out.extend(quotes::render_quote(kind, &body, width, bg));
+                out.push(Line::default());
+            }
+            MarkdownBlock::Heading { level, text } => {
+                if !out.is_empty() {
+                    out.push(Line::default());
+                }
+                out.extend(headings::render_heading(level, &text, bg));
+            }
+            MarkdownBlock::Rule => out.push(headings::render_rule(width, bg)),
+            MarkdownBlock::Code { language, code } => {
+                if !out.is_empty() {
+                    out.push(Line::default());
+                }
+                out.extend(code_blocks::render_code_block(&language, &code, width, bg, code_view));
+                out.push(Line::default());
+            }
         }
     }
     out
@@ -99,6 +140,7 @@ pub fn render_markdown_with_tables(
 fn parser_options() -> Options {
     let mut options = Options::ENABLE_STRIKETHROUGH;
     options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_GFM);
     options
 }
 
@@ -106,30 +148,122 @@ fn split_markdown_tables(text: &str) -> Vec<MarkdownBlock> {
     let mut blocks = Vec::new();
     let mut parser = Parser::new_ext(text, parser_options()).into_offset_iter().peekable();
     let mut text_start = 0usize;
+    let mut depth = 0usize;
 
     loop {
         let Some((event, range)) = parser.next() else {
             break;
         };
-        if let Event::Start(Tag::Table(alignments)) = event {
-            if text_start < range.start {
-                blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+        match event {
+            Event::Start(Tag::Table(alignments)) => {
+                if text_start < range.start {
+                    blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+                }
+
+                let mut table_end = range.end;
+                let table = parse_table_ast(alignments, &mut parser, &mut table_end);
+                blocks.push(MarkdownBlock::Table(table));
+                text_start = table_end;
             }
+            // Only top-level quotes; ones inside lists stay with their item.
+            Event::Start(Tag::BlockQuote(kind)) if depth == 0 => {
+                if text_start < range.start {
+                    blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+                }
 
-            let mut table_end = range.end;
-            let table = parse_table_ast(alignments, &mut parser, &mut table_end);
-            blocks.push(MarkdownBlock::Table(table));
-            text_start = table_end;
+                skip_block(&mut parser);
+                let body = quotes::strip_markers(&text[range.clone()], kind.is_some());
+                blocks.push(MarkdownBlock::Quote { kind, body });
+                text_start = range.end;
+            }
+            Event::Start(Tag::Heading { level, .. }) if depth == 0 => {
+                if text_start < range.start {
+                    blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+                }
+
+                skip_block(&mut parser);
+                let content = headings::heading_text(&text[range.clone()]);
+                blocks.push(MarkdownBlock::Heading { level, text: content });
+                text_start = range.end;
+            }
+            Event::Start(Tag::CodeBlock(kind)) if depth == 0 => {
+                if text_start < range.start {
+                    blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+                }
+
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or_default().to_owned()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut code = String::new();
+                for (event, _) in parser.by_ref() {
+                    match event {
+                        Event::Text(chunk) => code.push_str(&chunk),
+                        Event::End(TagEnd::CodeBlock) => break,
+                        _ => {}
+                    }
+                }
+                blocks.push(MarkdownBlock::Code { language, code });
+                text_start = range.end;
+            }
+            Event::Rule if depth == 0 => {
+                if text_start < range.start {
+                    blocks.push(MarkdownBlock::Text(text[text_start..range.start].to_owned()));
+                }
+                blocks.push(MarkdownBlock::Rule);
+                text_start = range.end;
+            }
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            _ => {}
         }
     }
 
-    if text_start < text.len() {
+    // The newline after a closing fence is not part of the block's range.
+    if !text[text_start..].trim().is_empty() {
         blocks.push(MarkdownBlock::Text(text[text_start..].to_owned()));
     }
 
     blocks
 }
 
+/// Consume the events of a block whose start was just read; quotes and
+/// headings are rendered from their source instead.
+fn skip_block<'input, I>(parser: &mut I)
+where
+    I: Iterator<Item = (Event<'input>, std::ops::Range<usize>)>,
+{
+    let mut depth = 1usize;
+    for (event, _) in parser.by_ref() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Word-wrap `line` to `width` columns, keeping span styles.
+pub(super) fn wrap_line(line: &Line<'_>, width: usize) -> Vec<Line<'static>> {
+    let chunks = line
+        .spans
+        .iter()
+        .map(|span| StyledChunk {
+            text: span.content.clone().into_owned(),
+            style: line.style.patch(span.style),
+        })
+        .collect();
+    let cell = TableCellAst { chunks, preferred_width: 0, soft_min_width: 0 };
+    render_cell_lines(&cell, width, ColumnAlignment::Left, Style::default())
+}
+
 fn parse_table_ast<'input, I>(
     alignments: Vec<Alignment>,
     parser: &mut std::iter::Peekable<I>,
@@ -313,11 +447,11 @@ impl CellBuilder {
 }
 
 fn measure_cell_widths(text: &str) -> (usize, usize) {
-    let preferred = text.lines().map(UnicodeWidthStr::width).max().unwrap_or(0);
+    let preferred = text.lines().map(display_width).max().unwrap_or(0);
     let soft_min = text
         .lines()
         .flat_map(|line| line.split_whitespace())
-        .map(UnicodeWidthStr::width)
+        .map(display_width)
         .max()
         .unwrap_or(preferred);
     (preferred, soft_min)
@@ -562,7 +696,7 @@ fn tokenize_chunks(chunks: &[StyledChunk], base_style: Style) -> Vec<WrapToken>
                 return;
             }
             let text = std::mem::take(current);
-            let width = UnicodeWidthStr::width(text.as_str());
+            let width = display_width(&text);
             let token = StyledToken { text, style, width };
             if is_space.unwrap_or(false) {
                 tokens.push(WrapToken::Space(token));
@@ -605,8 +739,7 @@ fn wrap_long_token(
     let mut segment = String::new();
     let mut segment_width = 0usize;
 
-    for ch in token.text.chars() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
+    for (grapheme, ch_width) in super::width::graphemes(&token.text) {
         if *line_width > 0 && *line_width + segment_width + ch_width > width {
             if !segment.is_empty() {
                 push_styled_text(spans, &segment, token.style);
@@ -625,7 +758,7 @@ fn wrap_long_token(
             finish_wrapped_line(lines, spans, line_width, width, alignment, base_style);
         }
 
-        segment.push(ch);
+        segment.push_str(grapheme);
         segment_width += ch_width;
     }
 
@@ -730,7 +863,7 @@ mod tests {
         let input = "```text\n| not | a table |\n| --- | --- |\n```\n";
         let blocks = split_markdown_tables(input);
         assert_eq!(blocks.len(), 1);
-        assert!(matches!(blocks[0], MarkdownBlock::Text(_)));
+        assert!(matches!(&blocks[0], MarkdownBlock::Code { language, .. } if language == "text"));
     }
 
     #[test]
@@ -1,7 +1,9 @@
 // Copyright 2025 Simon Peter Rothgang
 // SPDX-License-Identifier: Apache-2.0
 
-use ratatui::style::Color;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::collections::HashMap;
 
 // Accent
 pub const RUST_ORANGE: Color = Color::Rgb(244, 118, 0);
@@ -10,6 +12,8 @@ pub const RUST_ORANGE: Color = Color::Rgb(244, 118, 0);
 pub const DIM: Color = Color::DarkGray;
 pub const PROMPT_CHAR: &str = "\u{276f}";
 pub const SEPARATOR_CHAR: &str = "\u{2500}";
+pub const HEADING_MARK: &str = "\u{a7}";
+pub const SUBHEADING_MARK: &str = "\u{203a}";
 
 // Role header colors
 pub const ROLE_ASSISTANT: Color = RUST_ORANGE;
@@ -27,6 +31,211 @@ pub const STATUS_WARNING: Color = Color::Yellow;
 pub const SLASH_COMMAND: Color = Color::LightMagenta;
 pub const SUBAGENT_TOKEN: Color = Color::LightBlue;
 
+/// Lowest WCAG contrast ratio accepted for theme colors on the terminal
+/// background (the AA level for large and bold text).
+pub const MIN_CONTRAST: f64 = 3.0;
+
+/// Ratio the high-contrast theme lifts every foreground to (AA body text).
+const HIGH_CONTRAST_TARGET: f64 = 4.5;
+
+/// Theme colors checked against the background at startup.
+const CHECKED_COLORS: [(&str, Color); 6] = [
+    ("dim text", DIM),
+    ("accent", RUST_ORANGE),
+    ("errors", STATUS_ERROR),
+    ("warnings", STATUS_WARNING),
+    ("slash commands", SLASH_COMMAND),
+    ("subagent tokens", SUBAGENT_TOKEN),
+];
+
+/// xterm's default RGB values for the 16 ANSI colors.
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Terminal background color assumed by the contrast check and the
+/// high-contrast theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Background(pub Color);
+
+impl Default for Background {
+    fn default() -> Self {
+        Self(Color::Black)
+    }
+}
+
+impl Background {
+    /// From `COLORFGBG` (`fg;bg`, exported by rxvt, Konsole, iTerm2 and
+    /// others), falling back to the light or dark family of the `theme`
+    /// setting.
+    #[must_use]
+    pub fn detect(theme: Option<&str>) -> Self {
+        if let Some(color) = std::env::var("COLORFGBG").ok().as_deref().and_then(parse_colorfgbg) {
+            return Self(color);
+        }
+        if theme.is_some_and(|theme| theme.starts_with("light")) {
+            Self(Color::White)
+        } else {
+            Self(Color::Black)
+        }
+    }
+
+    fn rgb(self) -> (u8, u8, u8) {
+        rgb(self.0).unwrap_or((0, 0, 0))
+    }
+}
+
+fn parse_colorfgbg(value: &str) -> Option<Color> {
+    let index = value.rsplit(';').next()?.trim().parse::<u8>().ok()?;
+    Some(Color::Indexed(index))
+}
+
+/// Theme colors that fall below [`MIN_CONTRAST`] on `background`, with their
+/// ratio.
+#[must_use]
+pub fn low_contrast_colors(background: Background) -> Vec<(&'static str, f64)> {
+    let bg = background.rgb();
+    CHECKED_COLORS
+        .iter()
+        .filter_map(|&(name, color)| {
+            let ratio = contrast_ratio(rgb(color)?, bg);
+            (ratio < MIN_CONTRAST).then_some((name, ratio))
+        })
+        .collect()
+}
+
+/// High-contrast theme: a pass over the finished frame that lifts every
+/// foreground to [`HIGH_CONTRAST_TARGET`] against its cell background and
+/// drops the dim modifier. Running after render keeps the palette constants
+/// above the single source of colors for every widget.
+pub fn apply_high_contrast(buf: &mut Buffer, background: Background) {
+    let base = background.rgb();
+    let default_fg = if relative_luminance(base) > 0.5 { (0, 0, 0) } else { (255, 255, 255) };
+    let mut lifted: HashMap<(Color, Color), Color> = HashMap::new();
+    for cell in &mut buf.content {
+        cell.modifier.remove(Modifier::DIM);
+        let key = (cell.fg, cell.bg);
+        let fg = *lifted.entry(key).or_insert_with(|| {
+            let bg = rgb(cell.bg).unwrap_or(base);
+            let fg = rgb(cell.fg).unwrap_or(default_fg);
+            lift_contrast(fg, bg).map_or(cell.fg, |(r, g, b)| Color::Rgb(r, g, b))
+        });
+        cell.fg = fg;
+    }
+}
+
+/// Accent colors muted while the terminal window is unfocused.
+const UNFOCUSED_ACCENTS: [Color; 3] = [RUST_ORANGE, SLASH_COMMAND, SUBAGENT_TOKEN];
+
+/// Unfocused look: a pass over the finished frame that mixes the accent
+/// colors halfway into the background, so a window left in the background
+/// reads as inactive at a glance.
+pub fn dim_accents(buf: &mut Buffer, background: Background) {
+    let base = background.rgb();
+    let muted = UNFOCUSED_ACCENTS.map(|accent| {
+        let (r, g, b) = rgb(accent).unwrap_or(base);
+        Color::Rgb(u8::midpoint(r, base.0), u8::midpoint(g, base.1), u8::midpoint(b, base.2))
+    });
+    let mute = |color: Color| {
+        UNFOCUSED_ACCENTS.iter().position(|&accent| accent == color).map_or(color, |i| muted[i])
+    };
+    for cell in &mut buf.content {
+        cell.fg = mute(cell.fg);
+        cell.bg = mute(cell.bg);
+    }
+}
+
+/// Mix `fg` toward black or white, whichever is further from `bg`, until it
+/// reaches [`HIGH_CONTRAST_TARGET`]. `None` when it already does.
+fn lift_contrast(fg: (u8, u8, u8), bg: (u8, u8, u8)) -> Option<(u8, u8, u8)> {
+    if contrast_ratio(fg, bg) >= HIGH_CONTRAST_TARGET {
+        return None;
+    }
+    let toward = if relative_luminance(bg) > 0.5 { (0, 0, 0) } else { (255, 255, 255) };
+    let mix = |from: u8, to: u8, step: u16| {
+        let (from, to) = (u16::from(from), u16::from(to));
+        let value = if to >= from {
+            from + (to - from) * step / 10
+        } else {
+            from - (from - to) * step / 10
+        };
+        u8::try_from(value).unwrap_or(u8::MAX)
+    };
+    (1..=10)
+        .map(|step| {
+            (mix(fg.0, toward.0, step), mix(fg.1, toward.1, step), mix(fg.2, toward.2, step))
+        })
+        .find(|&mixed| contrast_ratio(mixed, bg) >= HIGH_CONTRAST_TARGET)
+        .or(Some(toward))
+}
+
+/// RGB for a color under xterm's default palette; `None` for `Reset`.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    Some(match index {
+        0..=15 => ANSI_RGB[usize::from(index)],
+        16..=231 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let n = index - 16;
+            (level(n / 36), level(n / 6 % 6), level(n % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.039_28 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// WCAG contrast ratio, from 1.0 (identical) to 21.0 (black on white).
+fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (light, dark) = if la > lb { (la, lb) } else { (lb, la) };
+    (light + 0.05) / (dark + 0.05)
+}
+
 /// SDK tool icon + label pair. Monochrome Unicode symbols.
 /// Unknown tool names fall back to a generic Tool label.
 pub fn tool_name_label(sdk_tool_name: &str) -> (&'static str, &'static str) {
@@ -55,11 +264,51 @@ pub fn tool_name_label(sdk_tool_name: &str) -> (&'static str, &'static str) {
 
 #[cfg(test)]
 mod tests {
-    use super::tool_name_label;
+    use super::*;
 
     #[test]
     fn task_and_agent_share_subagent_label_and_icon() {
         assert_eq!(tool_name_label("Task"), ("\u{25c7}", "Subagent"));
         assert_eq!(tool_name_label("Agent"), ("\u{25c7}", "Subagent"));
     }
+
+    #[test]
+    fn unfocused_frames_mix_accents_into_the_background() {
+        let mut buf = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buf.content[0].fg = RUST_ORANGE;
+        buf.content[1].fg = STATUS_ERROR;
+        dim_accents(&mut buf, Background(Color::Black));
+        assert_eq!(buf.content[0].fg, Color::Rgb(122, 59, 0));
+        assert_eq!(buf.content[1].fg, STATUS_ERROR);
+    }
+
+    #[test]
+    fn dim_text_is_flagged_on_a_palette_whose_background_is_bright_black() {
+        let low = low_contrast_colors(Background(Color::Indexed(8)));
+        assert!(low.iter().any(|&(name, ratio)| name == "dim text" && ratio < 1.01));
+        assert!(low_contrast_colors(Background(Color::Black)).is_empty());
+    }
+
+    #[test]
+    fn colorfgbg_takes_the_last_field_as_background() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Color::Indexed(0)));
+        assert_eq!(parse_colorfgbg("12;default;8"), Some(Color::Indexed(8)));
+        assert_eq!(parse_colorfgbg("15;default"), None);
+    }
+
+    #[test]
+    fn high_contrast_lifts_dim_text_and_keeps_readable_colors() {
+        let mut buf = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buf.content[0].set_fg(Color::Rgb(60, 60, 60)).modifier.insert(Modifier::DIM);
+        buf.content[1].set_fg(Color::White);
+
+        apply_high_contrast(&mut buf, Background(Color::Black));
+
+        let Color::Rgb(r, g, b) = buf.content[0].fg else {
+            panic!("expected a lifted rgb color, got {:?}", buf.content[0].fg);
+        };
+        assert!(contrast_ratio((r, g, b), (0, 0, 0)) >= HIGH_CONTRAST_TARGET);
+        assert!(!buf.content[0].modifier.contains(Modifier::DIM));
+        assert_eq!(buf.content[1].fg, Color::White);
+    }
 }
@@ -0,0 +1,56 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::app::App;
+use crate::app::toast::{Toast, ToastKind};
+use crate::ui::theme;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+
+/// Widest a toast may get, borders included.
+const MAX_TOAST_WIDTH: u16 = 48;
+/// Rows per toast: one line of text inside a border.
+const TOAST_HEIGHT: u16 = 3;
+
+/// Draw active toasts stacked down from the top-right corner, over everything else.
+pub fn render(frame: &mut Frame, app: &App) {
+    if app.toasts.is_empty() {
+        return;
+    }
+    let area = frame.area();
+    let mut y = area.y.saturating_add(1);
+    for toast in app.toasts.iter_newest_first() {
+        if y.saturating_add(TOAST_HEIGHT) > area.bottom() {
+            break;
+        }
+        let text_width = u16::try_from(unicode_width::UnicodeWidthStr::width(toast.text.as_str()))
+            .unwrap_or(u16::MAX);
+        let width = text_width.saturating_add(4).min(MAX_TOAST_WIDTH).min(area.width);
+        if width < 5 {
+            return;
+        }
+        let x = area.right().saturating_sub(width.saturating_add(1)).max(area.x);
+        let toast_area = Rect { x, y, width, height: TOAST_HEIGHT };
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(toast_paragraph(toast), toast_area);
+        y = y.saturating_add(TOAST_HEIGHT);
+    }
+}
+
+fn toast_paragraph(toast: &Toast) -> Paragraph<'_> {
+    let color = match toast.kind {
+        ToastKind::Info => Color::Reset,
+        ToastKind::Success => Color::Green,
+        ToastKind::Warning => theme::STATUS_WARNING,
+        ToastKind::Error => theme::STATUS_ERROR,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color));
+    Paragraph::new(Line::from(Span::styled(toast.text.as_str(), Style::default().fg(color))))
+        .block(block)
+}
@@ -7,6 +7,7 @@
 use crate::agent::model;
 use crate::app::ToolCallInfo;
 use crate::ui::highlight;
+use crate::ui::hscroll;
 use crate::ui::theme;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -14,7 +15,7 @@ use ratatui::text::{Line, Span};
 use super::errors::failed_execute_first_line;
 use super::interactions::{render_permission_lines, render_question_lines};
 use super::{
-    markdown_inline_spans, spans_width, status_icon, tool_output_badge_spans,
+    mark_watch_hits, markdown_inline_spans, spans_width, status_icon, tool_output_badge_spans,
     truncate_spans_to_width,
 };
 
@@ -42,6 +43,16 @@ pub(super) fn render_execute_content(tc: &ToolCallInfo) -> Vec<Line<'static>> {
         lines.push(Line::from(spans));
     }
 
+    // `/env` variables, when expanded
+    if !tc.collapsed && !tc.terminal_env.is_empty() {
+        let vars: Vec<String> =
+            tc.terminal_env.iter().map(|(name, value)| format!("{name}={value}")).collect();
+        lines.push(Line::from(Span::styled(
+            format!("env {}", vars.join(" ")),
+            Style::default().fg(theme::DIM),
+        )));
+    }
+
     // Output lines (capped, no border prefix)
     let mut body_lines: Vec<Line<'static>> = Vec::new();
 
@@ -55,7 +66,8 @@ pub(super) fn render_execute_content(tc: &ToolCallInfo) -> Vec<Line<'static>> {
                 Style::default().fg(theme::STATUS_ERROR),
             )));
         } else {
-            let raw_lines = highlight::render_terminal_output(&stripped_output);
+            let mut raw_lines = highlight::render_terminal_output(&stripped_output);
+            mark_watch_hits(tc, &mut raw_lines);
 
             let total = raw_lines.len();
             if total > TERMINAL_MAX_LINES {
@@ -95,6 +107,9 @@ pub(super) fn render_execute_with_borders(
     width: u16,
     spinner_frame: usize,
 ) -> Vec<Line<'static>> {
+    if crate::app::screen_reader::is_enabled() {
+        return render_execute_linear(tc, content, spinner_frame);
+    }
     let border = Style::default().fg(theme::DIM);
     let inner_w = (width as usize).saturating_sub(2);
     let mut out = Vec::with_capacity(content.len() + 2);
@@ -128,10 +143,14 @@ pub(super) fn render_execute_with_borders(
     top.push(Span::styled(format!("{top_fill}\u{256E}"), border));
     out.push(Line::from(top));
 
-    // Content lines with left border prefix
+    // Content lines with left border prefix, scrolled sideways when requested
+    let content_w = line_budget.saturating_sub(4).max(1);
     for line in content {
         let mut spans = vec![Span::styled("  \u{2502} ", border)];
-        spans.extend(line.spans.iter().cloned());
+        match tc.h_scroll {
+            Some(offset) => spans.extend(hscroll::clip_line(line, offset, content_w).spans),
+            None => spans.extend(line.spans.iter().cloned()),
+        }
         out.push(Line::from(spans));
     }
 
@@ -141,3 +160,33 @@ pub(super) fn render_execute_with_borders(
 
     out
 }
+
+/// Screen reader layout: the same rows as [`render_execute_with_borders`] but
+/// without box drawing, so the title and output read as plain lines.
+fn render_execute_linear(
+    tc: &ToolCallInfo,
+    content: &[Line<'static>],
+    spinner_frame: usize,
+) -> Vec<Line<'static>> {
+    let (status_label, status_color) = status_icon(tc.status, spinner_frame);
+    let (_tool_icon, tool_label) = theme::tool_name_label(&tc.sdk_tool_name);
+    let mut top = vec![
+        Span::styled(format!("  {status_label} "), Style::default().fg(status_color)),
+        Span::styled(
+            format!("{tool_label} "),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    top.extend(markdown_inline_spans(&tc.title));
+    top.extend(tool_output_badge_spans(tc));
+
+    let mut out = Vec::with_capacity(content.len() + 2);
+    out.push(Line::from(top));
+    for line in content {
+        let mut spans = vec![Span::raw("    ")];
+        spans.extend(line.spans.iter().cloned());
+        out.push(Line::from(spans));
+    }
+    out.push(Line::default());
+    out
+}
@@ -8,20 +8,22 @@
 //! - [`execute`] -- Execute/Bash two-layer bordered rendering
 //! - [`interactions`] -- inline permissions, questions, and plan approvals
 //! - [`errors`] -- error rendering and tool-use error extraction
+//! - [`web`] -- `WebSearch` result lists and `WebFetch` page summaries
 
 mod errors;
 mod execute;
 mod interactions;
 mod standard;
+mod web;
 
 use crate::agent::model;
 use crate::app::ToolCallInfo;
 use crate::ui::markdown;
 use crate::ui::theme;
+use crate::ui::width;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{Paragraph, Wrap};
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 // Re-export submodule items used by tests.
 #[cfg(test)]
@@ -40,6 +42,13 @@ const SPINNER_STRS: &[&str] = &[
 ];
 
 pub fn status_icon(status: model::ToolCallStatus, spinner_frame: usize) -> (&'static str, Color) {
+    if crate::app::screen_reader::is_enabled() {
+        let color = match status {
+            model::ToolCallStatus::Failed => theme::STATUS_ERROR,
+            _ => theme::RUST_ORANGE,
+        };
+        return (crate::app::screen_reader::status_label(status), color);
+    }
     match status {
         model::ToolCallStatus::Pending => ("\u{25CB}", theme::RUST_ORANGE),
         model::ToolCallStatus::InProgress => {
@@ -121,7 +130,8 @@ pub fn render_tool_call_cached(
     } else {
         crate::perf::mark("tc::cache_miss_body");
         let _t = crate::perf::start("tc::render_body");
-        let body = standard::render_tool_call_body(tc);
+        let mut body = standard::render_tool_call_body(tc);
+        standard::scroll_body_lines(tc, &mut body, width);
         tc.cache.store(body);
         if let Some(stored) = tc.cache.get() {
             out.extend_from_slice(stored);
@@ -142,6 +152,10 @@ pub fn measure_tool_call_height_cached(
         return (tc.last_measured_height, 0);
     }
     crate::perf::mark("tc_measure_recompute_count");
+    // Sideways-scrolled rows are clipped to the width they were rendered at.
+    if tc.h_scroll.is_some() && !tc.is_execute_tool() && tc.last_measured_width != width {
+        tc.cache.invalidate();
+    }
 
     let is_execute = tc.is_execute_tool();
     if is_execute {
@@ -199,7 +213,8 @@ pub fn measure_tool_call_height_cached(
         return (total, tc.cache.get().map_or(1, |b| b.len() + 1));
     }
 
-    let body = standard::render_tool_call_body(tc);
+    let mut body = standard::render_tool_call_body(tc);
+    standard::scroll_body_lines(tc, &mut body, width);
     let body_h =
         Paragraph::new(Text::from(body.clone())).wrap(Wrap { trim: false }).line_count(width);
     tc.cache.store(body);
@@ -220,7 +235,7 @@ fn markdown_inline_spans(input: &str) -> Vec<Span<'static>> {
 }
 
 fn spans_width(spans: &[Span<'static>]) -> usize {
-    spans.iter().map(|s| UnicodeWidthStr::width(s.content.as_ref())).sum()
+    spans.iter().map(|s| width::display_width(s.content.as_ref())).sum()
 }
 
 fn truncate_spans_to_width(spans: Vec<Span<'static>>, max_width: usize) -> Vec<Span<'static>> {
@@ -239,23 +254,31 @@ fn truncate_spans_to_width(spans: Vec<Span<'static>>, max_width: usize) -> Vec<S
         if used >= keep_width {
             break;
         }
-        let mut chunk = String::new();
-        for ch in span.content.chars() {
-            let w = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + w > keep_width {
-                break;
-            }
-            chunk.push(ch);
-            used += w;
-        }
+        let (chunk, chunk_width) = width::prefix_within(&span.content, keep_width - used);
+        used += chunk_width;
         if !chunk.is_empty() {
-            out.push(Span::styled(chunk, span.style));
+            out.push(Span::styled(chunk.to_owned(), span.style));
+        }
+        if chunk.len() < span.content.len() {
+            break;
         }
     }
     out.push(Span::styled("\u{2026}", Style::default().fg(theme::DIM)));
     out
 }
 
+/// Highlight the output lines that matched a `watchPatterns` entry.
+fn mark_watch_hits(tc: &ToolCallInfo, lines: &mut [Line<'static>]) {
+    let style = Style::default().fg(theme::STATUS_WARNING).add_modifier(Modifier::BOLD);
+    for &index in &tc.watch_hits {
+        if let Some(line) = lines.get_mut(index) {
+            for span in &mut line.spans {
+                span.style = span.style.patch(style);
+            }
+        }
+    }
+}
+
 fn tool_output_badge_spans(tc: &ToolCallInfo) -> Vec<Span<'static>> {
     let mut badges = Vec::new();
 
@@ -273,6 +296,13 @@ fn tool_output_badge_spans(tc: &ToolCallInfo) -> Vec<Span<'static>> {
         ));
     }
 
+    if let Some(kind) = tc.auto_retry {
+        badges.push(Span::styled(
+            format!("  [retried: {}]", kind.label()),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     if tc.verification_nudge_needed() {
         badges.push(Span::styled(
             "  [verification needed]",
@@ -304,12 +334,14 @@ mod tests {
             sdk_tool_name: sdk_tool_name.to_owned(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status,
             content: Vec::new(),
             collapsed: false,
             hidden: false,
             terminal_id: None,
             terminal_command: None,
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -323,6 +355,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         }
     }
 
@@ -419,12 +454,14 @@ mod tests {
             sdk_tool_name: "Bash".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::Pending,
             content: Vec::new(),
             collapsed: false,
             hidden: false,
             terminal_id: None,
             terminal_command: None,
+            terminal_env: Vec::new(),
             terminal_output: None,
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -438,6 +475,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         };
 
         let rendered = execute::render_execute_with_borders(&tc, &[], 80, 0);
@@ -631,12 +671,14 @@ mod tests {
             sdk_tool_name: "Bash".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::Completed,
             content: Vec::new(),
             collapsed: true,
             hidden: false,
             terminal_id: Some("term-1".into()),
             terminal_command: Some("echo done".into()),
+            terminal_env: Vec::new(),
             terminal_output: Some("<tool_use_error>bad</tool_use_error>\ndone".into()),
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -650,6 +692,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         };
         assert_eq!(content_summary(&tc), "done");
     }
@@ -662,12 +707,14 @@ mod tests {
             sdk_tool_name: "Bash".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::Failed,
             content: Vec::new(),
             collapsed: true,
             hidden: false,
             terminal_id: Some("term-1".into()),
             terminal_command: Some("echo done".into()),
+            terminal_env: Vec::new(),
             terminal_output: Some("<tool_use_error>bad</tool_use_error>\ndone".into()),
             terminal_output_len: 0,
             terminal_bytes_seen: 0,
@@ -681,6 +728,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         };
         assert_eq!(content_summary(&tc), "bad");
     }
@@ -693,12 +743,14 @@ mod tests {
             sdk_tool_name: "Bash".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::Failed,
             content: Vec::new(),
             collapsed: true,
             hidden: false,
             terminal_id: Some("term-2".into()),
             terminal_command: Some("cd path with spaces".into()),
+            terminal_env: Vec::new(),
             terminal_output: Some(
                 "Exit code 1\n/usr/bin/bash: line 1: cd: too many arguments\nmore detail".into(),
             ),
@@ -714,6 +766,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         };
         assert_eq!(content_summary(&tc), "Exit code 1");
     }
@@ -726,12 +781,14 @@ mod tests {
             sdk_tool_name: "Bash".into(),
             raw_input: None,
             output_metadata: None,
+            web_output: None,
             status: model::ToolCallStatus::Failed,
             content: Vec::new(),
             collapsed: false,
             hidden: false,
             terminal_id: Some("term-3".into()),
             terminal_command: Some("cd path with spaces".into()),
+            terminal_env: Vec::new(),
             terminal_output: Some(
                 "Exit code 1\n/usr/bin/bash: line 1: cd: too many arguments\nmore detail".into(),
             ),
@@ -747,6 +804,9 @@ mod tests {
             cache: BlockCache::default(),
             pending_permission: None,
             pending_question: None,
+            h_scroll: None,
+            auto_retry: None,
+            watch_hits: Vec::new(),
         };
 
         let lines = execute::render_execute_content(&tc);
@@ -780,4 +840,70 @@ mod tests {
         assert_eq!(rendered[WRITE_DIFF_HEAD_LINES + 3], "line 83");
         assert_eq!(rendered.last().map(String::as_str), Some("line 119"));
     }
+
+    fn web_search_output() -> crate::app::web_tools::WebToolOutput {
+        use crate::app::web_tools::{WebSearchHit, WebSearchOutput, WebToolOutput};
+        WebToolOutput::Search(WebSearchOutput {
+            query: Some("ratatui".to_owned()),
+            results: vec![
+                WebSearchHit {
+                    title: "Ratatui".to_owned(),
+                    url: "https://ratatui.rs".to_owned(),
+                    snippet: Some("Rust TUI library".to_owned()),
+                },
+                WebSearchHit {
+                    title: "Docs".to_owned(),
+                    url: "https://docs.rs/ratatui".to_owned(),
+                    snippet: None,
+                },
+            ],
+            summary: String::new(),
+        })
+    }
+
+    #[test]
+    fn web_search_body_renders_result_list() {
+        let mut tc = test_tool_call("tc-web-search", "WebSearch", model::ToolCallStatus::Completed);
+        tc.web_output = Some(web_search_output());
+
+        let body = standard::render_tool_call_body(&tc);
+        let rendered: Vec<String> = body
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect();
+
+        assert!(rendered.iter().any(|line| line.contains("1. Ratatui")));
+        assert!(rendered.iter().any(|line| line.contains("https://ratatui.rs")));
+        assert!(rendered.iter().any(|line| line.contains("Rust TUI library")));
+        assert!(rendered.iter().any(|line| line.contains("2. Docs")));
+    }
+
+    #[test]
+    fn web_search_collapsed_summary_counts_results() {
+        let mut tc = test_tool_call("tc-web-search", "WebSearch", model::ToolCallStatus::Completed);
+        tc.web_output = Some(web_search_output());
+        tc.collapsed = true;
+
+        assert_eq!(content_summary(&tc), "2 results for \"ratatui\"");
+    }
+
+    #[test]
+    fn web_fetch_body_renders_source_url_and_summary() {
+        use crate::app::web_tools::{WebFetchOutput, WebToolOutput};
+        let mut tc = test_tool_call("tc-web-fetch", "WebFetch", model::ToolCallStatus::Completed);
+        tc.web_output = Some(WebToolOutput::Fetch(WebFetchOutput {
+            url: Some("https://example.com".to_owned()),
+            summary: "Example page body".to_owned(),
+        }));
+
+        let body = standard::render_tool_call_body(&tc);
+        let rendered: Vec<String> = body
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect();
+
+        assert!(rendered.iter().any(|line| line.contains("Source: https://example.com")));
+        assert!(rendered.iter().any(|line| line.contains("Example page body")));
+        assert_eq!(content_summary(&tc), "https://example.com");
+    }
 }
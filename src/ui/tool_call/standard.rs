@@ -6,8 +6,10 @@
 
 use crate::agent::model;
 use crate::app::ToolCallInfo;
+use crate::app::web_tools::WebToolOutput;
 use crate::ui::diff::{is_markdown_file, lang_from_title, render_diff, strip_outer_code_fence};
 use crate::ui::highlight;
+use crate::ui::hscroll;
 use crate::ui::markdown;
 use crate::ui::theme;
 use ratatui::style::{Modifier, Style};
@@ -18,10 +20,13 @@ use super::errors::{
     looks_like_internal_error, render_internal_failure_content, render_tool_use_error_content,
 };
 use super::interactions::{render_permission_lines, render_question_lines};
-use super::{markdown_inline_spans, status_icon, tool_output_badge_spans};
+use super::web::{render_web_output, web_output_summary};
+use super::{mark_watch_hits, markdown_inline_spans, status_icon, tool_output_badge_spans};
 
 pub(super) const WRITE_DIFF_MAX_LINES: usize = 50;
 pub(super) const WRITE_DIFF_HEAD_LINES: usize = 10;
+/// Width of the `  │  ` / `  └─ ` prefix on body rows.
+const BODY_PREFIX_WIDTH: usize = 5;
 
 /// Render just the title line for a non-Execute tool call (the line containing the spinner icon).
 /// Used for in-progress tool calls where only the spinner changes each frame.
@@ -67,9 +72,29 @@ pub(super) fn render_tool_call(
     let title = render_tool_call_title(tc, width, spinner_frame);
     let mut lines = vec![title];
     render_standard_body(tc, &mut lines);
+    scroll_body_lines(tc, &mut lines[1..], width);
     lines
 }
 
+/// Show a sideways-scrolled window of the body rows when the tool call is
+/// scrolled (`ToolCallInfo::h_scroll`); the pipe prefix of each row stays put.
+pub(super) fn scroll_body_lines(tc: &ToolCallInfo, body: &mut [Line<'static>], width: u16) {
+    let Some(offset) = tc.h_scroll else {
+        return;
+    };
+    let max_width = usize::from(width).saturating_sub(BODY_PREFIX_WIDTH).max(1);
+    for line in body {
+        if line.spans.is_empty() {
+            continue;
+        }
+        let prefix = line.spans.remove(0);
+        let content = Line::from(std::mem::take(&mut line.spans));
+        line.spans = std::iter::once(prefix)
+            .chain(hscroll::clip_line(&content, offset, max_width).spans)
+            .collect();
+    }
+}
+
 /// Render the body (everything after the title line) of a standard (non-Execute) tool call.
 fn render_standard_body(tc: &ToolCallInfo, lines: &mut Vec<Line<'static>>) {
     let pipe_style = Style::default().fg(theme::DIM);
@@ -79,7 +104,11 @@ fn render_standard_body(tc: &ToolCallInfo, lines: &mut Vec<Line<'static>>) {
     // Diffs (Edit tool) are always shown -- user needs to see changes
     let has_diff = tc.content.iter().any(|c| matches!(c, model::ToolCallContent::Diff(_)));
 
-    if tc.content.is_empty() && !has_permission && !has_question {
+    if tc.content.is_empty()
+        && structured_web_output(tc).is_none()
+        && !has_permission
+        && !has_question
+    {
         return;
     }
 
@@ -153,6 +182,10 @@ pub(super) fn content_summary(tc: &ToolCallInfo) -> String {
         };
     }
 
+    if let Some(output) = structured_web_output(tc) {
+        return web_output_summary(output);
+    }
+
     for content in &tc.content {
         match content {
             model::ToolCallContent::Diff(diff) => {
@@ -219,7 +252,9 @@ fn render_tool_content(tc: &ToolCallInfo) -> Vec<Line<'static>> {
                     Style::default().fg(theme::STATUS_ERROR),
                 )));
             } else {
-                lines.extend(highlight::render_terminal_output(&stripped_output));
+                let mut output_lines = highlight::render_terminal_output(&stripped_output);
+                mark_watch_hits(tc, &mut output_lines);
+                lines.extend(output_lines);
             }
         } else if matches!(tc.status, model::ToolCallStatus::InProgress) {
             lines.push(Line::from(Span::styled("running...", Style::default().fg(theme::DIM))));
@@ -228,6 +263,10 @@ fn render_tool_content(tc: &ToolCallInfo) -> Vec<Line<'static>> {
         return lines;
     }
 
+    if let Some(output) = structured_web_output(tc) {
+        return render_web_output(output);
+    }
+
     for content in &tc.content {
         match content {
             model::ToolCallContent::Diff(diff) => {
@@ -254,6 +293,14 @@ fn render_tool_content(tc: &ToolCallInfo) -> Vec<Line<'static>> {
     lines
 }
 
+/// Parsed web tool output, unless the call failed (errors keep the standard rendering).
+fn structured_web_output(tc: &ToolCallInfo) -> Option<&WebToolOutput> {
+    if matches!(tc.status, model::ToolCallStatus::Failed) {
+        return None;
+    }
+    tc.web_output.as_ref()
+}
+
 fn render_text_content(tc: &ToolCallInfo, text: &str, lines: &mut Vec<Line<'static>>) {
     let stripped = strip_outer_code_fence(text);
     if matches!(tc.status, model::ToolCallStatus::Failed)
@@ -0,0 +1,104 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured rendering for `WebSearch` result lists and `WebFetch` page summaries.
+
+use crate::app::web_tools::{WebFetchOutput, WebSearchOutput, WebToolOutput};
+use crate::ui::markdown;
+use crate::ui::theme;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+const SNIPPET_MAX_CHARS: usize = 160;
+
+/// Render the expanded body for a parsed web tool result.
+pub(super) fn render_web_output(output: &WebToolOutput) -> Vec<Line<'static>> {
+    match output {
+        WebToolOutput::Search(search) => render_search(search),
+        WebToolOutput::Fetch(fetch) => render_fetch(fetch),
+    }
+}
+
+/// One-line summary for a collapsed web tool call.
+pub(super) fn web_output_summary(output: &WebToolOutput) -> String {
+    match output {
+        WebToolOutput::Search(search) => {
+            let count = search.results.len();
+            let noun = if count == 1 { "result" } else { "results" };
+            match search.query.as_deref() {
+                Some(query) => format!("{count} {noun} for \"{query}\""),
+                None => format!("{count} {noun}"),
+            }
+        }
+        WebToolOutput::Fetch(fetch) => fetch.url.clone().unwrap_or_else(|| {
+            let first = fetch.summary.lines().find(|line| !line.trim().is_empty()).unwrap_or("");
+            truncate_chars(first, 60)
+        }),
+    }
+}
+
+fn render_search(search: &WebSearchOutput) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let index_width = search.results.len().to_string().len();
+    for (i, hit) in search.results.iter().enumerate() {
+        let number = format!("{:>index_width$}. ", i + 1);
+        let indent = " ".repeat(number.len());
+        lines.push(Line::from(vec![
+            Span::styled(number, Style::default().fg(theme::DIM)),
+            Span::styled(
+                hit.title.clone(),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::raw(indent.clone()),
+            Span::styled(
+                hit.url.clone(),
+                Style::default().fg(theme::DIM).add_modifier(Modifier::UNDERLINED),
+            ),
+        ]));
+        if let Some(snippet) = hit.snippet.as_deref() {
+            lines.push(Line::from(vec![
+                Span::raw(indent),
+                Span::styled(truncate_chars(snippet, SNIPPET_MAX_CHARS), Style::default()),
+            ]));
+        }
+    }
+    if search.results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No results",
+            Style::default().fg(theme::DIM).add_modifier(Modifier::ITALIC),
+        )));
+    }
+    if !search.summary.is_empty() {
+        lines.push(Line::default());
+        lines.extend(markdown::render_markdown_safe(&search.summary, None));
+    }
+    lines
+}
+
+fn render_fetch(fetch: &WebFetchOutput) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(url) = fetch.url.as_deref() {
+        lines.push(Line::from(vec![
+            Span::styled("Source: ", Style::default().fg(theme::DIM).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                url.to_owned(),
+                Style::default().fg(theme::DIM).add_modifier(Modifier::UNDERLINED),
+            ),
+        ]));
+        lines.push(Line::default());
+    }
+    lines.extend(markdown::render_markdown_safe(&fetch.summary, None));
+    lines
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() > max {
+        let truncated: String = text.chars().take(max.saturating_sub(3)).collect();
+        format!("{truncated}...")
+    } else {
+        text.to_owned()
+    }
+}
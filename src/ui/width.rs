@@ -0,0 +1,154 @@
+// Copyright 2025 Simon Peter Rothgang
+// SPDX-License-Identifier: Apache-2.0
+
+//! Display width measured the way ratatui lays text into cells: per extended
+//! grapheme cluster, so ZWJ emoji, flags, combining marks and variation
+//! selectors count as one glyph, and control characters take no space. Chat
+//! capture, selection, input and footer all measure through here so cursor
+//! maps, selections and truncation line up with what is on screen.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Cells one grapheme cluster occupies.
+#[must_use]
+pub fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.contains(char::is_control) { 0 } else { UnicodeWidthStr::width(grapheme) }
+}
+
+/// Grapheme clusters of `text` with their cell widths.
+pub fn graphemes(text: &str) -> impl Iterator<Item = (&str, usize)> {
+    text.graphemes(true).map(|grapheme| (grapheme, grapheme_width(grapheme)))
+}
+
+/// Cells `text` occupies on one row.
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    graphemes(text).map(|(_, width)| width).sum()
+}
+
+/// Longest prefix of `text` that fits in `max_width` cells, with its width.
+/// Never splits a grapheme cluster.
+#[must_use]
+pub fn prefix_within(text: &str, max_width: usize) -> (&str, usize) {
+    let mut used = 0usize;
+    let mut end = 0usize;
+    for (grapheme, width) in graphemes(text) {
+        if used + width > max_width {
+            break;
+        }
+        used += width;
+        end += grapheme.len();
+    }
+    (&text[..end], used)
+}
+
+/// `text` cut to at most `max_width` cells, ending in an ellipsis when it
+/// had to be cut.
+#[must_use]
+pub fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_owned();
+    }
+    let (prefix, _) = prefix_within(text, max_width.saturating_sub(1));
+    format!("{}\u{2026}", prefix.trim_end())
+}
+
+/// Text of the graphemes that start within columns `start..end` of a row.
+#[must_use]
+pub fn slice_columns(text: &str, start: usize, end: usize) -> String {
+    let mut out = String::new();
+    if start >= end {
+        return out;
+    }
+    let mut col = 0usize;
+    for (grapheme, width) in graphemes(text) {
+        if col >= end {
+            break;
+        }
+        if col >= start {
+            out.push_str(grapheme);
+        }
+        col += width;
+    }
+    out
+}
+
+/// Row `y` of `buf` as text, one column per cell. Cells hidden behind a wide
+/// glyph are skipped rather than read back as the blanks ratatui leaves in
+/// them. Trailing whitespace is trimmed.
+#[must_use]
+pub fn buffer_row_text(buf: &Buffer, area: Rect, y: u16) -> String {
+    let mut line = String::new();
+    let mut x = 0u16;
+    while x < area.width {
+        let Some(cell) = buf.cell((area.x + x, area.y + y)) else {
+            break;
+        };
+        let symbol = cell.symbol();
+        line.push_str(symbol);
+        let width = u16::try_from(grapheme_width(symbol)).unwrap_or(1);
+        x = x.saturating_add(width.max(1));
+    }
+    line.truncate(line.trim_end().len());
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::text::Line;
+
+    const FAMILY: &str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+
+    #[test]
+    fn clusters_count_as_one_glyph() {
+        assert_eq!(display_width(FAMILY), 2);
+        assert_eq!(display_width("e\u{0301}x"), 2);
+        assert_eq!(display_width("\u{1F1FA}\u{1F1F8}"), 2);
+        assert_eq!(display_width("\u{4F60}\u{597D}"), 4);
+        assert_eq!(display_width("a\u{200B}b"), 2);
+    }
+
+    #[test]
+    fn prefix_never_splits_a_cluster() {
+        let text = format!("ab{FAMILY}c");
+        assert_eq!(prefix_within(&text, 3), ("ab", 2));
+        assert_eq!(prefix_within(&text, 4), (format!("ab{FAMILY}").as_str(), 4));
+        assert_eq!(prefix_within("\u{4F60}\u{597D}", 3), ("\u{4F60}", 2));
+    }
+
+    #[test]
+    fn truncation_counts_cells_and_adds_an_ellipsis() {
+        assert_eq!(truncate_with_ellipsis("short", 5), "short");
+        assert_eq!(truncate_with_ellipsis("fix the build", 8), "fix the\u{2026}");
+        // Four wide glyphs take eight cells; five fit only two and the ellipsis.
+        let wide = "\u{4F60}\u{597D}\u{4E16}\u{754C}";
+        assert_eq!(truncate_with_ellipsis(wide, 5), "\u{4F60}\u{597D}\u{2026}");
+        assert_eq!(display_width(&truncate_with_ellipsis(&FAMILY.repeat(4), 6)), 5);
+    }
+
+    #[test]
+    fn slice_columns_maps_screen_columns_to_text() {
+        let text = "a\u{4F60}b";
+        // a = col 0, ni = cols 1-2, b = col 3
+        assert_eq!(slice_columns(text, 1, 3), "\u{4F60}");
+        assert_eq!(slice_columns(text, 3, 4), "b");
+        assert_eq!(slice_columns(&format!("{FAMILY}x"), 0, 2), FAMILY);
+    }
+
+    #[test]
+    fn buffer_rows_read_back_without_wide_glyph_padding() {
+        let area = Rect::new(0, 0, 10, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_line(0, 0, &Line::from(format!("\u{4F60}{FAMILY}e\u{0301}!")), 10);
+
+        let row = buffer_row_text(&buf, area, 0);
+
+        assert_eq!(row, format!("\u{4F60}{FAMILY}e\u{0301}!"));
+        assert_eq!(slice_columns(&row, 4, 6), "e\u{0301}!");
+    }
+}
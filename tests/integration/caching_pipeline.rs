@@ -14,8 +14,9 @@
 use claude_code_rust::agent::events::ClientEvent;
 use claude_code_rust::agent::model;
 use claude_code_rust::app::{
-    App, AppStatus, BlockCache, ChatMessage, DEFAULT_CACHE_SPLIT_HARD_LIMIT_BYTES,
+    App, AppStatus, BlockCache, ChatMessage, CodeView, DEFAULT_CACHE_SPLIT_HARD_LIMIT_BYTES,
     DEFAULT_CACHE_SPLIT_SOFT_LIMIT_BYTES, MessageBlock, MessageRole, TextBlock, TextBlockSpacing,
+    ThinkingDisplay,
 };
 use claude_code_rust::ui::{SpinnerState, measure_message_height_cached};
 use ratatui::text::{Line, Span};
@@ -35,6 +36,8 @@ fn inactive_spinner() -> SpinnerState {
         is_thinking_mid_turn: false,
         is_subagent_thinking: false,
         is_compacting: false,
+        show_usage: false,
+        thinking_display: ThinkingDisplay::Collapsed,
     }
 }
 
@@ -56,6 +59,7 @@ fn user_text_message(text: &str) -> ChatMessage {
         role: MessageRole::User,
         blocks: vec![MessageBlock::Text(TextBlock::from_complete(text))],
         usage: None,
+        annotation: None,
     }
 }
 
@@ -73,8 +77,11 @@ fn assistant_message_with_cache(text: &str) -> ChatMessage {
             cache,
             markdown: claude_code_rust::app::IncrementalMarkdown::from_complete(text),
             trailing_spacing: TextBlockSpacing::None,
+            revision: None,
+            code_view: CodeView::default(),
         })],
         usage: None,
+        annotation: None,
     }
 }
 
@@ -3,5 +3,6 @@ mod helpers;
 mod caching_pipeline;
 mod internal_failures;
 mod permissions;
+mod render_snapshots;
 mod state_transitions;
 mod tool_lifecycle;
@@ -0,0 +1,34 @@
+// =====
+// TESTS: 2
+// =====
+//
+// Full-UI render snapshots. Every fixture is drawn into a `TestBackend` at a
+// regular and a narrow size and compared with the stored snapshot, so layout
+// regressions such as a truncated footer show up as a snapshot diff.
+// Review intended changes with `cargo insta review`.
+
+use claude_code_rust::ui::render_fixture::{Fixture, render_to_string};
+
+const SIZES: [(u16, u16); 2] = [(100, 30), (40, 24)];
+
+#[test]
+fn fixtures_match_snapshots() {
+    for fixture in Fixture::ALL {
+        for (width, height) in SIZES {
+            let mut app = fixture.build();
+            let text = render_to_string(&mut app, width, height);
+            insta::assert_snapshot!(format!("{}_{width}x{height}", fixture.name()), text);
+        }
+    }
+}
+
+#[test]
+fn tiny_terminals_render_without_panicking() {
+    for fixture in Fixture::ALL {
+        for (width, height) in [(1, 1), (10, 3), (20, 6)] {
+            let mut app = fixture.build();
+            let text = render_to_string(&mut app, width, height);
+            assert_eq!(text.lines().count(), usize::from(height), "{}", fixture.name());
+        }
+    }
+}